@@ -0,0 +1,35 @@
+//! 基准测试/输出转译的吞吐量
+//! * 🎯高频输出（如每秒数千行的ONA智能体循环）下，转译不成为瓶颈
+//! * 📄语料：[`output_samples`]中捕获的CIN输出
+//! * 🚩运行：`cargo bench --bench output_translate`
+
+use babel_nar::{cin_implements::ona, tests::output_samples};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+/// 语料中的非空行
+fn lines(corpus: &str) -> Vec<&str> {
+    corpus
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// ONA输出转译
+fn bench_ona(c: &mut Criterion) {
+    let lines = lines(output_samples::ONA);
+    let mut group = c.benchmark_group("output_translate");
+    group.throughput(Throughput::Elements(lines.len() as u64));
+    group.bench_function("ONA", |b| {
+        b.iter(|| {
+            for line in &lines {
+                let _ = black_box(ona::output_translate(black_box(line.to_string())));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_ona);
+criterion_main!(benches);
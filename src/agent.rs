@@ -0,0 +1,263 @@
+//! 闭环智能体
+//! * 🎯在NAVM运行时之上，以「操作⇒回调⇒反馈」的形式构建闭环智能体
+//!   * 📄左右操作的条件反射训练：CIN执行`^left`⇒环境判断好坏⇒输入`<{SELF} --> [good]>. :|:`
+//! * 🚩按操作符注册回调：不再需要对输出做字符串匹配（如`msg.contains("EXE")`）
+//! * 📌适用于任何[`VmRuntimeBoost`]：命令行运行时、Websocket运行时、模拟运行时……
+//!
+//! ## 用例
+//!
+//! ```
+//! use babel_nar::{agent::Agent, runtimes::FakeVm};
+//! use nar_dev_utils::manipulate;
+//! use narsese::{lexical_nse_task as nse_task, lexical_nse_term as nse_term};
+//! use navm::{cmd::Cmd, output::{Operation, Output}, vm::VmLauncher};
+//!
+//! // 模拟CIN：步进时执行`^left`
+//! let runtime = manipulate!(
+//!     FakeVm::new()
+//!     => .respond(Cmd::CYC(1), [Output::EXE {
+//!         content_raw: "EXE: ^left({SELF})".into(),
+//!         operation: Operation::new("left", [nse_term!({SELF})].into_iter()),
+//!     }])
+//! )
+//! .launch()
+//! .unwrap();
+//!
+//! // 执行`^left`⇒给予奖励
+//! let mut agent = Agent::new(runtime);
+//! agent.on_operation("left", |_params| {
+//!     vec![Cmd::NSE(nse_task!(<{SELF} --> [good]>. :|:))]
+//! });
+//! agent.input_cmd(Cmd::CYC(1)).unwrap();
+//! assert_eq!(agent.step().unwrap(), 1);
+//! ```
+
+use crate::runtimes::VmRuntimeBoost;
+use anyhow::Result;
+use narsese::lexical::Term;
+use navm::{cmd::Cmd, output::Output};
+use std::collections::HashMap;
+
+/// 操作回调
+/// * 🚩传入操作的参数，返回要反馈给CIN的指令
+///   * 📄`<{SELF} --> [good]>. :|:`
+/// * 📌参数原样来自CIN：通常包含`{SELF}`
+pub type OperationHandler = Box<dyn FnMut(&[Term]) -> Vec<Cmd> + Send>;
+
+/// 输出接收者
+/// * 🎯接收「未被操作回调处理」的输出：打印、记录等
+pub type OutputSink = Box<dyn FnMut(&Output) + Send>;
+
+/// 闭环智能体
+/// * 🎯拉取输出 ⇒ 将`EXE`分派给操作回调 ⇒ 将回调返回的指令输入CIN
+/// * 🚩不自行步进：何时输入`CYC`、目标等，由调用方决定
+pub struct Agent<R: VmRuntimeBoost> {
+    /// 所包装的运行时
+    runtime: R,
+
+    /// 操作回调
+    /// * 🚩以操作符名（不带`^`）为键
+    handlers: HashMap<String, OperationHandler>,
+
+    /// 输出接收者（可选）
+    sink: Option<OutputSink>,
+}
+
+impl<R: VmRuntimeBoost> Agent<R> {
+    /// 构造函数
+    /// * 🚩无操作回调、无输出接收者
+    pub fn new(runtime: R) -> Self {
+        Self {
+            runtime,
+            handlers: HashMap::new(),
+            sink: None,
+        }
+    }
+
+    /// 注册操作回调
+    /// * 🚩操作符名可带可不带`^`：`left`与`^left`等价
+    /// * 🚩重复注册⇒覆盖先前的回调
+    pub fn on_operation(
+        &mut self,
+        operator_name: &str,
+        handler: impl FnMut(&[Term]) -> Vec<Cmd> + Send + 'static,
+    ) {
+        let operator_name = operator_name.trim_start_matches('^').to_string();
+        self.handlers.insert(operator_name, Box::new(handler));
+    }
+
+    /// 配置/输出接收者
+    /// * 📌接收所有非`EXE`输出，以及没有对应回调的`EXE`输出
+    pub fn output_sink(&mut self, sink: impl FnMut(&Output) + Send + 'static) {
+        self.sink = Some(Box::new(sink));
+    }
+
+    /// 向CIN输入指令
+    pub fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        self.runtime.input_cmd(cmd)
+    }
+
+    /// 进行一轮「拉取⇒分派⇒反馈」
+    /// * 🚩拉取当前所有待处理的输出（不阻塞），逐个处理：
+    ///   * `EXE`且有对应回调⇒调用回调，并将返回的指令依次输入CIN
+    ///   * 其它⇒交给输出接收者（若有）
+    /// * ⚙️返回本轮被回调处理的操作数
+    /// * 📌反馈指令所引起的输出，在下一轮处理
+    pub fn step(&mut self) -> Result<usize> {
+        let mut handled = 0;
+        for output in self.runtime.drain_outputs()? {
+            let handler = output
+                .get_operation()
+                .and_then(|operation| self.handlers.get_mut(&operation.operator_name));
+            match (handler, output.get_operation()) {
+                (Some(handler), Some(operation)) => {
+                    handled += 1;
+                    for cmd in handler(&operation.params) {
+                        self.runtime.input_cmd(cmd)?;
+                    }
+                }
+                _ => {
+                    if let Some(sink) = &mut self.sink {
+                        sink(&output);
+                    }
+                }
+            }
+        }
+        Ok(handled)
+    }
+
+    /// 不可变借用运行时
+    pub fn runtime(&self) -> &R {
+        &self.runtime
+    }
+
+    /// 可变借用运行时
+    /// * 🎯原始输入、终止等
+    pub fn runtime_mut(&mut self) -> &mut R {
+        &mut self.runtime
+    }
+
+    /// 取回运行时
+    pub fn into_runtime(self) -> R {
+        self.runtime
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::{FakeVm, FakeVmRuntime};
+    use nar_dev_utils::manipulate;
+    use narsese::{lexical_nse_task as nse_task, lexical_nse_term as nse_term};
+    use navm::{
+        output::Operation,
+        vm::{VmLauncher, VmRuntime},
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// 「执行某操作」的输出
+    fn exe(operator_name: &str) -> Output {
+        Output::EXE {
+            content_raw: format!("EXE: ^{operator_name}({{SELF}})"),
+            operation: Operation::new(operator_name, [nse_term!({ SELF })].into_iter()),
+        }
+    }
+
+    /// 反馈「好」的指令
+    fn good() -> Cmd {
+        Cmd::NSE(nse_task!(<{SELF} --> [good]>. :|:))
+    }
+
+    /// 「二选一操作」的模拟CIN
+    /// * 🚩每步进一次，执行`^left`、`^right`、`^deactivate`各一次，并输出一条`INFO`
+    fn fake_runtime() -> FakeVmRuntime {
+        manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::CYC(1),
+                [
+                    exe("left"),
+                    exe("right"),
+                    exe("deactivate"),
+                    Output::INFO { message: "info".into() },
+                ],
+            )
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败")
+    }
+
+    /// 测试/分派操作，并反馈指令
+    #[test]
+    fn dispatch_operations() -> Result<()> {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let sunk = Arc::new(Mutex::new(vec![]));
+        let mut agent = Agent::new(fake_runtime());
+        let left_calls = calls.clone();
+        agent.on_operation("left", move |params| {
+            left_calls.lock().unwrap().push(("left", params.to_vec()));
+            vec![good()]
+        });
+        let right_calls = calls.clone();
+        agent.on_operation("^right", move |params| {
+            right_calls.lock().unwrap().push(("right", params.to_vec()));
+            vec![]
+        });
+        let sink = sunk.clone();
+        agent.output_sink(move |output| sink.lock().unwrap().push(output.clone()));
+
+        // 步进前无输出
+        assert_eq!(agent.step()?, 0);
+        agent.input_cmd(Cmd::CYC(1))?;
+        assert_eq!(agent.step()?, 2);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [
+                ("left", vec![nse_term!({ SELF })]),
+                ("right", vec![nse_term!({ SELF })])
+            ]
+        );
+        // 未注册的操作、非`EXE`输出⇒交给输出接收者
+        assert_eq!(
+            *sunk.lock().unwrap(),
+            [
+                exe("deactivate"),
+                Output::INFO {
+                    message: "info".into()
+                }
+            ]
+        );
+
+        // 反馈的指令已输入CIN：其回显在下一轮处理
+        sunk.lock().unwrap().clear();
+        assert_eq!(agent.step()?, 0);
+        assert!(matches!(
+            &sunk.lock().unwrap()[..],
+            [Output::IN { content, .. }] if content.contains("good")
+        ));
+        Ok(())
+    }
+
+    /// 测试/重复注册⇒覆盖
+    #[test]
+    fn override_handler() -> Result<()> {
+        let mut agent = Agent::new(fake_runtime());
+        agent.on_operation("left", |_| panic!("应被覆盖"));
+        agent.on_operation("left", |_| vec![]);
+        agent.input_cmd(Cmd::CYC(1))?;
+        assert_eq!(agent.step()?, 1);
+        Ok(())
+    }
+
+    /// 测试/反馈失败⇒报错
+    #[test]
+    fn feedback_error() -> Result<()> {
+        let mut agent = Agent::new(fake_runtime());
+        agent.on_operation("left", |_| vec![good()]);
+        agent.input_cmd(Cmd::CYC(1))?;
+        agent.runtime_mut().terminate()?;
+        assert!(agent.step().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+//! 基于「闭环智能体」的左右操作条件反射训练
+//! * 🎯以[`Agent`]重写`ws_server_test`中的训练过程：按操作符注册回调，而非对消息做字符串匹配
+//! * 🚩作为Websocket客户端，连接到已在运行的BabelNAR CLI（`ws://127.0.0.1:8765`）
+//!   * 📌握手为`cmd`输入模式：输入NAVM指令
+//! * 📌环境：「好」的一侧每隔一段时间左右互换，观察NARS的适应能力
+
+use anyhow::Result;
+use babel_nar::{
+    agent::Agent,
+    output_handler::json::output_from_json,
+    runtimes::{VmRuntimeBoost, WebsocketVm},
+};
+use nar_dev_utils::manipulate;
+use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
+use navm::{cmd::Cmd, output::Output, vm::VmLauncher};
+use std::{
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
+
+/// 服务端地址
+const ADDRESS: &str = "127.0.0.1:8765";
+
+/// 连续「好」多少次后，左右互换
+const MAX_GOOD: usize = 20;
+
+/// 每轮的间隔
+const ROUND_INTERVAL: Duration = Duration::from_millis(30);
+
+/// 训练环境
+/// * 🎯在左右操作的回调间共享状态
+#[derive(Debug, Default)]
+struct Environment {
+    /// 当前「好」的一侧是否为右侧
+    right_side: bool,
+    /// 当前一侧已连续「好」的次数
+    num_good: usize,
+    /// 自上次回到左侧以来的轮数
+    rounds: usize,
+    /// 最短的「适应周期」（轮数）
+    minimum_fitness_period: Option<usize>,
+}
+
+impl Environment {
+    /// 对一次操作给出反馈
+    /// * 🚩操作与「好」的一侧相符⇒奖励，否则⇒惩罚
+    fn feedback(&mut self, right: bool) -> Vec<Cmd> {
+        let side = if self.right_side { "right" } else { "left" };
+        let period = self.minimum_fitness_period;
+        let truth = match right == self.right_side {
+            true => {
+                println!("good\t{side}\tfor {}!\t{period:?}", self.num_good);
+                self.num_good += 1;
+                // 改变模式 | 一个轮回⇒以「轮数」记录「适应性」
+                if self.num_good > MAX_GOOD {
+                    if self.right_side {
+                        self.minimum_fitness_period =
+                            Some(period.map_or(self.rounds, |p| p.min(self.rounds)));
+                        self.rounds = 0;
+                    }
+                    self.right_side = !self.right_side;
+                    self.num_good = 0;
+                }
+                "%1.0; 0.5%"
+            }
+            false => {
+                println!("bad\t{side}\tfor {}!\t{period:?}", self.num_good);
+                "%0.0; 0.5%"
+            }
+        };
+        vec![nse(&format!("<{{SELF}} --> [good]>. :|: {truth}"))]
+    }
+}
+
+/// 从CommonNarsese构造`NSE`指令
+/// * ⚠️仅用于内置的Narsese：解析失败⇒panic
+fn nse(narsese: &str) -> Cmd {
+    let task = FORMAT_ASCII
+        .parse(narsese)
+        .expect("内置的Narsese有误")
+        .try_into_task_compatible()
+        .expect("内置的Narsese不是语句");
+    Cmd::NSE(task)
+}
+
+/// 将BabelNAR CLI回传的消息转译为NAVM输出
+/// * 📌消息形如`[{...}]`：每条消息包含一个输出
+fn translate_message(message: String) -> Result<Output> {
+    let inner = message
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'));
+    Ok(match inner.map(output_from_json) {
+        Some(Ok(output)) => output,
+        _ => Output::OTHER { content: message },
+    })
+}
+
+/// 进行一次训练
+/// * 🚩连接断开⇒返回错误
+fn train() -> Result<()> {
+    let runtime = manipulate!(
+        WebsocketVm::new(ADDRESS)
+        => .output_translator(translate_message)
+    )
+    .launch()?;
+    let mut agent = Agent::new(runtime);
+    agent.runtime_mut().input_raw(r#"{"inputMode":"cmd"}"#)?;
+
+    // 注册操作
+    let environment = Arc::new(Mutex::new(Environment::default()));
+    for (operator, right) in [("left", false), ("right", true)] {
+        let environment = environment.clone();
+        agent.on_operation(operator, move |_| {
+            environment.lock().unwrap().feedback(right)
+        });
+        agent.input_cmd(Cmd::REG {
+            name: operator.into(),
+        })?;
+    }
+
+    // 预先经验
+    for _ in 0..5 {
+        // 背景事件
+        agent.input_cmd(nse("<a --> b>. :|:"))?;
+        // 自身操作
+        agent.input_cmd(nse("<(*, {SELF}) --> ^left>. :|:"))?;
+        agent.input_cmd(nse("<(*, {SELF}) --> ^right>. :|:"))?;
+        // 一定间隔
+        agent.input_cmd(Cmd::CYC(10))?;
+        // 自身状态
+        agent.input_cmd(nse("<{SELF} --> [good]>. :|:"))?;
+    }
+    // 再间隔一段时间，开始训练
+    agent.input_cmd(Cmd::CYC(100))?;
+
+    // 训练：不断给出背景事件与目标，并处理操作
+    loop {
+        agent.input_cmd(nse("<a --> b>. :|:"))?;
+        agent.input_cmd(Cmd::CYC(10))?;
+        agent.input_cmd(nse("<{SELF} --> [good]>! :|:"))?;
+        agent.step()?;
+        environment.lock().unwrap().rounds += 1;
+        sleep(ROUND_INTERVAL);
+    }
+}
+
+fn main() {
+    // 循环 | 连接失败则延迟等待
+    loop {
+        if let Err(e) = train() {
+            eprintln!("训练中断：{e}");
+        }
+        sleep(Duration::from_secs(1));
+    }
+}
@@ -1,13 +1,16 @@
 //! BabelNAR CLI的命令行（参数 & 配置）解析支持
 //! * ⚠️【2024-04-01 14:31:09】特定于二进制crate，目前不要并入[`babel_nar`]
-//! * 🚩【2024-04-04 03:03:58】现在移出所有与「启动配置」相关的逻辑到[`super::vm_config`]
+//! * 🚩【2024-04-04 03:03:58】现在移出所有与「启动配置」相关的逻辑到[`babel_nar::cli_support::config`]
 
-use crate::{load_config_extern, read_config_extern, LaunchConfig};
-use babel_nar::println_cli;
+use crate::{
+    load_config_extern, read_config_extern, try_complete_path, ConfigProvenance, LaunchConfig,
+    LaunchConfigPreludeNAL, EXIT_CODES_HELP,
+};
+use babel_nar::{cli_support::presets::preset_by_name, println_cli};
 use clap::Parser;
 use std::{
     env::{current_dir, current_exe},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 /// 基于[`clap`]的命令行参数数据
@@ -16,6 +19,7 @@ use std::{
 #[command(name = "BabelNAR CLI")]
 #[command(about = "BabelNAR's Cmdline Interface", long_about = None)]
 #[command(version, about, long_about = None)]
+#[command(after_help = EXIT_CODES_HELP)]
 // 其它
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CliArgs {
@@ -30,6 +34,20 @@ pub struct CliArgs {
     #[arg(short, long, value_name = "FILE")]
     pub config: Vec<PathBuf>,
 
+    // 启动配置预设
+    // * 🎯无需编写配置文件即可启动常见CIN：`--preset ona --path ./executables/ONA.exe`
+    // * 🚩作为最底层的配置：`-c`指定的配置文件可在其上微调
+    // * 📌可用的预设参见[`babel_nar::cli_support::presets`]
+    /// Launch with a built-in configuration preset (opennars, ona, pynars), tweakable by `-c`
+    #[arg(long, value_name = "NAME", requires = "path")]
+    pub preset: Option<String>,
+
+    // 预设所用的路径
+    // * 📌OpenNARS⇒jar文件；ONA⇒exe文件；PyNARS⇒源码根目录
+    /// The path used by `--preset`: jar file for opennars, executable for ona, source root for pynars
+    #[arg(long, value_name = "PATH", requires = "preset")]
+    pub path: Option<PathBuf>,
+
     // 禁用默认配置
     // * 禁用与exe同目录的配置文件
     // * 📜默认为`false`
@@ -39,6 +57,78 @@ pub struct CliArgs {
     /// Disable the default configuration file in the same directory as exe
     #[arg(short, long)]
     pub disable_default: bool,
+
+    // 批处理：运行单个NAL文件后退出
+    // * 🎯脚本化回归测试：以退出码反映「所有预期是否通过」
+    // * 🚩禁用用户输入与Websocket服务，将文件作为「预置NAL」运行
+    // * 📌所有预期失败都会被统计，而不会在首个失败处中止
+    /// Run a single NAL file in batch mode, and exit with non-zero code if any expectation fails
+    #[arg(short, long, value_name = "FILE")]
+    pub eval: Option<PathBuf>,
+
+    // 以JSON格式输出
+    // * 🎯作为子进程被其它程序调用时，提供机器可读的输出
+    // * 🚩等效于配置`outputFormat: "json"`，且优先于配置文件
+    /// Print each NAVM output as one JSON line on stdout, and CLI messages on stderr
+    #[arg(long)]
+    pub output_json: bool,
+
+    // 安静模式
+    // * 🎯高音量下只关注有用的输出
+    // * 🚩等效于配置`printLevel: ["ERROR", "ANSWER"]`，且优先于配置文件
+    /// Only print ERROR and ANSWER outputs (all outputs are still cached and broadcast)
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    // 禁用彩色输出
+    // * 🎯重定向到文件、不支持转义序列的终端
+    // * 🚩等效于配置`noColor: true`，且优先于配置文件
+    // * 📌环境变量`NO_COLOR`、非终端输出亦会禁用
+    /// Disable colored output (also disabled by NO_COLOR or when not writing to a terminal)
+    #[arg(long)]
+    pub no_color: bool,
+
+    // 录制会话
+    // * 🎯记录所有输入的NAVM指令与输出，以便之后在其它CIN上重放、对比
+    // * 📌格式：NDJSON，参见[`babel_nar::cli_support::io::session`]
+    /// Record every NAVM command and output with timestamps into an NDJSON file
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    // 跨CIN测试
+    // * 🎯对比同一NAL文件在多个CIN上的通过情况
+    // * 🚩`-c`指定的每个配置文件各自对应一个CIN（而不合并），并行运行后输出Markdown表格
+    /// Run a NAL file on every CIN configured by `-c` in parallel, and print a markdown pass/fail table
+    #[arg(long, value_name = "FILE")]
+    pub cross: Option<PathBuf>,
+
+    // 测试报告
+    // * 🎯CI中汇总`.nal`预期的结果
+    // * 🚩等效于配置`reportFile`，且优先于配置文件
+    // * 📌扩展名为`.xml`⇒JUnit风格的XML；否则⇒JSON
+    /// Write a report of all NAL expectations in prelude/eval runs (JUnit XML if the file ends with `.xml`, JSON otherwise)
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+
+    // 列出转译器
+    // * 🎯查看配置`translators`可用的名称
+    // * 🚩列出所有转译器预设（及其别名）后退出
+    /// List all translator presets (with their aliases) available for `translators`, then exit
+    #[arg(long)]
+    pub list_translators: bool,
+
+    // 打印生效配置
+    // * 🎯多配置合并、路径变基、占位符展开、用户填充后，查看实际启动所用的配置
+    // * 🚩转换为运行时配置后，以HJSON打印，并以注释标注各顶层配置项的来源
+    /// Print the effective configuration (with the source of each field) before launching
+    #[arg(long)]
+    pub print_config: bool,
+
+    // 仅打印生效配置
+    // * 🚩同`--print-config`，但打印后退出，不启动CIN
+    /// Print the effective configuration (with the source of each field), then exit without launching
+    #[arg(long)]
+    pub print_config_only: bool,
     // ! 🚩【2024-04-02 11:36:18】目前除了「配置加载」外，莫将任何「NAVM实现特定，可以内置到『虚拟机配置』的字段放这儿」
 }
 
@@ -52,17 +142,19 @@ pub const DEFAULT_CONFIG_KEYWORD: &str = "BabelNAR-launch";
 /// * 🎯更灵活地寻找可用的配置文件
 ///   * exe当前目录下 | 工作目录下
 ///   * `BabelNAR.launch.(h)json`
-pub fn try_load_default_config() -> Option<LaunchConfig> {
+/// * 🚩连同配置文件路径一并返回
+pub fn try_load_default_config() -> Option<(PathBuf, LaunchConfig)> {
     // 检查一个目录
     #[inline(always)]
-    fn in_one_root(root: PathBuf) -> Option<LaunchConfig> {
+    fn in_one_root(root: PathBuf) -> Option<(PathBuf, LaunchConfig)> {
         // 计算路径：同目录下
         let path = match root.is_dir() {
             true => root.join(DEFAULT_CONFIG_KEYWORD),
             false => root.with_file_name(DEFAULT_CONFIG_KEYWORD),
         };
         // 尝试读取，静默失败
-        read_config_extern(&path).ok()
+        let config = read_config_extern(&path).ok()?;
+        Some((path, config))
     }
     // 寻找第一个可用的配置文件
     [current_dir(), current_exe()]
@@ -77,24 +169,41 @@ pub fn try_load_default_config() -> Option<LaunchConfig> {
 }
 
 /// 加载配置
-/// * 🚩按照一定优先级顺序进行覆盖（从先到后）
-///   * 命令行参数中指定的配置文件
-///   * 默认配置文件路径 | 可以在`disable_default = true`的情况下传入任意字串作占位符
-pub fn load_config(args: &CliArgs) -> LaunchConfig {
+/// * 🚩按照一定优先级顺序进行覆盖（从高到低）
+///   * 命令行参数中指定的配置文件 | 后指定者优先，覆盖先指定者中显式设置的项
+///   * 命令行参数中指定的预设 | 被配置文件覆盖
+///   * 默认配置文件路径 | 仅填补缺省项 | 可以在`disable_default = true`的情况下传入任意字串作占位符
+/// * 🚩一并记录各配置项的来源：配置文件的（补全后的）绝对路径
+pub fn load_config(args: &CliArgs) -> (LaunchConfig, ConfigProvenance) {
     // 构建返回值 | 全`None`
     let mut result = LaunchConfig::new();
+    let mut provenance = ConfigProvenance::new();
+    // 启动配置预设 | 作为最底层：之后的配置文件可覆盖之
+    // * 🚩路径绝对化：启动时会切换工作目录
+    if let (Some(name), Some(path)) = (&args.preset, &args.path) {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.clone());
+        match preset_by_name(name, path) {
+            Ok(preset) => {
+                result.override_from_tracked(&preset, &format!("预设「{name}」"), &mut provenance)
+            }
+            Err(e) => println_cli!([Warn] "{e}"),
+        }
+    }
     // 尝试从命令行参数中读取再合并配置 | 仅提取出其中`Some`的项
     args.config
         // 尝试加载配置文件，对错误采取「警告并抛掉」的策略
         .iter()
-        .map(PathBuf::as_ref)
-        .filter_map(load_config_extern)
-        // 逐个从「命令行参数指定的配置文件」中合并
-        .for_each(|config| result.merge_from(&config));
+        .filter_map(|path| Some((path, load_config_extern(path)?)))
+        // 逐个以「命令行参数指定的配置文件」覆盖
+        .for_each(|(path, config)| {
+            result.override_from_tracked(&config, &config_source(path), &mut provenance)
+        });
     // 若未禁用，尝试读取再合并默认启动配置
     if !args.disable_default {
         // * 🚩读取失败⇒警告&无动作 | 避免多次空合并
-        try_load_default_config().inspect(|config_extern| result.merge_from(config_extern));
+        if let Some((path, config_extern)) = try_load_default_config() {
+            result.merge_from_tracked(&config_extern, &config_source(&path), &mut provenance);
+        }
     }
     // 展示加载的配置 | 以便调试（以防其它地方意外插入别的配置）
     if result.is_empty() {
@@ -106,7 +215,28 @@ pub fn load_config(args: &CliArgs) -> LaunchConfig {
         }
     }
     // 返回
-    result
+    (result, provenance)
+}
+
+/// 配置文件作为「配置来源」的名称
+/// * 🚩补全扩展名后绝对化：与实际读取的文件一致
+fn config_source(path: &Path) -> String {
+    let path = try_complete_path(path);
+    std::path::absolute(&path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// 应用「批处理模式」配置
+/// * 🎯`--eval`：将NAL文件作为「预置NAL」运行
+/// * 🚩禁用用户输入、Websocket服务与严格模式
+///   * 📌关闭严格模式：预期失败后继续运行，以统计所有结果
+pub fn apply_eval_config(config: &mut LaunchConfig, nal_path: &Path) {
+    config.prelude_nal = Some(vec![LaunchConfigPreludeNAL::File(nal_path.to_path_buf())]);
+    config.user_input = Some(false);
+    config.websocket = None;
+    config.strict_mode = Some(false);
 }
 
 /// 单元测试
@@ -181,6 +311,70 @@ mod tests {
                     disable_default: true,
                     ..Default::default()
                 };
+                // JSON输出：使用`--output-json`
+                ["--output-json"]
+                => CliArgs {
+                    output_json: true,
+                    ..Default::default()
+                };
+                // 批处理：使用`-e`/`--eval`
+                ["-c", "1", "--eval", "test.nal"]
+                => CliArgs {
+                    config: vec!["1".into()],
+                    eval: Some("test.nal".into()),
+                    ..Default::default()
+                };
+                // 安静模式：使用`-q`/`--quiet`
+                ["-q"]
+                => CliArgs {
+                    quiet: true,
+                    ..Default::default()
+                };
+                // 启动配置预设：使用`--preset`与`--path`
+                ["--preset", "ona", "--path", "ONA.exe", "-c", "1"]
+                => CliArgs {
+                    config: vec!["1".into()],
+                    preset: Some("ona".into()),
+                    path: Some("ONA.exe".into()),
+                    ..Default::default()
+                };
+                // 禁用彩色输出：使用`--no-color`
+                ["--no-color"]
+                => CliArgs {
+                    no_color: true,
+                    ..Default::default()
+                };
+                // 录制会话：使用`--record`
+                ["--record", "session.ndjson"]
+                => CliArgs {
+                    record: Some("session.ndjson".into()),
+                    ..Default::default()
+                };
+                // 跨CIN测试：使用`--cross`，搭配多个`-c`
+                ["--cross", "test.nal", "-c", "ona.hjson", "-c", "pynars.hjson"]
+                => CliArgs {
+                    cross: Some("test.nal".into()),
+                    config: vec!["ona.hjson".into(), "pynars.hjson".into()],
+                    ..Default::default()
+                };
+                // 列出转译器：使用`--list-translators`
+                ["--list-translators"]
+                => CliArgs {
+                    list_translators: true,
+                    ..Default::default()
+                };
+                // 打印生效配置：使用`--print-config`/`--print-config-only`
+                ["-c", "1", "--print-config"]
+                => CliArgs {
+                    config: vec!["1".into()],
+                    print_config: true,
+                    ..Default::default()
+                };
+                ["--print-config-only"]
+                => CliArgs {
+                    print_config_only: true,
+                    ..Default::default()
+                };
             };
         }
 
@@ -203,16 +397,16 @@ mod tests {
     /// 测试/加载配置
     mod read_config {
         use super::*;
-        use crate::vm_config::*;
         use crate::LaunchConfigWebsocket;
+        use babel_nar::cli_support::{config::*, presets::preset_ona};
         use config_paths::*;
-        use nar_dev_utils::manipulate;
+        use nar_dev_utils::{asserts, manipulate};
 
         /// 测试/加载配置
         fn load(args: &[&str]) -> LaunchConfig {
             // 读取配置 | 自动填充第一个命令行参数作为「当前程序路径」
             let args = CliArgs::parse_from([&["test.exe"], args].concat());
-            let config = load_config(&args);
+            let (config, _) = load_config(&args);
             dbg!(config)
         }
 
@@ -255,6 +449,7 @@ mod tests {
                                 "nars.jar".into()
                             ]),
                             current_dir: Some(expected_current_dir.clone()),
+                            env: None,
                         }),
                         ..Default::default()
                     };
@@ -262,6 +457,7 @@ mod tests {
                         websocket: Some(LaunchConfigWebsocket {
                             host: "localhost".into(),
                             port: 8080,
+                            ..Default::default()
                         }),
                         ..Default::default()
                     };
@@ -284,10 +480,12 @@ mod tests {
                                 "nars.jar".into()
                             ]),
                             current_dir: Some(expected_current_dir.clone()),
+                            env: None,
                         }),
                         websocket: Some(LaunchConfigWebsocket {
                             host: "localhost".into(),
                             port: 8080,
+                            ..Default::default()
                         }),
                         ..Default::default()
                     };
@@ -311,10 +509,12 @@ mod tests {
                                 "nars.jar".into()
                             ]),
                             current_dir: Some(expected_current_dir.clone()),
+                            env: None,
                         }),
                         websocket: Some(LaunchConfigWebsocket {
                             host: "localhost".into(),
                             port: 8080,
+                            ..Default::default()
                         }),
                         user_input: Some(false),
                         auto_restart: Some(false),
@@ -323,5 +523,88 @@ mod tests {
                     }
             }
         }
+
+        /// 测试/启动配置预设
+        /// * 🎯预设作为最底层，被配置文件覆盖
+        #[test]
+        fn test_preset() {
+            let preset = ["--preset", "ONA", "--path", "exe/ONA.exe"];
+            let args = CliArgs::parse_from(
+                [&["test.exe", "-d"], &preset[..], &["-c", WEBSOCKET]].concat(),
+            );
+            let (config, provenance) = load_config(&args);
+            let mut expected = preset_ona(current_dir().unwrap().join("exe/ONA.exe"));
+            // * 📌配置合并不涉及「描述」
+            expected.description = None;
+            expected.websocket = Some(LaunchConfigWebsocket {
+                host: "localhost".into(),
+                port: 8080,
+                ..Default::default()
+            });
+            asserts! {
+                config => expected,
+                provenance.sources("command") => ["预设「ONA」"],
+                provenance.sources("websocket") => [config_source(Path::new(WEBSOCKET))],
+            }
+            // 未知预设⇒警告并忽略
+            let args = CliArgs::parse_from(["test.exe", "-d", "--preset", "onna", "--path", "."]);
+            assert!(load_config(&args).0.is_empty());
+        }
+
+        /// 测试/配置来源
+        /// * 🎯合并两个配置文件，打印的生效配置中标注各配置项的来源
+        #[test]
+        fn test_provenance() {
+            let args =
+                CliArgs::parse_from(["test.exe", "-d", "-c", ARG_PARSE_TEST, "-c", WEBSOCKET]);
+            let (config, provenance) = load_config(&args);
+            let source = |path: &str| config_source(Path::new(path));
+            asserts! {
+                provenance.sources("translators") => [source(ARG_PARSE_TEST)],
+                provenance.sources("command") => [source(ARG_PARSE_TEST)],
+                provenance.sources("websocket") => [source(WEBSOCKET)],
+                provenance.sources("userInput").is_empty(),
+            }
+
+            // 转换为运行时配置后，带注释打印
+            let config = RuntimeConfig::try_from(config).expect("无法转换为运行时配置");
+            let hjson = config.to_annotated_hjson(&provenance).expect("无法序列化");
+            let lines = hjson.lines().collect::<Vec<_>>();
+            let comment_of = |key: &str| {
+                let index = lines
+                    .iter()
+                    .position(|line| line.starts_with(&format!("  \"{key}\":")))
+                    .expect("找不到配置项");
+                lines[index - 1].trim()
+            };
+            asserts! {
+                comment_of("translators") => format!("// 来源：{}", source(ARG_PARSE_TEST)),
+                comment_of("command") => format!("// 来源：{}", source(ARG_PARSE_TEST)),
+                comment_of("websocket") => format!("// 来源：{}", source(WEBSOCKET)),
+                comment_of("userInput") => "// 默认值",
+                comment_of("preludeNAL") => "// 默认值",
+            }
+            // 路径已绝对化
+            let current_dir = config.command.current_dir.expect("缺少工作目录");
+            assert!(current_dir.is_absolute());
+            // 仍可作为配置读取
+            let reloaded = LaunchConfig::from_json_str(&hjson).expect("无法重新读取");
+            assert_eq!(reloaded.websocket, config.websocket);
+        }
+
+        /// 测试/批处理模式
+        /// * 🎯覆盖「用户输入」「Websocket」「严格模式」，并将文件作为「预置NAL」
+        #[test]
+        fn test_eval() {
+            let mut config = load(&["-c", WEBSOCKET, "-c", PRELUDE_TEST, "-d"]);
+            apply_eval_config(&mut config, Path::new("test.nal"));
+            assert_eq!(config.websocket, None);
+            assert_eq!(config.user_input, Some(false));
+            assert_eq!(config.strict_mode, Some(false));
+            assert_eq!(
+                config.prelude_nal,
+                Some(vec![LaunchConfigPreludeNAL::File("test.nal".into())])
+            );
+        }
     }
 }
@@ -1,30 +1,52 @@
 //! 用于从「启动参数」启动NAVM运行时
 
 use crate::{
-    read_config_extern, search_configs, LaunchConfig, LaunchConfigCommand, LaunchConfigTranslators,
-    RuntimeConfig, SUPPORTED_CONFIG_EXTENSIONS,
+    read_config_extern, search_configs, CliError, LaunchConfig, LaunchConfigCommand,
+    LaunchConfigConnect, LaunchConfigExecTranslator, LaunchConfigPythonEnv,
+    LaunchConfigTranslators, RuntimeConfig, TranslatorDirection, SUPPORTED_CONFIG_EXTENSIONS,
 };
 use anyhow::{anyhow, Result};
 use babel_nar::{
     cin_implements::{
-        common::generate_command, cxin_js, nars_python, native, ona, openjunars, opennars, pynars,
+        available_presets,
+        common::{
+            check_java_version, check_python_import, generate_command, is_jar_path,
+            is_java_program, python_path_env, venv_python, CommandGeneratorJava, COMMAND_JAVA,
+        },
+        exec::translators::ExecTranslator,
+        find_translator_preset,
+        opennars::OPENNARS_MIN_JAVA_VERSION,
+        preset_capabilities, preset_cyc_ack_matcher, preset_line_continuation, preset_names,
+        preset_ready_marker, TranslatorPreset,
+    },
+    cli_support::{
+        cin_search::name_match::{best_name_match, suggest_names},
+        io::{readline_iter::ReadlineIter, websocket::to_address},
     },
-    cli_support::{cin_search::name_match::name_match, io::readline_iter::ReadlineIter},
     eprintln_cli, println_cli,
+    process_io::{resolve_executable, ProcessEncoding},
     runtimes::{
         api::{InputTranslator, IoTranslators},
-        CommandVm, OutputTranslator,
+        Capabilities, CommandGenerator, CommandVm, CommandVmRuntime, CycPacing, KillHandle,
+        OutputTranslator, VmRuntimeBoost, WebsocketVm, WebsocketVmRuntime,
+        DEFAULT_COALESCE_TIMEOUT,
     },
 };
-use nar_dev_utils::pipe;
+use nar_dev_utils::{if_return, manipulate, pipe};
 use navm::{
     cmd::Cmd,
     output::Output,
-    vm::{VmLauncher, VmRuntime},
+    vm::{VmLauncher, VmRuntime, VmStatus},
+};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
 };
-use std::path::{Path, PathBuf};
 
 /// （若缺省）要求用户手动填充配置项
+/// * 🚩仅在「启动时必要项」缺失时提示，并列出缺失的项
+/// * 🚩用户指定的配置仅用于填补缺省项，不覆盖已加载的配置
 pub fn polyfill_config_from_user(config: &mut LaunchConfig, cwd: Option<impl AsRef<Path>>) {
     if config.need_polyfill() {
         // * 先搜索已有的文件 | 不开启
@@ -45,6 +67,7 @@ pub fn polyfill_config_from_user(config: &mut LaunchConfig, cwd: Option<impl AsR
         const HINT: &str = "现在需要输入配置文件位置。\n    示例：「BabelNAR.launch.json」\n    若搜索到已有配置文件，可输入其在方括号内的索引，如「0」\n    可直接按下回车，以查看详细搜索过程";
         const PROMPT: &str = "配置文件位置: ";
         // 提示（不会频繁打印）
+        println_cli!([Info] "配置缺少必要项：{}", config.missing_required_fields().join(", "));
         println_cli!([Info] "{}", HINT);
         for line in ReadlineIter::new(PROMPT) {
             // 检验输入
@@ -57,12 +80,18 @@ pub fn polyfill_config_from_user(config: &mut LaunchConfig, cwd: Option<impl AsR
             }; // ! 不能直接加`.trim()`，临时变量会被抛掉
             let line = line.trim();
             if let Ok(i) = line.parse::<usize>() {
-                if i < searched_configs.len() {
-                    println_cli!([Info] "已选择搜索到的第「{i}」个配置：{:?}", searched_configs[i])
+                let Some(searched) = searched_configs.get(i) else {
+                    eprintln_cli!([Error] "索引「{i}」超出搜索结果范围");
+                    continue;
+                };
+                println_cli!([Info] "已选择搜索到的第「{i}」个配置：{searched:?}");
+                // 填补缺省项；仍有缺失⇒继续要求输入
+                config.merge_from(searched);
+                if !config.need_polyfill() {
+                    break;
                 }
-                // 返回结果
-                *config = searched_configs[i].clone();
-                break;
+                println_cli!([Info] "配置仍缺少必要项：{}", config.missing_required_fields().join(", "));
+                continue;
             }
             // 输入为空⇒详细搜索配置⇒重新回到循环
             if line.is_empty() {
@@ -84,13 +113,36 @@ pub fn polyfill_config_from_user(config: &mut LaunchConfig, cwd: Option<impl AsR
                     continue;
                 }
             };
-            // 读取成功⇒覆盖，返回
-            *config = content;
-            break;
+            // 读取成功⇒填补缺省项；仍有缺失⇒继续要求输入
+            config.merge_from(&content);
+            if !config.need_polyfill() {
+                break;
+            }
+            println_cli!([Info] "配置仍缺少必要项：{}", config.missing_required_fields().join(", "));
         }
     }
 }
 
+/// （若缺省）从工作目录自动搜索配置，填充配置项
+/// * 🎯非交互模式（JSON输出、管道输入）下无法提示用户：改为直接使用搜索到的配置
+/// * 🚩按搜索顺序依次填补缺省项，直到不再缺少必要项
+/// * 🚩用到的配置打印为「信息」，仍有缺失则在启动时报错
+pub fn polyfill_config_from_search(config: &mut LaunchConfig, cwd: Option<impl AsRef<Path>>) {
+    if_return! { !config.need_polyfill() }
+    let searched_configs = match cwd
+        .as_ref()
+        .map(|p| search_configs(p.as_ref(), SUPPORTED_CONFIG_EXTENSIONS, false))
+    {
+        Some(Ok(v)) => v,
+        _ => return,
+    };
+    for (i, searched) in searched_configs.into_iter().enumerate() {
+        println_cli!([Info] "使用搜索到的第「{i}」个配置：{searched:?}");
+        config.merge_from(&searched);
+        if_return! { !config.need_polyfill() }
+    }
+}
+
 /// 从「启动参数」中启动
 /// * 🚩在转换中确认参数
 /// * ⚙️返回(启动后的运行时, 转换后的『运行时配置』)
@@ -100,62 +152,317 @@ pub fn polyfill_config_from_user(config: &mut LaunchConfig, cwd: Option<impl AsR
 ///   * ❗错误类型不一致，无法统一返回
 pub fn launch_by_config(
     config: impl TryInto<RuntimeConfig, Error = anyhow::Error>,
-) -> Result<(impl VmRuntime, RuntimeConfig)> {
-    // 转换启动配置
-    let config: RuntimeConfig = config.try_into()?;
+) -> Result<(impl VmRuntimeBoost, RuntimeConfig)> {
+    // 转换启动配置，并附加CIN所支持的功能
+    let mut config: RuntimeConfig = config.try_into().map_err(CliError::Config)?;
+    config.capabilities = get_capabilities_by_translators(&config.translators);
+
+    // 仅含文件名的启动命令⇒在CIN目录中查找
+    resolve_cmd_in_executables(&mut config.command, &config.config_path);
+
+    // 启动前检查配置 | 有错误⇒中止启动
+    validate_config(&config).map_err(CliError::Config)?;
 
     // * 🚩【2024-04-07 10:13:51】目前通过「设置exe工作路径」切换到启动环境中
     if let Some(path) = &config.command.current_dir {
-        std::env::set_current_dir(path)?;
+        std::env::set_current_dir(path).map_err(|e| CliError::Launch(e.into()))?;
     }
 
     // 生成虚拟机
-    let runtime = launch_by_runtime_config(&config)?;
+    let runtime = launch_by_runtime_config(&config).map_err(CliError::Launch)?;
 
     // 返回
     Ok((runtime, config))
 }
 
+/// 在CIN目录中查找仅含文件名的启动命令
+/// * 🎯配置中只写`ONA.exe`：无论从哪个目录启动CLI都能找到CIN
+///   * 📌查找顺序见[`executables_roots`](babel_nar::process_io::executables_roots)，最后查找配置文件所在目录
+/// * 🚩含路径分隔符、在工作目录下已存在⇒不查找
+/// * 🚩各目录中均不存在⇒保持原样：交由`PATH`查找（如`java`、`python`）
+pub fn resolve_cmd_in_executables(command: &mut LaunchConfigCommand, config_dir: &Path) {
+    if_return! { Path::new(&command.cmd).components().count() != 1 }
+    let local = match &command.current_dir {
+        Some(dir) => dir.join(&command.cmd),
+        None => PathBuf::from(&command.cmd),
+    };
+    if_return! { local.exists() }
+    let config_dir = Some(config_dir).filter(|dir| !dir.as_os_str().is_empty());
+    if let Some(resolved) = resolve_executable(&command.cmd, config_dir) {
+        println_cli!([Info] "在CIN目录{:?}中找到启动命令「{}」", resolved.root, command.cmd);
+        command.cmd = resolved.path.to_string_lossy().into_owned();
+    }
+}
+
+/// 检查「运行时配置」并打印所有问题
+/// * 🚩须在切换工作目录之前调用：配置中的相对路径基于当前工作目录
+/// * ⚙️存在「错误」级别的问题⇒返回错误
+pub fn validate_config(config: &RuntimeConfig) -> Result<()> {
+    let known_translators = preset_names().collect::<Vec<_>>();
+    let issues = config.validate(&known_translators);
+    for issue in issues.iter() {
+        match issue.is_error() {
+            true => eprintln_cli!([Error] "配置错误：{issue}"),
+            false => println_cli!([Warn] "配置警告：{issue}"),
+        }
+    }
+    match issues.iter().filter(|issue| issue.is_error()).count() {
+        0 => Ok(()),
+        n => Err(anyhow!("配置检查未通过：共{n}个错误")),
+    }
+}
+
 /// 根据「运行时启动参数」启动虚拟机
 /// * 🚩生成、配置、启动虚拟机
 /// * 🎯在「初次启动」与「二次重启」中共用代码
-pub fn launch_by_runtime_config(config: &RuntimeConfig) -> Result<impl VmRuntime> {
+/// * 🚩有连接参数⇒连接到已在运行的CIN；否则⇒启动子进程
+pub fn launch_by_runtime_config(config: &RuntimeConfig) -> Result<LaunchedRuntime> {
+    // 连接到已在运行的CIN
+    if let Some(connect) = &config.connect {
+        return launch_websocket_vm(connect, config).map(LaunchedRuntime::Websocket);
+    }
+
     // 生成虚拟机
     let config_command = &config.command;
-    let mut vm = load_command_vm(config_command)?;
+    let mut vm = match &config.python_env {
+        // * 🚩有Python环境⇒替换解释器，并在启动前检查模块
+        Some(python_env) => load_python_command(config_command, python_env)?.into(),
+        None => load_command_vm(config_command)?,
+    };
 
     // 配置虚拟机
     // * 🚩【2024-04-04 03:17:43】现在「转译器」成了必选项，所以必定会有配置
     config_launcher_translators(&mut vm, &config.translators)?;
 
+    // 合并多行输出 | 需显式启用；续行判断由输出转译器决定
+    let continuation = get_line_continuation_by_translators(&config.translators);
+    if let Some(continuation) = continuation.filter(|_| config.coalesce_lines) {
+        vm.coalesce_lines(continuation, DEFAULT_COALESCE_TIMEOUT);
+    }
+
+    // 启动检查 | 启动即失败⇒报错并附带CIN的输出；就绪标志由输出转译器决定
+    let ready_marker = get_ready_marker_by_translators(&config.translators);
+    vm.startup_check(Duration::from_millis(config.startup_timeout), ready_marker);
+
+    // CYC分批
+    if let Some(pacing) = config.cyc_pacing {
+        vm.cyc_pacing(CycPacing {
+            chunk: pacing.chunk,
+            interval: Duration::from_millis(pacing.interval_ms),
+        });
+    }
+
+    // 子进程IO的备用编码
+    if let Some(label) = &config.process_encoding {
+        vm.encoding(ProcessEncoding::from_label(label)?);
+    }
+
     // 启动虚拟机
     let runtime = vm.launch()?;
-    Ok(runtime)
+    Ok(LaunchedRuntime::Command(runtime))
+}
+
+/// 从「连接参数」连接到已在运行的CIN
+/// * 🚩使用配置中的转译器；「自动重启」⇒连接断开时自动重连
+pub fn launch_websocket_vm(
+    connect: &LaunchConfigConnect,
+    config: &RuntimeConfig,
+) -> Result<WebsocketVmRuntime> {
+    let mut vm = WebsocketVm::new(to_address(&connect.host, connect.port));
+    vm.translators(get_translator_by_name(&config.translators)?);
+    vm.auto_reconnect(config.auto_restart);
+    vm.launch()
+}
+
+/// CLI所启动的运行时
+/// * 🎯统一「启动子进程」与「连接到已在运行的CIN」：运行时管理者只需一种运行时类型
+/// * 📌各变体大小差异较大，但每个CLI实例只构造一次，无需装箱
+#[allow(clippy::large_enum_variant)]
+pub enum LaunchedRuntime {
+    /// 命令行运行时 | 启动子进程
+    Command(CommandVmRuntime),
+    /// Websocket运行时 | 连接到已在运行的CIN
+    Websocket(WebsocketVmRuntime),
+}
+
+/// 工具宏/分派到内部的运行时
+macro_rules! delegate {
+    ($self:ident => $runtime:ident => $e:expr) => {
+        match $self {
+            LaunchedRuntime::Command($runtime) => $e,
+            LaunchedRuntime::Websocket($runtime) => $e,
+        }
+    };
+}
+
+impl VmRuntime for LaunchedRuntime {
+    fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        delegate!(self => runtime => runtime.input_cmd(cmd))
+    }
+
+    fn fetch_output(&mut self) -> Result<Output> {
+        delegate!(self => runtime => runtime.fetch_output())
+    }
+
+    fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        delegate!(self => runtime => runtime.try_fetch_output())
+    }
+
+    fn status(&self) -> &VmStatus {
+        delegate!(self => runtime => runtime.status())
+    }
+
+    fn terminate(&mut self) -> Result<()> {
+        delegate!(self => runtime => runtime.terminate())
+    }
+}
+
+impl VmRuntimeBoost for LaunchedRuntime {
+    fn drain_outputs(&mut self) -> Result<Vec<Output>> {
+        delegate!(self => runtime => runtime.drain_outputs())
+    }
+
+    fn drain_outputs_timed(&mut self) -> Result<Vec<(Output, Instant)>> {
+        delegate!(self => runtime => runtime.drain_outputs_timed())
+    }
+
+    fn input_raw(&mut self, line: &str) -> Result<()> {
+        delegate!(self => runtime => runtime.input_raw(line))
+    }
+
+    fn cyc_ack_count(&self, steps: usize) -> usize {
+        delegate!(self => runtime => runtime.cyc_ack_count(steps))
+    }
+
+    fn flush_inputs(&mut self) -> Result<()> {
+        delegate!(self => runtime => runtime.flush_inputs())
+    }
+
+    fn kill_handle(&self) -> Option<KillHandle> {
+        delegate!(self => runtime => runtime.kill_handle())
+    }
 }
 
 /// 从「启动参数/启动命令」启动「命令行虚拟机」
 /// * ❓需要用到「具体启动器实现」吗
 pub fn load_command_vm(config: &LaunchConfigCommand) -> Result<CommandVm> {
     // 构造指令
-    let command = generate_command(
+    let command = load_command(config)?;
+    // 构造虚拟机
+    let vm = command.into();
+    // 返回
+    Ok(vm)
+}
+
+/// 从「启动参数/启动命令」构造[`Command`]
+/// * 🚩以Java启动⇒先检查Java版本：Java缺失或版本过低⇒及早报错
+///   * 📌所需的最低版本：[`OPENNARS_MIN_JAVA_VERSION`]
+/// * 🚩命令的构造：见[`generate_launch_command`]
+pub fn load_command(config: &LaunchConfigCommand) -> Result<Command> {
+    if let Some(java) = java_program(config) {
+        check_java_version(java, OPENNARS_MIN_JAVA_VERSION)?;
+    }
+    Ok(generate_launch_command(config))
+}
+
+/// 启动命令所用的Java可执行文件
+/// * 🚩启动命令为jar文件⇒`java`
+/// * 🚩启动命令为Java本身，且命令参数含`-jar`⇒启动命令
+///   * 📄`{ cmd: "java", cmdArgs: ["-Xmx1024m", "-jar", "nars.jar"] }`
+///   * 📌此时命令参数原样传入：JVM参数本就位于`-jar`之前
+/// * 🚩否则⇒[`None`]：不以Java启动
+fn java_program(config: &LaunchConfigCommand) -> Option<&str> {
+    if_return! { is_jar_path(&config.cmd) => Some(COMMAND_JAVA) }
+    let has_jar_arg = config.cmd_args.iter().flatten().any(|arg| arg == "-jar");
+    (is_java_program(&config.cmd) && has_jar_arg).then_some(config.cmd.as_str())
+}
+
+/// 从「启动参数/启动命令」生成[`Command`]
+/// * 🎯不检查Java版本，仅构造命令：可直接检查程序与参数
+/// * 🚩启动命令为jar文件⇒以Java启动，命令参数作为JVM参数置于`-jar`之前
+///   * 📄`{ cmd: "nars.jar", cmdArgs: ["-Xmx1024m"] }`⇒`java -Xmx1024m -jar nars.jar`
+/// * 🚩附加的环境变量：两种情况均设置
+pub fn generate_launch_command(config: &LaunchConfigCommand) -> Command {
+    // jar文件⇒以Java启动
+    if is_jar_path(&config.cmd) {
+        let mut command = manipulate!(
+            CommandGeneratorJava::new(&config.cmd)
+            => .jvm_args(config.cmd_args.iter().flatten())
+        )
+        .generate_command();
+        command.envs(config.env.iter().flatten());
+        if let Some(dir) = absolute_current_dir(config) {
+            command.current_dir(dir);
+        }
+        return command;
+    }
+    generate_command(
         &config.cmd,
-        // ! 🚩【2024-04-07 12:35:41】不能再设置工作目录：已在[`launch_by_config`]处设置
+        // ! 🚩【2024-04-07 12:35:41】相对的工作目录不能再设置：已在[`launch_by_config`]处切换
         // * 否则会导致「目录名称无效」
-        // config.current_dir.as_ref(),
-        None::<&str>,
+        absolute_current_dir(config),
         // 🚩获取其内部数组的引用，或使用一个空数组作迭代器（无法简化成[`unwrap_or`]）
         match &config.cmd_args {
             Some(v) => v.iter(),
             // ↓此处`unwrap_or_default`默认使用一个空数组作为迭代器
             None => [].iter(),
         },
-    );
-    // 构造虚拟机
-    let vm = command.into();
-    // 返回
-    Ok(vm)
+        config.env.iter().flatten(),
+    )
+}
+
+/// 启动命令中的绝对工作目录
+/// * 🎯不切换CLI自身的工作目录，也能在CIN目录中启动：如跨CIN测试中并行启动多个CIN
+/// * 🚩相对路径⇒[`None`]：由[`launch_by_config`]切换工作目录
+fn absolute_current_dir(config: &LaunchConfigCommand) -> Option<&Path> {
+    config
+        .current_dir
+        .as_deref()
+        .filter(|dir| dir.is_absolute())
+}
+
+/// 从「启动参数/启动命令」与「Python环境」构造[`Command`]
+/// * 🚩解释器：解释器路径 > 虚拟环境中的解释器 > 启动命令
+/// * 🚩附加的路径⇒`PYTHONPATH`
+/// * 🚩附加的环境变量：启动与检查模块时均设置
+/// * 🚩命令参数含`-m 模块`⇒启动前检查解释器能否导入模块
+///   * 🎯解释器缺失、环境中未安装CIN⇒及早给出可操作的错误
+pub fn load_python_command(
+    config: &LaunchConfigCommand,
+    python_env: &LaunchConfigPythonEnv,
+) -> Result<Command> {
+    let python = match (&python_env.python_path, &python_env.venv) {
+        (Some(python_path), _) => python_path.clone(),
+        (None, Some(venv)) => venv_python(venv),
+        (None, None) => PathBuf::from(&config.cmd),
+    };
+    let python_path = python_path_env(python_env.extra_paths.as_deref().unwrap_or_default())?;
+    // 仅设置解释器与环境的命令
+    let python_command = || {
+        let mut command = Command::new(&python);
+        if let Some(python_path) = &python_path {
+            command.env("PYTHONPATH", python_path);
+        }
+        command.envs(config.env.iter().flatten());
+        if let Some(dir) = absolute_current_dir(config) {
+            command.current_dir(dir);
+        }
+        command
+    };
+    // 检查模块
+    let args = config.cmd_args.as_deref().unwrap_or_default();
+    if let Some(module) = args.windows(2).find(|w| w[0] == "-m").map(|w| &w[1]) {
+        check_python_import(python_command(), module)?;
+    }
+    // 构造命令
+    let mut command = python_command();
+    command.args(args);
+    Ok(command)
 }
 
+/// 外部程序转译器中，另一方向默认使用的转译器预设
+pub const DEFAULT_EXEC_PRESET: &str = "Native";
+
 /// 从「启动参数/输入输出转译器」配置「命令行虚拟机」
 /// * 🚩【2024-04-02 01:03:54】此处暂时需要**硬编码**现有的CIN实现
 /// * 🏗️后续可能支持定义自定义转译器（long-term）
@@ -176,15 +483,12 @@ pub fn config_launcher_translators(
 
 /// 从「转译器名」检索「输入输出转译器」
 /// * 🚩继续分派到「输入转译器检索」与「输出转译器检索」
+/// * 🚩外部程序转译器⇒启动外部程序，另一方向使用预设（默认原生）
 pub fn get_translator_by_name(config: &LaunchConfigTranslators) -> Result<IoTranslators> {
-    let name_i = match config {
-        LaunchConfigTranslators::Same(input) | LaunchConfigTranslators::Separated { input, .. } => {
-            input
-        }
-    };
-    let name_o = match config {
-        LaunchConfigTranslators::Same(output)
-        | LaunchConfigTranslators::Separated { output, .. } => output,
+    let (name_i, name_o) = match config {
+        LaunchConfigTranslators::Same(name) => (name, name),
+        LaunchConfigTranslators::Separated { input, output } => (input, output),
+        LaunchConfigTranslators::Exec(exec) => return get_exec_translators(exec),
     };
     Ok(IoTranslators {
         input_translator: get_input_translator_by_name(name_i.as_str())?,
@@ -192,74 +496,145 @@ pub fn get_translator_by_name(config: &LaunchConfigTranslators) -> Result<IoTran
     })
 }
 
-/// 输入转译器的索引字典类型
-/// * 📌结构：`[(转译器名, 输入转译器, 输出转译器)]`
-pub type TranslatorDict<'a> = &'a [(
-    &'a str,
-    fn(Cmd) -> Result<String>,
-    fn(String) -> Result<Output>,
-)];
-
-/// 输入转译器的索引字典
-/// * 🚩静态存储映射，后续遍历可有序可无序
-pub const TRANSLATOR_DICT: TranslatorDict = &[
-    ("Native", native::input_translate, native::output_translate),
-    (
-        "OpenNARS",
-        opennars::input_translate,
-        opennars::output_translate,
-    ),
-    ("ONA", ona::input_translate, ona::output_translate),
-    (
-        "NARS-Python",
-        nars_python::input_translate,
-        nars_python::output_translate,
-    ),
-    (
-        "NARSPython",
-        nars_python::input_translate,
-        nars_python::output_translate,
-    ),
-    ("PyNARS", pynars::input_translate, pynars::output_translate),
-    (
-        "OpenJunars",
-        openjunars::input_translate,
-        openjunars::output_translate,
-    ),
-    (
-        "CXinJS",
-        cxin_js::input_translate,
-        cxin_js::output_translate,
-    ),
-];
+/// 从「外部程序转译器」配置「输入输出转译器」
+/// * 📌外部程序的相对路径：此时已切换到CIN的工作目录
+fn get_exec_translators(config: &LaunchConfigExecTranslator) -> Result<IoTranslators> {
+    let preset = config.preset.as_deref().unwrap_or(DEFAULT_EXEC_PRESET);
+    let args = config.exec_args.as_deref().unwrap_or_default();
+    let translator = ExecTranslator::launch(&config.exec, args)?;
+    Ok(match config.direction {
+        TranslatorDirection::In => IoTranslators {
+            input_translator: translator.into_input_translator(),
+            output_translator: get_output_translator_by_name(preset)?,
+        },
+        TranslatorDirection::Out => IoTranslators {
+            input_translator: get_input_translator_by_name(preset)?,
+            output_translator: translator.into_output_translator(),
+        },
+    })
+}
+
+/// 将配置中的转译器名解析为已知的名称
+/// * 🚩先按[`find_translator_preset`]精确查找：不区分大小写，含别名
+/// * 🚩再按「匹配度」模糊查找：📄"OpenNARS 3.0.4"⇒`OpenNARS`
+/// * 🚩均未找到⇒原样返回
+fn resolve_translator_name(cin_name: &str) -> &str {
+    if_return! { find_translator_preset(cin_name).is_some() => cin_name }
+    best_name_match(cin_name, preset_names()).unwrap_or(cin_name)
+}
+
+/// 根据名字查找转译器预设
+/// * 🚩按[`resolve_translator_name`]解析名称：精确查找优先，再模糊查找
+/// * 🚩未找到⇒报错，并附上「名称建议」与所有可用的转译器
+fn get_translator_preset_by_name(cin_name: &str) -> Result<&'static TranslatorPreset> {
+    find_translator_preset(resolve_translator_name(cin_name)).ok_or_else(|| {
+        let available = available_presets().join(", ");
+        match suggest_names(cin_name, preset_names()).first() {
+            Some((suggestion, _)) => {
+                anyhow!("未找到转译器「{cin_name}」，是否指「{suggestion}」？（可用：{available}）")
+            }
+            None => anyhow!("未找到转译器「{cin_name}」（可用：{available}）"),
+        }
+    })
+}
 
 /// 根据名字查找「输入转译器」
 pub fn get_input_translator_by_name(cin_name: &str) -> Result<Box<InputTranslator>> {
-    // 根据「匹配度」的最大值选取
-    let translator = TRANSLATOR_DICT
-        .iter()
-        .max_by_key(|(name, _, _)| name_match(name, cin_name))
-        .ok_or_else(|| anyhow!("未找到输入转译器"))?
-        .1; // 输入转译器
+    let translator = get_translator_preset_by_name(cin_name)?.2; // 输入转译器
     Ok(Box::new(translator))
 }
 
 /// 根据名字查找「输出转译器」
 pub fn get_output_translator_by_name(cin_name: &str) -> Result<Box<OutputTranslator>> {
-    // 根据「匹配度」的最大值选取
-    let translator = TRANSLATOR_DICT
-        .iter()
-        .max_by_key(|(name, _, _)| name_match(name, cin_name))
-        .ok_or_else(|| anyhow!("未找到输出转译器"))?
-        .2; // 输出转译器
+    let translator = get_translator_preset_by_name(cin_name)?.3; // 输出转译器
     Ok(Box::new(translator))
 }
 
+/// 根据转译器配置获取「所支持的功能」
+/// * 🚩输入输出转译器不同⇒取两者的交集
+/// * 🚩外部程序转译器⇒取其另一方向的预设
+/// * 📌未知的转译器名⇒视作支持所有功能：已在启动前检查中报错
+pub fn get_capabilities_by_translators(config: &LaunchConfigTranslators) -> Capabilities {
+    let capabilities =
+        |name: &str| preset_capabilities(resolve_translator_name(name)).unwrap_or_default();
+    match config {
+        LaunchConfigTranslators::Same(name) => capabilities(name),
+        LaunchConfigTranslators::Separated { input, output } => {
+            capabilities(input).intersect(capabilities(output))
+        }
+        LaunchConfigTranslators::Exec(exec) => {
+            capabilities(exec.preset.as_deref().unwrap_or(DEFAULT_EXEC_PRESET))
+        }
+    }
+}
+
+/// 根据转译器配置获取「输出的续行判断」
+/// * 🚩只看输出转译器：多行输出的合并发生在输出转译之前
+/// * 🚩外部程序负责输出⇒不合并
+pub fn get_line_continuation_by_translators(
+    config: &LaunchConfigTranslators,
+) -> Option<fn(&str, &str) -> bool> {
+    match config {
+        LaunchConfigTranslators::Same(output)
+        | LaunchConfigTranslators::Separated { output, .. } => {
+            preset_line_continuation(resolve_translator_name(output))
+        }
+        LaunchConfigTranslators::Exec(exec) => match exec.direction {
+            TranslatorDirection::In => preset_line_continuation(resolve_translator_name(
+                exec.preset.as_deref().unwrap_or(DEFAULT_EXEC_PRESET),
+            )),
+            TranslatorDirection::Out => None,
+        },
+    }
+}
+
+/// 根据转译器配置获取「启动时的就绪标志」
+/// * 🚩只看输出转译器：就绪标志是CIN的原始输出
+/// * 🚩外部程序负责输出⇒任意一行输出即视作就绪
+pub fn get_ready_marker_by_translators(
+    config: &LaunchConfigTranslators,
+) -> Option<fn(&str) -> bool> {
+    match config {
+        LaunchConfigTranslators::Same(output)
+        | LaunchConfigTranslators::Separated { output, .. } => {
+            preset_ready_marker(resolve_translator_name(output))
+        }
+        LaunchConfigTranslators::Exec(exec) => match exec.direction {
+            TranslatorDirection::In => preset_ready_marker(resolve_translator_name(
+                exec.preset.as_deref().unwrap_or(DEFAULT_EXEC_PRESET),
+            )),
+            TranslatorDirection::Out => None,
+        },
+    }
+}
+
+/// 根据转译器配置获取「步进完成的回执判断」
+/// * 🎯启动配置中的`cycAwait`：[`CycAwait::new`](babel_nar::test_tools::CycAwait::new)
+/// * 🚩只看输出转译器：回执是CIN的输出
+/// * 🚩外部程序负责输出⇒无回执
+pub fn get_cyc_ack_matcher_by_translators(
+    config: &LaunchConfigTranslators,
+) -> Option<fn(&Output) -> bool> {
+    match config {
+        LaunchConfigTranslators::Same(output)
+        | LaunchConfigTranslators::Separated { output, .. } => {
+            preset_cyc_ack_matcher(resolve_translator_name(output))
+        }
+        LaunchConfigTranslators::Exec(exec) => match exec.direction {
+            TranslatorDirection::In => preset_cyc_ack_matcher(resolve_translator_name(
+                exec.preset.as_deref().unwrap_or(DEFAULT_EXEC_PRESET),
+            )),
+            TranslatorDirection::Out => None,
+        },
+    }
+}
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
     use super::*;
     use nar_dev_utils::{asserts, f_parallel};
+    use std::ffi::OsStr;
 
     /// 测试「根据名字查找转译器」
     /// * 🚩仅能测试「是否查找成功」，无法具体地比较函数是否相同
@@ -274,7 +649,207 @@ mod tests {
         }
         f_parallel![
             t;
-            "opennars"; "ona"; "nars-python"; "narsPython"; "pynars"; "openjunars"; "cxinJS"
+            "opennars"; "ona"; "nars-python"; "narsPython"; "pynars"; "openjunars"; "cxinJS"; "cxin_js"; "native"; "OpenNARS 3.0.4"
         ];
     }
+
+    /// 测试「从启动命令构造命令」
+    /// * 🎯jar文件⇒以Java启动，命令参数作为JVM参数置于`-jar`之前
+    #[test]
+    fn load_command_jar() {
+        let config = |cmd: &str, args: &[&str]| LaunchConfigCommand {
+            cmd: cmd.into(),
+            cmd_args: Some(args.iter().map(|s| s.to_string()).collect()),
+            current_dir: None,
+            env: None,
+        };
+        let command = generate_launch_command(&config("nars.jar", &["-Xmx1024m"]));
+        assert_eq!(command.get_program(), "java");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["-Xmx1024m", "-jar", "nars.jar"]
+        );
+        // 非jar文件⇒原样
+        let command = generate_launch_command(&config("java", &["-jar", "nars.jar"]));
+        assert_eq!(command.get_program(), "java");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["-jar", "nars.jar"]);
+    }
+
+    /// 测试「从启动命令构造命令」：以Java启动⇒检查Java版本
+    /// * 🎯jar文件与`java -jar`两种形式均检查
+    #[test]
+    fn load_command_java_check() {
+        let config = |cmd: &str, args: &[&str]| LaunchConfigCommand {
+            cmd: cmd.into(),
+            cmd_args: Some(args.iter().map(|s| s.to_string()).collect()),
+            ..Default::default()
+        };
+        asserts! {
+            java_program(&config("nars.jar", &[])) => Some(COMMAND_JAVA)
+            java_program(&config("/opt/jdk/bin/java", &["-jar", "nars.jar"])) => Some("/opt/jdk/bin/java")
+            java_program(&config("java", &["-version"])) => None
+            java_program(&config("./ONA.exe", &["shell"])) => None
+        }
+        let e =
+            load_command(&config("./babelnar-not-exist/java", &["-jar", "nars.jar"])).unwrap_err();
+        assert!(e.to_string().contains("找不到Java"), "{e}");
+    }
+
+    /// 测试「从启动命令构造命令」：附加的环境变量
+    /// * 🎯jar文件与其它命令均设置
+    #[test]
+    fn load_command_env() {
+        for cmd in ["nars.jar", "./ONA.exe"] {
+            let config = LaunchConfigCommand {
+                cmd: cmd.into(),
+                env: Some([("JAVA_TOOL_OPTIONS".into(), "-Dfile.encoding=UTF-8".into())].into()),
+                ..Default::default()
+            };
+            let command = generate_launch_command(&config);
+            assert_eq!(
+                command.get_envs().collect::<Vec<_>>(),
+                [(
+                    OsStr::new("JAVA_TOOL_OPTIONS"),
+                    Some(OsStr::new("-Dfile.encoding=UTF-8"))
+                )],
+                "{cmd}"
+            );
+        }
+    }
+
+    /// 测试「从Python环境构造命令」
+    /// * 🎯解释器优先级：解释器路径 > 虚拟环境 > 启动命令
+    #[test]
+    fn load_python_command_env() -> Result<()> {
+        let config = LaunchConfigCommand {
+            cmd: "python".into(),
+            cmd_args: Some(vec!["script.py".into()]),
+            current_dir: None,
+            env: None,
+        };
+        let python_env = LaunchConfigPythonEnv {
+            venv: Some("/opt/venvs/nars".into()),
+            extra_paths: Some(vec!["/opt/PyNARS".into()]),
+            ..Default::default()
+        };
+        let command = load_python_command(&config, &python_env)?;
+        assert_eq!(command.get_program(), venv_python("/opt/venvs/nars"));
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["script.py"]);
+        assert!(command.get_envs().any(|(k, _)| k == "PYTHONPATH"));
+
+        let python_env = LaunchConfigPythonEnv {
+            python_path: Some("/usr/bin/python3".into()),
+            ..python_env
+        };
+        let command = load_python_command(&config, &python_env)?;
+        assert_eq!(command.get_program(), "/usr/bin/python3");
+        Ok(())
+    }
+
+    /// 测试「Python解释器不存在」
+    /// * 🎯含`-m 模块`⇒启动前即报错
+    #[test]
+    fn load_python_command_not_found() {
+        let config = LaunchConfigCommand {
+            cmd: "python".into(),
+            cmd_args: Some(vec!["-m".into(), "pynars.Console".into()]),
+            current_dir: None,
+            env: None,
+        };
+        let python_env = LaunchConfigPythonEnv {
+            python_path: Some("./babelnar-not-exist-python".into()),
+            ..Default::default()
+        };
+        let e = load_python_command(&config, &python_env).unwrap_err();
+        assert!(e.to_string().contains("找不到Python解释器"), "{e}");
+    }
+
+    /// 测试「在CIN目录中查找启动命令」
+    /// * 🚩仅含文件名、且工作目录下不存在⇒查找；找不到⇒保持原样
+    #[test]
+    fn resolve_cmd() {
+        let config_dir = std::env::temp_dir().join(format!("babelnar_cmd_{}", std::process::id()));
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(config_dir.join("babelnar-test-cin.exe"), "").unwrap();
+        let resolve = |cmd: &str| {
+            let mut command = LaunchConfigCommand {
+                cmd: cmd.into(),
+                cmd_args: None,
+                current_dir: None,
+                env: None,
+            };
+            resolve_cmd_in_executables(&mut command, &config_dir);
+            command.cmd
+        };
+        let expected = config_dir.join("babelnar-test-cin.exe");
+        asserts! {
+            resolve("babelnar-test-cin.exe") => expected.to_string_lossy()
+            resolve("./babelnar-test-cin.exe") => "./babelnar-test-cin.exe"
+            resolve("babelnar-not-exist") => "babelnar-not-exist"
+            resolve("Cargo.toml") => "Cargo.toml"
+        }
+        std::fs::remove_dir_all(&config_dir).unwrap();
+    }
+
+    /// 测试「根据转译器配置获取续行判断」
+    /// * 🚩只看输出转译器
+    #[test]
+    fn get_line_continuation() {
+        let separated = |input: &str, output: &str| LaunchConfigTranslators::Separated {
+            input: input.into(),
+            output: output.into(),
+        };
+        let stack_line = "\tat org.opennars.main.Nar.cycle(Nar.java:42)";
+        asserts! {
+            get_line_continuation_by_translators(&LaunchConfigTranslators::Same("OpenNARS".into()))
+                .is_some_and(|continues| continues("", stack_line))
+            get_line_continuation_by_translators(&separated("ona", "opennars")).is_some()
+            get_line_continuation_by_translators(&separated("opennars", "pynars")).is_none()
+        }
+    }
+
+    /// 测试「根据转译器配置获取就绪标志」
+    /// * 🚩只看输出转译器
+    #[test]
+    fn get_ready_marker() {
+        let separated = |input: &str, output: &str| LaunchConfigTranslators::Separated {
+            input: input.into(),
+            output: output.into(),
+        };
+        let config_line = "Got relative path for loading the config: ./config/defaultConfig.xml";
+        asserts! {
+            get_ready_marker_by_translators(&LaunchConfigTranslators::Same("OpenNARS".into()))
+                .is_some_and(|is_ready| is_ready(config_line) && !is_ready("<A --> B>."))
+            get_ready_marker_by_translators(&separated("ona", "opennars")).is_some()
+            get_ready_marker_by_translators(&separated("opennars", "ona")).is_none()
+        }
+    }
+
+    /// 测试「查找失败时给出建议」
+    #[test]
+    fn get_translator_suggestion() {
+        let e = get_input_translator_by_name("onna")
+            .err()
+            .expect("不应找到转译器");
+        assert!(e.to_string().contains("「ONA」"), "{e}");
+    }
+
+    /// 测试「外部程序转译器」
+    /// * 🚩程序不存在⇒报错；另一方向的预设不存在⇒报错
+    #[test]
+    #[cfg(unix)]
+    fn get_exec_translator() {
+        let config = |exec: &str, preset: Option<&str>| {
+            LaunchConfigTranslators::Exec(LaunchConfigExecTranslator {
+                exec: exec.into(),
+                exec_args: None,
+                direction: TranslatorDirection::Out,
+                preset: preset.map(str::to_owned),
+            })
+        };
+        assert!(super::get_translator_by_name(&config("cat", None)).is_ok());
+        assert!(super::get_translator_by_name(&config("cat", Some("ona"))).is_ok());
+        assert!(super::get_translator_by_name(&config("cat", Some("onna"))).is_err());
+        assert!(super::get_translator_by_name(&config("./babelnar-not-exist", None)).is_err());
+    }
 }
@@ -0,0 +1,116 @@
+//! 跨CIN测试
+//! * 🎯`--cross`：在`-c`指定的每个CIN上并行运行同一个NAL文件，输出Markdown对比表格
+//! * 🚩核心逻辑位于[`babel_nar::test_tools::cross_test`]，此处只负责「从配置启动」
+
+use crate::{
+    get_capabilities_by_translators, launch_by_runtime_config, read_config_extern,
+    resolve_cmd_in_executables, validate_config, CliArgs, RuntimeConfig,
+};
+use anyhow::{anyhow, Result};
+use babel_nar::{
+    println_cli,
+    test_tools::cross_test::{cross_test, CrossTestTarget, DEFAULT_TIMEOUT},
+};
+use std::path::Path;
+
+/// 运行跨CIN测试
+/// * 🚩`-c`指定的每个配置文件各自对应一个CIN：不合并、不加载默认配置
+/// * 🚩Markdown表格打印到标准输出
+/// * ⚙️有任一预期未通过⇒返回错误
+pub fn run_cross_test(args: &CliArgs, nal_path: &Path) -> Result<()> {
+    if args.config.is_empty() {
+        return Err(anyhow!("跨CIN测试需要以`-c`指定至少一个CIN配置"));
+    }
+    // 规范化NAL文件路径 | 🎯`''include:`等相对路径以NAL文件所在目录为根
+    let nal_path = nal_path.canonicalize()?;
+    let nal = std::fs::read_to_string(&nal_path)?;
+    let nal_root_path = nal_path.parent().unwrap_or(&nal_path);
+
+    // 逐个读取配置，在各CIN的线程中并行启动 | 启动失败的CIN仍参与对比，并在表格中标出
+    let targets = args
+        .config
+        .iter()
+        .map(|config_path| {
+            let name = config_path
+                .file_stem()
+                .map_or_else(
+                    || config_path.to_string_lossy(),
+                    |stem| stem.to_string_lossy(),
+                )
+                .to_string();
+            let config = load_runtime_config(config_path);
+            let (set_ack, capabilities) = config
+                .as_ref()
+                .map(|config| (config.set_ack.clone(), config.capabilities))
+                .unwrap_or_default();
+            let launcher_name = name.clone();
+            CrossTestTarget {
+                name,
+                launcher: move || {
+                    println_cli!([Info] "正在启动CIN「{launcher_name}」……");
+                    launch_by_runtime_config(&config?)
+                },
+                set_ack,
+                capabilities,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // 并行运行，打印结果
+    let report = cross_test(targets, &nal, nal_root_path, DEFAULT_TIMEOUT);
+    println!("{}", report.to_markdown());
+    match report.is_all_passed() {
+        true => Ok(()),
+        false => Err(anyhow!("跨CIN测试未全部通过")),
+    }
+}
+
+/// 从单个配置文件读取「运行时配置」
+/// * 🎯多个CIN并行启动：不能切换CLI自身的工作目录
+/// * 🚩工作目录、启动命令均转为绝对路径：启动时直接在CIN目录中启动
+fn load_runtime_config(config_path: &Path) -> Result<RuntimeConfig> {
+    let mut config: RuntimeConfig = read_config_extern(config_path)?.try_into()?;
+    config.capabilities = get_capabilities_by_translators(&config.translators);
+    resolve_cmd_in_executables(&mut config.command, &config.config_path);
+    validate_config(&config)?;
+    if let Some(dir) = &mut config.command.current_dir {
+        *dir = std::path::absolute(&*dir)?;
+        let cmd = dir.join(&config.command.cmd);
+        if Path::new(&config.command.cmd).is_relative() && cmd.exists() {
+            config.command.cmd = cmd.to_string_lossy().into_owned();
+        }
+    }
+    Ok(config)
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// 测试/工作目录、启动命令转为绝对路径
+    /// * 🎯并行启动时不依赖CLI自身的工作目录
+    #[test]
+    fn test_load_runtime_config() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("babelnar_cross_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("cin.sh"), "")?;
+        let config_path = dir.join("cin.hjson");
+        fs::write(
+            &config_path,
+            r#"{
+                translators: native
+                command: { cmd: "./cin.sh", currentDir: "." }
+            }"#,
+        )?;
+        let config = load_runtime_config(&config_path);
+        let _ = fs::remove_dir_all(&dir);
+        let config = config?;
+        let current_dir = config.command.current_dir.expect("缺少工作目录");
+        assert!(current_dir.is_absolute());
+        assert!(Path::new(&config.command.cmd).is_absolute());
+        assert!(config.command.cmd.ends_with("cin.sh"));
+        Ok(())
+    }
+}
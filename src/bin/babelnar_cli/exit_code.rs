@@ -0,0 +1,131 @@
+//! BabelNAR CLI的退出码约定
+//! * 🎯包装CLI的脚本：区分「配置有误」「CIN启动失败」「预期未通过」「CIN异常终止」
+//! * 🚩在出错处以[`CliError`]标明类别，经由[`anyhow::Error`]层层传递，最终在主入口换算为退出码
+//!   * 📌未标明类别的错误⇒[`EXIT_OTHER`]
+//! * 📌收到终止信号⇒`128+信号值`，见[`shutdown_exit_code`](babel_nar::cli_support::shutdown::shutdown_exit_code)
+
+use babel_nar::test_tools::OutputExpectationError;
+use thiserror::Error;
+
+/// 退出码/其它错误
+pub const EXIT_OTHER: i32 = 1;
+
+/// 退出码/配置错误
+/// * 📄配置文件格式错误、缺少必要的配置项、未知的转译器、找不到启动命令
+pub const EXIT_CONFIG: i32 = 2;
+
+/// 退出码/启动失败
+/// * 📄无法启动子进程、启动检查超时
+pub const EXIT_LAUNCH: i32 = 3;
+
+/// 退出码/NAL预期未通过
+/// * 📄批处理模式下有预期未通过、严格模式下的中止
+pub const EXIT_EXPECTATION: i32 = 4;
+
+/// 退出码/运行时异常终止
+/// * 📄CIN崩溃，且未启用自动重启
+pub const EXIT_RUNTIME: i32 = 5;
+
+/// 退出码的说明
+/// * 🎯附在`--help`之后
+pub const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  Success
+  1  Other errors
+  2  Configuration errors (malformed config file, missing required fields, unknown translator or command)
+  3  Launch failures (cannot spawn the CIN, startup check timed out)
+  4  NAL expectation failures (in --eval batch mode, or aborted by strictMode)
+  5  The CIN terminated abnormally (without autoRestart)
+  128+N  Terminated by signal N (130 for Ctrl-C)";
+
+/// 标明类别的CLI错误
+/// * 🚩包装原始错误：错误信息与之相同
+#[derive(Debug, Error)]
+pub enum CliError {
+    /// 配置错误
+    #[error(transparent)]
+    Config(anyhow::Error),
+
+    /// 启动失败
+    #[error(transparent)]
+    Launch(anyhow::Error),
+
+    /// NAL预期未通过
+    #[error(transparent)]
+    Expectation(anyhow::Error),
+
+    /// 运行时异常终止
+    #[error(transparent)]
+    Runtime(anyhow::Error),
+
+    /// 其它错误
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl CliError {
+    /// 严格模式下中止NAL输入的错误
+    /// * 🚩CIN中途终止⇒运行时异常终止；其它⇒预期未通过
+    pub fn nal_aborted(e: anyhow::Error) -> Self {
+        match e.downcast_ref::<OutputExpectationError>() {
+            Some(OutputExpectationError::VmTerminated(..)) => Self::Runtime(e),
+            _ => Self::Expectation(e),
+        }
+    }
+
+    /// 相应的退出码
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Config(..) => EXIT_CONFIG,
+            Self::Launch(..) => EXIT_LAUNCH,
+            Self::Expectation(..) => EXIT_EXPECTATION,
+            Self::Runtime(..) => EXIT_RUNTIME,
+            Self::Other(..) => EXIT_OTHER,
+        }
+    }
+
+    /// 原始错误
+    pub fn inner(&self) -> &anyhow::Error {
+        match self {
+            Self::Config(e)
+            | Self::Launch(e)
+            | Self::Expectation(e)
+            | Self::Runtime(e)
+            | Self::Other(e) => e,
+        }
+    }
+}
+
+/// 从[`anyhow::Error`]转换
+/// * 🚩其中包装了[`CliError`]⇒取出之，保留类别；否则⇒[`CliError::Other`]
+impl From<anyhow::Error> for CliError {
+    fn from(e: anyhow::Error) -> Self {
+        e.downcast::<Self>().unwrap_or_else(Self::Other)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    /// 测试/经由[`anyhow::Error`]传递后仍保留类别
+    #[test]
+    fn test_exit_code_through_anyhow() {
+        let wrapped = anyhow::Error::from(CliError::Launch(anyhow!("无法启动子进程")));
+        let e = CliError::from(wrapped.context("启动失败"));
+        assert_eq!(e.exit_code(), EXIT_LAUNCH);
+        assert_eq!(CliError::from(anyhow!("其它")).exit_code(), EXIT_OTHER);
+        // 严格模式下中止
+        let terminated = OutputExpectationError::VmTerminated("崩溃".into());
+        assert_eq!(
+            CliError::nal_aborted(terminated.into()).exit_code(),
+            EXIT_RUNTIME
+        );
+        assert_eq!(
+            CliError::nal_aborted(anyhow!("预期不符")).exit_code(),
+            EXIT_EXPECTATION
+        );
+    }
+}
@@ -7,17 +7,29 @@
 //! usage: BabelNAR [OPTIONS] <INPUT>
 //! ```
 
-use anyhow::Result;
-use babel_nar::{eprintln_cli, println_cli};
+use anyhow::{anyhow, Result};
+use babel_nar::{
+    cin_implements::TRANSLATOR_PRESETS,
+    cli_support::{
+        config::*,
+        io::{
+            output_print::{init_color_support, set_cli_output_to_stderr},
+            readline_iter::is_stdin_piped,
+            session::SessionRecorder,
+        },
+        shutdown::{install_shutdown_handler, is_shutdown_requested, shutdown_exit_code},
+    },
+    eprintln_cli, println_cli,
+    runtimes::VmRuntimeBoost,
+};
 use clap::Parser;
+use navm::vm::VmRuntime;
 use std::io::Result as IoResult;
 use std::thread::sleep;
 use std::time::Duration;
-use std::{env, path::PathBuf};
+use std::{env, fs::File, path::PathBuf};
 
 nar_dev_utils::mods! {
-    // 启动参数
-    use vm_config;
     // 命令行解析
     use arg_parse;
     // 配置（自动）搜索
@@ -26,47 +38,253 @@ nar_dev_utils::mods! {
     use config_launcher;
     // 运行时交互、管理
     use runtime_manage;
+    // 多实例监管
+    use supervisor;
+    // 运行统计
+    use runtime_metrics;
+    // NAL测试报告
+    use test_report;
     // Websocket服务端
     use websocket_server;
+    // 跨CIN测试
+    use cross_test;
+    // 退出码约定
+    use exit_code;
 }
 
 /// 主入口
-pub fn main() -> Result<()> {
+/// * 🚩出错⇒打印错误，并按[退出码约定](exit_code)退出
+pub fn main() {
     // 以默认参数启动
-    main_args(env::current_dir(), env::args())
+    let result = main_args(env::current_dir(), env::args());
+    // 收到终止信号⇒以相应的退出码退出 | 此时CIN已被终止
+    if let Some(code) = shutdown_exit_code() {
+        println_cli!([Info] "已收到终止信号，程序退出");
+        std::process::exit(code);
+    }
+    if let Err(e) = result {
+        eprintln!("Error: {:?}", e.inner());
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// 列出所有转译器预设
+/// * 📄`ONA`、`NARS-Python (NARSPython, nars_python)`
+/// * 🚩打印到标准输出：每行一个，便于脚本处理
+fn list_translators() {
+    for (name, aliases, ..) in TRANSLATOR_PRESETS {
+        match aliases.is_empty() {
+            true => println!("{name}"),
+            false => println!("{name} ({})", aliases.join(", ")),
+        }
+    }
+}
+
+/// 配置来源/命令行参数
+const SOURCE_ARGS: &str = "命令行参数";
+
+/// 配置来源/用户输入
+const SOURCE_USER: &str = "用户输入";
+
+/// 配置来源/自动搜索
+const SOURCE_SEARCH: &str = "自动搜索";
+
+/// 打印生效配置
+/// * 🎯`--print-config`：查看实际启动所用的配置，及各配置项的来源
+/// * 🚩转换为运行时配置后打印到标准输出
+/// * 🚩多实例⇒逐个打印各实例的配置：实例中设置的配置项，来源标注为实例
+fn print_effective_config(config: &LaunchConfig, provenance: &ConfigProvenance) -> Result<()> {
+    let configs = match config.instances.as_ref().is_some_and(|i| !i.is_empty()) {
+        true => config
+            .instance_configs()
+            .into_iter()
+            .map(|(name, instance_config)| {
+                let mut provenance = provenance.clone();
+                let source = format!("instances.{name}");
+                provenance.track_changes(config, &instance_config, &source);
+                (Some(name), instance_config, provenance)
+            })
+            .collect(),
+        false => vec![(None, config.clone(), provenance.clone())],
+    };
+    for (name, config, provenance) in configs {
+        let config = RuntimeConfig::try_from(config)
+            .map_err(|e| CliError::Config(anyhow!("无法转换为运行时配置：{e}")))?;
+        if let Some(name) = name {
+            println!("// 实例「{name}」");
+        }
+        print!("{}", config.to_annotated_hjson(&provenance)?);
+    }
+    Ok(())
 }
 
 /// 以特定参数开始命令行主程序
 /// * 🚩此处只应该有自[`env`]传入的参数
 /// * 🚩【2024-04-01 14:25:38】暂时用不到「当前工作路径」
-pub fn main_args(cwd: IoResult<PathBuf>, args: impl Iterator<Item = String>) -> Result<()> {
+/// * ⚙️出错⇒返回标明类别的错误，以决定退出码
+pub fn main_args(
+    cwd: IoResult<PathBuf>,
+    args: impl Iterator<Item = String>,
+) -> Result<(), CliError> {
+    run_args(cwd, args).map_err(CliError::from)
+}
+
+/// 以特定参数运行命令行主程序
+/// * 🚩错误类别由出错处以[`CliError`]标明，见[`main_args`]
+fn run_args(cwd: IoResult<PathBuf>, args: impl Iterator<Item = String>) -> Result<()> {
+    // 解析命令行参数
+    let args = CliArgs::parse_from(args);
+
+    // （Windows下）启用终端颜色 | 失败⇒回退到纯文本
+    #[cfg(windows)]
+    let virtual_terminal = colored::control::set_virtual_terminal(true).is_ok();
+    #[cfg(not(windows))]
+    let virtual_terminal = true;
+
+    // 决定是否着色 | ⚠️须在首次打印之前：`--no-color`、`NO_COLOR`、非终端⇒纯文本
+    let color_support = init_color_support(args.no_color || !virtual_terminal);
+    if !virtual_terminal {
+        eprintln_cli!([Warn] "无法启动终端彩色显示，将输出纯文本");
+    }
+    if !color_support.stdout && !color_support.stderr {
+        colored::control::set_override(false);
+    }
+
     // 解包当前工作目录
     let cwd = cwd
         .inspect_err(|e| println_cli!([Warn] "无法获取当前工作目录：{e}"))
         .ok();
 
-    // （Windows下）启用终端颜色
-    let _ = colored::control::set_virtual_terminal(true)
-        .inspect_err(|_| eprintln_cli!([Error] "无法启动终端彩色显示。。"));
+    // 终止信号处理 | Ctrl-C、`SIGTERM`⇒终止CIN后退出，再次收到⇒立即强制退出
+    let _ = install_shutdown_handler()
+        .inspect_err(|e| eprintln_cli!([Warn] "无法安装终止信号处理：{e}"));
 
-    // 解析命令行参数
-    let args = CliArgs::parse_from(args);
+    // 列出转译器⇒打印后退出
+    if args.list_translators {
+        list_translators();
+        return Ok(());
+    }
+
+    // 跨CIN测试⇒单独运行 | CLI信息转至标准错误，标准输出只留结果表格
+    if let Some(nal_path) = &args.cross {
+        set_cli_output_to_stderr(true);
+        return run_cross_test(&args, nal_path);
+    }
+
+    // JSON输出模式⇒CLI信息转至标准错误 | 须在「读取配置」之前，以免配置日志混入标准输出
+    if args.output_json {
+        set_cli_output_to_stderr(true);
+    }
 
     // 读取配置 | with 默认配置文件
-    let mut config = load_config(&args);
+    // * 🚩一并记录各配置项的来源：打印生效配置时标注
+    let (mut config, mut provenance) = load_config(&args);
+    let loaded = config.clone();
+
+    // 是否为「JSON输出模式」 | 命令行参数优先于配置文件
+    if args.output_json {
+        config.output_format = Some(OutputFormat::Json);
+    }
+    let output_json = config.output_format == Some(OutputFormat::Json);
+
+    // 安静模式⇒只打印错误与回答 | 命令行参数优先于配置文件
+    if args.quiet {
+        config.print_level = Some(PrintLevel::quiet());
+    }
+
+    // 禁用彩色输出 | 命令行参数优先于配置文件
+    if args.no_color {
+        config.no_color = Some(true);
+    }
+
+    provenance.track_changes(&loaded, &config, SOURCE_ARGS);
+
+    // 禁用彩色输出 | 同时影响CLI信息
+    if config.no_color == Some(true) {
+        init_color_support(true);
+        colored::control::set_override(false);
+    }
+    set_cli_output_to_stderr(output_json);
+
+    // 是否为「批处理模式」 | 运行单个NAL文件后退出
+    let batch = args.eval.is_some();
+
+    // 是否从管道读取输入 | 读完后退出，无需等待、提示
+    let piped_input = is_stdin_piped();
 
     // 是否向用户展示「详细信息」 | 用于等待、提示等
-    let user_verbose = config.user_input.is_none() || config.user_input.unwrap();
+    let user_verbose = !batch
+        && !output_json
+        && !piped_input
+        && (config.user_input.is_none() || config.user_input.unwrap());
+
+    // 多实例配置 | 各实例自行给出启动命令
+    let multi_instance = config.instances.as_ref().is_some_and(|i| !i.is_empty());
 
     // 用户填充配置项 | 需要用户输入、工作路径（🎯自动搜索）
-    polyfill_config_from_user(&mut config, cwd);
+    // * 🚩JSON输出模式、管道输入下不进行交互式提示：直接使用搜索到的配置，仍缺少的配置项将在启动时报错
+    // * 🚩多实例⇒各实例自行给出启动命令，无需填充
+    if !multi_instance {
+        let before = config.clone();
+        match output_json || piped_input {
+            true => {
+                polyfill_config_from_search(&mut config, cwd);
+                provenance.track_changes(&before, &config, SOURCE_SEARCH);
+            }
+            false => {
+                polyfill_config_from_user(&mut config, cwd);
+                provenance.track_changes(&before, &config, SOURCE_USER);
+            }
+        }
+    }
+    let before = config.clone();
+
+    // 批处理模式⇒覆盖配置 | ⚠️须在「用户填充」之后，以免被覆盖
+    if let Some(path) = &args.eval {
+        apply_eval_config(&mut config, path);
+    }
+
+    // 测试报告 | 命令行参数优先于配置文件
+    // * ⚠️须在「启动」之前绝对化：启动时会切换工作目录
+    if let Some(path) = &args.report {
+        config.report_file = Some(std::path::absolute(path)?);
+    }
+    provenance.track_changes(&before, &config, SOURCE_ARGS);
 
-    // 清屏，预备启动
+    // 打印生效配置 | 仅打印⇒打印后退出
+    let print_config = args.print_config || args.print_config_only;
+    if print_config {
+        print_effective_config(&config, &provenance)?;
+    }
+    if args.print_config_only {
+        return Ok(());
+    }
+
+    // 清屏，预备启动 | 批处理模式、JSON输出模式、管道输入下不清屏
     if user_verbose {
         println_cli!([Info] "配置加载完毕！程序将在1s后启动。。。");
         sleep(Duration::from_secs(1));
     }
-    let _ = clearscreen::clear().inspect_err(|e| eprintln_cli!([Warn] "清屏失败：{e}"));
+    if !batch && !output_json && !print_config && !piped_input {
+        let _ = clearscreen::clear().inspect_err(|e| eprintln_cli!([Warn] "清屏失败：{e}"));
+    }
+
+    // 多实例⇒逐个启动，交由监管者管理
+    if multi_instance {
+        if args.record.is_some() {
+            println_cli!([Warn] "多实例模式下不支持录制会话，已忽略`--record`");
+        }
+        return run_instances(&config, batch);
+    }
+
+    // 录制会话⇒预先创建文件 | ⚠️须在「启动」之前：启动时会切换工作目录
+    let record_file = match &args.record {
+        Some(path) => Some(
+            File::create(path)
+                .inspect_err(|e| eprintln_cli!([Error] "无法创建会话录制文件{path:?}：{e}"))?,
+        ),
+        None => None,
+    };
 
     // 从配置项启动 | 复制一个新配置，不会附带任何非基础类型开销
     let (runtime, config) = match launch_by_config(config.clone()) {
@@ -84,13 +302,43 @@ pub fn main_args(cwd: IoResult<PathBuf>, args: impl Iterator<Item = String>) ->
         }
     };
 
+    // 录制会话⇒包装运行时
+    // * ⚠️自动重启后的新运行时不再录制
+    match record_file {
+        Some(file) => run_runtime(SessionRecorder::new(runtime, file), config, batch),
+        None => run_runtime(runtime, config, batch),
+    }
+}
+
+/// 运行已启动的运行时
+/// * 🚩批处理模式⇒运行NAL文件后退出；否则⇒交互、管理
+pub fn run_runtime(
+    runtime: impl VmRuntimeBoost + Send + Sync + 'static,
+    config: RuntimeConfig,
+    batch: bool,
+) -> Result<()> {
+    // 批处理模式⇒运行NAL文件，以「所有预期是否通过」作为结果
+    if batch {
+        let mut manager = RuntimeManager::new(runtime, config);
+        let summary = manager.eval()?;
+        summary.print();
+        return match summary.is_success() {
+            true => Ok(()),
+            false => Err(CliError::Expectation(anyhow!("NAL批处理未通过")).into()),
+        };
+    }
+
     // 运行时交互、管理
     let manager = RuntimeManager::new(runtime, config.clone());
     let result = loop_manage(manager, &config);
 
-    // 启用用户输入时延时提示
-    if config.user_input {
-        println_cli!([Info] "程序将在 5 秒后自动退出。。。");
+    // 启用用户输入时延时提示 | JSON输出模式、管道输入、收到终止信号时不延时
+    if config.user_input
+        && config.output_format == OutputFormat::Text
+        && !is_stdin_piped()
+        && !is_shutdown_requested()
+    {
+        println_cli!([Info] "程序将在 3 秒后自动退出。。。");
         sleep(Duration::from_secs(3));
     }
 
@@ -98,6 +346,49 @@ pub fn main_args(cwd: IoResult<PathBuf>, args: impl Iterator<Item = String>) ->
     result
 }
 
+/// 启动并运行配置中的所有实例
+/// * 🚩逐个启动；任一实例启动失败⇒终止已启动的实例，然后中止
+///   * 📌运行时被丢弃时不会终止CIN子进程：须显式终止，以免遗留「野进程」
+/// * 🚩批处理模式⇒在各实例上运行NAL文件后退出；否则⇒由监管者交互、管理
+pub fn run_instances(config: &LaunchConfig, batch: bool) -> Result<()> {
+    let mut launched = vec![];
+    for (name, instance_config) in config.instance_configs() {
+        match launch_by_config(instance_config) {
+            Ok((runtime, instance_config)) => {
+                println_cli!([Info] "实例「{name}」已启动");
+                launched.push((name, runtime, instance_config));
+            }
+            Err(e) => {
+                eprintln_cli!([Error] "实例「{name}」启动错误：{e}");
+                for (name, mut runtime, _) in launched {
+                    if let Err(e) = runtime.terminate() {
+                        eprintln_cli!([Error] "终止实例「{name}」时发生错误：{e}");
+                    }
+                }
+                return Err(e);
+            }
+        }
+    }
+    let managers = launched
+        .into_iter()
+        .map(|(name, runtime, instance_config)| {
+            RuntimeManager::named(runtime, instance_config, name)
+        })
+        .collect();
+    let mut supervisor = Supervisor::new(managers)?;
+
+    // 批处理模式⇒以「所有实例的预期是否均通过」作为结果
+    if batch {
+        return match supervisor.eval()? {
+            true => Ok(()),
+            false => Err(CliError::Expectation(anyhow!("NAL批处理未通过")).into()),
+        };
+    }
+
+    // 运行时交互、管理
+    supervisor.manage()
+}
+
 /// 单元测试
 #[cfg(test)]
 #[allow(unused_attributes)]
@@ -113,7 +404,7 @@ mod tests {
     pub fn main(cin_config_path: &str, other_args: &[&str]) -> Result<()> {
         babel_nar::exists_or_exit!("./executables");
         // 以默认参数启动
-        main_args(
+        Ok(main_args(
             env::current_dir(),
             [
                 &["BabelNAR-cli.exe", "-d", "-c", cin_config_path],
@@ -122,7 +413,7 @@ mod tests {
             .concat()
             .into_iter()
             .map(str::to_string),
-        )
+        )?)
     }
 
     /// 测试入口/多配置加载
@@ -130,14 +421,9 @@ mod tests {
     /// * 🎯预引入NAL
     pub fn main_configs(cin_config_path: &str, other_config_paths: &[&str]) -> Result<()> {
         let args = list![
-            [
-                // 第二个文件，搭建测试环境
-                "-c",
-                config_path,
-                // 第三个文件，指示预加载
-                "-c",
-                config_path,
-            ]
+            // 后续文件：搭建测试环境、指示预加载
+            // * ⚠️每个文件仅加载一次：「预置NAL」会在合并时拼接
+            ["-c", config_path]
             for config_path in (other_config_paths)
         ]
         .concat();
@@ -430,11 +716,94 @@ mod tests {
     #[ignore = "仅作试运行用，不用于自动化测试"]
     pub fn main_websocket() -> Result<()> {
         // 以默认参数启动
-        main_args(
+        Ok(main_args(
             env::current_dir(),
             ["test.exe", "-d", "-c", ONA, "-c", WEBSOCKET]
                 .into_iter()
                 .map(str::to_string),
+        )?)
+    }
+
+    /// 测试入口/跨CIN测试
+    /// * 🎯同一NAL文件在多个CIN上的通过情况，替代上述「逐个CIN手动整理」
+    #[test]
+    #[ignore = "仅作试运行用，不用于自动化测试"]
+    pub fn main_cross() -> Result<()> {
+        babel_nar::exists_or_exit!("./executables");
+        Ok(main_args(
+            env::current_dir(),
+            [
+                "test.exe",
+                "--cross",
+                "./src/tests/nal/test_simple_deduction.nal",
+                "-c",
+                ONA,
+                "-c",
+                OPENNARS,
+                "-c",
+                PYNARS,
+            ]
+            .into_iter()
+            .map(str::to_string),
+        )?)
+    }
+
+    /// 测试/退出码
+    /// * 🚩以临时的配置文件、NAL文件制造各类错误，检查错误所对应的退出码
+    /// * 📌以`sh`作为CIN
+    #[test]
+    #[cfg_attr(not(unix), ignore = "依赖`sh`作为CIN")]
+    fn exit_codes() {
+        let dir = env::temp_dir().join(format!("babelnar_cli_exit_codes_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("无法创建临时目录");
+        // 以某配置启动，返回退出码
+        // * ⚠️启动时会切换工作目录：须使用绝对路径
+        let exit_code = |name: &str, config: &str, other_args: &[&str]| {
+            let config_path = dir.join(format!("{name}.hjson"));
+            std::fs::write(&config_path, config).expect("无法写入临时配置文件");
+            let config_path = config_path.to_str().unwrap();
+            let args = [
+                &["test.exe", "-d", "--output-json", "-c", config_path],
+                other_args,
+            ]
+            .concat();
+            let result = main_args(env::current_dir(), args.into_iter().map(str::to_string));
+            result.err().map_or(0, |e| e.exit_code())
+        };
+        // 无法执行的「CIN」
+        let not_executable = dir.join("not_executable");
+        std::fs::write(&not_executable, "").expect("无法写入临时文件");
+        // 预期无法通过的NAL
+        let nal_path = dir.join("fail.nal");
+        std::fs::write(
+            &nal_path,
+            "<A --> B>.\n''expect-contains: ANSWER <A --> B>.\n",
         )
+        .expect("无法写入临时NAL文件");
+        let nal_path = nal_path.to_str().unwrap();
+
+        // 配置文件格式错误⇒缺少启动命令
+        assert_eq!(exit_code("malformed", "{ translators: ", &[]), EXIT_CONFIG);
+        // 未知的转译器
+        let config = r#"{ translators: "onna", command: { cmd: "sh" } }"#;
+        assert_eq!(exit_code("unknown_translator", config, &[]), EXIT_CONFIG);
+        // 无法启动子进程
+        let config =
+            format!(r#"{{ translators: "native", command: {{ cmd: {not_executable:?} }} }}"#);
+        assert_eq!(exit_code("launch", &config, &[]), EXIT_LAUNCH);
+        // 批处理模式下预期未通过 | CIN无任何输出
+        let config = r#"{ translators: "native", command: { cmd: "cat" } }"#;
+        let args = ["--eval", nal_path];
+        assert_eq!(exit_code("expectation", config, &args), EXIT_EXPECTATION);
+        // CIN启动后崩溃，且未启用自动重启
+        let config = r#"{
+            translators: "native"
+            command: { cmd: "sh", cmdArgs: ["-c", "sleep 1; exit 1"] }
+            userInput: false
+            autoRestart: false
+        }"#;
+        assert_eq!(exit_code("runtime", config, &[]), EXIT_RUNTIME);
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -1,43 +1,279 @@
 //! 启动后运行时的（交互与）管理
 
 use super::websocket_server::*;
-use crate::{launch_by_runtime_config, InputMode, LaunchConfigPreludeNAL, RuntimeConfig};
+use crate::{
+    get_cyc_ack_matcher_by_translators, launch_by_runtime_config, output_to_json_tagged, CliError,
+    InputMode, LaunchConfigPreludeNAL, OutputFormat, RuntimeConfig, RuntimeMetrics, TestReport,
+};
 use anyhow::{anyhow, Result};
 use babel_nar::{
     cli_support::{
         error_handling_boost::error_anyhow,
         io::{
+            input_history::{InputHistory, DEFAULT_HISTORY_SIZE},
             navm_output_cache::{ArcMutex, OutputCache},
-            readline_iter::ReadlineIter,
+            output_print::{color_support, format_output_styled, OutputType, StyleOptions},
+            readline_iter::{is_stdin_piped, ReadlineIter},
         },
+        shutdown::is_shutdown_requested,
     },
     eprintln_cli, if_let_err_eprintln_cli, println_cli,
-    runtimes::TranslateError,
-    test_tools::{nal_format::parse, put_nal, VmOutputCache},
+    runtimes::{TranslateError, VmRuntimeBoost},
+    test_tools::{
+        nal_format::{parse_with_line_no, parse_with_source, source_line},
+        put_nal, read_included_nal, with_nal_line_no, with_nal_source, CycAwait,
+        InteractivityState, NALInput, PutNalContext, RequiresRegion, SaveOutputsOptions,
+        VmOutputCache,
+    },
 };
 use nar_dev_utils::{if_return, manipulate, pipe, ResultBoost};
-use navm::{
-    cmd::Cmd,
-    vm::{VmRuntime, VmStatus},
-};
+use navm::{cmd::Cmd, output::Output, vm::VmStatus};
 use std::{
+    any::Any,
     fmt::Debug,
     ops::ControlFlow::{self, Break, Continue},
-    path::Path,
-    sync::{Arc, Mutex},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
     thread::{self, sleep, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+/// NAL批处理结果统计
+/// * 🎯`--eval`批处理模式：统计所有预期的通过情况，并以此决定退出码
+/// * 🚩预期失败后继续运行，而非在首个失败处中止
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NALEvalSummary {
+    /// 通过的预期数
+    pub passed: usize,
+    /// 未通过的预期（错误信息）
+    pub failed: Vec<String>,
+    /// 其它错误（解析、置入等）的信息
+    pub errors: Vec<String>,
+    /// 因CIN缺少功能而跳过的预期数
+    /// * 📄`''requires: operations`区段中的`''expect-*`
+    pub skipped: usize,
+}
+
+impl NALEvalSummary {
+    /// 记录一次NAL输入的结果
+    /// * 📌仅`''expect-*`计入「预期」，其它输入仅在出错时被记录
+    /// * 🚩「不支持的指令」不计入错误：兼容尽可能多的CIN版本
+    pub fn record(&mut self, input: &NALInput, result: &Result<()>) {
+        match (input.is_expectation(), result) {
+            (true, Ok(..)) => self.passed += 1,
+            (true, Err(e)) => self.failed.push(format!("{input:?}：{e}")),
+            (false, Err(e)) => match e.downcast_ref::<TranslateError>() {
+                Some(TranslateError::UnsupportedInput(..)) => {}
+                _ => self.errors.push(format!("{input:?}：{e}")),
+            },
+            (false, Ok(..)) => {}
+        }
+    }
+
+    /// 记录一次被跳过的NAL输入
+    /// * 📌同样仅`''expect-*`计入「预期」
+    pub fn record_skipped(&mut self, input: &NALInput) {
+        if input.is_expectation() {
+            self.skipped += 1;
+        }
+    }
+
+    /// 记录一次解析错误
+    pub fn record_parse_error(&mut self, e: &anyhow::Error) {
+        self.errors.push(format!("解析错误：{e}"));
+    }
+
+    /// 是否全部通过
+    /// * 🚩既无失败的预期，也无其它错误
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.errors.is_empty()
+    }
+
+    /// 打印统计信息
+    pub fn print(&self) {
+        self.print_failures();
+        println_cli!([Info] "NAL批处理完成：{}", self.brief());
+    }
+
+    /// 打印所有未通过的预期与其它错误
+    pub fn print_failures(&self) {
+        for e in &self.failed {
+            eprintln_cli!([Error] "预期未通过：{e}");
+        }
+        for e in &self.errors {
+            eprintln_cli!([Error] "发生错误：{e}");
+        }
+    }
+
+    /// 各项计数的简要描述
+    /// * 📄`1项预期通过，0项预期未通过，0项预期被跳过，0项其它错误`
+    pub fn brief(&self) -> String {
+        format!(
+            "{}项预期通过，{}项预期未通过，{}项预期被跳过，{}项其它错误",
+            self.passed,
+            self.failed.len(),
+            self.skipped,
+            self.errors.len()
+        )
+    }
+}
+
+/// 元指令/重新运行「预置NAL」
+/// * 🎯编写测试时：修改`.nal`文件后，无需重启CIN即可重新运行
+/// * 🚩重新从磁盘读取所有`preludeNAL`文件，在当前运行时上依次运行
+pub const META_RELOAD_NAL: &str = ":reload-nal";
+
+/// 元指令/运行指定的NAL文件
+/// * 📄`:load tests/deduction.nal`
+/// * 🚩相对路径基于配置文件所在目录
+/// * ⚠️只能加载配置文件所在目录下的文件：Websocket客户端亦可发送此指令
+pub const META_LOAD: &str = ":load";
+
+/// 「粘性指令」记录
+/// * 🎯自动重启后，向新的CIN重放「会改变CIN状态」的指令
+///   * 📄Websocket控制的NARS智能体：CIN崩溃重启后仍能执行操作
+/// * 🚩`REG`：按注册顺序记录，重复注册不重复记录
+/// * 🚩`VOL`：仅保留最后一次设置
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StickyCmds(Vec<Cmd>);
+
+impl StickyCmds {
+    /// 记录一条（成功输入的）指令
+    /// * 🚩非粘性指令⇒忽略
+    pub fn record(&mut self, cmd: &Cmd) {
+        match cmd {
+            Cmd::REG { .. } if !self.0.contains(cmd) => self.0.push(cmd.clone()),
+            Cmd::VOL(..) => {
+                self.0.retain(|c| !matches!(c, Cmd::VOL(..)));
+                self.0.push(cmd.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// 按记录顺序遍历所有粘性指令
+    pub fn iter(&self) -> impl Iterator<Item = &Cmd> {
+        self.0.iter()
+    }
+
+    /// 向（新的）运行时重放所有粘性指令
+    /// * 🎯自动重启后恢复CIN状态
+    /// * 🚩每重放一条指令，都产生一条INFO输出，以便客户端观测到恢复过程
+    /// * 🚩重放失败⇒报错，但继续重放后续指令
+    pub fn replay(
+        &self,
+        runtime: &mut impl VmRuntimeBoost,
+        output_cache: &mut OutputCache,
+    ) -> Result<()> {
+        for cmd in self.iter() {
+            let output = match runtime.input_cmd(cmd.clone()) {
+                Ok(..) => Output::INFO {
+                    message: format!("重启后已重放指令：{cmd}"),
+                },
+                Err(e) => Output::ERROR {
+                    description: format!("重启后重放指令「{cmd}」失败：{e}"),
+                },
+            };
+            output_cache.put(output)?;
+        }
+        Ok(())
+    }
+}
+
+/// 子线程的「异常退出」标志
+/// * 🎯任一子线程异常退出（返回错误、panic）⇒其余子线程有序退出，而非留下断开的流水线空转
+/// * 🚩由[受监护的子线程](spawn_guarded)在异常退出时设置；各子线程在循环中检查
+/// * 📌各管理者独立：多实例下，单个实例的异常退出不影响其它实例
+#[derive(Debug, Clone, Default)]
+pub struct AbortFlag(Arc<AtomicBool>);
+
+impl AbortFlag {
+    /// 标记「有子线程异常退出」
+    pub fn abort(&self) {
+        self.0.store(true, Ordering::SeqCst)
+    }
+
+    /// 是否已有子线程异常退出
+    pub fn is_aborted(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// 等待子线程结束时，轮询的间隔
+pub const JOIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 生成「受监护」的子线程
+/// * 🚩panic⇒捕获并转换为错误，而非在[`JoinHandle::join`]时才被发现
+/// * 🚩异常退出（含panic）⇒设置[`AbortFlag`]，通知其它子线程退出
+/// * 📌`name`：线程名称，用于错误信息
+pub fn spawn_guarded(
+    name: &'static str,
+    abort: AbortFlag,
+    body: impl FnOnce() -> Result<()> + Send + 'static,
+) -> JoinHandle<Result<()>> {
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+            Err(anyhow!("「{name}」线程panic：{}", panic_message(&*payload)))
+        });
+        if result.is_err() {
+            abort.abort();
+        }
+        result
+    })
+}
+
+/// 从panic的载荷中提取消息
+/// * 📌`panic!`的载荷：`&str`或[`String`]
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload
+            .downcast_ref::<String>()
+            .map_or("（无法获取panic消息）", String::as_str),
+    }
+}
+
+/// 锁定共享状态，并从「中毒」中恢复
+/// * 🎯某个子线程持锁时panic，不应连累其它子线程：panic已由[`spawn_guarded`]记录
+/// * 🚩锁已中毒⇒直接取出其中的值
+pub fn lock_recovered<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 等待子线程结束，并抛出其抛出的错误
+/// * 🚩轮询而非依次`join`：任一子线程异常退出⇒立即返回其错误
+///   * 📌其余子线程经[`AbortFlag`]自行退出；用户输入可能仍在阻塞，不再等待
+/// * 🚩收到终止信号⇒首个子线程（「读取输出」）结束后即返回：用户输入可能仍在阻塞
+fn join_threads(threads: Vec<JoinHandle<Result<()>>>) -> Result<()> {
+    let mut threads = threads.into_iter().map(Some).collect::<Vec<_>>();
+    loop {
+        for slot in threads.iter_mut() {
+            if let Some(thread) = slot.take_if(|thread| thread.is_finished()) {
+                thread.join().transform_err(error_anyhow)??;
+            }
+        }
+        if_return! {
+            threads.iter().all(Option::is_none) => Ok(())
+            is_shutdown_requested() && threads[0].is_none() => Ok(())
+        }
+        sleep(JOIN_POLL_INTERVAL);
+    }
+}
+
 /// 运行时管理器
 /// * 🎯在一个数据结构中封装「虚拟机运行时」与「配置信息」
 /// * 📌只负责**单个运行时**的运行管理
 ///   * 🚩不负责「终止、重启运行时」等过程
-#[derive(Debug, Clone)]
+///   * 🚩多个实例由[`Supervisor`](crate::Supervisor)统一管理
+#[derive(Debug)]
 pub struct RuntimeManager<R>
 where
     // ! 🚩【2024-04-02 14:51:23】需要`Send + Sync`进行多线程操作，需要`'static`保证生命周期
-    R: VmRuntime + Send + Sync + 'static,
+    R: VmRuntimeBoost + Send + Sync + 'static,
 {
     /// 内部封装的虚拟机运行时
     /// * 🏗️后续可能会支持「同时运行多个虚拟机」
@@ -53,52 +289,187 @@ where
     /// * 🎯用于NAL测试
     /// * 🚩多线程共享
     pub(crate) output_cache: ArcMutex<OutputCache>,
+
+    /// 内部记录的「粘性指令」
+    /// * 🎯自动重启后重放
+    /// * 🚩多线程共享；重启后由新的管理者继承
+    pub(crate) sticky_cmds: ArcMutex<StickyCmds>,
+
+    /// 内部收集的「运行统计」
+    /// * 🎯`''status`与Websocket的`/status`
+    /// * 🚩输出由「输出缓存」的侦听器记录：含NAL测试中途拉取的输出
+    /// * 🚩多线程共享；重启后由新的管理者继承
+    pub(crate) metrics: ArcMutex<RuntimeMetrics>,
+
+    /// 内部记录的「NAL测试报告」
+    /// * 🎯运行「预置NAL」或`--eval`后写入`reportFile`
+    /// * 🚩重启后不继承：新的管理者会重新运行「预置NAL」
+    pub(crate) report: ArcMutex<TestReport>,
+
+    /// 实例名称
+    /// * 🎯多实例：打印与Websocket回传的输出附带实例名
+    /// * 📌单实例⇒[`None`]：输出不附带实例名
+    pub(crate) name: Option<String>,
+
+    /// 交互状态：是否启用用户输入、已连接的Websocket客户端数
+    /// * 🎯`''terminate(if-no-user)`与`''terminate(if-no-client)`
+    /// * 🚩多线程共享：客户端数由Websocket服务端维护；重启后由新的管理者继承
+    pub(crate) interactivity: Arc<InteractivityState>,
+
+    /// 子线程的「异常退出」标志
+    /// * 🚩多线程共享；重启后不继承：新的管理者重新生成子线程
+    pub(crate) abort: AbortFlag,
+}
+
+/// 手动实现[`Clone`]：仅拷贝共享引用
+/// * 📝派生的[`Clone`]会要求`R: Clone`
+impl<R> Clone for RuntimeManager<R>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            runtime: self.runtime.clone(),
+            config: self.config.clone(),
+            output_cache: self.output_cache.clone(),
+            sticky_cmds: self.sticky_cmds.clone(),
+            metrics: self.metrics.clone(),
+            report: self.report.clone(),
+            name: self.name.clone(),
+            interactivity: self.interactivity.clone(),
+            abort: self.abort.clone(),
+        }
+    }
 }
 
 impl<R> RuntimeManager<R>
 where
-    R: VmRuntime + Send + Sync + 'static,
+    R: VmRuntimeBoost + Send + Sync + 'static,
 {
     /// 构造函数
     /// * 🎯由此接管虚拟机实例、配置的所有权
     pub fn new(runtime: R, config: RuntimeConfig) -> Self {
+        Self::new_named(runtime, config, None, Arc::default())
+    }
+
+    /// 构造具名实例
+    /// * 🎯多实例：输出附带实例名
+    pub fn named(runtime: R, config: RuntimeConfig, name: impl Into<String>) -> Self {
+        Self::new_named(runtime, config, Some(name.into()), Arc::default())
+    }
+
+    /// 构造函数的内部实现
+    /// * 📌「运行统计」由调用方传入：重启后继承先前的统计
+    fn new_named(
+        runtime: R,
+        config: RuntimeConfig,
+        name: Option<String>,
+        metrics: ArcMutex<RuntimeMetrics>,
+    ) -> Self {
         Self {
             runtime: Arc::new(Mutex::new(runtime)),
-            config: Arc::new(config),
             // 创建的同时增加侦听器
-            output_cache: Self::new_output_cache(),
+            output_cache: Self::new_output_cache(&config, name.as_deref(), &metrics),
+            sticky_cmds: Arc::default(),
+            metrics,
+            report: Arc::default(),
+            interactivity: Arc::new(InteractivityState::new(config.user_input)),
+            config: Arc::new(config),
+            name,
+            abort: AbortFlag::default(),
         }
     }
 
+    /// 实例名称
+    /// * 📌单实例⇒[`None`]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// 是否已有子线程异常退出
+    /// * 🎯Websocket服务等外部线程据此有序退出
+    pub fn is_aborted(&self) -> bool {
+        self.abort.is_aborted()
+    }
+
     /// 新建一个「输出缓存」
     /// * 🚩创建缓存⇒增加侦听器⇒装入[`ArcMutex`]
     /// * 🎯避免
-    fn new_output_cache() -> ArcMutex<OutputCache> {
+    fn new_output_cache(
+        config: &RuntimeConfig,
+        name: Option<&str>,
+        metrics: &ArcMutex<RuntimeMetrics>,
+    ) -> ArcMutex<OutputCache> {
         pipe! {
             manipulate!(
                 // 产生一个新的「输出缓存」
                 OutputCache::default()
+                // 抑制输入回显
+                => .set_suppress_input_echo(config.suppress_input_echo)
+                // 「保存输出」的选项
+                => .set_save_outputs_options(SaveOutputsOptions {
+                    keep: config.save_outputs_keep,
+                    allow_absolute: config.save_outputs_allow_absolute,
+                })
                 // 添加侦听器
-                => Self::add_output_listener
+                => Self::add_metrics_listener(_, metrics.clone())
+                => Self::add_output_listener(_, config, name)
             )
             // 装入ArcMutex
             => Mutex::new => Arc::new
         }
     }
 
+    /// 增加「运行统计」侦听器
+    /// * 🎯统计所有进入缓存的输出：不论由「读取输出」线程，还是由NAL测试拉取
+    /// * ⚠️先于「打印输出」侦听器：打印过滤不影响统计
+    fn add_metrics_listener(output_cache: &mut OutputCache, metrics: ArcMutex<RuntimeMetrics>) {
+        output_cache.output_handlers.add_handler(move |output| {
+            lock_recovered(&metrics).record_output(&output);
+            Some(output)
+        });
+    }
+
     /// 增加「打印输出」侦听器
     /// * 🎯（与Websocket一同）分离「输出侦听」逻辑
     /// * 🎯统一给管理者添加功能
-    ///   * ❓后续可配置
-    fn add_output_listener(output_cache: &mut OutputCache) {
-        output_cache.output_handlers.add_handler(|output| {
+    /// * 🚩JSON输出模式⇒每行一个JSON对象，不经过CLI美化
+    /// * 🚩按「打印过滤」跳过不打印的输出
+    ///   * 📌仍然返回输出：后续的缓存、Websocket回传不受影响
+    /// * 🚩文本输出模式⇒对齐、着色的风格化输出
+    /// * 🚩具名实例⇒附带实例名：JSON中的`instance`字段，或文本行首的`[名称]`
+    fn add_output_listener(
+        output_cache: &mut OutputCache,
+        config: &RuntimeConfig,
+        name: Option<&str>,
+    ) {
+        let name = name.map(str::to_string);
+        let output_format = config.output_format;
+        let print_level = config
+            .print_level
+            .with_type_aliases(&config.output_type_aliases);
+        let mut style = StyleOptions::from_env();
+        // * 🚩配置禁用、标准输出非终端⇒不着色
+        style.color &= !config.no_color && color_support().stdout;
+        output_cache.output_handlers.add_handler(move |output| {
+            // 不在打印范围内⇒跳过打印
+            if !print_level.allows(&output) {
+                return Some(output);
+            }
+            // JSON输出模式⇒打印JSON | 📌始终输出到标准输出
+            if output_format == OutputFormat::Json {
+                println!("{}", output_to_json_tagged(&output, name.as_deref()));
+                return Some(output);
+            }
             // 打印输出
             // * 🚩【2024-04-13 17:57:32】暂不启用「详细输出」模式：尚未解决「详细输出后过长，但因信息取舍不能省掉『原始信息』」的问题
             // * 💭CIN的「原始输出」总是信息量相对最多的（NAVM输出只取其中一个规则的子集）
             //   * 📌因此，开启「详细模式」必定造成「信息冗余」
-            // TODO: 💡或许后续可用配置开关「详细模式/纯NAVM输出模式」，以实现「自定义输出形式」
-            //   * ✨这样的形式也方便调用其exe的其它外部程序解析exe输出（更为规范化）
-            println_cli!(&output);
+            // * 💡「方便外部程序解析」的需求由「JSON输出模式」实现
+            match &name {
+                Some(name) => println!("[{name}] {}", format_output_styled(&output, &style)),
+                None => OutputType::print_navm_output_styled(&output, &style),
+            }
             // 继续返回
             Some(output)
         });
@@ -130,9 +501,14 @@ where
             Continue(Ok(..)) => (),
         }
 
+        // 收到终止信号⇒等待「读取输出」子线程终止虚拟机后退出
+        if is_shutdown_requested() {
+            thread_read.join().transform_err(error_anyhow)??;
+            return Ok(Ok(()));
+        }
+
         // 虚拟机被终止 & 无用户输入 ⇒ 程序退出
-        if let VmStatus::Terminated(..) = self.runtime.lock().transform_err(error_anyhow)?.status()
-        {
+        if let VmStatus::Terminated(..) = lock_recovered(&self.runtime).status() {
             if !self.config.user_input {
                 // 直接返回，使程序退出
                 return Ok(Ok(()));
@@ -152,12 +528,14 @@ where
 
         // 等待子线程结束，并抛出其抛出的错误
         // ! 🚩【2024-04-02 15:09:32】错误处理交给外界
-        thread_read.join().transform_err(error_anyhow)??;
-        if let Some(thread_ws) = thread_ws {
-            thread_ws.join().transform_err(error_anyhow)??
-        }
-        if let Some(thread_input) = thread_input {
-            thread_input.join().transform_err(error_anyhow)??;
+        // * 🚩任一子线程异常退出（含panic）⇒不再等待其它子线程
+        let mut threads = vec![thread_read];
+        threads.extend(thread_ws);
+        threads.extend(thread_input);
+        if let Err(e) = join_threads(threads) {
+            // 异常退出⇒确保虚拟机已终止，再上报原始错误
+            let _ = lock_recovered(&self.runtime).terminate();
+            return Err(e);
         }
 
         // 正常运行结束
@@ -171,8 +549,19 @@ where
     ///   * 📌[`Break`] => 告知调用者「需要提前结束」
     ///     * 📌[`Break`]([`Ok`]) => 正常退出
     ///     * 📌[`Break`]([`Err`]) => 异常退出（报错）
+    /// * 🚩无论是否提前结束，都写入「测试报告」
+    ///   * 📌严格模式下，导致中止的预期亦已被记录
     pub fn prelude_nal(&mut self) -> ControlFlow<Result<()>, Result<()>> {
+        let flow = self.put_prelude_nal();
+        self.write_report();
+        flow
+    }
+
+    /// 按顺序输入所有「预置NAL」
+    /// * 🚩返回值同[`Self::prelude_nal`]
+    fn put_prelude_nal(&mut self) -> ControlFlow<Result<()>, Result<()>> {
         let config = &*self.config;
+        let interactivity = &*self.interactivity;
 
         /// 尝试获取结果并返回
         /// * 🎯对错误返回`Break(Err(错误))`而非`Err(错误)`
@@ -194,65 +583,203 @@ where
         // 尝试获取运行时引用 | 仅有其它地方panic了才会停止
         let runtime = &mut *try_break!(anyhow self.runtime.lock());
 
-        // 仅在有预置NAL时开始
-        if let Some(prelude_nal) = &config.prelude_nal {
-            // 尝试获取输出缓冲区引用 | 仅有其它地方panic了才会停止
-            let output_cache =
-                &mut *try_break!(OutputCache::unlock_arc_mutex(&mut self.output_cache));
-
-            // 读取内容
-            let nal = match prelude_nal {
-                // 文件⇒尝试读取文件内容 | ⚠️此处创建了一个新值，所以要统一成`String`
-                LaunchConfigPreludeNAL::File(path) => {
-                    try_break!(std::fs::read_to_string(path) => e {
-                        println_cli!([Error] "读取预置NAL文件 {path:?} 发生错误：{e}");
-                        // 继续（用户输入/Websocket服务端）
-                        e.into()
-                    })
-                }
-                // 纯文本⇒直接引入
-                LaunchConfigPreludeNAL::Text(nal) => nal.to_string(),
-            };
+        // 尝试获取输出缓冲区引用 | 仅有其它地方panic了才会停止
+        let output_cache = &mut *try_break!(OutputCache::unlock_arc_mutex(&mut self.output_cache));
 
-            // 获取「NAL执行路径」
-            // * 🎯在「预置NAL」中执行「保存文件」时，决定以哪个路径为「相对路径起点」
-            let nal_file_path = match prelude_nal {
-                // 文件⇒基于文件路径
-                LaunchConfigPreludeNAL::File(path) => {
-                    path.parent().unwrap_or(&self.config.config_path)
-                }
-                // 纯文本⇒直接引入
-                LaunchConfigPreludeNAL::Text(..) => &self.config.config_path,
-            };
+        // 尝试获取「粘性指令」记录 | 仅有其它地方panic了才会停止
+        let sticky_cmds = &mut *try_break!(anyhow self.sticky_cmds.lock());
+
+        // 「运行统计」 | 仅在记录时短暂锁定：输出缓存的侦听器亦会锁定
+        let metrics = &*self.metrics;
+
+        // 尝试获取「测试报告」 | 仅有其它地方panic了才会停止
+        let report = &mut *try_break!(anyhow self.report.lock());
+
+        // 按顺序输入所有预置NAL | 无预置NAL⇒正常继续
+        // * 🚩【2024-04-03 11:10:44】遇到错误，统一上报
+        //   * 根据「严格模式」判断要「继续」还是「终止」
+        let mut put_result = Ok(());
+        for prelude_nal in &config.prelude_nal {
+            // 读取内容 & 获取「NAL执行路径」
+            let (nal, nal_file_path) = try_break!(Self::read_prelude_nal(prelude_nal, config));
+            // 新的NAL文件⇒重新计算输出延迟，并标注测试报告的来源
+            output_cache.clear_nse_input();
+            report.set_source(Self::prelude_nal_source(prelude_nal));
+            // 输入NAL | 严格模式下，出错即终止
+            put_result = Self::input_nal_to_vm(
+                runtime,
+                &nal,
+                output_cache,
+                sticky_cmds,
+                metrics,
+                config,
+                interactivity,
+                nal_file_path,
+                Self::prelude_nal_source(prelude_nal),
+                None,
+                Some(report),
+            );
+            if_return! {
+                config.strict_mode && put_result.is_err()
+                    => Break(put_result.map_err(|e| CliError::nal_aborted(e).into()))
+            }
+        }
+        Self::report_unanswered_questions(output_cache);
+        match config.strict_mode && !config.prelude_nal.is_empty() {
+            false => Continue(put_result),
+            true => Break(put_result.map_err(|e| CliError::nal_aborted(e).into())),
+        }
+    }
+
+    /// 「预置NAL」所在的文件
+    /// * 🎯标注测试报告中各项预期的来源
+    /// * 📌纯文本⇒[`None`]
+    fn prelude_nal_source(prelude_nal: &LaunchConfigPreludeNAL) -> Option<&Path> {
+        match prelude_nal {
+            LaunchConfigPreludeNAL::File(path) => Some(path),
+            LaunchConfigPreludeNAL::Text(..) => None,
+        }
+    }
+
+    /// 报告尚未被回答的问题
+    /// * 🎯「预置NAL」运行完毕后，列出始终没有`ANSWER`的问题
+    /// * 🚩所有问题均已回答⇒不报告
+    fn report_unanswered_questions(output_cache: &OutputCache) {
+        let unanswered = output_cache.unanswered_questions();
+        if_return! { unanswered.is_empty() }
+        let questions = unanswered
+            .iter()
+            .map(|question| format!("#{} {}", question.id, question.narsese))
+            .collect::<Vec<_>>();
+        println_cli!([Info] "尚未被回答的问题（{}个）：{}", questions.len(), questions.join("; "));
+    }
+
+    /// 写入「测试报告」
+    /// * 🚩仅在配置了`reportFile`且有「预置NAL」时写入
+    /// * 🚩写入失败仅报错：不影响测试结果
+    fn write_report(&self) {
+        let Some(path) = &self.config.report_file else {
+            return;
+        };
+        if_return! { self.config.prelude_nal.is_empty() }
+        let result = self
+            .report
+            .lock()
+            .transform_err(error_anyhow)
+            .and_then(|report| report.write_to(path));
+        match result {
+            Ok(..) => println_cli!([Info] "测试报告已写入{path:?}"),
+            Err(e) => eprintln_cli!([Error] "写入测试报告{path:?}时发生错误：{e}"),
+        }
+    }
+
+    /// 读取「预置NAL」的内容，并获取「NAL执行路径」
+    /// * 🎯在「预置NAL」中执行「保存文件」时，决定以哪个路径为「相对路径起点」
+    ///   * 📌文件⇒基于文件路径；纯文本⇒基于配置路径
+    fn read_prelude_nal<'a>(
+        prelude_nal: &'a LaunchConfigPreludeNAL,
+        config: &'a RuntimeConfig,
+    ) -> Result<(String, &'a Path)> {
+        match prelude_nal {
+            // 文件⇒尝试读取文件内容 | ⚠️此处创建了一个新值，所以要统一成`String`
+            LaunchConfigPreludeNAL::File(path) => {
+                let nal = std::fs::read_to_string(path).inspect_err(
+                    |e| println_cli!([Error] "读取预置NAL文件 {path:?} 发生错误：{e}"),
+                )?;
+                Ok((nal, path.parent().unwrap_or(&config.config_path)))
+            }
+            // 纯文本⇒直接引入
+            LaunchConfigPreludeNAL::Text(nal) => Ok((nal.to_string(), &config.config_path)),
+        }
+    }
 
-            // 输入NAL并处理
-            // * 🚩【2024-04-03 11:10:44】遇到错误，统一上报
-            //   * 根据「严格模式」判断要「继续」还是「终止」
-            let put_result =
-                Self::input_nal_to_vm(runtime, &nal, output_cache, config, nal_file_path);
-            match self.config.strict_mode {
-                false => Continue(put_result),
-                true => Break(put_result),
+    /// 【主函数】批处理模式：运行「预置NAL」后终止虚拟机，并返回结果统计
+    /// * 🎯`--eval`：脚本化回归测试
+    /// * 🚩不启动「用户输入」与「Websocket服务」
+    /// * 🚩预期失败后继续运行，统计所有结果
+    pub fn eval(&mut self) -> Result<NALEvalSummary> {
+        // 生成「读取输出」子线程 | 📌必须最先
+        let thread_read = self.spawn_read_output()?;
+
+        let mut summary = NALEvalSummary::default();
+        {
+            let config = &*self.config;
+            let interactivity = &*self.interactivity;
+            if_return! {
+                config.prelude_nal.is_empty() => Err(anyhow!("批处理模式下未指定NAL文件"))
+            }
+            let runtime = &mut *self.runtime.lock().transform_err(error_anyhow)?;
+            let output_cache = &mut *OutputCache::unlock_arc_mutex(&mut self.output_cache)?;
+            let sticky_cmds = &mut *self.sticky_cmds.lock().transform_err(error_anyhow)?;
+            let metrics = &*self.metrics;
+            let report = &mut *self.report.lock().transform_err(error_anyhow)?;
+
+            // 按顺序读取并输入NAL | 统计结果，不提前返回
+            for prelude_nal in &config.prelude_nal {
+                let (nal, nal_file_path) = Self::read_prelude_nal(prelude_nal, config)?;
+                output_cache.clear_nse_input();
+                report.set_source(Self::prelude_nal_source(prelude_nal));
+                let _ = Self::input_nal_to_vm(
+                    runtime,
+                    &nal,
+                    output_cache,
+                    sticky_cmds,
+                    metrics,
+                    config,
+                    interactivity,
+                    nal_file_path,
+                    Self::prelude_nal_source(prelude_nal),
+                    Some(&mut summary),
+                    Some(report),
+                );
+            }
+            Self::report_unanswered_questions(output_cache);
+
+            // 运行完毕⇒终止虚拟机 | 若已终止，则不再重复
+            if !runtime.is_terminated() {
+                if_let_err_eprintln_cli!(
+                    runtime.terminate()
+                    => e => [Error] "终止NAVM运行时时发生错误：{e}"
+                );
             }
         }
-        // 否则自动返回「正常」
-        else {
-            // 返回 | 正常继续
-            Continue(Ok(()))
+
+        // 等待「读取输出」子线程结束 | 虚拟机意外终止仅作警告：已计入统计
+        if let Err(e) = thread_read.join().transform_err(error_anyhow)? {
+            eprintln_cli!([Warn] "{e}");
         }
+
+        // 写入测试报告
+        self.write_report();
+
+        // 返回统计结果
+        Ok(summary)
     }
 
     /// 生成「读取输出」子线程
+    /// * 🚩其它子线程异常退出⇒同样终止虚拟机后退出
     pub fn spawn_read_output(&mut self) -> Result<JoinHandle<Result<()>>> {
         // 准备引用
         let runtime = self.runtime.clone();
         let output_cache = self.output_cache.clone();
+        let abort = self.abort.clone();
 
         // 启动线程
-        let thread = thread::spawn(move || {
+        let thread = spawn_guarded("读取输出", self.abort.clone(), move || {
             loop {
-                // 尝试获取运行时引用 | 仅有其它地方panic了才会停止
-                let mut runtime = runtime.lock().transform_err(error_anyhow)?;
+                // 获取运行时引用 | 其它线程持锁时panic⇒恢复
+                let mut runtime = lock_recovered(&runtime);
+
+                // 收到终止信号、其它子线程异常退出⇒终止虚拟机（连同子进程）后退出
+                if is_shutdown_requested() || abort.is_aborted() {
+                    if !runtime.is_terminated() {
+                        if_let_err_eprintln_cli!(
+                            runtime.terminate()
+                            => e => [Error] "终止NAVM运行时时发生错误：{e}"
+                        );
+                    }
+                    break Ok(());
+                }
 
                 // 若运行时已终止，返回终止信号
                 if let VmStatus::Terminated(result) = runtime.status() {
@@ -264,16 +791,23 @@ where
                 }
 
                 // 尝试拉取所有NAVM运行时输出
-                while let Ok(Some(output)) = runtime
-                    .try_fetch_output()
-                    .inspect_err(|e| eprintln_cli!([Error] "尝试拉取NAVM运行时输出时发生错误：{e}"))
-                {
-                    // 缓存输出
-                    // * 🚩在缓存时格式化输出
-                    match output_cache.lock() {
-                        Ok(mut output_cache) => output_cache.put(output)?,
-                        Err(e) => eprintln_cli!([Error] "缓存NAVM运行时输出时发生错误：{e}"),
+                // * 🚩批量拉取：避免每拉取一行都锁定一次
+                let outputs = match runtime.drain_outputs() {
+                    Ok(outputs) => outputs,
+                    Err(e) => {
+                        eprintln_cli!([Error] "尝试拉取NAVM运行时输出时发生错误：{e}");
+                        continue;
                     }
+                };
+                // 无输出⇒跳过缓存
+                if outputs.is_empty() {
+                    continue;
+                }
+                // 缓存输出
+                // * 🚩在缓存时格式化输出
+                let mut output_cache = lock_recovered(&output_cache);
+                for output in outputs {
+                    output_cache.put(output)?;
                 }
             }
         });
@@ -300,25 +834,35 @@ where
         // ! 📝不能在此外置「可复用引用」变量：borrowed data escapes outside of method
         let runtime = self.runtime.clone();
         let config = self.config.clone();
+        let interactivity = self.interactivity.clone();
         let output_cache = self.output_cache.clone();
+        let sticky_cmds = self.sticky_cmds.clone();
+        let metrics = self.metrics.clone();
+        let abort = self.abort.clone();
 
         // 启动线程
-        let thread = thread::spawn(move || {
+        let thread = spawn_guarded("用户输入", self.abort.clone(), move || {
             // 主循环
             // ! 📝不能在此中出现裸露的`MutexGuard`对象：其并非线程安全
             //   * ✅可使用`&(mut) *`重引用语法，从`MutexGuard`转换为线程安全的引用
             //   * ✅对`Arc`使用`&*`同理：可以解包成引用，以便后续统一传递值的引用
             // ! 不建议在此启用提示词：会被异步的输出所打断
-            for io_result in ReadlineIter::default() {
+            for io_result in user_input_lines(&config) {
                 // 从迭代器中读取一行
                 let line = io_result?;
+                // 收到终止信号⇒不再输入
+                if is_shutdown_requested() {
+                    break;
+                }
+                // 其它子线程异常退出⇒不再输入 | 虚拟机由「读取输出」子线程终止
+                if abort.is_aborted() {
+                    return Ok(());
+                }
                 let line = line.trim(); // ! 这两句无法合并：临时变量的引用问题
 
-                // 尝试获取运行时引用 | 仅有其它地方panic了才会停止
+                // 获取运行时引用 | 其它线程持锁时panic⇒恢复
                 // ! 📝PoisonError无法在线程中传递
-                let runtime = &mut *runtime
-                    .lock()
-                    .transform_err(|e| anyhow!("获取运行时引用时发生错误：{e:?}"))?;
+                let runtime = &mut *lock_recovered(&runtime);
 
                 // 若运行时已终止，返回终止信号
                 if let VmStatus::Terminated(result) = runtime.status() {
@@ -329,22 +873,35 @@ where
                     }
                 }
 
-                // 尝试获取输出缓冲区引用 | 仅有其它地方panic了才会停止
-                // ! 🚩【2024-04-02 19:27:01】及早报错：即便无关紧要，也停止
-                let output_cache = &mut *output_cache
-                    .lock()
-                    .transform_err(|e| anyhow!("获取NAVM输出缓存时发生错误：{e}"))?;
+                // 获取输出缓冲区、「粘性指令」记录的引用 | 同上
+                let output_cache = &mut *lock_recovered(&output_cache);
+                let sticky_cmds = &mut *lock_recovered(&sticky_cmds);
 
                 // 非空⇒解析输入并执行
                 if !line.is_empty() {
                     if_let_err_eprintln_cli!(
                         // * 🚩【2024-04-09 22:11:41】置入时以「配置文件所在目录」为NAL工作目录
-                        Self::input_line_to_vm(runtime, line, &config, output_cache, &config.config_path)
+                        Self::input_line_to_vm(runtime, line, &config, &interactivity, output_cache, sticky_cmds, &metrics, &config.config_path)
                         => e => [Error] "输入过程中发生错误：{e}"
                     );
                 }
             }
 
+            // 管道输入读完⇒等待宽限期；仍有其它服务⇒不终止虚拟机
+            if is_stdin_piped()
+                && wait_after_piped_input(&config, || {
+                    abort.is_aborted() || lock_recovered(&runtime).is_terminated()
+                })
+            {
+                return Ok(());
+            }
+
+            // 用户主动退出（连续两次Ctrl-C）⇒终止虚拟机
+            let runtime = &mut *lock_recovered(&runtime);
+            if !runtime.is_terminated() {
+                runtime.terminate()?;
+            }
+
             // 返回
             Ok(())
         });
@@ -353,39 +910,224 @@ where
         Ok(thread)
     }
 
+    /// 以指定的「输入模式」向该实例置入一行输入
+    /// * 🎯多实例的输入路由：在锁定各共享状态后输入
+    /// * 🚩已终止⇒返回错误，不再输入
+    pub fn input_line(&self, line: &str, input_mode: InputMode) -> Result<()> {
+        let runtime = &mut *self.runtime.lock().transform_err(error_anyhow)?;
+        if let VmStatus::Terminated(..) = runtime.status() {
+            return Err(anyhow!("NAVM运行时已终止"));
+        }
+        let output_cache = &mut *self.output_cache.lock().transform_err(error_anyhow)?;
+        let sticky_cmds = &mut *self.sticky_cmds.lock().transform_err(error_anyhow)?;
+        Self::input_line_to_vm_as(
+            runtime,
+            line,
+            input_mode,
+            &self.config,
+            &self.interactivity,
+            output_cache,
+            sticky_cmds,
+            &self.metrics,
+            &self.config.config_path,
+        )
+    }
+
+    /// 是否已终止
+    /// * 🚩无法锁定运行时⇒视作已终止
+    pub fn is_terminated(&self) -> bool {
+        self.runtime
+            .lock()
+            .map_or(true, |runtime| runtime.is_terminated())
+    }
+
     /// 置入一行输入
     /// * 📄`nal_root_path`：从NAL文件加载⇒NAL文件所在路径；用户输入⇒配置文件所在路径
+    /// * 🚩使用配置中的「输入模式」
+    #[allow(clippy::too_many_arguments)]
     pub fn input_line_to_vm(
         runtime: &mut R,
         line: &str,
         config: &RuntimeConfig,
+        interactivity: &InteractivityState,
         output_cache: &mut OutputCache,
+        sticky_cmds: &mut StickyCmds,
+        metrics: &Mutex<RuntimeMetrics>,
         nal_root_path: &Path,
     ) -> Result<()> {
+        Self::input_line_to_vm_as(
+            runtime,
+            line,
+            config.input_mode,
+            config,
+            interactivity,
+            output_cache,
+            sticky_cmds,
+            metrics,
+            nal_root_path,
+        )
+    }
+
+    /// 以指定的「输入模式」置入一行输入
+    /// * 🎯Websocket：每个连接可协商自己的输入模式
+    #[allow(clippy::too_many_arguments)]
+    pub fn input_line_to_vm_as(
+        runtime: &mut R,
+        line: &str,
+        input_mode: InputMode,
+        config: &RuntimeConfig,
+        interactivity: &InteractivityState,
+        output_cache: &mut OutputCache,
+        sticky_cmds: &mut StickyCmds,
+        metrics: &Mutex<RuntimeMetrics>,
+        nal_root_path: &Path,
+    ) -> Result<()> {
+        // 元指令⇒不论输入模式，直接处理
+        if let Some(nal_files) = Self::parse_meta_command(line, config) {
+            return Self::reload_nal(
+                runtime,
+                nal_files?,
+                config,
+                interactivity,
+                output_cache,
+                sticky_cmds,
+                metrics,
+            );
+        }
         // 向运行时输入
-        match config.input_mode {
+        match input_mode {
             // NAVM指令
             // * ✨【2024-04-09 22:48:01】转义输入：使用（NAVM指令不可能用的）前缀「/」以重新启用「NAL输入」
             InputMode::Cmd => match line.starts_with('/') {
-                true => {
-                    Self::input_nal_to_vm(runtime, &line[1..], output_cache, config, nal_root_path)
-                }
-                false => Self::input_cmd_to_vm(runtime, line),
+                true => Self::input_nal_to_vm(
+                    runtime,
+                    &line[1..],
+                    output_cache,
+                    sticky_cmds,
+                    metrics,
+                    config,
+                    interactivity,
+                    nal_root_path,
+                    None,
+                    None,
+                    None,
+                ),
+                false => Self::input_cmd_to_vm(runtime, line, sticky_cmds, metrics),
             },
             // NAL输入
-            InputMode::Nal => {
-                Self::input_nal_to_vm(runtime, line, output_cache, config, nal_root_path)
-            }
+            InputMode::Nal => Self::input_nal_to_vm(
+                runtime,
+                line,
+                output_cache,
+                sticky_cmds,
+                metrics,
+                config,
+                interactivity,
+                nal_root_path,
+                None,
+                None,
+                None,
+            ),
+            // 原始输入 | 直通CIN
+            InputMode::Raw => runtime
+                .input_raw(line)
+                .inspect_err(|e| eprintln_cli!([Error] "原始输入错误：{e}")),
         }
     }
 
+    /// 解析元指令，得到要运行的NAL
+    /// * 🚩非元指令⇒[`None`]
+    /// * 📌[`META_RELOAD_NAL`]⇒配置中的所有「预置NAL」
+    /// * 📌[`META_LOAD`]⇒指定的文件 | 相对路径基于配置文件所在目录
+    fn parse_meta_command(
+        line: &str,
+        config: &RuntimeConfig,
+    ) -> Option<Result<Vec<LaunchConfigPreludeNAL>>> {
+        let (head, tail) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let tail = tail.trim();
+        match head {
+            META_RELOAD_NAL if tail.is_empty() => Some(match config.prelude_nal.is_empty() {
+                true => Err(anyhow!("未配置预置NAL，无法重新加载")),
+                false => Ok(config.prelude_nal.clone()),
+            }),
+            META_LOAD => Some(match tail.is_empty() {
+                true => Err(anyhow!("请指定要加载的NAL文件：`{META_LOAD} <路径>`")),
+                false => Self::resolve_load_path(tail, config)
+                    .map(|path| vec![LaunchConfigPreludeNAL::File(path)]),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 解析[`META_LOAD`]要加载的文件路径
+    /// * 🚩规范化后须位于配置文件所在目录之下：拒绝`..`、绝对路径、符号链接等越界访问
+    fn resolve_load_path(path: &str, config: &RuntimeConfig) -> Result<PathBuf> {
+        let root = config.config_path.canonicalize()?;
+        let resolved = root
+            .join(path)
+            .canonicalize()
+            .map_err(|e| anyhow!("无法定位NAL文件{path:?}：{e}"))?;
+        match resolved.starts_with(&root) {
+            true => Ok(resolved),
+            false => Err(anyhow!("只能加载配置文件所在目录下的NAL文件：{path:?}")),
+        }
+    }
+
+    /// 在当前运行时上（重新）运行NAL
+    /// * 🎯元指令[`META_RELOAD_NAL`]、[`META_LOAD`]：不重启CIN
+    /// * 🚩文件在此时从磁盘读取：总是运行最新内容
+    /// * 🚩运行完毕后，打印未通过的预期，并置入一条汇总各项计数的`INFO`输出
+    ///   * 📌Websocket客户端亦能收到汇总
+    /// * 📌调用方全程持有该运行时的锁：同一实例的重新加载依次进行，不同实例互不阻塞
+    fn reload_nal(
+        runtime: &mut R,
+        nal_files: Vec<LaunchConfigPreludeNAL>,
+        config: &RuntimeConfig,
+        interactivity: &InteractivityState,
+        output_cache: &mut OutputCache,
+        sticky_cmds: &mut StickyCmds,
+        metrics: &Mutex<RuntimeMetrics>,
+    ) -> Result<()> {
+        let mut summary = NALEvalSummary::default();
+        for nal_file in &nal_files {
+            let (nal, nal_root_path) = Self::read_prelude_nal(nal_file, config)?;
+            output_cache.clear_nse_input();
+            // 严格模式下的中止已被记录到统计中
+            let _ = Self::input_nal_to_vm(
+                runtime,
+                &nal,
+                output_cache,
+                sticky_cmds,
+                metrics,
+                config,
+                interactivity,
+                nal_root_path,
+                Self::prelude_nal_source(nal_file),
+                Some(&mut summary),
+                None,
+            );
+        }
+        summary.print_failures();
+        let message = format!("NAL重新加载完成：{}", summary.brief());
+        output_cache.put(Output::INFO { message })
+    }
+
     /// 像NAVM实例输入NAVM指令
-    fn input_cmd_to_vm(runtime: &mut R, line: &str) -> Result<()> {
+    /// * 🚩输入成功⇒记录「粘性指令」与「运行统计」
+    fn input_cmd_to_vm(
+        runtime: &mut R,
+        line: &str,
+        sticky_cmds: &mut StickyCmds,
+        metrics: &Mutex<RuntimeMetrics>,
+    ) -> Result<()> {
         let cmd =
             Cmd::parse(line).inspect_err(|e| eprintln_cli!([Error] "NAVM指令解析错误：{e}"))?;
         runtime
-            .input_cmd(cmd)
-            .inspect_err(|e| eprintln_cli!([Error] "NAVM指令执行错误：{e}"))
+            .input_cmd(cmd.clone())
+            .inspect_err(|e| eprintln_cli!([Error] "NAVM指令执行错误：{e}"))?;
+        sticky_cmds.record(&cmd);
+        lock_recovered(metrics).record_cmd();
+        Ok(())
     }
 
     /// 向NAVM实例输入NAL（输入）
@@ -393,34 +1135,183 @@ where
     /// * 🎯严格模式
     ///   * 📌要么是「有失败 + 非严格模式 ⇒ 仅报告错误」
     ///   * 📌要么是「有一个失败 + 严格模式 ⇒ 返回错误」
+    /// * 🎯批处理：若传入「结果统计」，则记录每条输入的结果
+    /// * 🎯测试报告：若传入「测试报告」，则记录每项预期的结果与耗时
+    /// * 🎯定位错误：若传入「来源文件」，则错误以`文件:行号:`开头，并附带出错的原始行
     /// * ⚠️可能有多行
+    #[allow(clippy::too_many_arguments)]
     fn input_nal_to_vm(
         runtime: &mut R,
         input: &str,
         output_cache: &mut OutputCache,
+        sticky_cmds: &mut StickyCmds,
+        metrics: &Mutex<RuntimeMetrics>,
         config: &RuntimeConfig,
+        interactivity: &InteractivityState,
         nal_root_path: &Path, // 📄从NAL文件加载⇒NAL文件所在路径；用户输入⇒配置文件所在路径
+        source: Option<&Path>,
+        summary: Option<&mut NALEvalSummary>,
+        report: Option<&mut TestReport>,
     ) -> Result<()> {
-        // 解析输入，并遍历解析出的每个NAL输入
-        for input in parse(input) {
+        // 从空的「包含栈」开始
+        Self::input_nal_to_vm_included(
+            runtime,
+            input,
+            output_cache,
+            sticky_cmds,
+            metrics,
+            config,
+            interactivity,
+            nal_root_path,
+            source,
+            summary,
+            report,
+            &mut vec![],
+        )
+    }
+
+    /// 向虚拟机置入NAL输入，并展开其中的`''include:`
+    /// * 🚩被包含的文件以其自身所在路径作为「NAL执行路径」
+    /// * 📌`include_stack`记录当前「正在展开」的文件（规范化路径），用于检测循环包含
+    #[allow(clippy::too_many_arguments)]
+    fn input_nal_to_vm_included(
+        runtime: &mut R,
+        input: &str,
+        output_cache: &mut OutputCache,
+        sticky_cmds: &mut StickyCmds,
+        metrics: &Mutex<RuntimeMetrics>,
+        config: &RuntimeConfig,
+        interactivity: &InteractivityState,
+        nal_root_path: &Path,
+        source: Option<&Path>,
+        mut summary: Option<&mut NALEvalSummary>,
+        mut report: Option<&mut TestReport>,
+        include_stack: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        // 「功能要求」区段 | 每个文件各自持有：被包含的文件不影响包含者
+        let mut requires = RequiresRegion::new(config.capabilities);
+        // 有来源文件⇒错误以`文件:行号:`开头，并附带原始行
+        let source_name = source.map(|path| path.display().to_string());
+        let locate = |e, line_no| match &source_name {
+            Some(source_name) => {
+                with_nal_source(e, source_name, line_no, source_line(input, line_no))
+            }
+            None => with_nal_line_no(e, line_no),
+        };
+        let inputs = match &source_name {
+            Some(source_name) => parse_with_source(input, source_name),
+            None => parse_with_line_no(input),
+        };
+        // 遍历解析出的每个NAL输入
+        for (line_no, input) in inputs {
+            // 收到终止信号⇒跳过其余输入，只执行「保存输出」：不丢失已缓存的输出
+            if is_shutdown_requested() && !matches!(input, Ok(NALInput::SaveOutputs { .. })) {
+                continue;
+            }
+            // CIN缺少所要求的功能⇒跳过（包括文件包含），不计入失败
+            if let Ok(nal) = &input {
+                if requires.skips(nal) {
+                    if let Some(summary) = summary.as_deref_mut() {
+                        summary.record_skipped(nal);
+                    }
+                    continue;
+                }
+            }
             // 尝试解析NAL输入
             match input {
                 // 错误⇒根据严格模式处理
                 Err(e) => {
                     // 无论是否严格模式，都报告错误
                     eprintln_cli!([Error] "解析NAL输入时发生错误：{e}");
+                    if let Some(summary) = summary.as_deref_mut() {
+                        summary.record_parse_error(&e);
+                    }
                     // 严格模式下提前返回
                     if_return! { config.strict_mode => Err(e) }
                 }
-                Ok(nal) => {
+                // 包含文件⇒读取并递归输入
+                Ok(NALInput::Include(path)) => {
+                    match read_included_nal(&path, nal_root_path, include_stack) {
+                        // 读取成功⇒递归展开 | 被包含文件中的错误已在递归中报告
+                        Ok((nal, included_path)) => {
+                            let root = included_path
+                                .parent()
+                                .unwrap_or(nal_root_path)
+                                .to_path_buf();
+                            include_stack.push(included_path.clone());
+                            let result = Self::input_nal_to_vm_included(
+                                runtime,
+                                &nal,
+                                output_cache,
+                                sticky_cmds,
+                                metrics,
+                                config,
+                                interactivity,
+                                &root,
+                                Some(&included_path),
+                                summary.as_deref_mut(),
+                                report.as_deref_mut(),
+                                include_stack,
+                            );
+                            include_stack.pop();
+                            result?;
+                        }
+                        // 读取失败⇒报告错误，严格模式下提前返回
+                        Err(e) => {
+                            let e = locate(e, line_no);
+                            eprintln_cli!([Error] "包含NAL文件{path:?}时发生错误：{e}");
+                            let result = Err(e);
+                            if let Some(summary) = summary.as_deref_mut() {
+                                summary.record(&NALInput::Include(path), &result);
+                            }
+                            if_return! { config.strict_mode => result }
+                        }
+                    }
+                }
+                // 报告运行状态⇒置入一条含统计的输出
+                // * ⚠️先生成输出再置入：置入时侦听器会锁定「运行统计」
+                Ok(NALInput::Status) => {
+                    let status = lock_recovered(metrics).status_output();
+                    output_cache.put(status)?
+                }
+                Ok(mut nal) => {
+                    // 未指定精度的预期⇒使用此前`''precision:`设置的精度，其次是配置中的默认精度
+                    nal.default_precision(interactivity.precision().or(config.precision_epoch));
+                    // 预期的输出类型⇒一并匹配其别名
+                    nal.default_type_aliases(&config.output_type_aliases);
                     // 尝试置入NAL输入 | 为了错误消息，必须克隆
-                    let put_result = put_nal(
-                        runtime,
-                        nal.clone(),
-                        output_cache,
-                        config.user_input,
+                    let start = Instant::now();
+                    let mut context = PutNalContext {
+                        interactivity,
                         nal_root_path,
-                    );
+                        set_ack: &config.set_ack,
+                        cyc_await: config.cyc_await.then(|| {
+                            CycAwait::new(get_cyc_ack_matcher_by_translators(&config.translators))
+                        }),
+                        requires: &mut requires,
+                    };
+                    let put_result = put_nal(runtime, nal.clone(), output_cache, &mut context)
+                        .map_err(|e| locate(e, line_no));
+                    // 记录结果
+                    if let Some(summary) = summary.as_deref_mut() {
+                        summary.record(&nal, &put_result);
+                    }
+                    // 记录测试报告 | 须在严格模式中止之前
+                    if let Some(report) = report.as_deref_mut() {
+                        report.record(
+                            include_stack.last().map(PathBuf::as_path),
+                            line_no,
+                            &nal,
+                            &put_result,
+                            start.elapsed(),
+                            output_cache,
+                        )?;
+                    }
+                    // 记录「粘性指令」与「运行统计」
+                    if let (Ok(..), NALInput::Put(cmd) | NALInput::Set(cmd)) = (&put_result, &nal) {
+                        sticky_cmds.record(cmd);
+                        lock_recovered(metrics).record_cmd();
+                    }
                     // 处理错误
                     if let Err(e) = put_result {
                         // 无论是否严格模式，都报告错误
@@ -450,8 +1341,8 @@ where
 /// * 📝从`ArcMutex<T>>`中拿取值的所有权：[`Arc::try_unwrap`] + [`Mutex::into_inner]
 ///   * 🔗参考：<https://users.rust-lang.org/t/move-out-of-arc-mutex-t/85940>
 pub fn restart_manager(
-    manager: RuntimeManager<impl VmRuntime + Send + Sync>,
-) -> Result<RuntimeManager<impl VmRuntime + Send + Sync>> {
+    manager: RuntimeManager<impl VmRuntimeBoost + Send + Sync>,
+) -> Result<RuntimeManager<impl VmRuntimeBoost + Send + Sync>> {
     // 尝试终止先前的虚拟机
     // ! ❌[`Arc::try_unwrap`]的返回值包括`VmRuntime`，所以连[`Debug`]都不支持
     // ! ❌【2024-04-02 20:33:01】目前测试中`Arc::into_inner`基本总是失败（线程里还有引用）
@@ -459,24 +1350,81 @@ pub fn restart_manager(
     // let old_runtime_mutex =
     // Arc::into_inner(manager.runtime).ok_or(anyhow!("runtime Arc解包失败"))?;
     // let mut old_runtime = old_runtime_mutex.into_inner()?;
-    let old_runtime = &mut *manager
-        .runtime
-        .lock()
-        .transform_err(|e| anyhow!("runtime Mutex解锁失败：{e:?}"))?;
-    old_runtime.terminate()?;
+    // * 🚩先前的子线程持锁时panic⇒恢复：仍需终止其中的虚拟机
+    let old_runtime = &mut *lock_recovered(&manager.runtime);
+    // * 🚩终止失败不影响重启：仅作警告
+    //   * 📄CIN自行退出后，终止不会报错（见[`KillOutcome`](babel_nar::process_io::KillOutcome)）
+    if let Err(e) = old_runtime.terminate() {
+        println_cli!([Warn] "终止先前的虚拟机失败：{e}");
+    }
 
     // 启动新的虚拟机
     let config_ref = &*manager.config;
     let new_runtime = launch_by_runtime_config(config_ref)?;
-    let new_manager = RuntimeManager::new(new_runtime, config_ref.clone());
+    let mut new_manager = RuntimeManager::new_named(
+        new_runtime,
+        config_ref.clone(),
+        manager.name.clone(),
+        manager.metrics.clone(),
+    );
+
+    // 继承「运行统计」「交互状态」，继承并重放「粘性指令」 | 在恢复正常运行之前
+    // * 📌Websocket连接不随重启断开：客户端数须沿用
+    new_manager.sticky_cmds = manager.sticky_cmds.clone();
+    new_manager.interactivity = manager.interactivity.clone();
+    {
+        let new_runtime = &mut *new_manager.runtime.lock().transform_err(error_anyhow)?;
+        let output_cache = &mut *OutputCache::unlock_arc_mutex(&mut new_manager.output_cache)?;
+        let sticky_cmds = &*new_manager.sticky_cmds.lock().transform_err(error_anyhow)?;
+        sticky_cmds.replay(new_runtime, output_cache)?;
+    }
 
     // 返回
     Ok(new_manager)
 }
 
+/// 生成「用户输入」的读取行迭代器
+/// * 🚩配置了历史记录文件⇒加载之，并在此后的输入中持续保存
+///   * 📌加载失败仅作警告：不影响输入
+/// * 📌迭代结束⇒用户主动退出
+pub fn user_input_lines(config: &RuntimeConfig) -> ReadlineIter {
+    let lines = ReadlineIter::default();
+    let Some(path) = &config.history_file else {
+        return lines;
+    };
+    match InputHistory::load(path, DEFAULT_HISTORY_SIZE) {
+        Ok(history) => lines.with_history(history),
+        Err(e) => {
+            eprintln_cli!([Warn] "无法加载输入历史「{}」：{e}", path.display());
+            lines
+        }
+    }
+}
+
+/// 管道输入读完后，等待「宽限期」
+/// * 🎯`echo "<A --> B>?" | babelnar_cli`：退出前留出CIN回答最后几条输入的时间
+/// * 🚩启用了Websocket服务或自动重启⇒不等待：仅结束用户输入，其它服务继续运行
+/// * 🚩收到终止信号、`is_finished`返回`true`⇒提前结束等待
+/// * ⚙️返回「是否仍保持运行」
+pub fn wait_after_piped_input(config: &RuntimeConfig, is_finished: impl Fn() -> bool) -> bool {
+    if config.websocket.is_some() || config.auto_restart {
+        println_cli!([Info] "管道输入已读完，其它服务继续运行");
+        return true;
+    }
+    let deadline = Instant::now() + Duration::from_millis(config.piped_input_grace);
+    while !is_shutdown_requested() && !is_finished() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        sleep(remaining.min(JOIN_POLL_INTERVAL));
+    }
+    false
+}
+
 /// 根据配置（的「是否重启」选项）管理（一系列）虚拟机实例
 pub fn loop_manage(
-    mut manager: RuntimeManager<impl VmRuntime + Send + Sync>,
+    mut manager: RuntimeManager<impl VmRuntimeBoost + Send + Sync>,
     config: &RuntimeConfig,
 ) -> Result<()> {
     match manager.manage() {
@@ -494,14 +1442,662 @@ pub fn loop_manage(
                     Ok(manager) => manager,
                     Err(e) => {
                         println_cli!([Error] "重启失败：{e}");
-                        return Err(anyhow!("NAVM运行时发生错误，且重启失败：{e}"));
+                        let e = anyhow!("NAVM运行时发生错误，且重启失败：{e}");
+                        return Err(CliError::Runtime(e).into());
                     }
                 };
                 // 重启之后继续循环
                 return loop_manage(new_manager, config);
             }
-            // 正常返回
+            // 不重启⇒以「运行时异常终止」返回
+            Err(CliError::Runtime(e).into())
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{LaunchConfigWebsocket, RuntimeMetricsSnapshot};
+    use babel_nar::{
+        runtimes::{Capabilities, FakeVm, FakeVmRuntime},
+        tests::test_runtime_config,
+    };
+    use narsese::{lexical_nse as nse, lexical_nse_task as nse_task};
+    use navm::vm::{VmLauncher, VmRuntime};
+
+    /// 测试/原始输入
+    /// * 🎯不经NAVM指令、NAL解析，原样送达运行时
+    ///   * 📄`*stats`既非NAVM指令，也非合法的NAL输入
+    #[test]
+    fn raw_input_passthrough() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Raw,
+            ..test_config()
+        };
+        let mut runtime = FakeVm::new().launch()?;
+        let mut output_cache = OutputCache::default();
+        let mut sticky_cmds = StickyCmds::default();
+        let lines = ["*stats", "/reset", "REG left", "'comment"];
+        for line in lines {
+            RuntimeManager::input_line_to_vm(
+                &mut runtime,
+                line,
+                &config,
+                &InteractivityState::default(),
+                &mut output_cache,
+                &mut sticky_cmds,
+                &Mutex::default(),
+                &config.config_path,
+            )?;
+        }
+        // 原样回显，未解析出Narsese
+        let received = runtime.drain_outputs()?;
+        assert!(received
+            .iter()
+            .all(|o| matches!(o, Output::IN { narsese: None, .. })));
+        let received = received.iter().map(Output::raw_content).collect::<Vec<_>>();
+        assert_eq!(received, lines);
+        // 未作为NAVM指令记录
+        assert_eq!(sticky_cmds.iter().count(), 0);
+        Ok(())
+    }
+
+    /// 测试/严格模式下的错误附带行号
+    #[test]
+    fn strict_mode_line_no() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Nal,
+            strict_mode: true,
+            ..test_config()
+        };
+        let mut runtime = FakeVm::new().launch()?;
+        let nal = "<A --> B>.\n\n''expect-contains: OUT <X --> Y>.";
+        let e = put_test_nal(&mut runtime, nal, &config).expect_err("严格模式下预期失败应返回错误");
+        assert!(e.to_string().starts_with("第3行："), "{e}");
+        Ok(())
+    }
+
+    /// 测试/来自文件的NAL：错误附带文件名、行号与原始行
+    /// * 🎯严格模式中止时，所返回的错误即为进程退出时的错误信息
+    #[test]
+    fn strict_mode_source() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Nal,
+            strict_mode: true,
+            ..test_config()
+        };
+        let input = |nal| {
+            let mut runtime = FakeVm::new().launch()?;
+            let args = TestNalArgs {
+                source: Some(Path::new("tests/bad.nal")),
+                ..Default::default()
+            };
+            put_test_nal_with(&mut runtime, nal, &config, args)
+        };
+        // 预期失败
+        let nal = "<A --> B>.\n<B --> C>.\n\n1\n\n'注释\n''expect-contains: OUT <X --> Y>.";
+        let e = input(nal).expect_err("严格模式下预期失败应返回错误");
+        assert!(
+            e.to_string()
+                .starts_with("tests/bad.nal:7: 「''expect-contains: OUT <X --> Y>.」："),
+            "{e}"
+        );
+        // 解析失败
+        let nal = "<A --> B>.\n<B --> C>.\n\n1\n\n'注释\n<A --> >.";
+        let e = input(nal).expect_err("严格模式下解析失败应返回错误");
+        assert!(
+            e.to_string()
+                .starts_with("tests/bad.nal:7: 「<A --> >.」解析失败："),
+            "{e}"
+        );
+        Ok(())
+    }
+
+    /// 测试/报告运行状态
+    /// * 🚩`''status`置入一条含统计JSON的INFO输出
+    /// * 🚩NAL测试中途拉取的输出亦被统计：由输出缓存的侦听器记录
+    #[test]
+    fn status_nal() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Nal,
+            ..test_config()
+        };
+        let mut runtime = FakeVm::new().launch()?;
+        let metrics = ArcMutex::<RuntimeMetrics>::default();
+        let output_cache =
+            RuntimeManager::<FakeVmRuntime>::new_output_cache(&config, None, &metrics);
+        let output_cache = &mut *lock_recovered(&output_cache);
+        let nal = "<A --> B>.\n''expect-contains: IN <A --> B>.\n'/VOL 0\n''status";
+        let args = TestNalArgs {
+            output_cache: Some(output_cache),
+            metrics: Some(&*metrics),
+            ..Default::default()
+        };
+        put_test_nal_with(&mut runtime, nal, &config, args)?;
+        let mut status = None;
+        output_cache.for_each(|output| {
+            if let Output::INFO { message } = output {
+                status = serde_json::from_str::<RuntimeMetricsSnapshot>(message).ok();
+            }
+            ControlFlow::<()>::Continue(())
+        })?;
+        let status = status.expect("应有运行状态输出");
+        assert_eq!(status.cmds_sent, 2);
+        assert_eq!(status.output_counts["IN"], 1);
+        Ok(())
+    }
+
+    /// 测试/输出类型的别名
+    /// * 🚩脚本化的模拟运行时：以别名`EXPECT`输出，预期中使用标准名`ANTICIPATE`
+    #[test]
+    fn output_type_aliases_nal() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Nal,
+            strict_mode: true,
+            output_type_aliases: [("ANTICIPATE".into(), vec!["EXPECT".into()])].into(),
+            ..test_config()
+        };
+        let mut runtime = manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::CYC(1),
+                [Output::UNCLASSIFIED {
+                    r#type: "EXPECT".into(),
+                    content: "EXPECT: <A --> B>".into(),
+                    narsese: Some(nse!(<A --> B>)),
+                }],
+            )
+        )
+        .launch()?;
+        let mut input = |nal: &str| put_test_nal(&mut runtime, nal, &config);
+        input("1\n''expect-contains: ANTICIPATE <A --> B>")?;
+        // 未声明的别名⇒不匹配
+        assert!(input("1\n''expect-contains: ANTICIPATION <A --> B>").is_err());
+        Ok(())
+    }
+
+    /// 测试/抑制输入回显
+    /// * 🚩模拟运行时：回显每条输入，并对Narsese输入响应一条`OUT`
+    /// * 📌`''await-echo`不受影响
+    #[test]
+    fn suppress_input_echo() -> Result<()> {
+        let cache_len = |suppress_input_echo: bool| -> Result<usize> {
+            let config = RuntimeConfig {
+                input_mode: InputMode::Nal,
+                strict_mode: true,
+                suppress_input_echo,
+                ..test_config()
+            };
+            let mut runtime = manipulate!(
+                FakeVm::new()
+                => .respond(
+                    Cmd::NSE(nse_task!(<A --> B>.)),
+                    [Output::OUT {
+                        content_raw: "<B --> A>.".into(),
+                        narsese: Some(nse!(<B --> A>.)),
+                    }],
+                )
+            )
+            .launch()?;
+            let output_cache =
+                RuntimeManager::<FakeVmRuntime>::new_output_cache(&config, None, &Arc::default());
+            let output_cache = &mut *lock_recovered(&output_cache);
+            let nal = "<A --> B>.\n''await-echo: <A --> B>.\n''expect-contains: OUT <B --> A>.";
+            let args = TestNalArgs {
+                output_cache: Some(output_cache),
+                ..Default::default()
+            };
+            put_test_nal_with(&mut runtime, nal, &config, args)?;
+            Ok(output_cache.borrow_inner().len())
+        };
+        // 仅少了回显的那一条
+        assert_eq!(cache_len(false)?, 2);
+        assert_eq!(cache_len(true)?, 1);
+        Ok(())
+    }
+
+    /// 测试/元指令：重新加载、加载NAL
+    /// * 🚩每次重新加载都从磁盘读取：修改文件后，结果随之改变
+    /// * 📌不论输入模式
+    #[test]
+    fn reload_nal_meta_command() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("babelnar_reload_{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let nal_path = dir.join("prelude.nal");
+        std::fs::write(&nal_path, "1\n''expect-contains: OUT <A --> B>.")?;
+        std::fs::write(dir.join("other.nal"), "''expect-contains: OUT <X --> Y>.")?;
+        let config = RuntimeConfig {
+            config_path: dir.clone(),
+            prelude_nal: vec![LaunchConfigPreludeNAL::File(nal_path.clone())],
+            ..test_config()
+        };
+        let mut runtime = manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::CYC(1),
+                [Output::OUT {
+                    content_raw: "<A --> B>.".into(),
+                    narsese: Some(nse!(<A --> B>.)),
+                }],
+            )
+        )
+        .launch()?;
+        let mut output_cache = OutputCache::default();
+        let mut input = |line: &str| -> Result<String> {
+            RuntimeManager::input_line_to_vm(
+                &mut runtime,
+                line,
+                &config,
+                &InteractivityState::default(),
+                &mut output_cache,
+                &mut StickyCmds::default(),
+                &Mutex::default(),
+                &config.config_path,
+            )?;
+            // 最后一条`INFO`即为汇总
+            let mut summary = String::new();
+            output_cache.for_each(|output| {
+                if let Output::INFO { message } = output {
+                    summary = message.clone();
+                }
+                ControlFlow::<()>::Continue(())
+            })?;
+            Ok(summary)
+        };
+        let summary = input(META_RELOAD_NAL)?;
+        assert!(summary.contains("1项预期通过，0项预期未通过"), "{summary}");
+        // 修改文件后重新加载
+        std::fs::write(&nal_path, "''expect-contains: OUT <C --> D>.")?;
+        let summary = input(META_RELOAD_NAL)?;
+        assert!(summary.contains("0项预期通过，1项预期未通过"), "{summary}");
+        // 加载其它文件 | 相对配置文件所在目录
+        let summary = input(&format!("{META_LOAD} other.nal"))?;
+        assert!(summary.contains("0项预期通过，1项预期未通过"), "{summary}");
+        assert!(input(&format!("{META_LOAD} not-exist.nal")).is_err());
+        assert!(input(META_LOAD).is_err());
+        // 配置文件所在目录之外的文件⇒拒绝
+        let outside = dir.with_extension("outside.nal");
+        std::fs::write(&outside, "''expect-contains: OUT <X --> Y>.")?;
+        let outside_name = outside.file_name().unwrap().to_string_lossy();
+        for path in [format!("../{outside_name}"), outside.display().to_string()] {
+            let e = input(&format!("{META_LOAD} {path}")).expect_err("应拒绝目录外的文件");
+            assert!(e.to_string().contains("只能加载"), "{e}");
+        }
+        std::fs::remove_file(&outside)?;
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// 测试/元指令：不同实例同时重新加载
+    /// * 🎯互不阻塞：一个实例的重新加载未完成时，另一个实例照常重新加载
+    #[test]
+    fn reload_nal_concurrent_instances() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("babelnar_reload_mt_{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let nal_path = dir.join("prelude.nal");
+        std::fs::write(&nal_path, "''sleep: 300ms")?;
+        let config = RuntimeConfig {
+            config_path: dir.clone(),
+            prelude_nal: vec![LaunchConfigPreludeNAL::File(nal_path)],
+            ..test_config()
+        };
+        let reload = |config: &RuntimeConfig| -> Result<()> {
+            RuntimeManager::input_line_to_vm(
+                &mut FakeVm::new().launch()?,
+                META_RELOAD_NAL,
+                config,
+                &InteractivityState::default(),
+                &mut OutputCache::default(),
+                &mut StickyCmds::default(),
+                &Mutex::default(),
+                &config.config_path,
+            )
+        };
+        thread::scope(|s| {
+            let other = s.spawn(|| reload(&config));
+            sleep(Duration::from_millis(50));
+            reload(&config)?;
+            other.join().expect("重新加载线程panic")
+        })?;
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// 测试/功能要求
+    /// * 🚩模拟运行时不支持操作：区段内的预期被跳过，严格模式下亦不失败
+    #[test]
+    fn requires_skipped_nal() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Nal,
+            strict_mode: true,
+            capabilities: Capabilities {
+                operations: false,
+                ..Capabilities::ALL
+            },
+            ..test_config()
+        };
+        let mut runtime = FakeVm::new().launch()?;
+        let mut summary = NALEvalSummary::default();
+        let nal = "''requires: operations\n\
+                   <(*, {SELF}) --> ^left>!\n\
+                   ''expect-contains: EXE <(*, {SELF}) --> ^left>\n\
+                   ''expect-cycle(10, 1): EXE <(*, {SELF}) --> ^left>\n\
+                   ''end-requires\n\
+                   1\n\
+                   ''expect-cycle-not(5, 1): EXE <(*, {SELF}) --> ^left>";
+        let args = TestNalArgs {
+            summary: Some(&mut summary),
+            ..Default::default()
+        };
+        put_test_nal_with(&mut runtime, nal, &config, args)?;
+        assert_eq!(summary.skipped, 2);
+        assert_eq!(summary.passed, 1);
+        assert!(summary.is_success(), "{summary:?}");
+        Ok(())
+    }
+
+    /// 测试/条件终止
+    /// * 🎯`if-no-user`：既无用户输入、亦无Websocket客户端时才终止
+    /// * 🎯`if-no-client`：仅看Websocket客户端，不论用户输入
+    #[test]
+    fn terminate_interactivity() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Nal,
+            ..test_config()
+        };
+        let is_terminated = |nal: &str, interactivity: &InteractivityState| -> Result<bool> {
+            let mut runtime = FakeVm::new().launch()?;
+            let args = TestNalArgs {
+                interactivity: Some(interactivity),
+                ..Default::default()
+            };
+            put_test_nal_with(&mut runtime, nal, &config, args)?;
+            Ok(matches!(runtime.status(), VmStatus::Terminated(..)))
+        };
+        let if_no_user = "''terminate(if-no-user)";
+        let if_no_client = "''terminate(if-no-client)";
+        // 仅Websocket：禁用用户输入，但有客户端连接⇒均不终止
+        let websocket_only = InteractivityState::new(false);
+        websocket_only.client_connected();
+        assert!(!is_terminated(if_no_user, &websocket_only)?);
+        assert!(!is_terminated(if_no_client, &websocket_only)?);
+        // 客户端断开⇒均终止
+        websocket_only.client_disconnected();
+        assert!(is_terminated(if_no_user, &websocket_only)?);
+        assert!(is_terminated(if_no_client, &websocket_only)?);
+        // 仅用户输入：`if-no-user`不终止，`if-no-client`终止
+        let user_only = InteractivityState::new(true);
+        assert!(!is_terminated(if_no_user, &user_only)?);
+        assert!(is_terminated(if_no_client, &user_only)?);
+        Ok(())
+    }
+
+    /// 测试/测试报告
+    /// * 🚩脚本化的模拟运行时：一项预期通过、一项预期未通过
+    /// * 📌严格模式下，导致中止的预期亦被记录
+    #[test]
+    fn test_report_junit_xml() -> Result<()> {
+        let config = RuntimeConfig {
+            input_mode: InputMode::Nal,
+            strict_mode: true,
+            ..test_config()
+        };
+        let mut runtime = manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::NSE(nse_task!(<A --> C>?)),
+                [Output::ANSWER {
+                    content_raw: "<A --> C>.".into(),
+                    narsese: Some(nse!(<A --> C>.)),
+                }],
+            )
+        )
+        .launch()?;
+        let mut report = TestReport::default();
+        report.set_source(Some(Path::new("test.nal")));
+        let nal = "<A --> C>?\n''expect-contains: ANSWER <A --> C>.\n''expect-contains: ANSWER <X --> Y>.";
+        let args = TestNalArgs {
+            report: Some(&mut report),
+            ..Default::default()
+        };
+        put_test_nal_with(&mut runtime, nal, &config, args)
+            .expect_err("严格模式下预期失败应返回错误");
+
+        let xml = report.to_junit_xml();
+        assert!(xml.starts_with("<?xml"), "{xml}");
+        // 各层标签成对出现
+        for tag in [
+            "testsuites",
+            "testsuite",
+            "testcase",
+            "failure",
+            "system-out",
+        ] {
+            let opened =
+                xml.matches(&format!("<{tag} ")).count() + xml.matches(&format!("<{tag}>")).count();
+            let closed = xml.matches(&format!("</{tag}>")).count();
+            assert_eq!(opened, closed, "标签<{tag}>不成对：{xml}");
+        }
+        assert!(
+            xml.contains("<testsuites tests=\"2\" failures=\"1\""),
+            "{xml}"
+        );
+        assert_eq!(xml.matches("<testcase ").count(), 2, "{xml}");
+        // 第一项：通过，附带匹配到的输出
+        let (passed, failed) = xml.split_once("</testcase>").unwrap();
+        assert!(passed.contains("file=\"test.nal\" line=\"2\""), "{xml}");
+        assert!(
+            passed.contains("<system-out>") && !passed.contains("<failure"),
+            "{xml}"
+        );
+        assert!(passed.contains("&lt;A --&gt; C&gt;."), "{xml}");
+        // 第二项：未通过，附带失败原因
+        assert!(failed.contains("line=\"3\""), "{xml}");
+        assert!(failed.contains("<failure message=\"第3行："), "{xml}");
+        assert!(!failed.contains("<system-out>"), "{xml}");
+        Ok(())
+    }
+
+    /// 在拉取输出时panic的运行时
+    /// * 🎯模拟「读取输出」子线程的panic
+    struct PanickingRuntime {
+        status: VmStatus,
+    }
+
+    impl VmRuntime for PanickingRuntime {
+        fn input_cmd(&mut self, _cmd: Cmd) -> Result<()> {
+            Ok(())
+        }
+
+        fn fetch_output(&mut self) -> Result<Output> {
+            panic!("拉取输出时崩溃")
+        }
+
+        fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+            panic!("拉取输出时崩溃")
+        }
+
+        fn status(&self) -> &VmStatus {
+            &self.status
+        }
+
+        fn terminate(&mut self) -> Result<()> {
+            self.status = VmStatus::Terminated(Ok(()));
             Ok(())
         }
     }
+
+    impl VmRuntimeBoost for PanickingRuntime {}
+
+    /// 测试/子线程panic⇒终止运行，而非挂起
+    /// * 🚩Websocket服务线程不会自行结束：须由「读取输出」线程的panic终止等待
+    #[test]
+    fn manage_thread_panic() -> Result<()> {
+        let config = RuntimeConfig {
+            websocket: Some(LaunchConfigWebsocket {
+                host: "127.0.0.1".into(),
+                port: 0,
+                ..Default::default()
+            }),
+            ..test_config()
+        };
+        let runtime = PanickingRuntime {
+            status: VmStatus::Running,
+        };
+        let mut manager = RuntimeManager::new(runtime, config);
+        let runtime = manager.runtime.clone();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || sender.send(manager.manage().map(|_| ())));
+        let result = receiver.recv_timeout(Duration::from_secs(5))?;
+        let e = result.expect_err("子线程panic应导致异常退出").to_string();
+        assert!(
+            e.contains("读取输出") && e.contains("拉取输出时崩溃"),
+            "{e}"
+        );
+        // 虚拟机已终止
+        assert!(matches!(
+            lock_recovered(&runtime).status(),
+            VmStatus::Terminated(..)
+        ));
+        Ok(())
+    }
+
+    /// 测试/从「中毒」的互斥锁中恢复
+    #[test]
+    fn lock_poisoned() {
+        let mutex = Arc::new(Mutex::new(1));
+        let poisoner = mutex.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("持锁时崩溃");
+        })
+        .join();
+        assert!(mutex.is_poisoned());
+        *lock_recovered(&mutex) += 1;
+        assert_eq!(*lock_recovered(&mutex), 2);
+    }
+
+    /// 测试用配置
+    /// * 🚩NAVM指令输入，启用自动重启
+    pub(crate) fn test_config() -> RuntimeConfig {
+        RuntimeConfig {
+            input_mode: InputMode::Cmd,
+            auto_restart: true,
+            ..test_runtime_config()
+        }
+    }
+
+    /// 测试用「置入NAL」的可选参数
+    /// * 🚩未指定⇒使用默认值：空的缓存与统计、默认的交互状态、无来源、不记录
+    #[derive(Default)]
+    struct TestNalArgs<'a> {
+        output_cache: Option<&'a mut OutputCache>,
+        metrics: Option<&'a Mutex<RuntimeMetrics>>,
+        interactivity: Option<&'a InteractivityState>,
+        source: Option<&'a Path>,
+        summary: Option<&'a mut NALEvalSummary>,
+        report: Option<&'a mut TestReport>,
+    }
+
+    /// 测试工具：以默认参数向运行时置入NAL
+    /// * 📌NAL执行路径为配置文件所在路径
+    fn put_test_nal<R>(runtime: &mut R, nal: &str, config: &RuntimeConfig) -> Result<()>
+    where
+        R: VmRuntimeBoost + Send + Sync + 'static,
+    {
+        put_test_nal_with(runtime, nal, config, TestNalArgs::default())
+    }
+
+    /// 测试工具：以指定的可选参数向运行时置入NAL
+    fn put_test_nal_with<R>(
+        runtime: &mut R,
+        nal: &str,
+        config: &RuntimeConfig,
+        args: TestNalArgs,
+    ) -> Result<()>
+    where
+        R: VmRuntimeBoost + Send + Sync + 'static,
+    {
+        RuntimeManager::input_nal_to_vm(
+            runtime,
+            nal,
+            args.output_cache.unwrap_or(&mut OutputCache::default()),
+            &mut StickyCmds::default(),
+            args.metrics.unwrap_or(&Mutex::default()),
+            config,
+            args.interactivity.unwrap_or(&InteractivityState::default()),
+            &config.config_path,
+            args.source,
+            args.summary,
+            args.report,
+        )
+    }
+
+    /// 测试/粘性指令的记录与重放
+    /// * 🚩经由NAVM指令与NAL输入两种途径输入，再向「新的」模拟运行时重放
+    /// * 📌重放顺序：`REG`按首次注册顺序，`VOL`仅保留最后一次
+    #[test]
+    fn sticky_cmds_replay() -> Result<()> {
+        let config = test_config();
+        let mut runtime = FakeVm::new().launch()?;
+        let mut output_cache = OutputCache::default();
+        let mut sticky_cmds = StickyCmds::default();
+        for line in [
+            "REG left",
+            "VOL 0",
+            "NSE <A --> B>.",
+            "/'/REG right",
+            "REG left",
+            "VOL 5",
+        ] {
+            RuntimeManager::input_line_to_vm(
+                &mut runtime,
+                line,
+                &config,
+                &InteractivityState::default(),
+                &mut output_cache,
+                &mut sticky_cmds,
+                &Mutex::default(),
+                &config.config_path,
+            )?;
+        }
+        let expected = [
+            Cmd::REG {
+                name: "left".into(),
+            },
+            Cmd::REG {
+                name: "right".into(),
+            },
+            Cmd::VOL(5),
+        ];
+        assert_eq!(sticky_cmds.iter().cloned().collect::<Vec<_>>(), expected);
+
+        // 向新的运行时重放 | 以预设响应标记每条指令的到达顺序
+        let mut new_runtime = manipulate!(
+            FakeVm::new()
+            => .respond(expected[0].clone(), [Output::OTHER { content: "left".into() }])
+            => .respond(expected[1].clone(), [Output::OTHER { content: "right".into() }])
+            => .respond(expected[2].clone(), [Output::OTHER { content: "vol".into() }])
+        )
+        .launch()?;
+        let mut output_cache = OutputCache::default();
+        sticky_cmds.replay(&mut new_runtime, &mut output_cache)?;
+        let received = new_runtime
+            .drain_outputs()?
+            .iter()
+            .map(Output::raw_content)
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(received, ["left", "right", "vol"]);
+        // 每条重放的指令都有一条INFO输出
+        let infos = output_cache
+            .borrow_inner()
+            .iter()
+            .filter(|o| matches!(o, Output::INFO { .. }))
+            .count();
+        assert_eq!(infos, expected.len());
+        Ok(())
+    }
 }
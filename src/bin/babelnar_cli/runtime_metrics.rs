@@ -0,0 +1,185 @@
+//! 运行统计
+//! * 🎯长时间运行的智能体：查询各类输出的数目、输入输出的吞吐量
+//! * 📌查询方式
+//!   * 📄NAL魔法注释`''status`：向输出缓存置入一条含统计JSON的`INFO`输出
+//!   * 📄Websocket文本命令`/status`：仅回复发出请求的客户端
+
+use navm::output::Output;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// 计算「每秒输出数」的滑动窗口
+pub const METRICS_RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// 运行统计的收集器
+/// * 🚩输出：由「读取输出」线程逐条记录
+/// * 🚩指令：成功输入NAVM指令后记录
+/// * 📌自动重启后由新的管理者继承：统计整个会话，而非单个CIN进程
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeMetrics {
+    /// 各类型输出的数目 | 键：输出类型名
+    output_counts: BTreeMap<String, usize>,
+    /// 已成功输入的NAVM指令数
+    cmds_sent: usize,
+    /// 滑动窗口内各输出的记录时间
+    recent_outputs: VecDeque<Instant>,
+}
+
+/// 运行统计的快照
+/// * 🎯序列化为JSON，供`''status`与`/status`回报
+/// * 📄`{"outputCounts":{"ANSWER":1,"IN":3},"totalOutputs":4,"cmdsSent":3,"outputsPerSecond":0.4}`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeMetricsSnapshot {
+    /// 各类型输出的数目
+    pub output_counts: BTreeMap<String, usize>,
+    /// 输出总数
+    pub total_outputs: usize,
+    /// 已成功输入的NAVM指令数
+    pub cmds_sent: usize,
+    /// 滑动窗口内的每秒输出数
+    pub outputs_per_second: f64,
+}
+
+impl RuntimeMetrics {
+    /// 记录一条输出
+    pub fn record_output(&mut self, output: &Output) {
+        self.record_output_at(output, Instant::now())
+    }
+
+    /// 记录一条在指定时刻产生的输出
+    /// * 🎯测试中可使用脚本化的时间
+    /// * 🚩同时清理滑动窗口之外的记录
+    pub fn record_output_at(&mut self, output: &Output, at: Instant) {
+        *self
+            .output_counts
+            .entry(output.type_name().to_string())
+            .or_default() += 1;
+        self.recent_outputs.push_back(at);
+        while let Some(earliest) = self.recent_outputs.front() {
+            match at.saturating_duration_since(*earliest) > METRICS_RATE_WINDOW {
+                true => self.recent_outputs.pop_front(),
+                false => break,
+            };
+        }
+    }
+
+    /// 记录一条成功输入的NAVM指令
+    pub fn record_cmd(&mut self) {
+        self.cmds_sent += 1;
+    }
+
+    /// 生成当前的快照
+    pub fn snapshot(&self) -> RuntimeMetricsSnapshot {
+        self.snapshot_at(Instant::now())
+    }
+
+    /// 生成指定时刻的快照
+    /// * 🚩每秒输出数：滑动窗口内的输出数÷窗口时长
+    pub fn snapshot_at(&self, now: Instant) -> RuntimeMetricsSnapshot {
+        let recent = self
+            .recent_outputs
+            .iter()
+            .filter(|at| now.saturating_duration_since(**at) <= METRICS_RATE_WINDOW)
+            .count();
+        RuntimeMetricsSnapshot {
+            output_counts: self.output_counts.clone(),
+            total_outputs: self.output_counts.values().sum(),
+            cmds_sent: self.cmds_sent,
+            outputs_per_second: recent as f64 / METRICS_RATE_WINDOW.as_secs_f64(),
+        }
+    }
+
+    /// 生成「报告运行状态」的输出
+    /// * 🚩`INFO`输出，内容为快照的JSON
+    pub fn status_output(&self) -> Output {
+        let message = serde_json::to_string(&self.snapshot())
+            .unwrap_or_else(|e| format!("无法序列化运行统计：{e}"));
+        Output::INFO { message }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/脚本化的输出序列
+    #[test]
+    fn scripted_outputs() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut metrics = RuntimeMetrics::default();
+        let script = [
+            (
+                0,
+                Output::IN {
+                    content: "<A --> B>.".into(),
+                    narsese: None,
+                },
+            ),
+            (
+                1,
+                Output::ANSWER {
+                    content_raw: "<A --> B>.".into(),
+                    narsese: None,
+                },
+            ),
+            (
+                2,
+                Output::COMMENT {
+                    content: "注释".into(),
+                },
+            ),
+            (
+                11,
+                Output::ERROR {
+                    description: "错误".into(),
+                },
+            ),
+            (
+                12,
+                Output::ANSWER {
+                    content_raw: "<A --> B>.".into(),
+                    narsese: None,
+                },
+            ),
+        ];
+        for (secs, output) in &script {
+            metrics.record_cmd();
+            metrics.record_output_at(output, at(*secs));
+        }
+        let snapshot = metrics.snapshot_at(at(12));
+        assert_eq!(snapshot.total_outputs, 5);
+        assert_eq!(snapshot.cmds_sent, 5);
+        assert_eq!(snapshot.output_counts["ANSWER"], 2);
+        assert_eq!(snapshot.output_counts["COMMENT"], 1);
+        assert_eq!(snapshot.output_counts["ERROR"], 1);
+        // 窗口：第2~12秒的输出
+        assert_eq!(snapshot.outputs_per_second, 0.3);
+        // 长时间无输出⇒速率归零，计数保留
+        let snapshot = metrics.snapshot_at(at(60));
+        assert_eq!(snapshot.outputs_per_second, 0.0);
+        assert_eq!(snapshot.total_outputs, 5);
+    }
+
+    /// 测试/快照的JSON序列化
+    #[test]
+    fn snapshot_json() {
+        let mut metrics = RuntimeMetrics::default();
+        metrics.record_output(&Output::INFO { message: "".into() });
+        let message = match metrics.status_output() {
+            Output::INFO { message } => message,
+            output => panic!("不是INFO输出：{output:?}"),
+        };
+        assert!(
+            message.contains("\"outputCounts\":{\"INFO\":1}"),
+            "{message}"
+        );
+        let snapshot = serde_json::from_str::<RuntimeMetricsSnapshot>(&message).unwrap();
+        assert_eq!(snapshot, metrics.snapshot_at(Instant::now()));
+    }
+}
@@ -0,0 +1,497 @@
+//! 多实例监管
+//! * 🎯在同一个CLI进程中同时运行多个CIN：如实时对比OpenNARS与ONA
+//! * 📌输入路由
+//!   * 📄`@ona NSE <A --> B>.`：仅输入到名为`ona`的实例
+//!   * 📄`@all CYC 10`：输入到所有（未终止的）实例
+//!   * 📄无前缀：输入到默认（首个）实例
+//! * 📌输出标注：打印与Websocket回传的输出，均附带实例名称
+//! * 📌单个实例终止，不影响其它实例
+
+use crate::{
+    lock_recovered, spawn_ws_server_supervised, user_input_lines, wait_after_piped_input,
+    InputMode, RuntimeConfig, RuntimeManager, JOIN_POLL_INTERVAL,
+};
+use anyhow::{anyhow, Result};
+use babel_nar::{
+    cli_support::{
+        error_handling_boost::error_anyhow, io::readline_iter::is_stdin_piped,
+        shutdown::is_shutdown_requested,
+    },
+    eprintln_cli, if_let_err_eprintln_cli,
+    output_handler::json::output_to_json,
+    println_cli,
+    runtimes::VmRuntimeBoost,
+};
+use nar_dev_utils::{if_return, ResultBoost};
+use navm::output::Output;
+use std::{
+    ops::ControlFlow::{Break, Continue},
+    sync::Arc,
+    thread::{self, sleep, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// 路由前缀
+/// * 📄`@ona NSE <A --> B>.`
+pub const ROUTE_PREFIX: char = '@';
+
+/// 「所有实例」的路由名
+/// * 📄`@all CYC 10`
+pub const ROUTE_ALL: &str = "all";
+
+/// 所有实例均已终止后，等待全局子线程结束的最长时间
+/// * 🎯Websocket服务、用户输入可能仍在阻塞：不无限等待
+const GLOBAL_THREADS_JOIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 输入路由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route<'a> {
+    /// 默认（首个）实例
+    Default,
+    /// 所有实例
+    All,
+    /// 指定名称的实例
+    Named(&'a str),
+}
+
+/// 解析一行输入的路由前缀
+/// * ⚙️返回「路由」与「去掉前缀后的输入」
+/// * 🚩无前缀⇒[`Route::Default`]，原样返回输入
+pub fn parse_route(line: &str) -> (Route<'_>, &str) {
+    let Some(tail) = line.strip_prefix(ROUTE_PREFIX) else {
+        return (Route::Default, line);
+    };
+    let (name, rest) = tail.split_once(char::is_whitespace).unwrap_or((tail, ""));
+    let route = match name {
+        ROUTE_ALL => Route::All,
+        name => Route::Named(name),
+    };
+    (route, rest.trim_start())
+}
+
+/// 将NAVM输出转换为JSON字符串，并附带实例名
+/// * 🚩有实例名⇒在JSON对象中追加`instance`字段；无实例名⇒与[`output_to_json`]一致
+pub fn output_to_json_tagged(output: &Output, instance: Option<&str>) -> String {
+    let mut value = output_to_json(output);
+    if let (Some(instance), Some(object)) = (instance, value.as_object_mut()) {
+        object.insert("instance".into(), instance.into());
+    }
+    value.to_string()
+}
+
+/// 取出已结束的子线程，并抛出其抛出的错误
+/// * 🚩未结束的子线程保持原样
+fn join_finished(threads: &mut [Option<JoinHandle<Result<()>>>]) -> Result<()> {
+    for slot in threads.iter_mut() {
+        if let Some(thread) = slot.take_if(|thread| thread.is_finished()) {
+            thread.join().transform_err(error_anyhow)??;
+        }
+    }
+    Ok(())
+}
+
+/// 多实例的监管者
+/// * 🎯持有所有实例的管理者，统一处理用户输入、Websocket与生命周期
+/// * 📌首个实例为「默认实例」：接收无路由前缀的输入，并提供用户输入、Websocket等全局配置
+#[derive(Debug)]
+pub struct Supervisor<R>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    /// 所有实例的管理者
+    managers: Vec<RuntimeManager<R>>,
+}
+
+/// 手动实现[`Clone`]：仅拷贝共享引用
+impl<R> Clone for Supervisor<R>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            managers: self.managers.clone(),
+        }
+    }
+}
+
+impl<R> Supervisor<R>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    /// 构造函数
+    /// * ⚠️至少需要一个实例
+    pub fn new(managers: Vec<RuntimeManager<R>>) -> Result<Self> {
+        match managers.is_empty() {
+            true => Err(anyhow!("至少需要一个实例")),
+            false => Ok(Self { managers }),
+        }
+    }
+
+    /// 所有实例的管理者
+    pub fn managers(&self) -> &[RuntimeManager<R>] {
+        &self.managers
+    }
+
+    /// 默认实例的配置
+    /// * 🎯用户输入、Websocket等全局配置
+    pub fn default_config(&self) -> &Arc<RuntimeConfig> {
+        &self.managers[0].config
+    }
+
+    /// 所有实例是否均已终止
+    pub fn is_all_terminated(&self) -> bool {
+        self.managers.iter().all(RuntimeManager::is_terminated)
+    }
+
+    /// 是否为多实例
+    /// * 🚩各实例均具名；单实例的管理者没有名称
+    pub fn is_multi_instance(&self) -> bool {
+        self.managers.iter().any(|manager| manager.name().is_some())
+    }
+
+    /// 解析一行输入的路由前缀
+    /// * 🚩仅多实例解析路由：单实例下，`@`开头的输入原样送达CIN
+    pub fn parse_route<'a>(&self, line: &'a str) -> (Route<'a>, &'a str) {
+        match self.is_multi_instance() {
+            true => parse_route(line),
+            false => (Route::Default, line),
+        }
+    }
+
+    /// 按路由找到目标实例
+    /// * 🚩未知的实例名⇒报错，并列出可用的实例
+    pub fn route(&self, route: Route) -> Result<Vec<&RuntimeManager<R>>> {
+        match route {
+            Route::Default => Ok(vec![&self.managers[0]]),
+            Route::All => Ok(self.managers.iter().collect()),
+            Route::Named(name) => match self.managers.iter().find(|m| m.name() == Some(name)) {
+                Some(manager) => Ok(vec![manager]),
+                None => Err(anyhow!(
+                    "未知的实例「{name}」，可用的实例：{}",
+                    self.managers
+                        .iter()
+                        .filter_map(RuntimeManager::name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            },
+        }
+    }
+
+    /// 按路由输入一行
+    /// * 📌`input_mode`：[`None`]⇒各实例使用自身配置的输入模式
+    /// * 🚩`@all`⇒跳过已终止的实例；指定的实例已终止⇒报错
+    /// * 🚩某个实例输入失败，不影响向其它实例输入：最后汇总报错
+    pub fn input_line(&self, line: &str, input_mode: Option<InputMode>) -> Result<()> {
+        let (route, line) = self.parse_route(line);
+        let mut errors = vec![];
+        for manager in self.route(route)? {
+            if route == Route::All && manager.is_terminated() {
+                continue;
+            }
+            let input_mode = input_mode.unwrap_or(manager.config.input_mode);
+            if let Err(e) = manager.input_line(line, input_mode) {
+                errors.push(match manager.name() {
+                    Some(name) => format!("实例「{name}」：{e}"),
+                    None => e.to_string(),
+                });
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(anyhow!("{}", errors.join("；"))),
+        }
+    }
+
+    /// 【主函数】管理所有实例
+    /// * 🚩各实例：生成「读取输出」子线程，运行「预置NAL」
+    ///   * 📌「预置NAL」要求终止⇒仅终止该实例
+    /// * 🚩全局：Websocket服务、用户输入 | 使用默认实例的配置
+    /// * 🚩等待所有实例终止：单个实例终止仅作提示
+    ///   * 📌全局子线程异常退出⇒终止所有实例，上报其错误
+    ///   * 📌所有实例终止后，全局子线程至多再等待[`GLOBAL_THREADS_JOIN_TIMEOUT`]
+    /// * ⚠️不支持自动重启
+    pub fn manage(&mut self) -> Result<()> {
+        let mut threads_read = vec![];
+        for manager in self.managers.iter_mut() {
+            let name = manager.name().unwrap_or_default().to_string();
+            // 生成「读取输出」子线程 | 📌必须最先
+            threads_read.push((name.clone(), manager.spawn_read_output()?));
+            // 预置输入 | ⚠️阻塞
+            match manager.prelude_nal() {
+                Break(result) => {
+                    if let Err(e) = result {
+                        eprintln_cli!([Error] "实例「{name}」的预置NAL输入发生错误：{e}");
+                    }
+                    if_let_err_eprintln_cli!(
+                        manager.runtime.lock().transform_err(error_anyhow)?.terminate()
+                        => e => [Error] "终止实例「{name}」时发生错误：{e}"
+                    );
+                }
+                Continue(Err(e)) => {
+                    eprintln_cli!([Error] "实例「{name}」的预置NAL输入发生错误：{e}")
+                }
+                Continue(Ok(..)) => (),
+            }
+        }
+
+        // 所有实例均已终止 & 无用户输入 ⇒ 程序退出
+        let config = self.default_config().clone();
+        if self.is_all_terminated() && !config.user_input {
+            return Ok(());
+        }
+
+        // 生成「Websocket服务」子线程（若有连接）
+        let thread_ws = match config.websocket {
+            Some(..) => Some(spawn_ws_server_supervised(self)?),
+            None => None,
+        };
+
+        // 生成「用户输入」子线程
+        let thread_input = match config.user_input {
+            true => Some(self.spawn_user_input()),
+            false => None,
+        };
+
+        // 等待各实例终止 | 单个实例终止不影响其它实例
+        // * 🚩轮询：全局子线程异常退出时不必等到所有实例终止
+        let mut threads_read = threads_read.into_iter().map(Some).collect::<Vec<_>>();
+        let mut threads_global = [thread_ws, thread_input];
+        while threads_read.iter().any(Option::is_some) {
+            for slot in threads_read.iter_mut() {
+                if let Some((name, thread_read)) = slot.take_if(|(_, t)| t.is_finished()) {
+                    match thread_read.join().transform_err(error_anyhow)? {
+                        Ok(..) => println_cli!([Info] "实例「{name}」已终止"),
+                        Err(e) => eprintln_cli!([Warn] "实例「{name}」：{e}"),
+                    }
+                }
+            }
+            if let Err(e) = join_finished(&mut threads_global) {
+                self.terminate_all();
+                return Err(e);
+            }
+            sleep(JOIN_POLL_INTERVAL);
+        }
+        // 收到终止信号⇒不再等待其它子线程：用户输入可能仍在阻塞
+        if_return! { is_shutdown_requested() => Ok(()) }
+        let deadline = Instant::now() + GLOBAL_THREADS_JOIN_TIMEOUT;
+        while threads_global.iter().any(Option::is_some) && Instant::now() < deadline {
+            join_finished(&mut threads_global)?;
+            sleep(JOIN_POLL_INTERVAL);
+        }
+
+        // 正常运行结束
+        Ok(())
+    }
+
+    /// 终止所有（未终止的）实例
+    /// * 🚩出错仅作提示
+    fn terminate_all(&self) {
+        for manager in self.managers.iter() {
+            let name = manager.name().unwrap_or_default();
+            let mut runtime = lock_recovered(&manager.runtime);
+            if !runtime.is_terminated() {
+                if_let_err_eprintln_cli!(
+                    runtime.terminate()
+                    => e => [Error] "终止实例「{name}」时发生错误：{e}"
+                );
+            }
+        }
+    }
+
+    /// 生成「用户输入」子线程
+    /// * 🚩按路由前缀输入；所有实例均已终止⇒结束
+    /// * 🚩用户主动退出（连续两次Ctrl-C）⇒终止所有实例
+    fn spawn_user_input(&self) -> JoinHandle<Result<()>> {
+        let supervisor = self.clone();
+        thread::spawn(move || {
+            for io_result in user_input_lines(supervisor.default_config()) {
+                let line = io_result?;
+                let line = line.trim();
+                if is_shutdown_requested() {
+                    break;
+                }
+                if supervisor.is_all_terminated() {
+                    return Ok(());
+                }
+                if !line.is_empty() {
+                    if_let_err_eprintln_cli!(
+                        supervisor.input_line(line, None)
+                        => e => [Error] "输入过程中发生错误：{e}"
+                    );
+                }
+            }
+            if is_stdin_piped()
+                && wait_after_piped_input(supervisor.default_config(), || {
+                    supervisor.is_all_terminated()
+                })
+            {
+                return Ok(());
+            }
+            for manager in supervisor.managers.iter() {
+                let runtime = &mut *manager.runtime.lock().transform_err(error_anyhow)?;
+                if !runtime.is_terminated() {
+                    runtime.terminate()?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// 【主函数】批处理模式：在每个实例上运行「预置NAL」
+    /// * 🎯`--eval`：同一NAL文件在各实例上的通过情况
+    /// * ⚙️返回「是否全部通过」
+    pub fn eval(&mut self) -> Result<bool> {
+        let mut success = true;
+        for manager in self.managers.iter_mut() {
+            let summary = manager.eval()?;
+            if let Some(name) = manager.name() {
+                println_cli!([Info] "实例「{name}」：");
+            }
+            summary.print();
+            success &= summary.is_success();
+        }
+        Ok(success)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{runtime_manage::tests::test_config, StickyCmds};
+    use babel_nar::{
+        output_handler::json::output_from_json,
+        runtimes::{FakeVm, FakeVmRuntime},
+    };
+    use nar_dev_utils::manipulate;
+    use navm::{
+        cmd::Cmd,
+        vm::{VmLauncher, VmRuntime},
+    };
+
+    /// 测试/路由前缀解析
+    #[test]
+    fn test_parse_route() {
+        assert_eq!(
+            parse_route("@ona NSE <A --> B>."),
+            (Route::Named("ona"), "NSE <A --> B>.")
+        );
+        assert_eq!(parse_route("@all  CYC 10"), (Route::All, "CYC 10"));
+        assert_eq!(parse_route("CYC 10"), (Route::Default, "CYC 10"));
+        assert_eq!(parse_route("@ona"), (Route::Named("ona"), ""));
+    }
+
+    /// 测试/仅多实例解析路由前缀
+    #[test]
+    fn test_route_single_instance() -> Result<()> {
+        let runtime = FakeVm::new().launch()?;
+        let single = Supervisor::new(vec![RuntimeManager::new(runtime, test_config())])?;
+        assert!(!single.is_multi_instance());
+        assert_eq!(
+            single.parse_route("@ona CYC 10"),
+            (Route::Default, "@ona CYC 10")
+        );
+        let multi = Supervisor::new(vec![fake_instance("ona")])?;
+        assert!(multi.is_multi_instance());
+        assert_eq!(
+            multi.parse_route("@ona CYC 10"),
+            (Route::Named("ona"), "CYC 10")
+        );
+        Ok(())
+    }
+
+    /// 测试/附带实例名的JSON输出
+    #[test]
+    fn test_output_json_tagged() {
+        let output = Output::INFO {
+            message: "hi".into(),
+        };
+        assert_eq!(
+            output_to_json_tagged(&output, None),
+            output_to_json(&output).to_string()
+        );
+        let json = output_to_json_tagged(&output, Some("ona"));
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(value["instance"], "ona");
+        assert_eq!(value["content"], "hi");
+        assert_eq!(output_from_json(&json).unwrap(), output);
+    }
+
+    /// 启动一个具名的模拟实例
+    /// * 🚩以预设响应标记「哪个实例收到了`CYC`」
+    fn fake_instance(name: &str) -> RuntimeManager<FakeVmRuntime> {
+        let runtime = manipulate!(
+            FakeVm::new()
+            => .respond(Cmd::CYC(10), [Output::OTHER { content: name.into() }])
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败");
+        RuntimeManager::named(runtime, test_config(), name)
+    }
+
+    /// 取出实例的所有输出
+    fn drain(manager: &RuntimeManager<FakeVmRuntime>) -> Vec<Output> {
+        manager.runtime.lock().unwrap().drain_outputs().unwrap()
+    }
+
+    /// 测试/两个模拟实例的输入路由
+    /// * 📌单个实例终止后，其它实例仍可输入
+    #[test]
+    fn test_route_two_instances() -> Result<()> {
+        let supervisor = Supervisor::new(vec![fake_instance("a"), fake_instance("b")])?;
+        let [a, b] = supervisor.managers() else {
+            unreachable!()
+        };
+
+        // 指定实例
+        supervisor.input_line("@b NSE <A --> B>.", None)?;
+        assert!(drain(a).is_empty());
+        assert!(matches!(&drain(b)[..], [Output::IN { content, .. }] if content == "<A --> B>."));
+
+        // 无前缀⇒默认实例
+        supervisor.input_line("NSE <B --> C>.", None)?;
+        assert_eq!(drain(a).len(), 1);
+        assert!(drain(b).is_empty());
+
+        // 所有实例
+        supervisor.input_line("@all CYC 10", None)?;
+        assert_eq!(
+            drain(a),
+            [Output::OTHER {
+                content: "a".into()
+            }]
+        );
+        assert_eq!(
+            drain(b),
+            [Output::OTHER {
+                content: "b".into()
+            }]
+        );
+
+        // 未知实例⇒报错
+        let e = supervisor.input_line("@c CYC 10", None).unwrap_err();
+        assert!(e.to_string().contains("a, b"), "{e}");
+
+        // 终止一个实例，不影响另一个
+        a.runtime.lock().unwrap().terminate()?;
+        assert!(!supervisor.is_all_terminated());
+        supervisor.input_line("@all CYC 10", None)?;
+        assert_eq!(
+            drain(b),
+            [Output::OTHER {
+                content: "b".into()
+            }]
+        );
+        assert!(supervisor.input_line("@a CYC 10", None).is_err());
+
+        // 粘性指令按实例分别记录
+        supervisor.input_line("@b VOL 0", None)?;
+        let sticky = |m: &RuntimeManager<FakeVmRuntime>| m.sticky_cmds.lock().unwrap().clone();
+        assert_eq!(sticky(a), StickyCmds::default());
+        assert_eq!(sticky(b).iter().collect::<Vec<_>>(), [&Cmd::VOL(0)]);
+        b.runtime.lock().unwrap().terminate()?;
+        assert!(supervisor.is_all_terminated());
+        Ok(())
+    }
+}
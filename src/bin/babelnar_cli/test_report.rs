@@ -0,0 +1,264 @@
+//! NAL测试报告
+//! * 🎯CI中汇总`.nal`预期的结果：每项预期的描述、结果、耗时与匹配到的输出
+//! * 📌记录范围：`''await`、`''expect-contains`、`''expect-cycle`、`''expect-rate`
+//! * 📌报告格式
+//!   * 📄JSON：默认
+//!   * 📄JUnit风格的XML：报告文件扩展名为`.xml`时
+//! * 🚩由运行时管理者持有：运行「预置NAL」或`--eval`时记录，结束后写入`reportFile`
+
+use anyhow::Result;
+use babel_nar::test_tools::{matched_output, NALInput, VmOutputCache};
+use navm::output::OutputJSON;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// NAL测试报告
+/// * 🚩按执行顺序记录每项预期
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestReport {
+    /// 所有已记录的预期
+    cases: Vec<TestCase>,
+    /// 当前正在执行的NAL文件
+    /// * 🎯为预期标注来源：多个预置NAL文件的行号可能重复
+    /// * 📌纯文本的预置NAL⇒[`None`]
+    source: Option<PathBuf>,
+}
+
+/// 单项预期的记录
+/// * 📄`{"source":"test.nal","line":3,"kind":"expect-contains","expectation":"…","passed":true,"durationSecs":0.001,"matched":{…}}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCase {
+    /// 所在NAL文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// 所在行号 | 从1开始
+    pub line: usize,
+    /// 预期的种类 | 与魔法注释同名，如`expect-contains`
+    pub kind: String,
+    /// 预期的文本描述
+    pub expectation: String,
+    /// 是否通过
+    pub passed: bool,
+    /// 未通过的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+    /// 耗时（秒）
+    pub duration_secs: f64,
+    /// 与预期相符的输出
+    /// * 🚩取输出缓存中最近一条相符的输出；未通过⇒[`None`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched: Option<OutputJSON>,
+}
+
+impl TestCase {
+    /// 预期的完整描述
+    /// * 📄`test.nal:3 expect-contains: ANSWER <A --> C>.`
+    pub fn description(&self) -> String {
+        match &self.source {
+            Some(source) => format!("{source}:{} {}: {}", self.line, self.kind, self.expectation),
+            None => format!("{} {}: {}", self.line, self.kind, self.expectation),
+        }
+    }
+}
+
+impl TestReport {
+    /// 设置当前正在执行的NAL文件
+    pub fn set_source(&mut self, source: Option<&Path>) {
+        self.source = source.map(Path::to_path_buf);
+    }
+
+    /// 记录一次NAL输入的结果
+    /// * 🚩非预期的输入⇒忽略
+    /// * 📌`source`：被包含的文件；[`None`]⇒当前正在执行的NAL文件
+    /// * ⚠️需在置入之后立即调用：匹配到的输出取自当时的输出缓存
+    pub fn record(
+        &mut self,
+        source: Option<&Path>,
+        line: usize,
+        input: &NALInput,
+        result: &Result<()>,
+        duration: Duration,
+        output_cache: &impl VmOutputCache,
+    ) -> Result<()> {
+        let (kind, expectation) = match input {
+            NALInput::Await(expectation) => ("await", expectation),
+            NALInput::ExpectContains(expectation, ..) => ("expect-contains", expectation),
+            NALInput::ExpectCycle(.., expectation) => ("expect-cycle", expectation),
+            NALInput::ExpectCycleNot(.., expectation) => ("expect-cycle-not", expectation),
+            NALInput::ExpectRate { expectation, .. } => ("expect-rate", expectation),
+            _ => return Ok(()),
+        };
+        // 通过⇒记录实际满足预期的输出
+        let matched = match result {
+            Ok(..) => matched_output(input, output_cache)?.map(|output| output.to_json_struct()),
+            Err(..) => None,
+        };
+        self.cases.push(TestCase {
+            source: source
+                .or(self.source.as_deref())
+                .map(|path| path.display().to_string()),
+            line,
+            kind: kind.into(),
+            expectation: expectation.to_string(),
+            passed: result.is_ok(),
+            failure: result.as_ref().err().map(|e| e.to_string()),
+            duration_secs: duration.as_secs_f64(),
+            matched,
+        });
+        Ok(())
+    }
+
+    /// 未通过的预期数
+    pub fn failures(&self) -> usize {
+        self.cases.iter().filter(|case| !case.passed).count()
+    }
+
+    /// 总耗时（秒）
+    fn total_secs(&self) -> f64 {
+        self.cases.iter().map(|case| case.duration_secs).sum()
+    }
+
+    /// 生成JSON格式的报告
+    /// * 📄`{"tests":2,"failures":1,"durationSecs":0.5,"cases":[…]}`
+    pub fn to_json(&self) -> Result<String> {
+        let json = serde_json::json!({
+            "tests": self.cases.len(),
+            "failures": self.failures(),
+            "durationSecs": self.total_secs(),
+            "cases": self.cases,
+        });
+        Ok(serde_json::to_string_pretty(&json)?)
+    }
+
+    /// 生成JUnit风格的XML报告
+    /// * 🚩所有预期归入同一个`testsuite`；`classname`为所在NAL文件
+    /// * 🚩未通过⇒`<failure>`；匹配到的输出⇒`<system-out>`（JSON）
+    pub fn to_junit_xml(&self) -> String {
+        let tests = self.cases.len();
+        let failures = self.failures();
+        let time = self.total_secs();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml +=
+            &format!("<testsuites tests=\"{tests}\" failures=\"{failures}\" time=\"{time:.3}\">\n");
+        xml += &format!(
+            "  <testsuite name=\"BabelNAR\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" time=\"{time:.3}\">\n"
+        );
+        for case in &self.cases {
+            let source = case.source.as_deref().unwrap_or("nal");
+            xml += &format!(
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" line=\"{}\" time=\"{:.3}\">\n",
+                escape_xml(&format!("{}: {}", case.kind, case.expectation)),
+                escape_xml(source),
+                escape_xml(source),
+                case.line,
+                case.duration_secs,
+            );
+            if let Some(failure) = &case.failure {
+                xml += &format!(
+                    "      <failure message=\"{}\">{}</failure>\n",
+                    escape_xml(failure),
+                    escape_xml(&case.description())
+                );
+            }
+            if let Some(matched) = &case.matched {
+                xml += &format!(
+                    "      <system-out>{}</system-out>\n",
+                    escape_xml(&matched.to_string())
+                );
+            }
+            xml += "    </testcase>\n";
+        }
+        xml += "  </testsuite>\n";
+        xml += "</testsuites>\n";
+        xml
+    }
+
+    /// 将报告写入文件
+    /// * 🚩扩展名为`.xml`⇒JUnit风格的XML；否则⇒JSON
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        let is_xml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+        let content = match is_xml {
+            true => self.to_junit_xml(),
+            false => self.to_json()?,
+        };
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// 转义XML中的特殊字符
+/// * 🚩同时适用于属性值与文本内容
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&apos;",
+            '\n' => escaped += "&#10;",
+            // 其它控制字符在XML 1.0中不合法⇒丢弃
+            c if c.is_control() && c != '\t' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use babel_nar::test_tools::OutputExpectation;
+    use navm::output::Output;
+
+    /// 测试/JSON报告
+    #[test]
+    fn report_json() -> Result<()> {
+        let mut report = TestReport::default();
+        report.set_source(Some(Path::new("test.nal")));
+        let outputs = vec![Output::ANSWER {
+            content_raw: "<A --> C>.".into(),
+            narsese: None,
+        }];
+        let input = NALInput::Await(OutputExpectation {
+            output_type: Some("ANSWER".into()),
+            ..Default::default()
+        });
+        report.record(None, 2, &input, &Ok(()), Duration::ZERO, &outputs)?;
+        // 非预期的输入⇒不记录
+        report.record(
+            None,
+            3,
+            &NALInput::Status,
+            &Err(anyhow!("错误")),
+            Duration::ZERO,
+            &outputs,
+        )?;
+        assert_eq!(report.cases.len(), 1);
+        let json = serde_json::from_str::<serde_json::Value>(&report.to_json()?)?;
+        assert_eq!(json["tests"], 1);
+        assert_eq!(json["failures"], 0);
+        assert_eq!(json["cases"][0]["source"], "test.nal");
+        assert_eq!(json["cases"][0]["kind"], "await");
+        assert_eq!(json["cases"][0]["matched"]["content"], "<A --> C>.");
+        Ok(())
+    }
+
+    /// 测试/XML转义
+    #[test]
+    fn xml_escape() {
+        assert_eq!(
+            escape_xml("<A --> \"B\"> & 'C'\n\u{0}"),
+            "&lt;A --&gt; &quot;B&quot;&gt; &amp; &apos;C&apos;&#10;"
+        );
+    }
+}
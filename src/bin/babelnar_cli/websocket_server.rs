@@ -2,8 +2,11 @@
 //! * 🎯为BabelNAR CLI实现Websocket IO
 //! * 🎯实现专有的Websocket服务端逻辑
 
-use crate::{LaunchConfigWebsocket, RuntimeConfig, RuntimeManager};
-use anyhow::Result;
+use crate::{
+    output_to_json_tagged, parse_route, InputMode, LaunchConfigWebsocket, RateLimitPolicy,
+    RuntimeManager, Supervisor,
+};
+use anyhow::{anyhow, Result};
 use babel_nar::{
     cli_support::{
         error_handling_boost::error_anyhow,
@@ -13,14 +16,46 @@ use babel_nar::{
         },
     },
     eprintln_cli, if_let_err_eprintln_cli, println_cli,
+    runtimes::VmRuntimeBoost,
 };
-use navm::{output::Output, vm::VmRuntime};
+use navm::output::Output;
 use std::{
-    sync::Arc,
+    collections::{HashMap, VecDeque},
+    io,
+    net::SocketAddr,
+    sync::{Arc, Condvar, Mutex},
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 use ws::{Factory, Handler, Sender};
 
+/// 每个客户端「待发消息队列」的默认容量
+/// * 📌沿用先前ws服务端「每个连接最多768条消息」的设置
+pub const DEFAULT_CLIENT_QUEUE_SIZE: usize = 0x300;
+
+/// 默认的心跳间隔（秒）
+pub const DEFAULT_HEARTBEAT_SECS: u64 = 30;
+
+/// 允许连续未响应的心跳次数
+/// * 🚩到下一次心跳时，已有这么多次ping未收到pong⇒断开连接
+///   * 📌即：客户端失去响应后，至多经过`(1 + MAX_MISSED_PONGS) × 心跳间隔`被断开
+pub const MAX_MISSED_PONGS: u32 = 2;
+
+/// 默认的最大连接数
+/// * 📌沿用先前ws服务端「最多32个连接」的设置
+pub const DEFAULT_MAX_CONNECTIONS: usize = 0x20;
+
+/// 默认的「输入积压」容量
+/// * 🎯[`RateLimitPolicy::Queue`]策略下，每个客户端最多积压的输入条数
+pub const DEFAULT_INPUT_BACKLOG: usize = 0x100;
+
+/// 心跳定时器的标识
+const HEARTBEAT: ws::util::Token = ws::util::Token(1);
+
+/// 「积压输入」定时器的标识
+/// * 🎯令牌恢复后，输入积压的消息
+const INPUT_BACKLOG: ws::util::Token = ws::util::Token(2);
+
 /// 工具宏：尝试执行，如果失败则上抛错误
 /// * 🎯在「无法使用[`anyhow::Result`]上抛错误」的情况下适用
 macro_rules! try_or_return_err {
@@ -45,8 +80,302 @@ macro_rules! try_or_return_err {
 ///  * ⚠️【2024-04-08 19:08:15】即便一次只回传一条消息，也需包装上方括号`[{...}]`
 #[inline]
 pub fn format_output_message(output: &Output) -> String {
+    format_output_message_tagged(output, None)
+}
+
+/// 通信用代码，并附带实例名
+/// * 🎯多实例：客户端据`instance`字段区分输出来自哪个实例
+/// * 🚩无实例名⇒与[`format_output_message`]一致
+#[inline]
+pub fn format_output_message_tagged(output: &Output, instance: Option<&str>) -> String {
     // 包装成「对象数组」
-    format!("[{}]", output.to_json_string())
+    format!("[{}]", output_to_json_tagged(output, instance))
+}
+
+/// 「输入模式握手」的键名
+/// * 📄`{"inputMode":"cmd"}`
+pub const HANDSHAKE_INPUT_MODE_KEY: &str = "inputMode";
+
+/// 「查询运行状态」的文本命令
+/// * 🎯长时间运行的智能体：查询各类输出的数目、吞吐量
+/// * 🚩不论输入模式，均不传给CIN；仅回复发出请求的客户端
+/// * 📌多实例：可附带路由前缀，如`@ona /status`
+pub const STATUS_COMMAND: &str = "/status";
+
+/// 尝试从消息中解析「输入模式握手」
+/// * 🎯让每个Websocket连接协商自己的输入模式
+///   * 📄GUI发送NAVM指令，测试客户端发送`.nal`文本
+/// * 🚩非握手消息⇒[`None`]
+/// * 🚩未知的输入模式⇒`Some(Err(..))`
+pub fn parse_input_mode_handshake(msg: &str) -> Option<Result<InputMode>> {
+    // 只接受「含有指定键的JSON对象」
+    let value = serde_json::from_str::<serde_json::Value>(msg).ok()?;
+    let mode = value.as_object()?.get(HANDSHAKE_INPUT_MODE_KEY)?;
+    // 解析输入模式
+    Some(
+        serde_json::from_value::<InputMode>(mode.clone())
+            .map_err(|_| anyhow!("未知的输入模式：{mode}")),
+    )
+}
+
+/// 单个客户端的「待发消息队列」
+/// * 🎯背压处理：客户端接收过慢时，不阻塞「读取NAVM输出」的线程
+///   * 📌每个客户端一个有界队列，由单独的「发送线程」取出并发送
+///   * 🚩队列已满⇒丢弃最旧的消息，并在之后向该客户端发送一条「已丢弃」警告
+/// * 🔗GitHub issue：<https://github.com/housleyjk/ws-rs/issues/346>
+#[derive(Debug)]
+pub struct ClientQueue {
+    /// 队列状态
+    state: Mutex<ClientQueueState>,
+    /// 用于唤醒「发送线程」
+    condvar: Condvar,
+    /// 队列容量
+    capacity: usize,
+}
+
+/// 「待发消息队列」的内部状态
+#[derive(Debug, Default)]
+struct ClientQueueState {
+    /// 待发送的消息
+    messages: VecDeque<String>,
+    /// 自上次发送以来丢弃的消息数
+    dropped: usize,
+    /// 是否已关闭
+    closed: bool,
+}
+
+impl ClientQueue {
+    /// 构造函数
+    /// * ⚠️容量至少为1
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::default(),
+            condvar: Condvar::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// 置入一条消息
+    /// * 🚩队列已满⇒丢弃最旧的消息
+    /// * 📌不会阻塞（除了短暂的加锁）
+    pub fn push(&self, message: String) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.closed {
+            return;
+        }
+        while state.messages.len() >= self.capacity {
+            state.messages.pop_front();
+            state.dropped += 1;
+        }
+        state.messages.push_back(message);
+        self.condvar.notify_one();
+    }
+
+    /// 关闭队列
+    /// * 🎯让「发送线程」退出
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.condvar.notify_all();
+    }
+
+    /// 取出下一条待发送的消息
+    /// * 🚩队列为空⇒阻塞等待
+    /// * 🚩先前有消息被丢弃⇒先返回一条「已丢弃」警告
+    /// * 🚩队列关闭⇒返回[`None`]
+    pub fn pop(&self) -> Option<String> {
+        let mut state = self.state.lock().ok()?;
+        loop {
+            if state.closed {
+                return None;
+            }
+            if state.dropped > 0 {
+                let dropped = std::mem::take(&mut state.dropped);
+                return Some(format_output_message(&Output::UNCLASSIFIED {
+                    r#type: "WARN".into(),
+                    content: format!("客户端接收过慢，已丢弃{dropped}条消息"),
+                    narsese: None,
+                }));
+            }
+            if let Some(message) = state.messages.pop_front() {
+                return Some(message);
+            }
+            state = self.condvar.wait(state).ok()?;
+        }
+    }
+
+    /// 生成「发送线程」
+    /// * 🚩不断从队列中取出消息，发送给客户端
+    /// * 📌只有该线程会在Websocket发送时阻塞
+    pub fn spawn_sender(self: &Arc<Self>, sender: Sender) -> JoinHandle<()> {
+        let queue = self.clone();
+        thread::spawn(move || {
+            while let Some(message) = queue.pop() {
+                if let Err(e) = sender.send(message) {
+                    eprintln_cli!([Error] "向Websocket客户端发送消息失败：{e}");
+                    break;
+                }
+            }
+        })
+    }
+}
+
+/// 所有客户端的「待发消息队列」
+/// * 🚩以「连接id」为键
+pub type ClientQueues = ArcMutex<HashMap<u32, Arc<ClientQueue>>>;
+
+/// 限流的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Admission {
+    /// 立即输入
+    Accepted(String),
+    /// 已积压，待令牌恢复后输入
+    Queued,
+    /// 已拒绝
+    Rejected,
+}
+
+/// 单个客户端的输入限流器
+/// * 🎯避免客户端输入过快：如短时间内大量`NSE`，使CIN的标准输入、输入通道无限膨胀
+/// * 🚩令牌桶：每条输入消耗一个令牌；令牌以固定速率恢复，至多恢复到「每秒条数」
+/// * 🚩令牌不足⇒按[策略](RateLimitPolicy)积压或拒绝
+///   * 📌积压时合并相邻的`CYC`：`CYC 1`+`CYC 2`⇒`CYC 3`，只消耗一个令牌
+/// * 📌只处理文本，不涉及Websocket：可直接用一串消息测试
+#[derive(Debug, Clone)]
+pub struct InputRateLimiter {
+    /// 每秒最多输入的条数
+    /// * 📌亦为令牌桶的容量
+    rate: u32,
+    /// 超出限制时的处理策略
+    policy: RateLimitPolicy,
+    /// 积压的输入
+    backlog: VecDeque<String>,
+    /// 最多积压的输入条数
+    backlog_capacity: usize,
+    /// 当前的令牌数
+    tokens: f64,
+    /// 上次恢复令牌的时刻
+    last_refill: Instant,
+}
+
+impl InputRateLimiter {
+    /// 构造函数
+    /// * 🚩初始即满：允许一开始就有「每秒条数」的突发
+    /// * ⚠️速率至少为1
+    pub fn new(rate: u32, policy: RateLimitPolicy, backlog_capacity: usize, now: Instant) -> Self {
+        let rate = rate.max(1);
+        Self {
+            rate,
+            policy,
+            backlog: VecDeque::new(),
+            backlog_capacity,
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    /// 每秒最多输入的条数
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// 是否有积压的输入
+    pub fn has_backlog(&self) -> bool {
+        !self.backlog.is_empty()
+    }
+
+    /// 按经过的时间恢复令牌
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let tokens = self.tokens + elapsed.as_secs_f64() * self.rate as f64;
+        self.tokens = tokens.min(self.rate as f64);
+        self.last_refill = now;
+    }
+
+    /// 尝试消耗一个令牌
+    fn take_token(&mut self) -> bool {
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    /// 处理一条输入
+    /// * 🚩有积压时不插队：新输入排在积压之后
+    pub fn admit(&mut self, line: String, input_mode: InputMode, now: Instant) -> Admission {
+        self.refill(now);
+        if self.backlog.is_empty() && self.take_token() {
+            return Admission::Accepted(line);
+        }
+        if self.policy == RateLimitPolicy::Reject {
+            return Admission::Rejected;
+        }
+        // 与上一条积压的`CYC`合并
+        if let Some(last) = self.backlog.back_mut() {
+            if let Some(merged) = merge_cycles(last, &line, input_mode) {
+                *last = merged;
+                return Admission::Queued;
+            }
+        }
+        if self.backlog.len() >= self.backlog_capacity {
+            return Admission::Rejected;
+        }
+        self.backlog.push_back(line);
+        Admission::Queued
+    }
+
+    /// 取出令牌允许范围内的积压输入
+    pub fn drain(&mut self, now: Instant) -> Vec<String> {
+        self.refill(now);
+        let mut lines = vec![];
+        while !self.backlog.is_empty() && self.take_token() {
+            lines.extend(self.backlog.pop_front());
+        }
+        lines
+    }
+
+    /// 距离下一个令牌恢复的时长
+    /// * 🎯安排「积压输入」定时器
+    pub fn next_token_in(&self) -> Duration {
+        let missing = (1.0 - self.tokens).max(0.0);
+        Duration::from_secs_f64(missing / self.rate as f64)
+    }
+}
+
+/// 解析`CYC`输入的步数
+/// * ⚙️返回「路由前缀」与步数
+/// * 📄指令模式：`CYC 10`、`@ona CYC 10`
+/// * 📄NAL模式：`10`、`@ona 10`
+/// * 🚩原始输入模式⇒不解析：原样送达CIN
+fn parse_cycles(line: &str, input_mode: InputMode) -> Option<(&str, usize)> {
+    let line = line.trim();
+    let (_, rest) = parse_route(line);
+    let prefix = &line[..line.len() - rest.len()];
+    let steps = match input_mode {
+        InputMode::Cmd => rest.strip_prefix("CYC")?.trim(),
+        InputMode::Nal => rest.trim(),
+        InputMode::Raw => return None,
+    };
+    Some((prefix, steps.parse().ok()?))
+}
+
+/// 合并两条`CYC`输入
+/// * 🚩路由相同⇒步数相加；否则⇒[`None`]
+fn merge_cycles(former: &str, latter: &str, input_mode: InputMode) -> Option<String> {
+    let (prefix, former) = parse_cycles(former, input_mode)?;
+    let (latter_prefix, latter) = parse_cycles(latter, input_mode)?;
+    if prefix.trim_end() != latter_prefix.trim_end() {
+        return None;
+    }
+    let steps = former.checked_add(latter)?;
+    Some(match input_mode {
+        InputMode::Cmd => format!("{prefix}CYC {steps}"),
+        _ => format!("{prefix}{steps}"),
+    })
 }
 
 /// 入口代码
@@ -57,13 +386,44 @@ pub fn format_output_message(output: &Output) -> String {
 ///   * 📌【2024-04-09 01:21:37】现在通过配置「最大连接数」与「队列大小」以**暂时缓解**此问题
 ///   * 🔗参考：<https://docs.rs/ws/latest/ws/struct.Settings.html>
 ///   * 🔗GitHub issue：<https://github.com/housleyjk/ws-rs/issues/346>
+/// * 🚩现在每个客户端有自己的有界「待发消息队列」，广播时不再阻塞
 pub fn spawn_ws_server<R>(manager: &mut RuntimeManager<R>) -> Result<JoinHandle<Result<()>>>
 where
-    R: VmRuntime + Send + Sync,
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    spawn_ws_server_supervised(&Supervisor::new(vec![manager.clone()])?)
+}
+
+/// 为（多个实例的）监管者生成Websocket服务端
+/// * 🚩使用默认实例的Websocket配置
+/// * 🚩按路由前缀输入；各实例的输出均广播给所有客户端
+pub fn spawn_ws_server_supervised<R>(supervisor: &Supervisor<R>) -> Result<JoinHandle<Result<()>>>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    bind_ws_server(supervisor).map(|(handle, _)| handle)
+}
+
+/// 为（多个实例的）监管者绑定并启动Websocket服务端
+/// * 🚩在当前线程中绑定地址：端口被占用等错误立即返回，而非在服务端线程中
+/// * 🚩配置的端口为`0`⇒由系统分配空闲端口
+/// * ⚙️返回服务端线程，以及实际绑定的地址
+pub fn bind_ws_server<R>(supervisor: &Supervisor<R>) -> Result<(JoinHandle<Result<()>>, SocketAddr)>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
 {
     // 提取并合并地址
-    let LaunchConfigWebsocket { host, port } = manager
-        .config
+    let config = supervisor.default_config().clone();
+    let LaunchConfigWebsocket {
+        host,
+        port,
+        queue_size,
+        heartbeat_secs,
+        max_connections,
+        max_inputs_per_second,
+        rate_limit_policy,
+        input_backlog,
+    } = config
         .websocket
         .as_ref()
         .expect("尝试在无配置时启动Websocket服务器");
@@ -71,50 +431,56 @@ where
 
     // 获取服务端「处理者工厂」
     // * 🚩拷贝[`Arc`]
+    let clients = ClientQueues::default();
     let server = WSServer {
-        runtime: manager.runtime.clone(),
-        output_cache: manager.output_cache.clone(),
-        config: manager.config.clone(),
+        supervisor: supervisor.clone(),
+        clients: clients.clone(),
+        queue_size: queue_size.unwrap_or(DEFAULT_CLIENT_QUEUE_SIZE),
+        heartbeat_secs: heartbeat_secs.unwrap_or(DEFAULT_HEARTBEAT_SECS),
+        max_inputs_per_second: *max_inputs_per_second,
+        rate_limit_policy: rate_limit_policy.unwrap_or_default(),
+        input_backlog: input_backlog.unwrap_or(DEFAULT_INPUT_BACKLOG),
     };
 
     // 生成定制版的Websocket服务端
-    // * 🎯获取生成的[`WebSocket`]（服务端）对象，调用[`WebSocket::boardcaster`]方法快速广播
     // * ❌【2024-04-08 23:23:08】无法独立为单独的函数：此中NAVM运行时「R」的生命周期问题（难以参与推导）
-    let (handle, sender) = {
+    let (handle, local_addr) = {
         let factory = server;
-        let address = address.clone();
         let ws_setting = ws::Settings {
             // * 📝使用`ws::Builder`结合`ws::Settings`生成配置
             // * ✅在配置中调节「队列大小」以扩宽「连续消息接收限制」
             // * 默认：100（最大连接）×5（最长队列）→500条后阻塞
             // * 🚩【2024-04-09 01:03:52】现在调整成「最多32个连接，每个连接最多768条消息」
             // * ⚠️仍然会在24576条消息后产生阻塞——但相比原先500条，情况少很多
-            max_connections: 0x20,
+            // * 🚩最大连接数可由配置指定
+            max_connections: max_connections.unwrap_or(DEFAULT_MAX_CONNECTIONS),
             queue_size: 0x300,
             ..Default::default()
         };
         let server = ws::Builder::new()
             .with_settings(ws_setting)
-            .build(factory)?;
-        let sender = server.broadcaster();
+            .build(factory)?
+            .bind(address)?;
+        let local_addr = server.local_addr()?;
         let handle = thread::spawn(move || {
-            server.listen(address)?;
+            server.run()?;
             // ! ❌此处不能缩并：必须转换为`anyhow::Error`
             Ok(())
         });
-        (handle, sender)
+        (handle, local_addr)
     };
-    println_cli!([Info] "Websocket服务器已在 {:?} 启动", address);
+    println_cli!([Info] "Websocket服务器已在 {:?} 启动", local_addr.to_string());
 
-    // 向（服务端自身）「输出缓存」添加侦听器
-    if_let_err_eprintln_cli! {
-        // ! 此处需要可变的`manager`
-        register_listener(&mut manager.output_cache, sender)
-        => e => [Error] "无法为服务端注册侦听器：{e}"
+    // 向（各实例的）「输出缓存」添加侦听器
+    for manager in supervisor.managers() {
+        if_let_err_eprintln_cli! {
+            register_listener(&manager.output_cache, clients.clone(), manager.name())
+            => e => [Error] "无法为服务端注册侦听器：{e}"
+        }
     }
 
-    // 返回线程句柄
-    Ok(handle)
+    // 返回线程句柄与实际绑定的地址
+    Ok((handle, local_addr))
 }
 
 /// 一个Websocket连接
@@ -122,63 +488,272 @@ where
 #[derive(Debug)]
 pub struct Connection<R>
 where
-    R: VmRuntime + Send + Sync,
+    R: VmRuntimeBoost + Send + Sync + 'static,
 {
-    /// 所涉及的运行时
-    pub(crate) runtime: ArcMutex<R>,
+    /// 所涉及的（各实例的）监管者
+    /// * 📌单实例⇒仅含一个无名实例
+    pub(crate) supervisor: Supervisor<R>,
+
+    /// 该连接的「待发消息队列」
+    /// * 🎯仅向该连接回复「握手」结果
+    pub(crate) queue: Arc<ClientQueue>,
 
-    /// 所涉及的运行时配置
-    pub(crate) config: Arc<RuntimeConfig>,
+    /// 所有客户端的「待发消息队列」
+    /// * 🎯连接关闭时注销自身
+    pub(crate) clients: ClientQueues,
+
+    /// 该连接的输入模式
+    /// * 🚩默认为配置中的输入模式，可通过「首条消息握手」覆盖
+    pub(crate) input_mode: InputMode,
+
+    /// 是否已收到首条消息
+    /// * 🎯仅首条消息可作为「输入模式握手」
+    pub(crate) received_first: bool,
 
-    /// 所涉及的运行时
-    pub(crate) output_cache: ArcMutex<OutputCache>,
-    // /// 连接（服务端这方的）发送者
-    // /// * 🚩【2024-04-03 19:44:58】现在不再需要
-    // pub(crate) sender: Sender,
     /// 连接id
     pub(crate) id: u32,
+
+    /// 该连接的「回传发送者」
+    /// * 🎯发送心跳、设置定时器
+    pub(crate) out: Sender,
+
+    /// 心跳间隔（秒）
+    /// * 📌`0`⇒不发送心跳
+    pub(crate) heartbeat_secs: u64,
+
+    /// 已发送但未收到pong的ping数
+    pub(crate) missed_pongs: u32,
+
+    /// 输入限流器
+    /// * 📌未配置「每秒最多输入的条数」⇒不限流
+    pub(crate) limiter: Option<InputRateLimiter>,
+
+    /// 是否已安排「积压输入」定时器
+    pub(crate) backlog_scheduled: bool,
+}
+
+impl<R> Connection<R>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    /// 处理「输入模式握手」
+    /// * 🚩成功⇒切换该连接的输入模式，回复`INFO`
+    /// * 🚩失败⇒保持原输入模式，回复`ERROR`
+    /// * 📌仅回复该连接，不广播
+    fn handshake(&mut self, result: Result<InputMode>) {
+        let reply = match result {
+            Ok(input_mode) => {
+                self.input_mode = input_mode;
+                println_cli!([Info] "Websocket连接 {} 的输入模式已切换为 {input_mode:?}", self.id);
+                Output::INFO {
+                    message: format!("输入模式已切换为 {input_mode:?}"),
+                }
+            }
+            Err(e) => {
+                eprintln_cli!([Error] "Websocket连接 {} 握手失败：{e}", self.id);
+                Output::ERROR {
+                    description: e.to_string(),
+                }
+            }
+        };
+        self.queue.push(format_output_message(&reply));
+    }
+
+    /// 记录连接事件
+    /// * 🎯连接的打开、关闭与断开可在「输出缓存」中查到：如测试中的断言
+    /// * 🚩作为`INFO`输出静默存入默认实例的「输出缓存」，并打印到终端
+    ///   * 📌不广播给Websocket客户端
+    fn log_event(&self, message: String) {
+        println_cli!([Info] "{message}");
+        let Some(manager) = self.supervisor.managers().first() else {
+            return;
+        };
+        if let Ok(mut output_cache) = manager.output_cache.lock() {
+            let _ = output_cache.put_silent(Output::INFO { message });
+        }
+    }
+
+    /// 心跳
+    /// * 🚩已有[`MAX_MISSED_PONGS`]次ping未收到pong⇒断开连接
+    ///   * 📌以IO错误断开：不再等待客户端回应「关闭」帧，半开的连接亦能及时释放
+    /// * 🚩否则⇒发送ping，并等待下一次心跳
+    #[allow(clippy::result_large_err)]
+    fn heartbeat(&mut self) -> ws::Result<()> {
+        if self.missed_pongs >= MAX_MISSED_PONGS {
+            self.log_event(format!(
+                "Websocket连接 {} 连续{}次未响应心跳，已断开",
+                self.id, self.missed_pongs
+            ));
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "心跳超时").into());
+        }
+        self.missed_pongs += 1;
+        self.out.ping(vec![])?;
+        self.out.timeout(self.heartbeat_secs * 1000, HEARTBEAT)
+    }
+
+    /// 所有实例的子线程均已异常退出⇒关闭Websocket服务
+    /// * 🎯有序退出：不再接受注定失败的输入，并让服务端线程结束
+    /// * ⚙️返回「是否已关闭」
+    #[allow(clippy::result_large_err)]
+    fn shutdown_if_aborted(&mut self) -> ws::Result<bool> {
+        if !self
+            .supervisor
+            .managers()
+            .iter()
+            .all(RuntimeManager::is_aborted)
+        {
+            return Ok(false);
+        }
+        println_cli!([Warn] "运行时的子线程已异常退出，关闭Websocket服务");
+        self.out.shutdown()?;
+        Ok(true)
+    }
+
+    /// 按路由输入一行
+    fn input(&self, line: &str) {
+        // * 📝【2024-04-08 22:10:17】现在查明「Websocket线程阻塞」问题在Websocket「回传发送者」的`send`调用中
+        if_let_err_eprintln_cli! {
+            self.supervisor.input_line(line, Some(self.input_mode))
+            => err => [Error] "在Websocket连接中输入「{line}」时发生错误：{err}"
+        }
+    }
+
+    /// 经限流器输入一行
+    /// * 🚩无限流器⇒直接输入
+    /// * 🚩被拒绝⇒仅向该连接回复`ERROR`
+    #[allow(clippy::result_large_err)]
+    fn input_limited(&mut self, line: String) -> ws::Result<()> {
+        let Some(limiter) = self.limiter.as_mut() else {
+            self.input(&line);
+            return Ok(());
+        };
+        match limiter.admit(line.clone(), self.input_mode, Instant::now()) {
+            Admission::Accepted(line) => self.input(&line),
+            Admission::Queued => self.schedule_backlog()?,
+            Admission::Rejected => {
+                let rate = limiter.rate();
+                let description =
+                    format!("输入过于频繁（超出每秒{rate}条的限制），已拒绝「{line}」");
+                self.queue
+                    .push(format_output_message(&Output::ERROR { description }))
+            }
+        }
+        Ok(())
+    }
+
+    /// 安排「积压输入」定时器
+    /// * 🚩在下一个令牌恢复时触发；已安排⇒不重复安排
+    #[allow(clippy::result_large_err)]
+    fn schedule_backlog(&mut self) -> ws::Result<()> {
+        let Some(limiter) = &self.limiter else {
+            return Ok(());
+        };
+        if self.backlog_scheduled || !limiter.has_backlog() {
+            return Ok(());
+        }
+        self.backlog_scheduled = true;
+        let ms = limiter.next_token_in().as_millis().max(1) as u64;
+        self.out.timeout(ms, INPUT_BACKLOG)
+    }
+
+    /// 输入积压的消息
+    /// * 🚩仍有积压⇒再次安排定时器
+    #[allow(clippy::result_large_err)]
+    fn input_backlog(&mut self) -> ws::Result<()> {
+        self.backlog_scheduled = false;
+        let lines = match self.limiter.as_mut() {
+            Some(limiter) => limiter.drain(Instant::now()),
+            None => return Ok(()),
+        };
+        for line in lines {
+            self.input(&line);
+        }
+        self.schedule_backlog()
+    }
+
+    /// 回复「运行状态」
+    /// * 🚩仅回复该连接，不广播
+    /// * 🚩多实例⇒按路由回复各实例的运行状态
+    #[allow(clippy::result_large_err)]
+    fn reply_status(&mut self, line: &str) -> ws::Result<()> {
+        let (route, _) = self.supervisor.parse_route(line);
+        let managers = try_or_return_err!(self.supervisor.route(route); err => "在Websocket连接中查询运行状态失败：{err}");
+        for manager in managers {
+            let metrics = try_or_return_err!(manager.metrics.lock(); err => "在Websocket连接中获取运行统计失败：{err}");
+            self.queue.push(format_output_message_tagged(
+                &metrics.status_output(),
+                manager.name(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 连接销毁时，注销并关闭「待发消息队列」，并更新各实例的「交互状态」
+impl<R> Drop for Connection<R>
+where
+    R: VmRuntimeBoost + Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.remove(&self.id);
+        }
+        self.queue.close();
+        // 更新各实例的「交互状态」
+        for manager in self.supervisor.managers() {
+            manager.interactivity.client_disconnected();
+        }
+    }
 }
 
 impl<R> Handler for Connection<R>
 where
-    R: VmRuntime + Send + Sync + 'static,
+    R: VmRuntimeBoost + Send + Sync + 'static,
 {
     fn on_shutdown(&mut self) {
         println_cli!([Info] "Websocket连接已关停")
     }
 
     fn on_open(&mut self, shake: ws::Handshake) -> ws::Result<()> {
-        if let Some(addr) = shake.remote_addr()? {
-            println_cli!([Info] "Websocket连接已打开：{addr}")
+        let addr = shake.remote_addr()?.unwrap_or_default();
+        self.log_event(format!("Websocket连接 {} 已打开：{addr}", self.id));
+        // 开始心跳
+        if self.heartbeat_secs > 0 {
+            self.out.timeout(self.heartbeat_secs * 1000, HEARTBEAT)?;
         }
         Ok(())
     }
 
     fn on_message(&mut self, msg: ws::Message) -> ws::Result<()> {
         println_cli!([Debug] "Websocket收到消息：{msg}");
-        // 获取所需的参数信息 | 在此时独占锁
-        let runtime = &mut *try_or_return_err!(self.runtime.lock(); poison => "在Websocket连接中获取运行时失败：{poison}");
-        let config = &self.config;
-        let output_cache = &mut *try_or_return_err!(self.output_cache.lock(); err => "在Websocket连接中获取输出缓存失败：{err}");
+        if self.shutdown_if_aborted()? {
+            return Ok(());
+        }
+        let line = msg.to_string();
 
-        // 输入信息，并监控缓存的新输出
-        // * 📝【2024-04-08 22:10:17】现在查明「Websocket线程阻塞」问题在Websocket「回传发送者」的`send`调用中
-        if_let_err_eprintln_cli! {
-            RuntimeManager::input_line_to_vm(
-                runtime,
-                &msg.to_string(),
-                config,
-                output_cache,
-                &config.config_path
-            )
-            => err => [Error] "在Websocket连接中输入「{msg}」时发生错误：{err}"
+        // 首条消息⇒尝试作为「输入模式握手」处理
+        if !self.received_first {
+            self.received_first = true;
+            if let Some(result) = parse_input_mode_handshake(&line) {
+                self.handshake(result);
+                return Ok(());
+            }
         }
 
-        Ok(())
+        // 查询运行状态⇒仅回复该连接
+        if self.supervisor.parse_route(line.trim()).1 == STATUS_COMMAND {
+            return self.reply_status(line.trim());
+        }
+
+        // 按路由输入信息（经限流），并监控缓存的新输出
+        self.input_limited(line)
     }
 
     fn on_close(&mut self, code: ws::CloseCode, reason: &str) {
-        println_cli!([Info] "Websocket连接关闭（退出码：{code:?}；原因：「{reason}」）");
+        self.log_event(format!(
+            "Websocket连接 {} 已关闭（退出码：{code:?}；原因：「{reason}」）",
+            self.id
+        ));
     }
 
     fn on_error(&mut self, err: ws::Error) {
@@ -188,12 +763,25 @@ where
             if let Some(104) = err.raw_os_error() {
                 return;
             }
+            // 心跳超时：已记录
+            if err.kind() == io::ErrorKind::TimedOut {
+                return;
+            }
         }
 
         println_cli!([Error] "连接发生错误：{err:?}");
     }
 
     fn on_timeout(&mut self, event: ws::util::Token) -> ws::Result<()> {
+        if self.shutdown_if_aborted()? {
+            return Ok(());
+        }
+        if event == HEARTBEAT {
+            return self.heartbeat();
+        }
+        if event == INPUT_BACKLOG {
+            return self.input_backlog();
+        }
         println_cli!([Warn] "连接超时：{:?}", event);
         Ok(())
     }
@@ -202,41 +790,65 @@ where
         // default implementation discards the timeout handle
         Ok(())
     }
+
+    fn on_frame(&mut self, frame: ws::Frame) -> ws::Result<Option<ws::Frame>> {
+        // 收到pong⇒客户端仍有响应
+        if frame.opcode() == ws::OpCode::Pong {
+            self.missed_pongs = 0;
+        }
+        // 其余同默认实现：不允许设置保留位
+        if frame.has_rsv1() || frame.has_rsv2() || frame.has_rsv3() {
+            return Err(ws::Error::new(
+                ws::ErrorKind::Protocol,
+                "Encountered frame with reserved bits set.",
+            ));
+        }
+        Ok(Some(frame))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct WSServer<R>
 where
-    R: VmRuntime,
+    R: VmRuntimeBoost + Send + Sync + 'static,
 {
-    /// 所涉及的虚拟机运行时
-    pub(crate) runtime: ArcMutex<R>,
+    /// 所涉及的（各实例的）监管者
+    pub(crate) supervisor: Supervisor<R>,
 
-    /// 所涉及的虚拟机配置
-    pub(crate) config: Arc<RuntimeConfig>,
+    /// 所有客户端的「待发消息队列」
+    pub(crate) clients: ClientQueues,
 
-    /// 所涉及的输出缓存
-    pub(crate) output_cache: ArcMutex<OutputCache>,
+    /// 每个客户端「待发消息队列」的容量
+    pub(crate) queue_size: usize,
+
+    /// 心跳间隔（秒）
+    pub(crate) heartbeat_secs: u64,
+
+    /// 每个客户端每秒最多输入的条数
+    /// * 📌[`None`]⇒不限流
+    pub(crate) max_inputs_per_second: Option<u32>,
+
+    /// 输入超出限制时的处理策略
+    pub(crate) rate_limit_policy: RateLimitPolicy,
+
+    /// 每个客户端最多积压的输入条数
+    pub(crate) input_backlog: usize,
 }
 
 /// 向所有「回传发送者」广播NAVM输出
 /// * 🎯回传所侦听到的NAVM输出
-pub(crate) fn broadcast_to_senders(
-    // senders: &mut ArcMutex<ResendSenders>,
-    broadcaster: &mut Sender,
+/// * 🚩置入各客户端的「待发消息队列」：不会因客户端接收过慢而阻塞
+/// * 🚩具名实例⇒附带实例名
+pub(crate) fn broadcast_to_clients(
+    clients: &ClientQueues,
     output: &Output,
+    instance: Option<&str>,
 ) -> Result<()> {
-    let output_str = format_output_message(output);
-
-    // println_cli!([Debug] "🏗️正在向接收者回传消息：\n{output_str}");
-    // * 通过一个`broadcaster`直接向所有连接广播消息
-    if_let_err_eprintln_cli! {
-        broadcaster.send(output_str.to_string())
-        => e => [Error] "广播消息失败：{e}"
-    };
-
-    // println_cli!([Debug] "✅向接收者回传消息完成：\n{output_str}");
-
+    let output_str = format_output_message_tagged(output, instance);
+    let clients = clients.lock().map_err(error_anyhow)?;
+    for queue in clients.values() {
+        queue.push(output_str.clone());
+    }
     Ok(())
 }
 
@@ -244,15 +856,17 @@ pub(crate) fn broadcast_to_senders(
 /// * 🎯绑定侦听器到输出缓存中，以便在「侦听器有输出」时广播
 /// * 🎯现在只有「输出缓存」会留存：因为`WebSocket.broadcaster`只在服务器启动后创建
 pub(crate) fn register_listener(
-    output_cache: &mut ArcMutex<OutputCache>,
-    mut broadcaster: Sender,
+    output_cache: &ArcMutex<OutputCache>,
+    clients: ClientQueues,
+    instance: Option<&str>,
 ) -> Result<()> {
     // 尝试解包「输出缓存」
     let output_cache = &mut *output_cache.lock().map_err(error_anyhow)?;
+    let instance = instance.map(str::to_string);
     output_cache.output_handlers.add_handler(move |output| {
         // 广播
         if_let_err_eprintln_cli! {
-            broadcast_to_senders(&mut broadcaster, &output)
+            broadcast_to_clients(&clients, &output, instance.as_deref())
             => e => [Error] "Websocket回传广播到发送者时出现错误：{:?}", e
         }
         // 返回
@@ -263,19 +877,43 @@ pub(crate) fn register_listener(
 
 impl<R> Factory for WSServer<R>
 where
-    R: VmRuntime + Send + Sync + 'static,
+    R: VmRuntimeBoost + Send + Sync + 'static,
 {
     type Handler = Connection<R>;
 
     fn connection_made(&mut self, sender: Sender) -> Connection<R> {
         let id = sender.connection_id();
         println_cli!([Info] "Websocket连接已在id {id} 处建立");
+        // 注册「待发消息队列」，并启动「发送线程」
+        let queue = Arc::new(ClientQueue::new(self.queue_size));
+        queue.spawn_sender(sender.clone());
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.insert(id, queue.clone());
+        }
+        // 更新各实例的「交互状态」 | 🎯`''terminate(if-no-user)`
+        for manager in self.supervisor.managers() {
+            manager.interactivity.client_connected();
+        }
         // 返回连接
         Connection {
-            runtime: self.runtime.clone(),
-            config: self.config.clone(),
-            output_cache: self.output_cache.clone(),
+            supervisor: self.supervisor.clone(),
+            queue,
+            clients: self.clients.clone(),
+            input_mode: self.supervisor.default_config().input_mode,
+            received_first: false,
             id,
+            out: sender,
+            heartbeat_secs: self.heartbeat_secs,
+            missed_pongs: 0,
+            limiter: self.max_inputs_per_second.map(|rate| {
+                InputRateLimiter::new(
+                    rate,
+                    self.rate_limit_policy,
+                    self.input_backlog,
+                    Instant::now(),
+                )
+            }),
+            backlog_scheduled: false,
         }
     }
 
@@ -289,4 +927,487 @@ where
     }
 }
 
-// TODO: ❓【2024-04-07 12:42:51】单元测试不好做：网络连接难以被模拟
+/// 单元测试
+/// * 🚩基于「模拟运行时」[`FakeVmRuntime`](babel_nar::runtimes::FakeVmRuntime)，无需CIN可执行文件
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::RuntimeConfig;
+    use babel_nar::{
+        runtimes::{FakeVm, FakeVmRuntime},
+        test_tools::VmOutputCache,
+        tests::test_runtime_config,
+    };
+    use navm::vm::{VmLauncher, VmRuntime};
+    use std::{
+        io::Write,
+        net::TcpStream,
+        sync::mpsc::{channel, Receiver},
+        time::{Duration, Instant},
+    };
+
+    /// 测试用Websocket地址
+    /// * 🚩端口为`0`：由系统分配空闲端口，避免并行测试、本地其它服务间的冲突
+    const TEST_HOST: &str = "127.0.0.1";
+
+    /// 基于「模拟运行时」的运行时管理器
+    /// * 🚩启用Websocket，其它均为默认配置
+    pub fn fake_manager() -> RuntimeManager<FakeVmRuntime> {
+        fake_manager_with_queue(None)
+    }
+
+    /// 基于「模拟运行时」的运行时管理器，并指定「待发消息队列」容量
+    pub fn fake_manager_with_queue(queue_size: Option<usize>) -> RuntimeManager<FakeVmRuntime> {
+        fake_manager_with_websocket(LaunchConfigWebsocket {
+            host: TEST_HOST.into(),
+            port: 0,
+            queue_size,
+            ..Default::default()
+        })
+    }
+
+    /// 启动Websocket服务端
+    /// * ⚙️返回服务端线程，以及系统分配的端口
+    pub fn spawn_test_ws_server(
+        manager: &RuntimeManager<FakeVmRuntime>,
+    ) -> (JoinHandle<Result<()>>, u16) {
+        let supervisor = Supervisor::new(vec![manager.clone()]).unwrap();
+        let (handle, address) = bind_ws_server(&supervisor).unwrap();
+        (handle, address.port())
+    }
+
+    /// 基于「模拟运行时」的运行时管理器，并指定Websocket配置
+    pub fn fake_manager_with_websocket(
+        websocket: LaunchConfigWebsocket,
+    ) -> RuntimeManager<FakeVmRuntime> {
+        let config = RuntimeConfig {
+            websocket: Some(websocket),
+            input_mode: InputMode::Nal,
+            ..test_runtime_config()
+        };
+        let runtime = FakeVm::new().launch().expect("模拟虚拟机启动失败");
+        RuntimeManager::new(runtime, config)
+    }
+
+    /// 启动一个客户端
+    /// * 🚩连接后依次发送消息，收到的所有NAVM输出经通道传回
+    #[allow(clippy::result_large_err)]
+    pub fn spawn_client(port: u16, messages: &[impl ToString]) -> Receiver<Output> {
+        let (tx, rx) = channel();
+        let messages = messages.iter().map(ToString::to_string).collect::<Vec<_>>();
+        thread::spawn(move || {
+            ws::connect(format!("ws://{TEST_HOST}:{port}"), move |out| {
+                for message in &messages {
+                    out.send(message.as_str()).expect("客户端发送消息失败");
+                }
+                let tx = tx.clone();
+                move |msg: ws::Message| {
+                    let outputs = serde_json::from_str::<Vec<Output>>(&msg.to_string())
+                        .expect("服务端回传的不是NAVM输出数组");
+                    for output in outputs {
+                        let _ = tx.send(output);
+                    }
+                    Ok(())
+                }
+            })
+        });
+        rx
+    }
+
+    /// 在超时前收集输出，直到满足条件
+    pub fn receive_until(
+        rx: &Receiver<Output>,
+        timeout: Duration,
+        mut criterion: impl FnMut(&[Output]) -> bool,
+    ) -> Vec<Output> {
+        let start = Instant::now();
+        let mut outputs = vec![];
+        while !criterion(&outputs) && start.elapsed() < timeout {
+            if let Ok(output) = rx.recv_timeout(Duration::from_millis(100)) {
+                outputs.push(output);
+            }
+        }
+        outputs
+    }
+
+    /// 是否有「回显」的输入包含指定内容
+    fn has_echo(outputs: &[Output], content: &str) -> bool {
+        outputs
+            .iter()
+            .any(|o| matches!(o, Output::IN { content: c, .. } if c.contains(content)))
+    }
+
+    fn is_info(output: &Output) -> bool {
+        matches!(output, Output::INFO { .. })
+    }
+
+    fn is_error(output: &Output) -> bool {
+        matches!(output, Output::ERROR { .. })
+    }
+
+    /// 测试/逐连接协商输入模式
+    /// * 🚩两个客户端分别使用`cmd`与`nal`模式，共享同一个运行时
+    /// * 📌`NSE <A --> B>.`仅能被解析为NAVM指令，`<B --> C>.`仅能被解析为NAL输入
+    #[test]
+    fn input_mode_handshake() {
+        let mut manager = fake_manager();
+        let _thread_read = manager.spawn_read_output().unwrap();
+        let (_thread_ws, port) = spawn_test_ws_server(&manager);
+        thread::sleep(Duration::from_millis(200));
+
+        let timeout = Duration::from_secs(10);
+        let cmd_client = spawn_client(port, &[r#"{"inputMode":"cmd"}"#, "NSE <A --> B>."]);
+        let nal_client = spawn_client(port, &[r#"{"inputMode":"nal"}"#, "<B --> C>."]);
+        let bad_client = spawn_client(port, &[r#"{"inputMode":"unknown"}"#]);
+
+        // 各自收到握手结果，且两种输入均被执行（回显广播到所有客户端）
+        let both_echoed =
+            |outputs: &[Output]| has_echo(outputs, "<A --> B>") && has_echo(outputs, "<B --> C>");
+        for client in [&cmd_client, &nal_client] {
+            let outputs = receive_until(client, timeout, both_echoed);
+            assert!(both_echoed(&outputs), "{outputs:?}");
+            assert!(outputs.iter().any(is_info), "{outputs:?}");
+            assert!(!outputs.iter().any(is_error), "{outputs:?}");
+        }
+
+        // 未知模式⇒仅该客户端收到错误
+        let outputs = receive_until(&bad_client, timeout, |o| o.iter().any(is_error));
+        assert!(outputs.iter().any(is_error), "{outputs:?}");
+        assert!(!outputs.iter().any(is_info), "{outputs:?}");
+
+        // 终止运行时
+        manager.runtime.lock().unwrap().terminate().unwrap();
+    }
+
+    /// 测试/待发消息队列
+    /// * 🎯队列已满⇒丢弃最旧的消息，并在之后发送一条「已丢弃」警告
+    #[test]
+    fn client_queue() {
+        let queue = ClientQueue::new(3);
+        for i in 1..=5 {
+            queue.push(i.to_string());
+        }
+        // 先是「已丢弃」警告
+        let warn = queue.pop().unwrap();
+        let warn = serde_json::from_str::<Vec<Output>>(&warn).unwrap();
+        assert!(
+            matches!(&warn[..], [Output::UNCLASSIFIED { r#type, content, .. }] if r#type == "WARN" && content.contains('2')),
+            "{warn:?}"
+        );
+        // 再是保留下来的最新消息
+        assert_eq!(queue.pop().as_deref(), Some("3"));
+        assert_eq!(queue.pop().as_deref(), Some("4"));
+        assert_eq!(queue.pop().as_deref(), Some("5"));
+        // 关闭后不再阻塞
+        queue.close();
+        assert_eq!(queue.pop(), None);
+    }
+
+    /// 测试/慢客户端不阻塞运行时
+    /// * 🎯仿照`ws_server_test`中的`test_overwhelming_nse`：短时间内产生大量输出
+    /// * 🚩一个客户端完成握手后从不读取，另一个客户端正常读取
+    /// * 📌大量输出后，服务端仍能处理新的Websocket输入，并将其回显广播给正常读取的客户端
+    #[test]
+    fn slow_client_not_blocking() {
+        const N: usize = 2000;
+        let mut manager = fake_manager_with_queue(Some(0x10));
+        let _thread_read = manager.spawn_read_output().unwrap();
+        let (_thread_ws, port) = spawn_test_ws_server(&manager);
+        thread::sleep(Duration::from_millis(200));
+
+        // 从不读取的客户端：手动完成Websocket握手，此后不再读取
+        let mut slow_client = TcpStream::connect((TEST_HOST, port)).unwrap();
+        slow_client
+            .write_all(
+                b"GET / HTTP/1.1\r\n\
+                Host: localhost\r\n\
+                Upgrade: websocket\r\n\
+                Connection: Upgrade\r\n\
+                Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                Sec-WebSocket-Version: 13\r\n\r\n",
+            )
+            .unwrap();
+        // 正常读取的客户端
+        let reader = spawn_client(port, &[r#"{"inputMode":"cmd"}"#]);
+        thread::sleep(Duration::from_millis(200));
+
+        // 大量输出 | 直接置入输出缓存，较长的内容以尽快填满慢客户端的缓冲区
+        let long_content = "A".repeat(0x400);
+        for i in 0..N {
+            let output = Output::OTHER {
+                content: format!("{long_content}{i}"),
+            };
+            manager.output_cache.lock().unwrap().put(output).unwrap();
+        }
+
+        // 经由Websocket输入的结束标记，仍能被处理并广播
+        let _writer = spawn_client(port, &[r#"{"inputMode":"cmd"}"#, "NSE <END --> B>."]);
+        let outputs = receive_until(&reader, Duration::from_secs(30), |o| {
+            has_echo(o, "<END --> B>")
+        });
+        assert!(has_echo(&outputs, "<END --> B>"), "{}", outputs.len());
+
+        // 终止运行时
+        drop(slow_client);
+        manager.runtime.lock().unwrap().terminate().unwrap();
+    }
+
+    /// 测试/心跳
+    /// * 🎯从不响应ping的客户端，在`(1 + MAX_MISSED_PONGS) × 心跳间隔`后被断开
+    /// * 🚩一个客户端手动完成握手后不再读写（不回复pong），另一个客户端正常响应
+    /// * 📌连接事件记录在「输出缓存」中
+    #[test]
+    fn heartbeat_timeout() {
+        const HEARTBEAT_SECS: u64 = 1;
+        let mut manager = fake_manager_with_websocket(LaunchConfigWebsocket {
+            host: TEST_HOST.into(),
+            port: 0,
+            heartbeat_secs: Some(HEARTBEAT_SECS),
+            ..Default::default()
+        });
+        let _thread_read = manager.spawn_read_output().unwrap();
+        let (_thread_ws, port) = spawn_test_ws_server(&manager);
+        thread::sleep(Duration::from_millis(200));
+
+        // 正常响应的客户端 | `ws`客户端自动回复pong
+        let _alive_client = spawn_client(port, &[r#"{"inputMode":"cmd"}"#]);
+        // 从不响应的客户端：手动完成Websocket握手，此后不再读写
+        let mut dead_client = TcpStream::connect((TEST_HOST, port)).unwrap();
+        dead_client
+            .write_all(
+                b"GET / HTTP/1.1\r\n\
+                Host: localhost\r\n\
+                Upgrade: websocket\r\n\
+                Connection: Upgrade\r\n\
+                Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                Sec-WebSocket-Version: 13\r\n\r\n",
+            )
+            .unwrap();
+        let start = Instant::now();
+
+        // 缓存中的连接事件
+        let events = |pattern: &str| {
+            let output_cache = manager.output_cache.lock().unwrap();
+            output_cache
+                .borrow_inner()
+                .iter()
+                .filter(|o| matches!(o, Output::INFO { message } if message.contains(pattern)))
+                .count()
+        };
+        let window = Duration::from_secs((1 + MAX_MISSED_PONGS as u64) * HEARTBEAT_SECS);
+        while events("未响应心跳") == 0 && start.elapsed() < window * 3 {
+            thread::sleep(Duration::from_millis(100));
+        }
+        let elapsed = start.elapsed();
+
+        // 在预期的时间窗口内断开，且只有不响应的客户端被断开
+        assert_eq!(events("已打开"), 2);
+        assert_eq!(events("未响应心跳"), 1, "{elapsed:?}");
+        assert!(
+            elapsed >= window - Duration::from_millis(500),
+            "{elapsed:?}"
+        );
+        assert!(
+            elapsed < window + Duration::from_secs(HEARTBEAT_SECS),
+            "{elapsed:?}"
+        );
+        assert_eq!(events("已关闭"), 1);
+        // 正常响应的客户端仍保持连接
+        thread::sleep(Duration::from_secs(HEARTBEAT_SECS * 2));
+        assert_eq!(events("未响应心跳"), 1);
+        assert_eq!(events("已关闭"), 1);
+
+        // 终止运行时
+        drop(dead_client);
+        manager.runtime.lock().unwrap().terminate().unwrap();
+    }
+
+    /// 测试/查询运行状态
+    /// * 🚩`/status`仅回复发出请求的客户端；其它客户端只收到广播的回显
+    #[test]
+    fn status_command() {
+        let mut manager = fake_manager();
+        let _thread_read = manager.spawn_read_output().unwrap();
+        let (_thread_ws, port) = spawn_test_ws_server(&manager);
+        thread::sleep(Duration::from_millis(200));
+
+        let timeout = Duration::from_secs(10);
+        let observer = spawn_client(port, &[r#"{"inputMode":"nal"}"#]);
+        thread::sleep(Duration::from_millis(200));
+        let requester = spawn_client(port, &["<A --> B>.", STATUS_COMMAND]);
+
+        // 请求者收到运行统计
+        let status = |outputs: &[Output]| {
+            outputs.iter().find_map(|o| match o {
+                Output::INFO { message } => {
+                    serde_json::from_str::<crate::RuntimeMetricsSnapshot>(message).ok()
+                }
+                _ => None,
+            })
+        };
+        let outputs = receive_until(&requester, timeout, |o| status(o).is_some());
+        let snapshot = status(&outputs).expect("未收到运行统计");
+        assert_eq!(snapshot.cmds_sent, 1, "{snapshot:?}");
+
+        // 其它客户端收到回显，但收不到运行统计
+        let outputs = receive_until(&observer, timeout, |o| has_echo(o, "<A --> B>"));
+        assert!(has_echo(&outputs, "<A --> B>"), "{outputs:?}");
+        let outputs = receive_until(&observer, Duration::from_millis(500), |_| false);
+        assert!(status(&outputs).is_none(), "{outputs:?}");
+
+        // 终止运行时
+        manager.runtime.lock().unwrap().terminate().unwrap();
+    }
+
+    /// 统计一串输入的限流结果
+    /// * ⚙️返回「立即输入、积压、拒绝」的条数
+    fn admit_all(
+        limiter: &mut InputRateLimiter,
+        lines: impl IntoIterator<Item = String>,
+        input_mode: InputMode,
+        now: Instant,
+    ) -> (usize, usize, usize) {
+        let mut counts = (0, 0, 0);
+        for line in lines {
+            match limiter.admit(line, input_mode, now) {
+                Admission::Accepted(..) => counts.0 += 1,
+                Admission::Queued => counts.1 += 1,
+                Admission::Rejected => counts.2 += 1,
+            }
+        }
+        counts
+    }
+
+    /// 测试/限流：拒绝策略
+    /// * 🚩突发只放行「每秒条数」，令牌按时间恢复
+    #[test]
+    fn rate_limit_reject() {
+        let start = Instant::now();
+        let mut limiter = InputRateLimiter::new(5, RateLimitPolicy::Reject, 4, start);
+        let burst = (0..20).map(|i| format!("NSE <A{i} --> B>."));
+        let counts = admit_all(&mut limiter, burst, InputMode::Cmd, start);
+        assert_eq!(counts, (5, 0, 15));
+        assert!(!limiter.has_backlog());
+        // 0.5秒后恢复两个半令牌
+        let later = start + Duration::from_millis(500);
+        let burst = (0..5).map(|i| format!("NSE <B{i} --> C>."));
+        let counts = admit_all(&mut limiter, burst, InputMode::Cmd, later);
+        assert_eq!(counts, (2, 0, 3));
+    }
+
+    /// 测试/限流：积压策略
+    /// * 🚩积压有界；相邻的`CYC`合并；令牌恢复后按顺序输入
+    #[test]
+    fn rate_limit_queue() {
+        let start = Instant::now();
+        let mut limiter = InputRateLimiter::new(2, RateLimitPolicy::Queue, 3, start);
+        let lines = [
+            "NSE <A --> B>.",
+            "CYC 1",
+            "CYC 2",
+            "CYC 3",
+            "@ona CYC 4",
+            "NSE <B --> C>.",
+            "CYC 5",
+            "NSE <C --> D>.",
+        ];
+        let lines = lines.map(String::from);
+        let counts = admit_all(&mut limiter, lines, InputMode::Cmd, start);
+        assert_eq!(counts, (2, 4, 2));
+        // 1秒后恢复两个令牌
+        let drained = limiter.drain(start + Duration::from_secs(1));
+        assert_eq!(drained, ["CYC 5", "@ona CYC 4"]);
+        assert!(limiter.has_backlog());
+        assert_eq!(limiter.next_token_in(), Duration::from_millis(500));
+        let drained = limiter.drain(start + Duration::from_millis(1500));
+        assert_eq!(drained, ["NSE <B --> C>."]);
+        assert!(!limiter.has_backlog());
+    }
+
+    /// 测试/合并`CYC`
+    #[test]
+    fn test_merge_cycles() {
+        let cases = [
+            ("CYC 1", "CYC 2", InputMode::Cmd, Some("CYC 3")),
+            ("@a CYC 1", "@a  CYC 2", InputMode::Cmd, Some("@a CYC 3")),
+            ("@a CYC 1", "@all CYC 2", InputMode::Cmd, None),
+            ("CYC 1", "NSE <A --> B>.", InputMode::Cmd, None),
+            ("10", "20", InputMode::Nal, Some("30")),
+            ("@all 10", "@all 20", InputMode::Nal, Some("@all 30")),
+            ("CYC 1", "CYC 2", InputMode::Nal, None),
+            ("10", "20", InputMode::Raw, None),
+        ];
+        for (former, latter, input_mode, expected) in cases {
+            assert_eq!(
+                merge_cycles(former, latter, input_mode).as_deref(),
+                expected,
+                "{former} + {latter} @ {input_mode:?}"
+            );
+        }
+    }
+
+    /// 测试/Websocket输入限流
+    /// * 🚩客户端一次性发送大量`NSE`：超出限制的输入被拒绝，并只回复该客户端
+    #[test]
+    fn rate_limit_websocket() {
+        const RATE: u32 = 5;
+        const N: usize = 20;
+        let mut manager = fake_manager_with_websocket(LaunchConfigWebsocket {
+            host: TEST_HOST.into(),
+            port: 0,
+            max_inputs_per_second: Some(RATE),
+            ..Default::default()
+        });
+        let _thread_read = manager.spawn_read_output().unwrap();
+        let (_thread_ws, port) = spawn_test_ws_server(&manager);
+        thread::sleep(Duration::from_millis(200));
+
+        let observer = spawn_client(port, &[r#"{"inputMode":"cmd"}"#]);
+        thread::sleep(Duration::from_millis(200));
+        let mut messages = vec![r#"{"inputMode":"cmd"}"#.to_string()];
+        messages.extend((0..N).map(|i| format!("NSE <A{i} --> B>.")));
+        let flooder = spawn_client(port, &messages);
+
+        // 发送者：恰好收到超出部分的拒绝
+        let errors = |outputs: &[Output]| outputs.iter().filter(|o| is_error(o)).count();
+        let outputs = receive_until(&flooder, Duration::from_secs(10), |o| {
+            errors(o) >= N - RATE as usize
+        });
+        assert_eq!(errors(&outputs), N - RATE as usize, "{outputs:?}");
+        // 旁观者：只收到被放行输入的回显
+        let outputs = receive_until(&observer, Duration::from_secs(1), |_| false);
+        let echoes = (0..N)
+            .filter(|i| has_echo(&outputs, &format!("<A{i} --> B>")))
+            .count();
+        assert_eq!(echoes, RATE as usize, "{outputs:?}");
+        assert_eq!(errors(&outputs), 0, "{outputs:?}");
+
+        // 终止运行时
+        manager.runtime.lock().unwrap().terminate().unwrap();
+    }
+
+    /// 测试/握手解析
+    #[test]
+    fn parse_handshake() {
+        assert!(matches!(
+            parse_input_mode_handshake(r#"{"inputMode":"cmd"}"#),
+            Some(Ok(InputMode::Cmd))
+        ));
+        assert!(matches!(
+            parse_input_mode_handshake(r#"{"inputMode":"nal"}"#),
+            Some(Ok(InputMode::Nal))
+        ));
+        assert!(matches!(
+            parse_input_mode_handshake(r#"{"inputMode":"raw"}"#),
+            Some(Ok(InputMode::Raw))
+        ));
+        assert!(matches!(
+            parse_input_mode_handshake(r#"{"inputMode":"unknown"}"#),
+            Some(Err(..))
+        ));
+        // 非握手消息
+        assert!(parse_input_mode_handshake("<A --> B>.").is_none());
+        assert!(parse_input_mode_handshake("NSE <A --> B>.").is_none());
+        assert!(parse_input_mode_handshake(r#"{"other":"cmd"}"#).is_none());
+    }
+}
@@ -13,6 +13,10 @@
 use anyhow::Result;
 use babel_nar::{
     cin_implements::{ona::ONA, opennars::OpenNARS, pynars::PyNARS},
+    cli_support::cin_search::{
+        name_match::{is_name_match, suggest_names},
+        path_walker::PathWalkerV1,
+    },
     eprintln_cli, println_cli,
     runtimes::CommandVmRuntime,
     tests::cin_paths::{ONA, OPENNARS, PYNARS_ROOT},
@@ -23,12 +27,24 @@ use navm::{
     output::Output,
     vm::{VmLauncher, VmRuntime},
 };
-use std::{fmt::Debug, io::stdin};
+use std::{
+    env::current_dir,
+    fmt::Debug,
+    io::stdin,
+    path::{Path, PathBuf},
+};
 
 const TEST_PATH_OPENNARS: &str = OPENNARS;
 const TEST_PATH_ONA: &str = ONA;
 const TEST_PATH_PYNARS: (&str, &str) = (PYNARS_ROOT, "pynars.ConsolePlus");
 
+/// 当前启动的CIN名称
+/// * 🎯启动失败时，用于搜索「名称相近」的可执行文件
+const CIN_NAME: &str = "PyNARS";
+
+/// 启动失败时，展示的候选数目
+const N_CANDIDATES: usize = 3;
+
 /// 启动并获取NARS
 /// * 🚩【2024-03-27 18:55:07】目前就返回一个测试用的运行时
 /// * 🎯敏捷开发用
@@ -45,16 +61,60 @@ fn put_cmd_to_nars(nars: &mut impl VmRuntime, cmd: Cmd) -> Result<()> {
 /// 主函数
 /// * 🚩【2024-04-02 20:58:07】现在更完整的支持交给BabelNAR CLI，此文件用于敏捷开发
 fn main() {
-    // 不断开始🔥
-    loop {
-        start();
-    }
+    // 不断开始🔥 | 启动失败⇒展示候选后退出
+    while start().is_ok() {}
 }
 
 /// 开始
-fn start() {
-    let nars = get_nars().launch().expect("无法启动虚拟机");
+/// * 🚩启动失败⇒打印搜索到的候选可执行文件
+fn start() -> Result<()> {
+    let nars = get_nars().launch().inspect_err(|e| {
+        eprintln_cli!([Error] "无法启动虚拟机：{e}");
+        print_candidates(CIN_NAME);
+    })?;
     shell(nars);
+    Ok(())
+}
+
+/// 打印「CIN自动搜索」的摘要
+/// * 🎯启动失败时，提示「可能想要启动的」可执行文件
+/// * 🚩从当前目录开始遍历，按「名称建议」的匹配度列出前几个候选
+fn print_candidates(cin_name: &str) {
+    // 深入条件
+    fn deep_criterion(path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| name.to_str().is_some_and(|s| is_name_match("nars", s)))
+    }
+    let Some(walker) = current_dir()
+        .ok()
+        .and_then(|cwd| PathWalkerV1::new(&cwd, deep_criterion).ok())
+    else {
+        return;
+    };
+    // 遍历并收集文件名
+    let paths = walker
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    let names = paths
+        .iter()
+        .filter_map(|path| path.file_name()?.to_str())
+        .collect::<Vec<_>>();
+    // 排序并展示
+    let suggestions = suggest_names(cin_name, names.iter().copied());
+    if suggestions.is_empty() {
+        println_cli!([Info] "未搜索到与「{cin_name}」相近的可执行文件");
+        return;
+    }
+    println_cli!([Info] "与「{cin_name}」最相近的候选：");
+    for (name, score) in suggestions.into_iter().take(N_CANDIDATES) {
+        // 找回完整路径 | 同名文件取首个
+        let path = paths
+            .iter()
+            .find(|path| path.file_name().is_some_and(|n| n == name))
+            .map_or_else(PathBuf::new, PathBuf::clone);
+        println_cli!([Info] "[{score}] {path:?}");
+    }
 }
 
 /// 打印错误
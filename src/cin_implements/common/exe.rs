@@ -14,13 +14,17 @@ use std::{ffi::OsStr, path::Path, process::Command};
 ///   * exe路径（可能不直接是可执行文件的路径）
 ///   * 当前文件夹（设置命令启动时的工作目录）
 ///   * 命令行参数（可以为空）
-pub fn generate_command<S>(
+///   * 环境变量（可以为空）：在继承自身环境变量的基础上追加、覆盖
+pub fn generate_command<S, K, V>(
     exe_path: impl AsRef<Path>,
     current_dir: Option<impl AsRef<Path>>,
     args: impl IntoIterator<Item = S>,
+    envs: impl IntoIterator<Item = (K, V)>,
 ) -> Command
 where
     S: AsRef<OsStr>,
+    K: AsRef<OsStr>,
+    V: AsRef<OsStr>,
 {
     // 构造指令
     let mut command = Command::new(exe_path.as_ref());
@@ -34,6 +38,9 @@ where
     // * 📝这里的`args`、`arg都返回的可变借用。。
     command.args(args);
 
+    // 设置环境变量
+    command.envs(envs);
+
     // 返回
     command
 }
@@ -5,14 +5,19 @@
 //! * 🚩【2024-03-27 15:31:02】取消「初始音量」的特化配置，将其变成一个「命令行参数生成器」而非独立的「启动器」
 
 use crate::runtimes::CommandGenerator;
-use std::{path::PathBuf, process::Command};
+use anyhow::{anyhow, Result};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 /// 启动Java运行时的命令
-const COMMAND_JAVA: &str = "java";
+pub const COMMAND_JAVA: &str = "java";
 
 /// jar文件启动的默认指令参数
-/// * 🎯默认预置指令：`java -Xmx1024m -jar [.jar文件路径]`
-/// * 🚩实际上"-Xmx1024m"非必要
+/// * 🎯默认预置指令：`java [..JVM参数] -jar [.jar文件路径]`
+/// * 📌JVM参数（如`-Xmx1024m`）必须位于`-jar`之前，否则会被当作jar程序自身的参数
 const COMMAND_ARGS_JAVA: [&str; 1] = ["-jar"];
 
 /// Java运行时启动配置参数：初始堆大小/最小堆大小
@@ -27,15 +32,40 @@ fn command_arg_xmx(size: usize) -> String {
     format!("-Xmx{size}m")
 }
 
+/// 判断路径是否为jar文件
+/// * 🎯启动配置中，`cmd`直接指定jar文件⇒以Java启动
+/// * 🚩仅依据扩展名，不区分大小写
+pub fn is_jar_path(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+}
+
+/// 判断启动命令是否为Java本身
+/// * 🎯启动配置中，`cmd: "java"`、`cmd: "C:/jdk/bin/java.exe"`等
+/// * 🚩仅依据文件名（不含扩展名）：`java`或`javaw`，不区分大小写
+pub fn is_java_program(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .file_stem()
+        .is_some_and(|stem| stem.eq_ignore_ascii_case("java") || stem.eq_ignore_ascii_case("javaw"))
+}
+
 /// Java jar启动器
 /// * 🎯以Java运行时专有形式启动虚拟机运行时
 ///   * 📄基于jar文件启动OpenNARS Shell
-///   * 默认预置指令：`java -jar [.jar文件路径] [..其它jar启动参数]`
+///   * 默认预置指令：`java [..JVM参数] -jar [.jar文件路径]`
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CommandGeneratorJava {
     /// jar文件路径
     /// * 📌必须有
     jar_path: PathBuf,
+    /// Java可执行文件路径
+    /// * 🚩可能没有：此时使用`PATH`中的`java`
+    java_path: Option<PathBuf>,
+    /// 附加的JVM参数
+    /// * 📄`["-Dfile.encoding=UTF-8"]`
+    /// * 🚩位于堆大小参数之后、`-jar`之前
+    jvm_args: Vec<String>,
     /// Java运行时的初始堆大小/最小堆大小
     /// * 📄在Java指令中的参数：`-Xms[数值]m`
     /// * 🚩可能没有：此时不会附加参数
@@ -56,24 +86,172 @@ impl CommandGeneratorJava {
             ..Default::default()
         }
     }
+
+    /// 配置/Java可执行文件路径
+    /// * 🎯系统中有多个Java版本时，指定其中之一
+    pub fn java_path(&mut self, java_path: impl Into<PathBuf>) {
+        self.java_path = Some(java_path.into());
+    }
+
+    /// 配置/附加的JVM参数
+    /// * 🚩追加到已有参数之后
+    pub fn jvm_args(&mut self, args: impl IntoIterator<Item = impl Into<String>>) {
+        self.jvm_args.extend(args.into_iter().map(Into::into));
+    }
+
+    /// 配置/初始堆大小（MB）
+    pub fn min_heap_size(&mut self, size: usize) {
+        self.min_heap_size = Some(size);
+    }
+
+    /// 配置/最大堆大小（MB）
+    pub fn max_heap_size(&mut self, size: usize) {
+        self.max_heap_size = Some(size);
+    }
+
+    /// 所使用的Java可执行文件
+    /// * 🚩未指定⇒`java`
+    pub fn java_program(&self) -> &OsStr {
+        match &self.java_path {
+            Some(path) => path.as_os_str(),
+            None => OsStr::new(COMMAND_JAVA),
+        }
+    }
 }
 
 /// 根据自身生成命令
 impl CommandGenerator for CommandGeneratorJava {
     fn generate_command(&self) -> Command {
         // 构造指令
-        let mut command_java = Command::new(COMMAND_JAVA);
-        // * 📝这里的`args`、`arg都返回的可变借用。。
-        command_java.args(COMMAND_ARGS_JAVA).arg(&self.jar_path);
+        let mut command_java = Command::new(self.java_program());
 
-        // 选择性添加参数
+        // 选择性添加参数 | ⚠️须在`-jar`之前
         if let Some(size) = self.min_heap_size {
             command_java.arg(command_arg_xms(size));
         }
         if let Some(size) = self.max_heap_size {
             command_java.arg(command_arg_xmx(size));
         }
+        command_java.args(&self.jvm_args);
+
+        // * 📝这里的`args`、`arg都返回的可变借用。。
+        command_java.args(COMMAND_ARGS_JAVA).arg(&self.jar_path);
 
         command_java
     }
 }
+
+/// 从`java -version`的输出中解析Java主版本号
+/// * 📄`java version "1.8.0_292"`⇒`8`
+/// * 📄`openjdk version "17.0.2" 2022-01-18`⇒`17`
+/// * 📄`openjdk version "21" 2023-09-19`⇒`21`
+/// * 🚩取首个引号内的版本字符串；以`1.`开头⇒取第二段
+pub fn parse_java_major_version(version_output: &str) -> Option<u32> {
+    let version = version_output.split('"').nth(1)?;
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    match parts.next()? {
+        "1" => parts.next()?.parse().ok(),
+        major => major.parse().ok(),
+    }
+}
+
+/// 检查Java版本
+/// * 🎯Java缺失或版本过低时，在启动前给出明确的错误，而非CIN启动失败后的一串报错
+/// * 🚩运行`java -version`，解析其输出（通常位于标准错误）中的主版本号
+/// * ⚙️返回检测到的主版本号
+///   * 📌无法解析版本号⇒不报错，返回[`None`]：交由启动过程自行报错
+pub fn check_java_version(java: impl AsRef<OsStr>, min_version: u32) -> Result<Option<u32>> {
+    let java = java.as_ref();
+    let output = Command::new(java)
+        .arg("-version")
+        .output()
+        .map_err(|e| anyhow!("找不到Java「{}」：{e}", java.to_string_lossy()))?;
+    let text = [output.stderr, output.stdout].concat();
+    let Some(version) = parse_java_major_version(&String::from_utf8_lossy(&text)) else {
+        return Ok(None);
+    };
+    match version < min_version {
+        true => Err(anyhow!(
+            "Java版本过低：需要Java {min_version}及以上，当前为Java {version}（{}）",
+            java.to_string_lossy()
+        )),
+        false => Ok(Some(version)),
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::manipulate;
+
+    /// 测试/生成命令：JVM参数位于`-jar`之前
+    #[test]
+    fn test_generate_command() {
+        let command = CommandGeneratorJava::new("nars.jar").generate_command();
+        assert_eq!(command.get_program(), "java");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["-jar", "nars.jar"]);
+
+        let command = manipulate!(
+            CommandGeneratorJava::new("nars.jar")
+            => .java_path("/opt/jdk8/bin/java")
+            => .min_heap_size(256)
+            => .max_heap_size(1024)
+            => .jvm_args(["-Dfile.encoding=UTF-8"])
+        )
+        .generate_command();
+        assert_eq!(command.get_program(), "/opt/jdk8/bin/java");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            [
+                "-Xms256m",
+                "-Xmx1024m",
+                "-Dfile.encoding=UTF-8",
+                "-jar",
+                "nars.jar"
+            ]
+        );
+    }
+
+    /// 测试/解析Java版本号
+    #[test]
+    fn test_parse_java_major_version() {
+        let cases = [
+            (r#"java version "1.8.0_292""#, Some(8)),
+            (r#"openjdk version "1.7.0_80""#, Some(7)),
+            (r#"openjdk version "17.0.2" 2022-01-18"#, Some(17)),
+            (r#"openjdk version "21" 2023-09-19 LTS"#, Some(21)),
+            (r#"java version "9-ea""#, Some(9)),
+            ("Error: could not find libjava.so", None),
+        ];
+        for (output, expected) in cases {
+            assert_eq!(parse_java_major_version(output), expected, "{output}");
+        }
+    }
+
+    /// 测试/Java不存在⇒报错
+    #[test]
+    fn test_java_not_found() {
+        let e = check_java_version("./babelnar-not-exist-java", 8).unwrap_err();
+        assert!(e.to_string().contains("找不到Java"), "{e}");
+    }
+
+    /// 测试/判断jar文件
+    #[test]
+    fn test_is_jar_path() {
+        assert!(is_jar_path("opennars.jar"));
+        assert!(is_jar_path("../OpenNARS.JAR"));
+        assert!(!is_jar_path("java"));
+        assert!(!is_jar_path("opennars.jar.exe"));
+    }
+
+    /// 测试/判断Java本身
+    #[test]
+    fn test_is_java_program() {
+        assert!(is_java_program("java"));
+        assert!(is_java_program("/usr/lib/jvm/bin/java"));
+        assert!(is_java_program("C:/jdk/bin/JAVAW.EXE"));
+        assert!(!is_java_program("opennars.jar"));
+        assert!(!is_java_program("javac"));
+    }
+}
@@ -4,13 +4,24 @@
 //! * 🚩从Julia脚本（`.jl`）启动NARS
 
 use crate::runtimes::CommandGenerator;
-use std::{path::PathBuf, process::Command};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 /// 启动Julia运行时的命令
 const COMMAND_JULIA: &str = "julia";
 
 /// ! Julia启动脚本无需附加参数
 
+/// 「日志重定向」模式下的Julia引导代码
+/// * 🎯让经由`@info`等打印的内容可被截获
+///   * 📌Julia日志默认写入**标准错误**，而非标准输出
+///   * 🚩将全局日志器指向标准输出，再引入原`.jl`脚本
+/// * 📌`{}`处填入Julia字符串字面量形式的脚本路径
+const JULIA_BOOTSTRAP_LOG_TO_STDOUT: &str =
+    "using Logging; global_logger(ConsoleLogger(stdout)); include({})";
+
 /// OpenJunars运行时启动器
 /// * 🎯配置OpenJunars专有的东西
 /// * 🎯以Julia模块形式启动OpenJunars
@@ -22,6 +33,10 @@ const COMMAND_JULIA: &str = "julia";
 pub struct CommandGeneratorJulia {
     /// Julia脚本文件路径
     jl_path: PathBuf,
+
+    /// 是否将Julia日志重定向至标准输出
+    /// * 🎯截获经由`@info`打印的结果（如OpenJunars的回答）
+    log_to_stdout: bool,
 }
 
 impl CommandGeneratorJulia {
@@ -29,8 +44,26 @@ impl CommandGeneratorJulia {
         Self {
             // 转换为路径
             jl_path: jl_path.into(),
+            log_to_stdout: false,
         }
     }
+
+    /// 配置/是否将Julia日志重定向至标准输出
+    /// * 🚩启用后，不再直接运行脚本，而是以`-e`执行引导代码
+    pub fn log_to_stdout(&mut self, log_to_stdout: bool) {
+        self.log_to_stdout = log_to_stdout;
+    }
+}
+
+/// 将路径转换为Julia字符串字面量
+/// * 🚩转义反斜杠、双引号与插值符`$`
+fn julia_string_literal(path: &Path) -> String {
+    let escaped = path
+        .to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$");
+    format!("\"{escaped}\"")
 }
 
 /// 转换为Julia启动命令
@@ -39,10 +72,40 @@ impl CommandGenerator for CommandGeneratorJulia {
         // 构造指令
         let mut command_julia = Command::new(COMMAND_JULIA);
 
-        // 填入路径参数
-        command_julia.arg(&self.jl_path);
+        // 填入路径参数 | 重定向日志⇒以引导代码引入脚本
+        match self.log_to_stdout {
+            true => command_julia.arg("-e").arg(
+                JULIA_BOOTSTRAP_LOG_TO_STDOUT.replace("{}", &julia_string_literal(&self.jl_path)),
+            ),
+            false => command_julia.arg(&self.jl_path),
+        };
 
         // 返回
         command_julia
     }
 }
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::manipulate;
+
+    /// 测试/日志重定向
+    #[test]
+    fn test_log_to_stdout() {
+        let generator = manipulate!(
+            CommandGeneratorJulia::new(r#"C:\a "b"\$c.jl"#)
+            => .log_to_stdout(true)
+        );
+        let command = generator.generate_command();
+        let args = command.get_args().collect::<Vec<_>>();
+        assert_eq!(
+            args,
+            [
+                "-e",
+                r#"using Logging; global_logger(ConsoleLogger(stdout)); include("C:\\a \"b\"\\\$c.jl")"#
+            ]
+        );
+    }
+}
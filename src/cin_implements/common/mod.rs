@@ -13,4 +13,12 @@ util::pub_mod_and_pub_use! {
     julia
     // Node.js
     node_js
+    // 音量标度
+    volume
+    // 随机种子
+    seed
+    // 参数设置
+    param
+    // Narsese解析链
+    narsese_chain
 }
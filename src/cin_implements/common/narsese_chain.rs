@@ -0,0 +1,134 @@
+//! 统一的「Narsese解析链」
+//! * 🎯CIN中途切换输出格式时仍能解析
+//!   * 📄ONA在部分代码路径中输出OpenNARS风格的操作：`<(&/, <a --> b>, +5, (^left, {SELF})) =/> c>.`
+//!   * 📄OpenNARS的输出中出现ONA风格的乘积：`<(* {SELF}) --> ^left>.`
+//! * 🚩依次尝试：CIN自身的方言⇒CommonNarsese（ASCII）⇒（可选）其它CIN的方言
+//!   * 📌返回首个成功的结果；全部失败⇒返回CIN自身方言的错误
+//!   * 📌由「非自身方言」解析成功时，每种组合仅打印一次INFO
+
+use crate::{
+    cin_implements::{ona, opennars},
+    support::print_notice,
+};
+use anyhow::Result;
+use narsese::lexical::Narsese;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+/// 具名的Narsese解析器
+/// * 🎯记录「由哪个解析器解析成功」
+#[derive(Debug, Clone, Copy)]
+pub struct NarseseParser {
+    /// 解析器名称
+    pub name: &'static str,
+    /// 解析函数
+    pub parse: fn(&str) -> Result<Narsese>,
+}
+
+/// CommonNarsese（ASCII）解析器
+pub const PARSER_COMMON_ASCII: NarseseParser = NarseseParser {
+    name: "CommonNarsese",
+    parse: parse_common_ascii,
+};
+
+/// OpenNARS方言解析器
+pub const PARSER_OPENNARS: NarseseParser = NarseseParser {
+    name: "OpenNARS",
+    parse: opennars::parse,
+};
+
+/// ONA方言解析器
+pub const PARSER_ONA: NarseseParser = NarseseParser {
+    name: "ONA",
+    parse: ona::parse,
+};
+
+/// 所有CIN方言的解析器
+/// * 🎯CommonNarsese也解析失败时，依次尝试其它CIN的方言
+pub const DIALECT_PARSERS: [NarseseParser; 2] = [PARSER_OPENNARS, PARSER_ONA];
+
+/// 是否尝试其它CIN的方言
+/// * 🚩全局设置：默认启用
+static DIALECT_FALLBACK: AtomicBool = AtomicBool::new(true);
+
+/// 设置「是否尝试其它CIN的方言」
+/// * 📌禁用后，解析链仅含CIN自身的方言与CommonNarsese
+pub fn set_dialect_fallback(enabled: bool) {
+    DIALECT_FALLBACK.store(enabled, Ordering::Relaxed);
+}
+
+/// 获取「是否尝试其它CIN的方言」
+pub fn dialect_fallback() -> bool {
+    DIALECT_FALLBACK.load(Ordering::Relaxed)
+}
+
+/// 已报告过的「CIN方言⇒实际解析器」组合
+/// * 🎯每种组合只打印一次INFO，避免刷屏
+static REPORTED_FALLBACKS: Mutex<Vec<(&str, &str)>> = Mutex::new(vec![]);
+
+// 完整解析CommonNarsese：NAL测试中也要用到
+pub use crate::support::parse_common_ascii;
+
+/// 沿「解析链」解析Narsese
+/// * 🚩先用CIN自身的方言`dialect`，再依次尝试CommonNarsese与其它CIN的方言
+/// * ⚙️返回解析结果，以及解析成功的解析器名称
+/// * ⚠️全部失败⇒返回CIN自身方言的错误
+pub fn parse_narsese_chain(input: &str, dialect: NarseseParser) -> Result<(Narsese, &'static str)> {
+    let dialect_error = match (dialect.parse)(input) {
+        Ok(narsese) => return Ok((narsese, dialect.name)),
+        Err(e) => e,
+    };
+    let other_dialects = DIALECT_PARSERS
+        .iter()
+        .filter(|parser| dialect_fallback() && parser.name != dialect.name);
+    for parser in [&PARSER_COMMON_ASCII].into_iter().chain(other_dialects) {
+        if let Ok(narsese) = (parser.parse)(input) {
+            report_fallback(dialect.name, parser.name);
+            return Ok((narsese, parser.name));
+        }
+    }
+    Err(dialect_error)
+}
+
+/// 报告「由非自身方言解析成功」
+/// * 🚩每种组合仅报告一次
+fn report_fallback(dialect: &'static str, parser: &'static str) {
+    let Ok(mut reported) = REPORTED_FALLBACKS.lock() else {
+        return;
+    };
+    if !reported.contains(&(dialect, parser)) {
+        reported.push((dialect, parser));
+        print_notice(
+            "INFO",
+            &format!("{dialect}方言未能解析的Narsese，已改用{parser}解析器"),
+        );
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/解析链：自身方言失败⇒依次尝试其它解析器，并返回解析成功的解析器名称
+    #[test]
+    fn test_parse_narsese_chain() {
+        let parser_name = |input, dialect| parse_narsese_chain(input, dialect).unwrap().1;
+        // 自身方言
+        assert_eq!(parser_name("<(* {SELF}) --> ^left>.", PARSER_ONA), "ONA");
+        // CommonNarsese
+        assert_eq!(
+            parser_name("<(* {SELF}) --> ^left>.", PARSER_OPENNARS),
+            "CommonNarsese"
+        );
+        // 其它CIN的方言
+        assert_eq!(
+            parser_name("<(&/, <a --> b>, +5, (^left, {SELF})) =/> c>.", PARSER_ONA),
+            "OpenNARS"
+        );
+        // 全部失败⇒报错
+        assert!(parse_narsese_chain(")(", PARSER_ONA).is_err());
+    }
+}
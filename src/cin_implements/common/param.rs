@@ -0,0 +1,64 @@
+//! 统一的「参数设置」指令
+//! * 🎯运行时调整CIN的参数：决策阈值、随机探索的操作数……
+//!   * 📄替代借助`REM`或原始输入直接写`*decisionthreshold=0.6`
+//! * 📌NAVM未内置此指令⇒借助[`Cmd::Custom`]扩展：`SET decisionthreshold=0.6`
+//!   * 📄NAL中可写作`''set-param: decisionthreshold=0.6`
+//! * 🚩各CIN仅转译其支持的参数；不支持⇒[`TranslateError::UnsupportedInput`]（仅警告，不中止）
+//!
+//! ## 各CIN的对应
+//!
+//! | CIN | 转译结果 | 支持的参数 |
+//! | :-- | :-- | :-- |
+//! | OpenNARS | `*key=value` | [`opennars::PARAM_SUPPORT`](crate::cin_implements::opennars::PARAM_SUPPORT) |
+//! | ONA | `*key=value` | [`ona::PARAM_SUPPORT`](crate::cin_implements::ona::PARAM_SUPPORT) |
+//! | PyNARS | `/config key value` | 原样转发，由PyNARS自行检查 |
+//! | CXinNARS.js | （不支持） | |
+//! | NARS-Python | （不支持） | |
+//! | OpenJunars | （不支持） | |
+//! | Native | `SET key=value` | 原样转发NAVM指令 |
+
+use crate::runtimes::TranslateError;
+use anyhow::Result;
+
+// 指令的构造与解析：不依赖具体CIN
+pub use crate::support::param_cmd::*;
+
+/// CIN对参数的支持情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamSupport {
+    /// 仅支持列出的参数
+    /// * 🚩参数名不区分大小写，转译时使用列表中的写法
+    Whitelist(&'static [&'static str]),
+    /// 原样转发所有参数
+    /// * 🎯CIN自身会检查参数名
+    Passthrough,
+}
+
+impl ParamSupport {
+    /// 解析出CIN所支持的参数名
+    /// * 🚩不支持⇒[`None`]
+    pub fn resolve<'a>(&self, key: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Whitelist(keys) => keys
+                .iter()
+                .find(|known| known.eq_ignore_ascii_case(key))
+                .copied(),
+            Self::Passthrough => Some(key),
+        }
+    }
+}
+
+/// 转译「参数设置」指令
+/// * 🚩`format`：参数名、参数值⇒CIN输入
+/// * ⚠️参数不受支持⇒[`TranslateError::UnsupportedInput`]
+pub fn translate_param(
+    tail: &str,
+    support: ParamSupport,
+    format: impl FnOnce(&str, &str) -> String,
+) -> Result<String> {
+    let (key, value) = parse_param(tail)?;
+    match support.resolve(key) {
+        Some(key) => Ok(format(key, value)),
+        None => Err(TranslateError::UnsupportedInput(param_cmd(key, value)).into()),
+    }
+}
@@ -2,9 +2,16 @@
 //! * 🎯通用于任何基于Python源码的CIN，不仅仅是PyNARS
 //! * 🎯封装「NAVM运行时启动过程」中有关「Python启动环境配置」的部分
 //! * 🚩从Python模块（`.py`脚本）启动NARS
+//! * ✨支持虚拟环境（venv、conda）：指定解释器、虚拟环境目录，以及附加的`PYTHONPATH`
 
 use crate::runtimes::CommandGenerator;
-use std::{path::PathBuf, process::Command};
+use anyhow::{anyhow, Result};
+use std::{
+    env,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 /// 启动Python运行时的命令
 const COMMAND_PYTHON: &str = "python";
@@ -13,6 +20,99 @@ const COMMAND_PYTHON: &str = "python";
 /// * 🎯默认预置指令：`python -m [当前工作目录下的Python模块]`
 const COMMAND_ARGS_PYTHON: [&str; 1] = ["-m"];
 
+/// 虚拟环境中Python解释器的候选位置
+/// * 📌Windows：venv位于`Scripts\python.exe`，conda环境位于根目录`python.exe`
+/// * 📌其它平台：venv、conda环境均位于`bin/python`，部分仅有`bin/python3`
+fn venv_python_candidates(windows: bool) -> &'static [&'static [&'static str]] {
+    match windows {
+        true => &[&["Scripts", "python.exe"], &["python.exe"]],
+        false => &[&["bin", "python"], &["bin", "python3"]],
+    }
+}
+
+/// 拼接虚拟环境中的路径
+/// * 🚩沿用虚拟环境路径自身的分隔符：`C:\envs\nars`⇒`\`，`C:/envs/nars`⇒`/`
+///   * 📌无分隔符⇒Windows用`\`，其它平台用`/`
+/// * 🎯配置文件中的路径在各平台上都能得到一致的结果
+fn join_venv_path(venv: &str, parts: &[&str], windows: bool) -> PathBuf {
+    let separator = match (venv.contains('\\'), venv.contains('/')) {
+        (true, false) => '\\',
+        (false, true) => '/',
+        _ if windows => '\\',
+        _ => '/',
+    };
+    let mut path = venv.trim_end_matches(['\\', '/']).to_string();
+    for part in parts {
+        path.push(separator);
+        path.push_str(part);
+    }
+    PathBuf::from(path)
+}
+
+/// 解析虚拟环境中的Python解释器
+/// * 🚩按[候选位置](venv_python_candidates)依次查找已存在的文件
+///   * 📌均不存在⇒返回首个候选：交由后续检查报错
+/// * 📄`.venv`⇒`.venv/bin/python`（Windows：`.venv\Scripts\python.exe`）
+pub fn venv_python(venv: impl AsRef<Path>) -> PathBuf {
+    resolve_venv_python(&venv.as_ref().to_string_lossy(), cfg!(windows))
+}
+
+/// 解析虚拟环境中的Python解释器（指定平台）
+fn resolve_venv_python(venv: &str, windows: bool) -> PathBuf {
+    let candidates = venv_python_candidates(windows)
+        .iter()
+        .map(|parts| join_venv_path(venv, parts, windows))
+        .collect::<Vec<_>>();
+    candidates
+        .iter()
+        .find(|path| path.is_file())
+        .unwrap_or(&candidates[0])
+        .clone()
+}
+
+/// 生成`PYTHONPATH`环境变量的值
+/// * 🚩附加的路径在前，环境中已有的`PYTHONPATH`在后
+/// * 🚩没有附加路径⇒[`None`]：不修改环境变量
+pub fn python_path_env(extra_paths: &[PathBuf]) -> Result<Option<OsString>> {
+    if extra_paths.is_empty() {
+        return Ok(None);
+    }
+    let existing = env::var_os("PYTHONPATH");
+    let paths = extra_paths
+        .iter()
+        .cloned()
+        .chain(existing.iter().flat_map(env::split_paths));
+    Ok(Some(env::join_paths(paths)?))
+}
+
+/// 检查Python解释器能否导入指定模块
+/// * 🎯在真正启动之前，给出「解释器不存在」「环境中未安装CIN」等可操作的错误
+/// * 🚩在传入的命令（已设置解释器、工作目录与环境变量）上附加`-c "import 模块"`并运行
+/// * 📌只导入顶层包：`pynars.Console`⇒`import pynars`
+pub fn check_python_import(mut command: Command, module_path: &str) -> Result<()> {
+    let package = module_path.split('.').next().unwrap_or(module_path);
+    let python = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .arg("-c")
+        .arg(format!("import {package}"))
+        .output()
+        .map_err(|e| {
+            anyhow!("找不到Python解释器「{python}」：{e}\n请检查解释器路径或虚拟环境目录")
+        })?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let reason = stderr
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default();
+    Err(anyhow!(
+        "Python解释器「{python}」无法导入「{package}」：{reason}\n请在该环境中安装{package}，或指定其所在的解释器、虚拟环境与PYTHONPATH"
+    ))
+}
+
 /// Python启动命令生成器
 /// * 🎯以Python模块形式生成启动命令
 /// * 🚩【2024-03-25 08:55:07】基于Python模块文件启动NARS
@@ -29,6 +129,18 @@ pub struct CommandGeneratorPython {
     /// * 📄`pynars.Console`
     /// * 📄`root_path` + `pynars.Console` => `root_path/pynars/Console`
     module_path: String,
+
+    /// Python解释器路径
+    /// * 🚩可能没有：此时使用虚拟环境中的解释器，或`PATH`中的`python`
+    python_path: Option<PathBuf>,
+
+    /// 虚拟环境目录
+    /// * 📄venv、conda环境
+    /// * 🚩解释器位置自动解析，见[`venv_python`]
+    venv: Option<PathBuf>,
+
+    /// 附加的`PYTHONPATH`
+    extra_paths: Vec<PathBuf>,
 }
 
 impl CommandGeneratorPython {
@@ -38,22 +150,168 @@ impl CommandGeneratorPython {
             root_path: root_path.into(),
             // 转换为字符串
             module_path: module_path.to_string(),
+            // 其它全是空
+            python_path: None,
+            venv: None,
+            extra_paths: vec![],
+        }
+    }
+
+    /// 配置/Python解释器路径
+    /// * 📌优先于[虚拟环境](Self::venv)
+    pub fn python_path(&mut self, python_path: impl Into<PathBuf>) {
+        self.python_path = Some(python_path.into());
+    }
+
+    /// 配置/虚拟环境目录
+    pub fn venv(&mut self, venv: impl Into<PathBuf>) {
+        self.venv = Some(venv.into());
+    }
+
+    /// 配置/附加的`PYTHONPATH`
+    /// * 🚩追加到已有路径之后
+    pub fn extra_paths(&mut self, paths: impl IntoIterator<Item = impl Into<PathBuf>>) {
+        self.extra_paths.extend(paths.into_iter().map(Into::into));
+    }
+
+    /// 所使用的Python解释器
+    /// * 🚩优先级：解释器路径 > 虚拟环境 > `python`
+    pub fn python_program(&self) -> PathBuf {
+        match (&self.python_path, &self.venv) {
+            (Some(python_path), _) => python_path.clone(),
+            (None, Some(venv)) => venv_python(venv),
+            (None, None) => COMMAND_PYTHON.into(),
         }
     }
+
+    /// 生成「仅设置解释器与环境」的命令
+    /// * 🎯启动命令与「导入检查」共用
+    fn python_command(&self) -> Command {
+        let mut command = Command::new(self.python_program());
+        // * 🚩设置指令工作目录
+        // * 📝`python -m`无法自行指定所执行的工作目录，必须在`Command`中设置
+        command.current_dir(&self.root_path); // 以此设置当前工作目录
+
+        // * 🚩附加`PYTHONPATH` | 拼接失败（路径中含分隔符）⇒忽略
+        if let Ok(Some(python_path)) = python_path_env(&self.extra_paths) {
+            command.env("PYTHONPATH", python_path);
+        }
+        command
+    }
+
+    /// 检查解释器能否导入模块
+    /// * 📌见[`check_python_import`]
+    pub fn check_import(&self) -> Result<()> {
+        check_python_import(self.python_command(), &self.module_path)
+    }
 }
 
 /// 启动到「命令行运行时」
 impl CommandGenerator for CommandGeneratorPython {
     fn generate_command(&self) -> Command {
         // 构造指令
-        let mut command = Command::new(COMMAND_PYTHON);
+        let mut command = self.python_command();
+        command.args(COMMAND_ARGS_PYTHON).arg(&self.module_path);
+
         command
-            // * 🚩设置指令工作目录
-            // * 📝`python -m`无法自行指定所执行的工作目录，必须在`Command`中设置
-            .current_dir(&self.root_path) // 以此设置当前工作目录
-            .args(COMMAND_ARGS_PYTHON)
-            .arg(&self.module_path);
+    }
+}
 
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::manipulate;
+    use std::ffi::OsStr;
+
+    /// 命令中所设置的环境变量值
+    fn command_env<'a>(command: &'a Command, key: &str) -> Option<&'a OsStr> {
         command
+            .get_envs()
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, v)| v)
+    }
+
+    /// 测试/虚拟环境中的解释器：沿用路径自身的分隔符
+    #[test]
+    fn test_resolve_venv_python() {
+        let cases = [
+            (r"C:\envs\nars", true, r"C:\envs\nars\Scripts\python.exe"),
+            (r"C:\envs\nars\", true, r"C:\envs\nars\Scripts\python.exe"),
+            ("C:/envs/nars", true, "C:/envs/nars/Scripts/python.exe"),
+            (".venv", true, r".venv\Scripts\python.exe"),
+            ("/home/user/.venv", false, "/home/user/.venv/bin/python"),
+            ("/home/user/.venv/", false, "/home/user/.venv/bin/python"),
+            (r"D:\conda\nars", false, r"D:\conda\nars\bin\python"),
+            (".venv", false, ".venv/bin/python"),
+        ];
+        for (venv, windows, expected) in cases {
+            assert_eq!(
+                resolve_venv_python(venv, windows),
+                PathBuf::from(expected),
+                "{venv}"
+            );
+        }
+    }
+
+    /// 测试/虚拟环境中的解释器：优先使用已存在的候选
+    /// * 📄conda环境：解释器仅有`bin/python3`
+    #[test]
+    fn test_resolve_venv_python_existing() {
+        let venv = env::temp_dir().join(format!("babelnar_venv_{}", std::process::id()));
+        let bin = venv.join("bin");
+        std::fs::create_dir_all(&bin).expect("无法创建临时目录");
+        std::fs::write(bin.join("python3"), "").expect("无法创建文件");
+        let venv_str = venv.to_string_lossy().replace('\\', "/");
+        assert_eq!(
+            resolve_venv_python(&venv_str, false),
+            PathBuf::from(format!("{venv_str}/bin/python3"))
+        );
+        let _ = std::fs::remove_dir_all(&venv);
+    }
+
+    /// 测试/生成命令
+    #[test]
+    fn test_generate_command() {
+        let generator = CommandGeneratorPython::new("root", "pynars.Console");
+        let command = generator.generate_command();
+        assert_eq!(command.get_program(), "python");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["-m", "pynars.Console"]
+        );
+        assert_eq!(command_env(&command, "PYTHONPATH"), None);
+
+        // 解释器路径优先于虚拟环境
+        let command = manipulate!(
+            generator.clone()
+            => .venv("/home/user/.venv")
+            => .python_path("/usr/bin/python3.11")
+        )
+        .generate_command();
+        assert_eq!(command.get_program(), "/usr/bin/python3.11");
+
+        // 附加`PYTHONPATH`
+        let command = manipulate!(
+            generator
+            => .extra_paths(["/opt/pynars"])
+        )
+        .generate_command();
+        let python_path = command_env(&command, "PYTHONPATH").expect("未设置PYTHONPATH");
+        assert_eq!(
+            env::split_paths(python_path).next(),
+            Some(PathBuf::from("/opt/pynars"))
+        );
+    }
+
+    /// 测试/解释器不存在⇒报错
+    #[test]
+    fn test_check_import_not_found() {
+        let generator = manipulate!(
+            CommandGeneratorPython::new(".", "pynars.Console")
+            => .python_path("./babelnar-not-exist-python")
+        );
+        let e = generator.check_import().unwrap_err();
+        assert!(e.to_string().contains("找不到Python解释器"), "{e}");
     }
 }
@@ -0,0 +1,38 @@
+//! 统一的「随机种子」指令
+//! * 🎯可复现的实验：重置CIN后，以固定的随机种子重新开始
+//! * 📌NAVM未内置此指令⇒借助[`Cmd::Custom`]扩展：`SEED 42`
+//!   * 📄NAL中可写作`'''SEED 42`
+//!
+//! ## 各CIN的对应
+//!
+//! | CIN | 转译结果 | 备注 |
+//! | :-- | :-- | :-- |
+//! | OpenNARS | `*seed=N` | Shell属性 |
+//! | ONA | `*seed=N` | |
+//! | PyNARS | （不支持） | |
+//! | CXinNARS.js | （不支持） | |
+//! | NARS-Python | （不支持） | |
+//! | OpenJunars | （不支持） | |
+//! | Native | `SEED N` | 原样转发NAVM指令 |
+
+use anyhow::{anyhow, Result};
+use navm::cmd::Cmd;
+
+/// 「随机种子」指令的指令头
+pub const SEED_CMD_HEAD: &str = "SEED";
+
+/// 构造「随机种子」指令
+pub fn seed_cmd(seed: u64) -> Cmd {
+    Cmd::Custom {
+        head: SEED_CMD_HEAD.into(),
+        tail: seed.to_string(),
+    }
+}
+
+/// 从「随机种子」指令的「指令尾」中解析种子
+/// * 🚩须为非负整数
+pub fn parse_seed(tail: &str) -> Result<u64> {
+    let tail = tail.trim();
+    tail.parse()
+        .map_err(|e| anyhow!("无效的随机种子 {tail:?}：{e}"))
+}
@@ -0,0 +1,26 @@
+//! 统一的「音量」标度
+//! * 🎯NAVM指令`VOL`在各CIN间有一致的语义
+//! * 📌标度：`0`~`100`
+//!   * `0`：静默，只输出回答等必要信息
+//!   * `100`：输出全部推理过程
+//! * 🚩超出范围的音量会被截断到最大值，再由各CIN的输入转译器换算
+//!
+//! ## 各CIN的对应
+//!
+//! | CIN | 转译结果 | 备注 |
+//! | :-- | :-- | :-- |
+//! | OpenNARS | `*volume=N` | 标度相同 |
+//! | ONA | `*volume=N` | 标度相同 |
+//! | PyNARS | `/volume N` | 控制台命令，标度相同 |
+//! | NARS-Python | （不支持） | 无音量控制 |
+//! | CXinNARS.js | （不支持） | Shell未提供音量控制命令 |
+//! | OpenJunars | （不支持） | 无音量控制 |
+//! | Native | `VOL N` | 原样转发NAVM指令 |
+
+/// NAVM音量的最大值
+pub const MAX_VOLUME: usize = 100;
+
+/// 规整音量：截断到`0`~[`MAX_VOLUME`]
+pub fn normalize_volume(volume: usize) -> usize {
+    volume.min(MAX_VOLUME)
+}
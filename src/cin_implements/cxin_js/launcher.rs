@@ -3,15 +3,15 @@
 //! * 🚩只憎加「启动器」类型，而不增加「运行时」类型
 //!   * ✨不同启动器可以启动到相同运行时
 
-use super::{input_translate, output_translate};
+use super::{input_translate, output_translate, AnswerSynthesizer, READY_MARKER};
 use crate::{
     cin_implements::common::{generate_command_vm, CommandGeneratorNodeJS},
-    runtimes::{CommandGenerator, CommandVmRuntime},
+    runtimes::{CommandGenerator, CommandVmRuntime, IoTranslators, DEFAULT_STARTUP_TIMEOUT},
 };
 use anyhow::Result;
 use navm::vm::VmLauncher;
 use std::path::PathBuf;
-use util::pipe;
+use util::manipulate;
 
 /// CXinNARS.js Shell启动的默认指令参数
 /// * 🎯默认预置指令：`[.js文件路径] shell`
@@ -26,6 +26,11 @@ const COMMAND_ARGS_CXIN_NARS: [&str; 1] = ["shell"];
 pub struct CXinJS {
     /// Node.js命令生成器
     command_generator: CommandGeneratorNodeJS,
+
+    /// 是否合成回答
+    /// * 🎯Shell不输出回答时，将「回答了问题」的导出结论视作回答
+    /// * 📄参见[`AnswerSynthesizer`]
+    synthesize_answer: bool,
 }
 
 // ! 🚩【2024-03-25 09:37:22】目前暂时不提取至「VmExe」：预置的`shell`参数需要被处理
@@ -34,24 +39,35 @@ impl CXinJS {
     pub fn new(js_path: impl Into<PathBuf>) -> Self {
         Self {
             command_generator: CommandGeneratorNodeJS::new(js_path, COMMAND_ARGS_CXIN_NARS),
+            synthesize_answer: false,
         }
     }
+
+    /// 配置/合成回答
+    /// * 🚩启用后，输入输出转译器共享「问题记录」
+    pub fn synthesize_answer(&mut self, synthesize_answer: bool) {
+        self.synthesize_answer = synthesize_answer;
+    }
 }
 
 /// 启动到「命令行运行时」
 impl VmLauncher for CXinJS {
     type Runtime = CommandVmRuntime;
     fn launch(self) -> Result<CommandVmRuntime> {
+        // * 🚩固定的「输入输出转译器」 | 可选合成回答
+        let translators: IoTranslators = match self.synthesize_answer {
+            true => AnswerSynthesizer::new().translators(),
+            false => (input_translate, output_translate).into(),
+        };
         // 构造并启动虚拟机
-        pipe! {
-            self.command_generator
+        manipulate!(
             // 构造指令 | 预置的指令参数
-            => .generate_command()
-            // * 🚩固定的「输入输出转译器」
-            => generate_command_vm(_, (input_translate, output_translate))
-            // 🔥启动
-            => .launch()
-        }
+            generate_command_vm(self.command_generator.generate_command(), translators)
+            // * 🚩启动检查：启动即失败⇒报错并附带其输出；等待该CIN的就绪标志
+            => .startup_check(DEFAULT_STARTUP_TIMEOUT, READY_MARKER)
+        )
+        // 🔥启动
+        .launch()
     }
 }
 
@@ -2,6 +2,9 @@
 //! * 🚩只提供「一行启动」的功能封装
 //!   * 🎯无需自行配置「输入输出转译器」
 
+use crate::runtimes::Capabilities;
+use navm::output::Output;
+
 // 转译器
 util::mod_and_pub_use! {
     // 转译器
@@ -10,6 +13,21 @@ util::mod_and_pub_use! {
     launcher
 }
 
+/// 所支持的功能
+/// * ⚠️无时序、操作
+pub const CAPABILITIES: Capabilities = Capabilities {
+    answers: true,
+    ..Capabilities::NONE
+};
+
+/// 「步进完成」的回执判断
+/// * 🚩不报告⇒等待CIN安静下来
+pub const CYC_ACK_MATCHER: Option<fn(&Output) -> bool> = None;
+
+/// 启动检查的「就绪标志」
+/// * 🚩没有固定的就绪提示⇒任意一行输出即视作就绪
+pub const READY_MARKER: Option<fn(&str) -> bool> = None;
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
@@ -69,11 +87,14 @@ mod tests {
     }
 
     /// 测试/通用 | 基于Narsese
+    /// * 🚩启用「回答合成」：Shell只导出结论，不输出回答
     #[test]
     fn test_universal() {
-        // // 启动OpenNARS虚拟机
-        // let vm = launch_vm();
-        // // 使用通用测试逻辑
-        // test_simple_answer(vm)
+        // 启动CXinNARS.js虚拟机
+        let mut launcher = CXinJS::new(CXIN_NARS_JS_PATH);
+        launcher.synthesize_answer(true);
+        let vm = launcher.launch().expect("无法启动虚拟机");
+        // 使用通用测试逻辑
+        test_simple_answer(vm)
     }
 }
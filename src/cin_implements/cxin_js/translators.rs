@@ -6,52 +6,65 @@
 //!
 //! ## 输出样例
 //!
-//! * `Input: <<(* x) --> ^left> ==> A>. Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000`
-//! * `Derived: <<(* x) --> ^left> ==> <self --> good>>. Priority=0.245189 Truth: frequency=1.000000, confidence=0.810000`
-//! * `Answer: <B --> C>. creationTime=2 Truth: frequency=1.000000, confidence=0.447514`
+//! * `[IN] Narsese{{ <A-->B>. %1;0.9% }}Sentence`
+//! * `IN: <A-->B>. %1;0.9%`
+//! * `Derived: <B-->C>. %1;0.9%`
+//! * `OUT: <A-->C>. Truth: frequency=1.000000, confidence=0.810000`
+//! * `Answer: <A-->C>. %1;0.81%`
 //! * `Answer: None.`
-//! * `^deactivate executed with args`
-//! * `^left executed with args (* {SELF})`
-//! * `^left executed with args ({SELF} * x)`
-//! * `decision expectation=0.616961 implication: <((<{SELF} --> [left_blocked]> &/ ^say) &/ <(* {SELF}) --> ^left>) =/> <{SELF} --> [SAFE]>>. Truth: frequency=0.978072 confidence=0.394669 dt=1.000000 precondition: <{SELF} --> [left_blocked]>. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=50`
+//!
+//! ## 回答合成
+//!
+//! * ⚠️CXinNARS.js Shell在推理出问题的结论时，可能只输出「导出结论」而不输出「回答」
+//! * 🚩可选：记录输入的问题，将「词项与某个问题相同」的导出判断合成为`ANSWER`
+//!   * 📄参见[`AnswerSynthesizer`]
 
-use crate::runtimes::TranslateError;
+use crate::{
+    runtimes::{IoTranslators, TranslateError, NO_ANSWER},
+    support::print_notice,
+};
 use anyhow::Result;
 use narsese::{
     conversion::string::impl_lexical::{format_instances::FORMAT_ASCII, ParseResult},
-    lexical::Narsese,
+    lexical::{Narsese, Sentence, Task, Term},
 };
 use navm::{
     cmd::Cmd,
     output::{Operation, Output},
 };
 use regex::Regex;
-use util::{if_return, pipe};
+use std::sync::{Arc, LazyLock, Mutex};
+use util::{if_return, ResultBoost};
 
 /// CXinNARS.js的「输入转译」函数
 /// * 🎯用于将统一的「NAVM指令」转译为「CXinNARS.js Shell输入」
 /// * 📝[`IoProcess`]会自动将输入追加上换行符
-pub fn input_translate(cmd: Cmd) -> Result<String> {
+pub fn input_translate(cmd: Cmd) -> Result<Option<String>> {
     let content = match cmd {
         // 直接使用「末尾」，此时将自动格式化任务（可兼容「空预算」的形式）
         Cmd::NSE(..) => cmd.tail(),
         // CYC指令：运行指定周期数
         Cmd::CYC(n) => n.to_string(),
         // 注释 ⇒ 忽略 | ❓【2024-04-02 22:43:05】可能需要打印，但这样却没法统一IO（到处print的习惯不好）
-        Cmd::REM { .. } => String::new(),
+        Cmd::REM { .. } => return Ok(None),
         // 退出 ⇒ 特殊命令 | // * 🚩【2024-06-13 00:16:38】最新版本行为
         Cmd::EXI { .. } => "/q".into(),
         // 其它类型
         // * 📌【2024-03-24 22:57:18】基本足够支持
+        // * 📌包括VOL指令：该CIN的Shell未提供音量控制命令
         _ => return Err(TranslateError::UnsupportedInput(cmd).into()),
     };
     // 转译
-    Ok(content)
+    Ok(Some(content))
 }
 
 /// CXinNARS.js的「输出转译」函数
 /// * 🎯用于将CXinNARS.js Shell的输出（字符串）转译为「NAVM输出」
-/// * 🚩直接根据选取的「头部」进行匹配
+/// * 🚩先识别「头部」，再根据头部生成输出
+///   * 📌兼容`[头部] 内容`与`头部: 内容`两种形式
+///   * 📄`[IN]`/`IN:`/`Input:`⇒输入回显
+///   * 📄`[OUT]`/`OUT:`/`Derived:`⇒导出结论
+///   * 📄`[ANSWER]`/`ANSWER:`/`Answer:`⇒回答
 pub fn output_translate(content_raw: String) -> Result<Output> {
     // 特别处理：终止信号
     // * 📄"node:internal/modules/cjs/loader:1080\n  throw err"
@@ -60,33 +73,34 @@ pub fn output_translate(content_raw: String) -> Result<Output> {
         // 模块未找到
         content_raw.contains("Error: Cannot find module") => Ok(Output::TERMINATED { description: content_raw })
     }
-    // 匹配「输出类型」的正则表达式
+    // 分离「头部」与「尾部」
     // * ✅此处的「尾部」不会有前导空格（若识别出了「头部」）
-    let line_r = Regex::new(r"\[(\w+)\]\s*(.*)").unwrap();
-    let head;
-    let tail;
-    if let Some(captures) = line_r.captures(&content_raw) {
-        head = captures[1].to_lowercase();
-        tail = captures[2].to_owned();
-    } else {
-        head = String::new();
-        tail = content_raw.clone();
-    }
+    let (head, tail) = match split_head(&content_raw) {
+        Some((head, tail)) => (head, tail.to_owned()),
+        None => (String::new(), content_raw.clone()),
+    };
     // 根据「头部」生成输出
     let output = match head.as_str() {
-        "answer" => Output::ANSWER {
+        // 回答，但排除「无回答」的`Answer: None.`
+        "answer" if !is_none_answer(&tail) => Output::ANSWER {
             // 先提取其中的Narsese
             narsese: segment_narsese(&head, &tail),
             // 然后传入整个内容
             content_raw,
         },
-        "in" => Output::IN {
+        // 「无回答」⇒与ONA一致的「无回答」输出
+        "answer" => Output::UNCLASSIFIED {
+            r#type: NO_ANSWER.into(),
+            content: content_raw,
+            narsese: None,
+        },
+        "in" | "input" => Output::IN {
             // 先提取其中的Narsese
             narsese: segment_narsese(&head, &tail),
             // 然后传入整个内容
             content: tail,
         },
-        "out" => Output::OUT {
+        "out" | "derived" => Output::OUT {
             // 先提取其中的Narsese
             narsese: segment_narsese(&head, &tail),
             // 然后传入整个内容
@@ -117,6 +131,28 @@ pub fn output_translate(content_raw: String) -> Result<Output> {
     Ok(output)
 }
 
+/// 分离输出行的「头部」与「尾部」
+/// * 🚩头部统一转为小写
+/// * 📄`[IN] <A-->B>.` ⇒ `("in", "<A-->B>.")`
+/// * 📄`Derived: <A-->B>.` ⇒ `("derived", "<A-->B>.")`
+/// * ⚠️「冒号形式」只识别行首的单词：避免把Narsese内容误作头部
+fn split_head(content_raw: &str) -> Option<(String, &str)> {
+    static RE_HEAD: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^\s*(?:\[(\w+)\]|(\w+):)\s*(.*)").unwrap());
+    let captures = RE_HEAD.captures(content_raw)?;
+    let head = captures.get(1).or(captures.get(2))?.as_str().to_lowercase();
+    let tail = captures.get(3).map_or("", |m| m.as_str());
+    Some((head, tail))
+}
+
+/// 判断是否为「无回答」
+/// * 📄`Answer: None.`
+fn is_none_answer(tail: &str) -> bool {
+    tail.trim()
+        .trim_end_matches('.')
+        .eq_ignore_ascii_case("none")
+}
+
 /// （CXinNARS.js）从原始输出中解析操作
 pub fn parse_operation(content_raw: &str) -> Operation {
     #![allow(unused_variables)]
@@ -127,11 +163,11 @@ fn segment_narsese(head: &str, tail: &str) -> Option<Narsese> {
     match try_segment_narsese(tail) {
         Some(Ok(narsese)) => Some(narsese),
         Some(Err(e)) => {
-            println!("【{head}】在解析Narsese时出现错误：{e}");
+            print_notice("WARN", &format!("【{head}】在解析Narsese时出现错误：{e}"));
             None
         }
         None => {
-            println!("【{head}】未匹配到输出中的Narsese块");
+            print_notice("WARN", &format!("【{head}】未匹配到输出中的Narsese块"));
             None
         }
     }
@@ -140,19 +176,35 @@ fn segment_narsese(head: &str, tail: &str) -> Option<Narsese> {
 /// 分割 & 解析Narsese
 /// * 🎯提供解析CXinNARS中Narsese的方法
 ///   * ❗不包含任何副作用（如打印）
-/// * 🚩先通过正则表达式从模式`Narsese{{ 【Narsese内容】 }}【Narsese类型】`中分解出Narsese
-/// * 🚩再通过标准ASCII解析器解析
+/// * 🚩若有模式`Narsese{{ 【Narsese内容】 }}【Narsese类型】`，则先从中分解出Narsese
+/// * 🚩再将其中的真值等格式规整为CommonNarsese，通过标准ASCII解析器解析
+/// * ⚙️内容为空⇒[`None`]
 pub fn try_segment_narsese(input: &str) -> Option<ParseResult> {
-    let re_narsese = Regex::new(r"Narsese\{\{ (.+) \}\}").unwrap();
-    pipe!(
-        // 尝试从模式中提取Narsese
-        re_narsese.captures(input)
-        // 提取Narsese
-        => .map(
-            // 尝试解析Narsese
-            |captures| try_parse_narsese(&captures[1])
-        )
-    )
+    static RE_NARSESE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"Narsese\{\{ (.+) \}\}").unwrap());
+    let narsese = match RE_NARSESE.captures(input) {
+        Some(captures) => reform_to_common_narsese(&captures[1]),
+        None => reform_to_common_narsese(input),
+    };
+    match narsese.is_empty() {
+        true => None,
+        false => Some(try_parse_narsese(&narsese)),
+    }
+}
+
+/// 将CXinNARS.js输出的Narsese规整为CommonNarsese
+/// * 🚩`Truth: frequency=F, confidence=C` ⇒ `%F;C%`
+/// * 🚩删去`Priority=`、`creationTime=`等附加信息
+/// * 📄`<A-->C>. creationTime=2 Truth: frequency=1.000000, confidence=0.810000`
+///   ⇒ `<A-->C>. %1.000000;0.810000%`
+pub fn reform_to_common_narsese(narsese: &str) -> String {
+    static RE_TRUTH: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"Truth:\s*frequency=([\d.]+),?\s*confidence=([\d.]+)").unwrap()
+    });
+    static RE_EXTRA: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\w+=[\d.]+").unwrap());
+    let narsese = RE_TRUTH.replace_all(narsese, "%$1;$2%");
+    let narsese = RE_EXTRA.replace_all(&narsese, "");
+    narsese.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 /// （尝试）从输出中解析出Narsese
@@ -161,3 +213,186 @@ pub fn try_parse_narsese(narsese: &str) -> ParseResult {
     // 提取并解析Narsese字符串
     FORMAT_ASCII.parse(narsese)
 }
+
+/// 回答合成器
+/// * 🎯弥补CXinNARS.js Shell「只导出结论，不输出回答」的问题
+/// * 🚩在输入转译时记录问题的词项，在输出转译时将「词项与某个问题相同」的导出判断转为`ANSWER`
+///   * 📌输入、输出转译器共享同一份「问题记录」
+/// * ⚠️只进行词项的字面比较：不处理「可交换词项的顺序」「变量重命名」
+#[derive(Debug, Clone, Default)]
+pub struct AnswerSynthesizer {
+    /// 已输入的问题（词项）
+    questions: Arc<Mutex<Vec<Term>>>,
+}
+
+impl AnswerSynthesizer {
+    /// 构造函数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 输入转译：同[`input_translate`]，并记录输入的问题
+    pub fn input_translate(&self, cmd: Cmd) -> Result<Option<String>> {
+        if let Cmd::NSE(Task {
+            sentence: Sentence {
+                term, punctuation, ..
+            },
+            ..
+        }) = &cmd
+        {
+            if punctuation == "?" {
+                let mut questions = self.questions.lock().transform_err(err)?;
+                if !questions.contains(term) {
+                    questions.push(term.clone());
+                }
+            }
+        }
+        input_translate(cmd)
+    }
+
+    /// 输出转译：同[`output_translate`]，并将「回答了问题」的导出判断合成为`ANSWER`
+    pub fn output_translate(&self, content_raw: String) -> Result<Output> {
+        let output = output_translate(content_raw)?;
+        let Output::OUT {
+            content_raw,
+            narsese: Some(narsese),
+        } = output
+        else {
+            return Ok(output);
+        };
+        let answered = match &narsese {
+            Narsese::Sentence(Sentence {
+                term, punctuation, ..
+            })
+            | Narsese::Task(Task {
+                sentence: Sentence {
+                    term, punctuation, ..
+                },
+                ..
+            }) => punctuation == "." && self.questions.lock().transform_err(err)?.contains(term),
+            Narsese::Term(..) => false,
+        };
+        let narsese = Some(narsese);
+        Ok(match answered {
+            true => Output::ANSWER {
+                content_raw,
+                narsese,
+            },
+            false => Output::OUT {
+                content_raw,
+                narsese,
+            },
+        })
+    }
+
+    /// 生成共享「问题记录」的输入输出转译器
+    pub fn translators(self) -> IoTranslators {
+        let input = self.clone();
+        IoTranslators::new(
+            move |cmd| input.input_translate(cmd),
+            move |content_raw| self.output_translate(content_raw),
+        )
+    }
+}
+
+/// 锁定「问题记录」时的错误
+fn err(e: impl std::fmt::Display) -> anyhow::Error {
+    anyhow::anyhow!("无法锁定问题记录：{e}")
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use narsese::conversion::string::impl_lexical::shortcuts::*;
+
+    /// 测试/输出分类
+    /// * 🎯输入回显、导出结论、回答均能被识别，并解析出Narsese
+    #[test]
+    fn test_output_classify() {
+        let translate = |line: &str| output_translate(line.into()).expect("转译失败");
+        let term = |output: &Output| match output.get_narsese() {
+            Some(Narsese::Sentence(Sentence { term, .. }))
+            | Some(Narsese::Task(Task {
+                sentence: Sentence { term, .. },
+                ..
+            })) => Some(term.clone()),
+            _ => None,
+        };
+        // 输入回显
+        for line in [
+            "[IN] Narsese{{ <A-->B>. %1;0.9% }}Sentence",
+            "IN: <A-->B>. %1;0.9%",
+            "Input: <A --> B>. Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000",
+        ] {
+            let output = translate(line);
+            assert!(matches!(output, Output::IN { .. }), "{line} => {output:?}");
+            assert_eq!(term(&output), Some(nse_term!(<A --> B>)), "{line}");
+        }
+        // 导出结论
+        for line in [
+            "Derived: <B-->C>. %1;0.9%",
+            "[OUT] Narsese{{ <B-->C>. %1;0.9% }}Sentence",
+        ] {
+            let output = translate(line);
+            assert!(matches!(output, Output::OUT { .. }), "{line} => {output:?}");
+            assert_eq!(term(&output), Some(nse_term!(<B --> C>)), "{line}");
+        }
+        // 回答
+        let line = "Answer: <A-->C>. creationTime=2 Truth: frequency=1.000000, confidence=0.810000";
+        let output = translate(line);
+        assert!(matches!(output, Output::ANSWER { .. }), "{output:?}");
+        assert_eq!(
+            output.get_narsese(),
+            Some(&Narsese::Sentence(
+                nse_task!(<A --> C>. %1.000000;0.810000%).sentence
+            ))
+        );
+        // 无回答
+        let output = translate("Answer: None.");
+        assert!(output.is_type(NO_ANSWER), "{output:?}");
+        // 其它
+        assert!(matches!(translate("NARS started"), Output::OTHER { .. }));
+    }
+
+    /// 测试/真值规整
+    #[test]
+    fn test_reform() {
+        assert_eq!(
+            reform_to_common_narsese(
+                "<A-->C>. Priority=0.245189 Truth: frequency=1.000000, confidence=0.810000"
+            ),
+            "<A-->C>. %1.000000;0.810000%"
+        );
+        assert_eq!(
+            reform_to_common_narsese("<A-->C>. %1;0.9%"),
+            "<A-->C>. %1;0.9%"
+        );
+    }
+
+    /// 测试/回答合成
+    /// * 🎯只合成「回答了已输入问题」的导出判断
+    #[test]
+    fn test_answer_synthesize() -> Result<()> {
+        let IoTranslators {
+            input_translator,
+            output_translator,
+        } = AnswerSynthesizer::new().translators();
+        // 未提问⇒仍为导出结论
+        let output = output_translator("Derived: <A-->C>. %1;0.81%".into())?;
+        assert!(matches!(output, Output::OUT { .. }), "{output:?}");
+        // 提问
+        assert_eq!(
+            input_translator(Cmd::NSE(nse_task!(<A --> C>?)))?,
+            Some("<A --> C>?".into())
+        );
+        // 提问后⇒合成回答
+        let output = output_translator("Derived: <A-->C>. %1;0.81%".into())?;
+        assert!(matches!(output, Output::ANSWER { .. }), "{output:?}");
+        assert_eq!(output.raw_content(), "<A-->C>. %1;0.81%");
+        // 其它结论不受影响
+        let output = output_translator("Derived: <B-->C>. %1;0.9%".into())?;
+        assert!(matches!(output, Output::OUT { .. }), "{output:?}");
+        Ok(())
+    }
+}
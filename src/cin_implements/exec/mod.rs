@@ -0,0 +1,10 @@
+//! 基于外部程序的转译器
+//! * 🎯为尚未支持的（实验性）CIN接入转译器，而无需重新编译
+//! * ✨输出转译：CIN的每行原始输出⇒外部程序⇒一行NAVM输出JSON
+//! * ✨输入转译：每条NAVM指令（JSON）⇒外部程序⇒一行CIN输入
+//! * 📌外部程序以子进程方式常驻：按行收发
+
+util::mods! {
+    // 输入输出转译
+    pub pub translators;
+}
@@ -0,0 +1,243 @@
+//! 输入输出转译
+//! * ✨输出转译：原始输出⇒外部程序⇒NAVM_JSON⇒[`Output`]
+//!   * 🚩复用[原生转译器](crate::cin_implements::native)解析JSON：解析失败⇒`OTHER`
+//! * ✨输入转译：[`Cmd`]⇒JSON⇒外部程序⇒CIN输入
+//!   * 📄`{"head":"NSE","tail":"<A --> B>."}`
+//!   * 📌外部程序回复空行⇒不输入
+
+use crate::{
+    cin_implements::native,
+    process_io::{IoProcess, IoProcessManager},
+    runtimes::{InputTranslator, OutputTranslator},
+    support::print_notice,
+};
+use anyhow::{anyhow, Result};
+use navm::{cmd::Cmd, output::Output};
+use std::{sync::Mutex, time::Duration};
+
+/// 等待外部程序回复的超时时间
+/// * 🎯外部程序卡死时，不至于让转译无限阻塞
+pub const EXEC_TRANSLATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 基于外部程序的转译器
+/// * 🚩每次转译：向外部程序写入一行，再读回一行
+/// * 🚩外部程序崩溃或超时⇒警告一次，此后不再使用
+///   * 📌输出转译⇒原样转为`OTHER`；输入转译⇒报错
+pub struct ExecTranslator {
+    /// 外部程序（及其参数）的描述
+    /// * 🎯警告、报错信息
+    program: String,
+    /// 外部程序的子进程
+    /// * 📌[`None`]⇒已崩溃或超时，不再使用
+    process: Mutex<Option<IoProcessManager>>,
+    /// 等待回复的超时时间
+    timeout: Duration,
+}
+
+impl ExecTranslator {
+    /// 启动外部程序
+    /// * ⚠️启动失败⇒返回错误
+    pub fn launch(program: &str, args: &[String]) -> Result<Self> {
+        let process = args
+            .iter()
+            .fold(IoProcess::new(program), |process, arg| process.arg(arg))
+            .launch()
+            .map_err(|e| anyhow!("无法启动转译程序「{program}」：{e}"))?;
+        Ok(Self {
+            program: program.to_owned(),
+            process: Mutex::new(Some(process)),
+            timeout: EXEC_TRANSLATE_TIMEOUT,
+        })
+    }
+
+    /// 设置等待回复的超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 向外部程序写入一行，并读回一行
+    /// * 🚩崩溃或超时⇒警告并停用外部程序，返回[`None`]
+    /// * 📌已停用⇒直接返回[`None`]
+    fn exchange(&self, line: &str) -> Option<String> {
+        let mut guard = self.process.lock().ok()?;
+        let process = guard.as_mut()?;
+        let reply = match process.put_line(line) {
+            Ok(..) => process.fetch_output_timeout(self.timeout),
+            Err(e) => Err(e),
+        };
+        let reason = match reply {
+            Ok(Some(reply)) => return Some(reply.trim_end_matches(['\r', '\n']).to_owned()),
+            Ok(None) if process.is_child_closed().unwrap_or(true) => "已退出".to_owned(),
+            Ok(None) => format!("在{:?}内未回复", self.timeout),
+            Err(e) => e.to_string(),
+        };
+        print_notice(
+            "WARN",
+            &format!(
+                "转译程序「{}」{reason}，已停用：此后的输出将原样保留",
+                self.program
+            ),
+        );
+        if let Some(mut process) = guard.take() {
+            let _ = process.kill();
+        }
+        None
+    }
+
+    /// 输出转译
+    /// * 🚩外部程序的回复⇒按NAVM_JSON解析
+    /// * 🚩外部程序不可用⇒`OTHER`
+    pub fn translate_output(&self, content_raw: String) -> Result<Output> {
+        match self.exchange(&content_raw) {
+            Some(json) => native::output_translate(json),
+            None => Ok(Output::OTHER {
+                content: content_raw,
+            }),
+        }
+    }
+
+    /// 输入转译
+    /// * 🚩指令⇒JSON⇒外部程序的回复；回复空行⇒不输入
+    /// * ⚠️外部程序不可用⇒报错
+    pub fn translate_input(&self, cmd: Cmd) -> Result<Option<String>> {
+        match self.exchange(&cmd_to_json(&cmd)) {
+            Some(line) if line.is_empty() => Ok(None),
+            Some(line) => Ok(Some(line)),
+            None => Err(anyhow!(
+                "转译程序「{}」不可用：无法转译指令「{cmd}」",
+                self.program
+            )),
+        }
+    }
+
+    /// 转换为「输入转译器」
+    pub fn into_input_translator(self) -> Box<InputTranslator> {
+        Box::new(move |cmd| self.translate_input(cmd))
+    }
+
+    /// 转换为「输出转译器」
+    pub fn into_output_translator(self) -> Box<OutputTranslator> {
+        Box::new(move |content_raw| self.translate_output(content_raw))
+    }
+}
+
+/// 转译器销毁时，一并终止外部程序
+impl Drop for ExecTranslator {
+    fn drop(&mut self) {
+        if let Ok(Some(mut process)) = self.process.get_mut().map(Option::take) {
+            let _ = process.kill();
+        }
+    }
+}
+
+/// 将NAVM指令转换为JSON
+/// * 📄`{"head":"NSE","tail":"<A --> B>."}`
+pub fn cmd_to_json(cmd: &Cmd) -> String {
+    serde_json::json!({
+        "head": cmd.head(),
+        "tail": cmd.tail(),
+    })
+    .to_string()
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use narsese::lexical_nse_task as nse_task;
+    use std::{fs, path::PathBuf};
+
+    /// 在临时目录中生成外部程序脚本
+    /// * 🚩Unix⇒`sh`脚本；Windows⇒批处理
+    fn helper_script(name: &str, unix: &str, windows: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("babelnar_exec_translator_{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("无法创建临时目录");
+        let path = match cfg!(windows) {
+            true => dir.join(format!("{name}.bat")),
+            false => dir.join(format!("{name}.sh")),
+        };
+        let content = match cfg!(windows) {
+            true => format!("@echo off\r\n{windows}\r\n"),
+            false => format!("#!/bin/sh\n{unix}\n"),
+        };
+        fs::write(&path, content).expect("无法写入脚本");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("无法设置权限");
+        }
+        path
+    }
+
+    /// 原样回传的外部程序
+    fn echo_translator(name: &str) -> ExecTranslator {
+        let path = helper_script(name, "exec cat", "findstr \"^\"");
+        ExecTranslator::launch(&path.to_string_lossy(), &[]).expect("无法启动转译程序")
+    }
+
+    /// 测试/输出转译：回传的JSON⇒输出；非JSON⇒`OTHER`
+    #[test]
+    fn test_translate_output() -> Result<()> {
+        let translator = echo_translator("echo_output");
+        let json = r#"{"type":"ANSWER","content":"<A --> B>."}"#;
+        assert_eq!(
+            translator.translate_output(json.into())?,
+            Output::ANSWER {
+                content_raw: "<A --> B>.".into(),
+                narsese: None,
+            }
+        );
+        assert_eq!(
+            translator.translate_output("not json".into())?,
+            Output::OTHER {
+                content: "not json".into()
+            }
+        );
+        Ok(())
+    }
+
+    /// 测试/输入转译：指令以JSON形式交给外部程序
+    #[test]
+    fn test_translate_input() -> Result<()> {
+        let translator = echo_translator("echo_input");
+        let cmd = Cmd::NSE(nse_task!(<A --> B>.));
+        assert_eq!(
+            translator.translate_input(cmd)?.as_deref(),
+            Some(r#"{"head":"NSE","tail":"<A --> B>."}"#)
+        );
+        Ok(())
+    }
+
+    /// 测试/外部程序崩溃⇒输出原样转为`OTHER`
+    #[test]
+    #[cfg(unix)]
+    fn test_helper_crash() -> Result<()> {
+        // 只回复一行就退出
+        let path = helper_script("exit_after_one", "read -r line\necho \"$line\"", "");
+        let translator = ExecTranslator::launch(&path.to_string_lossy(), &[])?
+            .timeout(Duration::from_millis(500));
+        let json = r#"{"type":"OUT","content":"<A --> B>."}"#;
+        assert!(matches!(
+            translator.translate_output(json.into())?,
+            Output::OUT { .. }
+        ));
+        // 已退出⇒原样保留
+        assert_eq!(
+            translator.translate_output(json.into())?,
+            Output::OTHER {
+                content: json.into()
+            }
+        );
+        // 已停用⇒输入转译报错
+        assert!(translator.translate_input(Cmd::CYC(1)).is_err());
+        Ok(())
+    }
+
+    /// 测试/程序不存在⇒启动失败
+    #[test]
+    fn test_launch_failed() {
+        assert!(ExecTranslator::launch("./babelnar-not-exist-translator", &[]).is_err());
+    }
+}
@@ -5,6 +5,7 @@
 //! * ✅单元测试中利用`taskkill`初步解决
 //! * ❌【2024-03-25 13:36:30】集成测试`cargo t --all-features`中未能解决
 //!   * ❗// ! ↑【少用乃至不用这条命令】
+//! * ✅CLI被Ctrl-C、`SIGTERM`终止时，先终止CIN再退出：见[`crate::cli_support::shutdown`]
 //!
 //! * 🚩【2024-04-09 20:47:04】基本完成对「复用」「性能」与「简洁」的兼顾
 //! * 📌复用：将OpenNARS、ONA抽象成「基于jar的启动逻辑」「基于exe的启动逻辑」等方式，以便后续重复使用
@@ -43,4 +44,225 @@ util::mods! {
     // CXinNARS.js
     pub cxin_js;
 
+    // 转译器预设
+    pub pub presets;
+
+    // 基于外部程序的转译器
+    pub exec;
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::{InputTranslator, OutputTranslator};
+    use crate::test_tools::{is_expected_echo, EchoMatchOptions};
+    use narsese::{lexical::Narsese, lexical_nse as nse, lexical_nse_task as nse_task};
+    use navm::{cmd::Cmd, output::type_names::IN};
+
+    /// 所有CIN的输入转译器
+    const INPUT_TRANSLATORS: &[(&str, &InputTranslator)] = &[
+        ("Native", &native::input_translate),
+        ("OpenNARS", &opennars::input_translate),
+        ("ONA", &ona::input_translate),
+        ("NARS-Python", &nars_python::input_translate),
+        ("PyNARS", &pynars::input_translate),
+        ("OpenJunars", &openjunars::input_translate),
+        ("CXinJS", &cxin_js::input_translate),
+    ];
+
+    /// 测试/输入转译：注释⇒不输入，Narsese⇒有输入
+    #[test]
+    fn test_input_translate_none() {
+        for (name, translate) in INPUT_TRANSLATORS {
+            let rem = Cmd::REM {
+                comment: "这是一个注释".into(),
+            };
+            assert_eq!(translate(rem).unwrap(), None, "{name}");
+            let nse = translate(Cmd::NSE(nse_task!(<A --> B>.))).unwrap();
+            assert!(nse.is_some_and(|s| !s.is_empty()), "{name}");
+        }
+    }
+
+    /// 测试/输入回显：各CIN的回显均能匹配所输入的Narsese
+    /// * 📄输出源自各CIN的命令行交互（见各自转译器的测试）
+    /// * 📌预算值、真值精度等因CIN而异
+    #[test]
+    fn test_echo_lines() {
+        let options = EchoMatchOptions::default();
+        let cases: [(&str, &OutputTranslator, Narsese); 4] = [
+            (
+                "IN: <A --> B>. %1.00;0.90% {-1 : (-7995324758518856376,0)}",
+                &opennars::output_translate,
+                nse!(<A --> B>. %1.0;0.9%),
+            ),
+            (
+                "Input: <a --> [warm]>. :|: occurrenceTime=1 Priority=1.000000 Truth: frequency=0.800000, confidence=0.900000",
+                &ona::output_translate,
+                nse!(<a --> [warm]>. :|: %0.8;0.9%),
+            ),
+            (
+                "|0.80|0.50|0.95| IN    : A. %1.000;0.900%",
+                &pynars::output_translate,
+                nse!($0.5;0.5;0.5$ A. %1.0;0.9%),
+            ),
+            (
+                "IN: <A-->B>. %1;0.9%",
+                &cxin_js::output_translate,
+                nse!(<A --> B>. %1.0;0.9%),
+            ),
+        ];
+        for (line, translate, expected) in &cases {
+            let output = translate(line.to_string()).expect("转译失败");
+            assert_eq!(output.type_name(), IN, "{line}");
+            let narsese = output.get_narsese().expect("回显没有Narsese");
+            assert!(is_expected_echo(expected, narsese, options), "{line}");
+        }
+        // 不同的真值不匹配
+        let output = opennars::output_translate(cases[0].0.into()).unwrap();
+        let narsese = output.get_narsese().unwrap();
+        assert!(!is_expected_echo(
+            &nse!(<A --> B>. %0.0;0.9%),
+            narsese,
+            options
+        ));
+    }
+
+    /// 测试/音量：各CIN的转译结果
+    /// * 🚩超出标度⇒截断到最大值
+    /// * 🚩无音量控制的CIN⇒报错
+    #[test]
+    fn test_input_translate_volume() {
+        let expected: &[(&str, Option<&str>)] = &[
+            ("Native", Some("VOL {}")),
+            ("OpenNARS", Some("*volume={}")),
+            ("ONA", Some("*volume={}")),
+            ("NARS-Python", None),
+            ("PyNARS", Some("/volume {}")),
+            ("OpenJunars", None),
+            ("CXinJS", None),
+        ];
+        for ((name, translate), (expected_name, template)) in INPUT_TRANSLATORS.iter().zip(expected)
+        {
+            assert_eq!(name, expected_name);
+            for (volume, normalized) in [(0, 0), (50, 50), (100, 100), (150, 100)] {
+                let result = translate(Cmd::VOL(volume));
+                match template {
+                    Some(template) => assert_eq!(
+                        result.unwrap().as_deref(),
+                        Some(template.replace("{}", &normalized.to_string()).as_str()),
+                        "{name}"
+                    ),
+                    None => assert!(result.is_err(), "{name}"),
+                }
+            }
+        }
+    }
+
+    /// 测试/重置与随机种子：各CIN的转译结果
+    /// * 🚩不支持的CIN⇒报错
+    #[test]
+    fn test_input_translate_reset_seed() {
+        use common::seed_cmd;
+        // `SEED 42`即为「随机种子」指令
+        assert_eq!(Cmd::parse("SEED 42").unwrap(), seed_cmd(42));
+        let expected: &[(&str, Option<&str>, Option<&str>)] = &[
+            ("Native", Some("RES memory"), Some("SEED 42")),
+            ("OpenNARS", Some("*reset"), Some("*seed=42")),
+            ("ONA", Some("*reset"), Some("*seed=42")),
+            ("NARS-Python", None, None),
+            ("PyNARS", Some("/reset"), None),
+            ("OpenJunars", None, None),
+            ("CXinJS", None, None),
+        ];
+        for ((name, translate), (expected_name, reset, seed)) in
+            INPUT_TRANSLATORS.iter().zip(expected)
+        {
+            assert_eq!(name, expected_name);
+            let cmds = [
+                (
+                    Cmd::RES {
+                        target: "memory".into(),
+                    },
+                    reset,
+                ),
+                (seed_cmd(42), seed),
+            ];
+            for (cmd, expected) in cmds {
+                let result = translate(cmd);
+                match expected {
+                    Some(expected) => {
+                        assert_eq!(result.unwrap().as_deref(), Some(*expected), "{name}")
+                    }
+                    None => assert!(result.is_err(), "{name}"),
+                }
+            }
+        }
+        // 无效的随机种子⇒报错
+        let invalid = Cmd::parse("SEED abc").unwrap();
+        assert!(ona::input_translate(invalid).is_err());
+    }
+
+    /// 测试/参数设置：各CIN的转译结果
+    /// * 🚩不支持的参数、不支持的CIN⇒「不支持的指令」，仅警告
+    /// * 🚩格式有误⇒报错
+    #[test]
+    fn test_input_translate_param() {
+        use crate::runtimes::TranslateError;
+        use common::param_cmd;
+        // `SET key=value`即为「参数设置」指令
+        let cmd = Cmd::parse("SET decisionthreshold=0.6").unwrap();
+        assert_eq!(cmd, param_cmd("decisionthreshold", "0.6"));
+        let expected: &[(&str, Option<&str>, Option<&str>)] = &[
+            (
+                "Native",
+                Some("SET decisionthreshold=0.6"),
+                Some("SET unknown=1"),
+            ),
+            ("OpenNARS", Some("*decisionthreshold=0.6"), None),
+            ("ONA", Some("*decisionthreshold=0.6"), None),
+            ("NARS-Python", None, None),
+            (
+                "PyNARS",
+                Some("/config decisionthreshold 0.6"),
+                Some("/config unknown 1"),
+            ),
+            ("OpenJunars", None, None),
+            ("CXinJS", None, None),
+        ];
+        for ((name, translate), (expected_name, supported, unknown)) in
+            INPUT_TRANSLATORS.iter().zip(expected)
+        {
+            assert_eq!(name, expected_name);
+            let cmds = [
+                (param_cmd("decisionthreshold", "0.6"), supported),
+                (param_cmd("unknown", "1"), unknown),
+            ];
+            for (cmd, expected) in cmds {
+                let result = translate(cmd);
+                match expected {
+                    Some(expected) => {
+                        assert_eq!(result.unwrap().as_deref(), Some(*expected), "{name}")
+                    }
+                    None => assert!(
+                        matches!(
+                            result.unwrap_err().downcast_ref::<TranslateError>(),
+                            Some(TranslateError::UnsupportedInput(..))
+                        ),
+                        "{name}"
+                    ),
+                }
+            }
+        }
+        // 参数名不区分大小写
+        let cmd = param_cmd("BabblingOps", "5");
+        assert_eq!(
+            opennars::input_translate(cmd).unwrap().as_deref(),
+            Some("*babblingops=5")
+        );
+        // 格式有误⇒报错，而非「不支持」
+        let invalid = Cmd::parse("SET decisionthreshold").unwrap();
+        let e = ona::input_translate(invalid).unwrap_err();
+        assert!(e.downcast_ref::<TranslateError>().is_none());
+    }
 }
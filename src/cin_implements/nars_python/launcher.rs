@@ -3,12 +3,15 @@
 //! * 🚩只憎加「启动器」类型，而不增加「运行时」类型
 //!   * ✨不同启动器可以启动到相同运行时
 
-use super::{input_translate, output_translate};
-use crate::runtimes::{CommandVm, CommandVmRuntime};
+use super::{input_translate, output_translate, READY_MARKER};
+use crate::{
+    process_io::IoProcess,
+    runtimes::{CommandVm, CommandVmRuntime, DEFAULT_STARTUP_TIMEOUT},
+};
 use anyhow::Result;
 use nar_dev_utils::manipulate;
 use navm::vm::VmLauncher;
-use std::path::PathBuf;
+use std::{fs::File, path::PathBuf};
 
 // ! NARS-Python作为一个独立的`main.exe`，没有默认的启动参数
 
@@ -20,6 +23,12 @@ use std::path::PathBuf;
 pub struct NARSPython {
     /// exe文件路径
     exe_path: PathBuf,
+
+    /// 输出文件路径（可选）
+    /// * 🎯NARS-Python作为GUI程序，不向标准输出写入结果
+    ///   * 🚩此时追踪其写入的日志文件，作为「辅助输出源」
+    /// * 📌启动前会清空该文件，避免读到上次运行的输出
+    output_path: Option<PathBuf>,
 }
 
 // ! 🚩【2024-03-25 09:37:22】目前暂时不提取至「VmExe」：参考`impl_runtime`根目录说明
@@ -30,20 +39,35 @@ impl NARSPython {
         Self {
             // 转换为路径
             exe_path: exe_path.into(),
+            output_path: None,
         }
     }
+
+    /// 配置/输出文件
+    /// * 🚩启动后追踪该文件的新增内容，与标准输出一同转译为NAVM输出
+    pub fn output_file(&mut self, output_path: impl Into<PathBuf>) {
+        self.output_path = Some(output_path.into());
+    }
 }
 
 /// 启动到「命令行运行时」
 impl VmLauncher for NARSPython {
     type Runtime = CommandVmRuntime;
     fn launch(self) -> Result<CommandVmRuntime> {
+        // 构造进程 | 有输出文件⇒清空后追踪
+        let mut io_process = IoProcess::new(self.exe_path);
+        if let Some(output_path) = self.output_path {
+            File::create(&output_path)?;
+            io_process = io_process.aux_output(File::open(output_path)?);
+        }
         // 构造指令，并启动虚拟机
         manipulate!(
-            CommandVm::new(self.exe_path)
+            CommandVm::from(io_process)
             // * 🚩固定的「输入输出转译器」
             => .input_translator(input_translate)
             => .output_translator(output_translate)
+            // * 🚩启动检查：启动即失败⇒报错并附带其输出；等待该CIN的就绪标志
+            => .startup_check(DEFAULT_STARTUP_TIMEOUT, READY_MARKER)
         )
         // 🔥启动
         .launch()
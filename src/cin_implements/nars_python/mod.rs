@@ -2,6 +2,9 @@
 //! * 🚩只提供「一行启动」的功能封装
 //!   * 🎯无需自行配置「输入输出转译器」
 
+use crate::runtimes::Capabilities;
+use navm::output::Output;
+
 // 转译器
 util::mod_and_pub_use! {
     // 方言（Narsese格式）
@@ -12,14 +15,37 @@ util::mod_and_pub_use! {
     launcher
 }
 
+/// 所支持的功能
+/// * ⚠️不支持`REG`
+pub const CAPABILITIES: Capabilities = Capabilities {
+    registration: false,
+    ..Capabilities::ALL
+};
+
+/// 「步进完成」的回执判断
+/// * 🚩不报告⇒等待CIN安静下来
+pub const CYC_ACK_MATCHER: Option<fn(&Output) -> bool> = None;
+
+/// 启动检查的「就绪标志」
+/// * 📌NARS-Python的输出写入日志文件，启动时通常无输出⇒任意一行输出即视作就绪
+pub const READY_MARKER: Option<fn(&str) -> bool> = None;
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{runtimes::CommandVmRuntime, tests::cin_paths::NARS_PYTHON};
+    use crate::{
+        runtimes::{
+            tests::{await_fetch_until, input_cmd_and_await_contains},
+            CommandVmRuntime,
+        },
+        tests::cin_paths::{NARS_PYTHON, NARS_PYTHON_OUTPUT},
+    };
+    use nar_dev_utils::manipulate;
     use narsese::conversion::string::impl_lexical::shortcuts::*;
     use navm::{
         cmd::Cmd,
+        output::Output,
         vm::{VmLauncher, VmRuntime},
     };
 
@@ -28,8 +54,13 @@ mod tests {
     fn test() {
         // 从别的地方获取exe路径
         let exe_path = NARS_PYTHON;
-        // 一行代码启动NARS-Python
-        let vm = NARSPython::new(exe_path).launch().expect("无法启动虚拟机");
+        // 启动NARS-Python | 追踪其输出文件
+        let vm = manipulate!(
+            NARSPython::new(exe_path)
+            => .output_file(NARS_PYTHON_OUTPUT)
+        )
+        .launch()
+        .expect("无法启动虚拟机");
         // 运行专有测试
         _test_nars_python(vm)
     }
@@ -44,19 +75,21 @@ mod tests {
     /// OUT: SentenceID:3:ID (A --> C). %1.00;0.81%
     /// ```
     ///
-    /// ! ❌仍然无法截获其输出
+    /// * 🚩标准输出中无结果⇒从「输出文件」中截获
     pub(crate) fn _test_nars_python(mut vm: CommandVmRuntime) {
         // 等待几秒钟，让exe的界面显示出来
         std::thread::sleep(std::time::Duration::from_secs(2));
 
-        vm.input_cmd(Cmd::NSE(nse_task!(<A --> B>.)))
-            .expect("无法输入NAVM指令");
-        vm.input_cmd(Cmd::NSE(nse_task!(<B --> C>.)))
-            .expect("无法输入NAVM指令");
+        input_cmd_and_await_contains(&mut vm, Cmd::NSE(nse_task!(<A --> B>.)), "(A --> B)");
+        input_cmd_and_await_contains(&mut vm, Cmd::NSE(nse_task!(<B --> C>.)), "(B --> C)");
         vm.input_cmd(Cmd::NSE(nse_task!(<A --> C>?)))
             .expect("无法输入NAVM指令");
 
-        std::thread::sleep(std::time::Duration::from_secs(4));
+        // 等待回答
+        await_fetch_until(&mut vm, |o, raw_content| {
+            matches!(o, Output::ANSWER { .. } | Output::OUT { .. })
+                && raw_content.contains("(A --> C).")
+        });
 
         // 终止虚拟机运行时
         vm.terminate().expect("无法终止虚拟机");
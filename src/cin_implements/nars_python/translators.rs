@@ -22,7 +22,7 @@ use navm::{
 
 /// NARS-Python的「输入转译」函数
 /// * 🎯用于将统一的「NAVM指令」转译为「NARS-Python输入」
-pub fn input_translate(cmd: Cmd) -> Result<String> {
+pub fn input_translate(cmd: Cmd) -> Result<Option<String>> {
     let content = match cmd {
         // 使用「末尾」将自动格式化任务（可兼容「空预算」的形式）
         // * ✅【2024-03-26 01:44:49】目前采用特定的「方言格式」解决格式化问题
@@ -31,13 +31,14 @@ pub fn input_translate(cmd: Cmd) -> Result<String> {
         // ! NARS-Python同样是自动步进的
         Cmd::CYC(n) => n.to_string(),
         // 注释 ⇒ 忽略 | ❓【2024-04-02 22:43:05】可能需要打印，但这样却没法统一IO（到处print的习惯不好）
-        Cmd::REM { .. } => String::new(),
+        Cmd::REM { .. } => return Ok(None),
         // 其它类型
+        // * 📌包括VOL指令：该CIN无音量控制
         // ! 🚩【2024-03-27 22:42:56】不使用[`anyhow!`]：打印时会带上一大堆调用堆栈
         _ => return Err(TranslateError::UnsupportedInput(cmd).into()),
     };
     // 转译
-    Ok(content)
+    Ok(Some(content))
 }
 
 /// NARS-Python的「输出转译」函数
@@ -54,7 +55,8 @@ pub fn output_translate(content: String) -> Result<Output> {
             narsese: None,
             content_raw: content,
         },
-        "derived" => Output::OUT {
+        // * 📄`OUT: SentenceID:3:ID (A --> C). %1.00;0.81%`
+        "derived" | "out" => Output::OUT {
             // TODO: 有待捕获转译
             narsese: None,
             content_raw: content,
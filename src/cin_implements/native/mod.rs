@@ -3,7 +3,22 @@
 //! * ✨NAVM_JSON输出转译：基于[`serde_json`]直接从JSON字符串读取[`Output`]
 //! * 📌没有固定的启动器：仅通过「命令行启动器」即可启动
 
+use crate::runtimes::Capabilities;
+use navm::output::Output;
+
 util::mods! {
     // 输入输出转译
     pub pub translators;
 }
+
+/// 所支持的功能
+/// * 🚩原样转发NAVM指令：视作支持所有功能
+pub const CAPABILITIES: Capabilities = Capabilities::ALL;
+
+/// 「步进完成」的回执判断
+/// * 🚩无约定的回执格式⇒等待CIN安静下来
+pub const CYC_ACK_MATCHER: Option<fn(&Output) -> bool> = None;
+
+/// 启动检查的「就绪标志」
+/// * 🚩由具体的原生CIN决定⇒任意一行输出即视作就绪
+pub const READY_MARKER: Option<fn(&str) -> bool> = None;
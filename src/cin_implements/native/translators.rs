@@ -2,15 +2,22 @@
 //! * ✨Cmd输入转译：直接将[`Cmd`]转换为字符串形式
 //! * ✨NAVM_JSON输出转译：基于[`serde_json`]直接从JSON字符串读取[`Output`]
 
+use crate::cin_implements::common::normalize_volume;
 use anyhow::Result;
 use navm::{cmd::Cmd, output::Output};
 extern crate serde_json;
 
 /// Cmd输入转译
 /// * 🚩直接将[`Cmd`]转换为字符串形式
+/// * 🚩注释⇒不输入
+/// * 🚩音量⇒截断到NAVM标度内
 /// * 📌总是成功
-pub fn input_translate(cmd: Cmd) -> Result<String> {
-    Ok(cmd.to_string())
+pub fn input_translate(cmd: Cmd) -> Result<Option<String>> {
+    match cmd {
+        Cmd::REM { .. } => Ok(None),
+        Cmd::VOL(n) => Ok(Some(Cmd::VOL(normalize_volume(n)).to_string())),
+        _ => Ok(Some(cmd.to_string())),
+    }
 }
 
 /// NAVM_JSON输出转译
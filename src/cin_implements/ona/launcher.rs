@@ -3,10 +3,10 @@
 //! * 🚩只憎加「启动器」类型，而不增加「运行时」类型
 //!   * ✨不同启动器可以启动到相同运行时
 
-use super::{input_translate, output_translate};
+use super::{continues_previous, input_translate, output_translate, READY_MARKER};
 use crate::{
     cin_implements::common::{generate_command, generate_command_vm},
-    runtimes::CommandVmRuntime,
+    runtimes::{CommandVmRuntime, DEFAULT_COALESCE_TIMEOUT, DEFAULT_STARTUP_TIMEOUT},
 };
 use anyhow::Result;
 use navm::{
@@ -32,6 +32,9 @@ pub struct ONA {
     /// ONA Shell的初始音量
     /// * 🚩可能没有：此时不会输入指令
     initial_volume: Option<usize>,
+    /// 是否合并多行的统计报告
+    /// * 📜默认值：`false`
+    coalesce_lines: bool,
 }
 
 // ! 🚩【2024-03-25 09:37:22】目前暂时不提取至「VmExe」：预置的`shell`参数需要被处理
@@ -47,28 +50,45 @@ impl ONA {
             ..Default::default()
         }
     }
+
+    /// 配置/是否合并多行的统计报告
+    /// * 🎯`*stats`的统计报告作为一个输出
+    pub fn coalesce_lines(&mut self, enabled: bool) {
+        self.coalesce_lines = enabled;
+    }
 }
 
 /// 启动到「命令行运行时」
 impl VmLauncher for ONA {
     type Runtime = CommandVmRuntime;
     fn launch(self) -> Result<CommandVmRuntime> {
-        // 构造并启动虚拟机
-        let mut runtime = pipe! {
+        // 构造虚拟机
+        let mut vm = pipe! {
             self.exe_path
             // 构造指令 | 预置的指令参数
-            => generate_command(_, None::<String>, COMMAND_ARGS_ONA.into_iter().by_ref())
+            => generate_command(
+                _,
+                None::<String>,
+                COMMAND_ARGS_ONA.into_iter().by_ref(),
+                None::<(&str, &str)>,
+            )
             // * 🚩固定的「输入输出转译器」
             => generate_command_vm(_, (input_translate, output_translate))
-            // 🔥启动
-            => .launch()
-        }?;
+        };
+        // * 🚩启动检查：启动即失败⇒报错并附带其输出；等待该CIN的就绪标志
+        vm.startup_check(DEFAULT_STARTUP_TIMEOUT, READY_MARKER);
+        // * 🚩选择性合并多行的统计报告
+        if self.coalesce_lines {
+            vm.coalesce_lines(continues_previous, DEFAULT_COALESCE_TIMEOUT);
+        }
+        // 🔥启动
+        let mut runtime = vm.launch()?;
 
         // 选择性设置初始音量
         if let Some(volume) = self.initial_volume {
             // 输入指令，并在执行错误时打印信息
             if let Err(e) = runtime.input_cmd(Cmd::VOL(volume)) {
-                println!("无法设置初始音量「{volume}」：{e}");
+                eprintln!("无法设置初始音量「{volume}」：{e}");
             }
         };
         Ok(runtime)
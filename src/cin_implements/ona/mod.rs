@@ -2,6 +2,9 @@
 //! * 🚩只提供「一行启动」的功能封装
 //!   * 🎯无需自行配置「输入输出转译器」
 
+use crate::runtimes::Capabilities;
+use navm::output::Output;
+
 // 转译器
 util::mod_and_pub_use! {
     // 转译器
@@ -12,18 +15,37 @@ util::mod_and_pub_use! {
     dialect
 }
 
+/// 所支持的功能
+/// * 📌`REG`仅限ONA预置的操作符名
+pub const CAPABILITIES: Capabilities = Capabilities::ALL;
+
+/// 「步进完成」的回执判断
+/// * 📄`done with 5 additional inference steps.`
+pub const CYC_ACK_MATCHER: Option<fn(&Output) -> bool> = Some(is_cyc_ack);
+
+/// 启动检查的「就绪标志」
+/// * 🚩ONA Shell启动时不打印提示⇒任意一行输出即视作就绪
+pub const READY_MARKER: Option<fn(&str) -> bool> = None;
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         runtimes::{
-            tests::{_test_ona, test_simple_answer},
-            CommandVmRuntime,
+            tests::{
+                _test_ona, await_fetch_until, input_cmd_and_await_narsese, test_simple_answer,
+            },
+            CommandVmRuntime, NO_ANSWER,
         },
         tests::cin_paths::ONA as EXE_PATH_ONA,
     };
-    use navm::vm::VmLauncher;
+    use narsese::conversion::string::impl_lexical::shortcuts::*;
+    use navm::{
+        cmd::Cmd,
+        output::Output,
+        vm::{VmLauncher, VmRuntime},
+    };
 
     /// 工具/启动ONA，获得虚拟机运行时
     fn launch_vm() -> CommandVmRuntime {
@@ -49,4 +71,27 @@ mod tests {
         // 使用通用测试逻辑
         test_simple_answer(vm)
     }
+
+    /// 测试/重置
+    /// * 🎯重置后，先前的信念不再被用于回答
+    #[test]
+    fn test_reset() {
+        let mut vm = launch_vm();
+        let _ = vm.input_cmd(Cmd::VOL(0));
+        // 重置前：可以回答
+        input_cmd_and_await_narsese(&mut vm, Cmd::NSE(nse_task!(<A --> B>.)), nse!(<A --> B>.));
+        input_cmd_and_await_narsese(&mut vm, Cmd::NSE(nse_task!(<A --> B>?)), nse!(<A --> B>?));
+        await_fetch_until(&mut vm, |output, _| matches!(output, Output::ANSWER { .. }));
+        // 重置后：同样的问题无法回答
+        vm.input_cmd(Cmd::RES {
+            target: "memory".into(),
+        })
+        .expect("无法输入RES指令");
+        input_cmd_and_await_narsese(&mut vm, Cmd::NSE(nse_task!(<A --> B>?)), nse!(<A --> B>?));
+        let output = await_fetch_until(&mut vm, |output, _| {
+            matches!(output, Output::ANSWER { .. }) || output.is_type(NO_ANSWER)
+        });
+        assert!(output.is_type(NO_ANSWER), "{output:?}");
+        vm.terminate().expect("无法终止虚拟机");
+    }
 }
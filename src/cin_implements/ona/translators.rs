@@ -22,11 +22,16 @@
 //! * 📄`*setopname 1 ^left`
 //! * 🔗参见<https://github.com/opennars/OpenNARS-for-Applications/blob/master/misc/Python/OpenNARS_for_Applications.ipynb>
 
-use super::dialect::parse as parse_dialect_ona;
 use crate::{
-    cin_implements::ona::{fold_pest_compound, DialectParser, Rule},
-    cli_support::io::output_print::OutputType,
-    runtimes::TranslateError,
+    cin_implements::{
+        common::{
+            normalize_volume, parse_narsese_chain, parse_seed, translate_param, ParamSupport,
+            PARAM_CMD_HEAD, PARSER_ONA, SEED_CMD_HEAD,
+        },
+        ona::{fold_pest_compound, DialectParser, Rule},
+    },
+    runtimes::{TranslateError, NO_ANSWER},
+    support::eprint_notice,
 };
 use anyhow::Result;
 use narsese::lexical::{Narsese, Term};
@@ -36,6 +41,7 @@ use navm::{
 };
 use pest::Parser;
 use regex::{Captures, Regex};
+use std::sync::LazyLock;
 #[cfg(not(test))]
 use util::OptionBoost;
 use util::{if_return, pipe};
@@ -58,9 +64,19 @@ pub const OPERATOR_NAME_LIST: &[&str] = &[
     "deactivate",
 ];
 
+/// ONA Shell可调整的参数
+/// * 📄`*motorbabbling=0.1`、`*decisionthreshold=0.6`
+/// * 🔗参考：<https://github.com/opennars/OpenNARS-for-Applications/blob/master/src/Shell.c>
+pub const PARAM_SUPPORT: ParamSupport = ParamSupport::Whitelist(&[
+    "decisionthreshold",
+    "babblingops",
+    "motorbabbling",
+    "questionpriming",
+]);
+
 /// ONA的「输入转译」函数
 /// * 🎯用于将统一的「NAVM指令」转译为「ONA Shell输入」
-pub fn input_translate(cmd: Cmd) -> Result<String> {
+pub fn input_translate(cmd: Cmd) -> Result<Option<String>> {
     let content = match cmd {
         // 直接使用「末尾」，此时将自动格式化任务（可兼容「空预算」的形式）
         Cmd::NSE(..) => cmd.tail(),
@@ -68,14 +84,25 @@ pub fn input_translate(cmd: Cmd) -> Result<String> {
         // ! ONA Shell同样是自动步进的
         Cmd::CYC(n) => n.to_string(),
         // VOL指令：调整音量
-        Cmd::VOL(n) => format!("*volume={n}"),
+        // * 📌标度与NAVM相同，仅截断超出范围的值
+        Cmd::VOL(n) => format!("*volume={}", normalize_volume(n)),
+        // RES指令：重置记忆区
+        Cmd::RES { .. } => "*reset".into(),
+        // SEED指令：设置随机种子
+        Cmd::Custom { head, tail } if head == SEED_CMD_HEAD => {
+            format!("*seed={}", parse_seed(&tail)?)
+        }
+        // SET指令：设置参数
+        Cmd::Custom { head, tail } if head == PARAM_CMD_HEAD => {
+            translate_param(&tail, PARAM_SUPPORT, |key, value| format!("*{key}={value}"))?
+        }
         // REG指令：注册操作
         Cmd::REG { name } => match OPERATOR_NAME_LIST.contains(&name.as_str()) {
-            true => String::new(),
+            true => return Ok(None),
             false => format!("*setopname {} ^{name}", hash_operator_id(&name)),
         },
         // 注释 ⇒ 忽略 | ❓【2024-04-02 22:43:05】可能需要打印，但这样却没法统一IO（到处print的习惯不好）
-        Cmd::REM { .. } => String::new(),
+        Cmd::REM { .. } => return Ok(None),
         // 退出 ⇒ 无效输入 | // ! 🚩故意使用ONA中会「报错退出」的输入，强制ONA shell退出（其后不会再接收输入）
         Cmd::EXI { .. } => "*quit".into(),
         // 其它类型
@@ -83,7 +110,7 @@ pub fn input_translate(cmd: Cmd) -> Result<String> {
         _ => return Err(TranslateError::UnsupportedInput(cmd).into()),
     };
     // 转译
-    Ok(content)
+    Ok(Some(content))
 }
 
 /// 🔗参见<https://vscode.dev/github/ARCJ137442/OpenNARS-for-Applications/blob/master/src/Config.h#L112>
@@ -150,28 +177,37 @@ pub fn output_translate(content_raw: String) -> Result<Output> {
     }
     // 根据冒号分隔一次，然后得到「头部」
     let (head, tail) = content_raw.split_once(':').unwrap_or(("", ""));
+    // 解析失败⇒附带原始输出
+    let failed = |e| TranslateError::output_parse_failed("ONA", &content_raw, e);
     // 根据「头部」生成输出
     // * 🚩此处不直接使用NAVM输出中的「头部字串常量」主要考虑是「此为ONA特有」
     let output = match head.to_lowercase().as_str() {
-        // 回答，但排除「似是而非」的`Answer: None.`
+        // 「似是而非」的`Answer: None.`
         // * 🚩ONA会输出带有误导性的`Answer: None.`
         //   * 看起来是回答，实际上不是
         // * 🚩【2024-04-11 23:01:50】现在将`Answer: None.`开除出「回答」的输出格式
-        "answer" if !content_raw.contains("Answer: None.") => Output::ANSWER {
+        // * 🚩作为「无回答」输出：可被`''expect-contains: NO_ANSWER`检查
+        "answer" if content_raw.contains("Answer: None.") => Output::UNCLASSIFIED {
+            r#type: NO_ANSWER.into(),
+            content: content_raw,
+            narsese: None,
+        },
+        // 回答
+        "answer" => Output::ANSWER {
             // 先提取其中的Narsese | ⚠️借用了`content_raw`
-            narsese: parse_narsese_ona(head, tail)?,
+            narsese: parse_narsese_ona(head, tail).map_err(failed)?,
             // 然后传入整个内容
             content_raw,
         },
         "derived" => Output::OUT {
             // 先提取其中的Narsese | ⚠️借用了`content_raw`
-            narsese: parse_narsese_ona(head, tail)?,
+            narsese: parse_narsese_ona(head, tail).map_err(failed)?,
             // 然后传入整个内容
             content_raw,
         },
         "input" => Output::IN {
             // 先提取其中的Narsese | ⚠️借用了`content_raw`
-            narsese: parse_narsese_ona(head, tail)?,
+            narsese: parse_narsese_ona(head, tail).map_err(failed)?,
             content: content_raw,
         },
         "err" | "error" => Output::ERROR {
@@ -180,14 +216,14 @@ pub fn output_translate(content_raw: String) -> Result<Output> {
         // * 🚩对于「操作」的特殊语法
         // * 🚩【2024-04-02 18:45:17】仅截取`executed with args`，不截取`executed by NAR`
         _ if content_raw.contains("executed with args") => Output::EXE {
-            operation: parse_operation_ona(&content_raw)?,
+            operation: parse_operation_ona(&content_raw).map_err(failed)?,
             content_raw,
         },
         // * 🚩对于「决策预期→ANTICIPATE」的特殊语法
         // * 🚩【2024-04-02 18:45:17】仅截取`executed with args`，不截取`executed by NAR`
         _ if content_raw.contains("decision expectation=") => Output::UNCLASSIFIED {
             r#type: ANTICIPATE.into(),
-            narsese: parse_anticipate_ona(&content_raw)?,
+            narsese: parse_anticipate_ona(&content_raw).map_err(failed)?,
             content: content_raw,
         },
         // 若是连续的「头部」⇒识别为「未归类」类型
@@ -213,8 +249,9 @@ pub fn output_translate(content_raw: String) -> Result<Output> {
 /// * ❌`right executed by NAR`
 pub fn parse_operation_ona(content_raw: &str) -> Result<Operation> {
     // 匹配ONA输出中的「操作」⇒转换 | 操作名 | 操作参数（Narsese复合词项⇒提取组分，变成字符串）
-    let re_operation = Regex::new(r"\^([^\s]+)\s*executed with args\s*(.*)").unwrap();
-    let captures = re_capture(&re_operation, content_raw.trim())?;
+    static RE_OPERATION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\^([^\s]+)\s*executed with args\s*(.*)").unwrap());
+    let captures = re_capture(&RE_OPERATION, content_raw.trim())?;
     // ! 即便是测试环境下，也有可能是[`None`]（但只在测试环境下返回[`Err`]并报错）
     match captures {
         Some(captures) => {
@@ -260,8 +297,9 @@ pub fn parse_operation_ona(content_raw: &str) -> Result<Operation> {
 /// * 📄`"decision expectation=0.578198 implication: <(a &/ ^left) =/> g>. Truth: frequency=1.000000 confidence=0.241351 dt=1.000000 precondition: a. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=4\n"`
 pub fn parse_anticipate_ona(content_raw: &str) -> Result<Option<Narsese>> {
     // 正则捕获
-    let re_operation = Regex::new(r"implication:\s*(.*)\s*dt=").unwrap();
-    let captures = re_capture(&re_operation, content_raw.trim())?;
+    static RE_IMPLICATION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"implication:\s*(.*)\s*dt=").unwrap());
+    let captures = re_capture(&RE_IMPLICATION, content_raw.trim())?;
     match captures {
         Some(captures) => {
             // 获取内容
@@ -269,14 +307,17 @@ pub fn parse_anticipate_ona(content_raw: &str) -> Result<Option<Narsese>> {
             // 解析
             let parse_result =
                 parse_narsese_ona(ANTICIPATE, narsese_content.trim()).inspect_err(|e| {
-                    OutputType::Error.eprint_line(&format!("ONA「预期」解析失败：{e}"));
+                    eprint_notice("ERROR", &format!("ONA「预期」解析失败：{e}"));
                 });
             // 返回
             parse_result
         }
         // 截取失败的情形
         None => {
-            OutputType::Error.eprint_line(&format!("ONA「预期」正则捕获失败：{content_raw:?}"));
+            eprint_notice(
+                "ERROR",
+                &format!("ONA「预期」正则捕获失败：{content_raw:?}"),
+            );
             Ok(None)
         }
     }
@@ -314,9 +355,13 @@ fn extract_params(params: Term) -> Vec<Term> {
 /// * 🚩生产环境中仅打印错误消息
 #[cfg(not(test))]
 fn re_capture<'a>(re: &'a Regex, haystack: &'a str) -> Result<Option<Captures<'a>>> {
-    Ok(re
-        .captures(haystack)
-        .inspect_none(|| println!("使用正则表达式「{re}」无法捕获「{haystack}」")))
+    use crate::support::print_notice;
+    Ok(re.captures(haystack).inspect_none(|| {
+        print_notice(
+            "WARN",
+            &format!("使用正则表达式「{re}」无法捕获「{haystack}」"),
+        )
+    }))
 }
 
 /// 正则捕获
@@ -333,14 +378,39 @@ fn re_capture<'a>(re: &'a Regex, haystack: &'a str) -> Result<Option<Captures<'a
     }
 }
 
+/// ONA输出的「步进完成」判断
+/// * 🎯启动配置中的`cycAwait`：等到ONA真正跑完所输入的步数
+/// * 📄`performing 5 inference steps:`⇒（推理）⇒`done with 5 additional inference steps.`
+///   * 📌前者在步进之前打印，只认后者
+pub fn is_cyc_ack(output: &Output) -> bool {
+    matches!(
+        output,
+        Output::OTHER { content }
+            if content.starts_with("done with") && content.contains("inference steps")
+    )
+}
+
+/// ONA输出的「续行」判断
+/// * 🎯将`*stats`的统计报告合并为一个输出
+///   * 📄`Statistics`之后的`----------`、`currentTime:\t\t\t100`、`total concepts:\t\t\t3`
+/// * 🚩仅在`Statistics`开头的输出块中：分隔线、以「冒号+制表符」分隔键值的行
+///   * 📌其它输出块不合并：即便其后的行恰好形如键值
+pub fn continues_previous(block: &str, line: &str) -> bool {
+    let in_stats = block.trim_start().starts_with("Statistics");
+    let line = line.trim_end();
+    in_stats && ((!line.is_empty() && line.chars().all(|c| c == '-')) || line.contains(":\t"))
+}
+
 /// （ONA）从原始输出中解析Narsese
 /// * 🎯用于结合`#[cfg]`控制「严格模式」
 ///   * 🚩生产环境下「Narsese解析出错」仅打印错误信息
 #[cfg(not(test))]
 pub fn parse_narsese_ona(head: &str, tail: &str) -> Result<Option<Narsese>> {
+    use crate::support::print_notice;
     use util::ResultBoost;
     // ! ↓下方会转换为None
-    Ok(try_parse_narsese(tail).ok_or_run(|e| println!("【{head}】在解析Narsese时出现错误：{e}")))
+    Ok(try_parse_narsese(tail)
+        .ok_or_run(|e| print_notice("WARN", &format!("【{head}】在解析Narsese时出现错误：{e}"))))
 }
 
 /// （ONA）从原始输出中解析Narsese
@@ -354,16 +424,32 @@ pub fn parse_narsese_ona(_: &str, tail: &str) -> Result<Option<Narsese>> {
 
 /// （尝试）从输出中解析出Narsese
 /// * ❌【2024-03-27 22:01:18】目前引入[`anyhow::Error`]会出问题：不匹配/未满足的特征
+/// * 🚩重整后沿「解析链」解析：ONA方言失败⇒尝试CommonNarsese与其它CIN的方言
 pub fn try_parse_narsese(tail: &str) -> Result<Narsese> {
-    // 提取并解析Narsese字符串
-    pipe! {
-        tail
-        // 重整
-        => #{&}
-        => reform_output_to_narsese
-        // 解析方言
-        => #{&}
-        => parse_dialect_ona
+    let (narsese, _) = parse_narsese_chain(&reform_output_to_narsese(tail), PARSER_ONA)?;
+    Ok(narsese)
+}
+
+/// ONA输出中数值的正则表达式
+/// * 📌可带正负号、科学计数法，可省略整数部分
+/// * 📄`0.900000`、`-1`、`1e-06`、`0.5e0`、`.5`
+const RE_NUMBER: &str = r"[+-]?(?:[0-9]+\.?[0-9]*|\.[0-9]+)(?:[eE][+-]?[0-9]+)?";
+
+/// 规整化真值中的数值
+/// * 🎯构造CommonNarsese合法的真值：`1e-06`⇒`0.000001`
+/// * 🚩已是`[0, 1]`内的十进制小数⇒原样保留；否则⇒限制到`[0, 1]`后以十进制小数表示
+/// * ⚠️无法解析为数值⇒原样保留，交给后续解析报错
+fn normalize_truth_value(value: &str) -> String {
+    let Ok(number) = value.parse::<f64>() else {
+        return value.into();
+    };
+    // * 📌取绝对值：消除`-0`
+    let clamped = number.clamp(0.0, 1.0).abs();
+    let is_plain = value.starts_with(|c: char| c.is_ascii_digit())
+        && value.chars().all(|c| c.is_ascii_digit() || c == '.');
+    match is_plain && clamped == number {
+        true => value.into(),
+        false => clamped.to_string(),
     }
 }
 
@@ -372,42 +458,51 @@ pub fn try_parse_narsese(tail: &str) -> Result<Narsese> {
 /// * 🚩【2024-03-25 21:38:39】目前使用正则表达式[`regex`]库
 /// * 🚩【2024-03-25 21:38:52】目前仅基于正则表达式做文本替换
 /// * 📌参数`tail`不附带`Answer:`等部分
+/// * 📌数值见[`RE_NUMBER`]：真值中的数值会被[规整化](normalize_truth_value)
+/// * 🚩正则表达式只在首次调用时编译：每行输出都会经过此处
 fn reform_output_to_narsese(out: &str) -> String {
-    // 构造正则表达式（实现中只会编译一次） //
+    // 构造正则表达式 //
+    /// 以[`RE_NUMBER`]匹配数值
+    fn re_with_number(pattern: &str) -> Regex {
+        Regex::new(&pattern.replace("{n}", RE_NUMBER)).unwrap()
+    }
     // 匹配ONA输出中的「真值」⇒转换
-    let re_truth = Regex::new(r"Truth:\s*frequency=([0-9.]+),\s*confidence=([0-9.]+)").unwrap();
+    static RE_TRUTH: LazyLock<Regex> =
+        LazyLock::new(|| re_with_number(r"Truth:\s*frequency=({n}),\s*confidence=({n})"));
     // 匹配ONA输出的「创建时间」⇒删去
-    let re_creation_t = Regex::new(r"creationTime=([0-9.]+)\s+").unwrap();
+    static RE_CREATION_T: LazyLock<Regex> =
+        LazyLock::new(|| re_with_number(r"creationTime=({n})\s+"));
     // 匹配ONA输出的「发生时间」⇒删去
-    let re_occurrence_t = Regex::new(r"occurrenceTime=([0-9.]+)\s+").unwrap();
+    static RE_OCCURRENCE_T: LazyLock<Regex> =
+        LazyLock::new(|| re_with_number(r"occurrenceTime=({n})\s+"));
     // 匹配ONA输出的「时间递进」⇒删去
-    let re_dt = Regex::new(r"dt=([0-9.]+)\s+").unwrap();
+    static RE_DT: LazyLock<Regex> = LazyLock::new(|| re_with_number(r"dt=({n})\s+"));
     // 匹配ONA输出的「优先级」⇒删去
-    let re_priority = Regex::new(r"Priority=([0-9.]+)\s+").unwrap();
+    static RE_PRIORITY: LazyLock<Regex> = LazyLock::new(|| re_with_number(r"Priority=({n})\s+"));
 
     // 两次替换 //
     pipe! {
         out
         // 重建真值表达式
-        => [re_truth.replace_all](_, |caps: &regex::Captures<'_>| {
+        => [RE_TRUTH.replace_all](_, |caps: &regex::Captures<'_>| {
             // * 第`0`个是正则表达式匹配的整个内容
-            let f = &caps[1];
-            let c = &caps[2];
+            let f = normalize_truth_value(&caps[1]);
+            let c = normalize_truth_value(&caps[2]);
             // 重建CommonNarsese合法的真值
             format!("%{f};{c}%")
         })
         => #{&}
         // 删去非必要的「创建时间」
-        => [re_creation_t.replace_all](_, "")
+        => [RE_CREATION_T.replace_all](_, "")
         => #{&} // 必须借用
         // 删去非必要的「发生时间」
-        => [re_occurrence_t.replace_all](_, "")
+        => [RE_OCCURRENCE_T.replace_all](_, "")
         => #{&} // 必须借用
         // 删去非必要的「递进时间」
-        => [re_dt.replace_all](_, "")
+        => [RE_DT.replace_all](_, "")
         => #{&} // 必须借用
         // 删去非必要的「优先级」
-        => [re_priority.replace_all](_, "")
+        => [RE_PRIORITY.replace_all](_, "")
         // 剪切前后空白符
         => .trim()
         // 返回字符串 //
@@ -419,8 +514,9 @@ fn reform_output_to_narsese(out: &str) -> String {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::cin_implements::ona::parse as parse_dialect_ona;
     use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
-    use navm::output::type_names::ANSWER;
+    use navm::output::type_names::{ANSWER, OUT};
     use util::asserts;
 
     /// 测试/正则重整
@@ -443,240 +539,116 @@ mod test {
         }
     }
 
+    /// 测试/续行判断：统计报告
+    #[test]
+    fn test_continues_previous() {
+        asserts! {
+            continues_previous("Statistics\n", "----------\n")
+            continues_previous("Statistics\n----------\n", "currentTime:\t\t\t100\n")
+            !continues_previous("Statistics\n", "Statistics\n")
+            !continues_previous("Statistics\n", "Input: <A --> B>. Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000")
+            !continues_previous("Statistics\n", "\n")
+            // 统计报告之外⇒不合并
+            !continues_previous("Answer: None.", "currentTime:\t\t\t100\n")
+            !continues_previous("Input: <A --> B>.", "----------\n")
+        }
+    }
+
+    /// 测试/步进完成的回执
+    #[test]
+    fn test_is_cyc_ack() {
+        let output = |line: &str| output_translate(line.into()).expect("ONA输出解析失败");
+        asserts! {
+            is_cyc_ack(&output("done with 5 additional inference steps."))
+            !is_cyc_ack(&output("performing 5 inference steps:"))
+            !is_cyc_ack(&output("Answer: None."))
+        }
+    }
+
+    /// 测试/真值数值的规整化
+    #[test]
+    fn test_normalize_truth_value() {
+        asserts! {
+            // 已是`[0, 1]`内的十进制小数⇒原样保留
+            normalize_truth_value("0.900000") => "0.900000",
+            normalize_truth_value("1") => "1",
+            // 科学计数法、省略整数部分、正负号
+            normalize_truth_value("1e-06") => "0.000001",
+            normalize_truth_value("0.5e0") => "0.5",
+            normalize_truth_value(".5") => "0.5",
+            normalize_truth_value("+0.25") => "0.25",
+            // 超出范围⇒限制到`[0, 1]`
+            normalize_truth_value("-0.1") => "0",
+            normalize_truth_value("1.5E0") => "1",
+        }
+    }
+
+    /// 测试/混合方言的输出：OpenNARS风格的操作也能解析
+    #[test]
+    fn test_output_parse_mixed_dialect() {
+        for (line, expected) in [
+            (
+                "Derived: <(* {SELF}) --> ^left>. Priority=0.245189 Truth: frequency=1.000000, confidence=0.810000",
+                "<(*, {SELF}) --> ^left>. %1.000000;0.810000%",
+            ),
+            (
+                "Derived: <(*, {SELF}) --> ^left>. Priority=0.245189 Truth: frequency=1.000000, confidence=0.810000",
+                "<(*, {SELF}) --> ^left>. %1.000000;0.810000%",
+            ),
+            (
+                "Derived: <(&/, <a --> b>, +5, (^left, {SELF})) =/> c>. Priority=0.245189 Truth: frequency=1.000000, confidence=0.810000",
+                "<(&/, <a --> b>, +5, <(*, {SELF}) --> ^left>) =/> c>. %1.000000;0.810000%",
+            ),
+        ] {
+            // ! 测试环境下[`parse_narsese_ona`]会强制要求「Narsese内容解析成功」
+            let output = output_translate(line.into()).expect("输出转译失败");
+            let narsese = output.get_narsese().expect("未解析出Narsese");
+            assert_eq!(FORMAT_ASCII.format_narsese(narsese), expected);
+        }
+    }
+
+    /// 测试/输出解析：带科学计数法、负数的数值
+    /// * 📄ONA以`%g`等格式输出极小的数值：`confidence=1e-06`
+    #[test]
+    fn test_output_parse_scientific() {
+        let cases = [
+            (
+                "Answer: <A --> C>. creationTime=2 Truth: frequency=1.000000, confidence=1e-06",
+                "<A --> C>. %1.000000;0.000001%",
+            ),
+            (
+                "Derived: <A --> B>. :|: occurrenceTime=1e+02 Priority=2.5e-01 Truth: frequency=0.5e0, confidence=.9",
+                "<A --> B>. :|: %0.5;0.9%",
+            ),
+            (
+                "Derived: dt=-1.000000 <A =/> B>. Priority=0.120425 Truth: frequency=-0.0, confidence=1.2",
+                "<A =/> B>. %0;1%",
+            ),
+        ];
+        for (output, expected) in cases {
+            let o = output_translate(output.into()).expect("输出解析失败");
+            assert!(o.is_type(ANSWER) || o.is_type(OUT), "{o:?}");
+            let narsese = o.get_narsese().expect("应有Narsese");
+            assert_eq!(FORMAT_ASCII.format_narsese(narsese), expected);
+        }
+        // 方言语法本身亦支持
+        let narsese = parse_dialect_ona("<A --> B>. %1e-06;-.5%").expect("方言解析失败");
+        assert_eq!(
+            FORMAT_ASCII.format_narsese(&narsese),
+            "<A --> B>. %1e-06;-.5%"
+        );
+    }
+
     /// 测试/输出解析
     #[test]
     fn test_output_parse() {
         // 📄输出源自ONA测试文件`whatwarmer.nal`与ONA的命令行交互
-        let outputs = "
-        <a --> [warm]>. :|: %0.8%
-        Input: <a --> [warm]>. :|: occurrenceTime=1 Priority=1.000000 Truth: frequency=0.800000, confidence=0.900000
-        <a --> [warm]>. :|: %0.8%
-        Input: <a --> [warm]>. :|: occurrenceTime=2 Priority=1.000000 Truth: frequency=0.800000, confidence=0.900000
-        <a --> [warm]>. :|: %0.8%
-        Input: <a --> [warm]>. :|: occurrenceTime=3 Priority=1.000000 Truth: frequency=0.800000, confidence=0.900000
-        <b --> [warm]>. :|: %0.3%
-        Input: <b --> [warm]>. :|: occurrenceTime=4 Priority=1.000000 Truth: frequency=0.300000, confidence=0.900000
-        Derived: dt=1.000000 <<a --> [$1]> =/> <b --> [$1]>>. Priority=0.120425 Truth: frequency=0.300000, confidence=0.254517
-        Derived: dt=1.000000 <<a --> [warm]> =/> <b --> [warm]>>. Priority=0.120425 Truth: frequency=0.300000, confidence=0.254517
-        Derived: <a --> b>. :|: occurrenceTime=4 Priority=0.246973 Truth: frequency=0.800000, confidence=0.162760
-        Derived: <b --> a>. :|: occurrenceTime=4 Priority=0.194273 Truth: frequency=0.300000, confidence=0.341412
-        Derived: <a <-> b>. :|: occurrenceTime=4 Priority=0.189423 Truth: frequency=0.279070, confidence=0.357855
-        Derived: <b <-> a>. :|: occurrenceTime=4 Priority=0.189423 Truth: frequency=0.279070, confidence=0.357855
-        Derived: <(b | a) --> [warm]>. :|: occurrenceTime=4 Priority=0.099456 Truth: frequency=0.240000, confidence=0.648000
-        Derived: <(a | b) --> [warm]>. :|: occurrenceTime=4 Priority=0.099456 Truth: frequency=0.240000, confidence=0.648000
-        Derived: <(b & a) --> [warm]>. :|: occurrenceTime=4 Priority=0.219984 Truth: frequency=0.860000, confidence=0.648000
-        Derived: <(a & b) --> [warm]>. :|: occurrenceTime=4 Priority=0.219984 Truth: frequency=0.860000, confidence=0.648000
-        Derived: <(b ~ a) --> [warm]>. :|: occurrenceTime=4 Priority=0.064464 Truth: frequency=0.060000, confidence=0.648000
-        Derived: <(a ~ b) --> [warm]>. :|: occurrenceTime=4 Priority=0.161664 Truth: frequency=0.560000, confidence=0.648000
-        Derived: <(a * b) --> (+ warm)>. :|: occurrenceTime=4 Priority=0.247200 Truth: frequency=1.000000, confidence=0.648000
-        Derived: <<a --> [$1]> ==> <b --> [$1]>>. :|: occurrenceTime=4 Priority=0.108382 Truth: frequency=0.300000, confidence=0.341412
-        Derived: <<b --> [$1]> ==> <a --> [$1]>>. :|: occurrenceTime=4 Priority=0.137782 Truth: frequency=0.800000, confidence=0.162760
-        Derived: <<a --> [$1]> <=> <b --> [$1]>>. :|: occurrenceTime=4 Priority=0.105676 Truth: frequency=0.279070, confidence=0.357855
-        Derived: <<b --> [$1]> <=> <a --> [$1]>>. :|: occurrenceTime=4 Priority=0.105676 Truth: frequency=0.279070, confidence=0.357855
-        Derived: (<a --> [#1]> && <b --> [#1]>). :|: occurrenceTime=4 Priority=0.083228 Truth: frequency=0.240000, confidence=0.648000
-        Derived: (<b --> [#1]> && <a --> [#1]>). :|: occurrenceTime=4 Priority=0.083228 Truth: frequency=0.240000, confidence=0.648000
-        <(?1 ~ ?2) --> [warm]>? :|:
-        Input: <(?1 ~ ?2) --> [warm]>? :|:
-        Answer: <(a ~ b) --> [warm]>. :|: occurrenceTime=4 creationTime=4 Truth: frequency=0.560000, confidence=0.648000
-        ^pick. :|:
-        Input: ^pick. :|: occurrenceTime=5 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        G. :|:
-        Input: G. :|: occurrenceTime=6 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=1.000000 <(<a --> [warm]> &/ ^pick) =/> G>. Priority=0.185124 Truth: frequency=1.000000, confidence=0.186952
-        Derived: dt=1.000000 <(<(a | b) --> [warm]> &/ ^pick) =/> G>. Priority=0.149877 Truth: frequency=1.000000, confidence=0.069427
-        Derived: dt=1.000000 <(<a --> b> &/ ^pick) =/> G>. Priority=0.177205 Truth: frequency=1.000000, confidence=0.059471
-        Derived: dt=1.000000 <(<b --> a> &/ ^pick) =/> G>. Priority=0.175070 Truth: frequency=1.000000, confidence=0.047999
-        Derived: dt=1.000000 <(<a <-> b> &/ ^pick) =/> G>. Priority=0.174870 Truth: frequency=1.000000, confidence=0.046913
-        Derived: dt=1.000000 <(<b <-> a> &/ ^pick) =/> G>. Priority=0.174870 Truth: frequency=1.000000, confidence=0.046913
-        Derived: dt=1.000000 <(<(b | a) --> [warm]> &/ ^pick) =/> G>. Priority=0.149877 Truth: frequency=1.000000, confidence=0.069427
-        Derived: dt=1.000000 <(<b --> [warm]> &/ ^pick) =/> G>. Priority=0.168996 Truth: frequency=1.000000, confidence=0.109355
-        Derived: dt=1.000000 <(<(a & b) --> [warm]> &/ ^pick) =/> G>. Priority=0.170733 Truth: frequency=1.000000, confidence=0.183101
-        Derived: dt=1.000000 <(<(b ~ a) --> [warm]> &/ ^pick) =/> G>. Priority=0.142227 Truth: frequency=1.000000, confidence=0.019374
-        Derived: dt=1.000000 <(<(a ~ b) --> [warm]> &/ ^pick) =/> G>. Priority=0.161554 Truth: frequency=1.000000, confidence=0.136690
-        Derived: dt=1.000000 <(<(a * b) --> (+ warm)> &/ ^pick) =/> G>. Priority=0.174542 Truth: frequency=1.000000, confidence=0.200929
-        Derived: dt=1.000000 <((<a --> [#1]> && <b --> [#1]>) &/ ^pick) =/> G>. Priority=0.134326 Truth: frequency=1.000000, confidence=0.069427
-        Derived: dt=1.000000 <((<b --> [#1]> && <a --> [#1]>) &/ ^pick) =/> G>. Priority=0.134326 Truth: frequency=1.000000, confidence=0.069427
-        Derived: dt=1.000000 <((<a --> [warm]> &/ <b --> [warm]>) &/ ^pick) =/> G>. Priority=0.134326 Truth: frequency=1.000000, confidence=0.069427
-        Derived: dt=1.000000 <(<(b & a) --> [warm]> &/ ^pick) =/> G>. Priority=0.170733 Truth: frequency=1.000000, confidence=0.183101
-        Derived: dt=3.000000 <<a --> [warm]> =/> G>. Priority=0.208187 Truth: frequency=1.000000, confidence=0.199438
-        Derived: dt=2.000000 <<(a | b) --> [warm]> =/> G>. Priority=0.162890 Truth: frequency=1.000000, confidence=0.075969
-        Derived: dt=2.000000 <<a --> b> =/> G>. Priority=0.206921 Truth: frequency=1.000000, confidence=0.065217
-        Derived: dt=2.000000 <<b --> a> =/> G>. Priority=0.204202 Truth: frequency=1.000000, confidence=0.052770
-        Derived: dt=2.000000 <<a <-> b> =/> G>. Priority=0.203948 Truth: frequency=1.000000, confidence=0.051588
-        Derived: dt=2.000000 <<b <-> a> =/> G>. Priority=0.203948 Truth: frequency=1.000000, confidence=0.051588
-        Derived: dt=2.000000 <<(b | a) --> [warm]> =/> G>. Priority=0.162890 Truth: frequency=1.000000, confidence=0.075969
-        Derived: dt=2.000000 <<(a * b) --> (+ warm)> =/> G>. Priority=0.191425 Truth: frequency=1.000000, confidence=0.213712
-        Derived: dt=2.000000 <(<a --> [#1]> && <b --> [#1]>) =/> G>. Priority=0.142122 Truth: frequency=1.000000, confidence=0.075969
-        Derived: dt=2.000000 <(<b --> [#1]> && <a --> [#1]>) =/> G>. Priority=0.142122 Truth: frequency=1.000000, confidence=0.075969
-        Derived: dt=2.000000 <(<a --> [warm]> &/ <b --> [warm]>) =/> G>. Priority=0.142122 Truth: frequency=1.000000, confidence=0.075969
-        Derived: dt=2.000000 <<(b & a) --> [warm]> =/> G>. Priority=0.187089 Truth: frequency=1.000000, confidence=0.195491
-        Derived: dt=2.000000 <<b --> [warm]> =/> G>. Priority=0.189098 Truth: frequency=1.000000, confidence=0.118623
-        Derived: dt=2.000000 <<(a & b) --> [warm]> =/> G>. Priority=0.187089 Truth: frequency=1.000000, confidence=0.195491
-        Derived: dt=2.000000 <<(b ~ a) --> [warm]> =/> G>. Priority=0.153812 Truth: frequency=1.000000, confidence=0.021435
-        Derived: dt=2.000000 <<(a ~ b) --> [warm]> =/> G>. Priority=0.176536 Truth: frequency=1.000000, confidence=0.147400
-        <(<(a ~ b) --> [warm]> &/ ^pick) =/> G>?
-        Input: <(<(a ~ b) --> [warm]> &/ ^pick) =/> G>?
-        Answer: <(<(a ~ b) --> [warm]> &/ ^pick) =/> G>. creationTime=6 Truth: frequency=1.000000, confidence=0.136690
-
-        a. :|:
-        Input: a. :|: occurrenceTime=1 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        ^left. :|:
-        Input: ^left. :|: occurrenceTime=2 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        g. :|:
-        Input: g. :|: occurrenceTime=3 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=1.000000 <(a &/ ^left) =/> g>. Priority=0.254962 Truth: frequency=1.000000, confidence=0.241351
-        Derived: dt=2.000000 <a =/> g>. Priority=0.335353 Truth: frequency=1.000000, confidence=0.254517
-        a. :|:
-        Input: a. :|: occurrenceTime=4 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=1.000000 <g =/> a>. Priority=0.348301 Truth: frequency=1.000000, confidence=0.282230
-        Derived: dt=1.000000 <(a &/ g) =/> a>. Priority=0.246000 Truth: frequency=1.000000, confidence=0.213712
-        g! :|:
-        Input: g! :|: occurrenceTime=5 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        decision expectation=0.578198 implication: <(a &/ ^left) =/> g>. Truth: frequency=1.000000 confidence=0.241351 dt=1.000000 precondition: a. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=4
-        ^left executed with args
-        Input: ^left. :|: occurrenceTime=5 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        A. :|:
-        Input: A. :|: occurrenceTime=7 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=2.000000 <((g &/ a) &/ ^left) =/> A>. Priority=0.201969 Truth: frequency=1.000000, confidence=0.174792
-        Derived: dt=2.000000 <(a &/ ^left) =/> A>. Priority=0.246000 Truth: frequency=1.000000, confidence=0.213712
-        Derived: dt=2.000000 <((a &/ g) &/ ^left) =/> A>. Priority=0.191125 Truth: frequency=1.000000, confidence=0.127972
-        Derived: dt=2.000000 <(g &/ ^left) =/> A>. Priority=0.237903 Truth: frequency=1.000000, confidence=0.186952
-        Derived: dt=3.000000 <(g &/ a) =/> A>. Priority=0.237903 Truth: frequency=1.000000, confidence=0.186952
-        Derived: dt=3.000000 <a =/> A>. Priority=0.323287 Truth: frequency=1.000000, confidence=0.226692
-        Derived: dt=4.000000 <(a &/ g) =/> A>. Priority=0.224460 Truth: frequency=1.000000, confidence=0.138259
-        Derived: dt=4.000000 <g =/> A>. Priority=0.312281 Truth: frequency=1.000000, confidence=0.199438
-        <(*, {SELF}) --> ^left>. :|:
-        Input: <(* {SELF}) --> ^left>. :|: occurrenceTime=8 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: (* {SELF}). :|: occurrenceTime=8 Priority=0.182344 Truth: frequency=1.000000, confidence=0.293146
-        G. :|:
-        Input: G. :|: occurrenceTime=9 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=1.000000 <(((g &/ A) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.134179 Truth: frequency=1.000000, confidence=0.068411
-        Derived: dt=1.000000 <((a &/ ^left) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.144347 Truth: frequency=1.000000, confidence=0.090215
-        Derived: dt=1.000000 <(((g &/ a) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.134179 Truth: frequency=1.000000, confidence=0.068411
-        Derived: dt=1.000000 <((g &/ ^left) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.141953 Truth: frequency=1.000000, confidence=0.074873
-        Derived: dt=1.000000 <(((a &/ A) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.136267 Truth: frequency=1.000000, confidence=0.082685
-        Derived: dt=1.000000 <(((a &/ g) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.131034 Truth: frequency=1.000000, confidence=0.046051
-        Derived: dt=1.000000 <((A &/ ^left) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.154562 Truth: frequency=1.000000, confidence=0.150345
-        Derived: dt=4.000000 <(a &/ ^left) =/> G>. Priority=0.230723 Truth: frequency=1.000000, confidence=0.161649
-        Derived: dt=4.000000 <((g &/ a) &/ ^left) =/> G>. Priority=0.191125 Truth: frequency=1.000000, confidence=0.127972
-        Derived: dt=4.000000 <(g &/ ^left) =/> G>. Priority=0.224460 Truth: frequency=1.000000, confidence=0.138259
-        Derived: dt=4.000000 <((a &/ g) &/ ^left) =/> G>. Priority=0.183193 Truth: frequency=1.000000, confidence=0.090215
-        Derived: dt=1.000000 <((g &/ A) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.150597 Truth: frequency=1.000000, confidence=0.127972
-        Derived: dt=1.000000 <(a &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.166364 Truth: frequency=1.000000, confidence=0.161649
-        Derived: dt=1.000000 <((g &/ a) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.150597 Truth: frequency=1.000000, confidence=0.127972
-        Derived: dt=1.000000 <(g &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.161849 Truth: frequency=1.000000, confidence=0.138259
-        Derived: dt=1.000000 <((a &/ A) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.154562 Truth: frequency=1.000000, confidence=0.150345
-        Derived: dt=1.000000 <((a &/ g) &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.144347 Truth: frequency=1.000000, confidence=0.090215
-        Derived: dt=1.000000 <(A &/ <(* {SELF}) --> ^left>) =/> G>. Priority=0.183842 Truth: frequency=1.000000, confidence=0.241351
-        Derived: dt=2.000000 <(g &/ A) =/> G>. Priority=0.224460 Truth: frequency=1.000000, confidence=0.138259
-        Derived: dt=5.000000 <a =/> G>. Priority=0.302437 Truth: frequency=1.000000, confidence=0.173382
-        Derived: dt=5.000000 <(g &/ a) =/> G>. Priority=0.224460 Truth: frequency=1.000000, confidence=0.138259
-        Derived: dt=6.000000 <g =/> G>. Priority=0.293787 Truth: frequency=1.000000, confidence=0.149042
-        Derived: dt=2.000000 <(a &/ A) =/> G>. Priority=0.230723 Truth: frequency=1.000000, confidence=0.161649
-        Derived: dt=1.000000 <(* {SELF}) =/> G>. Priority=0.195713 Truth: frequency=1.000000, confidence=0.148415
-        Derived: dt=6.000000 <(a &/ g) =/> G>. Priority=0.214505 Truth: frequency=1.000000, confidence=0.098268
-        Derived: dt=2.000000 <A =/> G>. Priority=0.335353 Truth: frequency=1.000000, confidence=0.254517
-        A. :|:
-        Input: A. :|: occurrenceTime=10 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=2.000000 <((a &/ ^left) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.141953 Truth: frequency=1.000000, confidence=0.074873
-        Derived: dt=2.000000 <(((g &/ a) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.132453 Truth: frequency=1.000000, confidence=0.056268
-        Derived: dt=2.000000 <(((g &/ A) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.132453 Truth: frequency=1.000000, confidence=0.056268
-        Derived: dt=2.000000 <(((a &/ g) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.129874 Truth: frequency=1.000000, confidence=0.037532
-        Derived: dt=2.000000 <((g &/ ^left) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.139967 Truth: frequency=1.000000, confidence=0.061748
-        Derived: dt=2.000000 <(((a &/ A) &/ ^left) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.134179 Truth: frequency=1.000000, confidence=0.068411
-        Derived: dt=2.000000 <(a &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.161849 Truth: frequency=1.000000, confidence=0.138259
-        Derived: dt=2.000000 <((g &/ a) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.147209 Truth: frequency=1.000000, confidence=0.107901
-        Derived: dt=2.000000 <((g &/ A) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.147209 Truth: frequency=1.000000, confidence=0.107901
-        Derived: dt=2.000000 <((a &/ g) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.141953 Truth: frequency=1.000000, confidence=0.074873
-        Derived: dt=2.000000 <(g &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.157967 Truth: frequency=1.000000, confidence=0.117083
-        Derived: dt=2.000000 <((a &/ A) &/ <(* {SELF}) --> ^left>) =/> A>. Priority=0.150597 Truth: frequency=1.000000, confidence=0.127972
-        Derived: dt=5.000000 <(a &/ ^left) =/> A>. Priority=0.224460 Truth: frequency=1.000000, confidence=0.138259
-        Revised: dt=3.113558 <(a &/ ^left) =/> A>. Priority=0.224460 Truth: frequency=1.000000, confidence=0.301794
-        Derived: dt=5.000000 <((g &/ a) &/ ^left) =/> A>. Priority=0.186825 Truth: frequency=1.000000, confidence=0.107901
-        Revised: dt=3.090418 <((g &/ a) &/ ^left) =/> A>. Priority=0.186825 Truth: frequency=1.000000, confidence=0.249682
-        Derived: dt=5.000000 <((a &/ g) &/ ^left) =/> A>. Priority=0.180156 Truth: frequency=1.000000, confidence=0.074873
-        Revised: dt=3.066382 <((a &/ g) &/ ^left) =/> A>. Priority=0.180156 Truth: frequency=1.000000, confidence=0.185459
-        Derived: dt=5.000000 <(g &/ ^left) =/> A>. Priority=0.219076 Truth: frequency=1.000000, confidence=0.117083
-        Revised: dt=3.097308 <(g &/ ^left) =/> A>. Priority=0.219076 Truth: frequency=1.000000, confidence=0.266081
-        Derived: dt=6.000000 <a =/> A>. Priority=0.293787 Truth: frequency=1.000000, confidence=0.149042
-        Revised: dt=4.100474 <a =/> A>. Priority=0.293787 Truth: frequency=0.980787, confidence=0.323166
-        Derived: dt=1.000000 <G =/> A>. Priority=0.348301 Truth: frequency=1.000000, confidence=0.282230
-        Derived: dt=2.000000 <(* {SELF}) =/> A>. Priority=0.190743 Truth: frequency=1.000000, confidence=0.126225
-        Derived: dt=1.000000 <(A &/ G) =/> A>. Priority=0.246000 Truth: frequency=1.000000, confidence=0.213712
-        Derived: dt=1.000000 <(g &/ G) =/> A>. Priority=0.219076 Truth: frequency=1.000000, confidence=0.117083
-        Derived: dt=1.000000 <((* {SELF}) &/ G) =/> A>. Priority=0.170371 Truth: frequency=1.000000, confidence=0.116545
-        Derived: dt=7.000000 <(a &/ g) =/> A>. Priority=0.210665 Truth: frequency=1.000000, confidence=0.081831
-        Revised: dt=5.053462 <(a &/ g) =/> A>. Priority=0.210665 Truth: frequency=0.983303, confidence=0.202427
-        Derived: dt=7.000000 <g =/> A>. Priority=0.286301 Truth: frequency=1.000000, confidence=0.126793
-        Revised: dt=5.084493 <g =/> A>. Priority=0.286301 Truth: frequency=0.981712, confidence=0.286567
-        Derived: dt=1.000000 <(a &/ G) =/> A>. Priority=0.224460 Truth: frequency=1.000000, confidence=0.138259
-        Derived: dt=6.000000 <(g &/ a) =/> A>. Priority=0.219076 Truth: frequency=1.000000, confidence=0.117083
-        Revised: dt=4.077649 <(g &/ a) =/> A>. Priority=0.219076 Truth: frequency=0.982085, confidence=0.269626
-        G! :|:
-        Input: G! :|: occurrenceTime=11 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=4.000000 <a =/> (* {SELF})>. Priority=0.182921 Truth: frequency=1.000000, confidence=0.088860
-        Derived: dt=4.000000 <(g &/ a) =/> (* {SELF})>. Priority=0.161381 Truth: frequency=1.000000, confidence=0.067330
-        Derived: dt=5.000000 <(a &/ g) =/> (* {SELF})>. Priority=0.157655 Truth: frequency=1.000000, confidence=0.045286
-        Derived: dt=5.000000 <g =/> (* {SELF})>. Priority=0.179929 Truth: frequency=1.000000, confidence=0.073708
-        decision expectation=0.578198 implication: <(A &/ <(* {SELF}) --> ^left>) =/> G>. Truth: frequency=1.000000 confidence=0.241351 dt=1.000000 precondition: A. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=10
-        ^left executed with args (* {SELF})
-        Input: <(* {SELF}) --> ^left>. :|: occurrenceTime=11 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: (* {SELF}). :|: occurrenceTime=11 Priority=0.120799 Truth: frequency=1.000000, confidence=0.175147
-
-        A. :|:
-        Input: A. :|: occurrenceTime=1 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        <(*, {SELF}) --> ^left>. :|:
-        Input: <(* {SELF}) --> ^left>. :|: occurrenceTime=2 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        G. :|:
-        Input: G. :|: occurrenceTime=3 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        A. :|:
-        Input: A. :|: occurrenceTime=4 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        G! :|:
-        Input: G! :|: occurrenceTime=5 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        decision expectation=0.578198 implication: <(A &/ <(* {SELF}) --> ^left>) =/> G>. Truth: frequency=1.000000 confidence=0.241351 dt=1.000000 precondition: A. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=4
-        ^left executed with args (* {SELF})
-        Input: <(* {SELF}) --> ^left>. :|: occurrenceTime=5 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-
-        A2. :|:
-        Input: A2. :|: occurrenceTime=8 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        <(*, {SELF}, P) --> ^left>. :|:
-        Input: <({SELF} * P) --> ^left>. :|: occurrenceTime=9 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        G2. :|:
-        Input: G2. :|: occurrenceTime=10 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        A2. :|:
-        Input: A2. :|: occurrenceTime=11 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        G2! :|:
-        Input: G2! :|: occurrenceTime=12 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        decision expectation=0.578198 implication: <(A2 &/ <({SELF} * P) --> ^left>) =/> G2>. Truth: frequency=1.000000 confidence=0.241351 dt=1.000000 precondition: A2. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=11
-        ^left executed with args ({SELF} * P)
-        Input: <({SELF} * P) --> ^left>. :|: occurrenceTime=12 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-
-        A. :|:
-        Input: A. :|: occurrenceTime=1 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        <(*, {SELF}) --> ^op>. :|:
-        Input: <(* {SELF}) --> ^op>. :|: occurrenceTime=2 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        G. :|:
-        Input: G. :|: occurrenceTime=3 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=1.000000 <(A &/ <(* {SELF}) --> ^op>) =/> G>. Priority=0.183842 Truth: frequency=1.000000, confidence=0.241351
-        Derived: dt=2.000000 <A =/> G>. Priority=0.335353 Truth: frequency=1.000000, confidence=0.254517
-        A. :|:
-        Input: A. :|: occurrenceTime=4 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        Derived: dt=1.000000 <G =/> A>. Priority=0.348301 Truth: frequency=1.000000, confidence=0.282230
-        Derived: dt=1.000000 <(A &/ G) =/> A>. Priority=0.246000 Truth: frequency=1.000000, confidence=0.213712
-        G! :|:
-        Input: G! :|: occurrenceTime=5 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-        decision expectation=0.578198 implication: <(A &/ <(* {SELF}) --> ^op>) =/> G>. Truth: frequency=1.000000 confidence=0.241351 dt=1.000000 precondition: A. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=4
-        ^op executed with args (* {SELF})
-        Input: <(* {SELF}) --> ^op>. :|: occurrenceTime=5 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000
-
-        A.
-        B?
-        Answer: None.
-        " // 【2024-03-29 16:58:32】省略的「操作注册」语法：`*setopname 1 ^op`
-        // 初步数据处理
-        .split('\n')
-        .map(str::trim)
-        .filter(|l| !l.is_empty());
+        // * 【2024-03-29 16:58:32】省略的「操作注册」语法：`*setopname 1 ^op`
+        let outputs = crate::tests::output_samples::ONA
+            // 初步数据处理
+            .split('\n')
+            .map(str::trim)
+            .filter(|l| !l.is_empty());
 
         // 开始测试解析
         for output in outputs {
@@ -685,6 +657,11 @@ mod test {
             // * 📌测试不能放过`Answer: None.`这个「不是回答的『回答』」
             // * 🚩「是回答」与「内容为`Answer: None.`」不能共存
             assert!(!(o.is_type(ANSWER) && o.raw_content().contains("None.")));
+            // * 🚩`Answer: None.`⇒「无回答」
+            assert_eq!(
+                o.is_type(NO_ANSWER),
+                o.raw_content().contains("Answer: None.")
+            );
             // 正常解析并展示Narsese
             if let Some(narsese) = o.get_narsese() {
                 println!("{}", FORMAT_ASCII.format_narsese(narsese))
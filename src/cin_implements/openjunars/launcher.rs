@@ -4,10 +4,10 @@
 //!   * ✨不同启动器可以启动到相同运行时
 //! * 🚩通过[`CommandGeneratorJulia`]管理启动参数
 
-use super::{input_translate, output_translate};
+use super::{input_translate, output_translate, READY_MARKER};
 use crate::{
     cin_implements::common::CommandGeneratorJulia,
-    runtimes::{CommandGenerator, CommandVm, CommandVmRuntime},
+    runtimes::{CommandGenerator, CommandVm, CommandVmRuntime, DEFAULT_STARTUP_TIMEOUT},
 };
 use anyhow::Result;
 use nar_dev_utils::manipulate;
@@ -21,6 +21,8 @@ use std::path::PathBuf;
 /// * 🚩【2024-03-25 08:55:07】基于Julia模块文件启动OpenJunars
 ///   * 默认预置指令：``julia [`.jl`脚本文件路径]``
 /// * 🚩【2024-03-25 09:15:07】删去[`Default`]派生：因为可能导致无效的路径
+/// * 🚩将Julia日志重定向至标准输出：OpenJunars经由`@info`打印回答
+///   * 实际指令：``julia -e [引入`.jl`脚本文件的引导代码]``
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct OpenJunars {
     /// Julia脚本文件路径
@@ -31,7 +33,10 @@ impl OpenJunars {
     pub fn new(jl_path: impl Into<PathBuf>) -> Self {
         Self {
             // 转换为路径
-            command_generator: CommandGeneratorJulia::new(jl_path),
+            command_generator: manipulate!(
+                CommandGeneratorJulia::new(jl_path)
+                => .log_to_stdout(true)
+            ),
         }
     }
 }
@@ -49,6 +54,8 @@ impl VmLauncher for OpenJunars {
             // * 🚩固定的「输入输出转译器」
             => .input_translator(input_translate)
             => .output_translator(output_translate)
+            // * 🚩启动检查：启动即失败⇒报错并附带其输出；等待该CIN的就绪标志
+            => .startup_check(DEFAULT_STARTUP_TIMEOUT, READY_MARKER)
         )
         // 🔥启动
         .launch()
@@ -2,6 +2,9 @@
 //! * 🚩只提供「一行启动」的功能封装
 //!   * 🎯无需自行配置「输入输出转译器」
 
+use crate::runtimes::Capabilities;
+use navm::output::Output;
+
 // 转译器
 util::mod_and_pub_use! {
     // 转译器
@@ -10,16 +13,33 @@ util::mod_and_pub_use! {
     launcher
 }
 
+/// 所支持的功能
+/// * ⚠️仅支持NAL-1~6：无时序、操作
+pub const CAPABILITIES: Capabilities = Capabilities {
+    answers: true,
+    ..Capabilities::NONE
+};
+
+/// 「步进完成」的回执判断
+/// * 🚩不报告⇒等待CIN安静下来
+pub const CYC_ACK_MATCHER: Option<fn(&Output) -> bool> = None;
+
+/// 启动检查的「就绪标志」
+/// * 🚩没有固定的就绪提示⇒任意一行输出即视作就绪
+pub const READY_MARKER: Option<fn(&str) -> bool> = None;
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
-    #![allow(unused)]
-
     use super::*;
-    use crate::{runtimes::CommandVmRuntime, tests::cin_paths::OPENJUNARS};
+    use crate::{
+        runtimes::{tests::await_fetch_until, CommandVmRuntime},
+        tests::cin_paths::OPENJUNARS,
+    };
     use narsese::conversion::string::impl_lexical::shortcuts::*;
     use navm::{
         cmd::Cmd,
+        output::Output,
         vm::{VmLauncher, VmRuntime},
     };
 
@@ -31,46 +51,31 @@ mod tests {
         // 一行代码启动OpenJunars
         let vm = OpenJunars::new(jl_path).launch().expect("无法启动虚拟机");
         // 运行专有测试
-        // ! ❌【2024-03-25 13:56:21】目前无法截取到Julia运行时输出，弃用
         _test_open_junars(vm)
     }
 
     /// 测试/OpenJunars
+    /// * 📝Julia日志（`[ Info: Answer: ...`）默认写入标准错误，而非标准输出
+    ///   * 🚩启动器已将其重定向至标准输出，故可截获回答
     pub(crate) fn _test_open_junars(mut vm: CommandVmRuntime) {
-        // ! ❌【2024-03-25 13:55:57】无效：似乎无法截取到Julia运行时输出
-
-        vm.input_cmd(Cmd::NSE(nse_task!(<A --> B>.)))
-            .expect("无法输入指令");
-
         // 等待四秒钟，让Junars启动
         std::thread::sleep(std::time::Duration::from_secs(4));
 
         vm.input_cmd(Cmd::NSE(nse_task!(<A --> B>.)))
             .expect("无法输入指令");
-
-        std::thread::sleep(std::time::Duration::from_secs(1));
-
         vm.input_cmd(Cmd::CYC(1)).expect("无法输入指令");
-
-        std::thread::sleep(std::time::Duration::from_secs(1));
-
         vm.input_cmd(Cmd::NSE(nse_task!(<A --> B>?)))
             .expect("无法输入指令");
-
-        std::thread::sleep(std::time::Duration::from_secs(1));
-
         vm.input_cmd(Cmd::CYC(1)).expect("无法输入指令");
 
-        std::thread::sleep(std::time::Duration::from_secs(3));
-
-        // 尝试截获其所有输出
-        // * 🚩【2024-04-13 16:10:27】目前经由Julia侧`flush(stdout)`，仍然无法捕获
-        // * 有输出`[ Info: Answer: <A-->B>. %1.0;0.9%`，但无法被程序捕获为文本
-        while let Ok(Some(output)) = vm.try_fetch_output() {
-            dbg!(output);
-        }
-
-        std::thread::sleep(std::time::Duration::from_secs(2));
+        // 等待回答
+        await_fetch_until(&mut vm, |o, _| match o {
+            Output::ANSWER {
+                narsese: Some(narsese),
+                ..
+            } => *narsese == nse!(<A --> B>. %1.0;0.9%),
+            _ => false,
+        });
 
         // 终止虚拟机运行时
         vm.terminate().expect("无法终止虚拟机");
@@ -4,45 +4,59 @@
 //! * ✨字符串→NAVM输出
 //!
 //! TODO: 🚧自OpenNARS复制而来，一些地方需要特别适配
+//!
+//! ## 输出样例
+//!
+//! * `[ Info: Answer: <A-->B>. %1.0;0.9%`
 
 use anyhow::Result;
+use narsese::{conversion::string::impl_lexical::format_instances::FORMAT_ASCII, lexical::Narsese};
 use navm::{
     cmd::Cmd,
     output::{Operation, Output},
 };
 
-use crate::runtimes::TranslateError;
+use crate::{runtimes::TranslateError, support::print_notice};
+
+/// Julia日志（`@info`）的行前缀
+/// * 📄`[ Info: Answer: <A-->B>. %1.0;0.9%`
+const JULIA_INFO_PREFIX: &str = "[ Info:";
 
 /// OpenJunars的「输入转译」函数
 /// * 🎯用于将统一的「NAVM指令」转译为「OpenJunars Shell输入」
-pub fn input_translate(cmd: Cmd) -> Result<String> {
+pub fn input_translate(cmd: Cmd) -> Result<Option<String>> {
     let content = match cmd {
         // 直接使用「末尾」，此时将自动格式化任务（可兼容「空预算」的形式）
         Cmd::NSE(..) => cmd.tail(),
         // CYC指令：运行指定周期数
         Cmd::CYC(n) => format!(":c {n}"),
         // 注释 ⇒ 忽略 | ❓【2024-04-02 22:43:05】可能需要打印，但这样却没法统一IO（到处print的习惯不好）
-        Cmd::REM { .. } => String::new(),
+        Cmd::REM { .. } => return Ok(None),
         // 其它类型
+        // * 📌包括VOL指令：该CIN无音量控制
         // * 📌【2024-03-24 22:57:18】基本足够支持
         // ! 🚩【2024-03-27 22:42:56】不使用[`anyhow!`]：打印时会带上一大堆调用堆栈
         _ => return Err(TranslateError::UnsupportedInput(cmd).into()),
     };
     // 转译
-    Ok(content)
+    Ok(Some(content))
 }
 
 /// OpenJunars的「输出转译」函数
 /// * 🎯用于将OpenJunars Shell的输出（字符串）转译为「NAVM输出」
 /// * 🚩直接根据选取的「头部」进行匹配
+/// * 🚩先去掉Julia日志的前缀`[ Info:`，再分割「头部」
 pub fn output_translate(content: String) -> Result<Output> {
-    // 根据冒号分隔一次，然后得到「头部」
-    let head = content.split_once(':').unwrap_or(("", "")).0.to_lowercase();
+    // 去掉日志前缀
+    let line = content.trim();
+    let line = line.strip_prefix(JULIA_INFO_PREFIX).unwrap_or(line).trim();
+    // 根据冒号分隔一次，然后得到「头部」与「尾部」
+    let (head, tail) = line.split_once(':').unwrap_or(("", ""));
+    let head = head.to_lowercase();
     // 根据「头部」生成输出
     let output = match &*head {
         "answer" => Output::ANSWER {
-            // TODO: 有待捕获转译
-            narsese: None,
+            narsese: try_parse_narsese(tail),
             content_raw: content,
         },
         "out" => Output::OUT {
@@ -68,3 +82,37 @@ pub fn output_translate(content: String) -> Result<Output> {
     // 返回
     Ok(output)
 }
+
+/// 从输出的「尾部」中解析Narsese
+/// * 📄`<A-->B>. %1.0;0.9%`
+/// * 🚩解析失败⇒打印错误信息，返回[`None`]
+fn try_parse_narsese(tail: &str) -> Option<Narsese> {
+    FORMAT_ASCII
+        .parse(tail.trim())
+        .inspect_err(|e| {
+            print_notice(
+                "WARN",
+                &format!("【OpenJunars】在解析Narsese「{tail}」时出现错误：{e}"),
+            )
+        })
+        .ok()
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use narsese::lexical_nse as nse;
+
+    /// 测试/Julia日志形式的回答
+    #[test]
+    fn test_answer() {
+        let output = output_translate("[ Info: Answer: <A-->B>. %1.0;0.9%\n".into()).unwrap();
+        match output {
+            Output::ANSWER { narsese, .. } => {
+                assert_eq!(narsese, Some(nse!(<A --> B>. %1.0;0.9%)))
+            }
+            _ => panic!("非预期的输出：{output:?}"),
+        }
+    }
+}
@@ -4,10 +4,13 @@
 //!   * ✨不同启动器可以启动到相同运行时
 //! * 🚩通过[`CommandGeneratorJava`]管理启动参数
 
-use super::{input_translate, output_translate};
+use super::{continues_previous, input_translate, output_translate, READY_MARKER};
 use crate::{
-    cin_implements::common::CommandGeneratorJava,
-    runtimes::{CommandGenerator, CommandVm, CommandVmRuntime},
+    cin_implements::common::{check_java_version, CommandGeneratorJava},
+    runtimes::{
+        CommandGenerator, CommandVm, CommandVmRuntime, DEFAULT_COALESCE_TIMEOUT,
+        DEFAULT_STARTUP_TIMEOUT,
+    },
 };
 use anyhow::Result;
 use nar_dev_utils::manipulate;
@@ -15,12 +18,17 @@ use navm::{
     cmd::Cmd,
     vm::{VmLauncher, VmRuntime},
 };
-use std::path::PathBuf;
+use std::{path::PathBuf, process::Command};
+
+/// OpenNARS所需的最低Java主版本
+/// * 📌OpenNARS 3.0.4需要Java 8及以上
+pub const OPENNARS_MIN_JAVA_VERSION: u32 = 8;
 
 /// OpenNARS Shell启动器
 /// * 🎯配置OpenNARS专有的东西
 /// * 🚩基于jar文件启动OpenNARS Shell
-#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// * 🚩启动前检查Java版本：Java缺失或版本过低⇒报错
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct OpenNARS {
     /// Java [`Command`]生成器
     /// * 📌必须有（包含jar文件路径）
@@ -28,6 +36,26 @@ pub struct OpenNARS {
     /// NARS的初始音量
     /// * 🚩可能没有：此时不会输入指令
     initial_volume: Option<usize>,
+    /// 所需的最低Java主版本
+    /// * 📜默认值：[`OPENNARS_MIN_JAVA_VERSION`]
+    /// * 📌`0`⇒不检查
+    min_java_version: u32,
+    /// 是否合并Java异常堆栈
+    /// * 📜默认值：`false`
+    coalesce_lines: bool,
+}
+
+/// 默认配置
+/// * 🚩手动实现：默认检查Java版本
+impl Default for OpenNARS {
+    fn default() -> Self {
+        Self {
+            command_generator: CommandGeneratorJava::default(),
+            initial_volume: None,
+            min_java_version: OPENNARS_MIN_JAVA_VERSION,
+            coalesce_lines: false,
+        }
+    }
 }
 
 impl OpenNARS {
@@ -40,31 +68,77 @@ impl OpenNARS {
             ..Default::default()
         }
     }
+
+    /// 配置/Java可执行文件路径
+    pub fn java_path(&mut self, java_path: impl Into<PathBuf>) {
+        self.command_generator.java_path(java_path);
+    }
+
+    /// 配置/附加的JVM参数
+    /// * 🚩位于`-jar`之前
+    pub fn jvm_args(&mut self, args: impl IntoIterator<Item = impl Into<String>>) {
+        self.command_generator.jvm_args(args);
+    }
+
+    /// 配置/最大内存（MB）
+    /// * 📄`1024`⇒`-Xmx1024m`
+    pub fn max_memory_mb(&mut self, size: u32) {
+        self.command_generator.max_heap_size(size as usize);
+    }
+
+    /// 配置/所需的最低Java主版本
+    /// * 📌`0`⇒不检查
+    pub fn min_java_version(&mut self, version: u32) {
+        self.min_java_version = version;
+    }
+
+    /// 配置/是否合并Java异常堆栈
+    /// * 🎯异常堆栈作为一个输出
+    pub fn coalesce_lines(&mut self, enabled: bool) {
+        self.coalesce_lines = enabled;
+    }
+
+    /// 生成启动命令
+    /// * 🎯不启动即可检查命令
+    pub fn command(&self) -> Command {
+        // * 🚩细致的Java参数配置，都外包给[`CommandGeneratorJava`]
+        self.command_generator.generate_command()
+    }
 }
 
 /// 启动到「命令行运行时」
 impl VmLauncher for OpenNARS {
     type Runtime = CommandVmRuntime;
     fn launch(self) -> Result<CommandVmRuntime> {
+        // 检查Java版本 | Java缺失或版本过低⇒及早报错
+        if self.min_java_version > 0 {
+            check_java_version(self.command_generator.java_program(), self.min_java_version)?;
+        }
+
         // 构造指令
-        // * 🚩细致的Java参数配置，都外包给[`CommandGeneratorJava`]
-        let command_java = self.command_generator.generate_command();
+        let command_java = self.command();
 
-        // 构造并启动虚拟机
+        // 构造虚拟机
         let mut vm = manipulate!(
             CommandVm::from(command_java)
             // * 🚩固定的「输入输出转译器」
             => .input_translator(input_translate)
             => .output_translator(output_translate)
-        )
+            // * 🚩启动检查：启动即失败⇒报错并附带其输出；等待该CIN的就绪标志
+            => .startup_check(DEFAULT_STARTUP_TIMEOUT, READY_MARKER)
+        );
+        // * 🚩选择性合并Java异常堆栈
+        if self.coalesce_lines {
+            vm.coalesce_lines(continues_previous, DEFAULT_COALESCE_TIMEOUT);
+        }
         // 🔥启动
-        .launch()?;
+        let mut vm = vm.launch()?;
 
         // 设置初始音量
         if let Some(volume) = self.initial_volume {
             // 输入指令，并在执行错误时打印信息
             if let Err(e) = vm.input_cmd(Cmd::VOL(volume)) {
-                println!("无法设置初始音量「{volume}」：{e}");
+                eprintln!("无法设置初始音量「{volume}」：{e}");
             }
         };
 
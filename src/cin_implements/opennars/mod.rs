@@ -2,6 +2,9 @@
 //! * 🚩只提供「一行启动」的功能封装
 //!   * 🎯无需自行配置「输入输出转译器」
 
+use crate::runtimes::Capabilities;
+use navm::output::Output;
+
 // 转译器
 util::mod_and_pub_use! {
     // 转译器
@@ -12,6 +15,21 @@ util::mod_and_pub_use! {
     dialect
 }
 
+/// 所支持的功能
+/// * ⚠️不支持`REG`：操作符由OpenNARS内置
+pub const CAPABILITIES: Capabilities = Capabilities {
+    registration: false,
+    ..Capabilities::ALL
+};
+
+/// 「步进完成」的回执判断
+/// * 📌OpenNARS步进时不打印任何提示⇒等待CIN安静下来
+pub const CYC_ACK_MATCHER: Option<fn(&Output) -> bool> = None;
+
+/// 启动检查的「就绪标志」
+/// * 📄`Got relative path for loading the config: ./config/defaultConfig.xml`
+pub const READY_MARKER: Option<fn(&str) -> bool> = Some(is_ready);
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
@@ -23,6 +41,7 @@ mod tests {
         },
         tests::cin_paths::OPENNARS as JAR_PATH_OPENNARS,
     };
+    use nar_dev_utils::manipulate;
     use navm::vm::VmLauncher;
 
     /// 工具/启动OpenNARS，获得虚拟机运行时
@@ -52,4 +71,60 @@ mod tests {
         // 使用通用测试逻辑
         test_simple_answer(vm)
     }
+
+    /// 测试/启动命令
+    /// * 🎯JVM参数位于`-jar`之前；不实际启动
+    #[test]
+    fn command() {
+        let command = OpenNARS::new("opennars.jar").command();
+        assert_eq!(command.get_program(), "java");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["-jar", "opennars.jar"]
+        );
+
+        let command = manipulate!(
+            OpenNARS::new("opennars.jar")
+            => .java_path("/usr/lib/jvm/java-8/bin/java")
+            => .max_memory_mb(2048)
+            => .jvm_args(["-Dfile.encoding=UTF-8"])
+        )
+        .command();
+        assert_eq!(command.get_program(), "/usr/lib/jvm/java-8/bin/java");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["-Xmx2048m", "-Dfile.encoding=UTF-8", "-jar", "opennars.jar"]
+        );
+    }
+
+    /// 测试/Java不存在⇒启动前即报错
+    #[test]
+    fn launch_java_not_found() {
+        let e = manipulate!(
+            OpenNARS::new("opennars.jar")
+            => .java_path("./babelnar-not-exist-java")
+        )
+        .launch()
+        .err()
+        .expect("Java不存在，却启动成功");
+        assert!(e.to_string().contains("找不到Java"), "{e}");
+    }
+
+    /// 测试/启动失败
+    /// * 🎯jar文件不存在⇒启动时即报错，错误信息附带Java的退出状态
+    ///   * 📌Java自身的报错（`Unable to access jarfile`）经继承的标准错误直接显示
+    #[test]
+    fn launch_missing_jar() {
+        let jar_path = std::env::temp_dir().join(format!(
+            "babelnar_nonexistent_opennars_{}.jar",
+            std::process::id()
+        ));
+        let e = OpenNARS::new(&jar_path)
+            .launch()
+            .err()
+            .expect("jar文件不存在，却启动成功");
+        let message = e.to_string();
+        assert!(message.contains("CIN启动失败"), "{message}");
+        assert!(message.contains("exited with code 1"), "{message}");
+    }
 }
@@ -18,19 +18,34 @@
 //! * `EXE: $0.11;0.33;0.57$ ^left([{SELF}, a, b, (/,^left,a,b,_)])=null`
 
 use super::dialect::parse as parse_dialect_opennars;
-use crate::runtimes::TranslateError;
-use anyhow::Result;
+use crate::{
+    cin_implements::common::{
+        normalize_volume, parse_narsese_chain, parse_seed, translate_param, ParamSupport,
+        PARAM_CMD_HEAD, PARSER_OPENNARS, SEED_CMD_HEAD,
+    },
+    runtimes::TranslateError,
+};
+use anyhow::{anyhow, Result};
 use narsese::lexical::{Narsese, Term};
 use navm::{
     cmd::Cmd,
-    output::{Operation, Output},
+    output::{type_names::ANTICIPATE, Operation, Output},
 };
-use regex::Regex;
 use util::ResultBoost;
 
+/// 输出类型名称 @ CONFIRM
+/// * 📌OpenNARS对「预期被证实」的输出
+/// * ⚠️[`navm::output::type_names`]中暂无此常量
+pub const CONFIRM: &str = "CONFIRM";
+
+/// OpenNARS Shell可调整的参数
+/// * 📄`*decisionthreshold=0.6`、`*babblingops=5`
+pub const PARAM_SUPPORT: ParamSupport =
+    ParamSupport::Whitelist(&["decisionthreshold", "babblingops"]);
+
 /// OpenNARS的「输入转译」函数
 /// * 🎯用于将统一的「NAVM指令」转译为「OpenNARS Shell输入」
-pub fn input_translate(cmd: Cmd) -> Result<String> {
+pub fn input_translate(cmd: Cmd) -> Result<Option<String>> {
     let content = match cmd {
         // 直接使用「末尾」，此时将自动格式化任务（可兼容「空预算」的形式）
         Cmd::NSE(..) => cmd.tail(),
@@ -38,9 +53,20 @@ pub fn input_translate(cmd: Cmd) -> Result<String> {
         // ! OpenNARS Shell是自动步进的
         Cmd::CYC(n) => n.to_string(),
         // VOL指令：调整音量
-        Cmd::VOL(n) => format!("*volume={n}"),
+        // * 📌标度与NAVM相同，仅截断超出范围的值
+        Cmd::VOL(n) => format!("*volume={}", normalize_volume(n)),
+        // RES指令：重置记忆区
+        Cmd::RES { .. } => "*reset".into(),
+        // SEED指令：设置随机种子
+        Cmd::Custom { head, tail } if head == SEED_CMD_HEAD => {
+            format!("*seed={}", parse_seed(&tail)?)
+        }
+        // SET指令：设置参数
+        Cmd::Custom { head, tail } if head == PARAM_CMD_HEAD => {
+            translate_param(&tail, PARAM_SUPPORT, |key, value| format!("*{key}={value}"))?
+        }
         // 注释 ⇒ 忽略 | ❓【2024-04-02 22:43:05】可能需要打印，但这样却没法统一IO（到处print的习惯不好）
-        Cmd::REM { .. } => String::new(),
+        Cmd::REM { .. } => return Ok(None),
         // 退出码
         Cmd::EXI { .. } => "*exit".into(),
         // 其它类型
@@ -49,21 +75,29 @@ pub fn input_translate(cmd: Cmd) -> Result<String> {
         _ => return Err(TranslateError::UnsupportedInput(cmd).into()),
     };
     // 转译
-    Ok(content)
+    Ok(Some(content))
 }
 
 /// OpenNARS的「输出转译」函数
 /// * 🎯用于将OpenNARS Shell的输出（字符串）转译为「NAVM输出」
 /// * 🚩直接根据选取的「头部」进行匹配
 pub fn output_translate(content_raw: String) -> Result<Output> {
+    // Java异常（可能连同合并进来的堆栈）⇒错误
+    if is_java_exception(&content_raw) {
+        return Ok(Output::ERROR {
+            description: content_raw,
+        });
+    }
     // 根据冒号分隔一次，然后得到「头部」
     let (head, tail) = content_raw.split_once(':').unwrap_or(("", &content_raw));
     let tail = tail.trim();
+    // 解析失败⇒附带原始输出
+    let failed = |e| TranslateError::output_parse_failed("OpenNARS", &content_raw, e);
     // 根据「头部」生成输出
     let output = match &*head.to_uppercase() {
         "IN" => Output::IN {
             // 先提取其中的Narsese | ⚠️借用了`content_raw`
-            narsese: parse_narsese_opennars(head, tail)?,
+            narsese: parse_narsese_opennars(head, tail).map_err(failed)?,
             // 然后传入整个内容
             content: content_raw,
         },
@@ -71,31 +105,38 @@ pub fn output_translate(content_raw: String) -> Result<Output> {
             // 返回
             Output::OUT {
                 // 先提取其中的Narsese | ⚠️借用了`content_raw`
-                narsese: parse_narsese_opennars(head, tail)?,
+                narsese: parse_narsese_opennars(head, tail).map_err(failed)?,
                 // 然后传入整个内容
                 content_raw,
             }
         }
         "ANSWER" => Output::ANSWER {
             // 先提取其中的Narsese | ⚠️借用了`content_raw`
-            narsese: parse_narsese_opennars(head, tail)?,
+            narsese: parse_narsese_opennars(head, tail).map_err(failed)?,
             // 然后传入整个内容
             content_raw,
         },
         "EXE" => Output::EXE {
-            operation: parse_operation_opennars(tail.trim_start()),
+            operation: parse_operation_opennars(tail.trim_start()).map_err(failed)?,
             content_raw,
         },
         // ! 🚩【2024-03-27 19:40:37】现在将ANTICIPATE降级到`UNCLASSIFIED`
+        // * 🚩与ONA保持一致：使用NAVM中的类型名常量，并遵循「严格模式」解析Narsese
         "ANTICIPATE" => Output::UNCLASSIFIED {
             // 指定的头部
-            r#type: "ANTICIPATE".to_string(),
+            r#type: ANTICIPATE.to_string(),
             // 先提取其中的Narsese | ⚠️借用了`content_raw`
-            narsese: try_parse_narsese(tail)
-                .ok_or_run(|e| println!("【{head}】在解析Narsese「{tail}」时出现错误：{e}")),
+            narsese: parse_narsese_opennars(head, tail).map_err(failed)?,
             // 然后传入整个内容
             content: content_raw,
         },
+        // * 📄`CONFIRM: <{SELF} --> [SAFE]><{SELF} --> [SAFE]>`
+        // * 🚩仅解析其中的第一个陈述
+        "CONFIRM" => Output::UNCLASSIFIED {
+            r#type: CONFIRM.to_string(),
+            narsese: parse_narsese_opennars(head, first_statement(tail)).map_err(failed)?,
+            content: content_raw,
+        },
         "ERR" | "ERROR" => Output::ERROR {
             description: content_raw,
         },
@@ -119,15 +160,56 @@ pub fn output_translate(content_raw: String) -> Result<Output> {
     Ok(output)
 }
 
+/// OpenNARS输出的「续行」判断
+/// * 🎯将Java异常堆栈合并为一个输出
+///   * 📄`\tat org.opennars.main.Nar.cycle(Nar.java:42)`
+///   * 📄`Caused by: java.lang.IllegalStateException`、`\t... 3 more`
+/// * 🚩以空白开头的非空行、`at `开头的行、`Caused by:`开头的行
+///   * 📌不看此前的输出块
+pub fn continues_previous(_block: &str, line: &str) -> bool {
+    let trimmed = line.trim_start();
+    !trimmed.is_empty()
+        && (trimmed.len() < line.len()
+            || trimmed.starts_with("at ")
+            || trimmed.starts_with("Caused by:"))
+}
+
+/// OpenNARS的「就绪」判断
+/// * 🎯启动检查：跳过JVM自身的提示，等到OpenNARS开始加载
+/// * 🚩OpenNARS在构造推理器时打印所加载的配置文件
+pub fn is_ready(line: &str) -> bool {
+    line.contains("loading the config")
+}
+
+/// 判断输出是否为Java异常
+/// * 📄`Exception in thread "main" java.lang.NullPointerException`
+/// * 📄`java.lang.IllegalStateException: message`
+/// * 🚩只看第一行：含`.`的类名，以`Exception`或`Error`结尾
+fn is_java_exception(content_raw: &str) -> bool {
+    let first_line = content_raw.lines().next().unwrap_or_default();
+    if first_line.starts_with("Exception in thread") {
+        return true;
+    }
+    let class_name = first_line.split(':').next().unwrap_or_default().trim_end();
+    class_name.contains('.')
+        && !class_name.contains(char::is_whitespace)
+        && (class_name.ends_with("Exception") || class_name.ends_with("Error"))
+}
+
 /// （OpenNARS）从原始输出中解析Narsese
 /// * 🎯用于结合`#[cfg]`控制「严格模式」
 ///   * 🚩生产环境下「Narsese解析出错」仅打印错误信息
 #[cfg(not(test))]
 pub fn parse_narsese_opennars(head: &str, tail: &str) -> Result<Option<Narsese>> {
+    use crate::support::print_notice;
     use util::ResultBoost;
     // ! ↓下方会转换为None
-    Ok(try_parse_narsese(tail)
-        .ok_or_run(|e| println!("【{head}】在解析Narsese「{tail}」时出现错误：{e}")))
+    Ok(try_parse_narsese(tail).ok_or_run(|e| {
+        print_notice(
+            "WARN",
+            &format!("【{head}】在解析Narsese「{tail}」时出现错误：{e}"),
+        )
+    }))
 }
 
 /// （OpenNARS）从原始输出中解析Narsese
@@ -141,40 +223,83 @@ pub fn parse_narsese_opennars(_: &str, tail: &str) -> Result<Option<Narsese>> {
 
 /// 在OpenNARS输出中解析出「NARS操作」
 /// * 📄`$0.11;0.33;0.57$ ^left([{SELF}, a, b, (/,^left,a,b,_)])=null`
-/// * 🚩【2024-03-29 22:45:11】目前能提取出其中的预算值，但实际上暂且不需要
-pub fn parse_operation_opennars(tail: &str) -> Operation {
-    // * 构建正则表达式（仅一次编译）
-    let r = Regex::new(r"(\$[0-9.;]+\$)\s*\^(\w+)\(\[(.*)\]\)=").unwrap();
-
-    // 构建返回值（参数）
-    let mut params = vec![];
-
-    // 提取输出中的字符串
-    let c = r.captures(tail);
-    // let budget;
-    let operator_name;
-    let params_str;
-    if let Some(c) = c {
-        // 提取
-        // budget = &c[1];
-        operator_name = c[2].to_string();
-        params_str = &c[3];
-        // 尝试解析
-        for param in params_str.split(", ") {
-            match parse_term_from_operation(param) {
-                Ok(term) => params.push(term),
-                // ? 【2024-03-27 22:29:43】↓是否要将其整合到一个日志系统中去
-                Err(e) => println!("【EXE】在解析Narsese时出现错误：{e}"),
-            }
+/// * 📄`$0.25;0.90;0.95$ (^left,{SELF},P)=null`
+/// * 🚩先去掉可选的预算值，再统一为「复合词项操作」的形式，交由OpenNARS方言解析
+///   * 📌末尾的`=null`等返回值会被忽略
+/// * 📌解析失败⇒上抛错误，由调用方封装为[`TranslateError::OutputParseFailed`]
+pub fn parse_operation_opennars(tail: &str) -> Result<Operation> {
+    // 去掉预算值 | 预算值中只有数字、`.`与`;`
+    let tail = match tail.strip_prefix('$').and_then(|rest| rest.split_once('$')) {
+        Some((_, rest)) => rest.trim_start(),
+        None => tail,
+    };
+    // 转换 & 解析
+    operation_compound_str(tail)
+        .ok_or_else(|| anyhow!("无效的操作格式：{tail:?}"))
+        .and_then(|compound| parse_term_from_operation(&compound))
+        .and_then(operation_from_term)
+}
+
+/// 将OpenNARS的操作输出统一为「复合词项操作」的字符串
+/// * 📄旧格式：`^left([{SELF}, a])=null` => `(^left, {SELF}, a)`
+/// * 📄新格式：`(^left,{SELF},a)=null` => `(^left,{SELF},a)`
+/// * 🚩按圆括号配对截取，忽略其后的返回值
+/// * 📌格式无效⇒[`None`]
+fn operation_compound_str(tail: &str) -> Option<String> {
+    match tail.starts_with('^') {
+        // 旧格式：操作名 + 括号内的参数列表
+        true => {
+            let (name, rest) = tail.split_at(tail.find('(')?);
+            let args = &rest[1..matching_paren(rest)?];
+            // 参数列表可能被方括号包裹
+            let args = match args.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+                Some(args) => args,
+                None => args,
+            };
+            Some(match args.trim().is_empty() {
+                true => format!("({name})"),
+                false => format!("({name}, {args})"),
+            })
+        }
+        // 新格式：整个复合词项
+        false if tail.starts_with('(') => Some(tail[..=matching_paren(tail)?].to_string()),
+        false => None,
+    }
+}
+
+/// 找到与开头圆括号配对的右括号位置
+/// * ⚠️字符串须以`(`开头
+fn matching_paren(s: &str) -> Option<usize> {
+    let mut depth = 0_usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth {
+                1 => return Some(i),
+                _ => depth = depth.saturating_sub(1),
+            },
+            _ => {}
         }
-    } else {
-        operator_name = String::new();
     }
+    None
+}
 
-    // 返回
-    Operation {
-        operator_name,
-        params,
+/// 从「操作」词项中提取出NAVM操作
+/// * 📄`<(*, {SELF}, a) --> ^left>` => `left({SELF}, a)`
+fn operation_from_term(term: Term) -> Result<Operation> {
+    match term {
+        Term::Statement {
+            subject, predicate, ..
+        } => match (*subject, *predicate) {
+            (Term::Compound { terms, .. }, Term::Atom { name, .. }) => Ok(Operation {
+                operator_name: name,
+                params: terms,
+            }),
+            (subject, predicate) => Err(anyhow!(
+                "无效的操作词项：主词{subject:?}，谓词{predicate:?}"
+            )),
+        },
+        term => Err(anyhow!("无效的操作词项：{term:?}")),
     }
 }
 
@@ -188,16 +313,51 @@ fn parse_term_from_operation(term_str: &str) -> Result<Term> {
         .transform_err(TranslateError::error_anyhow)
 }
 
+/// 截取字符串开头的第一个陈述
+/// * 🎯应对OpenNARS`CONFIRM`输出中「两个陈述首尾相连」的情况
+///   * 📄`<{SELF} --> [SAFE]><{SELF} --> [SAFE]>` => `<{SELF} --> [SAFE]>`
+/// * 🚩按尖括号配对截取，跳过系词中的尖括号
+/// * 📌不以`<`开头、或尖括号不配对时，返回原字符串
+fn first_statement(tail: &str) -> &str {
+    const COPULAS: [&str; 12] = [
+        "-->", "<->", "==>", "<=>", "=/>", "=\\>", "=|>", "</>", "<|>", "{--", "--]", "{-]",
+    ];
+    if !tail.starts_with('<') {
+        return tail;
+    }
+    let mut depth = 0_usize;
+    let mut i = 0;
+    while i < tail.len() {
+        let rest = &tail[i..];
+        if let Some(copula) = COPULAS.iter().find(|copula| rest.starts_with(*copula)) {
+            i += copula.len();
+            continue;
+        }
+        let c = rest.chars().next().unwrap();
+        match c {
+            '<' => depth += 1,
+            '>' => match depth {
+                1 => return &tail[..i + 1],
+                _ => depth = depth.saturating_sub(1),
+            },
+            _ => {}
+        }
+        i += c.len_utf8();
+    }
+    tail
+}
+
 /// 切分尾部字符串，并（尝试）从中解析出Narsese
 /// * 🎯对OpenNARS中的「时间戳/证据基」做切分
 ///   * 📄`<{SELF} --> [satisfied]>! :|: %1.00;0.90% {1269408|1269408 : (-8058943780727144183,628)}`
 ///   * 🚩现在无需考虑：[`pest`]会自动忽略无关前缀
 ///   * ❌在「无证据基case」如`ANTICIPATE: <{powerup_bad_x} --> [seen]>`中报错：把`{`截掉了
 /// * 📌此中`tail`已做好行切分
+/// * 🚩沿「解析链」解析：OpenNARS方言失败⇒尝试CommonNarsese与其它CIN的方言
 fn try_parse_narsese(tail: &str) -> Result<Narsese> {
     // 提取并解析Narsese字符
     // 提取解析结果
-    let narsese = parse_dialect_opennars(tail);
+    let narsese = parse_narsese_chain(tail, PARSER_OPENNARS).map(|(narsese, _)| narsese);
     match narsese {
         // 解析成功⇒提取 & 返回
         Ok(narsese) => Ok(narsese),
@@ -205,3 +365,154 @@ fn try_parse_narsese(tail: &str) -> Result<Narsese> {
         Err(err) => Err(TranslateError::from(err).into()),
     }
 }
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use narsese::{api::GetTerm, conversion::string::impl_lexical::format_instances::FORMAT_ASCII};
+
+    /// 测试/截取第一个陈述
+    #[test]
+    fn test_first_statement() {
+        for (inp, expected) in [
+            (
+                "<{SELF} --> [SAFE]><{SELF} --> [SAFE]>",
+                "<{SELF} --> [SAFE]>",
+            ),
+            (
+                "<<a --> b> ==> <c <-> d>><a --> b>",
+                "<<a --> b> ==> <c <-> d>>",
+            ),
+            ("<a --> b>", "<a --> b>"),
+            ("(^left, {SELF})", "(^left, {SELF})"),
+        ] {
+            assert_eq!(first_statement(inp), expected);
+        }
+    }
+
+    /// 测试/ANTICIPATE与CONFIRM
+    /// * 📄输出源自OpenNARS 3.0.4的命令行交互
+    #[test]
+    fn test_anticipate_confirm() {
+        test(
+            "ANTICIPATE: <{SELF} --> [SAFE]>",
+            ANTICIPATE,
+            "<{SELF}-->[SAFE]>",
+        );
+        test(
+            "ANTICIPATE: <{powerup_bad_x} --> [seen]>",
+            ANTICIPATE,
+            "<{powerup_bad_x}-->[seen]>",
+        );
+        test(
+            "CONFIRM: <{SELF} --> [SAFE]><{SELF} --> [SAFE]>",
+            CONFIRM,
+            "<{SELF}-->[SAFE]>",
+        );
+
+        fn test(line: &str, expected_type: &str, expected_term: &str) {
+            let output = output_translate(line.into()).expect("输出转译失败");
+            match output {
+                Output::UNCLASSIFIED {
+                    r#type,
+                    narsese: Some(narsese),
+                    ..
+                } => {
+                    assert_eq!(r#type, expected_type);
+                    let term = FORMAT_ASCII.format(narsese.get_term());
+                    assert_eq!(term.replace(' ', ""), expected_term);
+                }
+                _ => panic!("未能转译为带Narsese的「{expected_type}」：{output:?}"),
+            }
+        }
+    }
+
+    /// 测试/EXE操作
+    /// * 📄输出源自OpenNARS 3.0.4的命令行交互
+    #[test]
+    fn test_exe() {
+        test(
+            "EXE: $1.00;0.99;1.00$ ^left([{SELF}])=null",
+            "left",
+            &["{SELF}"],
+        );
+        test(
+            "EXE: $0.33;0.50;0.95$ ^left([{SELF}, (*,P1,P2)])=null",
+            "left",
+            &["{SELF}", "(*,P1,P2)"],
+        );
+        test(
+            "EXE: $0.11;0.33;0.57$ ^left([{SELF}, a, b, (/,^left,a,b,_)])=null",
+            "left",
+            &["{SELF}", "a", "b", "(/,^left,a,b,_)"],
+        );
+        test(
+            "EXE: $0.25;0.90;0.95$ ^say([{SELF}, <a --> b>])=true",
+            "say",
+            &["{SELF}", "<a-->b>"],
+        );
+        test("EXE: ^deactivate([])=null", "deactivate", &[]);
+        // 新格式：复合词项
+        test(
+            "EXE: $0.25;0.90;0.95$ (^left,{SELF},(*,P1,P2))=null",
+            "left",
+            &["{SELF}", "(*,P1,P2)"],
+        );
+        test("EXE: (^right,{SELF})", "right", &["{SELF}"]);
+
+        fn test(line: &str, operator_name: &str, params: &[&str]) {
+            let output = output_translate(line.into()).expect("输出转译失败");
+            let Output::EXE { operation, .. } = output else {
+                panic!("未能转译为EXE：{output:?}");
+            };
+            assert_eq!(operation.operator_name, operator_name, "{line}");
+            let formatted = operation
+                .params
+                .iter()
+                .map(|term| FORMAT_ASCII.format(term).replace(' ', ""))
+                .collect::<Vec<_>>();
+            assert_eq!(formatted, params, "{line}");
+        }
+    }
+
+    /// 测试/无效的EXE操作
+    /// * 🚩上抛「输出解析失败」，并附带原始输出
+    #[test]
+    fn test_exe_invalid() {
+        for line in ["EXE: ???", "EXE: $0.11;0.33;0.57$ ^left([{SELF}"] {
+            let e = output_translate(line.into()).expect_err("无效操作应解析失败");
+            assert!(
+                matches!(
+                    e.downcast_ref::<TranslateError>(),
+                    Some(TranslateError::OutputParseFailed { cin: "OpenNARS", raw_line, .. })
+                        if raw_line == line
+                ),
+                "{e}"
+            );
+        }
+    }
+
+    /// 测试/混合方言的输出：ONA风格的乘积也能解析
+    #[test]
+    fn test_output_mixed_dialect() {
+        for line in [
+            "OUT: <(*, {SELF}) --> ^left>. %1.00;0.90% {-1 : (-7995324758518856376,0)}",
+            "OUT: <(* {SELF}) --> ^left>. %1.00;0.90% {-1 : (-7995324758518856376,0)}",
+            "Answer: <(* {SELF}) --> ^left>. %1.00;0.90%",
+        ] {
+            let output = output_translate(line.into()).expect("输出转译失败");
+            let narsese = output.get_narsese().expect("未解析出Narsese");
+            let term = FORMAT_ASCII.format(narsese.get_term());
+            assert_eq!(term.replace(' ', ""), "<(*,{SELF})-->^left>", "{line}");
+        }
+    }
+
+    /// 测试/无法解析的Narsese
+    /// * 🚩测试环境中会上抛错误
+    #[test]
+    fn test_anticipate_strict() {
+        assert!(output_translate("ANTICIPATE: ???".into()).is_err());
+        assert!(output_translate("CONFIRM: ???".into()).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! 转译器预设
+//! * 🎯按名称（含别名）查找各CIN的「输入输出转译器」
+//!   * 📄启动配置中的`translators: "ona"`
+//! * 🎯新增CIN时，只需在此登记，而无需改动BabelNAR CLI
+//! * 📌名称匹配：不区分大小写，与「正式名称」或任一「别名」相同即可
+
+use super::{cxin_js, nars_python, native, ona, openjunars, opennars, pynars};
+use crate::runtimes::{Capabilities, IoTranslators};
+use anyhow::Result;
+use navm::{cmd::Cmd, output::Output};
+use std::sync::OnceLock;
+
+/// 转译器预设
+/// * 📌结构：`(正式名称, 别名, 输入转译器, 输出转译器, 所支持的功能, 输出的续行判断, 步进完成的回执判断, 启动时的就绪标志)`
+///   * 🚩续行判断为[`None`]⇒不合并多行输出
+///   * 🚩回执判断为[`None`]⇒CIN不报告「步进完成」
+///   * 🚩就绪标志为[`None`]⇒任意一行输出即视作就绪
+pub type TranslatorPreset = (
+    &'static str,
+    &'static [&'static str],
+    fn(Cmd) -> Result<Option<String>>,
+    fn(String) -> Result<Output>,
+    Capabilities,
+    Option<fn(&str, &str) -> bool>,
+    Option<fn(&Output) -> bool>,
+    Option<fn(&str) -> bool>,
+);
+
+/// 所有转译器预设
+/// * 🚩静态存储：按正式名称列出时保持此处顺序
+pub const TRANSLATOR_PRESETS: &[TranslatorPreset] = &[
+    (
+        "Native",
+        &["json"],
+        native::input_translate,
+        native::output_translate,
+        native::CAPABILITIES,
+        None,
+        native::CYC_ACK_MATCHER,
+        native::READY_MARKER,
+    ),
+    (
+        "OpenNARS",
+        &[],
+        opennars::input_translate,
+        opennars::output_translate,
+        opennars::CAPABILITIES,
+        Some(opennars::continues_previous),
+        opennars::CYC_ACK_MATCHER,
+        opennars::READY_MARKER,
+    ),
+    (
+        "ONA",
+        &[],
+        ona::input_translate,
+        ona::output_translate,
+        ona::CAPABILITIES,
+        Some(ona::continues_previous),
+        ona::CYC_ACK_MATCHER,
+        ona::READY_MARKER,
+    ),
+    (
+        "NARS-Python",
+        &["NARSPython", "nars_python"],
+        nars_python::input_translate,
+        nars_python::output_translate,
+        nars_python::CAPABILITIES,
+        None,
+        nars_python::CYC_ACK_MATCHER,
+        nars_python::READY_MARKER,
+    ),
+    (
+        "PyNARS",
+        &[],
+        pynars::input_translate,
+        pynars::output_translate,
+        pynars::CAPABILITIES,
+        None,
+        pynars::CYC_ACK_MATCHER,
+        pynars::READY_MARKER,
+    ),
+    (
+        "OpenJunars",
+        &[],
+        openjunars::input_translate,
+        openjunars::output_translate,
+        openjunars::CAPABILITIES,
+        None,
+        openjunars::CYC_ACK_MATCHER,
+        openjunars::READY_MARKER,
+    ),
+    (
+        "CXinJS",
+        &["cxin-js", "cxin_js"],
+        cxin_js::input_translate,
+        cxin_js::output_translate,
+        cxin_js::CAPABILITIES,
+        None,
+        cxin_js::CYC_ACK_MATCHER,
+        cxin_js::READY_MARKER,
+    ),
+];
+
+/// 按名称（含别名）查找转译器预设
+/// * 🚩不区分大小写
+pub fn find_translator_preset(name: &str) -> Option<&'static TranslatorPreset> {
+    TRANSLATOR_PRESETS.iter().find(|(canonical, aliases, ..)| {
+        canonical.eq_ignore_ascii_case(name)
+            || aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name))
+    })
+}
+
+/// 按名称（含别名）获取「输入输出转译器」
+/// * 🚩未找到⇒[`None`]
+pub fn translator_preset(name: &str) -> Option<IoTranslators> {
+    find_translator_preset(name)
+        .map(|(_, _, input, output, ..)| IoTranslators::new(*input, *output))
+}
+
+/// 按名称（含别名）获取「所支持的功能」
+/// * 🚩未找到⇒[`None`]
+pub fn preset_capabilities(name: &str) -> Option<Capabilities> {
+    find_translator_preset(name).map(|(.., capabilities, _, _, _)| *capabilities)
+}
+
+/// 按名称（含别名）获取「输出的续行判断」
+/// * 🎯合并跨越多行的输出：见[`CommandVm::coalesce_lines`](crate::runtimes::CommandVm::coalesce_lines)
+/// * 🚩未找到、或该CIN无需合并⇒[`None`]
+pub fn preset_line_continuation(name: &str) -> Option<fn(&str, &str) -> bool> {
+    find_translator_preset(name).and_then(|(.., continuation, _, _)| *continuation)
+}
+
+/// 按名称（含别名）获取「步进完成的回执判断」
+/// * 🎯启动配置中的`cycAwait`：置入`CYC`后等到CIN真正跑完
+/// * 🚩未找到、或该CIN不报告「步进完成」⇒[`None`]
+pub fn preset_cyc_ack_matcher(name: &str) -> Option<fn(&Output) -> bool> {
+    find_translator_preset(name).and_then(|(.., matcher, _)| *matcher)
+}
+
+/// 按名称（含别名）获取「启动时的就绪标志」
+/// * 🎯启动检查：见[`CommandVm::startup_check`](crate::runtimes::CommandVm::startup_check)
+/// * 🚩未找到、或该CIN没有固定的就绪提示⇒[`None`]
+pub fn preset_ready_marker(name: &str) -> Option<fn(&str) -> bool> {
+    find_translator_preset(name).and_then(|(.., ready_marker)| *ready_marker)
+}
+
+/// 所有转译器预设的正式名称
+/// * 🎯错误信息中的「可用转译器」，以及CLI的`--list-translators`
+pub fn available_presets() -> &'static [&'static str] {
+    static NAMES: OnceLock<Vec<&'static str>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        TRANSLATOR_PRESETS
+            .iter()
+            .map(|(canonical, ..)| *canonical)
+            .collect()
+    })
+}
+
+/// 所有可被识别的转译器名称：正式名称在前，别名在后
+/// * 🎯启动前检查配置中的转译器名
+pub fn preset_names() -> impl Iterator<Item = &'static str> {
+    let canonical = TRANSLATOR_PRESETS.iter().map(|(canonical, ..)| *canonical);
+    let aliases = TRANSLATOR_PRESETS
+        .iter()
+        .flat_map(|(_, aliases, ..)| aliases.iter().copied());
+    canonical.chain(aliases)
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/配置中使用过的名称均能找到
+    /// * 📄含示例配置中的大小写变体
+    #[test]
+    fn test_preset_aliases() {
+        let expected = [
+            ("native", "Native"),
+            ("Native", "Native"),
+            ("json", "Native"),
+            ("opennars", "OpenNARS"),
+            ("OpenNARS", "OpenNARS"),
+            ("ona", "ONA"),
+            ("ONA", "ONA"),
+            ("pynars", "PyNARS"),
+            ("PyNARS", "PyNARS"),
+            ("nars-python", "NARS-Python"),
+            ("narsPython", "NARS-Python"),
+            ("nars_python", "NARS-Python"),
+            ("openjunars", "OpenJunars"),
+            ("OpenJunars", "OpenJunars"),
+            ("cxinJS", "CXinJS"),
+            ("cxin-js", "CXinJS"),
+            ("cxin_js", "CXinJS"),
+        ];
+        for (name, canonical) in expected {
+            let preset = find_translator_preset(name).unwrap_or_else(|| panic!("未找到「{name}」"));
+            assert_eq!(preset.0, canonical, "{name}");
+            assert!(translator_preset(name).is_some(), "{name}");
+        }
+        // 所支持的功能
+        assert_eq!(preset_capabilities("ona"), Some(Capabilities::ALL));
+        assert_eq!(
+            preset_capabilities("pynars").map(|c| c.operations),
+            Some(false)
+        );
+        assert_eq!(preset_capabilities("onna"), None);
+        // 输出的续行判断
+        let continuation = preset_line_continuation("opennars").expect("OpenNARS应合并异常堆栈");
+        assert!(continuation(
+            "java.lang.NullPointerException",
+            "\tat org.opennars.main.Nar.run(Nar.java:42)"
+        ));
+        assert!(preset_line_continuation("pynars").is_none());
+        // 步进完成的回执判断
+        let is_ack = preset_cyc_ack_matcher("ona").expect("ONA应报告步进完成");
+        assert!(is_ack(&Output::OTHER {
+            content: "done with 5 additional inference steps.".into()
+        }));
+        assert!(preset_cyc_ack_matcher("opennars").is_none());
+    }
+
+    /// 测试/未知名称⇒未找到
+    #[test]
+    fn test_preset_not_found() {
+        for name in ["", "onna", "nars", "opennars3"] {
+            assert!(translator_preset(name).is_none(), "{name}");
+        }
+    }
+
+    /// 测试/正式名称的列表
+    #[test]
+    fn test_available_presets() {
+        assert_eq!(
+            available_presets(),
+            [
+                "Native",
+                "OpenNARS",
+                "ONA",
+                "NARS-Python",
+                "PyNARS",
+                "OpenJunars",
+                "CXinJS"
+            ]
+        );
+        // 所有名称各不相同
+        let mut names = preset_names().map(str::to_lowercase).collect::<Vec<_>>();
+        let len = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), len);
+    }
+}
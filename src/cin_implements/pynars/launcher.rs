@@ -5,15 +5,15 @@
 //!   * ✨不同启动器可以启动到相同运行时
 //! * 🚩通过[`CommandGeneratorPython`]管理启动参数
 
-use super::{input_translate, output_translate};
+use super::{input_translate, output_translate, READY_MARKER};
 use crate::{
     cin_implements::common::CommandGeneratorPython,
-    runtimes::{CommandGenerator, CommandVm, CommandVmRuntime},
+    runtimes::{CommandGenerator, CommandVm, CommandVmRuntime, DEFAULT_STARTUP_TIMEOUT},
 };
 use anyhow::Result;
 use nar_dev_utils::manipulate;
 use navm::vm::VmLauncher;
-use std::path::PathBuf;
+use std::{path::PathBuf, process::Command};
 
 /// PyNARS运行时启动器
 /// * 🎯配置PyNARS专有的东西
@@ -21,6 +21,7 @@ use std::path::PathBuf;
 /// * 📌没有内置的「音量」配置
 ///   * ⚠️该配置参考的是PyNARS的`ConsolePlus`模块
 /// * 🚩【2024-03-25 08:55:07】基于Python模块文件启动PyNARS Shell
+/// * 🚩启动前检查解释器能否导入PyNARS：解释器缺失或环境中未安装⇒报错
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct PyNARS {
     /// 命令生成器
@@ -33,14 +34,40 @@ impl PyNARS {
             command_generator: CommandGeneratorPython::new(root_path, module_path),
         }
     }
+
+    /// 配置/Python解释器路径
+    /// * 📌优先于虚拟环境
+    pub fn python_path(&mut self, python_path: impl Into<PathBuf>) {
+        self.command_generator.python_path(python_path);
+    }
+
+    /// 配置/虚拟环境目录
+    /// * 📄venv、conda环境
+    pub fn venv(&mut self, venv: impl Into<PathBuf>) {
+        self.command_generator.venv(venv);
+    }
+
+    /// 配置/附加的`PYTHONPATH`
+    pub fn extra_paths(&mut self, paths: impl IntoIterator<Item = impl Into<PathBuf>>) {
+        self.command_generator.extra_paths(paths);
+    }
+
+    /// 生成启动命令
+    /// * 🎯不启动即可检查命令
+    pub fn command(&self) -> Command {
+        self.command_generator.generate_command()
+    }
 }
 
 /// 启动到「命令行运行时」
 impl VmLauncher for PyNARS {
     type Runtime = CommandVmRuntime;
     fn launch(self) -> Result<CommandVmRuntime> {
+        // 检查环境 | 解释器缺失或无法导入PyNARS⇒及早报错
+        self.command_generator.check_import()?;
+
         // 构造指令
-        let command = self.command_generator.generate_command();
+        let command = self.command();
 
         // 构造并启动虚拟机
         manipulate!(
@@ -48,6 +75,8 @@ impl VmLauncher for PyNARS {
             // * 🚩固定的「输入输出转译器」
             => .input_translator(input_translate)
             => .output_translator(output_translate)
+            // * 🚩启动检查：启动即失败⇒报错并附带其输出；等待该CIN的就绪标志
+            => .startup_check(DEFAULT_STARTUP_TIMEOUT, READY_MARKER)
         )
         // 🔥启动
         .launch()
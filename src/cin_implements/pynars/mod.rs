@@ -6,6 +6,9 @@
 //!   * 📄主要形式：子进程结束后打印错误堆栈，输出`OSError: [Errno 22] Invalid argument`
 //!   * ❗无法被Rust捕获，可能是Python运行时的问题（输出未链接到管道）
 
+use crate::runtimes::Capabilities;
+use navm::output::Output;
+
 // 转译器
 util::mod_and_pub_use! {
     // 转译器
@@ -14,6 +17,21 @@ util::mod_and_pub_use! {
     launcher
 }
 
+/// 所支持的功能
+/// * ⚠️NAL-8的操作支持尚不完善
+pub const CAPABILITIES: Capabilities = Capabilities {
+    operations: false,
+    ..Capabilities::ALL
+};
+
+/// 「步进完成」的回执判断
+/// * ⚠️`Run 5 cycles.`在步进之前打印，不能作为回执⇒等待CIN安静下来
+pub const CYC_ACK_MATCHER: Option<fn(&Output) -> bool> = None;
+
+/// 启动检查的「就绪标志」
+/// * 🚩没有固定的就绪提示⇒任意一行输出即视作就绪
+pub const READY_MARKER: Option<fn(&str) -> bool> = None;
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
@@ -25,6 +43,7 @@ mod tests {
         },
         tests::cin_paths::{PYNARS_MODULE, PYNARS_ROOT},
     };
+    use nar_dev_utils::manipulate;
     use navm::vm::VmLauncher;
 
     /// 工具/启动PyNARS，获得虚拟机运行时
@@ -55,4 +74,35 @@ mod tests {
         // 使用通用测试逻辑
         test_simple_answer(vm)
     }
+
+    /// 测试/生成命令
+    #[test]
+    fn command() {
+        let command = PyNARS::new("root", "pynars.Console").command();
+        assert_eq!(command.get_program(), "python");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["-m", "pynars.Console"]
+        );
+
+        let command = manipulate!(
+            PyNARS::new("root", "pynars.Console")
+            => .python_path("/opt/conda/envs/nars/bin/python")
+        )
+        .command();
+        assert_eq!(command.get_program(), "/opt/conda/envs/nars/bin/python");
+    }
+
+    /// 测试/解释器不存在⇒启动前即报错
+    #[test]
+    fn launch_python_not_found() {
+        let e = manipulate!(
+            PyNARS::new(".", "pynars.Console")
+            => .venv("./babelnar-not-exist-venv")
+        )
+        .launch()
+        .err()
+        .expect("解释器不存在，却启动成功");
+        assert!(e.to_string().contains("找不到Python解释器"), "{e}");
+    }
 }
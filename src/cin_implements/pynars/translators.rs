@@ -12,7 +12,10 @@
 //! * 📄`\u{1b}[48;2;134;10;10m 0.98 \u{1b}[49m\u{1b}[48;2;10;124;10m 0.90 \u{1b}[49m\u{1b}[48;2;10;10;125m 0.90 \u{1b}[49m\u{1b}[32mANSWER:\u{1b}[39m<A-->C>. %1.000;0.810%\r\n`
 //! * 📄`    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0)-->^op> = $0.022;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}\r\n`
 
-use crate::runtimes::TranslateError;
+use crate::{
+    cin_implements::common::{normalize_volume, translate_param, ParamSupport, PARAM_CMD_HEAD},
+    runtimes::{TranslateError, NO_ANSWER},
+};
 use anyhow::{anyhow, Result};
 use narsese::{
     api::ExtractTerms,
@@ -27,11 +30,16 @@ use navm::{
     output::{Operation, Output},
 };
 use regex::{Captures, Regex};
+use std::sync::LazyLock;
 use util::{pipe, JoinTo};
 
+/// PyNARS可调整的参数
+/// * 🚩原样转发：参数名由PyNARS的`/config`自行检查
+pub const PARAM_SUPPORT: ParamSupport = ParamSupport::Passthrough;
+
 /// PyNARS的「输入转译」函数
 /// * 🎯用于将统一的「NAVM指令」转译为「PyNARS输入」
-pub fn input_translate(cmd: Cmd) -> Result<String> {
+pub fn input_translate(cmd: Cmd) -> Result<Option<String>> {
     let content = match cmd {
         // 直接使用「末尾」，此时将自动格式化任务（可兼容「空预算」的形式）
         Cmd::NSE(..) => cmd.tail(),
@@ -39,21 +47,33 @@ pub fn input_translate(cmd: Cmd) -> Result<String> {
         // * 📌PyNARS需要手动指定步进数
         Cmd::CYC(n) => n.to_string(),
         // VOL指令：调整音量
+        // * 📌标度与NAVM相同，仅截断超出范围的值
+        // ! ⚠️该指令仅适用于`ConsolePlus`
+        Cmd::VOL(n) => format!("/volume {}", normalize_volume(n)),
+        // RES指令：重置记忆区
         // ! ⚠️该指令仅适用于`ConsolePlus`
-        Cmd::VOL(n) => format!("/volume {n}"),
+        Cmd::RES { .. } => "/reset".into(),
         // REG指令：注册操作符
         // * 📄Input: /register name
         //  * `Operator ^name was successfully registered without code`
         Cmd::REG { name, .. } => format!("/register {name}"),
+        // SET指令：设置参数
+        // ! ⚠️该指令仅适用于`ConsolePlus`
+        Cmd::Custom { head, tail } if head == PARAM_CMD_HEAD => {
+            translate_param(&tail, PARAM_SUPPORT, |key, value| {
+                format!("/config {key} {value}")
+            })?
+        }
         // 注释 ⇒ 忽略 | ❓【2024-04-02 22:43:05】可能需要打印，但这样却没法统一IO（到处print的习惯不好）
-        Cmd::REM { .. } => String::new(),
+        Cmd::REM { .. } => return Ok(None),
         // 其它类型
+        // * 📌包括SEED指令：PyNARS不支持设置随机种子
         // * 📌【2024-03-24 22:57:18】基本足够支持
         // ! 🚩【2024-03-27 22:42:56】不使用[`anyhow!`]：打印时会带上一大堆调用堆栈
         _ => return Err(TranslateError::UnsupportedInput(cmd).into()),
     };
     // 转译
-    Ok(content)
+    Ok(Some(content))
 }
 
 /// 预处理
@@ -61,11 +81,11 @@ pub fn input_translate(cmd: Cmd) -> Result<String> {
 ///   * 📄ANSI转义序列
 pub fn preprocess(s: &str) -> String {
     // ! `\e` => `\u{1b}`
-    let re = Regex::new(r"\u{1b}\[[0-9;]*m").unwrap();
+    static RE_ANSI: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\u{1b}\[[0-9;]*m").unwrap());
     pipe! {
         s
         // 去掉ANSI转义序列
-        => [re.replace_all](_, "")
+        => [RE_ANSI.replace_all](_, "")
         // 去掉前后缀空白符
         => .trim()
         // 转换为字符串
@@ -78,11 +98,11 @@ pub fn preprocess(s: &str) -> String {
 /// * 🎯尝试获取「类型」字符串，若无则返回[`None`]
 fn try_get_output_type(preprocessed: &str) -> Option<String> {
     // 截获输出类型，忽略前边的预算值
-    let re2 = Regex::new(r"[0-9\s|]*(\w+)\s*:").unwrap();
+    static RE_HEAD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[0-9\s|]*(\w+)\s*:").unwrap());
     pipe! {
         preprocessed
         // 捕获
-        => [re2.captures](_)
+        => [RE_HEAD.captures](_)
         // 转换为字符串
         => .map(|captures|captures[1].into())
     }
@@ -94,9 +114,10 @@ fn try_get_output_type(preprocessed: &str) -> Option<String> {
 fn try_get_narsese(preprocessed: &str) -> Result<Narsese> {
     // 删去无用内容，并替换成预算值 | 三个预算+一个头
     // * 🚩【2024-03-30 00:15:24】开头必须是`[^0-9.]*`，以避免吃掉预算值「`0.98`⇒`8`」💥
-    let re_trim_and_budget =
-        Regex::new(r"^[^0-9.]*([0-9.]+)[\s|]+([0-9.]+)[\s|]+([0-9.]+)[\s|]+\w+\s*:\s*").unwrap();
-    let trimmed = re_trim_and_budget
+    static RE_TRIM_AND_BUDGET: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^[^0-9.]*([0-9.]+)[\s|]+([0-9.]+)[\s|]+([0-9.]+)[\s|]+\w+\s*:\s*").unwrap()
+    });
+    let trimmed = RE_TRIM_AND_BUDGET
         // 删去其中无用的内容，并重整其中的预算值 //
         .replace(preprocessed, |s: &Captures| {
             // 创建「预算值」字串
@@ -127,9 +148,11 @@ fn try_get_narsese(preprocessed: &str) -> Result<Narsese> {
 ///   * 🚩生产环境中「解析失败」仅提示（然后返回[`None`]）
 #[cfg(not(test))]
 fn get_narsese(preprocessed: &str) -> Result<Option<Narsese>> {
+    use crate::support::print_notice;
     use util::ResultBoost;
     // * 🚩解析失败⇒提示⇒返回[`None`]
-    Ok(try_get_narsese(preprocessed).ok_or_run(|e| println!("尝试解析Narsese错误：{e}")))
+    Ok(try_get_narsese(preprocessed)
+        .ok_or_run(|e| print_notice("WARN", &format!("尝试解析Narsese错误：{e}"))))
 }
 
 /// 获取输出中的Narsese
@@ -144,37 +167,76 @@ fn get_narsese(preprocessed: &str) -> Result<Option<Narsese>> {
 
 /// 尝试获取输出中的「Narsese操作」
 /// * 🎯截获PyNARS中的「EXE」部分
+/// * 🚩先截取「操作词项」，再交由[`try_parse_operation_term`]解析
+///   * 📌`EXE`行：截取`=`之前的词项
+///   * 📌`executed:`行：截取`task=`之后的目标词项
 /// * 📄`    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0)-->^op> = $0.022;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}\r\n`
 /// * 📄"executed: arguments=<Terms: (0, 1, 2, 3)>, task=$0.000;0.339;0.950$ <(*, 0, 1, 2, 3)-->^op>! %1.000;0.853% {None: 7, 4, 5}, memory=<Memory: #items=21, #buckets=100>. the \"task\" will be returned\r\n"
 /// * 📄`    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0, 1, 2, 3)-->^op> = $0.000;0.339;0.950$ <(*, 0, 1, 2, 3)-->^op>! %1.000;0.853% {None: 7, 4, 5}\r\n`
 /// * 📄"executed: arguments=<Terms: (0)>, task=$0.220;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}, memory=<Memory: #items=8, #buckets=100>. the \"task\" will be returned\r\n"
 fn try_get_operation(preprocessed: &str) -> Result<Operation> {
-    let re_operation = Regex::new(r"EXE\s*:\s*(.+) = ").unwrap();
-    let op = re_operation
+    static RE_EXE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"EXE\s*:\s*(.+?)\s*=\s*\$").unwrap());
+    static RE_EXECUTED: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"executed:.*task=\s*(?:\$[0-9.;]*\$)?\s*(.+?)\s*!").unwrap());
+    let op = RE_EXE
         .captures(preprocessed)
-        .unwrap()
-        .get(1)
-        .unwrap()
+        .or_else(|| RE_EXECUTED.captures(preprocessed))
+        .and_then(|captures| captures.get(1))
+        .ok_or_else(|| anyhow!("未找到「操作词项」：{preprocessed:?}"))?
         .as_str();
-    let op = FORMAT_ASCII.parse(op).unwrap().try_into_term().unwrap();
+    try_parse_operation_term(op)
+}
+
+/// 从「操作词项」中解析「Narsese操作」
+/// * 🎯兼容PyNARS中操作的两种表示
+///   * 📄继承式：`<(*, 0, 1)-->^op>`、`<{SELF}-->^op>`
+///   * 📄元组式：`(^op, 0, 1)`、`(^op)`
+/// * 📌操作名不带尖号
+fn try_parse_operation_term(op: &str) -> Result<Operation> {
+    // 元组式 | ⚠️`(^op, ...)`无法直接被解析为词项：转换成「乘积」再解析参数
+    static RE_TUPLE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^\(\s*\^([^\s,()]+)\s*(?:,\s*(.*))?\)$").unwrap());
+    if let Some(captures) = RE_TUPLE.captures(op.trim()) {
+        let operator_name = captures[1].to_string();
+        let params = match captures.get(2) {
+            Some(params) => {
+                let product = FORMAT_ASCII
+                    .parse(&format!("(*, {})", params.as_str()))?
+                    .try_into_term()?;
+                product.extract_terms_to_vec()
+            }
+            None => vec![],
+        };
+        return Ok(Operation {
+            operator_name,
+            params,
+        });
+    }
+
+    // 继承式
+    let op = FORMAT_ASCII.parse(op)?.try_into_term()?;
     match op {
         // * 📄`<(*, 0)-->^op>`
         Term::Statement {
             subject, predicate, ..
         } => {
-            // 从主词提取操作参数
-            let params = subject.extract_terms_to_vec();
             // 从谓词提取操作名
             let operator_name = match *predicate {
-                Term::Atom { name, .. } => name,
-                _ => return Err(anyhow!("陈述谓词不是原子词项")),
+                Term::Atom { prefix, name } if prefix == "^" => name,
+                _ => return Err(anyhow!("陈述谓词不是操作符：{predicate:?}")),
+            };
+            // 从主词提取操作参数 | 乘积⇒各组分，其它⇒仅含其自身
+            let params = match *subject {
+                Term::Compound { connecter, terms } if connecter == "*" => terms,
+                subject => vec![subject],
             };
             Ok(Operation {
                 operator_name,
                 params,
             })
         }
-        _ => Err(anyhow::anyhow!("无效的「操作表示」词项：{op:?}")),
+        _ => Err(anyhow!("无效的「操作表示」词项：{op:?}")),
     }
 }
 
@@ -187,7 +249,7 @@ fn try_get_operation(preprocessed: &str) -> Result<Operation> {
 fn get_operation(preprocessed: &str) -> Operation {
     // * 🚩解析失败仅提示，然后返回「空操作」
     try_get_operation(preprocessed).unwrap_or_else(|e| {
-        println!("尝试从「{preprocessed}」解析Narsese操作错误：{e}");
+        eprintln!("尝试从「{preprocessed}」解析Narsese操作错误：{e}");
         // 空操作
         Operation {
             operator_name: "".into(),
@@ -237,26 +299,35 @@ pub fn output_translate(content: String) -> Result<Output> {
         Some(s) => s,
         None => "",
     };
+    // 解析失败⇒附带原始输出
+    let failed = |e| TranslateError::output_parse_failed("PyNARS", &content, e);
     // 根据「头部」生成输出
     let output = match head {
+        // 「无回答」⇒与ONA一致的「无回答」输出
+        "answer" if is_empty_answer(&content) => Output::UNCLASSIFIED {
+            r#type: NO_ANSWER.into(),
+            content,
+            narsese: None,
+        },
         "answer" => Output::ANSWER {
-            narsese: get_narsese(&content)?,
+            narsese: get_narsese(&content).map_err(failed)?,
             content_raw: content,
         },
         "achieved" => Output::ACHIEVED {
-            narsese: get_narsese(&content)?,
+            narsese: get_narsese(&content).map_err(failed)?,
             content_raw: content,
         },
         "out" => Output::OUT {
-            narsese: get_narsese(&content)?,
+            narsese: get_narsese(&content).map_err(failed)?,
             content_raw: content,
         },
         "input" | "in" => Output::IN {
-            narsese: get_narsese(&content)?,
+            narsese: get_narsese(&content).map_err(failed)?,
             content,
         },
         "info" => Output::INFO { message: content },
-        "exe" => Output::EXE {
+        // * 🚩`executed:`为PyNARS操作符被执行时的打印
+        "exe" | "executed" => Output::EXE {
             operation: get_operation(&content),
             content_raw: content,
         },
@@ -269,6 +340,17 @@ pub fn output_translate(content: String) -> Result<Output> {
     Ok(output)
 }
 
+/// 判断是否为「无回答」的提示
+/// * 🚩`ANSWER:`之后没有内容，或仅有`None`
+/// * 📄`ANSWER:`、`ANSWER: None.`
+fn is_empty_answer(preprocessed: &str) -> bool {
+    let Some((_, tail)) = preprocessed.split_once(':') else {
+        return false;
+    };
+    let tail = tail.trim().trim_end_matches('.');
+    tail.is_empty() || tail.eq_ignore_ascii_case("none")
+}
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
@@ -297,12 +379,93 @@ mod tests {
     /// 测试/尝试获取操作
     #[test]
     fn test_try_get_operation() {
-        test("    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0)-->^op> = $0.022;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}\r\n");
-        test("    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0, 1, 2, 3)-->^op> = $0.000;0.339;0.950$ <(*, 0, 1, 2, 3)-->^op>! %1.000;0.853% {None: 7, 4, 5}\r\n");
-        fn test(inp: &str) {
+        // 继承式
+        test(
+            "    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0)-->^op> = $0.022;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}\r\n",
+            "op",
+            &["0"],
+        );
+        test(
+            "    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0, 1, 2, 3)-->^op> = $0.000;0.339;0.950$ <(*, 0, 1, 2, 3)-->^op>! %1.000;0.853% {None: 7, 4, 5}\r\n",
+            "op",
+            &["0", "1", "2", "3"],
+        );
+        test(
+            "EXE   :<(*, {SELF}, [good])-->^f> = $0.026;0.450;0.905$ <(*, {SELF}, [good])-->^f>! %1.000;0.810% {None: 2, 1}",
+            "f",
+            &["{SELF}", "[good]"],
+        );
+        test(
+            "EXE   :<{SELF}-->^left> = $0.016;0.225;0.562$ <{SELF}-->^left>! %1.000;0.125% {None: 3, 1, 2}",
+            "left",
+            &["{SELF}"],
+        );
+        // 元组式
+        test(
+            "EXE   :(^left, {SELF}, x) = $0.016;0.225;0.562$ (^left, {SELF}, x)! %1.000;0.125% {None: 3, 1, 2}",
+            "left",
+            &["{SELF}", "x"],
+        );
+        test(
+            "EXE   :(^deactivate) = $0.016;0.225;0.562$ (^deactivate)! %1.000;0.125% {None: 3, 1, 2}",
+            "deactivate",
+            &[],
+        );
+        // 操作符被执行时的打印
+        test(
+            "executed: arguments=<Terms: (0, 1, 2, 3)>, task=$0.000;0.339;0.950$ <(*, 0, 1, 2, 3)-->^op>! %1.000;0.853% {None: 7, 4, 5}, memory=<Memory: #items=21, #buckets=100>. the \"task\" will be returned\r\n",
+            "op",
+            &["0", "1", "2", "3"],
+        );
+        test(
+            "executed: arguments=<Terms: (0)>, task=$0.220;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}, memory=<Memory: #items=8, #buckets=100>. the \"task\" will be returned\r\n",
+            "op",
+            &["0"],
+        );
+        fn test(inp: &str, operator_name: &str, params: &[&str]) {
             let inp = preprocess(inp);
             let op = try_get_operation(&inp).unwrap();
-            dbg!(op);
+            assert_eq!(op.operator_name, operator_name);
+            let op_params = op
+                .params
+                .iter()
+                .map(|term| FORMAT_ASCII.format(term))
+                .collect::<Vec<_>>();
+            assert_eq!(op_params, params);
+        }
+    }
+
+    /// 测试/输出转译：操作
+    /// * 🎯`EXE`与`executed:`行均被转译为[`Output::EXE`]
+    #[test]
+    fn test_output_translate_exe() {
+        for line in [
+            "    \u{1b}[49m    \u{1b}[49m    \u{1b}[49m\u{1b}[32mEXE   :\u{1b}[39m<(*, 0)-->^op> = $0.022;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}\r\n",
+            "executed: arguments=<Terms: (0)>, task=$0.220;0.232;0.926$ <(*, 0)-->^op>! :\\: %1.000;0.853% {7: 2, 0, 1}, memory=<Memory: #items=8, #buckets=100>. the \"task\" will be returned\r\n",
+        ] {
+            let output = output_translate(line.into()).unwrap();
+            match output {
+                Output::EXE { operation, .. } => {
+                    assert_eq!(operation.operator_name, "op");
+                    assert_eq!(operation.params.len(), 1);
+                }
+                _ => panic!("未转译为EXE：{output:?}"),
+            }
+        }
+    }
+
+    /// 测试/无回答
+    #[test]
+    fn test_no_answer() {
+        for line in ["0.98  0.90  0.90 ANSWER:", "ANSWER: None."] {
+            let output = output_translate(line.into()).expect("输出解析失败");
+            assert!(output.is_type(NO_ANSWER), "{output:?}");
         }
+        let output = output_translate("0.98  0.90  0.90 ANSWER:<A-->C>. %1.000;0.810%".into())
+            .expect("输出解析失败");
+        assert!(
+            output.is_type(navm::output::type_names::ANSWER),
+            "{output:?}"
+        );
     }
 }
@@ -1,8 +1,8 @@
 //! 存储各CIN的「路径构建器」
 //! * ✅OpenNARS
 //! * ✅ONA
-//! TODO: PyNARS
-//! TODO: CXinNARS
+//! * ✅PyNARS
+//! * ✅CXinNARS
 //! * 🚩【2024-03-31 01:27:09】其它接口完成度不高的CIN，暂时弃了
 
 use crate::cli_support::cin_search::{
@@ -16,6 +16,10 @@ util::mods! {
     use pub path_builder_opennars;
     // ONA
     use pub path_builder_ona;
+    // PyNARS
+    use pub path_builder_pynars;
+    // CXinNARS.js
+    use pub path_builder_cxin_js;
 }
 
 // 深入条件
@@ -72,3 +76,77 @@ mod tests {
         ));
     }
 }
+
+/// 测试用工具
+/// * 🎯在临时目录中构造「目录树」，供各「路径构建器」测试
+#[cfg(test)]
+pub(super) mod test_utils {
+    use crate::cli_support::cin_search::path_walker::PathWalker;
+    use anyhow::Result;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    /// 临时目录树
+    /// * 🚩创建于系统临时目录下，销毁时删除
+    pub struct TempTree {
+        root: PathBuf,
+    }
+
+    impl TempTree {
+        /// 构造函数
+        /// * 🚩按相对路径创建空文件（及其所在文件夹）
+        pub fn new(name: &str, files: &[&str]) -> Self {
+            let root = std::env::temp_dir().join(format!("babelnar_{name}_{}", std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            for file in files {
+                let path = root.join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, "").unwrap();
+            }
+            Self { root }
+        }
+
+        /// 根目录
+        pub fn path(&self) -> &Path {
+            &self.root
+        }
+
+        /// 遍历整个目录树的「路径遍历器」
+        /// * 🚩深度优先，按名称排序
+        pub fn walker(&self) -> PathWalkerList {
+            fn walk(path: &Path, paths: &mut Vec<PathBuf>) {
+                let mut entries = fs::read_dir(path)
+                    .unwrap()
+                    .map(|entry| entry.unwrap().path())
+                    .collect::<Vec<_>>();
+                entries.sort();
+                for entry in entries {
+                    paths.push(entry.clone());
+                    if entry.is_dir() {
+                        walk(&entry, paths);
+                    }
+                }
+            }
+            let mut paths = vec![];
+            walk(&self.root, &mut paths);
+            PathWalkerList(paths.into_iter())
+        }
+    }
+
+    impl Drop for TempTree {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    /// 依次给出既定路径的「路径遍历器」
+    pub struct PathWalkerList(std::vec::IntoIter<PathBuf>);
+
+    impl PathWalker for PathWalkerList {
+        fn next_path(&mut self) -> Result<Option<PathBuf>> {
+            Ok(self.0.next())
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! 用于CXinNARS.js的路径构建器
+
+use crate::{
+    cin_implements::cxin_js::CXinJS,
+    cli_support::cin_search::{name_match::name_match_only_contains, path_builder::CinPathBuilder},
+    runtimes::CommandVmRuntime,
+};
+use nar_dev_utils::{if_return, OptionBoost};
+use std::path::Path;
+
+/// CXinNARS.js路径构建器
+/// * 🎯判别路径并构建CXinNARS.js启动器
+/// * 📄`cxin-nars-shell.js`
+pub struct PathBuilderCXinJS;
+
+impl PathBuilderCXinJS {
+    // 匹配文件名（不含扩展名）
+    // * 🚩仅「含于」匹配：避免`n.js`这类短名称被误匹配
+    #[inline(always)]
+    fn match_name(name: &str) -> usize {
+        // 二者综合，只需「二者均不满足⇒0」即可
+        name_match_only_contains("cxin", name) + name_match_only_contains("nars", name)
+    }
+
+    /// 检查文件匹配度
+    fn valid_js(path: &Path) -> usize {
+        // ! 不一定是本地存在的文件
+        if_return! { path.extension().is_none_or(|ex| ex != "js") => 0 }
+        // 名称匹配`cxin`、`nars`
+        path.file_stem().map_unwrap_or(
+            |name_os| name_os.to_str().map_unwrap_or(Self::match_name, 0),
+            0,
+        )
+    }
+}
+
+impl CinPathBuilder for PathBuilderCXinJS {
+    type Runtime = CommandVmRuntime;
+    type Launcher = CXinJS;
+
+    fn match_path(&self, path: &Path) -> usize {
+        // ! 与本地文件系统有关
+        // 不是本地的文件⇒0
+        if_return! { !path.is_file() => 0 }
+        // 否则⇒查看js匹配度
+        Self::valid_js(path)
+    }
+
+    fn construct_from_path(&self, path: &Path) -> Self::Launcher {
+        CXinJS::new(path)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_support::cin_search::impls_path_builder::{
+        launchers_from_walker_sorted, test_utils::TempTree,
+    };
+    use nar_dev_utils::{f_parallel, fail_tests};
+
+    /// 工具/测试单个路径
+    fn test_matched(path: &str) {
+        let path = Path::new(path);
+        assert!(dbg!(PathBuilderCXinJS::valid_js(path)) > 0);
+    }
+
+    /// 测试/名称匹配
+    #[test]
+    fn test_match() {
+        f_parallel![
+            test_matched;
+            "../cxin-nars-shell.js";
+            "../CXinNARS.js";
+            "cxin.js";
+            "nars.js";
+        ];
+    }
+
+    fail_tests! {
+        无效扩展名 test_matched("../cxin-nars-shell.ts");
+        无效名称 test_matched("../n.js");
+    }
+
+    /// 测试/目录树中的搜索与排序
+    #[test]
+    fn test_walker() {
+        let tree = TempTree::new(
+            "path_builder_cxin_js",
+            &[
+                "cxin-nars/dist/cxin-nars-shell.js",
+                "cxin-nars/dist/utils.js",
+                "cxin-nars/README.md",
+                "web/nars.js",
+            ],
+        );
+        let root = tree.path();
+
+        let launchers = launchers_from_walker_sorted(tree.walker(), PathBuilderCXinJS);
+        assert_eq!(
+            launchers,
+            [
+                CXinJS::new(root.join("cxin-nars/dist/cxin-nars-shell.js")),
+                CXinJS::new(root.join("web/nars.js")),
+            ]
+        );
+    }
+}
@@ -27,7 +27,7 @@ impl PathBuilderONA {
     /// 检查文件匹配度
     fn valid_exe(path: &Path) -> usize {
         // ! 不一定是本地存在的文件
-        if_return! { !path.extension().is_some_and(|ex| ex == "exe") => 0}
+        if_return! { path.extension().is_none_or(|ex| ex != "exe") => 0}
         // 名称匹配`ona`
         path.file_name().map_unwrap_or(
             |name_os| name_os.to_str().map_unwrap_or(Self::match_name, 0),
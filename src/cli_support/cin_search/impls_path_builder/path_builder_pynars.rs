@@ -0,0 +1,131 @@
+//! 用于PyNARS的路径构建器
+
+use crate::{
+    cin_implements::pynars::PyNARS,
+    cli_support::cin_search::{name_match::name_match, path_builder::CinPathBuilder},
+    runtimes::CommandVmRuntime,
+};
+use nar_dev_utils::{if_return, OptionBoost};
+use std::path::Path;
+
+/// PyNARS的Python包名
+const PACKAGE_PYNARS: &str = "pynars";
+
+/// PyNARS的默认启动模块
+/// * 📌对接`ConsolePlus`：支持`/volume`等指令
+const MODULE_CONSOLE_PLUS: &str = "ConsolePlus";
+
+/// PyNARS的后备启动模块
+const MODULE_CONSOLE: &str = "Console";
+
+/// PyNARS路径构建器
+/// * 🎯判别路径并构建PyNARS启动器
+/// * 📌所匹配的是「包含`pynars`包的根目录」，而非`pynars`包本身
+///   * 📄`PyNARS-dev/` ⇒ `python -m pynars.ConsolePlus` @ `PyNARS-dev/`
+pub struct PathBuilderPyNARS;
+
+impl PathBuilderPyNARS {
+    // 匹配文件夹名
+    #[inline(always)]
+    fn match_name(name: &str) -> usize {
+        name_match("pynars", name)
+    }
+
+    /// 检查根目录匹配度
+    /// * 🚩必须包含`pynars`包（含`__init__.py`）
+    /// * 🚩包含`ConsolePlus`模块的，匹配度更高
+    fn valid_root(path: &Path) -> usize {
+        let package = path.join(PACKAGE_PYNARS);
+        // 不含`pynars`包⇒0
+        if_return! { !package.join("__init__.py").is_file() => 0 }
+        // 按所含模块加分
+        let score_module = if package.join(format!("{MODULE_CONSOLE_PLUS}.py")).is_file() {
+            4
+        } else if package.join(format!("{MODULE_CONSOLE}.py")).is_file() {
+            2
+        } else {
+            1
+        };
+        // 根目录名称匹配`pynars`⇒额外加分
+        let score_name = path.file_name().map_unwrap_or(
+            |name_os| name_os.to_str().map_unwrap_or(Self::match_name, 0),
+            0,
+        );
+        score_module + score_name
+    }
+
+    /// 确定所启动的模块
+    /// * 🚩优先使用`ConsolePlus`，否则使用`Console`
+    fn module_path(path: &Path) -> String {
+        let package = path.join(PACKAGE_PYNARS);
+        let module = match package.join(format!("{MODULE_CONSOLE_PLUS}.py")).is_file() {
+            true => MODULE_CONSOLE_PLUS,
+            false => MODULE_CONSOLE,
+        };
+        format!("{PACKAGE_PYNARS}.{module}")
+    }
+}
+
+impl CinPathBuilder for PathBuilderPyNARS {
+    type Runtime = CommandVmRuntime;
+    type Launcher = PyNARS;
+
+    fn match_path(&self, path: &Path) -> usize {
+        // ! 与本地文件系统有关
+        // 不是本地的文件夹⇒0
+        if_return! { !path.is_dir() => 0 }
+        // 否则⇒查看根目录匹配度
+        Self::valid_root(path)
+    }
+
+    fn construct_from_path(&self, path: &Path) -> Self::Launcher {
+        PyNARS::new(path, &Self::module_path(path))
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_support::cin_search::impls_path_builder::{
+        launchers_from_walker_sorted, test_utils::TempTree,
+    };
+
+    /// 测试/路径匹配
+    #[test]
+    fn test_match() {
+        let tree = TempTree::new(
+            "path_builder_pynars",
+            &[
+                "PyNARS-dev/pynars/__init__.py",
+                "PyNARS-dev/pynars/ConsolePlus.py",
+                "PyNARS-dev/pynars/Console.py",
+                "old/pynars/__init__.py",
+                "old/pynars/Console.py",
+                "not_a_package/pynars/Console.py",
+                "other/main.py",
+            ],
+        );
+        let root = tree.path();
+
+        // 匹配度
+        let score = |sub: &str| PathBuilderPyNARS.match_path(&root.join(sub));
+        assert!(score("PyNARS-dev") > score("old"));
+        assert!(score("old") > 0);
+        assert_eq!(score("not_a_package"), 0);
+        assert_eq!(score("other"), 0);
+        // 文件、`pynars`包本身均不匹配
+        assert_eq!(score("other/main.py"), 0);
+        assert_eq!(score("PyNARS-dev/pynars"), 0);
+
+        // 排序后构建启动器
+        let launchers = launchers_from_walker_sorted(tree.walker(), PathBuilderPyNARS);
+        assert_eq!(
+            launchers,
+            [
+                PyNARS::new(root.join("PyNARS-dev"), "pynars.ConsolePlus"),
+                PyNARS::new(root.join("old"), "pynars.Console"),
+            ]
+        );
+    }
+}
@@ -77,3 +77,206 @@ pub fn name_match_only_contains(name: &str, target: &str) -> usize {
 pub fn is_name_match(name: &str, target: &str) -> bool {
     name_match(name, target) > 0
 }
+
+/// 名称模糊查找
+/// * 🎯精确匹配失败时，按[`name_match`]选出「匹配度」最高的名称
+///   * 📄"OpenNARS 3.0.4"⇒"OpenNARS"
+/// * 🚩匹配度相同⇒取靠后者
+/// * 🚩全不匹配⇒[`None`]
+pub fn best_name_match<'a>(
+    target: &str,
+    names: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    names
+        .into_iter()
+        .map(|name| (name, name_match(name, target)))
+        .filter(|(_, score)| *score > 0)
+        .max_by_key(|(_, score)| *score)
+        .map(|(name, _)| name)
+}
+
+/// 编辑距离（Levenshtein距离）
+/// * 🎯在「名称匹配」全部失败时，找出「最接近」的名称，作为纠错建议
+///   * 📄"opnenars"最接近"opennars"
+/// * 🚩按字符（而非字节）计算，忽略大小写
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_lowercase().chars().collect::<Vec<_>>();
+    let b = b.to_lowercase().chars().collect::<Vec<_>>();
+    // 仅保留上一行，滚动计算
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// 「编辑距离」兜底匹配允许的最大距离
+/// * 🚩超出此距离的候选不作为建议
+pub const SUGGESTION_MAX_DISTANCE: usize = 3;
+
+/// 名称建议/单个候选的匹配度
+/// * 🎯在「匹配失败」时，给出按匹配度排序的建议
+/// * 📌匹配度：前缀 > 含于 > 忽略大小写 > 编辑距离
+///   * 完全相等⇒`9`
+///   * 前缀⇒`8`
+///   * 含于⇒`7`
+///   * 忽略大小写后相等⇒`6`
+///   * 忽略大小写后前缀⇒`5`
+///   * 忽略大小写后含于⇒`4`
+///   * 编辑距离不超过[`SUGGESTION_MAX_DISTANCE`]⇒`1..=3`（越近越高）
+///   * 否则⇒`0`（不建议）
+pub fn suggestion_score(input: &str, candidate: &str) -> usize {
+    if_return! {
+        candidate == input => 9
+        candidate.starts_with(input) => 8
+        candidate.contains(input) => 7
+    }
+
+    let input_lower = input.to_lowercase();
+    let candidate_lower = candidate.to_lowercase();
+    if_return! {
+        candidate_lower == input_lower => 6
+        candidate_lower.starts_with(&input_lower) => 5
+        candidate_lower.contains(&input_lower) => 4
+    }
+
+    // 兜底：编辑距离
+    match edit_distance(input, candidate) {
+        distance if distance <= SUGGESTION_MAX_DISTANCE => SUGGESTION_MAX_DISTANCE + 1 - distance,
+        _ => 0,
+    }
+}
+
+/// 名称建议
+/// * 🎯在「匹配失败」时，给出「是否指……」的候选
+///   * 📄"onna"⇒建议"ONA"
+/// * 🚩按[`suggestion_score`]计算匹配度，过滤掉不匹配者，并从高到低排序
+///   * 📌匹配度相同⇒保持候选原有顺序
+/// * ⚙️返回`(候选, 匹配度)`的数组
+pub fn suggest_names<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<(&'a str, usize)> {
+    let mut suggestions = candidates
+        .into_iter()
+        .map(|candidate| (candidate, suggestion_score(input, candidate)))
+        .filter(|(_, score)| *score > 0)
+        .collect::<Vec<_>>();
+    // ! 稳定排序，保证同分候选的顺序
+    suggestions.sort_by(|(_, a), (_, b)| b.cmp(a));
+    suggestions
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+
+    #[test]
+    fn test_edit_distance() {
+        asserts! {
+            edit_distance("", "") => 0
+            edit_distance("ona", "ONA") => 0
+            edit_distance("onna", "ona") => 1
+            edit_distance("opnenars", "OpenNARS") => 2
+            edit_distance("kitten", "sitting") => 3
+            edit_distance("", "abc") => 3
+        }
+    }
+
+    /// 测试用名称
+    /// * 🎯覆盖大小写、前缀、含于、拼写错误等情况
+    const NAMES: &[&str] = &[
+        "ona",
+        "ONA",
+        "onna",
+        "OpenNARS",
+        "opennars",
+        "opnenars",
+        "open",
+        "NARS",
+        "nars",
+        "PyNARS",
+        "pynars",
+        "NARS-Python",
+        "NARSPython",
+        "OpenJunars",
+        "CXinJS",
+        "Native",
+        "",
+        "a",
+        "xyz",
+    ];
+
+    /// 测试/名称模糊查找
+    #[test]
+    fn test_best_name_match() {
+        let names = ["Native", "OpenNARS", "ONA", "PyNARS"];
+        asserts! {
+            best_name_match("OpenNARS 3.0.4", names) => Some("OpenNARS")
+            best_name_match("ona", names) => Some("ONA")
+            best_name_match("opnenars", names) => None
+            best_name_match("xyz", names) => None
+        }
+    }
+
+    /// 测试/名称建议
+    #[test]
+    fn test_suggest_names() {
+        // "onna"⇒"ONA"
+        let suggestions = suggest_names("onna", ["Native", "OpenNARS", "ONA", "PyNARS"]);
+        assert_eq!(suggestions.first().map(|(name, _)| *name), Some("ONA"));
+        // 前缀优先于含于，含于优先于忽略大小写
+        let suggestions = suggest_names("nars", ["PyNARS", "pynars", "narsese", "NARS"]);
+        let names = suggestions
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["narsese", "pynars", "NARS", "PyNARS"]);
+        // 相差过大⇒不建议
+        assert_eq!(suggest_names("xyzxyz", ["OpenNARS", "ONA"]), []);
+    }
+
+    /// 测试/名称建议的排序性质
+    /// * 🚩在所有名称对上检验不变量
+    #[test]
+    fn test_suggest_names_ordering() {
+        for input in NAMES {
+            let suggestions = suggest_names(input, NAMES.iter().copied());
+            // 匹配度从高到低，且均非零
+            assert!(suggestions.windows(2).all(|w| w[0].1 >= w[1].1));
+            assert!(suggestions.iter().all(|(_, score)| *score > 0));
+            for candidate in NAMES {
+                let score = suggestion_score(input, candidate);
+                // 完全相等⇒最高
+                if candidate == input {
+                    assert_eq!(score, 9);
+                }
+                // 前缀 > 含于 > 忽略大小写 > 编辑距离
+                let lower = (input.to_lowercase(), candidate.to_lowercase());
+                let expected_min = match () {
+                    _ if candidate.starts_with(input) => 8,
+                    _ if candidate.contains(input) => 7,
+                    _ if lower.1.contains(&lower.0) => 4,
+                    _ => 0,
+                };
+                assert!(score >= expected_min, "{input:?} {candidate:?} {score}");
+                // 非大小写匹配⇒低于任何忽略大小写的匹配
+                if !lower.1.contains(&lower.0) {
+                    assert!(score < 4, "{input:?} {candidate:?} {score}");
+                }
+                // 被建议 ⇔ 匹配度非零
+                assert_eq!(
+                    suggestions.iter().any(|(name, _)| name == candidate),
+                    score > 0
+                );
+            }
+        }
+    }
+}
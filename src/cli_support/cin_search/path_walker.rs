@@ -2,8 +2,15 @@
 //! * 🎯用于分离「路径查找」与「CIN识别」两功能
 //!   * 📌「路径遍历器」负责「提供路径，并有选择地 深入/跳出 路径」
 
-use anyhow::{Error, Result};
-use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Error, Result};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+/// 默认的「最大深入深度」
+/// * 🎯避免在`C:\`、`/home`等宽泛的起始目录下遍历整个文件系统
+pub const DEFAULT_MAX_DEPTH: usize = 6;
 
 /// 抽象的「路径遍历」特征
 /// * ✨允许「迭代出下一个路径」
@@ -47,18 +54,44 @@ pub trait PathWalker {
 ///     * 遍历其【直接包含】的文件/文件夹
 ///     * 若有满足特定「可深入条件」的文件夹，则深入扫描该文件夹（仍然是「条件扫描」）
 ///   * 🚩父目录扫描完毕后，继续扫描父目录
+/// * 🚩限制遍历范围
+///   * 📌深入不超过「最大深入深度」（相对「起始目录」或所扫描的父目录）
+///   * 📌记录已访问过的目录（规范化路径），避免符号链接导致的循环
+///   * 📌可选的「最大路径数」：达到后返回一条「警告」错误，然后终止
 pub struct PathWalkerV1<'a> {
     // 父目录堆栈
     ancestors_stack: Vec<PathBuf>,
 
     /// 待遍历目录的堆栈
-    to_visit_stack: Vec<PathBuf>,
+    /// * 🚩附带其深度
+    to_visit_stack: Vec<(PathBuf, usize)>,
 
     /// 可深入条件
     deep_criterion: Box<dyn Fn(&Path) -> bool + Send + Sync + 'a>,
 
     /// 当前在遍历目录的迭代器
     current_dir_iter: Box<dyn Iterator<Item = Result<PathBuf>>>,
+
+    /// 当前在遍历目录的深度
+    current_depth: usize,
+
+    /// 最大深入深度
+    /// * 📜默认为[`DEFAULT_MAX_DEPTH`]
+    max_depth: usize,
+
+    /// 已访问过的目录
+    /// * 🚩存储规范化后的路径
+    visited: HashSet<PathBuf>,
+
+    /// 最多遍历的路径数
+    /// * 📜默认不限
+    max_paths: Option<usize>,
+
+    /// 已遍历的路径数
+    num_paths: usize,
+
+    /// 是否已中止
+    aborted: bool,
 }
 
 impl<'a> PathWalkerV1<'a> {
@@ -82,12 +115,40 @@ impl<'a> PathWalkerV1<'a> {
         };
         let deep_criterion = Box::new(deep_criterion);
         let current_dir_iter = Box::new(Self::new_path_iter(&root)?);
-        Ok(Self {
+        let mut walker = Self {
             ancestors_stack,
             to_visit_stack: vec![], // 空栈初始化
             deep_criterion,
             current_dir_iter,
-        })
+            current_depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            visited: HashSet::new(),
+            max_paths: None,
+            num_paths: 0,
+            aborted: false,
+        };
+        walker.visit(&root);
+        Ok(walker)
+    }
+
+    /// 配置/最大深入深度
+    /// * 🚩`0`⇒仅遍历各目录的直接内容
+    pub fn max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// 配置/最多遍历的路径数
+    /// * 🚩达到后返回一条「警告」错误，然后终止
+    pub fn max_paths(&mut self, max_paths: usize) {
+        self.max_paths = Some(max_paths);
+    }
+
+    /// 标记目录为「已访问」
+    /// * 🚩返回「此前是否未访问过」
+    /// * 📌无法规范化（如权限不足）时，使用原路径
+    fn visit(&mut self, path: &Path) -> bool {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+        self.visited.insert(canonical)
     }
 
     /// ✨构造路径迭代器
@@ -103,13 +164,28 @@ impl<'a> PathWalkerV1<'a> {
     /// * 🎯应对「切换到父目录的迭代器后，首个迭代结果还是[`None`]」的情况
     ///   * 🚩解决方案：再次[`Self::poll_path`]
     fn poll_path(&mut self) -> PathPollResult {
+        // 已中止⇒直接终止
+        if self.aborted {
+            return PathPollResult::Ended;
+        }
         // ! ❌【2024-03-30 22:34:04】目前没法稳定地使用`?`
         match self.current_dir_iter.next() {
             // 正常情况
             Some(Ok(path)) => {
-                // 如果「值得深入」⇒预备在后续深入
-                if path.is_dir() && (self.deep_criterion)(&path) {
-                    self.to_visit_stack.push(path.clone())
+                // 达到「最大路径数」⇒中止，并返回警告
+                if let Some(max_paths) = self.max_paths {
+                    if self.num_paths >= max_paths {
+                        self.aborted = true;
+                        return PathPollResult::Err(anyhow!(
+                            "已遍历{max_paths}个路径，达到上限，中止遍历"
+                        ));
+                    }
+                }
+                self.num_paths += 1;
+                // 如果「值得深入」且未超过最大深度⇒预备在后续深入
+                let depth = self.current_depth + 1;
+                if depth <= self.max_depth && path.is_dir() && (self.deep_criterion)(&path) {
+                    self.to_visit_stack.push((path.clone(), depth))
                 }
                 // 返回
                 PathPollResult::Some(path)
@@ -123,29 +199,34 @@ impl<'a> PathWalkerV1<'a> {
 
     /// 尝试切换路径
     /// * 切换到一个新的路径
+    /// * 🚩已访问过的目录⇒跳过（不切换）
     fn try_switch_current_path(&mut self) -> PathPollResult {
-        match self.to_visit_stack.pop() {
-            // 「待检查路径」有⇒尝试pop一个，构造并切换到新的迭代器
-            Some(path) => match self.change_current_path(&path) {
-                Ok(()) => PathPollResult::None, // 构造了就收手，无需立马查看里边有无路径
-                Err(e) => PathPollResult::Err(e),
-            },
-            // 「待检查路径」没有⇒尝试从「祖先路径」中尝试pop一个
+        let (path, depth) = match self.to_visit_stack.pop() {
+            // 「待检查路径」有⇒尝试pop一个
+            Some(entry) => entry,
+            // 「待检查路径」没有⇒尝试从「祖先路径」中尝试pop一个 | 深度从零开始
             None => match self.ancestors_stack.pop() {
-                // 「祖先路径」有⇒尝试pop一个，构造并切换到新的迭代器
-                Some(path) => match self.change_current_path(&path) {
-                    Ok(()) => PathPollResult::None, // 构造了就收手，无需立马查看里边有无路径
-                    Err(e) => PathPollResult::Err(e),
-                }, // 「祖先路径」没有⇒终止
-                None => PathPollResult::Ended,
+                Some(path) => (path, 0),
+                // 「祖先路径」没有⇒终止
+                None => return PathPollResult::Ended,
             },
+        };
+        // 已访问过⇒跳过 | 📄符号链接导致的循环
+        if !self.visit(&path) {
+            return PathPollResult::None;
+        }
+        // 构造并切换到新的迭代器
+        match self.change_current_path(&path, depth) {
+            Ok(()) => PathPollResult::None, // 构造了就收手，无需立马查看里边有无路径
+            Err(e) => PathPollResult::Err(e),
         }
     }
 
     /// 尝试更改到某个目录（的迭代器）
-    fn change_current_path(&mut self, path: &Path) -> Result<()> {
+    fn change_current_path(&mut self, path: &Path, depth: usize) -> Result<()> {
         let iter = Self::new_path_iter(path)?;
         self.current_dir_iter = Box::new(iter);
+        self.current_depth = depth;
         Ok(())
     }
 }
@@ -211,7 +292,9 @@ impl Iterator for PathWalkerV1<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli_support::cin_search::name_match::is_name_match;
+    use crate::cli_support::cin_search::{
+        impls_path_builder::test_utils::TempTree, name_match::is_name_match,
+    };
     use std::env::current_dir;
 
     fn _test_path_walker_v1(start: impl Into<PathBuf>) {
@@ -237,4 +320,81 @@ mod tests {
         // 测试当前路径
         _test_path_walker_v1(current_dir().unwrap());
     }
+
+    /// 工具/遍历某目录，只保留其下的路径
+    /// * 🚩仅深入该目录之内
+    /// * 📌该目录自身会在遍历父目录时出现，亦过滤掉
+    fn walk_inside(root: &Path, config: impl FnOnce(&mut PathWalkerV1)) -> Vec<PathBuf> {
+        let inside = root.to_owned();
+        let mut walker = PathWalkerV1::new(root, move |path| path.starts_with(&inside)).unwrap();
+        config(&mut walker);
+        walker
+            .filter_map(Result::ok)
+            .filter(|path| path.starts_with(root) && path != root)
+            .collect()
+    }
+
+    /// 测试/最大深入深度
+    #[test]
+    fn test_max_depth() {
+        let tree = TempTree::new("path_walker_depth", &["1/2/3/4/5.txt"]);
+        let root = tree.path();
+        // 默认深度足够
+        let paths = walk_inside(root, |_| {});
+        assert!(paths.contains(&root.join("1/2/3/4/5.txt")), "{paths:?}");
+        // 限制深度：最多遍历到`1/2`的内容
+        let paths = walk_inside(root, |walker| walker.max_depth(2));
+        assert!(paths.contains(&root.join("1/2/3")), "{paths:?}");
+        assert!(!paths.contains(&root.join("1/2/3/4")), "{paths:?}");
+        // 深度为零：仅起始目录的直接内容
+        let paths = walk_inside(root, |walker| walker.max_depth(0));
+        assert_eq!(paths, [root.join("1")]);
+    }
+
+    /// 测试/符号链接导致的循环
+    /// * ⚠️无法创建符号链接的平台上跳过
+    #[test]
+    fn test_symlink_cycle() {
+        let tree = TempTree::new("path_walker_cycle", &["a/file.txt"]);
+        let root = tree.path();
+        if let Err(e) = symlink_dir(&root.join("a"), &root.join("a/loop")) {
+            println!("无法创建符号链接，跳过测试：{e}");
+            return;
+        }
+        // 即便深度充足，也不会重复遍历同一目录
+        let mut paths = walk_inside(root, |walker| walker.max_depth(100));
+        paths.sort();
+        assert_eq!(
+            paths,
+            [root.join("a"), root.join("a/file.txt"), root.join("a/loop")]
+        );
+    }
+
+    /// 工具/创建指向目录的符号链接
+    #[cfg(unix)]
+    fn symlink_dir(original: &Path, link: &Path) -> std::io::Result<()> {
+        std::os::unix::fs::symlink(original, link)
+    }
+
+    /// 工具/创建指向目录的符号链接
+    #[cfg(windows)]
+    fn symlink_dir(original: &Path, link: &Path) -> std::io::Result<()> {
+        std::os::windows::fs::symlink_dir(original, link)
+    }
+
+    /// 测试/最大路径数
+    #[test]
+    fn test_max_paths() {
+        let tree = TempTree::new(
+            "path_walker_max_paths",
+            &["1.txt", "2.txt", "3.txt", "4.txt", "5.txt"],
+        );
+        let mut walker = PathWalkerV1::new(tree.path(), |_| false).unwrap();
+        walker.max_paths(3);
+        let results = walker.collect::<Vec<_>>();
+        // 三个路径，一条警告，然后终止
+        assert_eq!(results.len(), 4);
+        assert!(results[..3].iter().all(Result::is_ok));
+        assert!(results[3].is_err());
+    }
 }
@@ -0,0 +1,2501 @@
+//! BabelNAR CLI的启动配置
+//! * ✨格式支持
+//!   * ✅JSON
+//!   * ✅HJSON
+//! * 🎯用于配置表示，❗不用于命令行解析
+//! * 🎯可被其它二进制库（CIN启动器、后续的图形界面等）所复用
+//!
+//! ## ⚙️内容
+//!
+//! Rust结构：
+//!
+//! * 📌转译器组合?
+//!   * （互斥）单个值?（输入输出相同） `opennars` / `ona` / `nars-python` / `pynars` / `openjunars` / `cxin-js`
+//!   * （互斥）输入输出单独配置?
+//!     * 输入 `opennars` / `ona` / `nars-python` / `pynars` / `openjunars` / `cxin-js`
+//!     * 输出 `opennars` / `ona` / `nars-python` / `pynars` / `openjunars` / `cxin-js`
+//! * 📌启动命令?
+//!   * 命令 `XXX.exe` / `python` / `java` / `node` / ...
+//!   * 命令参数? `["-m", 【Python模块】]` / `["-jar", 【Jar路径】]`
+//!   * 工作目录? `root/path/to/current_dir` | 🎯用于Python模块
+//! * 📌预置NAL?（单个或多个，按顺序执行）
+//!   * （互斥）文件路径? `root/path/to/file` | 与下边「纯文本」互斥
+//!   * （互斥）纯文本? `"'/VOL 0"`
+//! * 📌Websocket参数? | ✅支持ipv6
+//!   * 主机地址 `localhost` `192.168.1.1` `fe80::abcd:fade:dad1`
+//!   * 连接端口 `3040`
+//!
+//! TypeScript声明：
+//!
+//! ```ts
+//! type LaunchConfig = {
+//!     translators?: LaunchConfigTranslators,
+//!     command?: LaunchConfigCommand,
+//!     connect?: LaunchConfigConnect, // 与`command`二选一：连接到已在运行的CIN
+//!     websocket?: LaunchConfigWebsocket,
+//!     preludeNAL?: LaunchConfigPreludeNAL | LaunchConfigPreludeNAL[],
+//!     userInput?: boolean
+//!     inputMode?: InputMode
+//!     autoRestart?: boolean
+//!     outputFormat?: OutputFormat
+//!     setAck?: string | { [cmdHead: string]: string } // `''set:`的回执：所有指令，或按指令头分别指定（`"*"`⇒其余指令）
+//!     printLevel?: string[] // NAVM输出类型名，或`"*"`
+//!     noColor?: boolean
+//!     precisionEpoch?: number // NAL预期比对真值、预算值时的默认精度
+//!     outputTypeAliases?: { [name: string]: string[] } // NAL预期中输出类型的别名
+//!     suppressInputEcho?: boolean // 不缓存、不打印、不回传CIN回显的输入（`IN`）
+//!     saveOutputsKeep?: number // `''save-outputs`的路径含`{timestamp}`时，保留的文件数
+//!     saveOutputsAllowAbsolute?: boolean // `''save-outputs`是否允许绝对路径
+//!     cycPacing?: LaunchConfigCycPacing // 大步数的CYC分批输入
+//!     cycAwait?: boolean // NAL中的CYC：等到CIN跑完这些步数，再置入下一条输入
+//!     coalesceLines?: boolean // 合并跨越多行的输出，如Java异常堆栈、ONA的统计报告
+//!     processEncoding?: string // 子进程输出非UTF-8时的备用编码，如`"gbk"`
+//!     pipedInputGrace?: number // 管道输入读完后，等待CIN输出的时间（毫秒）
+//!     pythonEnv?: LaunchConfigPythonEnv // Python解释器与虚拟环境
+//!     instances?: { [name: string]: LaunchConfig } // 多实例：各实例继承外层配置
+//! }
+//!
+//! type InputMode = 'cmd' | 'nal' | 'raw'
+//!
+//! type OutputFormat = 'text' | 'json'
+//!
+//! type LaunchConfigTranslators = string | {
+//!     // ↓虽然`in`是JavaScript/TypeScript/Rust的关键字，但仍可在此直接使用
+//!     in: string,
+//!     out: string,
+//! } | {
+//!     exec: string, // 外部转译程序
+//!     execArgs?: string[],
+//!     direction: 'in' | 'out', // 外部程序负责的方向
+//!     preset?: string, // 另一方向的转译器，默认`native`
+//! }
+//!
+//! type LaunchConfigCommand = {
+//!     cmd: string, // jar文件⇒以Java启动
+//!     cmdArgs?: string[], // 命令为jar文件时，作为JVM参数置于`-jar`之前
+//!     currentDir?: string,
+//!     env?: { [key: string]: string }, // 附加的环境变量，如`PYTHONIOENCODING`
+//! }
+//! type LaunchConfigConnect = {
+//!     host: string,
+//!     port: number, // Uint16
+//! }
+//! type LaunchConfigCycPacing = {
+//!     chunk: number, // 每批步数
+//!     intervalMs?: number, // 批间间隔（毫秒）
+//! }
+//! type LaunchConfigPythonEnv = {
+//!     pythonPath?: string, // 解释器路径，优先于`venv`
+//!     venv?: string, // venv、conda环境目录
+//!     extraPaths?: string[], // 附加的PYTHONPATH
+//! }
+//! type LaunchConfigWebsocket = {
+//!     host: string,
+//!     port: number, // Uint16
+//!     queueSize?: number,
+//!     heartbeatSecs?: number, // 心跳间隔（秒），`0`⇒不发送心跳
+//!     maxConnections?: number,
+//!     maxInputsPerSecond?: number, // 每个客户端每秒最多输入的条数
+//!     rateLimitPolicy?: "queue" | "reject", // 超出限制时的处理，默认`reject`
+//!     inputBacklog?: number, // `queue`策略下，每个客户端最多积压的输入条数
+//! }
+//! // ↓ 文件、纯文本 二选一
+//! type LaunchConfigPreludeNAL = {
+//!     file?: string,
+//!     text?: string,
+//! }
+//! ```
+//!
+//! 占位符：`command`的`cmd`、`cmdArgs`、`currentDir`与`preludeNAL`的`file`中，可使用
+//! * `${ENV:变量名}`：环境变量，如`${ENV:JAVA_HOME}`
+//! * `${configDir}`：配置文件所在目录
+//! * `${exeDir}`：CLI可执行文件所在目录
+
+// * 📌库内无法经由路径引用`println_cli!`（由宏展开的模块导出），故直接调用[`OutputType`]
+use crate::{
+    cli_support::io::output_print::OutputType,
+    runtimes::{Capabilities, DEFAULT_STARTUP_TIMEOUT, NO_ANSWER},
+    test_tools::{FloatPrecision, OutputTypeAliases, SetAck},
+};
+use anyhow::{anyhow, Result};
+use nar_dev_utils::{if_return, pipe, OptionBoost, ResultBoost};
+use navm::output::{type_names, Output};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::read_to_string,
+    path::{Component, Path, PathBuf},
+};
+
+/// 允许的配置文件扩展名
+/// * 🚩【2024-04-07 18:30:24】目前支持JSON与HJSON
+/// * 📌其顺序决定了在「扩展名优先补充」中的遍历顺序
+///   * 📄当`a.hjson`与`a.json`存在时，`a`优先补全为`a.hjson`
+pub const SUPPORTED_CONFIG_EXTENSIONS: &[&str] = &["hjson", "json"];
+
+/// 工具宏/批量拷贝性合并
+/// * 🎯简化重复的`对象.方法`调用
+/// * 📄参考[`Option::coalesce_clone`]
+macro_rules! coalesce_clones {
+    {
+        // 合并的方向
+        $other:ident => $this:ident;
+        // 要合并的键
+        $($field:ident)*
+    } => { $( $this.$field.coalesce_clone(&$other.$field); )* };
+}
+
+/// 工具宏/批量拷贝性覆盖
+/// * 🎯与[`coalesce_clones`]相对：并入者中**显式设置**的值总是覆盖自身
+///   * 📄包括显式设置的`false`
+/// * 🚩并入者为`None`⇒保留自身
+macro_rules! override_clones {
+    {
+        // 覆盖的方向
+        $other:ident => $this:ident;
+        // 要覆盖的键
+        $($field:ident)*
+    } => { $(
+        if let Some(value) = &$other.$field {
+            $this.$field = Some(value.clone());
+        }
+    )* };
+}
+
+/// 工具宏/对[`LaunchConfig`]中所有「直接合并」的键调用指定的宏
+/// * 🎯[`LaunchConfig::merge_from`]与[`LaunchConfig::override_from`]共用一份键列表
+///   * 📌新增配置项时只需在此添加
+/// * 📄`launch_config_keys!(coalesce_clones! { other => self })`
+macro_rules! launch_config_keys {
+    ($method:ident! { $other:ident => $this:ident }) => {
+        $method! {
+            $other => $this;
+            translators
+            // command // ! 此键需递归处理
+            connect
+            websocket
+            // prelude_nal // ! 此键需拼接
+            user_input
+            input_mode
+            auto_restart
+            strict_mode
+            output_format
+            set_ack
+            print_level
+            no_color
+            startup_timeout
+            report_file
+            piped_input_grace
+            history_file
+            precision_epoch
+            output_type_aliases
+            suppress_input_echo
+            save_outputs_keep
+            save_outputs_allow_absolute
+            cyc_pacing
+            cyc_await
+            coalesce_lines
+            process_encoding
+            python_env
+            instances
+        }
+    };
+}
+
+/// NAVM虚拟机（运行时）启动配置
+/// * 🎯启动完整的NAVM实例，并附带相关运行时配置
+///   * ✨启动时数据提供
+///   * ✨运行时数据提供
+/// * 📍【2024-04-04 02:17:10】现在所有都是**可选**的
+///   * 🎯用于无损合并从键值对中加载而来的配置
+///     * 📄`true`可以在识别到`null`时替换`null`，而无需管其是否为默认值
+///   * 🚩在启动时会转换为「运行时配置」，并在此时检查完整性
+///   * 📌这意味着其总是能派生[`Default`]
+/// * ⚠️其中的所有**相对路径**，在[`read_config_extern`]中都基于**配置文件自身**
+///   * 🎯不论CLI自身所处何处，均保证配置读取稳定
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")] // 🔗参考：<https://serde.rs/container-attrs.html>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchConfig {
+    /// 配置的加载路径
+    /// * 🎯用于记录「基于配置自身的配置路径」
+    /// * 🚩从文件中加载⇒`Some(配置文件所在目录)`
+    /// * 🚩从其它加载⇒[`None`]
+    /// * 📌不在反序列化（解析）时解析
+    #[serde(skip)]
+    #[serde(default)]
+    pub config_path: Option<PathBuf>,
+
+    /// 启动配置的文本描述
+    /// * 🎯在自动搜索时呈现给用户
+    /// * 📌一般是单行文本
+    ///
+    /// * ❓I18n 国际化
+    pub description: Option<String>,
+
+    /// 转译器组合
+    /// * 🚩使用字符串模糊匹配
+    pub translators: Option<LaunchConfigTranslators>,
+
+    /// 启动命令
+    pub command: Option<LaunchConfigCommand>,
+
+    /// 连接参数
+    /// * 🎯连接到已在运行的CIN（Websocket服务端），而非启动子进程
+    /// * 📌与`command`二选一：有连接参数⇒不再需要启动命令
+    pub connect: Option<LaunchConfigConnect>,
+
+    /// Websocket参数
+    /// * 🚩【2024-04-03 18:21:00】目前对客户端输出JSON
+    pub websocket: Option<LaunchConfigWebsocket>,
+
+    /// 预置NAL
+    /// * 🚩可为单个，亦可为列表：按顺序执行
+    /// * 🚩多启动配置合并时拼接，而非覆盖
+    #[serde(rename = "preludeNAL")] // * 📝serde配置中，`rename`优先于`rename_all`
+    #[serde(default, deserialize_with = "deserialize_prelude_nal")]
+    pub prelude_nal: Option<Vec<LaunchConfigPreludeNAL>>,
+
+    /// 启用用户输入
+    /// * 🎯控制该实例是否需要（来自用户的）交互式输入
+    /// * 🚩【2024-04-04 02:19:36】默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    pub user_input: Option<bool>,
+
+    /// 输入模式
+    /// * 🚩对输入（不论交互还是Websocket）采用的解析模式
+    ///   * 📄纯NAVM指令的解析
+    /// * 🎯兼容旧`BabelNAR.jl`服务端
+    /// * 🚩【2024-04-04 02:19:36】默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub input_mode: Option<InputMode>,
+
+    /// 自动重启
+    /// * 🎯程序健壮性：用户的意外输入，不会随意让程序崩溃
+    /// * 🚩在虚拟机终止（收到「终止」输出）时，自动用配置重启虚拟机
+    /// * 🚩【2024-04-04 02:19:36】默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    pub auto_restart: Option<bool>,
+
+    /// 严格模式
+    /// * 🎯测试敏感性：测试中的「预期失败」可以让程序上报异常
+    /// * 🚩在「预引入NAL」等场景中，若出现「预期失败」则程序直接异常退出
+    /// * 🚩【2024-04-04 02:19:36】默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    pub strict_mode: Option<bool>,
+
+    /// 输出格式
+    /// * 🎯被其它程序作为子进程调用时，提供机器可读的输出
+    /// * 🚩`json`：标准输出中每行一个NAVM输出（JSON），CLI信息转至标准错误
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub output_format: Option<OutputFormat>,
+
+    /// 「设置」指令的回执
+    /// * 🎯`''set:`魔法注释：CIN输出的原始内容包含该指令的回执字串⇒视作已确认
+    /// * 📌因CIN（转译器）与指令而异，故交由配置指定：见[`SetAck`]
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub set_ack: Option<SetAck>,
+
+    /// 终端打印的输出类型
+    /// * 🎯高音量下只关注「回答」「操作」等有用输出，不被「导出」刷屏
+    /// * 📄`["ANSWER", "EXE", "ERROR"]`
+    /// * 🚩仅影响打印：所有输出仍会被缓存（用于NAL预期）与Websocket回传
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub print_level: Option<PrintLevel>,
+
+    /// 禁用彩色输出
+    /// * 🎯终端不支持ANSI转义序列、输出被重定向到文件等场景
+    /// * 📌环境变量`NO_COLOR`亦可禁用
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub no_color: Option<bool>,
+
+    /// 启动检查的超时时间（毫秒）
+    /// * 🎯CIN启动即失败时，及时报错并展示其输出，而非一直沉默
+    /// * 📌`0`⇒不检查
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub startup_timeout: Option<u64>,
+
+    /// NAL测试报告的输出路径
+    /// * 🎯CI中汇总`.nal`预期的结果：运行「预置NAL」后写入
+    /// * 📌扩展名为`.xml`⇒JUnit风格的XML；否则⇒JSON
+    /// * 🚩相对路径以配置文件所在目录为根
+    #[serde(default)]
+    pub report_file: Option<PathBuf>,
+
+    /// 管道输入读完后的宽限期（毫秒）
+    /// * 🎯`echo "<A --> B>?" | babelnar_cli`：退出前留出CIN回答的时间
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub piped_input_grace: Option<u64>,
+
+    /// 用户输入的历史记录文件
+    /// * 🎯在多次会话之间保留交互式输入的历史：上下方向键回溯
+    /// * 🚩相对路径以配置文件所在目录为根
+    #[serde(default)]
+    pub history_file: Option<PathBuf>,
+
+    /// NAL预期的默认精度
+    /// * 🎯比对真值、预算值时允许的误差：如`0.01`
+    /// * 📌NAL文件中的`''precision:`、预期中的`(±0.05)`优先
+    #[serde(default)]
+    pub precision_epoch: Option<FloatPrecision>,
+
+    /// 输出类型的别名
+    /// * 🎯不同CIN对同一类输出使用不同的类型名：同一份NAL测试可用于各CIN
+    /// * 📄`{ "ANTICIPATE": ["EXPECT", "ANTICIPATION"] }`
+    /// * 🚩NAL预期中的「标准名」亦匹配其所有别名；打印过滤中亦然
+    #[serde(default)]
+    pub output_type_aliases: Option<OutputTypeAliases>,
+
+    /// 是否抑制输入回显
+    /// * 🎯OpenNARS、ONA会回显每条输入（`IN`）：高频的智能体循环中，输出缓存与Websocket流量因此翻倍
+    /// * 🚩丢弃`IN`输出：不缓存、不打印、不回传
+    ///   * 📌`''await-echo`仍可用：等待时直接比对拉取到的输出，不依赖缓存
+    ///   * ⚠️`''expect-contains: IN …`等基于缓存的预期将无法满足
+    #[serde(default)]
+    pub suppress_input_echo: Option<bool>,
+
+    /// `''save-outputs`保留的文件数
+    /// * 🎯路径含`{timestamp}`时，每次运行都会产生新文件：只保留最新的若干个
+    /// * 🚩写入后删去同一目录下同模式的旧文件
+    #[serde(default)]
+    pub save_outputs_keep: Option<usize>,
+
+    /// `''save-outputs`是否允许绝对路径
+    /// * 🚩默认不允许：只能保存到NAL文件所在目录之下
+    #[serde(default)]
+    pub save_outputs_allow_absolute: Option<bool>,
+
+    /// CYC分批
+    /// * 🎯避免大步数的`CYC`使较慢的CIN长时间无响应，并拖慢对回答的检测
+    /// * 📄`{ "chunk": 50, "intervalMs": 20 }`
+    /// * 📌仅对「启动子进程」的CIN生效
+    #[serde(default)]
+    pub cyc_pacing: Option<LaunchConfigCycPacing>,
+
+    /// 是否等待CYC步进完成
+    /// * 🎯免去NAL中为等待推理而加的`''sleep`
+    /// * 🚩CIN报告「步进完成」⇒等待其回执；否则⇒等待CIN安静下来
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub cyc_await: Option<bool>,
+
+    /// 是否合并跨越多行的输出
+    /// * 🎯Java异常堆栈、ONA的统计报告作为一个输出
+    /// * 🚩「续行」判断由输出转译器决定：无判断的CIN不受影响
+    /// * 🚩默认值由「运行时转换」决定
+    ///   * 🎯兼容「多启动配置合并」
+    #[serde(default)]
+    pub coalesce_lines: Option<bool>,
+
+    /// 子进程IO的备用编码
+    /// * 🎯输出非UTF-8文本的CIN：如在GBK控制台下运行的Windows程序
+    /// * 📄`"gbk"`、`"GB18030"`、`"shift_jis"`
+    /// * 🚩读：先按UTF-8解码，失败⇒按此编码解码，仍失败⇒有损解码；写：按此编码编码
+    /// * 📌仅对「启动子进程」的CIN生效
+    #[serde(default)]
+    pub process_encoding: Option<String>,
+
+    /// Python环境
+    /// * 🎯在虚拟环境（venv、conda）中启动基于Python的CIN：如PyNARS
+    /// * 📄`{ "venv": ".venv", "extraPaths": ["../PyNARS"] }`
+    /// * 🚩相对路径以配置文件所在目录为根
+    #[serde(default)]
+    pub python_env: Option<LaunchConfigPythonEnv>,
+
+    /// 多个具名实例
+    /// * 🎯在同一个CLI进程中同时运行多个CIN：如实时对比OpenNARS与ONA
+    /// * 📄`{ "opennars": { … }, "ona": { … } }`
+    /// * 🚩各实例以外层配置为基础，并以自身显式设置的值覆盖之
+    ///   * 📌外层的「预置NAL」先于实例自身的执行
+    /// * 📌按名称排序，首个实例为「默认实例」：接收无路由前缀的输入
+    #[serde(default)]
+    pub instances: Option<BTreeMap<String, LaunchConfig>>,
+}
+
+/// 使用`const`常量存储「空启动配置」
+/// * 🎯用于启动配置的「判空」逻辑
+/// * ✅与此同时，实现了「有提醒的后期维护」
+///   * 📌后续若新增字段，此处会因「缺字段」立即报错
+const EMPTY_LAUNCH_CONFIG: LaunchConfig = LaunchConfig {
+    config_path: None,
+    description: None,
+    translators: None,
+    command: None,
+    connect: None,
+    websocket: None,
+    prelude_nal: None,
+    user_input: None,
+    input_mode: None,
+    auto_restart: None,
+    strict_mode: None,
+    output_format: None,
+    set_ack: None,
+    print_level: None,
+    no_color: None,
+    startup_timeout: None,
+    report_file: None,
+    piped_input_grace: None,
+    history_file: None,
+    precision_epoch: None,
+    output_type_aliases: None,
+    suppress_input_echo: None,
+    save_outputs_keep: None,
+    save_outputs_allow_absolute: None,
+    cyc_pacing: None,
+    cyc_await: None,
+    coalesce_lines: None,
+    process_encoding: None,
+    python_env: None,
+    instances: None,
+};
+
+/// NAVM虚拟机（运行时）运行时配置
+/// * 🎯没有任何非必要的空值
+/// * 🚩自[`LaunchConfig`]加载而来
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")] // 🔗参考：<https://serde.rs/container-attrs.html>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    /// 配置的加载路径
+    /// * 🎯用于记录「基于配置自身的配置路径」
+    /// * 🚩从文件中加载⇒配置文件所在目录
+    /// * 🚩从其它加载⇒[`PathBuf::new`]
+    #[serde(skip)]
+    pub config_path: PathBuf,
+
+    /// 转译器组合
+    /// * 🚩运行时必须提供转译器
+    /// * 📌【2024-04-04 02:11:44】即便是所谓「默认」转译器，使用「及早报错」避免非预期运行
+    pub translators: LaunchConfigTranslators,
+
+    /// 启动命令
+    /// * 🚩运行时必须有一个启动命令
+    /// * 🚩内部可缺省
+    /// * 📌有连接参数时不使用：此时为默认值
+    pub command: LaunchConfigCommand,
+
+    /// 连接参数（可选）
+    /// * 🚩有⇒连接到已在运行的CIN，而非启动子进程
+    #[serde(default)]
+    pub connect: Option<LaunchConfigConnect>,
+
+    /// Websocket参数（可选）
+    /// * 🚩允许无：不启动Websocket服务器
+    pub websocket: Option<LaunchConfigWebsocket>,
+
+    /// 预置NAL
+    /// * 🚩允许空：不预置NAL测试文件
+    /// * 🚩按顺序执行
+    #[serde(rename = "preludeNAL")] // * 📝serde配置中，`rename`优先于`rename_all`
+    #[serde(default, deserialize_with = "deserialize_prelude_nal_list")]
+    pub prelude_nal: Vec<LaunchConfigPreludeNAL>,
+
+    /// 启用用户输入
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`true`（启用）
+    #[serde(default = "bool_true")]
+    pub user_input: bool,
+
+    /// 输入模式
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`"nal"`
+    #[serde(default)]
+    pub input_mode: InputMode,
+
+    /// 自动重启
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`false`（关闭）
+    #[serde(default = "bool_false")]
+    pub auto_restart: bool,
+
+    /// 严格模式
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`false`（关闭）
+    #[serde(default = "bool_false")]
+    pub strict_mode: bool,
+
+    /// 输出格式
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`"text"`
+    #[serde(default)]
+    pub output_format: OutputFormat,
+
+    /// 「设置」指令的回执
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：不指定任何回执（`''set:`报告「无法确认」）
+    #[serde(default)]
+    pub set_ack: SetAck,
+
+    /// 终端打印的输出类型
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`["*"]`（打印所有输出）
+    #[serde(default)]
+    pub print_level: PrintLevel,
+
+    /// 禁用彩色输出
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`false`
+    #[serde(default)]
+    pub no_color: bool,
+
+    /// 启动检查的超时时间（毫秒）
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：[`DEFAULT_STARTUP_TIMEOUT`]
+    /// * 📌`0`⇒不检查
+    #[serde(default = "default_startup_timeout")]
+    pub startup_timeout: u64,
+
+    /// NAL测试报告的输出路径（可选）
+    /// * 🚩允许无：不输出报告
+    #[serde(default)]
+    pub report_file: Option<PathBuf>,
+
+    /// 管道输入读完后的宽限期（毫秒）
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：[`DEFAULT_PIPED_INPUT_GRACE`]
+    /// * 📌`0`⇒读完即退出
+    #[serde(default = "default_piped_input_grace")]
+    pub piped_input_grace: u64,
+
+    /// 用户输入的历史记录文件（可选）
+    /// * 🚩允许无：不持久化输入历史
+    #[serde(default)]
+    pub history_file: Option<PathBuf>,
+
+    /// NAL预期的默认精度（可选）
+    /// * 🚩允许无：精确比对
+    #[serde(default)]
+    pub precision_epoch: Option<FloatPrecision>,
+
+    /// 输出类型的别名
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`{}`（无别名）
+    #[serde(default)]
+    pub output_type_aliases: OutputTypeAliases,
+
+    /// 是否抑制输入回显
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`false`（保留回显）
+    #[serde(default)]
+    pub suppress_input_echo: bool,
+
+    /// `''save-outputs`保留的文件数（可选）
+    /// * 🚩[`None`]⇒不清理旧文件
+    #[serde(default)]
+    pub save_outputs_keep: Option<usize>,
+
+    /// `''save-outputs`是否允许绝对路径
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`false`
+    #[serde(default)]
+    pub save_outputs_allow_absolute: bool,
+
+    /// CIN所支持的功能
+    /// * 🚩不来自启动配置：启动时按转译器附加，见[`Capabilities`]
+    /// * 📜默认值：支持所有功能
+    #[serde(default)]
+    pub capabilities: Capabilities,
+
+    /// CYC分批（可选）
+    /// * 🚩允许无：不分批
+    #[serde(default)]
+    pub cyc_pacing: Option<LaunchConfigCycPacing>,
+
+    /// 是否等待CYC步进完成
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`false`
+    #[serde(default)]
+    pub cyc_await: bool,
+
+    /// 是否合并跨越多行的输出
+    /// * 🚩必选：[`None`]将视为默认值
+    /// * 📜默认值：`false`
+    #[serde(default)]
+    pub coalesce_lines: bool,
+
+    /// 子进程IO的备用编码（可选）
+    /// * 🚩允许无：仅UTF-8
+    #[serde(default)]
+    pub process_encoding: Option<String>,
+
+    /// Python环境（可选）
+    /// * 🚩允许无：使用启动命令本身
+    #[serde(default)]
+    pub python_env: Option<LaunchConfigPythonEnv>,
+}
+
+/// 默认的启动检查超时时间（毫秒）
+/// * 🎯配置解析中的默认值指定
+#[inline(always)]
+fn default_startup_timeout() -> u64 {
+    DEFAULT_STARTUP_TIMEOUT.as_millis() as u64
+}
+
+/// 默认的「管道输入宽限期」（毫秒）
+/// * 🎯留出CIN推理、输出回答的时间
+pub const DEFAULT_PIPED_INPUT_GRACE: u64 = 1000;
+
+/// 默认的「管道输入宽限期」
+/// * 🎯配置解析中的默认值指定
+#[inline(always)]
+fn default_piped_input_grace() -> u64 {
+    DEFAULT_PIPED_INPUT_GRACE
+}
+
+/// 布尔值`true`
+/// * 🎯配置解析中「默认为`true`」的默认值指定
+/// * 📝serde中，`#[serde(default)]`使用的是[`bool::default`]而非容器的`default`
+///   * 因此需要指定一个函数来初始化
+#[inline(always)]
+const fn bool_true() -> bool {
+    true
+}
+
+/// 布尔值`false`
+/// * 🎯配置解析中「默认为`false`」的默认值指定
+/// * 📝serde中，`#[serde(default)]`使用的是[`bool::default`]而非容器的`default`
+///   * 因此需要指定一个函数来初始化（false特别标识）
+#[inline(always)]
+const fn bool_false() -> bool {
+    false
+}
+
+/// 尝试将启动时配置[`LaunchConfig`]转换成运行时配置[`RuntimeConfig`]
+/// * 📌默认项：存在默认值，如「启用用户输入」「不自动重启」
+/// * 📌必选项：要求必填值，如「转译器组」「启动命令」
+///   * ⚠️正是此处可能报错
+/// * 📌可选项：仅为可选值，如「Websocket」「预引入NAL」
+impl TryFrom<LaunchConfig> for RuntimeConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(config: LaunchConfig) -> Result<Self> {
+        Ok(Self {
+            // * 路径承袭：空值自动补默认值（空白）
+            config_path: config.config_path.unwrap_or_default(),
+            // * 🚩必选项统一用`ok_or(..)?`
+            translators: config.translators.ok_or(anyhow!("启动配置缺少转译器"))?,
+            // * 🚩有连接参数⇒无需启动命令
+            command: match config.connect {
+                Some(..) => config.command.unwrap_or_default(),
+                None => config.command.ok_or(anyhow!("启动配置缺少启动命令"))?,
+            },
+            connect: config.connect,
+            // * 🚩可选项直接置入
+            websocket: config.websocket,
+            prelude_nal: config.prelude_nal.unwrap_or_default(),
+            // * 🚩默认项统一用`unwrap_or`
+            // 默认启用用户输入
+            user_input: config.user_input.unwrap_or(true),
+            // 输入模式传递默认值
+            input_mode: config.input_mode.unwrap_or_default(),
+            // 不自动重启
+            auto_restart: config.auto_restart.unwrap_or(false),
+            // 不开启严格模式
+            strict_mode: config.strict_mode.unwrap_or(false),
+            // 输出格式传递默认值
+            output_format: config.output_format.unwrap_or_default(),
+            // 不指定任何回执
+            set_ack: config.set_ack.unwrap_or_default(),
+            // 打印所有输出
+            print_level: config.print_level.unwrap_or_default(),
+            // 启用彩色输出
+            no_color: config.no_color.unwrap_or(false),
+            // 默认的启动检查超时时间
+            startup_timeout: config
+                .startup_timeout
+                .unwrap_or_else(default_startup_timeout),
+            // 默认的管道输入宽限期
+            piped_input_grace: config
+                .piped_input_grace
+                .unwrap_or(DEFAULT_PIPED_INPUT_GRACE),
+            // * 🚩可选项直接置入
+            report_file: config.report_file,
+            history_file: config.history_file,
+            precision_epoch: config.precision_epoch,
+            // 无别名
+            output_type_aliases: config.output_type_aliases.unwrap_or_default(),
+            // 保留回显
+            suppress_input_echo: config.suppress_input_echo.unwrap_or(false),
+            save_outputs_keep: config.save_outputs_keep,
+            // 不允许绝对路径
+            save_outputs_allow_absolute: config.save_outputs_allow_absolute.unwrap_or(false),
+            // 启动时按转译器附加
+            capabilities: Capabilities::default(),
+            cyc_pacing: config.cyc_pacing,
+            // 不等待CYC步进完成
+            cyc_await: config.cyc_await.unwrap_or(false),
+            // 不合并多行输出
+            coalesce_lines: config.coalesce_lines.unwrap_or(false),
+            process_encoding: config.process_encoding,
+            python_env: config.python_env,
+        })
+    }
+}
+
+impl RuntimeConfig {
+    /// 转换为附带来源注释的HJSON
+    /// * 🎯`--print-config`：展示实际生效的配置，及各配置项的来源
+    /// * 🚩以JSON格式序列化（亦为合法的HJSON），并在各顶层配置项前以注释标注来源
+    ///   * 📌无来源⇒标注为默认值
+    /// * 📌其中的路径均已绝对化
+    pub fn to_annotated_hjson(&self, provenance: &ConfigProvenance) -> Result<String> {
+        let json = serde_json::to_string_pretty(self)?;
+        let mut result = String::new();
+        for line in json.lines() {
+            // 顶层配置项：缩进两格
+            if let Some(key) = line.strip_prefix("  \"").and_then(|s| s.split('"').next()) {
+                match provenance.sources(key) {
+                    [] => result.push_str("  // 默认值\n"),
+                    sources => result.push_str(&format!("  // 来源：{}\n", sources.join(", "))),
+                }
+            }
+            result.push_str(line);
+            result.push('\n');
+        }
+        Ok(result)
+    }
+}
+
+/// NAVM实例的输入类型
+/// * 🎯处理用户输入、Websocket输入的解析方式
+/// * 📜默认值：`nal`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+// #[serde(untagged)] // ! 🚩【2024-04-02 18:14:16】不启用方通过：本质上是几个字符串里选一个
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputMode {
+    /// （NAVM）指令
+    /// * 📄类型：[`navm::cmd::Cmd`]
+    #[serde(rename = "cmd")]
+    Cmd,
+    /// `.nal`输入
+    /// * 📜默认值
+    /// * 📄类型：[`babel_nar::test_tools::NALInput`]
+    #[serde(rename = "nal")]
+    #[default]
+    Nal,
+    /// 原始输入
+    /// * 🎯将BabelNAR作为CIN的「薄包装」：输入原样送达CIN，输出照常转译
+    /// * 📄可使用CIN特有的命令，如ONA的`*stats`、PyNARS的`/reset`
+    /// * 🚩不经过NAVM指令与NAL解析
+    #[serde(rename = "raw")]
+    Raw,
+}
+
+/// CLI的输出格式
+/// * 🎯处理NAVM输出的呈现方式
+/// * 📜默认值：`text`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// 人类可读的彩色文本
+    /// * 📜默认值
+    #[serde(rename = "text")]
+    #[default]
+    Text,
+    /// 每行一个JSON对象（NDJSON）
+    /// * 📄格式：[`crate::output_handler::json::output_to_json`]
+    /// * 🚩CLI信息转至标准错误，且不再有交互式提示与退出延时
+    #[serde(rename = "json")]
+    Json,
+}
+
+/// 终端打印的输出类型
+/// * 🎯按NAVM输出类型过滤终端打印
+/// * 📌元素：NAVM输出类型名（忽略大小写），或通配符`"*"`
+/// * 📜默认值：`["*"]`
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintLevel(pub Vec<String>);
+
+impl PrintLevel {
+    /// 通配符：匹配所有输出类型
+    pub const ALL: &'static str = "*";
+
+    /// 所有已知的NAVM输出类型名
+    /// * 🎯用于检查配置中的未知类型名
+    pub const KNOWN_TYPES: &'static [&'static str] = &[
+        type_names::IN,
+        type_names::OUT,
+        type_names::ERROR,
+        type_names::ANSWER,
+        type_names::ACHIEVED,
+        type_names::EXE,
+        type_names::INFO,
+        type_names::COMMENT,
+        type_names::TERMINATED,
+        type_names::OTHER,
+        type_names::ANTICIPATE,
+        NO_ANSWER,
+    ];
+
+    /// 安静模式：只打印「错误」与「回答」
+    /// * 🎯命令行参数`--quiet`
+    pub fn quiet() -> Self {
+        Self(vec![type_names::ERROR.into(), type_names::ANSWER.into()])
+    }
+
+    /// 判断某类型的输出是否应被打印
+    pub fn allows_type(&self, type_name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|name| name == Self::ALL || name.eq_ignore_ascii_case(type_name))
+    }
+
+    /// 判断某输出是否应被打印
+    pub fn allows(&self, output: &Output) -> bool {
+        self.allows_type(output.type_name())
+    }
+
+    /// 并入输出类型的别名
+    /// * 🎯打印「标准名」的输出时，亦打印其别名的输出
+    /// * 🚩别名表中的「标准名」忽略大小写
+    pub fn with_type_aliases(&self, aliases: &OutputTypeAliases) -> Self {
+        let mut names = self.0.clone();
+        for name in &self.0 {
+            for (canonical, alias_names) in aliases {
+                if canonical.eq_ignore_ascii_case(name) {
+                    names.extend(alias_names.iter().cloned());
+                }
+            }
+        }
+        Self(names)
+    }
+
+    /// 获取所有「未知」的类型名
+    /// * 🎯启动时警告：可能为拼写错误
+    pub fn unknown_names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str).filter(|name| {
+            *name != Self::ALL
+                && !Self::KNOWN_TYPES
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+/// 默认：打印所有输出
+impl Default for PrintLevel {
+    fn default() -> Self {
+        Self(vec![Self::ALL.into()])
+    }
+}
+
+/// 转译器组合
+/// * 🚩【2024-04-01 11:20:36】目前使用「字符串+内置模糊匹配」进行有限的「转译器支持」
+/// * ✨亦可指定外部程序作为某一方向的转译器
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)] // 🔗参考：<https://serde.rs/enum-representations.html#untagged>
+#[serde(rename_all = "camelCase")] // 🔗参考：<https://serde.rs/container-attrs.html>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchConfigTranslators {
+    /// 🚩单个字符串⇒输入输出使用同一个转译配置
+    Same(String),
+
+    /// 🚩一个对象⇒输入和输出分别使用不同的转译配置
+    Separated {
+        #[serde(rename = "in")]
+        input: String,
+        #[serde(rename = "out")]
+        output: String,
+    },
+
+    /// 🚩带`exec`的对象⇒某一方向使用外部程序转译
+    Exec(LaunchConfigExecTranslator),
+}
+
+/// 基于外部程序的转译器
+/// * 🎯为尚无内置转译器的CIN提供转译：无需重新编译BabelNAR
+/// * 📌外部程序逐行读写
+///   * 输出方向：读入CIN原始输出，回复NAVM_JSON
+///   * 输入方向：读入NAVM指令的JSON，回复CIN输入（空行⇒不输入）
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchConfigExecTranslator {
+    /// 外部程序
+    /// * 📌相对路径：相对于CIN的工作目录
+    pub exec: String,
+
+    /// 外部程序的参数（可选）
+    #[serde(default)]
+    pub exec_args: Option<Vec<String>>,
+
+    /// 外部程序负责的转译方向
+    pub direction: TranslatorDirection,
+
+    /// 另一方向使用的转译器预设（可选）
+    /// * 🚩默认为原生转译器
+    #[serde(default)]
+    pub preset: Option<String>,
+}
+
+/// 转译方向
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslatorDirection {
+    /// 输入：NAVM指令⇒CIN输入
+    In,
+    /// 输出：CIN输出⇒NAVM输出
+    Out,
+}
+
+/// 启动命令
+/// * ❓后续可能支持「自动搜索」
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")] // 🔗参考：<https://serde.rs/container-attrs.html>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchConfigCommand {
+    /// 命令
+    /// * 直接对应[`std::process::Command`]
+    /// * 🚩[`Default`]中默认对应空字串
+    /// * 📌jar文件⇒以Java启动：`java [..命令参数] -jar [jar文件]`
+    pub cmd: String,
+
+    /// 命令的参数（可选）
+    /// * 📌命令为jar文件时，作为JVM参数置于`-jar`之前
+    pub cmd_args: Option<Vec<String>>,
+
+    /// 工作目录（可选）
+    /// * 🎯可用于Python模块
+    /// * 🚩【2024-04-07 10:13:59】现在用于「基于配置文件的相对路径」
+    ///   * 📌被主程序在启动时用于「设置自身工作目录」
+    pub current_dir: Option<PathBuf>,
+
+    /// 附加的环境变量（可选）
+    /// * 🎯`JAVA_TOOL_OPTIONS`、`PYTHONIOENCODING=utf-8`、`NODE_OPTIONS`等
+    /// * 🚩在继承BabelNAR自身环境变量的基础上追加、覆盖
+    #[serde(default)]
+    pub env: Option<BTreeMap<String, String>>,
+}
+
+/// 连接参数
+/// * 🎯连接到已在运行的CIN：如BabelNAR.jl等在Websocket后运行的CIN
+/// * 🚩发送转译后的指令，接收并转译其输出
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchConfigConnect {
+    /// 主机地址
+    /// * 📄`localhost`
+    /// * 📄`192.168.0.0`
+    pub host: String,
+
+    /// 连接端口
+    pub port: u16,
+}
+
+/// CYC分批参数
+/// * 🎯将大步数的`CYC`拆成多批输入，批间等待
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LaunchConfigCycPacing {
+    /// 每批的推理步数
+    /// * 📌`0`⇒不分批
+    pub chunk: usize,
+
+    /// 两批之间的间隔（毫秒）
+    #[serde(default)]
+    pub interval_ms: u64,
+}
+
+/// Python环境参数
+/// * 🎯指定基于Python的CIN所用的解释器与模块搜索路径
+/// * 🚩解释器优先级：解释器路径 > 虚拟环境 > 启动命令
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchConfigPythonEnv {
+    /// Python解释器路径（可选）
+    /// * 📄`/opt/conda/envs/nars/bin/python`
+    #[serde(default)]
+    pub python_path: Option<PathBuf>,
+
+    /// 虚拟环境目录（可选）
+    /// * 📄`.venv`⇒`.venv/bin/python`（Windows：`.venv\Scripts\python.exe`）
+    #[serde(default)]
+    pub venv: Option<PathBuf>,
+
+    /// 附加的`PYTHONPATH`（可选）
+    #[serde(default)]
+    pub extra_paths: Option<Vec<PathBuf>>,
+}
+
+/// Websocket参数
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")] // 🔗参考：<https://serde.rs/container-attrs.html>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchConfigWebsocket {
+    /// 主机地址
+    /// * 📄`localhost`
+    /// * 📄`192.168.0.0`
+    /// * 📄`fe80::abcd:fade:dad1`
+    pub host: String,
+
+    /// 连接端口
+    /// * 🚩采用十六位无符号整数
+    ///   * 📄范围：0 ~ 65535
+    ///   * 🔗参考：<https://zh.wikipedia.org/wiki/通訊埠>
+    pub port: u16,
+
+    /// 每个客户端的「待发消息队列」容量（可选）
+    /// * 🎯背压处理：客户端接收过慢时，丢弃其最旧的消息，而非阻塞运行时
+    /// * 📜默认值：[`crate::DEFAULT_CLIENT_QUEUE_SIZE`]
+    #[serde(default)]
+    pub queue_size: Option<usize>,
+
+    /// 心跳间隔（秒）（可选）
+    /// * 🎯及时断开失去响应的客户端：半开的TCP连接不再长期占用连接数
+    /// * 🚩每隔此时长向客户端发送ping；连续多次未收到pong⇒断开连接
+    /// * 📌`0`⇒不发送心跳
+    /// * 📜默认值：[`crate::DEFAULT_HEARTBEAT_SECS`]
+    #[serde(default)]
+    pub heartbeat_secs: Option<u64>,
+
+    /// 最大连接数（可选）
+    /// * 📜默认值：[`crate::DEFAULT_MAX_CONNECTIONS`]
+    #[serde(default)]
+    pub max_connections: Option<usize>,
+
+    /// 每个客户端每秒最多输入的条数（可选）
+    /// * 🎯限流：避免客户端输入过快，使CIN的标准输入、输入通道无限膨胀
+    /// * 🚩每个客户端一个「令牌桶」：容量与速率均为此值
+    /// * 📌仅限制Websocket输入，不影响用户输入
+    /// * 📜默认值：不限制
+    #[serde(default)]
+    pub max_inputs_per_second: Option<u32>,
+
+    /// 输入超出限制时的处理策略（可选）
+    /// * 📜默认值：[`RateLimitPolicy::Reject`]
+    #[serde(default)]
+    pub rate_limit_policy: Option<RateLimitPolicy>,
+
+    /// [`RateLimitPolicy::Queue`]策略下，每个客户端最多积压的输入条数（可选）
+    /// * 📜默认值：[`crate::DEFAULT_INPUT_BACKLOG`]
+    #[serde(default)]
+    pub input_backlog: Option<usize>,
+}
+
+/// Websocket输入超出限制时的处理策略
+/// * 📜默认值：`reject`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitPolicy {
+    /// 积压到有界的队列中，待令牌恢复后再输入
+    /// * 🚩队列中相邻的`CYC`会被合并
+    /// * 🚩队列已满⇒拒绝
+    #[serde(rename = "queue")]
+    Queue,
+    /// 直接拒绝，并向该客户端回复`ERROR`
+    /// * 📜默认值
+    #[serde(rename = "reject")]
+    #[default]
+    Reject,
+}
+
+/// 预置NAL
+/// * 🚩在CLI启动后自动执行
+/// * 📝[`serde`]允许对枚举支持序列化/反序列化
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")] // 🔗参考：<https://serde.rs/container-attrs.html>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchConfigPreludeNAL {
+    /// 从文件路径导入
+    /// * 📌键名：`file`
+    /// * 📌类型：路径
+    #[serde(rename = "file")]
+    File(PathBuf),
+
+    /// 从文本解析
+    /// * 📌键名：`text`
+    /// * 📌类型：纯文本（允许换行等）
+    #[serde(rename = "text")]
+    Text(String),
+}
+
+/// 「单个或多个」值
+/// * 🎯反序列化时兼容「单个预置NAL」与「预置NAL列表」两种写法
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> From<OneOrMany<T>> for Vec<T> {
+    fn from(value: OneOrMany<T>) -> Self {
+        match value {
+            OneOrMany::One(one) => vec![one],
+            OneOrMany::Many(many) => many,
+        }
+    }
+}
+
+/// 反序列化「预置NAL」：单个⇒单元素列表
+fn deserialize_prelude_nal<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Vec<LaunchConfigPreludeNAL>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<OneOrMany<LaunchConfigPreludeNAL>>::deserialize(deserializer)?;
+    Ok(value.map(Vec::from))
+}
+
+/// 反序列化「预置NAL」：单个⇒单元素列表，空值⇒空列表
+fn deserialize_prelude_nal_list<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Vec<LaunchConfigPreludeNAL>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(deserialize_prelude_nal(deserializer)?.unwrap_or_default())
+}
+
+/// 启动配置
+impl LaunchConfig {
+    /// 零参构造函数
+    /// * 🚩使用[`Default`]提供默认空数据
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 判断配置是否为空
+    /// * 📌本质：判断字段是否全为[`None`]
+    /// * 🚩直接与「空配置」相匹配
+    pub fn is_empty(&self) -> bool {
+        self == &EMPTY_LAUNCH_CONFIG
+    }
+
+    /// （尝试）从(H)JSON字符串构造
+    /// * 🚩【2024-04-04 03:43:01】现在使用[`deser_hjson`]兼容`json`且一并兼容`hjson`
+    /// * 🔗有关`hjson`格式：<https://hjson.github.io>
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        Ok(deser_hjson::from_str(json)?)
+    }
+
+    /// 判断其自身是否需要用户填充
+    /// * 🎯用于在「启动NAVM运行时」时避免「参数无效」情况
+    /// * 📌原则：必填参数不能为空
+    /// * 🚩判断「启动时必要项」是否为空
+    pub fn need_polyfill(&self) -> bool {
+        !self.missing_required_fields().is_empty()
+    }
+
+    /// 列出缺省的「启动时必要项」
+    /// * 🎯仅就真正缺失的项要求用户填充
+    /// * 📄`["command", "translators"]`
+    pub fn missing_required_fields(&self) -> Vec<&'static str> {
+        let mut missing = vec![];
+        // 多实例⇒各实例在启动时检查
+        if_return! { !self.instance_configs().is_empty() => missing }
+        // 启动命令非空 | 有连接参数⇒无需启动命令
+        if self.command.is_none() && self.connect.is_none() {
+            missing.push("command");
+        }
+        // 输入输出转译器非空
+        if self.translators.is_none() {
+            missing.push("translators");
+        }
+        // ! Websocket为空⇒不启动Websocket服务器
+        // ! 预加载NAL为空⇒不预加载NAL
+        missing
+    }
+
+    /// 变基一个相对路径
+    /// * 🚩将`config_path`的路径作为自身[`Path::is_relative`]的根路径
+    ///   * 📌引入[`Path::canonicalize`]解决「`path/test/../a` => `path/a`」的问题
+    /// * 📌总是将相对路径（按照以`config_path`为根路径）展开成绝对路径
+    #[inline(always)]
+    pub fn rebase_relative_path(config_path: &Path, relative_path: &mut PathBuf) -> Result<()> {
+        // 若`relative_path`非相对路径，直接返回
+        if_return! { relative_path.is_absolute() => Ok(()) }
+        // 先绝对化「配置根路径」
+        let mut new_path = config_path.canonicalize()?;
+        // 遍历「相对路径」的组分，追加/上溯路径
+        for component in relative_path.components() {
+            match component {
+                // 当前文件夹⇒跳过
+                Component::CurDir => continue,
+                // 上一级文件夹⇒上溯
+                Component::ParentDir => {
+                    new_path.pop();
+                }
+                // 其它⇒增加组分
+                _ => new_path.push(component),
+            }
+        }
+
+        // * ❌无法通过真正治本的「前缀替换」行事：[`PrefixComponent`]全为私有字段，无法构建⇒无法构建`Component`
+        // let new_path = new_path
+        //     .components()
+        //     .map(|com| match com {
+        //         Component::Prefix(prefix) => {
+        //             let prefix = match prefix.kind() {
+        //                 Prefix::VerbatimUNC(a, b) => Prefix::UNC(a, b),
+        //                 Prefix::VerbatimDisk(name) => Prefix::Disk(name),
+        //                 kind => kind,
+        //             };
+        //             Component::from(prefix)
+        //         }
+        //         _ => com,
+        //     })
+        //     .collect::<PathBuf>();
+
+        // 转换回字符串，然后删除`canonicalize`产生的多余前缀
+        // * ⚠️【2024-04-07 13:51:16】删除原因：JVM、Python等启动命令不能处理带`\\?\【盘符】:`、`\\.\【盘符】:`前缀的路径
+        //   * 📌即便其实际上为「Verbatim UNC prefixes」
+        // * 🔗参考：<https://rust.ffactory.org/std/path/enum.Prefix.html>
+        // * 🔗参考：<https://users.rust-lang.org/t/understanding-windows-paths/58583>
+        // 赋值
+        *relative_path = trim_verbatim_prefix(new_path);
+        Ok(())
+    }
+
+    /// 变基配置中所含的路径，从其它地方变为「以配置文件自身为根」的绝对路径
+    /// * 🎯解决「配置中的**相对路径**仅相对于exe而非配置文件本身」的问题
+    /// * 🎯将配置中相对路径的**根目录**从「exe」变更到配置文件本身
+    /// * 📌原则：由此消灭所有相对路径，均以「配置文件自身路径」为根，转换为绝对路径
+    /// * 一同决定的还有其中的[`Self::config_path`]字段
+    pub fn rebase_relative_path_from(&mut self, config_path: &Path) -> Result<()> {
+        // 配置所在目录
+        if let Some(root) = config_path.parent() {
+            self.config_path = Some(root.to_path_buf());
+        }
+        // 预加载NAL
+        for prelude_nal in self.prelude_nal.iter_mut().flatten() {
+            if let LaunchConfigPreludeNAL::File(ref mut path) = prelude_nal {
+                Self::rebase_relative_path(config_path, path)?;
+            }
+        }
+        // 测试报告
+        if let Some(ref mut path) = self.report_file {
+            Self::rebase_relative_path(config_path, path)?;
+        }
+        // 输入历史
+        if let Some(ref mut path) = self.history_file {
+            Self::rebase_relative_path(config_path, path)?;
+        }
+        // Python环境 | 仅含文件名的解释器（如`python3`）交由`PATH`查找
+        if let Some(python_env) = &mut self.python_env {
+            if let Some(path) = &mut python_env.python_path {
+                if path.components().count() > 1 {
+                    Self::rebase_relative_path(config_path, path)?;
+                }
+            }
+            for path in python_env.venv.iter_mut() {
+                Self::rebase_relative_path(config_path, path)?;
+            }
+            for path in python_env.extra_paths.iter_mut().flatten() {
+                Self::rebase_relative_path(config_path, path)?;
+            }
+        }
+        // 各实例
+        for instance in self.instances.iter_mut().flat_map(BTreeMap::values_mut) {
+            instance.rebase_relative_path_from(config_path)?;
+        }
+        // 启动命令
+        if let Some(LaunchConfigCommand {
+            current_dir: Some(ref mut path),
+            ..
+        }) = &mut self.command
+        {
+            Self::rebase_relative_path(config_path, path)?;
+        }
+        // 返回成功
+        Ok(())
+    }
+
+    /// 变基路径，但基于所有权
+    /// * 📌总体逻辑：[`Self`]→[`Self`]
+    /// * ⚠️有可能会出错（引入[`Path::canonicalize`]）
+    pub fn rebase_path_from_owned(mut self, config_path: &Path) -> Result<Self> {
+        self.rebase_relative_path_from(config_path)?;
+        Ok(self)
+    }
+
+    /// 展开配置中的占位符
+    /// * 🎯配置在不同机器间可移植：启动命令、工作目录无需写死绝对路径
+    /// * 📌支持的占位符见[`expand_placeholders`]
+    /// * 📌展开范围：`command`的`cmd`、`cmdArgs`各项、`currentDir`、`env`各值，以及`preludeNAL`中的`file`
+    ///   * 🚩各实例一并展开
+    /// * ⚠️须在[变基路径](Self::rebase_relative_path_from)之前调用：展开后的相对路径仍以配置文件为根
+    pub fn expand_placeholders(&mut self, config_dir: &Path) -> Result<()> {
+        self.expand_placeholders_with_key(config_dir, "")
+    }
+
+    /// 展开占位符，但基于所有权
+    pub fn expand_placeholders_owned(mut self, config_dir: &Path) -> Result<Self> {
+        self.expand_placeholders(config_dir)?;
+        Ok(self)
+    }
+
+    /// 展开占位符
+    /// * 🎯出错时指明所在的键：`instances.ona.command.cmd`
+    fn expand_placeholders_with_key(&mut self, config_dir: &Path, key_prefix: &str) -> Result<()> {
+        // 启动命令
+        if let Some(command) = &mut self.command {
+            let key = format!("{key_prefix}command");
+            command.cmd = expand_placeholders(&command.cmd, &format!("{key}.cmd"), config_dir)?;
+            for (i, arg) in command.cmd_args.iter_mut().flatten().enumerate() {
+                *arg = expand_placeholders(arg, &format!("{key}.cmdArgs[{i}]"), config_dir)?;
+            }
+            if let Some(path) = &mut command.current_dir {
+                expand_placeholders_path(path, &format!("{key}.currentDir"), config_dir)?;
+            }
+            for (name, value) in command.env.iter_mut().flatten() {
+                *value = expand_placeholders(value, &format!("{key}.env.{name}"), config_dir)?;
+            }
+        }
+        // 预加载NAL
+        for (i, prelude_nal) in self.prelude_nal.iter_mut().flatten().enumerate() {
+            if let LaunchConfigPreludeNAL::File(path) = prelude_nal {
+                let key = format!("{key_prefix}preludeNAL[{i}].file");
+                expand_placeholders_path(path, &key, config_dir)?;
+            }
+        }
+        // 各实例
+        for (name, instance) in self.instances.iter_mut().flatten() {
+            let key_prefix = format!("{key_prefix}instances.{name}.");
+            instance.expand_placeholders_with_key(config_dir, &key_prefix)?;
+        }
+        Ok(())
+    }
+
+    /// 从另一个配置中并入配置
+    /// * 📌优先级：`self` > `other`
+    /// * 🚩合并逻辑：`Some(..)` => `None`
+    ///   * 当并入者为`Some`，自身为`None`时，合并`Some`中的值
+    /// * ✨对【内部含有可选键】的值，会**递归深入**
+    /// * 🎯用于「仅填补缺省项」的场合：默认配置文件、用户手动指定的配置文件
+    pub fn merge_from(&mut self, other: &Self) {
+        // 合并所有内部Option | 使用工具宏简化语法
+        launch_config_keys!(coalesce_clones! { other => self });
+        // 递归合并所有【含有可选键】的值
+        LaunchConfigCommand::merge_as_key(&mut self.command, &other.command);
+        // 拼接「预置NAL」：先自身，后并入者
+        if let Some(other_prelude_nal) = &other.prelude_nal {
+            self.prelude_nal
+                .get_or_insert_with(Vec::new)
+                .extend(other_prelude_nal.iter().cloned());
+        }
+    }
+
+    /// 以另一个配置覆盖自身
+    /// * 📌优先级：`other` > `self`
+    /// * 🚩覆盖逻辑：并入者中显式设置（`Some`）的值总是覆盖自身，未设置（`None`）的保留自身
+    ///   * 📄`-c base.hjson -c test.hjson`：`test`中的`userInput: true`可覆盖`base`中的`false`
+    /// * ✨对【内部含有可选键】的值，会**递归深入**
+    /// * 🎯用于「命令行中先后指定的多个配置文件」：后指定者优先
+    pub fn override_from(&mut self, other: &Self) {
+        // 覆盖所有内部Option | 使用工具宏简化语法
+        launch_config_keys!(override_clones! { other => self });
+        // 递归覆盖所有【含有可选键】的值
+        LaunchConfigCommand::override_as_key(&mut self.command, &other.command);
+        // 拼接「预置NAL」：先自身，后并入者
+        if let Some(other_prelude_nal) = &other.prelude_nal {
+            self.prelude_nal
+                .get_or_insert_with(Vec::new)
+                .extend(other_prelude_nal.iter().cloned());
+        }
+    }
+
+    /// 从另一个配置中并入配置，并记录来源
+    /// * 🚩只记录实际被并入（有所改变）的配置项
+    /// * 📌合并逻辑同[`Self::merge_from`]
+    pub fn merge_from_tracked(
+        &mut self,
+        other: &Self,
+        source: &str,
+        provenance: &mut ConfigProvenance,
+    ) {
+        let before = self.clone();
+        self.merge_from(other);
+        provenance.track_changes(&before, self, source);
+    }
+
+    /// 以另一个配置覆盖自身，并记录来源
+    /// * 🚩并入者中显式设置的配置项，均记作由其提供
+    /// * 📌覆盖逻辑同[`Self::override_from`]
+    pub fn override_from_tracked(
+        &mut self,
+        other: &Self,
+        source: &str,
+        provenance: &mut ConfigProvenance,
+    ) {
+        self.override_from(other);
+        for (key, value) in config_fields(other) {
+            if !value.is_null() {
+                provenance.record(&key, source);
+            }
+        }
+    }
+
+    /// 展开各具名实例的完整配置
+    /// * 🚩以外层配置（去掉`instances`）为基础，用实例配置覆盖
+    /// * 🚩测试报告路径附加实例名：`report.xml` ⇒ `report.ona.xml`
+    ///   * 🎯避免各实例的报告相互覆盖
+    /// * 📌按名称排序；无实例⇒空列表
+    pub fn instance_configs(&self) -> Vec<(String, LaunchConfig)> {
+        let Some(instances) = &self.instances else {
+            return vec![];
+        };
+        let base = LaunchConfig {
+            instances: None,
+            ..self.clone()
+        };
+        instances
+            .iter()
+            .map(|(name, instance)| {
+                let mut config = base.clone();
+                config.override_from(instance);
+                if let Some(path) = &mut config.report_file {
+                    *path = Self::instance_report_file(path, name);
+                }
+                (name.clone(), config)
+            })
+            .collect()
+    }
+
+    /// 为测试报告路径附加实例名
+    /// * 📄`report.xml` ⇒ `report.ona.xml`；`report` ⇒ `report.ona`
+    fn instance_report_file(path: &Path, name: &str) -> PathBuf {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let file_name = match path.extension() {
+            Some(ext) => format!("{stem}.{name}.{}", ext.to_string_lossy()),
+            None => format!("{stem}.{name}"),
+        };
+        path.with_file_name(file_name)
+    }
+}
+
+impl LaunchConfigCommand {
+    /// 从另一个配置中并入配置
+    /// * 🚩`Some(..)` => `None`
+    pub fn merge_from(&mut self, other: &Self) {
+        coalesce_clones! {
+            other => self;
+            cmd_args
+            current_dir
+            env
+        }
+    }
+
+    /// 作为一个键，从另一个配置中并入配置
+    /// * 🚩`Some(..)` => `None`
+    /// * 适用于自身为[`Option`]的情况
+    pub fn merge_as_key(option: &mut Option<Self>, other: &Option<Self>) {
+        // 先处理「自身为`None`」的情况
+        option.coalesce_clone(other);
+        // 双重`inspect`
+        if let (Some(config_self), Some(config_other)) = (option, other) {
+            config_self.merge_from(config_other);
+        }
+    }
+
+    /// 以另一个配置覆盖自身
+    /// * 🚩命令本身总是被覆盖，可选的参数与工作目录仅在并入者显式设置时覆盖
+    pub fn override_from(&mut self, other: &Self) {
+        self.cmd.clone_from(&other.cmd);
+        override_clones! {
+            other => self;
+            cmd_args
+            current_dir
+            env
+        }
+    }
+
+    /// 作为一个键，以另一个配置覆盖自身
+    /// * 🚩并入者为`None`⇒保留自身
+    /// * 适用于自身为[`Option`]的情况
+    pub fn override_as_key(option: &mut Option<Self>, other: &Option<Self>) {
+        match (option, other) {
+            (Some(config_self), Some(config_other)) => config_self.override_from(config_other),
+            (option @ None, Some(config_other)) => *option = Some(config_other.clone()),
+            (_, None) => {}
+        }
+    }
+}
+
+/// 配置来源
+/// * 🎯`--print-config`：标注生效配置中各顶层配置项由何处提供
+/// * 📌键：顶层配置项（同配置文件中的键名）；值：依次提供该项的来源
+///   * 📄来源：配置文件路径、`命令行参数`、`用户输入`
+///   * 📌可拼接、递归合并的配置项（如`preludeNAL`）可有多个来源
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigProvenance(BTreeMap<String, Vec<String>>);
+
+/// 可由多个来源共同提供的配置项
+/// * 📌与[`LaunchConfig::merge_from`]中拼接、递归合并的键一致
+const COMBINED_CONFIG_KEYS: &[&str] = &["command", "preludeNAL"];
+
+/// 启动配置的各顶层配置项
+/// * 🚩经由序列化获取：与配置文件中的键名一致，且无需随新增字段维护
+/// * 📌未设置的配置项为`null`
+fn config_fields(config: &LaunchConfig) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(config) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    }
+}
+
+impl ConfigProvenance {
+    /// 零参构造函数
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 某配置项的来源
+    /// * 🚩无来源⇒空：未被任何来源设置，取默认值
+    pub fn sources(&self, key: &str) -> &[String] {
+        self.0.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// 记录某配置项的来源
+    /// * 🚩可共同提供的配置项⇒追加；其它⇒替换
+    pub fn record(&mut self, key: &str, source: &str) {
+        let sources = self.0.entry(key.to_string()).or_default();
+        if !COMBINED_CONFIG_KEYS.contains(&key) {
+            sources.clear();
+        }
+        if !sources.iter().any(|s| s == source) {
+            sources.push(source.to_string());
+        }
+    }
+
+    /// 比对前后两个配置，将有所改变的配置项记作由`source`提供
+    /// * 🎯记录命令行参数、用户输入等对配置的修改
+    pub fn track_changes(&mut self, before: &LaunchConfig, after: &LaunchConfig, source: &str) {
+        let before = config_fields(before);
+        for (key, value) in config_fields(after) {
+            if before.get(&key) != Some(&value) {
+                self.record(&key, source);
+            }
+        }
+    }
+}
+
+/// 从外部JSON文件中加载启动配置
+/// * 🎯错误处理 & 错误⇒空置
+/// * 🚩在遇到错误时会发出警告
+/// * ⚠️若无需打印警告（并手动处理错误），请使用[`read_config_extern`]
+/// * ⚠️其中的所有**相对路径**，在[`read_config_extern`]中都基于**配置文件自身**
+///   * 🎯不论CLI自身所处何处，均保证配置读取稳定
+pub fn load_config_extern(path: &Path) -> Option<LaunchConfig> {
+    // Ok⇒Some，Err⇒警告+None
+    let warn = |message: &str| OutputType::Warn.print_line(message);
+    read_config_extern(path).ok_or_run(|e| {
+        // 根据错误类型进行分派 //
+        // 文件读写错误
+        if let Some(e) = e.downcast_ref::<std::io::Error>() {
+            match e.kind() {
+                std::io::ErrorKind::NotFound => {
+                    warn(&format!("未在路径 {path:?} 找到外部配置，返回空配置……"));
+                }
+                _ => warn(&format!("读取外部配置时出现预期之外的错误: {}", e)),
+            }
+        }
+        // 配置解析错误/serde
+        else if let Some(e) = e.downcast_ref::<serde_json::Error>() {
+            match e.classify() {
+                serde_json::error::Category::Syntax => {
+                    warn("外部配置文件格式错误，返回空配置……");
+                }
+                _ => warn(&format!("解析外部配置时出现预期之外的错误: {}", e)),
+            }
+        }
+        // 配置解析错误/hjson
+        else if let Some(e) = e.downcast_ref::<deser_hjson::Error>() {
+            match e {
+                deser_hjson::Error::Syntax { .. } => {
+                    warn("外部配置文件格式错误，使用空配置……");
+                }
+                deser_hjson::Error::Io { .. } => {
+                    warn("外部配置文件读取错误，使用空配置……");
+                }
+                _ => warn(&format!("解析外部配置时出现预期之外的错误: {}", e)),
+            }
+        }
+        // 其它
+        else {
+            warn(&format!("加载外部配置时出现预期之外的错误: {}", e));
+        }
+        // 空置
+    })
+}
+
+/// 从外部JSON文件中读取启动配置
+/// * 🎯仅涉及具体读取逻辑，不涉及错误处理
+/// * ⚠️其中的所有**相对路径**，在[`read_config_extern`]中都基于**配置文件自身**
+///   * 🎯不论CLI自身所处何处，均保证配置读取稳定
+/// * 🚩先[展开占位符](LaunchConfig::expand_placeholders)，再变基相对路径
+pub fn read_config_extern(path: &Path) -> Result<LaunchConfig> {
+    // 配置文件所在目录 | 用于`${configDir}`
+    let config_dir = config_dir_of(path);
+    // 尝试读取外部启动配置，并尝试解析
+    pipe! {
+        path
+        // 尝试补全路径
+        => try_complete_path
+        // 尝试读取文件内容
+        => read_to_string
+        => {?}#
+        // 尝试解析JSON配置
+        => #{&}
+        => LaunchConfig::from_json_str
+        => {?}#
+        // 展开占位符 | ⚠️须在变基之前
+        => .expand_placeholders_owned(&config_dir)
+        => {?}#
+        // 变基相对路径，从「基于CLI自身」到「基于配置文件自身」
+        => .rebase_path_from_owned(path.parent().ok_or(anyhow!("无效的根路径！"))?)
+        => {?}#
+        // 返回Ok（转换为`anyhow::Result`）
+        => Ok
+    }
+    // ! 若需使用`confy`，必须封装
+    // * 🚩目前无需使用`confy`：可以自动创建配置文件，但个人希望其路径与exe同目录
+    // Ok(confy::load_path(path)?) // ! 必须封装
+}
+
+/// 配置文件所在目录的绝对路径
+/// * 🎯作为占位符`${configDir}`的值
+/// * 🚩无法绝对化（如目录不存在）⇒原样返回
+fn config_dir_of(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    match dir.canonicalize() {
+        Ok(dir) => trim_verbatim_prefix(dir),
+        Err(..) => dir.to_path_buf(),
+    }
+}
+
+/// 删除[`Path::canonicalize`]产生的多余前缀
+/// * ⚠️JVM、Python等启动命令不能处理带`\\?\【盘符】:`、`\\.\【盘符】:`前缀的路径
+fn trim_verbatim_prefix(path: PathBuf) -> PathBuf {
+    match path.to_str() {
+        Some(s) => s
+            .trim_start_matches(r"\\?\")
+            .trim_start_matches(r"\\.\")
+            .into(),
+        None => path,
+    }
+}
+
+/// 展开字符串中的占位符
+/// * 📌支持的占位符
+///   * `${ENV:变量名}`：环境变量，如`${ENV:JAVA_HOME}`
+///   * `${configDir}`：配置文件所在目录
+///   * `${exeDir}`：CLI可执行文件所在目录
+/// * 🚩未知的占位符、未设置的环境变量⇒报错，并指明所在的键
+/// * 📌未闭合的`${`原样保留
+/// * 📄`${configDir}/nars.jar` => `/home/user/configs/nars.jar`
+pub fn expand_placeholders(value: &str, key: &str, config_dir: &Path) -> Result<String> {
+    expand_placeholders_with_env(value, key, config_dir, |var| std::env::var(var))
+}
+
+/// 展开字符串中的占位符，从指定来源读取环境变量
+/// * 🎯测试时无需修改进程的环境变量
+/// * 🚩其余同[`expand_placeholders`]
+pub fn expand_placeholders_with_env(
+    value: &str,
+    key: &str,
+    config_dir: &Path,
+    env_var: impl Fn(&str) -> Result<String, std::env::VarError>,
+) -> Result<String> {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = &rest[start + 2..start + len];
+        let expanded = match name {
+            "configDir" => config_dir.to_string_lossy().into_owned(),
+            "exeDir" => std::env::current_exe()?
+                .parent()
+                .ok_or(anyhow!(
+                    "配置键「{key}」中的变量「{name}」无法展开：找不到可执行文件所在目录"
+                ))?
+                .to_string_lossy()
+                .into_owned(),
+            _ => match name.strip_prefix("ENV:") {
+                Some(var) => env_var(var).map_err(|e| {
+                    anyhow!("配置键「{key}」中的变量「{name}」无法展开：环境变量「{var}」{e}")
+                })?,
+                None => return Err(anyhow!("配置键「{key}」中的变量「{name}」未知")),
+            },
+        };
+        result.push_str(&expanded);
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// 展开路径中的占位符
+/// * 🚩非UTF-8路径⇒原样保留
+fn expand_placeholders_path(path: &mut PathBuf, key: &str, config_dir: &Path) -> Result<()> {
+    if let Some(s) = path.to_str() {
+        *path = expand_placeholders(s, key, config_dir)?.into();
+    }
+    Ok(())
+}
+
+/// 尝试对无扩展名的路径添加扩展名
+/// * 🎯用于自动匹配`.json`与`.hjson`
+/// * ❌不能用于「多扩展名」的情况，如`BabelNAR.launch`
+///   * 此处会认定是「有扩展名」而不会补全
+pub fn try_complete_path(path: &Path) -> PathBuf {
+    // 创建路径缓冲区
+    let path = path.to_path_buf();
+    // 当扩展名为空时补全
+    if path.extension().is_none() {
+        // 尝试用已有的扩展名填充文件名
+        for extension in SUPPORTED_CONFIG_EXTENSIONS {
+            // 尝试补全为指定扩展名 | 无扩展名⇒追加，有扩展名⇒替换
+            let path_ = path.with_extension(extension);
+            if_return! { path_.exists() => path_ }
+        }
+    }
+    path
+}
+
+/// 单元测试
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::tests::*;
+    use anyhow::Result;
+    use nar_dev_utils::asserts;
+
+    /// 实用测试宏
+    macro_rules! test_parse {
+        { $( $data:expr => $expected:expr )* } => {
+            $(
+                _test(&$data, &$expected).expect("测试失败");
+            )*
+        };
+    }
+
+    fn _test(data: &str, expected: &LaunchConfig) -> Result<()> {
+        // Some JSON input data as a &str. Maybe this comes from the user.
+        let parsed = LaunchConfig::from_json_str(data)?;
+
+        dbg!(&parsed);
+        assert_eq!(parsed, *expected);
+
+        Ok(())
+    }
+
+    /// 测试/解析
+    /// * 🎯JSON/HJSON的解析逻辑
+    #[test]
+    fn test_parse() {
+        test_parse! {
+            // 平凡情况/空
+            "{}" => LaunchConfig::new()
+            "{}" => LaunchConfig::default()
+            "{}" => EMPTY_LAUNCH_CONFIG
+            // 完整情况
+            r#"
+            {
+                "translators": "opennars",
+                "command": {
+                    "cmd": "java",
+                    "cmdArgs": ["-Xmx1024m", "-jar", "nars.jar"],
+                    "currentDir": "root/nars/test"
+                },
+                "websocket": {
+                    "host": "localhost",
+                    "port": 8080
+                },
+                "preludeNAL": {
+                    "text": "'/VOL 0"
+                }
+            }"# => LaunchConfig {
+                translators: Some(LaunchConfigTranslators::Same("opennars".into())),
+                command: Some(LaunchConfigCommand {
+                    cmd: "java".into(),
+                    cmd_args: Some(vec!["-Xmx1024m".into(), "-jar".into(), "nars.jar".into()]),
+                    current_dir: Some("root/nars/test".into()),
+                    env: None,
+                }),
+                websocket: Some(LaunchConfigWebsocket{
+                    host: "localhost".into(),
+                    port: 8080,
+                    queue_size: None,
+                    heartbeat_secs: None,
+                    max_connections: None,
+                    max_inputs_per_second: None,
+                    rate_limit_policy: None,
+                    input_backlog: None,
+                }),
+                prelude_nal: Some(vec![LaunchConfigPreludeNAL::Text("'/VOL 0".into())]),
+                ..Default::default()
+            }
+            // 测试`translators`、`prelude_nal`的其它枚举
+            r#"
+            {
+                "translators": {
+                    "in": "opennars",
+                    "out": "ona"
+                },
+                "command": {
+                    "cmd": "root/nars/open_ona.exe"
+                },
+                "preludeNAL": {
+                    "file": "root/nars/prelude.nal"
+                }
+            }"# => LaunchConfig {
+                translators: Some(LaunchConfigTranslators::Separated {
+                    input: "opennars".into(),
+                    output: "ona".into()
+                }),
+                command: Some(LaunchConfigCommand {
+                    cmd: "root/nars/open_ona.exe".into(),
+                    ..Default::default()
+                }),
+                prelude_nal: Some(vec![LaunchConfigPreludeNAL::File("root/nars/prelude.nal".into())]),
+                ..Default::default()
+            }
+            // 测试默认精度
+            r#"
+            {
+                "precisionEpoch": 0.01
+            }"# => LaunchConfig {
+                precision_epoch: Some(FloatPrecision(0.01)),
+                ..Default::default()
+            }
+            // 测试输出类型的别名
+            r#"
+            {
+                "outputTypeAliases": { "ANTICIPATE": ["EXPECT", "ANTICIPATION"] }
+            }"# => LaunchConfig {
+                output_type_aliases: Some(OutputTypeAliases::from([(
+                    "ANTICIPATE".into(),
+                    vec!["EXPECT".into(), "ANTICIPATION".into()],
+                )])),
+                ..Default::default()
+            }
+            // 测试抑制输入回显
+            r#"
+            {
+                "suppressInputEcho": true
+            }"# => LaunchConfig {
+                suppress_input_echo: Some(true),
+                ..Default::default()
+            }
+            // 测试「保存输出」的选项
+            r#"
+            {
+                "saveOutputsKeep": 10,
+                "saveOutputsAllowAbsolute": true
+            }"# => LaunchConfig {
+                save_outputs_keep: Some(10),
+                save_outputs_allow_absolute: Some(true),
+                ..Default::default()
+            }
+            // 测试管道输入的宽限期
+            r#"
+            {
+                "pipedInputGrace": 0
+            }"# => LaunchConfig {
+                piped_input_grace: Some(0),
+                ..Default::default()
+            }
+            // 测试CYC分批
+            r#"
+            {
+                "cycPacing": { "chunk": 50, "intervalMs": 20 }
+            }"# => LaunchConfig {
+                cyc_pacing: Some(LaunchConfigCycPacing { chunk: 50, interval_ms: 20 }),
+                ..Default::default()
+            }
+            // 测试「等待CYC步进完成」
+            r#"
+            {
+                "cycAwait": true
+            }"# => LaunchConfig {
+                cyc_await: Some(true),
+                ..Default::default()
+            }
+            // 测试「合并多行输出」
+            r#"
+            {
+                "coalesceLines": true
+            }"# => LaunchConfig {
+                coalesce_lines: Some(true),
+                ..Default::default()
+            }
+            // 测试启动命令的环境变量
+            r#"
+            {
+                "command": {
+                    "cmd": "python",
+                    "env": { "PYTHONIOENCODING": "utf-8" }
+                }
+            }"# => LaunchConfig {
+                command: Some(LaunchConfigCommand {
+                    cmd: "python".into(),
+                    env: Some([("PYTHONIOENCODING".into(), "utf-8".into())].into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+            // 测试「设置」指令的回执：所有指令
+            r#"
+            {
+                "setAck": "ok"
+            }"# => LaunchConfig {
+                set_ack: Some(SetAck::all("ok")),
+                ..Default::default()
+            }
+            // 测试「设置」指令的回执：按指令头分别指定
+            r#"
+            {
+                "setAck": { "vol": "volume", "*": "ok" }
+            }"# => LaunchConfig {
+                set_ack: Some(SetAck::all("ok").with("VOL", "volume")),
+                ..Default::default()
+            }
+            // 测试子进程IO的备用编码
+            r#"
+            {
+                "processEncoding": "gbk"
+            }"# => LaunchConfig {
+                process_encoding: Some("gbk".into()),
+                ..Default::default()
+            }
+            // 测试Python环境
+            r#"
+            {
+                "pythonEnv": {
+                    "venv": ".venv",
+                    "extraPaths": ["../PyNARS"]
+                }
+            }"# => LaunchConfig {
+                python_env: Some(LaunchConfigPythonEnv {
+                    venv: Some(".venv".into()),
+                    extra_paths: Some(vec!["../PyNARS".into()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+            // 测试外部程序转译器
+            r#"
+            {
+                "translators": { "exec": "./my_translator", "direction": "out" }
+            }"# => LaunchConfig {
+                translators: Some(LaunchConfigTranslators::Exec(LaunchConfigExecTranslator {
+                    exec: "./my_translator".into(),
+                    exec_args: None,
+                    direction: TranslatorDirection::Out,
+                    preset: None,
+                })),
+                ..Default::default()
+            }
+            r#"
+            {
+                "inputMode": "cmd"
+            }"# => LaunchConfig {
+                input_mode: Some(InputMode::Cmd),
+                ..Default::default()
+            }
+            r#"
+            {
+                "outputFormat": "json"
+            }"# => LaunchConfig {
+                output_format: Some(OutputFormat::Json),
+                ..Default::default()
+            }
+            r#"
+            {
+                "printLevel": ["ANSWER", "EXE", "ERROR"]
+            }"# => LaunchConfig {
+                print_level: Some(PrintLevel(vec!["ANSWER".into(), "EXE".into(), "ERROR".into()])),
+                ..Default::default()
+            }
+            r#"
+            {
+                "noColor": true
+            }"# => LaunchConfig {
+                no_color: Some(true),
+                ..Default::default()
+            }
+            r#"
+            {
+                "websocket": {
+                    "host": "localhost",
+                    "port": 8080,
+                    "queueSize": 16,
+                    "heartbeatSecs": 10,
+                    "maxConnections": 4,
+                    "maxInputsPerSecond": 20,
+                    "rateLimitPolicy": "queue",
+                    "inputBacklog": 64
+                }
+            }"# => LaunchConfig {
+                websocket: Some(LaunchConfigWebsocket {
+                    host: "localhost".into(),
+                    port: 8080,
+                    queue_size: Some(16),
+                    heartbeat_secs: Some(10),
+                    max_connections: Some(4),
+                    max_inputs_per_second: Some(20),
+                    rate_limit_policy: Some(RateLimitPolicy::Queue),
+                    input_backlog: Some(64),
+                }),
+                ..Default::default()
+            }
+            r#"{
+                "autoRestart": true,
+                "userInput": false
+            }"# => LaunchConfig {
+                auto_restart: Some(true),
+                user_input: Some(false),
+                ..Default::default()
+            }
+            // 预置NAL列表
+            r#"{
+                "preludeNAL": [
+                    { "file": "env.nal" },
+                    { "text": "'/VOL 0" },
+                    { "file": "test.nal" }
+                ]
+            }"# => LaunchConfig {
+                prelude_nal: Some(vec![
+                    LaunchConfigPreludeNAL::File("env.nal".into()),
+                    LaunchConfigPreludeNAL::Text("'/VOL 0".into()),
+                    LaunchConfigPreludeNAL::File("test.nal".into()),
+                ]),
+                ..Default::default()
+            }
+        }
+        /*
+        "file": "root/path/to/file"
+        */
+    }
+
+    /// 测试/打印过滤
+    #[test]
+    fn test_print_level() {
+        let answer = Output::ANSWER {
+            content_raw: "<A --> B>.".into(),
+            narsese: None,
+        };
+        let derived = Output::OUT {
+            content_raw: "<A --> B>.".into(),
+            narsese: None,
+        };
+        // 默认⇒全部打印
+        let all = PrintLevel::default();
+        assert!(all.allows(&answer) && all.allows(&derived));
+        // 指定类型⇒只打印指定的，忽略大小写
+        let level = PrintLevel(vec!["answer".into(), "EXE".into()]);
+        assert!(level.allows(&answer));
+        assert!(!level.allows(&derived));
+        assert!(level.allows_type("EXE"));
+        // 安静模式⇒错误与回答
+        let quiet = PrintLevel::quiet();
+        assert!(quiet.allows(&answer) && quiet.allows_type("ERROR"));
+        assert!(!quiet.allows(&derived) && !quiet.allows_type("INFO"));
+        // 空⇒全不打印
+        assert!(!PrintLevel(vec![]).allows(&answer));
+        // 未知类型名
+        let level = PrintLevel(vec!["*".into(), "answr".into(), "Exe".into()]);
+        assert_eq!(level.unknown_names().collect::<Vec<_>>(), ["answr"]);
+        // 别名⇒一并打印
+        let expect = Output::UNCLASSIFIED {
+            r#type: "EXPECT".into(),
+            content: "EXPECT: <A --> B>".into(),
+            narsese: None,
+        };
+        let aliases = OutputTypeAliases::from([("ANTICIPATE".into(), vec!["EXPECT".into()])]);
+        let level = PrintLevel(vec!["anticipate".into()]);
+        assert!(!level.allows(&expect));
+        assert!(level.with_type_aliases(&aliases).allows(&expect));
+        let quiet = PrintLevel::quiet().with_type_aliases(&aliases);
+        assert!(!quiet.allows(&expect));
+    }
+
+    /// 测试/合并
+    /// * 🎯「预置NAL」按合并顺序拼接，而非覆盖
+    #[test]
+    fn test_merge_prelude_nal() {
+        let file = |path: &str| LaunchConfigPreludeNAL::File(path.into());
+        let mut config = LaunchConfig {
+            prelude_nal: Some(vec![file("env.nal")]),
+            ..Default::default()
+        };
+        config.merge_from(&LaunchConfig::new());
+        config.merge_from(&LaunchConfig {
+            prelude_nal: Some(vec![file("test.nal")]),
+            strict_mode: Some(true),
+            ..Default::default()
+        });
+        asserts! {
+            config.prelude_nal => Some(vec![file("env.nal"), file("test.nal")])
+            config.strict_mode => Some(true)
+        }
+        // 自身为空⇒直接取并入者
+        let mut config = LaunchConfig::new();
+        config.merge_from(&LaunchConfig {
+            prelude_nal: Some(vec![file("test.nal")]),
+            ..Default::default()
+        });
+        assert_eq!(config.prelude_nal, Some(vec![file("test.nal")]));
+    }
+
+    /// 测试/覆盖/后者显式设置的`true`覆盖前者的`false`
+    #[test]
+    fn test_override_true_over_false() {
+        let mut config = LaunchConfig {
+            user_input: Some(false),
+            auto_restart: Some(true),
+            ..Default::default()
+        };
+        config.override_from(&LaunchConfig {
+            user_input: Some(true),
+            auto_restart: Some(false),
+            ..Default::default()
+        });
+        asserts! {
+            config.user_input => Some(true)
+            config.auto_restart => Some(false)
+        }
+    }
+
+    /// 测试/覆盖/后者未设置⇒保留前者
+    #[test]
+    fn test_override_unset_keeps() {
+        let mut config = LaunchConfig {
+            user_input: Some(false),
+            command: Some(LaunchConfigCommand {
+                cmd: "java".into(),
+                cmd_args: Some(vec!["-jar".into(), "nars.jar".into()]),
+                current_dir: None,
+                env: None,
+            }),
+            ..Default::default()
+        };
+        let expected = config.clone();
+        config.override_from(&LaunchConfig::new());
+        assert_eq!(config, expected);
+        // 嵌套的键：仅覆盖显式设置的项
+        config.override_from(&LaunchConfig {
+            command: Some(LaunchConfigCommand {
+                cmd: "java".into(),
+                cmd_args: None,
+                current_dir: Some("root".into()),
+                env: None,
+            }),
+            ..Default::default()
+        });
+        let command = config.command.unwrap();
+        asserts! {
+            command.cmd_args => Some(vec!["-jar".into(), "nars.jar".into()])
+            command.current_dir => Some("root".into())
+            config.user_input => Some(false)
+        }
+    }
+
+    /// 测试/覆盖/三个配置的链式覆盖
+    /// * 🎯模拟`-c a -c b -c c`，最后再以默认配置填补缺省项
+    #[test]
+    fn test_override_chain() {
+        let configs = [
+            LaunchConfig {
+                user_input: Some(false),
+                strict_mode: Some(true),
+                set_ack: Some("a".into()),
+                ..Default::default()
+            },
+            LaunchConfig {
+                user_input: Some(true),
+                no_color: Some(true),
+                ..Default::default()
+            },
+            LaunchConfig {
+                strict_mode: Some(false),
+                set_ack: Some("c".into()),
+                ..Default::default()
+            },
+        ];
+        let mut config = LaunchConfig::new();
+        for other in &configs {
+            config.override_from(other);
+        }
+        // 默认配置：仅填补缺省项
+        config.merge_from(&LaunchConfig {
+            user_input: Some(false),
+            auto_restart: Some(true),
+            ..Default::default()
+        });
+        asserts! {
+            config.user_input => Some(true)
+            config.strict_mode => Some(false)
+            config.set_ack => Some(SetAck::all("c"))
+            config.no_color => Some(true)
+            config.auto_restart => Some(true)
+        }
+    }
+
+    /// 测试/缺失的必要项
+    #[test]
+    fn test_missing_required_fields() {
+        let mut config = LaunchConfig::new();
+        assert_eq!(config.missing_required_fields(), ["command", "translators"]);
+        config.command = Some(LaunchConfigCommand::default());
+        assert_eq!(config.missing_required_fields(), ["translators"]);
+        config.translators = Some(LaunchConfigTranslators::Same("ona".into()));
+        assert!(!config.need_polyfill());
+    }
+
+    /// 测试/展开具名实例
+    /// * 🎯实例配置覆盖外层配置；报告路径附加实例名
+    #[test]
+    fn test_instance_configs() -> Result<()> {
+        let config = LaunchConfig::from_json_str(
+            r#"{
+                "inputMode": "cmd",
+                "reportFile": "report.xml",
+                "instances": {
+                    "ona": { "translators": "ona", "command": { "cmd": "ona" } },
+                    "pynars": { "translators": "pynars", "inputMode": "nal", "command": { "cmd": "pynars" } }
+                }
+            }"#,
+        )?;
+        assert!(config.missing_required_fields().is_empty());
+        let instances = config.instance_configs();
+        let names = instances
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["ona", "pynars"]);
+        let (_, ona) = &instances[0];
+        let (_, pynars) = &instances[1];
+        asserts! {
+            ona.instances => None,
+            ona.input_mode => Some(InputMode::Cmd),
+            pynars.input_mode => Some(InputMode::Nal),
+            ona.translators => Some(LaunchConfigTranslators::Same("ona".into())),
+            ona.report_file => Some(PathBuf::from("report.ona.xml")),
+            pynars.report_file => Some(PathBuf::from("report.pynars.xml")),
+        }
+        Ok(())
+    }
+
+    /// 测试/连接已有的CIN
+    /// * 🎯有`connect`时无需`command`
+    #[test]
+    fn test_connect() -> Result<()> {
+        let config = LaunchConfig::from_json_str(
+            r#"{
+                "translators": "ona",
+                "connect": { "host": "127.0.0.1", "port": 8765 },
+                "autoRestart": true
+            }"#,
+        )?;
+        assert!(config.missing_required_fields().is_empty());
+        let runtime_config = RuntimeConfig::try_from(config)?;
+        asserts! {
+            runtime_config.connect => Some(LaunchConfigConnect {
+                host: "127.0.0.1".into(),
+                port: 8765,
+            }),
+            runtime_config.auto_restart => true,
+        }
+        Ok(())
+    }
+
+    /// 测试/展开占位符
+    #[test]
+    fn test_expand_placeholders() -> Result<()> {
+        let config_dir = Path::new("/home/user/configs");
+        let env_var = |var: &str| match var {
+            "BABELNAR_TEST_JAVA_HOME" => Ok("/opt/jdk8".into()),
+            _ => Err(std::env::VarError::NotPresent),
+        };
+        let expand =
+            |value| expand_placeholders_with_env(value, "command.cmd", config_dir, env_var);
+        let exe_dir = std::env::current_exe()?;
+        let exe_dir = exe_dir.parent().unwrap().to_string_lossy();
+        for (value, expected) in [
+            ("java", "java".to_string()),
+            (
+                "${configDir}/nars.jar",
+                "/home/user/configs/nars.jar".into(),
+            ),
+            ("${exeDir}/nars.jar", format!("{exe_dir}/nars.jar")),
+            (
+                "${ENV:BABELNAR_TEST_JAVA_HOME}/bin/java",
+                "/opt/jdk8/bin/java".into(),
+            ),
+            (
+                "${configDir}:${configDir}",
+                "/home/user/configs:/home/user/configs".into(),
+            ),
+            ("${configDir", "${configDir".into()),
+        ] {
+            assert_eq!(expand(value)?, expected);
+        }
+        // 未知的变量、未设置的环境变量⇒报错，并指明键与变量
+        for (value, variable) in [
+            ("${javaHome}/bin/java", "javaHome"),
+            ("${ENV:BABELNAR_TEST_NOT_SET}", "ENV:BABELNAR_TEST_NOT_SET"),
+        ] {
+            let e = expand(value).unwrap_err();
+            let e = e.to_string();
+            assert!(e.contains("command.cmd") && e.contains(variable), "{e}");
+        }
+        Ok(())
+    }
+
+    /// 测试/展开配置中的占位符
+    /// * 🎯启动命令、命令参数、工作目录与预置NAL文件，以及各实例
+    #[test]
+    fn test_expand_placeholders_config() -> Result<()> {
+        let config_dir = Path::new("/home/user/configs");
+        let mut config = LaunchConfig::from_json_str(
+            r#"{
+                "command": {
+                    "cmd": "${configDir}/nars.jar",
+                    "cmdArgs": ["-Dhome=${configDir}"],
+                    "currentDir": "${configDir}/root"
+                },
+                "preludeNAL": [{ "text": "${configDir}" }, { "file": "${configDir}/prelude.nal" }],
+                "instances": { "ona": { "command": { "cmd": "${configDir}/NAR" } } }
+            }"#,
+        )?;
+        config.expand_placeholders(config_dir)?;
+        let command = config.command.as_ref().unwrap();
+        let instance = &config.instances.as_ref().unwrap()["ona"];
+        asserts! {
+            command.cmd => "/home/user/configs/nars.jar",
+            command.cmd_args => Some(vec!["-Dhome=/home/user/configs".to_string()]),
+            command.current_dir => Some(PathBuf::from("/home/user/configs/root")),
+            config.prelude_nal => Some(vec![
+                LaunchConfigPreludeNAL::Text("${configDir}".into()),
+                LaunchConfigPreludeNAL::File("/home/user/configs/prelude.nal".into()),
+            ]),
+            instance.command.as_ref().unwrap().cmd => "/home/user/configs/NAR",
+        }
+
+        // 报错时指明所在的键
+        let mut config = LaunchConfig::from_json_str(
+            r#"{ "instances": { "ona": { "command": { "cmd": "NAR", "cmdArgs": ["${foo}"] } } } }"#,
+        )?;
+        let e = config
+            .expand_placeholders(config_dir)
+            .unwrap_err()
+            .to_string();
+        assert!(e.contains("instances.ona.command.cmdArgs[0]"), "{e}");
+        Ok(())
+    }
+
+    /// 测试/配置来源
+    /// * 🎯覆盖：显式设置者记为来源；并入：仅填补缺省项；拼接：多个来源
+    #[test]
+    fn test_provenance() -> Result<()> {
+        let a = LaunchConfig::from_json_str(
+            r#"{ "translators": "ona", "userInput": false, "preludeNAL": { "text": "A." } }"#,
+        )?;
+        let b = LaunchConfig::from_json_str(
+            r#"{ "userInput": false, "preludeNAL": { "text": "B." } }"#,
+        )?;
+        let c =
+            LaunchConfig::from_json_str(r#"{ "translators": "opennars", "strictMode": true }"#)?;
+        let mut config = LaunchConfig::new();
+        let mut provenance = ConfigProvenance::new();
+        config.override_from_tracked(&a, "a", &mut provenance);
+        config.override_from_tracked(&b, "b", &mut provenance);
+        config.merge_from_tracked(&c, "c", &mut provenance);
+        asserts! {
+            provenance.sources("translators") => ["a"],
+            provenance.sources("userInput") => ["b"],
+            provenance.sources("preludeNAL") => ["a", "b"],
+            provenance.sources("strictMode") => ["c"],
+            provenance.sources("autoRestart").is_empty(),
+        }
+
+        // 修改记录
+        let before = config.clone();
+        config.strict_mode = Some(false);
+        config.websocket = None;
+        provenance.track_changes(&before, &config, "命令行参数");
+        asserts! {
+            provenance.sources("strictMode") => ["命令行参数"],
+            provenance.sources("websocket").is_empty(),
+        }
+        Ok(())
+    }
+
+    /// 测试/读取
+    /// * 🎯相对**配置文件**的路径表示
+    /// * 🎯被重定向到`./executables`，以便启动其下的`.jar`文件
+    #[test]
+    fn test_read() {
+        // * 🚩使用OpenNARS配置文件的路径作测试
+        let path: PathBuf = config_paths::OPENNARS.into();
+        let launch_config = read_config_extern(&path).expect("路径读取失败");
+        // * 🚩构造预期路径
+        let root_path = PathBuf::from(".");
+        let mut expected_path: PathBuf = "./executables".into();
+        LaunchConfig::rebase_relative_path(&root_path, &mut expected_path)
+            .expect("【2024-06-12 23:53:59】现在应该是绝对路径");
+        // * 🚩测试&比对
+        asserts! {
+            // * 🎯启动命令中的「当前目录」应该被追加到配置自身的路径上
+            // * ✅即便拼接后路径是`"./src/tests/cli/config\\root/nars/test"`，也和上边的路径相等
+            launch_config.command.unwrap().current_dir => Some(expected_path)
+        }
+    }
+
+    /// 测试/所有配置样例的往返
+    /// * 🎯迁入库之后，现有的`.hjson`配置文件仍可解析
+    /// * 🚩读取⇒序列化为JSON⇒再解析，前后应一致（除不参与序列化的「配置文件路径」）
+    #[test]
+    fn test_round_trip_samples() -> Result<()> {
+        let config_dir = Path::new(config_paths::OPENNARS)
+            .parent()
+            .expect("配置样例应位于某个目录下");
+        let mut n_samples = 0;
+        for entry in std::fs::read_dir(config_dir)? {
+            let path = entry?.path();
+            if path.extension() != Some("hjson".as_ref()) {
+                continue;
+            }
+            let config = read_config_extern(&path)?;
+            let json = serde_json::to_string(&config)?;
+            let mut parsed = LaunchConfig::from_json_str(&json)?;
+            parsed.config_path = config.config_path.clone();
+            assert_eq!(parsed, config, "配置样例{path:?}往返后不一致：{json}");
+            n_samples += 1;
+        }
+        assert!(n_samples > 0, "未找到任何配置样例");
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+//! 启动配置的检查
+//! * 🎯在启动CIN之前发现配置中的问题，并给出可操作的建议
+//!   * 📄转译器名拼错：`"translators": "opnenars"` ⇒ 建议`OpenNARS`
+//!   * 📄启动命令、jar包、工作目录、预置NAL文件不存在
+//!   * 📄Websocket端口已被占用
+//!   * 📄子进程IO的备用编码无法识别
+//! * 🚩只检查、不修改：是否中止启动，由调用方根据「严重程度」决定
+
+use super::{
+    cin_search::name_match::{best_name_match, suggest_names},
+    config::{
+        LaunchConfigExecTranslator, LaunchConfigPreludeNAL, LaunchConfigTranslators, PrintLevel,
+        RuntimeConfig,
+    },
+};
+use crate::{cin_implements::common::is_jar_path, process_io::ProcessEncoding};
+use std::{
+    env,
+    fmt::{self, Display, Formatter},
+    net::TcpListener,
+    path::{Path, PathBuf},
+};
+
+/// 配置问题的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigIssueSeverity {
+    /// 警告：不影响启动
+    Warning,
+    /// 错误：启动必定失败，需中止
+    Error,
+}
+
+/// 配置问题
+/// * 📌包含「严重程度」「问题描述」与（可选的）「修改建议」
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// 严重程度
+    pub severity: ConfigIssueSeverity,
+    /// 问题描述
+    pub message: String,
+    /// 修改建议
+    pub suggestion: Option<String>,
+}
+
+impl ConfigIssue {
+    /// 构造函数/错误
+    pub fn error(message: impl Into<String>, suggestion: Option<String>) -> Self {
+        Self {
+            severity: ConfigIssueSeverity::Error,
+            message: message.into(),
+            suggestion,
+        }
+    }
+
+    /// 构造函数/警告
+    pub fn warning(message: impl Into<String>, suggestion: Option<String>) -> Self {
+        Self {
+            severity: ConfigIssueSeverity::Warning,
+            message: message.into(),
+            suggestion,
+        }
+    }
+
+    /// 是否为错误
+    pub fn is_error(&self) -> bool {
+        self.severity == ConfigIssueSeverity::Error
+    }
+}
+
+/// 展示：问题描述（+建议）
+impl Display for ConfigIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "（{suggestion}）")?;
+        }
+        Ok(())
+    }
+}
+
+impl RuntimeConfig {
+    /// 检查配置
+    /// * 🎯在启动前报告所有问题，而非在启动过程中报出难以理解的错误
+    /// * 📌`known_translators`：所有已知的转译器名（含别名）
+    ///   * 🚩转译器由调用方（如BabelNAR CLI）注册，故由调用方传入
+    /// * ⚙️返回所有发现的问题；空数组表示未发现问题
+    pub fn validate(&self, known_translators: &[&str]) -> Vec<ConfigIssue> {
+        let mut issues = vec![];
+        self.validate_translators(known_translators, &mut issues);
+        self.validate_command(&mut issues);
+        self.validate_prelude_nal(&mut issues);
+        self.validate_websocket(&mut issues);
+        self.validate_print_level(&mut issues);
+        self.validate_process_encoding(&mut issues);
+        issues
+    }
+
+    /// 检查转译器名能否匹配到已知转译器
+    /// * 🚩不区分大小写，与某个已知名称相同⇒可解析
+    /// * 🚩否则按「名称匹配」模糊查找：📄"OpenNARS 3.0.4"⇒可解析为`OpenNARS`
+    /// * 🚩无法解析时，按「名称建议」给出匹配度最高的候选
+    fn validate_translators(&self, known_translators: &[&str], issues: &mut Vec<ConfigIssue>) {
+        let names = match &self.translators {
+            LaunchConfigTranslators::Same(name) => vec![name.as_str()],
+            LaunchConfigTranslators::Separated { input, output } => vec![input.as_str(), output],
+            LaunchConfigTranslators::Exec(exec) => {
+                self.validate_exec_translator(exec, issues);
+                exec.preset.as_deref().into_iter().collect()
+            }
+        };
+        for name in names {
+            let resolved = known_translators
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(name))
+                || best_name_match(name, known_translators.iter().copied()).is_some();
+            if !resolved {
+                issues.push(ConfigIssue::error(
+                    format!("未知的转译器「{name}」"),
+                    suggest_names(name, known_translators.iter().copied())
+                        .first()
+                        .map(|(s, _)| format!("是否指「{s}」？")),
+                ))
+            }
+        }
+    }
+
+    /// 检查外部程序转译器
+    /// * 🚩外部程序可在工作目录或`PATH`中找到
+    fn validate_exec_translator(
+        &self,
+        exec: &LaunchConfigExecTranslator,
+        issues: &mut Vec<ConfigIssue>,
+    ) {
+        if !command_exists(&exec.exec, self.command.current_dir.as_deref()) {
+            issues.push(ConfigIssue::error(
+                format!("未找到转译程序「{}」", exec.exec),
+                Some("请检查`translators.exec`：应为工作目录下的文件，或位于`PATH`中的命令".into()),
+            ));
+        }
+    }
+
+    /// 检查启动命令
+    /// * 🚩工作目录存在
+    /// * 🚩命令可在工作目录或`PATH`中找到
+    /// * 🚩`-jar`之后的jar包存在
+    /// * 📌有连接参数⇒不启动子进程，无需检查
+    fn validate_command(&self, issues: &mut Vec<ConfigIssue>) {
+        if self.connect.is_some() {
+            return;
+        }
+        let command = &self.command;
+        // 工作目录
+        if let Some(current_dir) = &command.current_dir {
+            if !current_dir.is_dir() {
+                issues.push(ConfigIssue::error(
+                    format!("工作目录{current_dir:?}不存在"),
+                    Some("请检查`currentDir`：相对路径基于配置文件所在目录".into()),
+                ));
+                // 工作目录都不存在⇒后续基于工作目录的检查无意义
+                return;
+            }
+        }
+        let current_dir = command.current_dir.as_deref();
+        // 启动命令 | jar文件⇒以Java启动
+        if is_jar_path(&command.cmd) {
+            if !resolve_path(&command.cmd, current_dir).is_file() {
+                issues.push(ConfigIssue::error(
+                    format!("未找到jar包「{}」", command.cmd),
+                    Some("请检查`cmd`：相对路径基于工作目录".into()),
+                ));
+            }
+            if !command_exists("java", None) {
+                issues.push(ConfigIssue::error(
+                    "未找到Java",
+                    Some("请安装Java，并确保`java`位于`PATH`中".into()),
+                ));
+            }
+        } else if !command_exists(&command.cmd, current_dir) {
+            issues.push(ConfigIssue::error(
+                format!("未找到启动命令「{}」", command.cmd),
+                Some("请检查`cmd`：应为工作目录下的文件，或位于`PATH`中的命令".into()),
+            ));
+        }
+        // jar包
+        let args = command.cmd_args.as_deref().unwrap_or_default();
+        let jars = args
+            .windows(2)
+            .filter(|pair| pair[0] == "-jar")
+            .map(|pair| &pair[1]);
+        for jar in jars {
+            if !resolve_path(jar, current_dir).is_file() {
+                issues.push(ConfigIssue::error(
+                    format!("未找到jar包「{jar}」"),
+                    Some("请检查`cmdArgs`：相对路径基于工作目录".into()),
+                ));
+            }
+        }
+    }
+
+    /// 检查预置NAL文件
+    fn validate_prelude_nal(&self, issues: &mut Vec<ConfigIssue>) {
+        for prelude_nal in &self.prelude_nal {
+            if let LaunchConfigPreludeNAL::File(path) = prelude_nal {
+                if !path.is_file() {
+                    issues.push(ConfigIssue::error(
+                        format!("未找到预置NAL文件{path:?}"),
+                        Some("请检查`preludeNAL.file`：相对路径基于配置文件所在目录".into()),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// 检查Websocket端口是否可用
+    /// * 🚩尝试绑定后立即释放
+    fn validate_websocket(&self, issues: &mut Vec<ConfigIssue>) {
+        if let Some(websocket) = &self.websocket {
+            let address = (websocket.host.as_str(), websocket.port);
+            if let Err(e) = TcpListener::bind(address) {
+                issues.push(ConfigIssue::error(
+                    format!(
+                        "无法绑定Websocket地址{}:{}：{e}",
+                        websocket.host, websocket.port
+                    ),
+                    Some("端口可能已被占用，请更换`websocket.port`".into()),
+                ));
+            }
+        }
+    }
+
+    /// 检查打印过滤中的类型名
+    /// * 🚩未知类型名⇒警告：不影响启动，但该项不会匹配任何输出
+    /// * 📌别名表中声明的类型名视作已知
+    fn validate_print_level(&self, issues: &mut Vec<ConfigIssue>) {
+        let declared = |name: &str| {
+            self.output_type_aliases
+                .iter()
+                .flat_map(|(canonical, aliases)| std::iter::once(canonical).chain(aliases))
+                .any(|declared| declared.eq_ignore_ascii_case(name))
+        };
+        let unknown_names = self
+            .print_level
+            .unknown_names()
+            .filter(|name| !declared(name));
+        for name in unknown_names {
+            issues.push(ConfigIssue::warning(
+                format!("未知的输出类型「{name}」"),
+                suggest_names(name, PrintLevel::KNOWN_TYPES.iter().copied())
+                    .first()
+                    .map(|(s, _)| format!("是否指「{s}」？")),
+            ))
+        }
+    }
+
+    /// 检查子进程IO的备用编码能否识别
+    fn validate_process_encoding(&self, issues: &mut Vec<ConfigIssue>) {
+        if let Some(label) = &self.process_encoding {
+            if let Err(e) = ProcessEncoding::from_label(label) {
+                issues.push(ConfigIssue::error(
+                    e.to_string(),
+                    Some("`processEncoding`应为编码名，如`\"gbk\"`、`\"GB18030\"`".into()),
+                ));
+            }
+        }
+    }
+}
+
+/// 解析路径：相对路径基于工作目录（若有）
+fn resolve_path(path: impl AsRef<Path>, current_dir: Option<&Path>) -> PathBuf {
+    match current_dir {
+        Some(dir) => dir.join(path),
+        None => path.as_ref().to_path_buf(),
+    }
+}
+
+/// 判断命令是否存在
+/// * 🚩含路径分隔符⇒视作文件路径，基于工作目录查找
+/// * 🚩否则⇒在`PATH`中查找
+///   * 📌Windows下补充`PATHEXT`中的扩展名（如`.exe`）
+fn command_exists(cmd: &str, current_dir: Option<&Path>) -> bool {
+    let has_separator = cmd.contains('/') || cmd.contains(std::path::MAIN_SEPARATOR);
+    if has_separator {
+        return resolve_path(cmd, current_dir).is_file();
+    }
+    let Some(paths) = env::var_os("PATH") else {
+        return false;
+    };
+    let extensions = match env::var("PATHEXT") {
+        Ok(ext) if cfg!(windows) => ext.split(';').map(str::to_string).collect(),
+        _ => vec![],
+    };
+    env::split_paths(&paths).any(|dir| {
+        let path = dir.join(cmd);
+        path.is_file()
+            || extensions
+                .iter()
+                .any(|ext| path.with_extension(ext.trim_start_matches('.')).is_file())
+    })
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cli_support::config::LaunchConfigCommand, tests::test_runtime_config};
+
+    /// 已知的转译器名
+    const KNOWN: &[&str] = &["OpenNARS", "ONA", "PyNARS", "Native"];
+
+    /// 基于`sh`/`cmd`的测试配置
+    fn config(translators: &str, cmd_args: &[&str]) -> RuntimeConfig {
+        RuntimeConfig {
+            translators: LaunchConfigTranslators::Same(translators.into()),
+            command: LaunchConfigCommand {
+                cmd: if cfg!(windows) { "cmd" } else { "sh" }.into(),
+                cmd_args: Some(cmd_args.iter().map(|s| s.to_string()).collect()),
+                current_dir: None,
+                env: None,
+            },
+            ..test_runtime_config()
+        }
+    }
+
+    /// 测试/无问题
+    #[test]
+    fn test_valid() {
+        assert_eq!(config("opennars", &[]).validate(KNOWN), vec![]);
+    }
+
+    /// 测试/转译器名拼错⇒报错并给出建议
+    #[test]
+    fn test_translator_typo() {
+        let issues = config("opnenars", &[]).validate(KNOWN);
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(issues[0].is_error());
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("是否指「OpenNARS」？")
+        );
+        // 相差过大⇒不建议
+        let issues = config("onna", &[]).validate(KNOWN);
+        assert_eq!(issues[0].suggestion.as_deref(), Some("是否指「ONA」？"));
+        let issues = config("xyzxyzxyz", &[]).validate(KNOWN);
+        assert_eq!(issues[0].suggestion, None);
+    }
+
+    /// 测试/带版本号的转译器名⇒模糊匹配，不报错
+    #[test]
+    fn test_translator_fuzzy() {
+        assert_eq!(config("OpenNARS 3.0.4", &[]).validate(KNOWN), vec![]);
+    }
+
+    /// 测试/jar包不存在
+    #[test]
+    fn test_missing_jar() {
+        let issues = config("opennars", &["-Xmx1024m", "-jar", "./not-exist.jar"]).validate(KNOWN);
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(issues[0].is_error());
+        assert!(issues[0].message.contains("not-exist.jar"), "{issues:?}");
+    }
+
+    /// 测试/启动命令为jar文件⇒检查jar包本身
+    #[test]
+    fn test_missing_jar_cmd() {
+        let mut c = config("opennars", &["-Xmx1024m"]);
+        c.command.cmd = "./not-exist.jar".into();
+        let issues = c.validate(KNOWN);
+        let messages = issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>();
+        assert!(
+            messages.contains(&"未找到jar包「./not-exist.jar」"),
+            "{issues:?}"
+        );
+        assert!(
+            !messages.iter().any(|m| m.contains("启动命令")),
+            "{issues:?}"
+        );
+    }
+
+    /// 测试/未知的输出类型⇒仅警告
+    #[test]
+    fn test_unknown_print_level() {
+        let mut c = config("ona", &[]);
+        c.print_level = PrintLevel(vec!["ANSWER".into(), "answr".into()]);
+        let issues = c.validate(KNOWN);
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(!issues[0].is_error());
+        assert_eq!(issues[0].suggestion.as_deref(), Some("是否指「ANSWER」？"));
+        // 别名表中声明的类型名⇒已知
+        c.print_level = PrintLevel(vec!["EXPECT".into()]);
+        c.output_type_aliases = [("ANTICIPATE".into(), vec!["EXPECT".into()])].into();
+        assert_eq!(c.validate(KNOWN), vec![]);
+    }
+
+    /// 测试/子进程IO的备用编码
+    #[test]
+    #[cfg(feature = "text_encoding")]
+    fn test_process_encoding() {
+        let mut c = config("ona", &[]);
+        c.process_encoding = Some("GBK".into());
+        assert_eq!(c.validate(KNOWN), vec![]);
+        c.process_encoding = Some("gbkk".into());
+        let issues = c.validate(KNOWN);
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(issues[0].is_error());
+    }
+
+    /// 测试/启动命令与工作目录不存在
+    #[test]
+    fn test_missing_command() {
+        let mut c = config("ona", &[]);
+        c.command.cmd = "./not-exist.exe".into();
+        assert!(c.validate(KNOWN).iter().any(ConfigIssue::is_error));
+        c.command.cmd = "babelnar-not-exist-command".into();
+        assert!(c.validate(KNOWN).iter().any(ConfigIssue::is_error));
+        c.command.current_dir = Some("./not-exist-dir".into());
+        let issues = c.validate(KNOWN);
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(issues[0].message.contains("not-exist-dir"), "{issues:?}");
+    }
+}
@@ -0,0 +1,150 @@
+//! 用户输入的历史记录
+//! * 🎯在交互式输入中回溯先前的输入（上下方向键）
+//! * 🎯在多次会话之间持久化：保存到历史记录文件
+//! * 📌文件格式：每行一条记录，旧者在前
+
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    io::{ErrorKind, Result as IoResult},
+    path::{Path, PathBuf},
+};
+
+/// 默认的历史记录条数上限
+pub const DEFAULT_HISTORY_SIZE: usize = 1000;
+
+/// 用户输入的历史记录
+/// * 🚩忽略空行与「与上一条相同」的输入
+/// * 🚩超出上限⇒丢弃最旧的记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputHistory {
+    /// 所有记录 | 旧者在前
+    entries: Vec<String>,
+    /// 记录条数上限
+    max_size: usize,
+    /// 历史记录文件
+    /// * 📌[`None`]⇒不持久化
+    file: Option<PathBuf>,
+}
+
+impl Default for InputHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_SIZE)
+    }
+}
+
+impl InputHistory {
+    /// 构造一个不持久化的空历史记录
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            entries: vec![],
+            max_size,
+            file: None,
+        }
+    }
+
+    /// 从历史记录文件中加载
+    /// * 🚩文件不存在⇒空记录：首次使用时尚未保存
+    /// * 📌此后的[`Self::save`]写入同一文件
+    pub fn load(path: impl Into<PathBuf>, max_size: usize) -> IoResult<Self> {
+        let file = path.into();
+        let mut history = Self::new(max_size);
+        match read_to_string(&file) {
+            Ok(content) => content.lines().for_each(|line| {
+                history.push(line);
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        history.file = Some(file);
+        Ok(history)
+    }
+
+    /// 追加一条记录
+    /// * 🚩去除首尾空白；空行、与上一条相同⇒不记录
+    /// * ⚙️返回「是否已记录」
+    pub fn push(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        if line.is_empty() || self.entries.last().is_some_and(|last| last == line) {
+            return false;
+        }
+        self.entries.push(line.to_owned());
+        if self.entries.len() > self.max_size {
+            let overflow = self.entries.len() - self.max_size;
+            self.entries.drain(..overflow);
+        }
+        true
+    }
+
+    /// 所有记录 | 旧者在前
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// 历史记录文件
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// 保存到历史记录文件
+    /// * 🚩自动创建所在目录；未指定文件⇒不做任何事
+    pub fn save(&self) -> IoResult<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if let Some(parent) = file
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            create_dir_all(parent)?;
+        }
+        let mut content = self.entries.join("\n");
+        content.push('\n');
+        write(file, content)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::remove_dir_all;
+
+    /// 测试/追加记录
+    #[test]
+    fn push() {
+        let mut history = InputHistory::new(3);
+        assert!(history.push("<A --> B>."));
+        assert!(!history.push("  <A --> B>.  ")); // 与上一条相同
+        assert!(!history.push("   ")); // 空行
+        assert!(history.push("/cyc 10"));
+        assert!(history.push("<A --> B>."));
+        assert!(history.push("<B --> C>."));
+        // 超出上限⇒丢弃最旧的记录
+        assert_eq!(history.entries(), ["/cyc 10", "<A --> B>.", "<B --> C>."]);
+    }
+
+    /// 测试/保存与加载
+    #[test]
+    fn save_and_load() -> IoResult<()> {
+        let dir =
+            std::env::temp_dir().join(format!("babelnar_input_history_{}", std::process::id()));
+        let _ = remove_dir_all(&dir);
+        let path = dir.join("history.txt");
+        // 文件不存在⇒空记录
+        let mut history = InputHistory::load(&path, 2)?;
+        assert!(history.entries().is_empty());
+        for line in ["<A --> B>.", "<B --> C>.", "<A --> C>?"] {
+            history.push(line);
+        }
+        // 保存时自动创建目录
+        history.save()?;
+        // 重新加载⇒得到相同的记录
+        let loaded = InputHistory::load(&path, 2)?;
+        assert_eq!(loaded, history);
+        assert_eq!(loaded.entries(), ["<B --> C>.", "<A --> C>?"]);
+        // 加载时亦遵循上限
+        let loaded = InputHistory::load(&path, 1)?;
+        assert_eq!(loaded.entries(), ["<A --> C>?"]);
+        remove_dir_all(&dir)
+    }
+}
@@ -9,9 +9,15 @@ util::mods! {
     // 读取行迭代器
     pub readline_iter;
 
+    // 输入历史记录
+    pub input_history;
+
     // NAVM输出缓存
     pub navm_output_cache;
 
     // Websocket支持
     pub websocket;
+
+    // 会话录制与重放
+    pub session;
 }
@@ -5,14 +5,16 @@
 use crate::{
     cli_support::error_handling_boost::error_anyhow,
     output_handler::flow_handler_list::{FlowHandlerList, HandleResult},
-    test_tools::VmOutputCache,
+    test_tools::{QuestionTracker, SaveOutputsOptions, TrackedQuestion, VmOutputCache},
 };
 use anyhow::Result;
 use nar_dev_utils::ResultBoost;
+use narsese::lexical::Task;
 use navm::output::Output;
 use std::{
     ops::ControlFlow,
     sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
 };
 
 /// 线程间可变引用计数的别名
@@ -28,6 +30,47 @@ pub struct OutputCache {
     /// * 🚩【2024-04-03 01:43:41】不附带任何包装类型，仅包装其自身
     pub(crate) inner: Vec<Output>,
 
+    /// 各输出的「收到时间」
+    /// * 🚩与`inner`按索引一一对应：自缓存创建以来的时长
+    ///   * 📌经[`VmOutputCache::put_received`]存入⇒运行时收到输出的时刻；否则⇒存入的时刻
+    /// * ⚠️经[`OutputCache::borrow_inner_mut`]直接加入的输出没有时间
+    put_times: Vec<Duration>,
+
+    /// 缓存创建的时刻
+    /// * 🎯作为所有时间的起点
+    created: Instant,
+
+    /// 最近一次「置入Narsese」的时间
+    /// * 🎯带「最大延迟」的`''expect-contains`
+    last_nse_input: Option<Duration>,
+
+    /// 预期窗口的起点
+    /// * 🎯先后的预期不会匹配到同一条旧输出
+    /// * 📌索引：预期只检查此位置及之后的输出
+    expect_window: usize,
+
+    /// 是否已检查过预期
+    /// * 🚩此后首次置入指令时，将[预期窗口](Self::expect_window)移至末尾
+    expectation_checked: bool,
+
+    /// 问题追踪器
+    /// * 🎯将`ANSWER`关联到所回答的问题
+    questions: QuestionTracker,
+
+    /// 各输出所回答的问题编号
+    /// * 🚩与`inner`按索引一一对应
+    /// * ⚠️经[`OutputCache::borrow_inner_mut`]直接加入的输出不关联问题
+    answers_to: Vec<Option<usize>>,
+
+    /// 是否抑制输入回显
+    /// * 🚩开启后，[存入](VmOutputCache::put)时直接丢弃`IN`输出：不交给侦听器，也不缓存
+    /// * 📌`''await-echo`不受影响：等待时比对的是拉取到的输出本身
+    suppress_input_echo: bool,
+
+    /// 「保存输出」的选项
+    /// * 🎯`''save-outputs`：保留文件数、是否允许绝对路径
+    save_outputs_options: SaveOutputsOptions,
+
     /// 流式侦听器列表
     /// * 🎯用于功能解耦、易分派的「NAVM输出处理」
     ///   * 📌可在此过程中对输出进行拦截、转换等操作
@@ -41,7 +84,16 @@ impl OutputCache {
     /// 构造函数
     pub fn new(inner: Vec<Output>) -> Self {
         Self {
+            put_times: vec![Duration::ZERO; inner.len()],
+            answers_to: vec![None; inner.len()],
             inner,
+            created: Instant::now(),
+            last_nse_input: None,
+            expect_window: 0,
+            expectation_checked: false,
+            questions: QuestionTracker::default(),
+            suppress_input_echo: false,
+            save_outputs_options: SaveOutputsOptions::default(),
             output_handlers: FlowHandlerList::new(),
         }
     }
@@ -56,6 +108,28 @@ impl OutputCache {
         &mut self.inner
     }
 
+    /// 获取第`index`个输出的「收到时间」
+    /// * 📌自缓存创建以来的时长
+    pub fn put_time(&self, index: usize) -> Option<Duration> {
+        self.put_times.get(index).copied()
+    }
+
+    /// 设置「是否抑制输入回显」
+    pub fn set_suppress_input_echo(&mut self, suppress: bool) {
+        self.suppress_input_echo = suppress;
+    }
+
+    /// 设置「保存输出」的选项
+    pub fn set_save_outputs_options(&mut self, options: SaveOutputsOptions) {
+        self.save_outputs_options = options;
+    }
+
+    /// 清除「置入Narsese」的时间
+    /// * 🎯开始执行新的NAL文件时调用：`''expect-contains`的延迟只在同一文件内计算
+    pub fn clear_nse_input(&mut self) {
+        self.last_nse_input = None;
+    }
+
     /// 默认[`Arc`]<[`Mutex`]>
     pub fn default_arc_mutex() -> ArcMutex<Self> {
         Arc::new(Mutex::new(Self::default()))
@@ -70,8 +144,21 @@ impl OutputCache {
     /// * 🎯内部可用的「静默存入输出」逻辑
     ///   * 🚩【2024-04-03 01:07:55】不打算封装了
     pub fn put_silent(&mut self, output: Output) -> Result<()> {
-        // 加入输出
+        self.put_silent_received(output, Instant::now())
+    }
+
+    /// 静默存入输出，并附带其「收到时间」
+    /// * 📌早于缓存创建⇒记为缓存创建的时刻
+    fn put_silent_received(&mut self, output: Output, received: Instant) -> Result<()> {
+        // 加入输出，并记录时间、所回答的问题
+        // * 🚩先补齐「直接加入」的输出所缺的时间与问题，保持索引一致
+        let now = self.created.elapsed();
+        self.put_times.resize(self.inner.len(), now);
+        self.answers_to.resize(self.inner.len(), None);
+        self.answers_to.push(self.questions.answer(&output));
         self.inner.push(output);
+        let received = received.saturating_duration_since(self.created);
+        self.put_times.push(received);
         Ok(())
     }
 }
@@ -90,11 +177,21 @@ impl VmOutputCache for OutputCache {
     /// * 🎯统一的「打印输出」逻辑
     ///   * 🚩【2024-04-03 01:07:55】不打算封装了
     fn put(&mut self, output: Output) -> Result<()> {
+        self.put_received(output, Instant::now())
+    }
+
+    /// 存入输出，并记录其「收到时间」
+    /// * 🚩其余同[`VmOutputCache::put`]
+    fn put_received(&mut self, output: Output, received: Instant) -> Result<()> {
+        // 抑制输入回显⇒丢弃
+        if self.suppress_input_echo && matches!(output, Output::IN { .. }) {
+            return Ok(());
+        }
         // 交给处理者处理
         let r = self.output_handlers.handle(output);
         match r {
             // 通过⇒静默加入输出
-            HandleResult::Passed(output) => self.put_silent(output),
+            HandleResult::Passed(output) => self.put_silent_received(output, received),
             // 被消耗⇒提示
             HandleResult::Consumed(index) => Ok(println!("NAVM输出在[{index}]位置被拦截。")),
         }
@@ -115,4 +212,90 @@ impl VmOutputCache for OutputCache {
         // 返回
         Ok(None)
     }
+
+    /// 遍历输出，并附带其「收到时间」
+    fn for_each_timed<T>(
+        &self,
+        mut f: impl FnMut(&Output, Option<Duration>) -> ControlFlow<T>,
+    ) -> Result<Option<T>> {
+        for (index, output) in self.inner.iter().enumerate() {
+            if let ControlFlow::Break(value) = f(output, self.put_time(index)) {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 记录「置入Narsese」的时间
+    fn mark_nse_input(&mut self) {
+        self.last_nse_input = Some(self.created.elapsed());
+    }
+
+    /// 最近一次「置入Narsese」的时间
+    fn last_nse_input(&self) -> Option<Duration> {
+        self.last_nse_input
+    }
+
+    /// 当前位置
+    fn snapshot(&self) -> Result<usize> {
+        Ok(self.inner.len())
+    }
+
+    /// 从指定位置起遍历输出
+    fn for_each_from<T>(
+        &self,
+        start: usize,
+        mut f: impl FnMut(&Output) -> ControlFlow<T>,
+    ) -> Result<Option<T>> {
+        for output in self.inner.iter().skip(start) {
+            if let ControlFlow::Break(value) = f(output) {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 预期窗口的起点
+    fn expect_window(&self) -> usize {
+        self.expect_window
+    }
+
+    /// 标记「已检查过预期」
+    fn mark_expectation_checked(&mut self) {
+        self.expectation_checked = true;
+    }
+
+    /// 置入指令时，若已检查过预期，则将预期窗口移至末尾
+    fn advance_expect_window(&mut self) {
+        if self.expectation_checked {
+            self.expect_window = self.inner.len();
+            self.expectation_checked = false;
+        }
+    }
+
+    /// 将预期窗口重置到缓存开头
+    fn reset_expect_window(&mut self) {
+        self.expect_window = 0;
+        self.expectation_checked = false;
+    }
+
+    /// 登记问题
+    fn track_question(&mut self, task: &Task) {
+        self.questions.ask(task);
+    }
+
+    /// 第`index`个输出所回答的问题编号
+    fn answer_to(&self, index: usize) -> Option<usize> {
+        self.answers_to.get(index).copied().flatten()
+    }
+
+    /// 尚未被回答的问题
+    fn unanswered_questions(&self) -> Vec<TrackedQuestion> {
+        self.questions.unanswered().cloned().collect()
+    }
+
+    /// 「保存输出」的选项
+    fn save_outputs_options(&self) -> SaveOutputsOptions {
+        self.save_outputs_options
+    }
 }
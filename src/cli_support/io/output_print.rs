@@ -40,10 +40,125 @@
 //! * 参考链接：<https://github.com/ARCJ137442/BabelNAR_Implements/blob/master/scripts/console.jl#L160>
 
 use colored::Colorize;
-use nar_dev_utils::manipulate;
-use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
-use navm::output::Output;
-use std::fmt::Display;
+use narsese::{conversion::string::impl_lexical::format_instances::FORMAT_ASCII, lexical::Narsese};
+use navm::output::{Operation, Output};
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Display,
+    io::{stderr, stdout, IsTerminal},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+/// 是否将CLI输出重定向到标准错误
+/// * 🎯JSON输出模式：标准输出只留给「机器可读」的NAVM输出
+/// * 🚩全局开关：影响所有[`println_cli`]
+static CLI_OUTPUT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// 设置「是否将CLI输出重定向到标准错误」
+pub fn set_cli_output_to_stderr(value: bool) {
+    CLI_OUTPUT_TO_STDERR.store(value, Ordering::Relaxed);
+}
+
+/// 获取「是否将CLI输出重定向到标准错误」
+pub fn is_cli_output_to_stderr() -> bool {
+    CLI_OUTPUT_TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// 着色支持（环境）
+/// * 🎯不支持着色时（重定向到文件、`NO_COLOR`等），回退到纯文本的`[INFO] 内容`
+/// * 🚩全局：首次使用时检测一次，只取决于环境（`NO_COLOR`、是否为终端）
+///   * 📌显式禁用另见[`COLOR_DISABLED`]：不受初始化先后的影响
+static COLOR_SUPPORT: OnceLock<ColorSupport> = OnceLock::new();
+
+/// 是否已显式禁用着色
+/// * 🎯`--no-color`、配置中的`noColor`
+/// * 📌只会从「启用」变为「禁用」：此后的输出均不着色
+static COLOR_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// 各输出流是否着色
+/// * 📌按输出流区分：如「标准输出重定向到文件，标准错误仍在终端」
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorSupport {
+    /// 标准输出是否着色
+    pub stdout: bool,
+    /// 标准错误是否着色
+    pub stderr: bool,
+}
+
+impl ColorSupport {
+    /// 根据各项条件决定
+    /// * 🚩显式禁用，或`NO_COLOR`设置且非空⇒均不着色
+    /// * 🚩非终端⇒对应的输出流不着色
+    pub fn decide(
+        no_color: bool,
+        no_color_env: Option<OsString>,
+        stdout_is_terminal: bool,
+        stderr_is_terminal: bool,
+    ) -> Self {
+        let color = !no_color && !is_no_color(no_color_env);
+        Self {
+            stdout: color && stdout_is_terminal,
+            stderr: color && stderr_is_terminal,
+        }
+    }
+
+    /// 从当前环境检测
+    /// * 🚩读取`NO_COLOR`环境变量，并检测标准输出、标准错误是否为终端
+    pub fn detect(no_color: bool) -> Self {
+        Self::decide(
+            no_color,
+            env::var_os("NO_COLOR"),
+            stdout().is_terminal(),
+            stderr().is_terminal(),
+        )
+    }
+
+    /// 指定的输出流是否着色
+    pub fn enabled(&self, to_stderr: bool) -> bool {
+        match to_stderr {
+            true => self.stderr,
+            false => self.stdout,
+        }
+    }
+}
+
+/// 初始化着色支持
+/// * 🎯在CLI启动早期调用：传入命令行参数`--no-color`
+/// * 🚩`no_color`⇒此后均不着色：即便此前已有输出检测过环境
+/// * 📌返回最终生效的着色支持
+pub fn init_color_support(no_color: bool) -> ColorSupport {
+    if no_color {
+        COLOR_DISABLED.store(true, Ordering::Relaxed);
+    }
+    color_support()
+}
+
+/// 获取着色支持
+/// * 🚩环境只检测一次：读取`NO_COLOR`，并检测各输出流是否为终端
+/// * 🚩已显式禁用⇒均不着色
+pub fn color_support() -> ColorSupport {
+    let detected = *COLOR_SUPPORT.get_or_init(|| ColorSupport::detect(false));
+    match COLOR_DISABLED.load(Ordering::Relaxed) {
+        true => ColorSupport {
+            stdout: false,
+            stderr: false,
+        },
+        false => detected,
+    }
+}
+
+/// 打印一行到标准输出或标准错误
+#[inline(always)]
+fn print_line_to(to_stderr: bool, line: impl Display) {
+    match to_stderr {
+        true => eprintln!("{line}"),
+        false => println!("{line}"),
+    }
+}
 
 /// 统一的「CLI输出类型」
 #[derive(Debug, Clone, Copy)]
@@ -90,16 +205,8 @@ impl OutputType<'_> {
     /// * 🎯封装「从NAVM输出打印」
     #[inline(always)]
     pub fn format_navm_output(out: &Output) -> impl Display {
-        let message = manipulate!(
-            // 新建字符串对象
-            String::new()
-            // 格式化头部
-            => Self::format_navm_output_type(out, _)
-            // 格式化原始内容
-            => Self::format_navm_output_content(out, _)
-        );
         // 载入着色
-        OutputType::from(out).to_colored_str(message)
+        OutputType::from(out).to_colored_str(Self::navm_output_message(out, false))
     }
 
     /// 从NAVM输出格式化（详细）
@@ -107,18 +214,46 @@ impl OutputType<'_> {
     /// * ✨提供「解析出的Narsese」与「解析出的NARS操作」信息
     #[inline(always)]
     pub fn format_from_navm_output_verbose(out: &Output) -> impl Display {
-        let message = manipulate!(
-            // 新建字符串对象
-            String::new()
-            // 格式化头部
-            => Self::format_navm_output_type(out, _)
-            // 详细格式化：Narsese、NARS操作
-            => Self::format_navm_output_verbose(out, _)
-            // 格式化原始内容
-            => Self::format_navm_output_content(out, _)
-        );
-        // 载入
-        OutputType::from(out).to_colored_str(message)
+        // 载入着色
+        OutputType::from(out).to_colored_str(Self::navm_output_message(out, true))
+    }
+
+    /// 渲染CLI输出
+    /// * 🎯在[`Self::format_line`]的基础上，可选地不着色
+    /// * 📌不着色⇒纯文本：`[INFO] 内容`
+    pub fn render_line(&self, message: &str, color: bool) -> String {
+        self.paint(format!("[{}] {}", self.as_str(), message), color)
+    }
+
+    /// 渲染NAVM输出
+    /// * 🎯在[`Self::format_navm_output`]与[`Self::format_from_navm_output_verbose`]的基础上，可选地不着色
+    pub fn render_navm_output(out: &Output, verbose: bool, color: bool) -> String {
+        OutputType::from(out).paint(Self::navm_output_message(out, verbose), color)
+    }
+
+    /// 按需着色
+    /// * 🚩不着色⇒原样返回
+    fn paint(&self, message: String, color: bool) -> String {
+        match color {
+            true => self.to_colored_str(message).to_string(),
+            false => message,
+        }
+    }
+
+    /// 从NAVM输出生成（未着色的）消息
+    /// * 📄`[OUT] 原始内容`
+    /// * 📄详细：`[OUT] [# Narsese #] [% 操作 %] 原始内容`
+    fn navm_output_message(out: &Output, verbose: bool) -> String {
+        let mut message = String::new();
+        // 格式化头部
+        Self::format_navm_output_type(out, &mut message);
+        // 详细格式化：Narsese、NARS操作
+        if verbose {
+            Self::format_navm_output_verbose(out, &mut message);
+        }
+        // 格式化原始内容
+        Self::format_navm_output_content(out, &mut message);
+        message
     }
 
     /// 从NAVM输出格式化（详细）
@@ -193,16 +328,22 @@ impl OutputType<'_> {
 
     /// ✨格式化打印CLI输出
     /// * 🎯BabelNAR CLI
+    /// * 🚩启用「重定向到标准错误」时，打印到标准错误
+    /// * 🚩按[着色支持](color_support)决定是否着色
     #[inline]
     pub fn print_line(&self, message: &str) {
-        println!("{}", self.format_line(message));
+        let to_stderr = is_cli_output_to_stderr();
+        let color = color_support().enabled(to_stderr);
+        print_line_to(to_stderr, self.render_line(message, color))
     }
 
     /// ✨格式化打印NAVM输出
     /// * 🎯BabelNAR CLI
     #[inline]
     pub fn print_navm_output(out: &Output) {
-        println!("{}", Self::format_navm_output(out));
+        let to_stderr = is_cli_output_to_stderr();
+        let color = color_support().enabled(to_stderr);
+        print_line_to(to_stderr, Self::render_navm_output(out, false, color))
     }
 
     /// ✨格式化打印NAVM输出（详细）
@@ -210,21 +351,38 @@ impl OutputType<'_> {
     /// * 🎯附带debug效果（检验「输出转译是否成功达到预期」）
     #[inline]
     pub fn print_navm_output_verbose(out: &Output) {
-        println!("{}", Self::format_from_navm_output_verbose(out));
+        let to_stderr = is_cli_output_to_stderr();
+        let color = color_support().enabled(to_stderr);
+        print_line_to(to_stderr, Self::render_navm_output(out, true, color))
+    }
+
+    /// ✨风格化打印NAVM输出
+    /// * 🎯BabelNAR CLI
+    /// * 🔗参见[`format_output_styled`]
+    /// * 🚩选项与[着色支持](color_support)都允许时，才着色
+    #[inline]
+    pub fn print_navm_output_styled(out: &Output, options: &StyleOptions) {
+        let to_stderr = is_cli_output_to_stderr();
+        let options = StyleOptions {
+            color: options.color && color_support().enabled(to_stderr),
+            ..options.clone()
+        };
+        print_line_to(to_stderr, format_output_styled(out, &options))
     }
 
     /// ✨格式化打印CLI输出（标准错误）
     /// * 🎯BabelNAR CLI
     #[inline]
     pub fn eprint_line(&self, message: &str) {
-        eprintln!("{}", self.format_line(message));
+        print_line_to(true, self.render_line(message, color_support().stderr))
     }
 
     /// ✨格式化打印NAVM输出（标准错误）
     /// * 🎯BabelNAR CLI
     #[inline]
     pub fn eprint_navm_output(out: &Output) {
-        eprintln!("{}", Self::format_navm_output(out));
+        let color = color_support().stderr;
+        print_line_to(true, Self::render_navm_output(out, false, color))
     }
 
     /// ✨格式化打印NAVM输出（标准错误）（详细）
@@ -232,7 +390,8 @@ impl OutputType<'_> {
     /// * 🎯附带debug效果（检验「输出转译是否成功达到预期」）
     #[inline]
     pub fn eprint_navm_output_verbose(out: &Output) {
-        eprintln!("{}", Self::format_from_navm_output_verbose(out));
+        let color = color_support().stderr;
+        print_line_to(true, Self::render_navm_output(out, true, color))
     }
 }
 
@@ -291,3 +450,355 @@ impl<'a> From<&'a Output> for OutputType<'a> {
         OutputType::Vm(out.type_name())
     }
 }
+
+/// 风格化输出中「类型标签」的对齐宽度
+/// * 📌最长的NAVM输出类型标签：`[TERMINATED]`
+pub const STYLED_TAG_WIDTH: usize = 12;
+
+/// 风格化输出的选项
+/// * 🎯[`format_output_styled`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleOptions {
+    /// 是否着色
+    /// * 🚩关闭⇒输出纯文本
+    pub color: bool,
+
+    /// 换行宽度
+    /// * 🚩Narsese（或原始内容）超出此宽度⇒换行，并与首行内容对齐
+    /// * 📌`0`⇒不换行
+    pub wrap_width: usize,
+
+    /// 附带的原始内容的最大长度
+    /// * 🚩超出⇒截断，以`…`结尾
+    /// * 📌`0`⇒不附带原始内容
+    pub max_raw_len: usize,
+}
+
+impl StyleOptions {
+    /// 从环境变量构造
+    /// * 🚩遵循[`NO_COLOR`](https://no-color.org)约定：设置且非空⇒不着色
+    pub fn from_env() -> Self {
+        Self {
+            color: !is_no_color(env::var_os("NO_COLOR")),
+            ..Self::default()
+        }
+    }
+
+    /// 纯文本：不着色
+    pub fn plain() -> Self {
+        Self {
+            color: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// 默认：着色，宽度`100`，原始内容最长`60`
+impl Default for StyleOptions {
+    fn default() -> Self {
+        Self {
+            color: true,
+            wrap_width: 100,
+            max_raw_len: 60,
+        }
+    }
+}
+
+/// 根据`NO_COLOR`环境变量的值，判断是否禁用着色
+/// * 📌设置且非空⇒禁用
+pub fn is_no_color(value: Option<OsString>) -> bool {
+    value.is_some_and(|value| !value.is_empty())
+}
+
+/// 风格化格式化NAVM输出
+/// * 🎯对齐、着色、易读的单条输出
+/// * 📌格式：`[类型]（对齐） 主体 真值 原始内容`
+///   * 主体：操作⇒`^op(参数...)`；Narsese⇒CommonNarsese（ASCII）；否则⇒原始内容
+///   * 真值：单独高亮
+///   * 原始内容：仅在主体非原始内容时附带，并截断
+/// * 📄`[ANSWER]     <A --> C>. %1.0;0.81% <A --> C>. %1.00;0.81%`
+pub fn format_output_styled(out: &Output, options: &StyleOptions) -> String {
+    // 类型标签 | 先补齐再着色，避免转义序列影响宽度
+    let tag = format!("[{}]", out.type_name());
+    let tag_width = tag.chars().count().max(STYLED_TAG_WIDTH);
+    let tag = format!("{tag:<STYLED_TAG_WIDTH$}");
+
+    // 主体与真值
+    let raw = out.get_content().trim();
+    let (body, truth, structured) = match (out.get_operation(), out.get_narsese()) {
+        (Some(operation), _) => (format_operation_styled(operation), None, true),
+        (None, Some(narsese)) => {
+            let (body, truth) = format_narsese_styled(narsese);
+            (body, truth, true)
+        }
+        (None, None) => (raw.to_string(), None, false),
+    };
+    let indent = format!("\n{}", " ".repeat(tag_width + 1));
+    let body = wrap_text(&body, options.wrap_width).join(&indent);
+
+    // 拼接
+    let mut result = match options.color {
+        true => format!("{}", OutputType::from(out).to_colored_str(tag)),
+        false => tag,
+    };
+    result.push(' ');
+    result += &body;
+    if let Some(truth) = truth {
+        result.push(' ');
+        result += &match options.color {
+            true => truth.bright_yellow().bold().to_string(),
+            false => truth,
+        };
+    }
+    if structured && options.max_raw_len > 0 && !raw.is_empty() {
+        let raw = truncate_text(raw, options.max_raw_len);
+        result.push(' ');
+        result += &match options.color {
+            true => raw.bright_black().to_string(),
+            false => raw,
+        };
+    }
+    result
+}
+
+/// 格式化NARS操作
+/// * 📄`^left({SELF}, x)`
+fn format_operation_styled(operation: &Operation) -> String {
+    let params = operation
+        .params
+        .iter()
+        .map(|param| FORMAT_ASCII.format(param))
+        .collect::<Vec<_>>();
+    format!("^{}({})", operation.operator_name, params.join(", "))
+}
+
+/// 格式化Narsese，并拆出真值
+/// * 🎯真值单独高亮
+/// * ⚙️返回`(不含真值的Narsese, 真值)`
+fn format_narsese_styled(narsese: &Narsese) -> (String, Option<String>) {
+    let sentence = match narsese {
+        Narsese::Term(term) => return (FORMAT_ASCII.format_term(term), None),
+        Narsese::Sentence(sentence) => sentence,
+        // 空预算⇒按语句展示 | 避免多余的`$$`
+        Narsese::Task(task) if task.budget.is_empty() => &task.sentence,
+        Narsese::Task(task) => {
+            let mut task = task.clone();
+            let truth = std::mem::take(&mut task.sentence.truth);
+            return (FORMAT_ASCII.format_task(&task), format_truth_styled(&truth));
+        }
+    };
+    let mut sentence = sentence.clone();
+    let truth = std::mem::take(&mut sentence.truth);
+    (
+        FORMAT_ASCII.format_sentence(&sentence),
+        format_truth_styled(&truth),
+    )
+}
+
+/// 格式化真值 | 空真值⇒[`None`]
+fn format_truth_styled(truth: &[String]) -> Option<String> {
+    match truth.is_empty() {
+        true => None,
+        false => Some(FORMAT_ASCII.format_truth(&truth.to_vec())),
+    }
+}
+
+/// 按宽度换行
+/// * 🚩优先在空白处断行；单个过长的片段⇒强制断开
+/// * 📌宽度为`0`⇒不换行
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    if width == 0 || text.chars().count() <= width {
+        return vec![text.to_string()];
+    }
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in text.split(' ') {
+        // 加入后超宽⇒先换行
+        let line_len = line.chars().count();
+        if line_len > 0 && line_len + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line += word;
+        // 单个片段过长⇒强制断开
+        while line.chars().count() > width {
+            let rest = line.chars().skip(width).collect::<String>();
+            line = line.chars().take(width).collect();
+            lines.push(std::mem::replace(&mut line, rest));
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// 截断文本 | 超出⇒以`…`结尾，总长不超过`max_len`
+fn truncate_text(text: &str, max_len: usize) -> String {
+    match text.chars().count() > max_len {
+        true => text.chars().take(max_len - 1).chain(['…']).collect(),
+        false => text.to_string(),
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nar_dev_utils::asserts;
+    use narsese::lexical_nse as nse;
+
+    /// 测试用选项：纯文本，便于快照比对
+    fn plain(wrap_width: usize, max_raw_len: usize) -> StyleOptions {
+        StyleOptions {
+            color: false,
+            wrap_width,
+            max_raw_len,
+        }
+    }
+
+    /// 快照：格式化后应与预期完全一致
+    macro_rules! assert_styled {
+        ($($output:expr => $expected:expr)*) => {
+            $(
+                assert_eq!(
+                    format_output_styled(&$output, &StyleOptions::plain()),
+                    $expected
+                );
+            )*
+        };
+    }
+
+    /// 测试/各输出类型
+    #[test]
+    fn test_styled_variants() {
+        assert_styled! {
+            Output::IN {
+                content: "<A --> B>.".into(),
+                narsese: Some(nse!(<A --> B>. %1.0;0.9%)),
+            } => "[IN]         <A --> B>. %1.0;0.9% <A --> B>."
+            Output::OUT {
+                content_raw: "Derived: <A --> C>.".into(),
+                narsese: Some(nse!($0.5;0.5;0.5$ <A --> C>. %1.0;0.81%)),
+            } => "[OUT]        $0.5;0.5;0.5$ <A --> C>. %1.0;0.81% Derived: <A --> C>."
+            Output::ANSWER {
+                content_raw: "Answer: <A --> C>.".into(),
+                narsese: Some(nse!(<A --> C>. :|: %1.0;0.81%)),
+            } => "[ANSWER]     <A --> C>. :|: %1.0;0.81% Answer: <A --> C>."
+            Output::ACHIEVED {
+                content_raw: "ACHIEVED: G.".into(),
+                narsese: Some(nse!(G)),
+            } => "[ACHIEVED]   G ACHIEVED: G."
+            Output::EXE {
+                content_raw: "EXE: ^left([{SELF}, x])".into(),
+                operation: Operation::new("left", [nse!({SELF}), nse!(x)].into_iter().map(|t| t.try_into_term().unwrap())),
+            } => "[EXE]        ^left({SELF}, x) EXE: ^left([{SELF}, x])"
+            Output::ERROR { description: "出错了".into() } => "[ERROR]      出错了"
+            Output::INFO { message: " 信息 ".into() } => "[INFO]       信息"
+            Output::COMMENT { content: "注释".into() } => "[COMMENT]    注释"
+            Output::TERMINATED { description: "终止".into() } => "[TERMINATED] 终止"
+            Output::OTHER { content: "其它".into() } => "[OTHER]      其它"
+            Output::UNCLASSIFIED {
+                r#type: "DISAPPOINT".into(),
+                content: "失望".into(),
+                narsese: None,
+            } => "[DISAPPOINT] 失望"
+            // 无Narsese⇒回退到原始内容
+            Output::ANSWER {
+                content_raw: "<A --> C>. %1.00;0.81%".into(),
+                narsese: None,
+            } => "[ANSWER]     <A --> C>. %1.00;0.81%"
+        }
+    }
+
+    /// 测试/换行与截断
+    #[test]
+    fn test_styled_wrap_truncate() {
+        let out = Output::OUT {
+            content_raw: "Derived: <(&&, A, B) --> C>.".into(),
+            narsese: Some(nse!(<(&&, A, B) --> C>.)),
+        };
+        asserts! {
+            // 换行后与首行内容对齐
+            format_output_styled(&out, &plain(10, 0))
+                => "[OUT]        <(&&, A,\n             B) --> C>."
+            // 原始内容截断
+            format_output_styled(&out, &plain(0, 10))
+                => "[OUT]        <(&&, A, B) --> C>. Derived: …"
+            // 强制断开过长片段
+            wrap_text("abcdefg", 3) => ["abc", "def", "g"]
+            wrap_text("ab cd", 0) => ["ab cd"]
+            truncate_text("abc", 3) => "abc"
+        }
+    }
+
+    /// 测试/`NO_COLOR`
+    #[test]
+    fn test_no_color() {
+        asserts! {
+            is_no_color(None) => false
+            is_no_color(Some("".into())) => false
+            is_no_color(Some("1".into())) => true
+        }
+        // 关闭着色⇒无转义序列
+        let out = Output::ERROR {
+            description: "e".into(),
+        };
+        assert!(!format_output_styled(&out, &StyleOptions::plain()).contains('\x1b'));
+    }
+
+    /// 测试/着色支持
+    #[test]
+    fn test_color_support() {
+        let both = |color| ColorSupport {
+            stdout: color,
+            stderr: color,
+        };
+        asserts! {
+            ColorSupport::decide(false, None, true, true) => both(true)
+            // 显式禁用、`NO_COLOR`
+            ColorSupport::decide(true, None, true, true) => both(false)
+            ColorSupport::decide(false, Some("1".into()), true, true) => both(false)
+            // 非终端⇒仅该输出流不着色
+            ColorSupport::decide(false, None, false, true) => ColorSupport { stdout: false, stderr: true }
+            ColorSupport::decide(false, None, false, true).enabled(true) => true
+        }
+    }
+
+    /// 测试/显式禁用晚于首次使用
+    /// * 🎯此前已有输出检测过环境，`--no-color`仍然生效
+    #[test]
+    fn test_color_disabled_after_use() {
+        let _ = color_support();
+        let disabled = ColorSupport {
+            stdout: false,
+            stderr: false,
+        };
+        assert_eq!(init_color_support(true), disabled);
+        assert_eq!(color_support(), disabled);
+    }
+
+    /// 测试/不着色⇒纯文本前缀，无转义序列
+    #[test]
+    fn test_render_plain() {
+        let out = Output::ANSWER {
+            content_raw: "Answer: <A --> C>.".into(),
+            narsese: Some(nse!(<A --> C>.)),
+        };
+        asserts! {
+            OutputType::Info.render_line("信息", false) => "[INFO] 信息"
+            OutputType::Error.render_line("错误", false) => "[ERROR] 错误"
+            OutputType::render_navm_output(&out, false, false) => "[ANSWER] Answer: <A --> C>."
+            OutputType::render_navm_output(&out, true, false) => "[ANSWER] [# <A --> C>. #] Answer: <A --> C>."
+        }
+        for line in [
+            OutputType::Warn.render_line("警告", false),
+            OutputType::Vm("EXE").render_line("^left", false),
+            OutputType::render_navm_output(&out, true, false),
+        ] {
+            assert!(!line.contains('\x1b'), "{line:?}");
+        }
+    }
+}
@@ -1,9 +1,19 @@
 //! 读取行迭代器
 //! * 🎯以迭代器的语法获取、处理用户输入
 //! * ❌【2024-04-03 14:28:02】放弃「泛型化改造」：[`Stdin`]能`read_line`，但却没实现[`std::io::BufRead`]
+//! * ✨行编辑器：启用`line_editor`特性，且标准输入为终端时
+//!   * 📌上下方向键回溯历史输入，左右方向键编辑当前行
+//!   * 📌Ctrl-C取消当前行；连续第二次Ctrl-C（或Ctrl-D）⇒迭代结束
+//!   * 🚩其它情况（如管道输入）⇒逐行读取标准输入，直到输入结束
 
-use crate::cli_support::io::output_print::OutputType;
-use std::io::{stdin, stdout, Result as IoResult, Stdin, Write};
+use crate::cli_support::io::{input_history::InputHistory, output_print::OutputType};
+use std::io::{stdin, stdout, IsTerminal, Result as IoResult, Stdin, Write};
+
+/// 标准输入是否来自管道（或文件），而非终端
+/// * 🎯`echo "<A --> B>?" | babelnar_cli`：读完输入后退出，而非等待用户
+pub fn is_stdin_piped() -> bool {
+    !stdin().is_terminal()
+}
 
 /// 读取行迭代器
 /// * 🚩每迭代一次，请求用户输入一行
@@ -20,6 +30,17 @@ pub struct ReadlineIter {
     stdin: Stdin,
     /// 输入提示词
     prompt: String,
+    /// 输入的历史记录
+    /// * ⚠️仅记录经由行编辑器的输入
+    history: InputHistory,
+    /// 行编辑器
+    /// * 📌标准输入不是终端，或创建失败⇒[`None`]
+    #[cfg(feature = "line_editor")]
+    editor: Option<rustyline::DefaultEditor>,
+    /// 上一次输入是否被Ctrl-C取消
+    /// * 🎯连续第二次Ctrl-C⇒结束迭代
+    #[cfg(feature = "line_editor")]
+    interrupted: bool,
 }
 
 impl ReadlineIter {
@@ -28,6 +49,69 @@ impl ReadlineIter {
             buffer: String::new(),
             stdin: stdin(),
             prompt: prompt.into(),
+            history: InputHistory::default(),
+            #[cfg(feature = "line_editor")]
+            editor: Self::new_editor(),
+            #[cfg(feature = "line_editor")]
+            interrupted: false,
+        }
+    }
+
+    /// 使用（已加载的）历史记录
+    /// * 🎯回溯先前会话的输入，并将此后的输入保存到历史记录文件
+    pub fn with_history(mut self, history: InputHistory) -> Self {
+        #[cfg(feature = "line_editor")]
+        if let Some(editor) = &mut self.editor {
+            for entry in history.entries() {
+                let _ = editor.add_history_entry(entry.as_str());
+            }
+        }
+        self.history = history;
+        self
+    }
+
+    /// 创建行编辑器
+    /// * 🚩标准输入不是终端⇒不创建：逐行读取
+    #[cfg(feature = "line_editor")]
+    fn new_editor() -> Option<rustyline::DefaultEditor> {
+        if is_stdin_piped() {
+            return None;
+        }
+        match rustyline::DefaultEditor::new() {
+            Ok(editor) => Some(editor),
+            Err(e) => {
+                OutputType::Warn.print_line(&format!("无法启用行编辑器，将逐行读取输入: {e}"));
+                None
+            }
+        }
+    }
+
+    /// 经由行编辑器读取一行
+    /// * 🚩Ctrl-C⇒取消当前行，继续读取；连续第二次Ctrl-C或Ctrl-D⇒结束迭代
+    #[cfg(feature = "line_editor")]
+    fn read_edited(&mut self) -> Option<IoResult<String>> {
+        use rustyline::error::ReadlineError;
+        let editor = self.editor.as_mut()?;
+        loop {
+            match editor.readline(&self.prompt) {
+                Ok(line) => {
+                    self.interrupted = false;
+                    let _ = editor.add_history_entry(line.as_str());
+                    if self.history.push(&line) {
+                        if let Err(e) = self.history.save() {
+                            OutputType::Warn.print_line(&format!("无法保存输入历史: {e}"));
+                        }
+                    }
+                    return Some(Ok(line));
+                }
+                Err(ReadlineError::Interrupted) if !self.interrupted => {
+                    self.interrupted = true;
+                    OutputType::Info.print_line("已取消当前输入；再次按下Ctrl-C以退出");
+                }
+                Err(ReadlineError::Interrupted | ReadlineError::Eof) => return None,
+                Err(ReadlineError::Io(e)) => return Some(Err(e)),
+                Err(e) => return Some(Err(std::io::Error::other(e))),
+            }
         }
     }
 }
@@ -43,6 +127,11 @@ impl Iterator for ReadlineIter {
     type Item = IoResult<String>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        // 有行编辑器⇒经由行编辑器读取
+        #[cfg(feature = "line_editor")]
+        if self.editor.is_some() {
+            return self.read_edited();
+        }
         // 清空缓冲区
         self.buffer.clear();
         // 打印提示词
@@ -52,8 +141,11 @@ impl Iterator for ReadlineIter {
         }
         // 读取一行
         // * 📝`stdin()`是懒加载的，只会获取一次，随后返回的都是引用对象
-        if let Err(e) = self.stdin.read_line(&mut self.buffer) {
-            return Some(Err(e));
+        // * 🚩读到0字节⇒输入结束（如管道关闭、Ctrl-D）⇒结束迭代
+        match self.stdin.read_line(&mut self.buffer) {
+            Ok(0) => return None,
+            Err(e) => return Some(Err(e)),
+            Ok(_) => {}
         }
         // 返回
         Some(IoResult::Ok(self.buffer.clone()))
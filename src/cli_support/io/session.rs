@@ -0,0 +1,419 @@
+//! NAVM会话的录制与重放
+//! * 🎯完整记录一次交互（所有输入的[`Cmd`]与输出的[`Output`]），以便之后在其它CIN上重跑、对比
+//! * 📌文件格式：NDJSON，每行一条记录`{"t": 毫秒, "dir": "in"|"raw"|"out", "payload": 内容}`
+//!   * 📄`{"t":0,"dir":"in","payload":"NSE <A --> B>."}`
+//!   * 📄`{"t":1,"dir":"raw","payload":"*stats"}`
+//!   * 📄`{"t":3,"dir":"out","payload":{"type":"IN","content":"..."}}`
+//! * 🚩录制：[`SessionRecorder`]包装一个[`VmRuntime`]，自身亦为[`VmRuntime`]
+//! * 🚩重放：[`SessionReplayer`]按原有时间间隔，将`in`记录输入到另一个运行时
+
+use crate::runtimes::{KillHandle, VmRuntimeBoost};
+use anyhow::{anyhow, Result};
+use navm::{
+    cmd::Cmd,
+    output::Output,
+    vm::{VmRuntime, VmStatus},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+/// 会话记录的方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionDirection {
+    /// 输入：NAVM指令
+    In,
+    /// 原始输入：绕过NAVM指令，直接送达CIN的一行
+    /// * 🎯对应[`VmRuntimeBoost::input_raw`]
+    /// * ⚠️不被[`SessionReplayer`]重放：重放只输入NAVM指令
+    Raw,
+    /// 输出：NAVM输出
+    Out,
+}
+
+/// 一条会话记录
+/// * 📌对应NDJSON文件中的一行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// 时间戳
+    /// * 🚩自录制开始以来的毫秒数
+    pub t: u64,
+
+    /// 方向
+    pub dir: SessionDirection,
+
+    /// 内容
+    /// * 🚩输入⇒NAVM指令的字符串形式（可被[`Cmd::parse`]解析）
+    /// * 🚩输出⇒NAVM输出的JSON对象
+    pub payload: serde_json::Value,
+}
+
+impl SessionRecord {
+    /// 从「输入记录」中解析出NAVM指令
+    /// * 🚩非输入记录⇒[`None`]
+    pub fn parse_cmd(&self) -> Option<Result<Cmd>> {
+        if self.dir != SessionDirection::In {
+            return None;
+        }
+        let cmd = match self.payload.as_str() {
+            Some(line) => {
+                Cmd::parse(line).map_err(|e| anyhow!("无法解析录制的指令「{line}」：{e}"))
+            }
+            None => Err(anyhow!("输入记录的内容不是字符串：{}", self.payload)),
+        };
+        Some(cmd)
+    }
+}
+
+/// 会话录制器
+/// * 🎯以「装饰器」形式包装运行时：对外表现与原运行时一致
+/// * 🚩每次成功输入指令、拉取到输出时，追加一行记录
+///   * 📌逐行写入：即便程序意外退出，已写入的记录仍然完整
+/// * ⚠️写入失败不影响运行时本身：仅作为错误返回
+pub struct SessionRecorder<R: VmRuntime, W: Write = File> {
+    /// 被包装的运行时
+    inner: R,
+
+    /// 记录写入的目标
+    writer: W,
+
+    /// 录制开始的时刻
+    start: Instant,
+}
+
+impl<R: VmRuntime> SessionRecorder<R> {
+    /// 构造函数/创建文件
+    /// * 🚩若文件已存在，将被覆盖
+    pub fn create(inner: R, path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(inner, file))
+    }
+}
+
+impl<R: VmRuntime, W: Write> SessionRecorder<R, W> {
+    /// 构造函数
+    /// * 🚩以当前时刻作为录制起点
+    pub fn new(inner: R, writer: W) -> Self {
+        Self {
+            inner,
+            writer,
+            start: Instant::now(),
+        }
+    }
+
+    /// 拆出被包装的运行时与写入目标
+    pub fn into_inner(self) -> (R, W) {
+        (self.inner, self.writer)
+    }
+
+    /// 追加一条记录
+    fn record(&mut self, dir: SessionDirection, payload: serde_json::Value) -> Result<()> {
+        let record = SessionRecord {
+            t: self.start.elapsed().as_millis() as u64,
+            dir,
+            payload,
+        };
+        let line = serde_json::to_string(&record)?;
+        writeln!(self.writer, "{line}")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// 记录一个输出
+    fn record_output(&mut self, output: &Output) -> Result<()> {
+        let payload = serde_json::to_value(output)?;
+        self.record(SessionDirection::Out, payload)
+    }
+}
+
+impl<R: VmRuntime, W: Write> VmRuntime for SessionRecorder<R, W> {
+    fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        let line = cmd.to_string();
+        self.inner.input_cmd(cmd)?;
+        self.record(SessionDirection::In, line.into())
+    }
+
+    fn fetch_output(&mut self) -> Result<Output> {
+        let output = self.inner.fetch_output()?;
+        self.record_output(&output)?;
+        Ok(output)
+    }
+
+    fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        let output = self.inner.try_fetch_output()?;
+        if let Some(output) = &output {
+            self.record_output(output)?;
+        }
+        Ok(output)
+    }
+
+    fn status(&self) -> &VmStatus {
+        self.inner.status()
+    }
+
+    fn terminate(&mut self) -> Result<()> {
+        self.inner.terminate()
+    }
+}
+
+/// 增强功能
+/// * 🚩转发给被包装的运行时，并记录经过的输入输出
+///   * 📌保留被包装运行时的「批量拉取」与「原始输入」实现
+impl<R: VmRuntimeBoost, W: Write> VmRuntimeBoost for SessionRecorder<R, W> {
+    fn drain_outputs(&mut self) -> Result<Vec<Output>> {
+        let outputs = self.inner.drain_outputs()?;
+        for output in &outputs {
+            self.record_output(output)?;
+        }
+        Ok(outputs)
+    }
+
+    fn drain_outputs_timed(&mut self) -> Result<Vec<(Output, Instant)>> {
+        let outputs = self.inner.drain_outputs_timed()?;
+        for (output, _) in &outputs {
+            self.record_output(output)?;
+        }
+        Ok(outputs)
+    }
+
+    fn input_raw(&mut self, line: &str) -> Result<()> {
+        self.inner.input_raw(line)?;
+        self.record(SessionDirection::Raw, line.into())
+    }
+
+    fn cyc_ack_count(&self, steps: usize) -> usize {
+        self.inner.cyc_ack_count(steps)
+    }
+
+    fn flush_inputs(&mut self) -> Result<()> {
+        self.inner.flush_inputs()
+    }
+
+    fn kill_handle(&self) -> Option<KillHandle> {
+        self.inner.kill_handle()
+    }
+}
+
+/// 会话重放器
+/// * 🎯将录制的输入重新送入另一个运行时
+/// * 🚩只重放`in`记录，并保持原有的时间间隔
+///   * 📌可通过「速度倍率」加速或减速
+///   * 💡若要对比输出，可将目标运行时也包装进[`SessionRecorder`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionReplayer {
+    /// 所有记录
+    records: Vec<SessionRecord>,
+
+    /// 速度倍率
+    /// * 🚩原时间间隔除以此倍率
+    /// * 📜默认为`1.0`
+    /// * ⚠️非正数⇒不等待
+    speed: f64,
+}
+
+impl SessionReplayer {
+    /// 构造函数
+    pub fn new(records: Vec<SessionRecord>) -> Self {
+        Self {
+            records,
+            speed: 1.0,
+        }
+    }
+
+    /// 从NDJSON中读取
+    /// * 🚩跳过空行
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut records = vec![];
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(&line)
+                .map_err(|e| anyhow!("第{}行会话记录解析失败：{e}", i + 1))?;
+            records.push(record);
+        }
+        Ok(Self::new(records))
+    }
+
+    /// 从NDJSON文件中读取
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// 配置/速度倍率
+    pub fn speed(&mut self, speed: f64) {
+        self.speed = speed;
+    }
+
+    /// 获取所有记录
+    pub fn records(&self) -> &[SessionRecord] {
+        &self.records
+    }
+
+    /// 解析出所有「输入记录」
+    /// * ⚙️返回`(时间戳, 指令)`的数组
+    pub fn input_cmds(&self) -> Result<Vec<(u64, Cmd)>> {
+        self.records
+            .iter()
+            .filter_map(|record| Some(record.parse_cmd()?.map(|cmd| (record.t, cmd))))
+            .collect()
+    }
+
+    /// 将输入重放到运行时中
+    /// * 🚩先解析所有指令，再按时间间隔逐个输入
+    ///   * 📌首条指令立即输入
+    /// * ⚙️返回已输入的指令
+    pub fn replay(&self, runtime: &mut impl VmRuntime) -> Result<Vec<Cmd>> {
+        let cmds = self.input_cmds()?;
+        let mut last_t = cmds.first().map_or(0, |(t, _)| *t);
+        let mut replayed = vec![];
+        for (t, cmd) in cmds {
+            sleep(self.delay(t.saturating_sub(last_t)));
+            last_t = t;
+            runtime.input_cmd(cmd.clone())?;
+            replayed.push(cmd);
+        }
+        Ok(replayed)
+    }
+
+    /// 根据速度倍率计算等待时长
+    fn delay(&self, millis: u64) -> Duration {
+        match self.speed > 0.0 {
+            true => {
+                Duration::try_from_secs_f64(millis as f64 / 1000.0 / self.speed).unwrap_or_default()
+            }
+            false => Duration::ZERO,
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::FakeVm;
+    use nar_dev_utils::manipulate;
+    use narsese::lexical_nse_task as nse_task;
+    use navm::vm::VmLauncher;
+
+    /// 测试用的脚本化会话
+    fn script() -> Vec<Cmd> {
+        vec![
+            Cmd::NSE(nse_task!(<A --> B>.)),
+            Cmd::NSE(nse_task!(<B --> C>.)),
+            Cmd::NSE(nse_task!(<A --> C>?)),
+            Cmd::CYC(5),
+        ]
+    }
+
+    /// 测试用的模拟运行时
+    /// * 🚩步进时回答
+    fn fake_vm() -> impl VmRuntimeBoost {
+        manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::CYC(5),
+                [Output::ANSWER {
+                    content_raw: "<A --> C>.".into(),
+                    narsese: None,
+                }],
+            )
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败")
+    }
+
+    /// 测试/录制后重放
+    #[test]
+    fn test_record_replay() -> Result<()> {
+        // 录制
+        let mut recorder = SessionRecorder::new(fake_vm(), vec![]);
+        for cmd in script() {
+            recorder.input_cmd(cmd)?;
+            recorder.drain_outputs()?;
+        }
+        let (_, recorded) = recorder.into_inner();
+
+        // 检查记录
+        let replayer = SessionReplayer::from_reader(recorded.as_slice())?;
+        let dirs = replayer
+            .records()
+            .iter()
+            .map(|record| record.dir)
+            .collect::<Vec<_>>();
+        use SessionDirection::*;
+        assert_eq!(dirs, [In, Out, In, Out, In, Out, In, Out]);
+        assert!(replayer.records().windows(2).all(|w| w[0].t <= w[1].t));
+        assert_eq!(
+            replayer.records()[7].payload["type"],
+            serde_json::json!("ANSWER")
+        );
+
+        // 重放到另一个（同样被录制的）运行时
+        let mut replayer = replayer;
+        replayer.speed(100.0);
+        let mut target = SessionRecorder::new(fake_vm(), vec![]);
+        let replayed = replayer.replay(&mut target)?;
+        assert_eq!(replayed, script());
+        let (_, replayed_record) = target.into_inner();
+        let replayed_cmds = SessionReplayer::from_reader(replayed_record.as_slice())?
+            .input_cmds()?
+            .into_iter()
+            .map(|(_, cmd)| cmd)
+            .collect::<Vec<_>>();
+        assert_eq!(replayed_cmds, script());
+        Ok(())
+    }
+
+    /// 测试/录制原始输入
+    /// * 🎯`inputMode: raw`下的`--record`：原始输入不报错，且被记录
+    #[test]
+    fn test_record_raw() -> Result<()> {
+        let mut recorder = SessionRecorder::new(fake_vm(), vec![]);
+        recorder.input_raw("*stats")?;
+        recorder.input_cmd(Cmd::CYC(5))?;
+        recorder.drain_outputs()?;
+        let (_, recorded) = recorder.into_inner();
+        let replayer = SessionReplayer::from_reader(recorded.as_slice())?;
+        let records = replayer
+            .records()
+            .iter()
+            .map(|record| (record.dir, record.payload.clone()))
+            .collect::<Vec<_>>();
+        use SessionDirection::*;
+        assert_eq!(records[0], (Raw, "*stats".into()));
+        assert_eq!(records[1], (In, "CYC 5".into()));
+        assert_eq!(records[2].0, Out);
+        // 重放只输入NAVM指令
+        let t = replayer.records()[1].t;
+        assert_eq!(replayer.input_cmds()?, [(t, Cmd::CYC(5))]);
+        Ok(())
+    }
+
+    /// 测试/重放时保持时间间隔
+    #[test]
+    fn test_replay_delay() -> Result<()> {
+        let records = [(0, "CYC 1"), (200, "CYC 2")]
+            .into_iter()
+            .map(|(t, line)| SessionRecord {
+                t,
+                dir: SessionDirection::In,
+                payload: line.into(),
+            })
+            .collect();
+        let mut replayer = SessionReplayer::new(records);
+        replayer.speed(2.0);
+        let start = Instant::now();
+        replayer.replay(&mut fake_vm())?;
+        // 200ms ÷ 2 = 100ms
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        Ok(())
+    }
+}
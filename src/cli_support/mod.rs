@@ -6,8 +6,20 @@ util::mods! {
     // CIN搜索
     pub cin_search;
 
+    // 启动配置
+    pub config;
+
+    // 启动配置检查
+    pub config_validate;
+
+    // 启动配置预设
+    pub presets;
+
     // 输入输出
     pub io;
+
+    // 终止信号处理
+    pub shutdown;
 }
 
 // 错误处理增强
@@ -0,0 +1,225 @@
+//! 启动配置预设
+//! * 🎯各CIN「开箱即用」的启动配置：无需为每个CIN复制同样的HJSON
+//!   * 📌转译器、带标准参数的启动命令、预置NAL（静音）、自动重启
+//! * 🎯BabelNAR CLI的`--preset ona --path ./executables/ONA.exe`
+//! * 🚩预设只是普通的[`LaunchConfig`]：与配置文件一样合并、检查
+//!   * 📄`--preset ona --path ONA.exe -c override.hjson`⇒以配置文件微调预设
+//! * 📌可执行文件、jar文件⇒以其所在目录为工作目录，命令中只保留文件名
+//!   * ⚠️相对路径基于启动时的工作目录，调用方宜先绝对化
+
+use super::config::{
+    LaunchConfig, LaunchConfigCommand, LaunchConfigPreludeNAL, LaunchConfigTranslators,
+};
+use anyhow::{anyhow, Result};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// 所有启动配置预设的名称
+/// * 🎯错误信息中的「可用预设」
+pub const LAUNCH_PRESET_NAMES: &[&str] = &["opennars", "ona", "pynars"];
+
+/// 预设的预置NAL
+/// * 🎯静音：避免派生结论刷屏，只留回答、操作等
+pub const PRESET_PRELUDE_NAL: &str = "'/VOL 0";
+
+/// PyNARS预设默认启动的模块
+/// * 📌扩展了「附加指令」的「高级控制台」
+pub const DEFAULT_PYNARS_MODULE: &str = "pynars.ConsolePlus";
+
+/// PyNARS预设的环境变量
+/// * 🎯Windows控制台默认编码非UTF-8时，PyNARS的中文、符号输出不乱码
+pub const PYNARS_ENV: &[(&str, &str)] = &[("PYTHONIOENCODING", "utf-8")];
+
+/// 构造预设的公共部分
+/// * 🚩转译器、启动命令、预置NAL、自动重启
+/// * 📌无附加的环境变量⇒不设置`env`
+fn preset(
+    description: &str,
+    translators: &str,
+    cmd: impl Into<String>,
+    cmd_args: Vec<String>,
+    current_dir: Option<PathBuf>,
+    env: &[(&str, &str)],
+) -> LaunchConfig {
+    let env = env
+        .iter()
+        .map(|&(key, value)| (key.to_string(), value.to_string()))
+        .collect::<BTreeMap<_, _>>();
+    LaunchConfig {
+        description: Some(description.into()),
+        translators: Some(LaunchConfigTranslators::Same(translators.into())),
+        command: Some(LaunchConfigCommand {
+            cmd: cmd.into(),
+            cmd_args: Some(cmd_args),
+            current_dir,
+            env: (!env.is_empty()).then_some(env),
+        }),
+        prelude_nal: Some(vec![LaunchConfigPreludeNAL::Text(
+            PRESET_PRELUDE_NAL.into(),
+        )]),
+        auto_restart: Some(true),
+        ..Default::default()
+    }
+}
+
+/// 拆分文件路径：`(./文件名, 所在目录)`
+/// * 🎯以文件所在目录为工作目录启动
+/// * 📌前缀`./`：指定「启动工作目录下的文件」，而非在`PATH`中查找
+/// * 🚩无上级目录（如`ONA.exe`）⇒不切换工作目录
+fn split_file_path(path: &Path) -> (String, Option<PathBuf>) {
+    let file_name = match path.file_name() {
+        Some(file_name) => format!("./{}", file_name.to_string_lossy()),
+        None => path.to_string_lossy().into_owned(),
+    };
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .map(Path::to_path_buf);
+    (file_name, parent)
+}
+
+/// OpenNARS预设
+/// * 🚩`java -jar 【jar文件】`，工作目录为jar文件所在目录
+pub fn preset_opennars(jar_path: impl AsRef<Path>) -> LaunchConfig {
+    let (jar, current_dir) = split_file_path(jar_path.as_ref());
+    preset(
+        "OpenNARS（jar文件，需要Java运行时JRE）",
+        "opennars",
+        "java",
+        vec!["-jar".into(), jar],
+        current_dir,
+        &[],
+    )
+}
+
+/// ONA预设
+/// * 🚩`【exe文件】 shell`，工作目录为exe文件所在目录
+pub fn preset_ona(exe_path: impl AsRef<Path>) -> LaunchConfig {
+    let (exe, current_dir) = split_file_path(exe_path.as_ref());
+    preset(
+        "ONA (OpenNARS for Applications)",
+        "ona",
+        exe,
+        vec!["shell".into()],
+        current_dir,
+        &[],
+    )
+}
+
+/// PyNARS预设
+/// * 🚩`python -m 【模块】`，工作目录为源码根目录
+/// * 🚩默认以UTF-8输出：见[`PYNARS_ENV`]
+/// * 📄`preset_pynars("./PyNARS", DEFAULT_PYNARS_MODULE)`
+pub fn preset_pynars(root: impl AsRef<Path>, module: &str) -> LaunchConfig {
+    preset(
+        "PyNARS（基于Python运行时，从源码启动）",
+        "pynars",
+        "python",
+        vec!["-m".into(), module.into()],
+        Some(root.as_ref().to_path_buf()),
+        PYNARS_ENV,
+    )
+}
+
+/// 按名称获取预设
+/// * 🚩不区分大小写
+/// * 📌`path`：OpenNARS⇒jar文件；ONA⇒exe文件；PyNARS⇒源码根目录（使用[默认模块](DEFAULT_PYNARS_MODULE)）
+/// * ⚠️未知名称⇒报错，并列出可用的预设
+pub fn preset_by_name(name: &str, path: impl AsRef<Path>) -> Result<LaunchConfig> {
+    let path = path.as_ref();
+    match name.to_lowercase().as_str() {
+        "opennars" => Ok(preset_opennars(path)),
+        "ona" => Ok(preset_ona(path)),
+        "pynars" => Ok(preset_pynars(path, DEFAULT_PYNARS_MODULE)),
+        _ => Err(anyhow!(
+            "未知的启动预设「{name}」，可用的预设：{}",
+            LAUNCH_PRESET_NAMES.join(", ")
+        )),
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_support::config::RuntimeConfig;
+    use nar_dev_utils::asserts;
+
+    /// 测试/所有预设均可转换为运行时配置
+    #[test]
+    fn test_presets_to_runtime_config() -> Result<()> {
+        for name in LAUNCH_PRESET_NAMES {
+            let config = preset_by_name(name, "executables/cin")?;
+            assert!(!config.need_polyfill(), "{name}");
+            let runtime = RuntimeConfig::try_from(config)?;
+            asserts! {
+                runtime.translators => LaunchConfigTranslators::Same(name.to_string())
+                runtime.prelude_nal => [LaunchConfigPreludeNAL::Text(PRESET_PRELUDE_NAL.into())]
+                runtime.auto_restart => true
+            }
+        }
+        Ok(())
+    }
+
+    /// 测试/启动命令
+    #[test]
+    fn test_preset_commands() {
+        let command = |config: LaunchConfig| {
+            let command = config.command.unwrap();
+            (command.cmd, command.cmd_args.unwrap(), command.current_dir)
+        };
+        asserts! {
+            command(preset_opennars("exe/nars.jar"))
+                => ("java".into(), vec!["-jar".into(), "./nars.jar".into()], Some("exe".into()))
+            command(preset_ona("exe/ONA.exe"))
+                => ("./ONA.exe".into(), vec!["shell".into()], Some("exe".into()))
+            command(preset_pynars("PyNARS", "pynars.Console"))
+                => ("python".into(), vec!["-m".into(), "pynars.Console".into()], Some("PyNARS".into()))
+            // 无上级目录⇒不切换工作目录
+            command(preset_ona("ONA.exe")).2 => None
+        }
+    }
+
+    /// 测试/环境变量：仅PyNARS默认设置
+    #[test]
+    fn test_preset_env() {
+        let env = |config: LaunchConfig| config.command.unwrap().env;
+        asserts! {
+            env(preset_pynars("PyNARS", DEFAULT_PYNARS_MODULE))
+                => Some([("PYTHONIOENCODING".into(), "utf-8".into())].into())
+            env(preset_ona("ONA.exe")) => None
+            env(preset_opennars("nars.jar")) => None
+        }
+    }
+
+    /// 测试/按名称获取
+    #[test]
+    fn test_preset_by_name() {
+        asserts! {
+            preset_by_name("ONA", "ONA.exe").unwrap() => preset_ona("ONA.exe")
+            preset_by_name("PyNARS", "PyNARS").unwrap() => preset_pynars("PyNARS", DEFAULT_PYNARS_MODULE)
+        }
+        let e = preset_by_name("onna", "ONA.exe").unwrap_err().to_string();
+        assert!(
+            e.contains("onna") && e.contains("opennars, ona, pynars"),
+            "{e}"
+        );
+    }
+
+    /// 测试/以配置文件微调预设
+    #[test]
+    fn test_preset_override() -> Result<()> {
+        let mut config = preset_ona("exe/ONA.exe");
+        config.override_from(&LaunchConfig::from_json_str(
+            r#"{"autoRestart": false, "preludeNAL": {"text": "'/VOL 100"}}"#,
+        )?);
+        let runtime = RuntimeConfig::try_from(config)?;
+        asserts! {
+            runtime.auto_restart => false
+            runtime.translators => LaunchConfigTranslators::Same("ona".into())
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+//! 终止信号处理
+//! * 🎯CLI被Ctrl-C、`SIGTERM`终止时，先终止CIN子进程再退出，避免「进程残留」
+//! * 🚩信号处理函数只设置「终止标志」：清理工作交给各线程在循环中检查后完成
+//!   * 📌首个信号⇒设置标志，等待清理
+//!   * 📌再次收到信号⇒立即强制退出
+//! * 🚩按平台分别实现
+//!   * 📌Unix：`SIGINT`、`SIGTERM`
+//!   * 📌Windows：控制台控制事件（Ctrl-C、Ctrl-Break、关闭窗口）
+
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 终止标志：不依赖`cli_support`，测试工具等也会检查
+pub use crate::support::shutdown_flag::*;
+
+/// 是否已安装信号处理函数
+static HANDLER_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// 安装信号处理函数
+/// * 🚩重复调用⇒无操作
+pub fn install_shutdown_handler() -> Result<()> {
+    if HANDLER_INSTALLED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    platform::install().inspect_err(|_| HANDLER_INSTALLED.store(false, Ordering::SeqCst))
+}
+
+/// Unix：`SIGINT`、`SIGTERM`
+#[cfg(unix)]
+mod platform {
+    use super::request_shutdown;
+    use anyhow::Result;
+
+    /// 信号处理函数
+    /// * ⚠️只能调用异步信号安全的函数
+    extern "C" fn handle(signal: libc::c_int) {
+        if !request_shutdown(signal) {
+            // SAFETY: `_exit`是异步信号安全的
+            unsafe { libc::_exit(128 + signal) }
+        }
+    }
+
+    pub fn install() -> Result<()> {
+        for signal in [libc::SIGINT, libc::SIGTERM] {
+            // SAFETY: `sigaction`结构体已完整初始化，处理函数只进行原子操作与`_exit`
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = handle as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                action.sa_flags = libc::SA_RESTART;
+                libc::sigemptyset(&mut action.sa_mask);
+                if libc::sigaction(signal, &action, std::ptr::null_mut()) != 0 {
+                    return Err(std::io::Error::last_os_error().into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Windows：控制台控制事件
+/// * 📌事件值`+2`后作为「信号」：Ctrl-C⇒`130`，与Unix的`SIGINT`一致
+#[cfg(windows)]
+mod platform {
+    use super::request_shutdown;
+    use anyhow::Result;
+
+    extern "system" {
+        fn SetConsoleCtrlHandler(
+            handler: Option<unsafe extern "system" fn(u32) -> i32>,
+            add: i32,
+        ) -> i32;
+    }
+
+    /// 控制事件处理函数
+    /// * 📌返回非零⇒已处理，不再执行默认的「立即终止」
+    unsafe extern "system" fn handle(event: u32) -> i32 {
+        let signal = event as i32 + 2;
+        if !request_shutdown(signal) {
+            std::process::exit(128 + signal);
+        }
+        1
+    }
+
+    pub fn install() -> Result<()> {
+        // SAFETY: 处理函数在整个程序运行期间有效
+        match unsafe { SetConsoleCtrlHandler(Some(handle), 1) } {
+            0 => Err(std::io::Error::last_os_error().into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 其它平台：不支持信号处理
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    use anyhow::Result;
+
+    pub fn install() -> Result<()> {
+        Err(anyhow::anyhow!("当前平台不支持终止信号处理"))
+    }
+}
@@ -18,6 +18,15 @@ util::mods! {
     // 输出处理者
     pub output_handler;
 
+    // 闭环智能体
+    pub agent;
+
+    // 共用支持
+    pub support;
+
+    // 预导入
+    pub prelude;
+
     // 可选模块 //
 
     // 各CIN的启动器、运行时实现
@@ -115,22 +124,91 @@ pub mod tests {
             PYNARS_ROOT = "./executables/PyNARS"
             PYNARS_MODULE = "pynars.ConsolePlus"
             NARS_PYTHON = "./executables/nars-python-main.exe"
+            NARS_PYTHON_OUTPUT = "./executables/nars-python-output.log"
             CXIN_JS = "./executables/cxin-nars-shell.js"
             OPENJUNARS = "./executables/OpenJunars/launch.jl"
         }
     }
 
+    /// 测试用CIN输出样本
+    /// * 🎯输出转译的单元测试与基准测试（`benches/`）共用
+    /// * 📌每行一条CIN输出，含CIN的输入回显与空行
+    pub mod output_samples {
+        /// ONA
+        /// * 📄源自ONA测试文件`whatwarmer.nal`与ONA的命令行交互
+        pub const ONA: &str = include_str!("tests/outputs/ona_whatwarmer.txt");
+    }
+
+    /// 测试用运行时配置
+    /// * 🎯各处测试共用：只需写出与默认不同的配置项
+    ///   * 📄`RuntimeConfig { strict_mode: true, ..test_runtime_config() }`
+    /// * 📌原生转译器、空命令；其余均为「未设置」时的值
+    #[cfg(feature = "cli_support")]
+    pub fn test_runtime_config() -> crate::cli_support::config::RuntimeConfig {
+        use crate::cli_support::config::*;
+        RuntimeConfig {
+            config_path: Default::default(),
+            translators: LaunchConfigTranslators::Same("native".into()),
+            command: LaunchConfigCommand::default(),
+            connect: None,
+            websocket: None,
+            prelude_nal: vec![],
+            user_input: false,
+            input_mode: Default::default(),
+            auto_restart: false,
+            strict_mode: false,
+            output_format: Default::default(),
+            set_ack: Default::default(),
+            print_level: Default::default(),
+            no_color: false,
+            startup_timeout: 0,
+            piped_input_grace: 0,
+            report_file: None,
+            history_file: None,
+            precision_epoch: None,
+            output_type_aliases: Default::default(),
+            suppress_input_echo: false,
+            save_outputs_keep: None,
+            save_outputs_allow_absolute: false,
+            capabilities: Default::default(),
+            cyc_pacing: None,
+            cyc_await: false,
+            coalesce_lines: false,
+            process_encoding: None,
+            python_env: None,
+        }
+    }
+
     /// 测试用宏/找不到路径即退出
-    /// * 🚩输入一个`&str`，构建`&Path`并在其不存在时退出程序，或返回该`&Path`对象
+    /// * 🚩输入一个`&str`，构建路径并在其不存在时退出程序，或返回该路径（[`PathBuf`](std::path::PathBuf)）
+    /// * 🚩`./executables/`下的路径⇒在各CIN目录中查找，并打印所使用的目录
+    ///   * 📌查找顺序见[`process_io::executables_dir`](crate::process_io::executables_dir)
+    ///   * 📌最后查找项目根目录下的`executables`：从子目录运行时也能找到
     #[macro_export]
     macro_rules! exists_or_exit {
         ($path:expr) => {{
             let path = std::path::Path::new($path);
-            if !path.exists() {
-                println!("所需路径 {path:?} 不存在，已自动退出");
-                std::process::exit(0)
+            let name = $crate::process_io::strip_executables_dir(path);
+            let resolved = match name == path {
+                true => path.exists().then(|| path.to_path_buf()),
+                false => {
+                    let project_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                        .join($crate::process_io::EXECUTABLES_DIR);
+                    $crate::process_io::resolve_executable(name, Some(&project_dir)).map(
+                        |resolved| {
+                            println!("[INFO] 在CIN目录{:?}中找到{name:?}", resolved.root);
+                            resolved.path
+                        },
+                    )
+                }
+            };
+            match resolved {
+                Some(path) => path,
+                None => {
+                    println!("所需路径 {path:?} 不存在，已自动退出");
+                    std::process::exit(0)
+                }
             }
-            path
         }};
     }
 }
@@ -0,0 +1,157 @@
+//! 模块：NAVM输出的JSON格式
+//! * 🎯统一BabelNAR对外输出的JSON：Websocket回传、`--output-json`、`''save-outputs`
+//! * 🎯供下游客户端解析：无需逆向[`Output::to_json_string`]的结构
+//! * 📌格式：在[`OutputJSON`]的基础上附加`schema`字段（版本号）
+//!   * 📄`{"schema":1,"type":"ANSWER","content":"Answer: <A --> B>.","narsese":"<A --> B>."}`
+//!   * 📄`{"schema":1,"type":"EXE","content":"EXE: ^left","operation":["left","{SELF}"]}`
+//! * 🚩解析时忽略未知字段：后续版本新增的字段（以及`t`、`instance`等附加字段）不影响解析
+
+use anyhow::{anyhow, Result};
+use navm::output::{Output, OutputJSON};
+use serde_json::Value;
+
+/// 当前的JSON格式版本
+/// * 🚩格式有不兼容的变动时递增
+pub const OUTPUT_JSON_SCHEMA: u64 = 1;
+
+/// 格式版本所在的字段
+pub const FIELD_SCHEMA: &str = "schema";
+
+/// 将NAVM输出转换为JSON值
+/// * 🚩在[`Output::to_json_struct`]的基础上附加[`FIELD_SCHEMA`]字段
+/// * 📌总是JSON对象：调用方可在其中追加字段
+pub fn output_to_json(output: &Output) -> Value {
+    let mut value = serde_json::to_value(output.to_json_struct())
+        .expect("不会转换失败：内部JSON结构总是转换成功");
+    if let Some(object) = value.as_object_mut() {
+        object.insert(FIELD_SCHEMA.into(), OUTPUT_JSON_SCHEMA.into());
+    }
+    value
+}
+
+/// 从JSON字符串中解析NAVM输出
+/// * 🚩先检查格式版本，再折叠为[`Output`]
+///   * 📌缺少版本号⇒视作旧格式：与当前格式一致
+///   * ⚠️版本号不是非负整数⇒报错
+/// * 📌忽略未知字段
+pub fn output_from_json(json: &str) -> Result<Output> {
+    let value = serde_json::from_str::<Value>(json)?;
+    if let Some(schema) = value.get(FIELD_SCHEMA) {
+        schema
+            .as_u64()
+            .ok_or_else(|| anyhow!("无效的NAVM输出JSON格式版本：{schema}"))?;
+    }
+    let json = serde_json::from_value::<OutputJSON>(value)?;
+    Output::try_from_json_struct(json)
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use narsese::{lexical_nse as nse, lexical_nse_term as nse_term};
+    use navm::output::Operation;
+
+    /// 所有输出类型的样例
+    fn all_outputs() -> Vec<Output> {
+        vec![
+            Output::IN {
+                content: "IN: <A --> B>.".into(),
+                narsese: Some(nse!(<A --> B>.)),
+            },
+            Output::IN {
+                content: "IN: *volume=0".into(),
+                narsese: None,
+            },
+            Output::OUT {
+                content_raw: "OUT: <A --> B>. %1.00;0.90%".into(),
+                narsese: Some(nse!(<A --> B>. %1.00;0.90%)),
+            },
+            Output::ERROR {
+                description: "a, \"b\"\nc".into(),
+            },
+            Output::ANSWER {
+                content_raw: "Answer: <A --> C>?".into(),
+                narsese: Some(nse!(<A --> C>?)),
+            },
+            Output::ACHIEVED {
+                content_raw: "ACHIEVED: <(*, {SELF}) --> ^left>! :|:".into(),
+                narsese: Some(nse!(<(*, {SELF}) --> ^left>! :|:)),
+            },
+            Output::EXE {
+                content_raw: "EXE: ^left({SELF}, x)".into(),
+                operation: Operation::new("left", [nse_term!({ SELF }), nse_term!(x)].into_iter()),
+            },
+            Output::EXE {
+                content_raw: "EXE: ^pick".into(),
+                operation: Operation::new("pick", [].into_iter()),
+            },
+            Output::INFO {
+                message: "信息".into(),
+            },
+            Output::COMMENT {
+                content: "comment".into(),
+            },
+            Output::TERMINATED {
+                description: "exit code: 0".into(),
+            },
+            Output::OTHER {
+                content: "executed based on: ...".into(),
+            },
+            Output::UNCLASSIFIED {
+                r#type: "ANTICIPATE".into(),
+                content: "ANTICIPATE: <A --> B>".into(),
+                narsese: Some(nse!(<A --> B>)),
+            },
+            Output::UNCLASSIFIED {
+                r#type: "CUSTOM".into(),
+                content: "".into(),
+                narsese: None,
+            },
+        ]
+    }
+
+    /// 测试/往返转换：所有输出类型
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        for output in all_outputs() {
+            let value = output_to_json(&output);
+            assert_eq!(value[FIELD_SCHEMA], OUTPUT_JSON_SCHEMA, "{value}");
+            assert_eq!(value["type"], output.type_name(), "{value}");
+            let parsed = output_from_json(&value.to_string())?;
+            assert_eq!(parsed, output, "{value}");
+        }
+        Ok(())
+    }
+
+    /// 测试/解析：忽略未知字段、兼容无版本号的旧格式
+    #[test]
+    fn test_parse_tolerant() -> Result<()> {
+        let output = Output::INFO {
+            message: "hi".into(),
+        };
+        // 未知字段
+        let json = r#"{"schema":2,"type":"INFO","content":"hi","t":12,"instance":"ona","future":{"x":[1]}}"#;
+        assert_eq!(output_from_json(json)?, output);
+        // 旧格式
+        assert_eq!(output_from_json(&output.to_json_string())?, output);
+        Ok(())
+    }
+
+    /// 测试/解析：无效输入⇒报错
+    #[test]
+    fn test_parse_invalid() {
+        let cases = [
+            "",
+            "[]",
+            r#"{"schema":1,"content":"hi"}"#,
+            r#"{"schema":"1","type":"INFO","content":"hi"}"#,
+            r#"{"schema":1,"type":"EXE","content":"EXE: ^left"}"#,
+            r#"{"schema":1,"type":"EXE","content":"EXE: ^left","operation":[]}"#,
+            r#"{"schema":1,"type":"OUT","content":"","narsese":"<A --> "}"#,
+        ];
+        for json in cases {
+            assert!(output_from_json(json).is_err(), "{json}");
+        }
+    }
+}
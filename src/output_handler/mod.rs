@@ -3,3 +3,7 @@
 
 // 流式处理者列表
 pub mod flow_handler_list;
+
+// NAVM输出的JSON格式
+#[cfg(feature = "serde_json")]
+pub mod json;
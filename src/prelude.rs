@@ -0,0 +1,71 @@
+//! 预导入模块
+//! * 🎯一次导入「启动CIN⇒输入指令⇒拉取输出⇒NAL测试」流程中的常用类型
+//!   * 📄`use babel_nar::prelude::*;`
+//! * 📌各部分随所属模块的特性启用
+//!
+//! ## 用例
+//!
+//! 基于模拟运行时，无需任何CIN可执行文件：
+//!
+//! ```
+//! use babel_nar::prelude::*;
+//! use nar_dev_utils::manipulate;
+//! use narsese::{lexical_nse as nse, lexical_nse_task as nse_task};
+//! use std::path::Path;
+//!
+//! // 启动：在下一个`CYC`后回答问题
+//! let mut vm = manipulate!(
+//!     FakeVm::new()
+//!     => .respond_after_cycle(
+//!         Cmd::NSE(nse_task!(<A --> C>?)),
+//!         [Output::ANSWER {
+//!             content_raw: "<A --> C>. %1.00;0.81%".into(),
+//!             narsese: Some(nse!(<A --> C>. %1.0;0.81%)),
+//!         }],
+//!     )
+//! )
+//! .launch()
+//! .unwrap();
+//!
+//! // 逐条置入NAL，并检查输出预期
+//! let nal = "<A --> C>?\n1\n''expect-contains: ANSWER <A --> C>.";
+//! let mut output_cache: Vec<Output> = vec![];
+//! let mut context = PutNalContext {
+//!     interactivity: &InteractivityState::default(),
+//!     nal_root_path: Path::new("."),
+//!     set_ack: &SetAck::default(),
+//!     cyc_await: None,
+//!     requires: &mut RequiresRegion::default(),
+//! };
+//! for input in parse(nal) {
+//!     put_nal(&mut vm, input.unwrap(), &mut output_cache, &mut context).unwrap();
+//! }
+//! assert!(output_cache.iter().any(|output| matches!(output, Output::ANSWER { .. })));
+//! vm.terminate().unwrap();
+//! ```
+
+// NAVM
+pub use navm::{
+    cmd::Cmd,
+    output::{Operation, Output},
+    vm::{VmLauncher, VmRuntime, VmStatus},
+};
+
+// 运行时
+pub use crate::runtimes::{
+    CommandVm, CommandVmRuntime, FakeVm, FakeVmRuntime, IoTranslators, VmRuntimeBoost,
+};
+
+// 各CIN的启动器
+#[cfg(feature = "cin_implements")]
+pub use crate::cin_implements::{
+    cxin_js::CXinJS, nars_python::NARSPython, ona::ONA, openjunars::OpenJunars, opennars::OpenNARS,
+    pynars::PyNARS,
+};
+
+// NAL测试
+#[cfg(feature = "test_tools")]
+pub use crate::test_tools::{
+    nal_format::parse, put_nal, InteractivityState, NALInput, OutputExpectation, PutNalContext,
+    RequiresRegion, SetAck, VmOutputCache,
+};
@@ -0,0 +1,182 @@
+//! 查找CIN可执行文件所在的目录
+//! * 🎯CIN不附带于源码中，通常放在`executables`目录下
+//!   * 📌从其它目录启动CLI、运行测试时，`./executables`不一定存在
+//! * 🚩按固定顺序搜索若干「根目录」，返回第一个存在的路径
+//!   1. 环境变量[`EXECUTABLES_ENV`]所指定的目录
+//!   2. 当前可执行文件所在目录下的`executables`
+//!   3. 当前工作目录下的`executables`
+//!   4. 配置文件所在目录（若有）
+
+use std::{
+    env,
+    ffi::OsString,
+    path::{Component, Path, PathBuf},
+};
+
+/// 指定CIN目录的环境变量
+pub const EXECUTABLES_ENV: &str = "BABELNAR_EXECUTABLES";
+
+/// CIN目录的默认名称
+pub const EXECUTABLES_DIR: &str = "executables";
+
+/// 查找到的CIN
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedExecutable {
+    /// 所在的根目录
+    pub root: PathBuf,
+    /// 完整路径
+    pub path: PathBuf,
+}
+
+/// 获取所有搜索根目录
+/// * 🚩顺序即优先级，参见模块文档
+/// * 📌环境变量为空⇒视作未设置
+pub fn executables_roots(config_dir: Option<&Path>) -> Vec<PathBuf> {
+    executables_roots_with_env(env::var_os(EXECUTABLES_ENV), config_dir)
+}
+
+/// 获取所有搜索根目录，以`env_dir`作为环境变量[`EXECUTABLES_ENV`]的值
+/// * 🎯测试时无需修改进程的环境变量
+/// * 🚩其余同[`executables_roots`]
+pub fn executables_roots_with_env(
+    env_dir: Option<OsString>,
+    config_dir: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut roots = vec![];
+    if let Some(dir) = env_dir.filter(|dir| !dir.is_empty()) {
+        roots.push(PathBuf::from(dir));
+    }
+    if let Some(exe_dir) = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        roots.push(exe_dir.join(EXECUTABLES_DIR));
+    }
+    if let Ok(cwd) = env::current_dir() {
+        roots.push(cwd.join(EXECUTABLES_DIR));
+    }
+    roots.extend(config_dir.map(Path::to_path_buf));
+    roots
+}
+
+/// 在各根目录中查找CIN
+/// * 📌`name`为相对于根目录的路径，通常是单个文件名（如`ONA.exe`）
+///   * 🚩空路径⇒查找根目录自身
+/// * 🚩返回第一个存在的路径；均不存在⇒[`None`]
+pub fn resolve_executable_in(
+    name: impl AsRef<Path>,
+    roots: impl IntoIterator<Item = PathBuf>,
+) -> Option<ResolvedExecutable> {
+    let name = name.as_ref();
+    roots.into_iter().find_map(|root| {
+        let path = match name.as_os_str().is_empty() {
+            true => root.clone(),
+            false => root.join(name),
+        };
+        path.exists().then_some(ResolvedExecutable { root, path })
+    })
+}
+
+/// 在默认的根目录中查找CIN
+/// * 🚩根目录见[`executables_roots`]
+pub fn resolve_executable(
+    name: impl AsRef<Path>,
+    config_dir: Option<&Path>,
+) -> Option<ResolvedExecutable> {
+    resolve_executable_in(name, executables_roots(config_dir))
+}
+
+/// 去掉路径开头的`./executables/`
+/// * 🎯将「相对项目根目录」的测试用路径转换为「相对CIN目录」的路径
+/// * 📄`./executables/ONA.exe` ⇒ `ONA.exe`
+/// * 🚩不以`executables`开头⇒原样返回
+pub fn strip_executables_dir(path: &Path) -> &Path {
+    let mut components = path.components();
+    loop {
+        match components.next() {
+            Some(Component::CurDir) => continue,
+            Some(Component::Normal(name)) if name == EXECUTABLES_DIR => {
+                return components.as_path()
+            }
+            _ => return path,
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// 测试/按顺序查找
+    /// * 🎯前面的根目录优先；不存在⇒继续查找
+    #[test]
+    fn test_resolve_in() {
+        let temp = env::temp_dir().join(format!("babelnar_executables_{}", std::process::id()));
+        let (first, second) = (temp.join("first"), temp.join("second"));
+        fs::create_dir_all(&first).unwrap();
+        fs::create_dir_all(&second).unwrap();
+        fs::write(second.join("ONA.exe"), "").unwrap();
+        fs::write(first.join("nars.jar"), "").unwrap();
+        fs::write(second.join("nars.jar"), "").unwrap();
+        let roots = || [temp.join("missing"), first.clone(), second.clone()];
+
+        let resolved = resolve_executable_in("ONA.exe", roots()).expect("应能找到");
+        assert_eq!(resolved.root, second);
+        assert_eq!(resolved.path, second.join("ONA.exe"));
+        let resolved = resolve_executable_in("nars.jar", roots()).expect("应能找到");
+        assert_eq!(resolved.root, first);
+        assert_eq!(resolve_executable_in("PyNARS", roots()), None);
+        // 空路径⇒根目录自身
+        let resolved = resolve_executable_in("", roots()).expect("应能找到");
+        assert_eq!(resolved.path, first);
+
+        fs::remove_dir_all(&temp).unwrap();
+    }
+
+    /// 测试/环境变量
+    /// * 🎯设置时优先于其它根目录；为空、未设置⇒不参与查找
+    /// * 🚩直接传入环境变量的值：不修改进程的环境变量
+    #[test]
+    fn test_env() {
+        let temp = env::temp_dir().join(format!("babelnar_executables_env_{}", std::process::id()));
+        let (from_env, from_config) = (temp.join("env"), temp.join("config"));
+        fs::create_dir_all(&from_env).unwrap();
+        fs::create_dir_all(&from_config).unwrap();
+        fs::write(from_env.join("ONA.exe"), "").unwrap();
+        fs::write(from_config.join("ONA.exe"), "").unwrap();
+        let roots = |env_dir: Option<&Path>| {
+            executables_roots_with_env(env_dir.map(OsString::from), Some(&from_config))
+        };
+
+        let with_env = roots(Some(&from_env));
+        assert_eq!(with_env[0], from_env);
+        let resolved = resolve_executable_in("ONA.exe", with_env).expect("应能找到");
+        assert_eq!(resolved.root, from_env);
+
+        let empty_env = roots(Some(Path::new("")));
+        assert!(!empty_env.contains(&PathBuf::new()));
+        let resolved = resolve_executable_in("ONA.exe", empty_env).expect("应能找到");
+        assert_eq!(resolved.root, from_config);
+
+        let without_env = roots(None);
+        assert_eq!(without_env.last(), Some(&from_config));
+        assert!(!without_env.contains(&from_env));
+        let resolved = resolve_executable_in("ONA.exe", without_env).expect("应能找到");
+        assert_eq!(resolved.root, from_config);
+
+        fs::remove_dir_all(&temp).unwrap();
+    }
+
+    /// 测试/去掉开头的`./executables/`
+    #[test]
+    fn test_strip_executables_dir() {
+        let strip = |path: &str| strip_executables_dir(Path::new(path)).to_path_buf();
+        assert_eq!(strip("./executables/ONA.exe"), PathBuf::from("ONA.exe"));
+        assert_eq!(strip("executables/PyNARS"), PathBuf::from("PyNARS"));
+        assert_eq!(strip("./executables"), PathBuf::new());
+        assert_eq!(strip("./other/ONA.exe"), PathBuf::from("./other/ONA.exe"));
+        assert_eq!(strip("ONA.exe"), PathBuf::from("ONA.exe"));
+    }
+}
@@ -12,18 +12,23 @@
 //!   * ⚠️【2024-03-25 13:32:50】
 
 use std::{
+    collections::VecDeque,
     error::Error,
     ffi::OsStr,
     fmt::{self, Debug, Display, Formatter},
-    io::{BufRead, BufReader, ErrorKind, Result as IoResult, Write},
+    io::{BufRead, BufReader, ErrorKind, Read, Result as IoResult, Write},
+    path::Path,
     process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio},
     sync::{
-        mpsc::{channel, Receiver, Sender},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
         Arc, Mutex,
     },
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 // use util::*;
+use super::{isolate_process_group, terminate_process_tree, ProcessEncoding};
+use crate::support::eprint_notice;
 use anyhow::Result;
 use util::ResultBoost;
 
@@ -46,6 +51,121 @@ type OutputListener = dyn FnMut(String) + Send + Sync;
 /// 简化定义`Arc< Mutex<T>>`
 type ArcMutex<T> = Arc<Mutex<T>>;
 
+/// 统一定义「辅助输出源」的类型
+type AuxOutput = dyn Read + Send;
+
+/// 经「子进程输入」通道发送的一行输入
+/// * 🎯控制指令（如`VOL`、`EXI`）不被大量排队的普通输入阻塞
+/// * ⚠️需【自行尾缀换行符】：同[`IoProcessManager::put`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessInput {
+    /// 普通输入：按发送先后写入
+    Normal(String),
+    /// 优先输入：先于所有尚未写入的普通输入
+    Priority(String),
+}
+
+/// 「辅助输出源」读到末尾后，再次尝试读取前的等待时间
+/// * 🎯避免「追踪文件」时空转
+pub const AUX_OUTPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 按行读取子进程的输出
+/// * 🎯统一换行符：`\r\n`、单独的`\r`均视作行尾，读出的行只以`\n`结尾
+///   * 📄Windows上ONA、OpenNARS输出`\r\n`：行尾的`\r`不再混入转译器与`raw_content`
+///   * 📄进度条、转圈动画等以单独的`\r`刷新：每次刷新各为一行，不会累积成一个巨大的缓冲区
+/// * 🚩一直读到行尾：子进程在行中途刷新（如超长的Java调用栈）时，仍读出完整的一行
+/// * 📌除换行符外，原样保留其它字节
+/// * 🚩按字节读取，再按[文本编码](ProcessEncoding)解码：非法字节不会导致丢行
+struct LineReader<R> {
+    /// 内部的缓冲区读取器
+    reader: R,
+    /// 文本编码
+    encoding: ProcessEncoding,
+    /// 上一行是否以`\r`结尾
+    /// * 🚩是⇒跳过紧随其后的`\n`：`\r\n`只算一个行尾
+    /// * 📌不在读到`\r`时立即等待下一个字节：单独的`\r`之后可能许久没有输出
+    skip_lf: bool,
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// 构造函数
+    /// * 🚩按UTF-8解码
+    #[cfg(test)]
+    fn new(reader: R) -> Self {
+        Self::with_encoding(reader, ProcessEncoding::UTF8)
+    }
+
+    /// 构造函数（指定文本编码）
+    fn with_encoding(reader: R, encoding: ProcessEncoding) -> Self {
+        Self {
+            reader,
+            encoding,
+            skip_lf: false,
+        }
+    }
+
+    /// 读取一行，追加到`buf`中
+    /// * ⚙️返回解码后的字节数（换行符统一为`\n`）：`0`⇒已到末尾
+    /// * 🚩到末尾仍无行尾⇒读出不带换行符的剩余内容
+    /// * 📌无法解码的内容不报错：见[`ProcessEncoding::decode`]
+    fn read_line(&mut self, buf: &mut String) -> IoResult<usize> {
+        let mut bytes = vec![];
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(available) => available,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            // 已到末尾
+            if available.is_empty() {
+                break;
+            }
+            // 跳过`\r\n`中的`\n`
+            if std::mem::take(&mut self.skip_lf) && available[0] == b'\n' {
+                self.reader.consume(1);
+                continue;
+            }
+            match available.iter().position(|&b| b == b'\n' || b == b'\r') {
+                // 读到行尾
+                Some(i) => {
+                    bytes.extend_from_slice(&available[..i]);
+                    bytes.push(b'\n');
+                    self.skip_lf = available[i] == b'\r';
+                    self.reader.consume(i + 1);
+                    break;
+                }
+                // 尚无行尾⇒继续读取
+                None => {
+                    let len = available.len();
+                    bytes.extend_from_slice(available);
+                    self.reader.consume(len);
+                }
+            }
+        }
+        let line = self.encoding.decode_owned(bytes);
+        let len = line.len();
+        // 缓冲区为空⇒直接移入，免去拷贝
+        match buf.is_empty() {
+            true => *buf = line,
+            false => buf.push_str(&line),
+        }
+        Ok(len)
+    }
+}
+
+/// 「杀死子进程」的结果
+/// * 🎯区分「子进程已自行退出」与「子进程被强制终止」
+///   * 📄CIN自行退出后再终止，不应被视作错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillOutcome {
+    /// 子进程被强制终止
+    Killed,
+    /// 子进程在此之前已自行退出 | 附带其退出状态
+    AlreadyExited(ExitStatus),
+    /// 先前已调用过[`IoProcessManager::kill`]：此次无操作
+    AlreadyKilled,
+}
+
 /// 构建一个「IO进程」
 /// * 📌只是作为一个基于配置的「子进程启动器」存在
 ///   * 作为真正的`IoProcessManager`的launcher/builder
@@ -56,6 +176,14 @@ pub struct IoProcess {
     command: Command,
     /// 内部配置的「输出侦听器」
     out_listener: Option<Box<OutputListener>>,
+    /// 内部配置的「辅助输出源」
+    aux_output: Option<Box<AuxOutput>>,
+    /// 内部配置的「文本编码」
+    /// * 📌亦可经由[`CommandVm::encoding`](crate::runtimes::CommandVm::encoding)配置
+    pub(crate) encoding: ProcessEncoding,
+    /// 是否关闭子进程的标准输入
+    /// * 🚩`true`⇒标准输入置空，不启动「写输入」守护线程
+    no_stdin: bool,
 }
 
 impl IoProcess {
@@ -76,6 +204,64 @@ impl IoProcess {
         self
     }
 
+    /// 添加多个命令行参数
+    pub fn args<S>(mut self, args: impl IntoIterator<Item = S>) -> Self
+    where
+        S: AsRef<OsStr>,
+    {
+        // 添加参数
+        self.command.args(args);
+        // 返回自身以便链式调用
+        self
+    }
+
+    /// 设置环境变量
+    /// * 📄`JAVA_TOOL_OPTIONS`、`PYTHONIOENCODING=utf-8`、`NODE_OPTIONS`
+    /// * 📌在继承自身环境变量的基础上追加、覆盖
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        // 设置环境变量
+        self.command.env(key, value);
+        // 返回自身以便链式调用
+        self
+    }
+
+    /// 设置多个环境变量
+    /// * 📌同[`Self::env`]
+    pub fn envs<K, V>(mut self, envs: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        // 设置环境变量
+        self.command.envs(envs);
+        // 返回自身以便链式调用
+        self
+    }
+
+    /// 设置工作目录
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        // 设置工作目录
+        self.command.current_dir(dir);
+        // 返回自身以便链式调用
+        self
+    }
+
+    /// 关闭标准输入
+    /// * 🎯从文件而非标准输入读取输入的CIN：避免其等待永不到来的输入
+    /// * ⚠️此后[`IoProcessManager::put`]等输入方法均会报错
+    pub fn no_stdin(mut self) -> Self {
+        // 字段赋值
+        self.no_stdin = true;
+        // 返回自身以便链式调用
+        self
+    }
+
+    /// 获取内部的「进程指令」
+    /// * 🎯检查所构建的程序、参数、环境变量
+    pub fn command(&self) -> &Command {
+        &self.command
+    }
+
     /// 添加输出侦听器
     /// * 📌此处因生命周期问题（难以绑定`listener`到`self`）设置`F`的约束为`'static`
     pub fn out_listener<F>(mut self, listener: F) -> Self
@@ -88,6 +274,30 @@ impl IoProcess {
         self
     }
 
+    /// 添加辅助输出源
+    /// * 🎯用于「不向标准输出打印结果」的CIN：从日志文件等其它来源获取输出
+    ///   * 📄NARS-Python：GUI程序，结果不写入标准输出
+    /// * 🚩读到的每一行，都和标准输出一样进入「输出通道」
+    ///   * ⚠️不经过「输出侦听器」
+    /// * 📌读到末尾时不会结束，而是等待后续内容（类似`tail -f`）
+    pub fn aux_output(mut self, source: impl Read + Send + 'static) -> Self {
+        // 字段赋值
+        self.aux_output = Some(Box::new(source));
+        // 返回自身以便链式调用
+        self
+    }
+
+    /// 设置文本编码
+    /// * 🎯非UTF-8的CIN：如GBK编码的Windows控制台
+    /// * 🚩读取输出、标准错误时作为备用编码解码；写入输入时以之编码
+    ///   * 📌见[`ProcessEncoding`]
+    pub fn encoding(mut self, encoding: ProcessEncoding) -> Self {
+        // 字段赋值
+        self.encoding = encoding;
+        // 返回自身以便链式调用
+        self
+    }
+
     /// 启动
     /// * 🚩通过[`Self::try_launch`]尝试启动，然后直接解包
     /// * 🚩【2024-04-02 04:11:27】现在为方便反馈处理错误，重新变为[`Result`]类型
@@ -101,22 +311,33 @@ impl IoProcess {
     /// * 🚩此处只负责创建子进程[`Child`]，
     ///   * ⚠️不负责对子进程的控制（监听、通道）等
     pub fn try_launch(mut self) -> std::io::Result<IoProcessManager> {
+        // 在独立的进程组中启动 | 🎯终止时一并终止其产生的子进程
+        isolate_process_group(&mut self.command);
         // 创建一个子进程
         let child =
             // 指令+参数
             self.command
                 // 输入输出
-                .stdin(Stdio::piped())
+                .stdin(match self.no_stdin {
+                    true => Stdio::null(),
+                    false => Stdio::piped(),
+                })
                 .stdout(Stdio::piped())
                 // 产生进程
                 .spawn()?;
-        println!("Started process: {}", child.id());
+        // * 🚩打印到标准错误：不干扰「以标准输出为数据通道」的调用者
+        eprintln!("Started process: {}", child.id());
 
         // 获取输出侦听器
         let out_listener = self.out_listener;
 
         // 创建「子进程管理器」对象
-        Ok(IoProcessManager::new(child, out_listener))
+        Ok(IoProcessManager::new_with_aux(
+            child,
+            out_listener,
+            self.aux_output,
+            self.encoding,
+        ))
     }
 }
 
@@ -130,6 +351,12 @@ impl From<Command> for IoProcess {
             command,
             // 侦听器空置
             out_listener: None,
+            // 辅助输出源空置
+            aux_output: None,
+            // 默认UTF-8
+            encoding: ProcessEncoding::UTF8,
+            // 默认开启标准输入
+            no_stdin: false,
         }
     }
 }
@@ -155,18 +382,34 @@ pub struct IoProcessManager {
     /// * 🚩现在兼容「侦听器」「通道」两种模式，重新必要化
     thread_read_out: Option<JoinHandle<()>>,
     // thread_read_out: JoinHandle<()>,
+    /// 「读辅助输出源」守护线程
+    /// * 🚩仅在配置了「辅助输出源」时存在
+    thread_read_aux: Option<JoinHandle<()>>,
     /// 子线程的终止信号
     termination_signal: ArcMutex<bool>,
 
+    /// 子进程的「标准IO已关闭」信号
+    /// * 🎯在未收到终止信号时，由「读输出」子线程报告「子进程已关闭」
+    ///   * 📄读取到标准输出末尾
+    /// * 📌此时子进程通常已退出：可结合[`Self::exit_status`]确认
+    child_closed: ArcMutex<bool>,
+
+    /// 最近一次读到输出的时间
+    /// * 🎯判断CIN是否已「安静下来」：见[`Self::last_output_at`]
+    /// * 🚩由「读输出」「读辅助输出源」子线程在发送每行输出前更新
+    last_output: ArcMutex<Option<Instant>>,
+
     /// 子进程输出的「接收者」
     /// * 🚩子进程发送给外部侦听器，同时由外部接收
     ///   * 在将输出发送给侦听器时，会在此留下备份
     /// * ⚠️如果直接调用[`Receiver::recv`]方法，可能会导致线程阻塞
-    child_out: Mutex<Receiver<String>>,
+    /// * 📌每行附带「收到时间」：子线程读到该行的时刻
+    child_out: Mutex<Receiver<(String, Instant)>>,
     // ! 【2024-03-23 19:31:56】现在兼容「输出侦听」与「输出通道」二者
     /// 子进程输入的「发送者」
     /// * 🚩子进程接收来自外部发送的消息，由外部发送
-    child_in: Mutex<Sender<String>>,
+    /// * 📌普通输入与「优先输入」共用此通道：由「写输入」子线程区分
+    child_in: Mutex<Sender<ProcessInput>>,
     // /// 子进程的「输出监听器」
     // out_listener: Option<Box<OutputListener>>,
     // ! 【2024-03-22 09:54:22】↑现在使用「输出侦听器」模式，此字段数据存储在`thread_read_out`中
@@ -182,9 +425,22 @@ impl IoProcessManager {
 
     /// 构造方法
     /// * 🚩从「子进程」与「输出侦听器」构造「进程管理者」
-    pub fn new(mut child: Child, out_listener: Option<Box<OutputListener>>) -> Self {
+    pub fn new(child: Child, out_listener: Option<Box<OutputListener>>) -> Self {
+        Self::new_with_aux(child, out_listener, None, ProcessEncoding::UTF8)
+    }
+
+    /// 构造方法（附带「辅助输出源」与「文本编码」）
+    /// * 🚩从「子进程」「输出侦听器」「辅助输出源」与「文本编码」构造「进程管理者」
+    /// * 📌「辅助输出源」的内容与子进程输出汇入同一「输出通道」
+    pub fn new_with_aux(
+        mut child: Child,
+        out_listener: Option<Box<OutputListener>>,
+        aux_output: Option<Box<AuxOutput>>,
+        encoding: ProcessEncoding,
+    ) -> Self {
         // 提取子进程的标准输入输出
-        let stdin = child.stdin.take().unwrap();
+        // * 🚩标准输入可能已关闭：见[`IoProcess::no_stdin`]
+        let stdin = child.stdin.take();
         let stdout = child.stdout.take().unwrap();
 
         // 创建通道
@@ -195,21 +451,42 @@ impl IoProcessManager {
 
         // 生成「终止信号」共享数据
         let termination_signal = Arc::new(Mutex::new(false));
+        // 生成「标准IO已关闭」共享数据
+        let child_closed = Arc::new(Mutex::new(false));
+        // 生成「最近输出时间」共享数据
+        let last_output = Arc::new(Mutex::new(None));
 
         // // 生成「输出计数」共享数据
         // let num_output = Arc::new(Mutex::new(0));
 
         // 生成进程的「读写守护」（线程）
-        let thread_write_in = Some(IoProcessManager::spawn_thread_write_in(
-            stdin,
-            child_in,
-            termination_signal.clone(),
-        ));
+        // * 🚩无标准输入⇒不启动，输入通道随之断开
+        let thread_write_in = stdin.map(|stdin| {
+            IoProcessManager::spawn_thread_write_in(
+                stdin,
+                child_in,
+                termination_signal.clone(),
+                encoding,
+            )
+        });
+        let thread_read_aux = aux_output.map(|source| {
+            IoProcessManager::spawn_thread_read_aux(
+                source,
+                child_out.clone(),
+                termination_signal.clone(),
+                child_closed.clone(),
+                last_output.clone(),
+                encoding,
+            )
+        });
         let thread_read_out = Some(IoProcessManager::spawn_thread_read_out(
             stdout,
             child_out,
             out_listener,
             termination_signal.clone(),
+            child_closed.clone(),
+            last_output.clone(),
+            encoding,
             // num_output.clone(),
         ));
         // let thread_read_out =
@@ -221,6 +498,7 @@ impl IoProcessManager {
             process: child,
             thread_read_out,
             thread_write_in,
+            thread_read_aux,
             // 捕获通道的两端
             child_out: Mutex::new(out_sender),
             child_in: Mutex::new(in_receiver),
@@ -228,6 +506,8 @@ impl IoProcessManager {
             // ! 【2024-03-22 09:53:50】↑不再于自身存储「输出侦听器」，而是存储在`thread_read_out`中
             // 共享变量
             termination_signal,
+            child_closed,
+            last_output,
             // num_output,
             // ! 【2024-03-24 01:24:58】↑不再使用「输出计数」：有时会遗漏输出，并且有`try_recv`的更可靠方案
         }
@@ -235,33 +515,67 @@ impl IoProcessManager {
 
     /// 生成一个子线程，管理子进程的标准输入，接收通道另一端输出
     /// * 📌读输入，写进程 | stdin >>> child_in_receiver
+    /// * 🚩每次写入普通输入前，先取出通道中已有的所有输入
+    ///   * 📌「优先输入」立即写入；普通输入排队，每次写入一行
+    ///   * 📌普通输入、「优先输入」各自保持输入顺序
+    ///   * 📌无排队的普通输入⇒阻塞等待通道，不空转
     #[inline]
     fn spawn_thread_write_in(
         stdin: ChildStdin,
-        child_in_receiver: Receiver<String>,
+        child_in_receiver: Receiver<ProcessInput>,
         termination_signal: ArcMutex<bool>,
+        encoding: ProcessEncoding,
     ) -> thread::JoinHandle<()> {
         thread::spawn(move || {
             // 从通道接收者读取输入 | 从「进程消息发送者」向进程发送文本
             let mut stdin = stdin;
-            // ! 注意：这个`for`循环是阻塞的
-            for line in child_in_receiver {
+            // 写入一行 | 返回「是否继续」
+            let mut write_line = |line: String| {
                 // 检查终止信号 | ⚠️不要在终止后还发消息
                 if *termination_signal.lock().expect("无法锁定终止信号") {
-                    // println!("子进程收到终止信号");
-                    break;
+                    return false;
                 }
-                // 写入输出
-                if let Err(e) = stdin.write_all(line.as_bytes()) {
-                    match e.kind() {
-                        // * 🚩进程已关闭⇒退出
-                        // TODO: 🏗️外包「错误处理」逻辑
-                        ErrorKind::BrokenPipe => {
-                            println!("子进程已关闭");
-                            break;
+                // 写入输出 | 按文本编码编码
+                match stdin.write_all(&encoding.encode(&line)) {
+                    Ok(()) => true,
+                    // * 🚩进程已关闭⇒退出
+                    //   * 📌此后的输入将因「通道已断开」而报错
+                    //   * 📌「已关闭」由「读输出」子线程在读完所有输出后报告，以保证输出顺序
+                    Err(e) if e.kind() == ErrorKind::BrokenPipe => false,
+                    // 其它
+                    Err(e) => {
+                        eprint_notice("ERROR", &format!("子进程写入错误：{e}"));
+                        true
+                    }
+                }
+            };
+            // 已取出、尚未写入的普通输入
+            let mut queued = VecDeque::new();
+            loop {
+                // 无排队的普通输入⇒阻塞等待下一个输入
+                // * 📌[`Self::kill`]经通道发送空行：仍能立即唤醒
+                let first = match queued.is_empty() {
+                    true => match child_in_receiver.recv() {
+                        Ok(input) => Some(input),
+                        Err(..) => break,
+                    },
+                    false => None,
+                };
+                // 取出通道中已有的所有输入：「优先输入」立即写入，普通输入排队
+                for input in first.into_iter().chain(child_in_receiver.try_iter()) {
+                    match input {
+                        ProcessInput::Normal(line) => queued.push_back(line),
+                        ProcessInput::Priority(line) => {
+                            if !write_line(line) {
+                                return;
+                            }
                         }
-                        // 其它
-                        _ => println!("子进程写入错误：{e}"),
+                    }
+                }
+                // 再写一行普通输入
+                if let Some(line) = queued.pop_front() {
+                    if !write_line(line) {
+                        break;
                     }
                 }
             }
@@ -275,19 +589,23 @@ impl IoProcessManager {
     ///   * 通过「缓冲区读取器」[`BufReader`]读取子进程输出
     ///   * 不断尝试读取，直到有内容
     ///   * 朝通道[`Sender`]发送内容
+    /// * 🚩统一换行符：见[`LineReader`]
     #[inline]
     fn spawn_thread_read_out(
         stdout: ChildStdout,
-        child_out_sender: Sender<String>,
+        child_out_sender: Sender<(String, Instant)>,
         out_listener: Option<Box<dyn FnMut(String) + Send + Sync>>,
         termination_signal: ArcMutex<bool>,
+        child_closed: ArcMutex<bool>,
+        last_output: ArcMutex<Option<Instant>>,
+        encoding: ProcessEncoding,
         // num_output: ArcMutex<usize>,
     ) -> thread::JoinHandle<()> {
         // 将Option包装成一个新的函数
         // ! ⚠️【2024-03-23 19:54:43】↓类型注释是必须的：要约束闭包类型一致
-        let mut listener_code: Box<dyn FnMut(&String) + Send + Sync> = match out_listener {
+        let mut listener_code: Box<dyn FnMut(&str) + Send + Sync> = match out_listener {
             // * 🚩先前有⇒实际执行 | 仅在实际有值时拷贝并传送给侦听器
-            Some(mut listener) => Box::new(move |s: &String| listener(s.clone())),
+            Some(mut listener) => Box::new(move |s: &str| listener(s.to_owned())),
             // * 🚩先前无⇒空函数
             None => Box::new(move |_| {}),
         };
@@ -295,7 +613,7 @@ impl IoProcessManager {
         thread::spawn(move || {
             // 创建缓冲区读取器 | ⚠️【2024-03-23 23:42:08】这里的`BufReader`不能简化
             // * 📝`ChildStdout`没有`read_line`功能，但可以通过`BufReader`封装
-            let mut stdout_reader = BufReader::new(stdout);
+            let mut stdout_reader = LineReader::with_encoding(BufReader::new(stdout), encoding);
 
             // 创建缓冲区 | 🎯可持续使用
             let mut buf = String::new();
@@ -307,24 +625,32 @@ impl IoProcessManager {
                 // * 📄在ONA处不阻塞，但在OpenNARS时阻塞
                 // * 🔗<https://rustwiki.org/zh-CN/std/io/trait.BufRead.html#method.read_line>
                 match stdout_reader.read_line(&mut buf) {
-                    // 没有任何输入⇒检查终止信号
-                    // * 📌不能在这里中断，需要检查终止信号
+                    // 没有任何输入⇒标准输出已到末尾
                     // * 🚩【2024-03-24 01:48:19】目前**允许**在进程终止时获取其输出
                     //   * 一般侦听器都能侦听到
+                    // * 🚩未收到终止信号⇒子进程自行关闭：报告「已关闭」
+                    //   * 📌此前的输出均已发送，关闭信号总在最后一条输出之后
                     Ok(0) => {
-                        if *termination_signal.lock().expect("无法锁定终止信号") {
-                            // println!("子进程收到终止信号");
-                            break;
+                        if !*termination_signal.lock().expect("无法锁定终止信号") {
+                            *child_closed.lock().expect("无法锁定关闭信号") = true;
                         }
+                        break;
                     }
                     // 有效输入
                     Ok(_) => {
                         // ! 🚩现在兼容「侦听器」「通道」二者
                         // 先侦听 | 只传递引用，仅在「实际有侦听器」时拷贝消息
                         listener_code(&buf);
+                        // 记录时间 | 先于发送：拉取到输出时，时间总已更新
+                        let received = Instant::now();
+                        if let Ok(mut last_output) = last_output.lock() {
+                            *last_output = Some(received);
+                        }
                         // 向「进程消息接收者」传递消息（实际上是「输出」）
-                        if let Err(e) = child_out_sender.send(buf.clone()) {
-                            println!("无法向主进程发送消息：{e:?}");
+                        // * 🚩移交缓冲区而非拷贝：缓冲区随之清空，下次读取时重新分配
+                        let line = std::mem::take(&mut buf);
+                        if let Err(e) = child_out_sender.send((line, received)) {
+                            eprint_notice("ERROR", &format!("无法向主进程发送消息：{e:?}"));
                             break;
                         }
                         // // 输出计数
@@ -335,16 +661,73 @@ impl IoProcessManager {
                         // ! 【2024-03-24 01:42:46】现在取消「输出计数」机制：计数可能不准确，并且被`try_recv`取代
                     }
                     // 报错⇒处理错误
+                    // * 📌内容已有损解码，此处只有真正的IO错误
                     Err(e) => {
-                        // 只是「不包含字符」（过早读取）⇒跳过
-                        let message = e.to_string();
-                        if message.contains("stream did not contain") {
-                            // 什么都不做
-                        } else {
-                            println!("无法接收子进程输出：{e:?} in「{buf}」");
+                        eprint_notice("ERROR", &format!("无法接收子进程输出：{e:?} in「{buf}」"));
+                        break;
+                    }
+                }
+                // 清空缓冲区
+                buf.clear();
+            }
+        })
+    }
+
+    /// 生成一个子线程，读取「辅助输出源」，传送读到的行到「输出通道」
+    /// * 📌与[`Self::spawn_thread_read_out`]共用同一个[`Sender`]
+    /// * 🚩读到末尾⇒检查终止信号，未终止则等待后重试
+    ///   * 📄文件在末尾之后追加的内容，仍可被后续读取
+    ///   * 📌子进程已关闭⇒不会再有追加：结束线程、释放[`Sender`]，输出通道随之断开
+    /// * 🚩不完整的行（写入方尚未写完）⇒保留在缓冲区，待补全后再发送
+    /// * 🚩按字节读取，完整的行再按文本编码解码
+    #[inline]
+    fn spawn_thread_read_aux(
+        source: Box<AuxOutput>,
+        child_out_sender: Sender<(String, Instant)>,
+        termination_signal: ArcMutex<bool>,
+        child_closed: ArcMutex<bool>,
+        last_output: ArcMutex<Option<Instant>>,
+        encoding: ProcessEncoding,
+    ) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let mut reader = BufReader::new(source);
+            let mut buf = vec![];
+            loop {
+                match reader.read_until(b'\n', &mut buf) {
+                    // 读到末尾⇒检查终止信号、关闭信号，然后等待
+                    Ok(0) => {
+                        if *termination_signal.lock().expect("无法锁定终止信号")
+                            || *child_closed.lock().expect("无法锁定关闭信号")
+                        {
+                            break;
+                        }
+                        thread::sleep(AUX_OUTPUT_POLL_INTERVAL);
+                        continue;
+                    }
+                    // 不完整的行⇒等待后续内容
+                    Ok(_) if !buf.ends_with(b"\n") => {
+                        thread::sleep(AUX_OUTPUT_POLL_INTERVAL);
+                        continue;
+                    }
+                    // 完整的行⇒记录时间并发送
+                    Ok(_) => {
+                        let received = Instant::now();
+                        if let Ok(mut last_output) = last_output.lock() {
+                            *last_output = Some(received);
+                        }
+                        if let Err(e) = child_out_sender.send((encoding.decode(&buf), received)) {
+                            eprint_notice("ERROR", &format!("无法向主进程发送消息：{e:?}"));
                             break;
                         }
                     }
+                    // 报错⇒终止
+                    Err(e) => {
+                        eprint_notice(
+                            "ERROR",
+                            &format!("无法读取辅助输出：{e:?} in「{}」", encoding.decode(&buf)),
+                        );
+                        break;
+                    }
                 }
                 // 清空缓冲区
                 buf.clear();
@@ -374,9 +757,27 @@ impl IoProcessManager {
             .transform_err(err)?
             // 通道接收者接收
             .recv()
+            .map(|(line, _)| line)
             .transform_err(err)
     }
 
+    /// 在限定时间内（从「输出通道」中）拉取一个输出
+    /// * 🎯等待输出的同时，定期检查子进程是否已退出
+    /// * 🚩超时⇒返回[`None`]
+    /// * 🚩输出通道已断开⇒等待同样时长后返回[`None`]，由调用方决定是否继续等待
+    pub fn fetch_output_timeout(&mut self, timeout: Duration) -> Result<Option<String>> {
+        let receiver = self.child_out.lock().transform_err(err)?;
+        match receiver.recv_timeout(timeout) {
+            Ok((line, _)) => Ok(Some(line)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => {
+                drop(receiver);
+                thread::sleep(timeout);
+                Ok(None)
+            }
+        }
+    }
+
     /// 尝试（从「输出通道」中）拉取一个输出
     /// * 🎯保证不会发生「线程阻塞」
     /// * 🚩类似[`Self::fetch_output`]，但仅在「有输出」时拉取
@@ -391,12 +792,37 @@ impl IoProcessManager {
             .transform_err(err)?
             // 通道接收者接收
             .try_recv()
-            .ok();
+            .ok()
+            .map(|(line, _)| line);
         // ! ↑此处使用`ok`是为了区分「锁定错误」与「通道无输出」
         // 返回
         Ok(out)
     }
 
+    /// 拉取「输出通道」中当前所有的输出
+    /// * 🎯批量拉取：避免每拉取一行都锁定一次互斥锁
+    /// * 🚩只锁定一次，再用[`Receiver::try_iter`]取出所有已有输出
+    /// * 📌不会阻塞：无输出⇒返回空数组
+    pub fn drain_outputs(&mut self) -> Result<Vec<String>> {
+        let outputs = self.drain_outputs_timed()?;
+        Ok(outputs.into_iter().map(|(line, _)| line).collect())
+    }
+
+    /// 拉取「输出通道」中当前所有的输出，并附带各行的「收到时间」
+    /// * 🎯测量延迟：以子线程读到输出的时刻为准，而非被拉取的时刻
+    /// * 🚩其余同[`Self::drain_outputs`]
+    pub fn drain_outputs_timed(&mut self) -> Result<Vec<(String, Instant)>> {
+        let outputs = self
+            .child_out
+            // 互斥锁锁定
+            .lock()
+            .transform_err(err)?
+            // 取出所有已有输出
+            .try_iter()
+            .collect();
+        Ok(outputs)
+    }
+
     /// 向子进程写入数据（字符串）
     /// * 🚩通过使用自身「子进程输入」的互斥锁，从中输入数据
     /// * ⚙️返回空，或返回字符串形式的错误（互斥锁错误）
@@ -411,11 +837,30 @@ impl IoProcessManager {
             .lock()
             .transform_err(err)?
             // 发送 | 📄文档说此处不会阻塞：<https://rustwiki.org/zh-CN/std/sync/mpsc/struct.Sender.html#method.send>
-            .send(input_line.to_string())
+            .send(ProcessInput::Normal(input_line.to_string()))
             .transform_err(err)
         // * ✅【2024-04-08 22:46:04】有关「线程死锁」的问题已定位：`ws`库中的`Sender.send`方法使用`std::mpsc::SyncSender`导致阻塞
     }
 
+    /// 向子进程优先写入数据（字符串）
+    /// * 🎯控制指令不被大量排队的普通输入阻塞：如Websocket客户端大量输入时的`VOL 0`
+    /// * 🚩作为[`ProcessInput::Priority`]发送：先于所有尚未写入的普通输入
+    ///   * 📌「优先输入」之间仍按发送先后写入
+    /// * ⚠️同[`Self::put`]，需【自行尾缀换行符】
+    pub fn put_priority(&self, input_line: impl ToString) -> Result<()> {
+        self.child_in
+            .lock()
+            .transform_err(err)?
+            .send(ProcessInput::Priority(input_line.to_string()))
+            .transform_err(err)
+    }
+
+    /// 向子进程优先写入**一行**数据（字符串）
+    /// * 🚩功能同[`Self::put_priority`]，但会自动加上换行符
+    pub fn put_line_priority(&self, input: impl ToString) -> Result<()> {
+        self.put_priority(format!("{}\n", input.to_string()))
+    }
+
     /// 向子进程写入**一行**数据（字符串）
     /// * 🚩功能同[`Self::put`]，但会自动加上换行符
     /// * 📌类似[`print`]和[`println`]的关系
@@ -426,6 +871,38 @@ impl IoProcessManager {
         self.put(format!("{}\n", input.to_string()))
     }
 
+    /// 获取「子进程输入」的发送端
+    /// * 🎯在其它线程中向子进程写入数据：如后台分批输入
+    /// * 📌与[`Self::put`]写入同一通道：经此发送的普通输入按发送先后输入子进程
+    pub fn input_sender(&self) -> Result<Sender<ProcessInput>> {
+        self.child_in
+            .lock()
+            .transform_err(err)
+            .map(|sender| sender.clone())
+    }
+
+    /// 子进程的标准IO是否已（自行）关闭
+    /// * 🚩由「读写子线程」报告：调用[`Self::kill`]导致的关闭不算在内
+    /// * 📌为真时，此前的所有输出都已进入「输出通道」
+    pub fn is_child_closed(&self) -> Result<bool> {
+        Ok(*self.child_closed.lock().transform_err(err)?)
+    }
+
+    /// 最近一次读到输出的时间
+    /// * 🎯不拉取输出即可判断CIN是否已「安静下来」
+    /// * 📌含「辅助输出源」的输出；不含标准错误
+    /// * ⚙️尚无任何输出⇒[`None`]
+    pub fn last_output_at(&self) -> Result<Option<Instant>> {
+        Ok(*self.last_output.lock().transform_err(err)?)
+    }
+
+    /// 获取子进程的退出状态
+    /// * 🚩调用[`Child::try_wait`]方法：不会阻塞
+    /// * ⚙️尚未退出⇒[`None`]
+    pub fn exit_status(&mut self) -> Result<Option<ExitStatus>> {
+        self.process.try_wait().transform_err(err)
+    }
+
     /// 等待子进程结束
     /// * 🚩调用[`Child::wait`]方法
     /// * ⚠️对于【不会主动终止】的子进程，此举可能导致调用者死锁
@@ -440,22 +917,34 @@ impl IoProcessManager {
     /// * 🚩【2024-04-02 20:37:28】如今不再消耗自身所有权
     ///   * ✅【2024-04-02 20:36:40】现在通过「将字段类型变为[`Option`]」安全借走子线程所有权
     ///   * 📌销毁自身的逻辑，交给调用方处理
+    /// * 🚩幂等：终止信号已设置⇒直接返回[`KillOutcome::AlreadyKilled`]
+    /// * 🚩子进程已自行退出⇒只清理子线程，不再强制终止，返回[`KillOutcome::AlreadyExited`]
     ///
     /// * ❓不稳定：有时会导致「野进程」的情况
-    pub fn kill(&mut self) -> Result<()> {
+    pub fn kill(&mut self) -> Result<KillOutcome> {
         // ! ❌【2024-03-23 21:08:56】暂不独立其中的逻辑：无法脱开对`self`的借用
         // ! 📌更具体而言：对其中两个线程`thread_write_in`、`thread_read_out`的部分借用
         // 向子线程发送终止信号 //
         let mut signal = self.termination_signal.lock().transform_err(err)?;
+        // 已发送过终止信号⇒无操作
+        if *signal {
+            return Ok(KillOutcome::AlreadyKilled);
+        }
         *signal = true;
         drop(signal); // ! 手动释放锁
                       // * 📝【2024-03-24 00:15:10】必须手动释放锁，否则会导致后续线程死锁
 
+        // 检查子进程是否已自行退出
+        let exited = self.process.try_wait().transform_err(err)?;
+
         // ! 解除子线程「write_stdin」的阻塞
         // * 有可能在程序崩溃后还发信息，此时是`SendError`
-        let _ = self
-            .put("\n")
-            .inspect_err(|e| println!("向「进程读取」子线程发送消息失败！{e}"));
+        // * 🚩子进程已退出时，发送失败在预期之内，不再提示
+        if let Err(e) = self.put("\n") {
+            if exited.is_none() {
+                eprint_notice("ERROR", &format!("向「进程读取」子线程发送消息失败！{e}"));
+            }
+        }
 
         // 等待子线程终止 //
         // * 🚩【2024-03-24 18:49:31】现在强制销毁持有的两个子线程，不再等待其结束
@@ -469,29 +958,35 @@ impl IoProcessManager {
                 .map(|t| t.join().transform_err(err)),
         ); // * ✅目前这个是可以终止的
         drop(self.thread_read_out.take());
+        drop(self.thread_read_aux.take());
 
         // * 📝此时子线程连同「子进程的标准输入输出」一同关闭，
         //   * 子进程自身可以做输出
         // * 📄如：ONA在最后会打印程序运行报告
         //   * ⚠️这意味着「输出侦听器」仍然能对其输出产生响应
 
+        // 已自行退出⇒无需杀死
+        if let Some(status) = exited {
+            return Ok(KillOutcome::AlreadyExited(status));
+        }
+
         // 杀死子进程 //
-        // * 【2024-03-25 13:22:12】尝试使用`taskkill`强制杀死子进程（不会影响后边的kill）
-        // * 📌启动失败也不影响：主要目的是在系统层面防止「进程残留」
+        // * 【2024-03-25 13:22:12】尝试强制杀死子进程（不会影响后边的kill）
+        // * 📌失败也不影响：主要目的是在系统层面防止「进程残留」
         //   * 📄【2024-03-25 13:23:41】目前对OpenNARS有效（Java进程得到了有效终止）
-        // * ❗可能是系统特定的
-        if let Ok(child) = Command::new("taskkill")
-            // 强制终止id为子进程id的进程
-            .args(["-F", "-PID", &self.process.id().to_string()])
-            .spawn()
-        {
-            // 等待taskkill杀死子进程
-            if let Err(err) = child.wait_with_output() {
-                println!("指令执行失败！{err:?}");
-            }
+        // * 🚩终止整个进程树：CIN自身产生的子进程也一并终止
+        if let Err(e) = terminate_process_tree(self.process.id()) {
+            eprint_notice("ERROR", &format!("无法终止进程树：{e}"));
         }
         // * 🚩通用：调用`Child`对象的`kill`方法
-        self.process.kill().transform_err(err)
+        // * 📌进程树可能已被终止：此时`kill`报错，但结果仍是「已被杀死」
+        match self.process.kill() {
+            Ok(()) => Ok(KillOutcome::Killed),
+            Err(e) => match self.process.try_wait().transform_err(err)? {
+                Some(..) => Ok(KillOutcome::Killed),
+                None => Err(err(e)),
+            },
+        }
     }
 }
 
@@ -516,6 +1011,238 @@ pub(crate) mod tests {
         }
     }
 
+    /// 测试工具：启动一个系统Shell子进程
+    /// * 🎯无需CIN可执行文件即可测试
+    fn launch_shell(script: &str) -> IoProcessManager {
+        #[cfg(not(windows))]
+        let process = IoProcess::new("sh").arg("-c").arg(script);
+        #[cfg(windows)]
+        let process = IoProcess::new("cmd").arg("/C").arg(script);
+        process.launch().expect("Shell启动失败")
+    }
+
+    /// 测试/辅助输出源：子进程退出后，输出通道随之断开
+    /// * 🎯不依赖轮询即可得知「不会再有输出」
+    #[test]
+    #[cfg(not(windows))]
+    fn test_aux_output_disconnects() {
+        use std::{fs, sync::mpsc};
+        let path = std::env::temp_dir().join(format!(
+            "babelnar_aux_disconnect_{}.log",
+            std::process::id()
+        ));
+        fs::write(&path, "AUX\n").expect("无法创建临时文件");
+        let process = IoProcess::new("sh")
+            .arg("-c")
+            .arg("echo OUT")
+            .aux_output(fs::File::open(&path).expect("无法打开临时文件"));
+        let mut process = process.launch().expect("Shell启动失败");
+        // 阻塞拉取：取完两行后，通道断开⇒报错
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut lines = vec![];
+            while let Ok(line) = process.fetch_output() {
+                lines.push(line.trim().to_string());
+            }
+            let _ = sender.send(lines);
+        });
+        let mut lines = receiver
+            .recv_timeout(Duration::from_secs(10))
+            .expect("输出通道未断开");
+        lines.sort();
+        assert_eq!(lines, ["AUX", "OUT"]);
+        let _ = fs::remove_file(path);
+    }
+
+    /// 测试/杀死仍在运行的子进程 | 重复调用无操作
+    #[test]
+    fn test_kill_running() {
+        #[cfg(not(windows))]
+        let mut process = launch_shell("sleep 10");
+        #[cfg(windows)]
+        let mut process = launch_shell("ping -n 11 127.0.0.1 > nul");
+        assert_eq!(process.kill().expect("无法杀死进程"), KillOutcome::Killed);
+        assert_eq!(
+            process.kill().expect("重复杀死出错"),
+            KillOutcome::AlreadyKilled
+        );
+    }
+
+    /// 测试/优先输入：先于排队中的大量普通输入写入
+    /// * 🚩子进程逐行缓慢回显：普通输入堆积在管道与通道中
+    #[test]
+    #[cfg(not(windows))]
+    fn test_put_priority() {
+        let mut process = launch_shell(
+            r#"while IFS= read -r line; do printf '%.8s\n' "$line"; sleep 0.01; done"#,
+        );
+        // 每行约1KB：总量远超管道缓冲区，「写输入」子线程被子进程拖慢
+        let padding = "_".repeat(1024);
+        for i in 0..1000 {
+            process
+                .put_line(format!("normal{i:04}{padding}"))
+                .expect("无法放置输入");
+        }
+        process
+            .put_line_priority("PRIORITY")
+            .expect("无法放置优先输入");
+        // 优先输入应只排在管道中已有的少数普通输入之后
+        let mut position = 0;
+        while !process
+            .fetch_output()
+            .expect("无法拉取输出")
+            .starts_with("PRIORITY")
+        {
+            position += 1;
+        }
+        process.kill().expect("无法杀死进程");
+        assert!(position < 200, "优先输入排在{position}行普通输入之后");
+    }
+
+    /// 测试/子进程已自行退出⇒不强制终止，也不报错
+    #[test]
+    fn test_kill_exited() {
+        let mut process = launch_shell("exit 3");
+        let status = process.wait().expect("无法等待进程结束");
+        assert_eq!(
+            process.kill().expect("杀死已退出的进程出错"),
+            KillOutcome::AlreadyExited(status)
+        );
+        assert_eq!(
+            process.kill().expect("重复杀死出错"),
+            KillOutcome::AlreadyKilled
+        );
+    }
+
+    /// 测试/按行读取：统一换行符
+    /// * 🚩缓冲区容量为1：`\r\n`被拆分到两次读取中，行亦在中途被拆分
+    #[test]
+    fn test_line_reader() {
+        let content = "a\r\nb\rc\nlong line\r\r\n\n50%\r100%\rend";
+        let expected = [
+            "a\n",
+            "b\n",
+            "c\n",
+            "long line\n",
+            "\n",
+            "\n",
+            "50%\n",
+            "100%\n",
+            "end",
+        ];
+        for capacity in [1, 2, 0x100] {
+            let mut reader =
+                LineReader::new(BufReader::with_capacity(capacity, content.as_bytes()));
+            let mut lines = vec![];
+            let mut buf = String::new();
+            while reader.read_line(&mut buf).expect("读取失败") > 0 {
+                lines.push(std::mem::take(&mut buf));
+            }
+            assert_eq!(lines, expected, "capacity = {capacity}");
+        }
+        // 非UTF-8内容⇒有损解码，且不影响后续的行
+        let mut reader = LineReader::new(BufReader::new(&b"\xff\r\nok\n"[..]));
+        let mut buf = String::new();
+        reader.read_line(&mut buf).expect("读取失败");
+        assert_eq!(buf, "\u{FFFD}\n");
+        buf.clear();
+        assert_eq!(reader.read_line(&mut buf).expect("读取失败"), 3);
+        assert_eq!(buf, "ok\n");
+    }
+
+    /// 测试/子进程输出混合`\r\n`与单独的`\r`⇒读出干净的行
+    #[test]
+    #[cfg(not(windows))]
+    fn test_read_out_line_endings() {
+        let mut process =
+            launch_shell(r"printf 'IN: <A --> C>.\r\nOUT: <A --> B>.\r50%%\r100%%\r\ndone\n'");
+        let mut lines = vec![];
+        while lines.last().map(String::as_str) != Some("done\n") {
+            lines.push(process.fetch_output().expect("无法拉取输出"));
+        }
+        assert_eq!(
+            lines,
+            [
+                "IN: <A --> C>.\n",
+                "OUT: <A --> B>.\n",
+                "50%\n",
+                "100%\n",
+                "done\n"
+            ]
+        );
+    }
+
+    /// 测试/子进程输出非法UTF-8⇒有损解码，不丢失任何一行
+    #[test]
+    #[cfg(not(windows))]
+    fn test_read_out_invalid_utf8() {
+        let mut process = launch_shell(r"printf 'bad: \377\376\nμs ─\ndone\n'");
+        let mut lines = vec![];
+        while lines.last().map(String::as_str) != Some("done\n") {
+            lines.push(process.fetch_output().expect("无法拉取输出"));
+        }
+        assert_eq!(lines, ["bad: \u{FFFD}\u{FFFD}\n", "μs ─\n", "done\n"]);
+    }
+
+    /// 测试/构建的指令：程序、参数、环境变量、工作目录
+    #[test]
+    fn test_builder_command() {
+        let process = IoProcess::new("python")
+            .arg("-m")
+            .args(["pynars.ConsolePlus", "--quiet"])
+            .env("PYTHONIOENCODING", "utf-8")
+            .envs([("NODE_OPTIONS", "--max-old-space-size=4096")])
+            .current_dir("PyNARS");
+        let command = process.command();
+        assert_eq!(command.get_program(), "python");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["-m", "pynars.ConsolePlus", "--quiet"]
+        );
+        assert_eq!(
+            command.get_envs().collect::<Vec<_>>(),
+            [
+                (
+                    OsStr::new("NODE_OPTIONS"),
+                    Some(OsStr::new("--max-old-space-size=4096"))
+                ),
+                (OsStr::new("PYTHONIOENCODING"), Some(OsStr::new("utf-8"))),
+            ]
+        );
+        assert_eq!(command.get_current_dir(), Some(Path::new("PyNARS")));
+    }
+
+    /// 测试/关闭标准输入：子进程立即读到末尾，输入报错
+    #[test]
+    #[cfg(not(windows))]
+    fn test_no_stdin() {
+        let mut process = IoProcess::new("sh")
+            .args(["-c", "cat; echo done"])
+            .no_stdin()
+            .launch()
+            .expect("Shell启动失败");
+        assert_eq!(process.fetch_output().expect("无法拉取输出"), "done\n");
+        process.wait().expect("无法等待进程结束");
+        assert!(process.put_line("<A --> B>.").is_err());
+    }
+
+    /// 测试/备用编码：以GBK写入，再以GBK解码读出
+    #[test]
+    #[cfg(all(not(windows), feature = "text_encoding"))]
+    fn test_encoding_round_trip() {
+        let gbk = ProcessEncoding::from_label("gbk").expect("未知编码");
+        let mut process = IoProcess::new("cat")
+            .encoding(gbk)
+            .launch()
+            .expect("无法启动cat");
+        process.put("<中文 --> 汉字>.\n").expect("无法写入");
+        assert_eq!(
+            process.fetch_output().expect("无法拉取输出"),
+            "<中文 --> 汉字>.\n"
+        );
+        process.kill().expect("无法杀死进程");
+    }
+
     /// 实用测试工具：启动一个ONA，并附带「输出缓存」
     fn launch_ona() -> (IoProcessManager, ArcMutex<Vec<String>>) {
         // 输出缓存
@@ -608,7 +1335,7 @@ pub(crate) mod tests {
         {
             let r = process.child_out.lock().unwrap();
             for _ in r.try_iter() {
-                let line = r.recv().expect("接收失败！");
+                let (line, _) = r.recv().expect("接收失败！");
                 print!("从输出中读取到的一行（多了会阻塞！）：{line}");
             }
             // * 此处自动释放锁
@@ -6,4 +6,10 @@
 util::pub_mod_and_pub_use! {
     // 输入输出进程
     io_process
+    // 终止进程树
+    process_tree
+    // 查找CIN目录
+    executables_dir
+    // 子进程IO的文本编码
+    text_encoding
 }
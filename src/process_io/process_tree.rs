@@ -0,0 +1,120 @@
+//! 终止「进程树」
+//! * 🎯CIN自身产生的子进程（如Java、Node.js的辅助进程）不会随[`std::process::Child::kill`]终止
+//! * 🚩按平台分别实现
+//!   * 📌Windows：`taskkill /T /F`，终止进程及其所有子进程
+//!   * 📌Unix：子进程在独立的进程组中启动，终止时向整个进程组发送信号
+
+use anyhow::Result;
+use std::process::Command;
+
+/// 在Unix上，发送`SIGTERM`后等待进程组自行退出的时长
+/// * 🚩超时后发送`SIGKILL`
+#[cfg(unix)]
+pub const TERMINATION_GRACE: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// 令将要启动的子进程成为新进程组的组长
+/// * 🎯使[`terminate_process_tree`]能一并终止其产生的所有子进程
+/// * 🚩Unix：在子进程中调用`setsid`，进程组id即为子进程id
+/// * 🚩其它平台：无操作
+pub fn isolate_process_group(command: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: `setsid`是异步信号安全的，且不访问父进程的任何状态
+        unsafe {
+            command.pre_exec(|| match libc::setsid() {
+                -1 => Err(std::io::Error::last_os_error()),
+                _ => Ok(()),
+            });
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = command;
+}
+
+/// 终止进程树
+/// * 🎯终止进程及其产生的所有子进程，避免「野进程」残留
+/// * 🚩Windows：调用`taskkill /T /F /PID`
+///   * 📌只在无法启动`taskkill`时报错：进程已不存在时，`taskkill`同样返回失败
+#[cfg(windows)]
+pub fn terminate_process_tree(pid: u32) -> Result<()> {
+    Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .output()?;
+    Ok(())
+}
+
+/// 终止进程树
+/// * 🎯终止进程及其产生的所有子进程，避免「野进程」残留
+/// * 🚩Unix：向进程组发送`SIGTERM`，等待[`TERMINATION_GRACE`]后再发送`SIGKILL`
+///   * ⚠️要求进程已通过[`isolate_process_group`]成为进程组组长
+/// * 📌进程组已不存在时不报错
+#[cfg(unix)]
+pub fn terminate_process_tree(pid: u32) -> Result<()> {
+    let pgid = libc::pid_t::try_from(pid)?;
+    // 先礼后兵：给进程组自行退出的机会
+    if !signal_group(pgid, libc::SIGTERM)? {
+        return Ok(());
+    }
+    std::thread::sleep(TERMINATION_GRACE);
+    signal_group(pgid, libc::SIGKILL)?;
+    Ok(())
+}
+
+/// 向进程组发送信号
+/// * ⚙️返回「进程组是否存在」
+#[cfg(unix)]
+fn signal_group(pgid: libc::pid_t, signal: libc::c_int) -> Result<bool> {
+    // SAFETY: `killpg`只读取传入的整数参数
+    match unsafe { libc::killpg(pgid, signal) } {
+        0 => Ok(true),
+        _ => match std::io::Error::last_os_error() {
+            e if e.raw_os_error() == Some(libc::ESRCH) => Ok(false),
+            e => Err(e.into()),
+        },
+    }
+}
+
+/// 其它平台：不支持终止进程树
+/// * 🚩交由调用方以[`std::process::Child::kill`]兜底
+#[cfg(not(any(unix, windows)))]
+pub fn terminate_process_tree(_pid: u32) -> Result<()> {
+    Err(anyhow::anyhow!("当前平台不支持终止进程树"))
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use crate::process_io::IoProcess;
+
+    /// 测试/终止进程树
+    /// * 🎯子进程产生的「孙进程」也随之终止
+    #[test]
+    #[cfg(unix)]
+    fn test_kill_grandchild() {
+        // 产生一个长期睡眠的孙进程，并输出其进程id
+        let mut process = IoProcess::new("sh")
+            .arg("-c")
+            .arg("sleep 30 & echo $!; wait")
+            .launch()
+            .expect("Shell启动失败");
+        let grandchild = process.fetch_output().expect("无法获取孙进程id");
+        let grandchild = grandchild.trim();
+        assert!(is_alive(grandchild), "孙进程未启动：{grandchild}");
+        process.kill().expect("无法杀死进程");
+        assert!(!is_alive(grandchild), "孙进程仍存活：{grandchild}");
+    }
+
+    /// 判断进程是否仍在运行
+    /// * 🚩已不存在，或已成为僵尸进程（等待回收）⇒未在运行
+    #[cfg(unix)]
+    fn is_alive(pid: &str) -> bool {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "stat=", "-p", pid])
+            .output()
+            .expect("无法查询进程状态");
+        let stat = String::from_utf8_lossy(&output.stdout);
+        let stat = stat.trim();
+        !stat.is_empty() && !stat.starts_with('Z')
+    }
+}
@@ -0,0 +1,165 @@
+//! 子进程IO的文本编码
+//! * 🎯不因编码问题丢失CIN的任何一行输出
+//!   * 📄PyNARS、OpenNARS的中文日志、`μ`符号、制表符边框
+//!   * 📄GBK编码的Windows控制台
+//! * 🚩读：先按UTF-8解码；失败⇒尝试「备用编码」；仍失败⇒有损解码（非法字节替换为`�`）
+//! * 🚩写：配置了「备用编码」⇒以之编码；否则⇒UTF-8
+//! * ⚠️备用编码需启用`text_encoding`特性；未启用⇒仅支持UTF-8
+
+use anyhow::{anyhow, Result};
+#[cfg(feature = "text_encoding")]
+use encoding_rs::{Encoding, UTF_8};
+use std::borrow::Cow;
+
+/// 子进程IO的文本编码
+/// * 🚩默认：仅UTF-8
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessEncoding {
+    /// 备用编码
+    /// * 🚩[`None`]⇒仅UTF-8
+    #[cfg(feature = "text_encoding")]
+    fallback: Option<&'static Encoding>,
+}
+
+impl ProcessEncoding {
+    /// 仅UTF-8
+    pub const UTF8: Self = Self {
+        #[cfg(feature = "text_encoding")]
+        fallback: None,
+    };
+
+    /// 从编码名构造
+    /// * 📄`gbk`、`GB18030`、`shift_jis`
+    /// * 🚩编码名按[WHATWG标准](https://encoding.spec.whatwg.org/#names-and-labels)识别，不区分大小写
+    /// * 📌`utf-8`⇒[`Self::UTF8`]
+    #[cfg(feature = "text_encoding")]
+    pub fn from_label(label: &str) -> Result<Self> {
+        let encoding = Encoding::for_label(label.trim().as_bytes())
+            .ok_or_else(|| anyhow!("未知的文本编码{label:?}"))?;
+        Ok(match encoding == UTF_8 {
+            true => Self::UTF8,
+            false => Self {
+                fallback: Some(encoding),
+            },
+        })
+    }
+
+    /// 从编码名构造
+    /// * ⚠️未启用`text_encoding`特性：仅接受`utf-8`
+    #[cfg(not(feature = "text_encoding"))]
+    pub fn from_label(label: &str) -> Result<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" | "unicode-1-1-utf-8" => Ok(Self::UTF8),
+            _ => Err(anyhow!(
+                "未知的文本编码{label:?}：未启用`text_encoding`特性，仅支持UTF-8"
+            )),
+        }
+    }
+
+    /// 编码名
+    /// * 📄`UTF-8`、`GBK`
+    pub fn name(&self) -> &'static str {
+        #[cfg(feature = "text_encoding")]
+        return self.fallback.unwrap_or(UTF_8).name();
+        #[cfg(not(feature = "text_encoding"))]
+        "UTF-8"
+    }
+
+    /// 解码一行输出
+    /// * 🚩UTF-8⇒备用编码⇒有损的UTF-8：总能解码出内容
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        if let Ok(line) = std::str::from_utf8(bytes) {
+            return line.to_string();
+        }
+        #[cfg(feature = "text_encoding")]
+        if let Some(line) = self.fallback.and_then(|encoding| {
+            encoding.decode_without_bom_handling_and_without_replacement(bytes)
+        }) {
+            return line.into_owned();
+        }
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+
+    /// 解码一行输出（取得所有权）
+    /// * 🎯合法的UTF-8⇒直接复用字节缓冲区，免去拷贝
+    /// * 🚩其它情况同[`Self::decode`]
+    pub fn decode_owned(&self, bytes: Vec<u8>) -> String {
+        String::from_utf8(bytes).unwrap_or_else(|e| self.decode(e.as_bytes()))
+    }
+
+    /// 编码一行输入
+    /// * 📌备用编码中无法表示的字符⇒以HTML数字字符引用代替（同`encoding_rs`的`Encoding::encode`）
+    pub fn encode<'a>(&self, text: &'a str) -> Cow<'a, [u8]> {
+        #[cfg(feature = "text_encoding")]
+        if let Some(encoding) = self.fallback {
+            return encoding.encode(text).0;
+        }
+        Cow::Borrowed(text.as_bytes())
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 「中文」的GBK编码
+    #[cfg(feature = "text_encoding")]
+    const GBK_CHINESE: &[u8] = b"\xd6\xd0\xce\xc4";
+
+    /// 测试/仅UTF-8：不依赖备用编码
+    #[test]
+    fn test_utf8_only() {
+        let utf8 = ProcessEncoding::from_label(" UTF-8 ").unwrap();
+        assert_eq!(utf8, ProcessEncoding::UTF8);
+        assert_eq!(utf8.name(), "UTF-8");
+        assert_eq!(utf8.decode(b"ok\xff"), "ok\u{FFFD}");
+        assert_eq!(utf8.encode("中文").as_ref(), "中文".as_bytes());
+    }
+
+    /// 测试/编码名
+    #[test]
+    #[cfg(feature = "text_encoding")]
+    fn test_from_label() {
+        assert_eq!(
+            ProcessEncoding::from_label("utf-8").unwrap(),
+            ProcessEncoding::UTF8
+        );
+        assert_eq!(ProcessEncoding::from_label(" GBK ").unwrap().name(), "GBK");
+        assert!(ProcessEncoding::from_label("不存在的编码").is_err());
+    }
+
+    /// 测试/解码：不丢失任何一行
+    #[test]
+    #[cfg(feature = "text_encoding")]
+    fn test_decode() {
+        let utf8 = ProcessEncoding::UTF8;
+        let gbk = ProcessEncoding::from_label("gbk").unwrap();
+        // 合法的UTF-8⇒原样
+        for encoding in [utf8, gbk] {
+            assert_eq!(encoding.decode("μs ─ 中文".as_bytes()), "μs ─ 中文");
+        }
+        // 非UTF-8⇒备用编码
+        assert_eq!(gbk.decode(GBK_CHINESE), "中文");
+        // 无备用编码、备用编码亦无法解码⇒有损解码
+        assert_eq!(utf8.decode(b"ok\xff"), "ok\u{FFFD}");
+        assert_eq!(gbk.decode(b"ok\xff"), "ok\u{FFFD}");
+        // 取得所有权的解码：结果相同
+        for bytes in [&b"ok"[..], GBK_CHINESE, b"ok\xff"] {
+            assert_eq!(gbk.decode_owned(bytes.to_vec()), gbk.decode(bytes));
+        }
+    }
+
+    /// 测试/编码
+    #[test]
+    #[cfg(feature = "text_encoding")]
+    fn test_encode() {
+        assert_eq!(
+            ProcessEncoding::UTF8.encode("中文").as_ref(),
+            "中文".as_bytes()
+        );
+        let gbk = ProcessEncoding::from_label("gbk").unwrap();
+        assert_eq!(gbk.encode("中文").as_ref(), GBK_CHINESE);
+        assert_eq!(gbk.encode("<A --> B>.").as_ref(), b"<A --> B>.");
+    }
+}
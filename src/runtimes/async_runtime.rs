@@ -0,0 +1,255 @@
+//! 异步运行时
+//! * 🎯在基于tokio的异步服务中嵌入NAVM运行时，无需在每次拉取输出时`spawn_blocking`
+//! * 📌核心流程
+//!   * 输入：`async fn input_cmd` ⇒ 阻塞线程池中锁定运行时并输入
+//!   * 输出：「桥接线程」批量拉取运行时的输出 ⇒ [`tokio::sync::mpsc`]通道 ⇒ 异步流
+//! * ⚠️原有的阻塞式读取线程保留不变，桥接线程只负责搬运
+//! * ⚠️需启用`async_runtime`特性
+
+use crate::runtimes::{CommandVmRuntime, VmRuntimeBoost};
+use anyhow::{anyhow, Result};
+use navm::{cmd::Cmd, output::Output};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio_stream::{wrappers::UnboundedReceiverStream, Stream};
+
+/// 桥接线程在「无输出」时的轮询间隔
+pub const BRIDGE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 异步的NAVM运行时
+/// * 🎯包装任意「可在线程间传递」的运行时，提供异步的输入、终止与输出流
+/// * 🚩输出由专门的「桥接线程」搬运至异步通道
+///   * 📌通道无界：输出不会因为调用方未及时消费而丢失
+/// * 📌终止时：桥接线程在运行时终止后仍会取尽剩余输出，再关闭通道
+///   * 📄终止后，输出流仍可取得终止前的所有输出，随后结束
+/// * ⚠️未经[`Self::terminate`]即被丢弃⇒在丢弃时同步终止被包装的运行时
+pub struct AsyncVmRuntime<R: VmRuntimeBoost> {
+    /// 被包装的运行时
+    runtime: Arc<Mutex<R>>,
+
+    /// 输出通道的接收端
+    /// * 🚩首次调用[`Self::outputs`]时被取走
+    receiver: Option<UnboundedReceiver<Output>>,
+
+    /// 桥接线程
+    bridge: Option<JoinHandle<()>>,
+
+    /// 「停止桥接」标志
+    /// * 🎯未经终止即被丢弃时，让桥接线程及时退出
+    stop: Arc<AtomicBool>,
+}
+
+/// 异步的命令行虚拟机运行时
+pub type AsyncCommandVmRuntime = AsyncVmRuntime<CommandVmRuntime>;
+
+impl<R> AsyncVmRuntime<R>
+where
+    R: VmRuntimeBoost + Send + 'static,
+{
+    /// 构造函数
+    /// * 🚩包装运行时，并启动桥接线程
+    /// * 📌无需处在tokio运行时中：桥接线程为普通线程
+    pub fn new(runtime: R) -> Self {
+        let runtime = Arc::new(Mutex::new(runtime));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = unbounded_channel();
+        let bridge = {
+            let runtime = runtime.clone();
+            let stop = stop.clone();
+            thread::spawn(move || bridge_outputs(runtime, sender, stop))
+        };
+        Self {
+            runtime,
+            receiver: Some(receiver),
+            bridge: Some(bridge),
+            stop,
+        }
+    }
+
+    /// 获取输出流
+    /// * 🚩产生运行时的所有输出，直至运行时终止且剩余输出被取尽
+    /// * ⚠️只有首次调用能取得输出：此后再调用⇒立即结束的空流
+    pub fn outputs(&mut self) -> impl Stream<Item = Output> {
+        let receiver = self.receiver.take().unwrap_or_else(|| {
+            // 发送端立即被丢弃⇒空流
+            let (_, receiver) = unbounded_channel();
+            receiver
+        });
+        UnboundedReceiverStream::new(receiver)
+    }
+
+    /// 输入NAVM指令
+    /// * 🚩在阻塞线程池中锁定运行时并输入，不阻塞异步任务
+    pub async fn input_cmd(&self, cmd: Cmd) -> Result<()> {
+        let runtime = self.runtime.clone();
+        tokio::task::spawn_blocking(move || lock(&runtime)?.input_cmd(cmd)).await?
+    }
+
+    /// 终止运行时
+    /// * 🚩先终止被包装的运行时，再等待桥接线程取尽剩余输出
+    /// * 📌返回后，输出流中已含有终止前的所有输出
+    pub async fn terminate(&mut self) -> Result<()> {
+        let runtime = self.runtime.clone();
+        tokio::task::spawn_blocking(move || lock(&runtime)?.terminate()).await??;
+        if let Some(bridge) = self.bridge.take() {
+            tokio::task::spawn_blocking(move || bridge.join())
+                .await?
+                .map_err(|_| anyhow!("输出桥接线程意外退出"))?;
+        }
+        Ok(())
+    }
+}
+
+/// 由运行时直接构造
+impl<R> From<R> for AsyncVmRuntime<R>
+where
+    R: VmRuntimeBoost + Send + 'static,
+{
+    fn from(runtime: R) -> Self {
+        Self::new(runtime)
+    }
+}
+
+/// 丢弃时停止桥接线程，并终止被包装的运行时
+/// * 📌运行时自身被丢弃时不会终止CIN子进程：须在此显式终止，以免遗留「野进程」
+/// * 🚩已终止⇒不再重复终止；出错⇒忽略（无从报告）
+impl<R: VmRuntimeBoost> Drop for AsyncVmRuntime<R> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Ok(mut runtime) = lock(&self.runtime) {
+            if !runtime.is_terminated() {
+                let _ = runtime.terminate();
+            }
+        }
+    }
+}
+
+/// 锁定运行时
+/// * 🚩锁被「毒化」⇒返回错误
+fn lock<R>(runtime: &Mutex<R>) -> Result<std::sync::MutexGuard<'_, R>> {
+    runtime.lock().map_err(|e| anyhow!("无法锁定运行时：{e}"))
+}
+
+/// 桥接线程：将运行时的输出搬运至异步通道
+/// * 🚩批量拉取输出并逐个发送；无输出时短暂休眠
+/// * 🚩运行时终止后继续拉取，直到某次拉取为空，再关闭通道
+///   * 🎯不丢失终止前已产生、但尚未被读取的输出
+/// * 📌接收端被丢弃、拉取出错、收到「停止」标志⇒直接退出
+fn bridge_outputs<R: VmRuntimeBoost>(
+    runtime: Arc<Mutex<R>>,
+    sender: UnboundedSender<Output>,
+    stop: Arc<AtomicBool>,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        // * 🚩「拉取」与「检查终止」在同一次锁定中：保证终止前的输出都已被拉取
+        let (outputs, terminated) = match lock(&runtime) {
+            Ok(mut runtime) => (runtime.drain_outputs(), runtime.is_terminated()),
+            Err(..) => return,
+        };
+        let outputs = match outputs {
+            Ok(outputs) => outputs,
+            Err(..) => return,
+        };
+        let drained = outputs.is_empty();
+        for output in outputs {
+            if sender.send(output).is_err() {
+                return;
+            }
+        }
+        // 已终止且已取尽⇒关闭通道
+        if terminated && drained {
+            return;
+        }
+        thread::sleep(BRIDGE_POLL_INTERVAL);
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::FakeVm;
+    use nar_dev_utils::manipulate;
+    use narsese::lexical_nse_task as nse_task;
+    use navm::vm::{VmLauncher, VmRuntime};
+    use tokio_stream::StreamExt;
+
+    /// 在单线程的tokio运行时中执行
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("无法构建tokio运行时")
+            .block_on(future)
+    }
+
+    /// 启动带预设响应的模拟虚拟机
+    fn launch_fake() -> AsyncVmRuntime<crate::runtimes::FakeVmRuntime> {
+        manipulate!(
+            FakeVm::new()
+            => .respond_after_cycle(
+                Cmd::NSE(nse_task!(<A --> C>?)),
+                [Output::ANSWER { content_raw: "<A --> C>.".into(), narsese: None }],
+            )
+        )
+        .launch()
+        .expect("无法启动模拟虚拟机")
+        .into()
+    }
+
+    /// 测试/异步输入与输出流
+    #[test]
+    fn test_input_and_outputs() {
+        block_on(async {
+            let mut vm = launch_fake();
+            let mut outputs = vm.outputs();
+            vm.input_cmd(Cmd::NSE(nse_task!(<A --> C>?))).await.unwrap();
+            assert!(matches!(outputs.next().await, Some(Output::IN { .. })));
+            vm.input_cmd(Cmd::CYC(1)).await.unwrap();
+            assert!(matches!(outputs.next().await, Some(Output::ANSWER { .. })));
+            vm.terminate().await.unwrap();
+            // 终止后流结束
+            assert!(outputs.next().await.is_none());
+            // 再次获取⇒空流
+            assert!(vm.outputs().next().await.is_none());
+        })
+    }
+
+    /// 测试/终止时不丢失输出
+    /// * 🎯输入后立即终止：终止前产生的所有输出，都能在终止后从流中取得
+    #[test]
+    fn test_terminate_keeps_outputs() {
+        const N: usize = 100;
+        block_on(async {
+            let mut vm = launch_fake();
+            for _ in 0..N {
+                vm.input_cmd(Cmd::NSE(nse_task!(<A --> C>?))).await.unwrap();
+            }
+            vm.input_cmd(Cmd::CYC(1)).await.unwrap();
+            vm.terminate().await.unwrap();
+            // 终止后不再接收指令
+            assert!(vm.input_cmd(Cmd::CYC(1)).await.is_err());
+            let outputs = vm.outputs().collect::<Vec<_>>().await;
+            let count = |f: fn(&Output) -> bool| outputs.iter().filter(|o| f(o)).count();
+            assert_eq!(outputs.len(), 2 * N, "{outputs:?}");
+            assert_eq!(count(|o| matches!(o, Output::IN { .. })), N);
+            assert_eq!(count(|o| matches!(o, Output::ANSWER { .. })), N);
+        })
+    }
+
+    /// 测试/未经终止即被丢弃⇒终止被包装的运行时
+    #[test]
+    fn test_drop_terminates() {
+        let vm = launch_fake();
+        let runtime = vm.runtime.clone();
+        assert!(!lock(&runtime).unwrap().is_terminated());
+        drop(vm);
+        assert!(lock(&runtime).unwrap().is_terminated());
+    }
+}
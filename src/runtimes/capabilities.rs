@@ -0,0 +1,124 @@
+//! CIN的功能支持声明
+//! * 🎯跨CIN的NAL测试：CIN不支持某项功能⇒跳过相应的测试，而非报告失败
+//!   * 📄`''requires: operations temporal`
+//! * 📌由各CIN实现（转译器预设）声明，启动时附加到运行时配置上
+//! * 🚩未声明（如外部转译器）⇒视作支持所有功能
+
+/// CIN的功能支持
+/// * 📌各字段名即`''requires:`中的「功能名」
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities {
+    /// 操作（NAL-8）
+    /// * 📄`<(*, {SELF}) --> ^left>!`⇒`EXE`输出
+    pub operations: bool,
+
+    /// 时序推理（NAL-7）
+    /// * 📄`<a --> b>. :|:`、`=/>`
+    pub temporal: bool,
+
+    /// 注册操作符
+    /// * 📄NAVM指令`REG left`
+    pub registration: bool,
+
+    /// 回答问题
+    /// * 📄`<A --> B>?`⇒`ANSWER`输出
+    pub answers: bool,
+}
+
+impl Capabilities {
+    /// 支持所有功能
+    pub const ALL: Self = Self {
+        operations: true,
+        temporal: true,
+        registration: true,
+        answers: true,
+    };
+
+    /// 不支持任何功能
+    pub const NONE: Self = Self {
+        operations: false,
+        temporal: false,
+        registration: false,
+        answers: false,
+    };
+
+    /// 所有功能名
+    /// * 🎯解析`''requires:`时检查拼写
+    pub const NAMES: &'static [&'static str] =
+        &["operations", "temporal", "registration", "answers"];
+
+    /// 是否支持某项功能
+    /// * 🚩未知的功能名⇒[`None`]
+    pub fn supports(&self, name: &str) -> Option<bool> {
+        match name {
+            "operations" => Some(self.operations),
+            "temporal" => Some(self.temporal),
+            "registration" => Some(self.registration),
+            "answers" => Some(self.answers),
+            _ => None,
+        }
+    }
+
+    /// 所要求的功能中，不被支持的那些
+    /// * 🚩未知的功能名亦视作「不被支持」
+    pub fn missing<'a>(&self, names: &'a [String]) -> Vec<&'a str> {
+        names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| self.supports(name) != Some(true))
+            .collect()
+    }
+
+    /// 两者皆支持的功能
+    /// * 🎯输入、输出转译器分属不同CIN时，取其交集
+    pub fn intersect(self, other: Self) -> Self {
+        Self {
+            operations: self.operations && other.operations,
+            temporal: self.temporal && other.temporal,
+            registration: self.registration && other.registration,
+            answers: self.answers && other.answers,
+        }
+    }
+}
+
+/// 默认：支持所有功能
+/// * 🎯未声明功能的CIN不跳过任何测试
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/功能名
+    #[test]
+    fn test_capability_names() {
+        for name in Capabilities::NAMES {
+            assert_eq!(Capabilities::ALL.supports(name), Some(true), "{name}");
+            assert_eq!(Capabilities::NONE.supports(name), Some(false), "{name}");
+        }
+        assert_eq!(Capabilities::ALL.supports("operation"), None);
+        let required = ["operations".into(), "answers".into(), "unknown".into()];
+        let capabilities = Capabilities {
+            operations: false,
+            ..Capabilities::ALL
+        };
+        assert_eq!(capabilities.missing(&required), ["operations", "unknown"]);
+        assert_eq!(
+            capabilities.intersect(Capabilities {
+                answers: false,
+                ..Capabilities::ALL
+            }),
+            Capabilities {
+                operations: false,
+                answers: false,
+                ..Capabilities::ALL
+            }
+        );
+    }
+}
@@ -13,7 +13,10 @@ use thiserror::Error;
 ///   * 📝若给上边类型传入值`None`，编译器无法自动推导合适的类型
 /// * 📌要求线程稳定
 ///   * 只有转译功能，没有其它涉及外部的操作（纯函数）
-pub type InputTranslator = dyn Fn(Cmd) -> Result<String> + Send + Sync;
+/// * 📌返回[`None`]⇒有意不向进程输入任何内容
+///   * 📄注释`REM`、CIN内置的操作`REG`等
+///   * 🎯与「输入空行」区分开
+pub type InputTranslator = dyn Fn(Cmd) -> Result<Option<String>> + Send + Sync;
 
 /// 进程输出→[`Output`]转译器
 /// * 🚩现在不再使用特征，以便在`Option<Box<OutputTranslator>>`中推断类型
@@ -22,11 +25,19 @@ pub type InputTranslator = dyn Fn(Cmd) -> Result<String> + Send + Sync;
 ///   * 只有转译功能，没有其它涉及外部的操作（纯函数）
 pub type OutputTranslator = dyn Fn(String) -> Result<Output> + Send + Sync;
 
+/// 「无回答」的输出类型
+/// * 🎯CIN明确报告「尚无回答」时，统一转译为该类型的[`Output::UNCLASSIFIED`]
+///   * 📄ONA、CXinNARS.js：`Answer: None.`
+///   * 📄PyNARS：不含Narsese的`ANSWER:`提示
+/// * 📌与[`Output::ANSWER`]区分：不会被`''expect-contains: ANSWER`误认
+/// * 🚩NAVM未内置此类型，故在此扩展
+pub const NO_ANSWER: &str = "NO_ANSWER";
+
 /// 默认输入转译器
 /// * 🎯给「输入输出转译器」提供「默认选项」
 /// * 🚩按照NAVM指令原样输入：调用[`Cmd::to_string`]原样转换成字符串
-pub fn default_input_translate(cmd: Cmd) -> Result<String> {
-    Ok(cmd.to_string())
+pub fn default_input_translate(cmd: Cmd) -> Result<Option<String>> {
+    Ok(Some(cmd.to_string()))
 }
 
 /// 默认输出转译器
@@ -67,7 +78,7 @@ impl IoTranslators {
     /// * 📌需要直接传入闭包（要求全局周期`'static`）
     pub fn new<I, O>(i: I, o: O) -> Self
     where
-        I: Fn(Cmd) -> Result<String> + Send + Sync + 'static,
+        I: Fn(Cmd) -> Result<Option<String>> + Send + Sync + 'static,
         O: Fn(String) -> Result<Output> + Send + Sync + 'static,
     {
         Self {
@@ -89,7 +100,7 @@ impl Default for IoTranslators {
     ///   * 使用泛型⇒难以定义通用的[`Self::default`]方法
     fn default() -> IoTranslators {
         IoTranslators {
-            input_translator: Box::new(|cmd| Ok(cmd.to_string())),
+            input_translator: Box::new(|cmd| Ok(Some(cmd.to_string()))),
             output_translator: Box::new(|content| Ok(Output::OTHER { content })),
         }
     }
@@ -102,7 +113,7 @@ impl Default for IoTranslators {
 ///     * 📄[`super::super::CommandVm::translators`]
 impl<I, O> From<(I, O)> for IoTranslators
 where
-    I: Fn(Cmd) -> Result<String> + Send + Sync + 'static,
+    I: Fn(Cmd) -> Result<Option<String>> + Send + Sync + 'static,
     O: Fn(String) -> Result<Output> + Send + Sync + 'static,
 {
     fn from(value: (I, O)) -> Self {
@@ -132,6 +143,18 @@ mod translate_error {
         /// * 🎯表示原先的「转译错误」
         #[error("NAVM转译错误：「{0}」")]
         ParseError(#[from] anyhow::Error),
+        /// 输出解析失败
+        /// * 🎯诊断：指明是哪个CIN的哪一行输出
+        /// * 📌由各CIN的输出转译器构造
+        #[error("{cin}输出解析失败：{source}（原始输出：{raw_line:?}）")]
+        OutputParseFailed {
+            /// CIN名称
+            cin: &'static str,
+            /// 原始输出行
+            raw_line: String,
+            /// 解析失败的原因
+            source: anyhow::Error,
+        },
     }
 
     // ! ❌弃用：为一个泛型参数实现转换，会导致其它「泛型实现」无法使用
@@ -161,6 +184,20 @@ mod translate_error {
             Self::from(&value.to_string())
         }
 
+        /// 构造「输出解析失败」
+        /// * 🚩原始输出行会去掉末尾换行符
+        pub fn output_parse_failed(
+            cin: &'static str,
+            raw_line: &str,
+            source: anyhow::Error,
+        ) -> Self {
+            Self::OutputParseFailed {
+                cin,
+                raw_line: raw_line.trim_end().to_string(),
+                source,
+            }
+        }
+
         /// 从[`Error`]转换为[`anyhow::Error`]
         /// * 🚩【2024-04-02 22:39:47】此处「转换为[`anyhow::Error`]的需求」就是`Error + Send + Sync + 'static`
         pub fn error_anyhow(value: impl Error + Send + Sync + 'static) -> anyhow::Error {
@@ -196,4 +233,24 @@ mod tests {
         // TODO: 【2024-03-27 22:56:26】有待完善
         let _t1 = IoTranslators::default();
     }
+
+    /// 测试/输出解析失败
+    /// * 🎯展示CIN名称与原始输出行；可从[`anyhow::Error`]中还原
+    #[test]
+    fn test_output_parse_failed() {
+        let e: anyhow::Error = TranslateError::output_parse_failed(
+            "ONA",
+            "Answer: <A -->.\n",
+            anyhow::anyhow!("缺少谓项"),
+        )
+        .into();
+        assert_eq!(
+            e.to_string(),
+            "ONA输出解析失败：缺少谓项（原始输出：\"Answer: <A -->.\"）"
+        );
+        assert!(matches!(
+            e.downcast_ref::<TranslateError>(),
+            Some(TranslateError::OutputParseFailed { cin: "ONA", .. })
+        ));
+    }
 }
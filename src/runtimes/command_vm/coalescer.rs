@@ -0,0 +1,114 @@
+//! 多行输出的合并
+//! * 🎯CIN的一条「输出」跨越多行：如Java异常堆栈、ONA的统计报告
+//!   * 📌逐行转译⇒每行各成一个`OTHER`输出：JSON导出中难以阅读，也无法作为整体匹配
+//! * 🚩由各CIN的转译器提供「续行」判断：续行⇒并入上一行所在的输出块
+//!   * 📌输出块在「下一个非续行到来」或「超时无新行」时结束，作为一行交给输出转译器
+//!   * 📌块内各行以换行符分隔
+
+use std::time::{Duration, Instant};
+
+/// 「续行」判断函数
+/// * 🎯判断一行原始输出是否「接续上一行」：属于同一条输出
+/// * 📌输入：缓冲中的输出块、子进程输出的原始行
+///   * 🎯只在特定的输出块中合并：如ONA只合并`Statistics`开头的统计报告
+/// * 📄Java异常堆栈：以空白或`at `开头的行
+pub type LineContinuation = dyn Fn(&str, &str) -> bool + Send + Sync;
+
+/// 默认的合并超时时间
+/// * 🎯最后一行之后，等待续行的时长
+/// * 📌异常堆栈等通常一次性输出：较短即可，以免延迟其后的输出
+pub const DEFAULT_COALESCE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// 多行输出合并器
+/// * 🚩缓冲一个「尚未结束」的输出块
+pub struct LineCoalescer {
+    /// 「续行」判断
+    continues_previous: Box<LineContinuation>,
+
+    /// 超时时间
+    /// * 🚩最后一行之后超过此时长仍无续行⇒输出块结束
+    timeout: Duration,
+
+    /// 缓冲中的输出块
+    /// * 🚩空⇒没有
+    buffer: String,
+
+    /// 最近一次并入新行的时刻
+    last_line: Instant,
+}
+
+impl LineCoalescer {
+    /// 构造函数
+    pub fn new(continues_previous: Box<LineContinuation>, timeout: Duration) -> Self {
+        Self {
+            continues_previous,
+            timeout,
+            buffer: String::new(),
+            last_line: Instant::now(),
+        }
+    }
+
+    /// 送入一行原始输出
+    /// * 🚩续行⇒并入缓冲；否则⇒结束缓冲中的输出块，以此行开始新的输出块
+    ///   * 📌没有缓冲时，续行亦开始新的输出块
+    /// * ⚙️返回已结束的输出块（若有）
+    pub fn push(&mut self, line: String) -> Option<String> {
+        self.last_line = Instant::now();
+        if !self.buffer.is_empty() && (self.continues_previous)(&self.buffer, &line) {
+            if !self.buffer.ends_with('\n') {
+                self.buffer.push('\n');
+            }
+            self.buffer.push_str(&line);
+            return None;
+        }
+        let finished = self.flush();
+        self.buffer = line;
+        finished
+    }
+
+    /// 取出已超时的输出块
+    /// * 🚩最后一行之后超过[超时时间](Self::timeout)⇒取出；否则⇒[`None`]
+    pub fn flush_expired(&mut self) -> Option<String> {
+        match self.last_line.elapsed() >= self.timeout {
+            true => self.flush(),
+            false => None,
+        }
+    }
+
+    /// 取出缓冲中的输出块，无论是否超时
+    /// * 🎯子进程已退出：不会再有续行
+    pub fn flush(&mut self) -> Option<String> {
+        match self.buffer.is_empty() {
+            true => None,
+            false => Some(std::mem::take(&mut self.buffer)),
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/合并续行
+    /// * 🎯续行并入上一行；非续行、超时⇒结束输出块
+    #[test]
+    fn test_coalesce() {
+        let mut coalescer = LineCoalescer::new(
+            Box::new(|_, line| line.starts_with('\t')),
+            Duration::from_millis(50),
+        );
+        let mut push = |line: &str| coalescer.push(line.into());
+        // 开头的续行亦开始新的输出块
+        assert_eq!(push("\torphan\n"), None);
+        assert_eq!(push("head\n"), Some("\torphan\n".into()));
+        assert_eq!(push("\tbody 1\n"), None);
+        assert_eq!(push("\tbody 2"), None);
+        assert_eq!(push("next"), Some("head\n\tbody 1\n\tbody 2".into()));
+        // 超时前不取出；超时后取出
+        assert_eq!(coalescer.flush_expired(), None);
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(coalescer.flush_expired(), Some("next".into()));
+        assert_eq!(coalescer.flush(), None);
+    }
+}
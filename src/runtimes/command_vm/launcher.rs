@@ -1,10 +1,27 @@
 //! 命令行虚拟机（构建者）
 
-use super::{InputTranslator, IoTranslators, OutputTranslator};
-use crate::process_io::IoProcess;
+use super::{InputTranslator, IoTranslators, LineContinuation, OutputTranslator};
+use crate::process_io::{IoProcess, ProcessEncoding};
 use anyhow::Result;
 use navm::{cmd::Cmd, output::Output};
-use std::{ffi::OsStr, process::Command};
+use std::{ffi::OsStr, process::Command, time::Duration};
+
+/// 「就绪标志」判断函数
+/// * 🎯启动检查中，识别CIN「已就绪」的输出行
+/// * 📌输入：子进程输出的原始行
+pub type ReadyMarker = dyn Fn(&str) -> bool + Send + Sync;
+
+/// 「CYC分批」参数
+/// * 🎯避免一次性输入大量推理步数，使较慢的CIN（如PyNARS）长时间无响应
+/// * 📄`CYC 1000`、每批`50`⇒分`20`批输入，每批之间等待`interval`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycPacing {
+    /// 每批的推理步数
+    /// * 📌非零
+    pub chunk: usize,
+    /// 两批之间的间隔
+    pub interval: Duration,
+}
 
 /// 命令行虚拟机（构建者）
 /// * 🎯配置化构造[`CommandVmRuntime`]
@@ -19,6 +36,28 @@ pub struct CommandVm {
 
     /// 进程输出→[`Output`]转译器
     pub(super) output_translator: Option<Box<OutputTranslator>>,
+
+    /// 启动检查的超时时间
+    /// * 🚩[`None`]⇒不检查，启动子进程后立即返回
+    pub(super) startup_timeout: Option<Duration>,
+
+    /// 启动检查的「就绪标志」
+    /// * 🚩[`None`]⇒任意一行输出均视作就绪
+    pub(super) ready_marker: Option<Box<ReadyMarker>>,
+
+    /// 「CYC分批」参数
+    /// * 🚩[`None`]⇒不分批，原样输入
+    pub(super) cyc_pacing: Option<CycPacing>,
+
+    /// 是否「严格转译」
+    /// * 🚩`false`⇒输出转译失败时产生[`Output::ERROR`]，不中断拉取
+    /// * 🚩`true`⇒输出转译失败时，拉取输出直接报错
+    pub(super) strict_translation: bool,
+
+    /// 多行输出的合并
+    /// * 🚩[`None`]⇒不合并，逐行转译
+    /// * 📌`(续行判断, 超时时间)`
+    pub(super) line_coalescing: Option<(Box<LineContinuation>, Duration)>,
 }
 
 impl CommandVm {
@@ -36,7 +75,7 @@ impl CommandVm {
     ///   * ✅链式操作现在可以使用[`util::manipulate`]简化
     pub fn input_translator(
         &mut self,
-        translator: impl Fn(Cmd) -> Result<String> + Send + Sync + 'static,
+        translator: impl Fn(Cmd) -> Result<Option<String>> + Send + Sync + 'static,
     ) {
         self.input_translator = Some(Box::new(translator));
     }
@@ -59,6 +98,75 @@ impl CommandVm {
         self.input_translator = Some(translators.input_translator);
         self.output_translator = Some(translators.output_translator);
     }
+
+    /// 配置/启动检查的超时时间
+    /// * 🎯CIN启动即失败（Java版本不对、缺少Python模块等）时，在启动时报错，而非此后一直沉默
+    /// * 🚩启动子进程后，在超时时间内等待「就绪标志」
+    ///   * 收到⇒启动成功
+    ///   * 子进程先行退出⇒启动失败，错误信息中附带其输出
+    ///   * 超时而子进程仍在运行⇒视作启动成功（部分CIN启动时不输出）
+    /// * 📌零时长⇒不检查
+    pub fn startup_timeout(&mut self, timeout: Duration) {
+        self.startup_timeout = match timeout.is_zero() {
+            true => None,
+            false => Some(timeout),
+        };
+    }
+
+    /// 配置/启动检查的「就绪标志」
+    /// * 🎯跳过CIN在就绪前的输出，如加载进度
+    /// * ⚠️仅在配置了[`Self::startup_timeout`]时生效
+    pub fn ready_marker(&mut self, marker: impl Fn(&str) -> bool + Send + Sync + 'static) {
+        self.ready_marker = Some(Box::new(marker));
+    }
+
+    /// 配置/启动检查
+    /// * 🎯各CIN的启动器统一配置：超时时间，与该CIN的「就绪标志」
+    /// * 🚩就绪标志为[`None`]⇒任意一行输出即视作就绪
+    /// * 📌其余同[`Self::startup_timeout`]、[`Self::ready_marker`]
+    pub fn startup_check(&mut self, timeout: Duration, ready_marker: Option<fn(&str) -> bool>) {
+        self.startup_timeout(timeout);
+        self.ready_marker = ready_marker.map(|marker| Box::new(marker) as Box<ReadyMarker>);
+    }
+
+    /// 配置/CYC分批
+    /// * 🎯将大步数的`CYC`拆成多批，在后台逐批输入，批间等待
+    ///   * 📌输入指令时立即返回，输出照常拉取
+    ///   * 📌后续指令总在最后一批之后输入
+    /// * 📌每批步数为零⇒不分批
+    pub fn cyc_pacing(&mut self, pacing: CycPacing) {
+        self.cyc_pacing = match pacing.chunk {
+            0 => None,
+            _ => Some(pacing),
+        };
+    }
+
+    /// 配置/严格转译
+    /// * 🎯测试转译器时，让无法转译的输出行直接报错
+    /// * 📌默认关闭：无法转译的行产生[`Output::ERROR`]，并留存以供诊断
+    ///   * 🔗见[`CommandVmRuntime::take_untranslated`](super::CommandVmRuntime::take_untranslated)
+    pub fn strict_translation(&mut self, strict: bool) {
+        self.strict_translation = strict;
+    }
+
+    /// 配置/合并多行输出
+    /// * 🎯跨越多行的输出（异常堆栈、统计报告）作为一个整体转译
+    /// * 🚩「续行」并入上一行所在的输出块；输出块在下一个非续行到来、或超时无新行时交给输出转译器
+    ///   * 📌超时时间见[`DEFAULT_COALESCE_TIMEOUT`](super::DEFAULT_COALESCE_TIMEOUT)
+    pub fn coalesce_lines(
+        &mut self,
+        continues_previous: impl Fn(&str, &str) -> bool + Send + Sync + 'static,
+        timeout: Duration,
+    ) {
+        self.line_coalescing = Some((Box::new(continues_previous), timeout));
+    }
+
+    /// 配置/子进程IO的文本编码
+    /// * 🎯输出非UTF-8文本的CIN：不因解码失败丢失输出行
+    /// * 🚩直接配置内部的「输入输出进程」，见[`IoProcess::encoding`]
+    pub fn encoding(&mut self, encoding: ProcessEncoding) {
+        self.io_process.encoding = encoding;
+    }
 }
 
 /// 实现/从[`IoProcess`]对象转换为[`CommandVm`]对象
@@ -71,6 +179,11 @@ impl From<IoProcess> for CommandVm {
             // 其它所有置空
             input_translator: None,
             output_translator: None,
+            startup_timeout: None,
+            ready_marker: None,
+            cyc_pacing: None,
+            strict_translation: false,
+            line_coalescing: None,
         }
     }
 }
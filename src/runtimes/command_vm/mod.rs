@@ -8,4 +8,6 @@ util::pub_mod_and_pub_use! {
     launcher
     // 运行时
     runtime
+    // 多行输出的合并
+    coalescer
 }
@@ -7,17 +7,43 @@
 //!     * 🚩实现方式：两处转译器
 
 use super::{
-    default_input_translator, default_output_translator, CommandVm, InputTranslator,
-    OutputTranslator,
+    default_input_translator, default_output_translator, CommandVm, CycPacing, InputTranslator,
+    LineCoalescer, OutputTranslator, ReadyMarker,
+};
+use crate::{
+    process_io::{terminate_process_tree, IoProcessManager, KillOutcome, ProcessInput},
+    runtimes::{wait_until_idle, IdleProbe, IdleResult, KillHandle, VmRuntimeBoost},
 };
-use crate::process_io::IoProcessManager;
 use anyhow::{anyhow, Result};
-use nar_dev_utils::if_return;
 use navm::{
     cmd::Cmd,
     output::Output,
     vm::{VmLauncher, VmRuntime, VmStatus},
 };
+use std::{
+    collections::VecDeque,
+    process::ExitStatus,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// 等待输出时，检查「子进程是否已退出」的间隔
+pub const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 默认的启动检查超时时间
+/// * 🎯覆盖大多数「启动即失败」的情形：如Java找不到jar文件
+pub const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 启动失败时，错误信息中至多附带的输出行数
+pub const STARTUP_DIAGNOSTIC_LINES: usize = 20;
+
+/// 至多留存的「无法转译的输出行」数目
+/// * 🚩超出⇒丢弃最早的行
+pub const UNTRANSLATED_CAPACITY: usize = 100;
 
 /// 命令行虚拟机运行时
 /// * 🎯封装「进程通信」逻辑
@@ -35,45 +61,144 @@ pub struct CommandVmRuntime {
 
     /// 用于指示的「状态」变量
     status: VmStatus,
+
+    /// 启动检查中已读取的输出行，及其「收到时间」
+    /// * 🚩在后续拉取输出时优先转译并传出，不丢失CIN的启动输出
+    startup_lines: VecDeque<(String, Instant)>,
+
+    /// 尚未转译的输出块，及其「收到时间」
+    /// * 🎯严格转译：批量拉取中途出错时，不丢弃同批次的其它输出
+    /// * 🚩在后续拉取输出时优先转译并传出
+    pending_blocks: VecDeque<(String, Instant)>,
+
+    /// 尚未报告的转译错误
+    /// * 🎯严格转译：批量拉取中途出错时，先传出已转译的输出，下次拉取时再报错
+    pending_error: Option<anyhow::Error>,
+
+    /// 「CYC分批」参数
+    /// * 🚩[`None`]⇒不分批
+    cyc_pacing: Option<CycPacing>,
+
+    /// 正在后台分批输入的`CYC`
+    /// * 🚩[`None`]⇒没有
+    pending_cyc: Option<PendingCyc>,
+
+    /// 是否「严格转译」
+    /// * 🚩`true`⇒输出转译失败时，拉取输出直接报错
+    strict_translation: bool,
+
+    /// 无法转译的输出行
+    /// * 🎯诊断：CIN输出中混入的异常堆栈等
+    /// * 📌至多留存[`UNTRANSLATED_CAPACITY`]行
+    untranslated: VecDeque<String>,
+
+    /// 多行输出合并器
+    /// * 🚩[`None`]⇒不合并，逐行转译
+    coalescer: Option<LineCoalescer>,
+
+    /// 最近一行送入合并器的输出的「收到时间」
+    /// * 🎯作为合并后输出块的「收到时间」：块在其最后一行到达时才完整
+    last_coalesced: Instant,
+}
+
+/// 正在后台分批输入的`CYC`
+struct PendingCyc {
+    /// 逐批输入的子线程
+    handle: JoinHandle<Result<()>>,
+    /// 取消信号：置为`true`后不再输入剩余批次
+    cancel: Arc<AtomicBool>,
 }
 
 impl VmRuntime for CommandVmRuntime {
     fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        // 大步数的`CYC`⇒分批输入
+        if let (Cmd::CYC(steps), Some(pacing)) = (&cmd, self.cyc_pacing) {
+            if *steps > pacing.chunk {
+                return self.input_cyc_paced(*steps, pacing);
+            }
+        }
+        // 控制指令⇒优先输入：不等待排队中的普通输入
+        let priority = is_priority_cmd(&cmd);
+        // 等待此前分批的`CYC`输入完毕 | 保证输入顺序
+        if !priority {
+            self.flush_cyc()?;
+        }
         // 尝试转译
-        let input = (self.input_translator)(cmd)?;
-        // 当输入非空时，置入转译结果
-        // * 🚩【2024-04-03 02:20:48】目前用「空字串」作为「空输入」的情形
-        // TODO: 后续或将让「转译器」返回`Option<String>`
-        // 空⇒提前返回
-        if_return! { input.is_empty() => Ok(()) }
+        // * 🚩转译器返回`None`⇒有意不输入，提前返回
+        let input = match (self.input_translator)(cmd)? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
         // 置入
         // * 🚩没有换行符
         // * 📌【2024-04-07 23:43:59】追踪「Websocket进程阻塞」漏洞：问题不在此，在`ws::Sender::send`处
-        self.process.put_line(input)
+        match priority {
+            true => self.process.put_line_priority(input),
+            false => self.process.put_line(input),
+        }
     }
 
+    /// 拉取一个输出
+    /// * 🚩等待输出的同时，定期检查子进程是否已退出
+    ///   * 📌子进程已退出⇒产生一个`TERMINATED`输出（仅一次）
+    /// * 🎯避免调用方在「已崩溃的CIN」上无限等待
     fn fetch_output(&mut self) -> Result<Output> {
-        let s = self.process.fetch_output()?;
-        (self.output_translator)(s)
+        if let Some(output) = self.fetch_startup_output()? {
+            return Ok(output);
+        }
+        loop {
+            // ! 先检查「是否已关闭」再拉取：确保「已关闭」时所有输出都已在通道中
+            let closed = self.process.is_child_closed()?;
+            // * 🚩拉取失败⇒视作「意外终止」
+            let s = self
+                .process
+                .fetch_output_timeout(EXIT_POLL_INTERVAL)
+                .inspect_err(|e| {
+                    self.status = VmStatus::Terminated(Err(anyhow!("无法拉取子进程输出：{e}")))
+                })?;
+            // 有输出⇒仅在输出块结束时转译
+            if let Some(s) = s {
+                if let Some((block, _)) = self.coalesce(s, Instant::now()) {
+                    return self.translate_output(block);
+                }
+                continue;
+            }
+            // 无输出⇒缓冲的输出块已超时、或子进程已关闭⇒转译
+            if let Some((block, _)) = self.flush_coalesced(closed) {
+                return self.translate_output(block);
+            }
+            // 已报告过终止⇒不会再有输出
+            if self.is_terminated() {
+                return Err(anyhow!("子进程已终止，无法再拉取输出"));
+            }
+            if closed {
+                if let Some(output) = self.exit_output()? {
+                    return Ok(output);
+                }
+            }
+        }
     }
 
     fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        if let Some(output) = self.fetch_startup_output()? {
+            return Ok(Some(output));
+        }
+        let closed = self.process.is_child_closed()?;
         let s = self.process.try_fetch_output()?;
         // 匹配分支
-        match s {
+        // 有输出⇒送入合并器，仅在输出块结束时转译
+        // * 🚩没有结束的输出块⇒尝试取出已超时的
+        let block = match s {
+            Some(s) => self.coalesce(s, Instant::now()),
+            None => self.flush_coalesced(closed),
+        };
+        // 匹配分支
+        match block {
             // 有输出⇒尝试转译并返回
-            Some(s) => Ok(Some({
-                // 转译输出
-                let output = (self.output_translator)(s)?;
-                // * 当输出为「TERMINATED」时，将自身终止状态置为「TERMINATED」
-                if let Output::TERMINATED { description } = &output {
-                    // ! 🚩【2024-04-02 21:39:56】目前将所有「终止」视作「意外终止」⇒返回`Err`
-                    self.status = VmStatus::Terminated(Err(anyhow!(description.clone())));
-                }
-                // 传出输出
-                output
-            })),
-            // 没输出⇒没输出 | ⚠️注意：不能使用`map`，否则`?`穿透不出闭包
+            Some((block, _)) => self.translate_output(block).map(Some),
+            // 没输出⇒子进程已退出则报告终止，否则没输出
+            // * ⚠️注意：不能使用`map`，否则`?`穿透不出闭包
+            None if closed => self.exit_output(),
             None => Ok(None),
         }
     }
@@ -86,27 +211,380 @@ impl VmRuntime for CommandVmRuntime {
         // 给CIN发送「终止」指令：告知CIN内部「需要结束程序」
         // * 📌【2024-05-09 14:20:00】目前似乎通过这一手段，仍然无法彻底关闭Java程序
         // * 🔬【2024-05-09 14:20:22】目前在程序关闭时，即便杀掉了子进程，也会因此被阻塞（需要kill`java.exe`才能解锁）
-        self.input_cmd(Cmd::EXI {
+        // * 🚩子进程可能已自行退出（或已被终止）：此时发送失败在预期之内，不影响后续终止
+        // * 🚩尚未输入的`CYC`批次不再输入
+        if let Some(pending) = &self.pending_cyc {
+            pending.cancel.store(true, Ordering::SeqCst);
+        }
+        let _ = self.input_cmd(Cmd::EXI {
             reason: "CIN terminated by BabelNAR".into(),
-        })?;
+        });
 
         // 杀死子进程
-        self.process.kill()?;
-
-        // （杀死后）设置状态
-        // * 🚩【2024-04-02 21:42:30】目前直接覆盖状态
-        self.status = VmStatus::Terminated(Ok(()));
+        // * 🚩根据「杀死结果」设置状态
+        //   * 被杀死⇒终止
+        //   * 已自行退出⇒保留已有的「终止」状态（如来自`TERMINATED`输出）
+        //   * 先前已终止⇒无操作（重复调用）
+        match self.process.kill()? {
+            KillOutcome::Killed => self.status = VmStatus::Terminated(Ok(())),
+            KillOutcome::AlreadyExited(..) => {
+                if !matches!(self.status, VmStatus::Terminated(..)) {
+                    self.status = VmStatus::Terminated(Ok(()))
+                }
+            }
+            KillOutcome::AlreadyKilled => {}
+        }
 
         // 返回「终止完成」
         Ok(())
     }
 }
 
+/// 增强功能
+impl VmRuntimeBoost for CommandVmRuntime {
+    /// 拉取当前所有待处理的输出
+    /// * 🚩只锁定一次「输出通道」，取出所有已有的行后再逐个转译
+    /// * 📌单行转译失败⇒记为[`Output::ERROR`]，不中断整批拉取（严格转译除外）
+    /// * 📌子进程已退出，且已无待处理的输出⇒产生一个`TERMINATED`输出（仅一次）
+    ///   * 🚩同[`VmRuntime::try_fetch_output`]：先取完所有输出，再报告终止
+    fn drain_outputs(&mut self) -> Result<Vec<Output>> {
+        let outputs = self.drain_outputs_timed()?;
+        Ok(outputs.into_iter().map(|(output, _)| output).collect())
+    }
+
+    /// 拉取当前所有待处理的输出，并附带「收到时间」
+    /// * 🚩时间由「读输出」子线程在读到各行时记录
+    ///   * 📌合并的输出块⇒以其最后一行为准
+    ///   * 📌`TERMINATED`输出⇒拉取的时刻
+    fn drain_outputs_timed(&mut self) -> Result<Vec<(Output, Instant)>> {
+        // * 🚩上次留下的转译错误⇒先报错，暂不从进程拉取新输出（以免丢失）
+        if let Some(e) = self.pending_error.take() {
+            return Err(e);
+        }
+        let closed = self.process.is_child_closed()?;
+        let lines = self
+            .startup_lines
+            .drain(..)
+            .chain(self.process.drain_outputs_timed()?)
+            .collect::<Vec<_>>();
+        let coalesced = lines
+            .into_iter()
+            .filter_map(|(line, received)| self.coalesce(line, received))
+            .collect::<Vec<_>>();
+        let mut blocks = self.pending_blocks.drain(..).collect::<VecDeque<_>>();
+        blocks.extend(coalesced);
+        blocks.extend(self.flush_coalesced(closed));
+        let mut outputs = Vec::with_capacity(blocks.len());
+        while let Some((block, received)) = blocks.pop_front() {
+            match self.translate_output(block) {
+                Ok(output) => outputs.push((output, received)),
+                // * 🚩严格转译出错⇒其余输出块留待下次拉取
+                //   * 📌已有输出⇒先传出，下次拉取时再报错
+                Err(e) => {
+                    self.pending_blocks = blocks;
+                    if outputs.is_empty() {
+                        return Err(e);
+                    }
+                    self.pending_error = Some(e);
+                    return Ok(outputs);
+                }
+            }
+        }
+        if closed && outputs.is_empty() {
+            let now = Instant::now();
+            outputs.extend(self.exit_output()?.map(|output| (output, now)));
+        }
+        Ok(outputs)
+    }
+
+    /// 向子进程直接写入一行
+    /// * 🚩不经过「输入转译器」
+    /// * 🚩先等待此前分批的`CYC`输入完毕：与[`Self::input_cmd`]相同，保证输入顺序
+    fn input_raw(&mut self, line: &str) -> Result<()> {
+        self.flush_cyc()?;
+        self.process.put_line(line)
+    }
+
+    /// 分批输入的`CYC`⇒每批一个回执
+    fn cyc_ack_count(&self, steps: usize) -> usize {
+        match self.cyc_pacing {
+            Some(pacing) if steps > pacing.chunk => steps.div_ceil(pacing.chunk),
+            _ => 1,
+        }
+    }
+
+    /// 等待后台分批输入的`CYC`全部输入完毕
+    fn flush_inputs(&mut self) -> Result<()> {
+        self.flush_cyc()
+    }
+
+    /// 按进程id终止子进程树
+    /// * 🚩子进程退出后，阻塞在其输出上的调用随之返回
+    fn kill_handle(&self) -> Option<KillHandle> {
+        let pid = self.process.id();
+        Some(KillHandle::new(move || {
+            let _ = terminate_process_tree(pid);
+        }))
+    }
+}
+
+/// 是否为经「优先输入」置入的控制指令
+/// * 🎯`VOL`、`EXI`、`RES`不被大量排队的普通输入（如`NSE`）阻塞
+/// * ⚠️会先于此前尚未写入的普通输入：如排队中的`NSE`可能在`RES`之后才被输入
+///   * 📌此后的普通输入仍在`RES`之后：优先通道先被取尽
+fn is_priority_cmd(cmd: &Cmd) -> bool {
+    matches!(cmd, Cmd::VOL(..) | Cmd::EXI { .. } | Cmd::RES { .. })
+}
+
+impl CommandVmRuntime {
+    /// 等待后台分批输入的`CYC`全部输入完毕
+    /// * 🎯保证后续输入位于最后一批之后
+    /// * 📌输入指令前会自动调用：通常无需手动调用
+    /// * 🚩没有正在分批输入的`CYC`⇒直接返回
+    pub fn flush_cyc(&mut self) -> Result<()> {
+        let Some(PendingCyc { handle, .. }) = self.pending_cyc.take() else {
+            return Ok(());
+        };
+        handle
+            .join()
+            .map_err(|_| anyhow!("分批输入CYC的子线程崩溃"))?
+    }
+
+    /// 分批输入`CYC`
+    /// * 🚩在当前线程中逐批转译，再交由子线程逐批输入、批间等待
+    ///   * 📌转译失败⇒立即报错，不输入任何批次
+    /// * 📄`CYC 120`、每批`50`⇒`CYC 50`、`CYC 50`、`CYC 20`
+    fn input_cyc_paced(&mut self, steps: usize, pacing: CycPacing) -> Result<()> {
+        self.flush_cyc()?;
+        let mut inputs = vec![];
+        for start in (0..steps).step_by(pacing.chunk) {
+            let chunk = pacing.chunk.min(steps - start);
+            inputs.extend((self.input_translator)(Cmd::CYC(chunk))?);
+        }
+        let sender = self.process.input_sender()?;
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_inner = cancel.clone();
+        let handle = thread::spawn(move || {
+            for (i, input) in inputs.into_iter().enumerate() {
+                if i > 0 {
+                    thread::sleep(pacing.interval);
+                }
+                if cancel_inner.load(Ordering::SeqCst) {
+                    break;
+                }
+                sender
+                    .send(ProcessInput::Normal(format!("{input}\n")))
+                    .map_err(|e| anyhow!("无法分批输入CYC：{e}"))?;
+            }
+            Ok(())
+        });
+        self.pending_cyc = Some(PendingCyc { handle, cancel });
+        Ok(())
+    }
+
+    /// 等待CIN「安静下来」
+    /// * 🎯在一阵`CYC`之后，等到CIN不再产出新的推理结果，再做下一步决策
+    /// * 🚩计时与判定见[`wait_until_idle`]
+    ///   * 📌后台分批输入的`CYC`尚未输入完毕⇒不算安静
+    ///   * 📌子进程已关闭⇒不会再有输出，亦算安静
+    /// * 📌不拉取输出：输出仍留在通道中，由后续拉取照常传出
+    ///   * 🚩输出时间由「读输出」子线程记录，见[`IoProcessManager::last_output_at`]
+    pub fn wait_for_idle(
+        &mut self,
+        quiet_period: Duration,
+        max_wait: Duration,
+    ) -> Result<IdleResult> {
+        let mut seen = self.process.last_output_at()?;
+        wait_until_idle(quiet_period, max_wait, || {
+            if self.process.is_child_closed()? {
+                return Ok(IdleProbe::Closed);
+            }
+            let cyc_pending = self
+                .pending_cyc
+                .as_ref()
+                .is_some_and(|pending| !pending.handle.is_finished());
+            let latest = self.process.last_output_at()?;
+            let active = cyc_pending || latest != seen;
+            seen = latest;
+            Ok(match active {
+                true => IdleProbe::Active,
+                false => IdleProbe::Quiet,
+            })
+        })
+    }
+
+    /// 取出所有「无法转译的输出行」
+    /// * 🎯诊断：查看CIN输出中未被转译器识别的内容
+    /// * 📌至多[`UNTRANSLATED_CAPACITY`]行，按输出顺序排列
+    pub fn take_untranslated(&mut self) -> Vec<String> {
+        self.untranslated.drain(..).collect()
+    }
+
+    /// 转译一行输出
+    /// * 🚩转译失败⇒产生[`Output::ERROR`]（附带错误与原始输出），并留存原始输出行
+    ///   * 📌严格转译⇒直接报错
+    /// * 🚩当输出为「TERMINATED」时，将自身终止状态置为「TERMINATED」
+    fn translate_output(&mut self, line: String) -> Result<Output> {
+        let output = match (self.output_translator)(line.clone()) {
+            Ok(output) => output,
+            Err(e) if self.strict_translation => return Err(e),
+            Err(e) => {
+                let description = format!("输出转译失败：{e}\n{}", line.trim_end());
+                if self.untranslated.len() >= UNTRANSLATED_CAPACITY {
+                    self.untranslated.pop_front();
+                }
+                self.untranslated.push_back(line);
+                Output::ERROR { description }
+            }
+        };
+        self.update_status_from(&output);
+        Ok(output)
+    }
+
+    /// 将一行原始输出送入合并器
+    /// * 🚩未启用合并⇒原样返回
+    /// * ⚙️返回已结束的输出块（若有），及其「收到时间」
+    fn coalesce(&mut self, line: String, received: Instant) -> Option<(String, Instant)> {
+        match &mut self.coalescer {
+            Some(coalescer) => {
+                // 结束的输出块不含此行：时间取其最后一行
+                let block_received = std::mem::replace(&mut self.last_coalesced, received);
+                coalescer.push(line).map(|block| (block, block_received))
+            }
+            None => Some((line, received)),
+        }
+    }
+
+    /// 取出合并器中缓冲的输出块，及其「收到时间」
+    /// * 🚩`force`（子进程已关闭）⇒无论是否超时都取出；否则⇒仅取出已超时的
+    fn flush_coalesced(&mut self, force: bool) -> Option<(String, Instant)> {
+        let coalescer = self.coalescer.as_mut()?;
+        let block = match force {
+            true => coalescer.flush(),
+            false => coalescer.flush_expired(),
+        };
+        block.map(|block| (block, self.last_coalesced))
+    }
+
+    /// 转译并传出「启动检查中已读取的输出」
+    /// * 🚩先报告此前未报告的转译错误，再传出此前未转译的输出块
+    /// * 🚩逐行送入合并器，直到有输出块结束
+    /// * 🚩无⇒[`None`]
+    fn fetch_startup_output(&mut self) -> Result<Option<Output>> {
+        if let Some(e) = self.pending_error.take() {
+            return Err(e);
+        }
+        if let Some((block, _)) = self.pending_blocks.pop_front() {
+            return self.translate_output(block).map(Some);
+        }
+        while let Some((line, received)) = self.startup_lines.pop_front() {
+            if let Some((block, _)) = self.coalesce(line, received) {
+                return self.translate_output(block).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// 启动检查
+    /// * 🚩在超时时间内等待「就绪标志」（无⇒任意一行输出）
+    ///   * 收到⇒成功
+    ///   * 子进程先行退出⇒失败：错误信息中附带退出状态与开头的输出
+    ///   * 超时⇒成功：部分CIN在启动时不输出
+    /// * 📌期间读取的输出行会被保留，由后续拉取传出
+    fn await_startup(
+        &mut self,
+        timeout: Duration,
+        ready_marker: Option<&ReadyMarker>,
+    ) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // ! 先检查「是否已关闭」再拉取：确保「已关闭」时所有输出都已在通道中
+            let closed = self.process.is_child_closed()?;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(());
+            }
+            match self
+                .process
+                .fetch_output_timeout(remaining.min(EXIT_POLL_INTERVAL))?
+            {
+                Some(line) => {
+                    let ready = ready_marker.is_none_or(|is_ready| is_ready(&line));
+                    self.startup_lines.push_back((line, Instant::now()));
+                    if ready {
+                        return Ok(());
+                    }
+                }
+                None if closed => return Err(self.startup_error()),
+                None => {}
+            }
+        }
+    }
+
+    /// 生成「启动失败」的错误
+    /// * 🚩附带退出状态，以及开头至多[`STARTUP_DIAGNOSTIC_LINES`]行的输出
+    /// * 📌标准错误由子进程继承：其内容（如Java的`Error: Unable to access jarfile`）直接显示给用户
+    fn startup_error(&mut self) -> anyhow::Error {
+        let exit = match self.process.exit_status() {
+            Ok(Some(status)) => exit_description(status),
+            _ => "process closed its output".into(),
+        };
+        let lines = self
+            .startup_lines
+            .iter()
+            .map(|(line, _)| line.trim_end().to_string())
+            .filter(|line| !line.is_empty())
+            .take(STARTUP_DIAGNOSTIC_LINES)
+            .collect::<Vec<_>>();
+        match lines.is_empty() {
+            true => anyhow!("CIN启动失败：{exit}（无任何输出）"),
+            false => anyhow!("CIN启动失败：{exit}\n{}", lines.join("\n")),
+        }
+    }
+
+    /// 根据输出更新自身状态
+    /// * 🚩当输出为「TERMINATED」时，将自身终止状态置为「TERMINATED」
+    fn update_status_from(&mut self, output: &Output) {
+        if let Output::TERMINATED { description } = output {
+            // ! 🚩【2024-04-02 21:39:56】目前将所有「终止」视作「意外终止」⇒返回`Err`
+            self.status = VmStatus::Terminated(Err(anyhow!(description.clone())));
+        }
+    }
+
+    /// 子进程已自行退出⇒生成`TERMINATED`输出，并更新自身状态
+    /// * 🎯让调用方（如自动重启）及时得知CIN退出
+    /// * 🚩已处于「终止」状态⇒不再生成：避免重复报告
+    /// * ⚠️须在「子进程标准IO已关闭」后调用：保证此前的输出都已被拉取
+    fn exit_output(&mut self) -> Result<Option<Output>> {
+        if self.is_terminated() {
+            return Ok(None);
+        }
+        let Some(exit_status) = self.process.exit_status()? else {
+            return Ok(None);
+        };
+        let output = Output::TERMINATED {
+            description: exit_description(exit_status),
+        };
+        self.update_status_from(&output);
+        Ok(Some(output))
+    }
+}
+
+/// 描述子进程的退出状态
+/// * 📄`process exited with code 1`
+/// * 🚩无退出码（如被信号终止）⇒使用[`ExitStatus`]自身的描述
+fn exit_description(exit_status: ExitStatus) -> String {
+    match exit_status.code() {
+        Some(code) => format!("process exited with code {code}"),
+        None => format!("process exited: {exit_status}"),
+    }
+}
+
 /// 构建功能：启动命令行虚拟机
 impl VmLauncher for CommandVm {
     type Runtime = CommandVmRuntime;
     fn launch(self) -> Result<CommandVmRuntime> {
-        Ok(CommandVmRuntime {
+        let mut runtime = CommandVmRuntime {
             // 状态：正在运行
             status: VmStatus::Running,
             // 启动内部的「进程管理者」
@@ -124,7 +602,29 @@ impl VmLauncher for CommandVm {
                 // * 🚩【2024-04-04 02:02:53】似乎不应有如此默认行为：后续若配置载入失败，将难以识别问题
                 .unwrap_or(default_output_translator()),
             // * 🚩【2024-03-24 02:06:59】目前到此为止：只需处理「转译」问题
-        })
+            startup_lines: VecDeque::new(),
+            pending_blocks: VecDeque::new(),
+            pending_error: None,
+            // CYC分批
+            cyc_pacing: self.cyc_pacing,
+            pending_cyc: None,
+            // 输出转译
+            strict_translation: self.strict_translation,
+            untranslated: VecDeque::new(),
+            // 多行输出的合并
+            coalescer: self.line_coalescing.map(|(continues_previous, timeout)| {
+                LineCoalescer::new(continues_previous, timeout)
+            }),
+            last_coalesced: Instant::now(),
+        };
+        // 启动检查 | 失败⇒清理子进程后报错
+        if let Some(timeout) = self.startup_timeout {
+            if let Err(e) = runtime.await_startup(timeout, self.ready_marker.as_deref()) {
+                let _ = runtime.process.kill();
+                return Err(e);
+            }
+        }
+        Ok(runtime)
     }
 }
 
@@ -136,7 +636,8 @@ pub mod tests {
     use super::*;
     use crate::{
         cin_implements::common::generate_command,
-        runtimes::TranslateError,
+        runtimes::{TranslateError, DEFAULT_COALESCE_TIMEOUT},
+        test_tools::{is_expected_echo, EchoMatchOptions},
         tests::cin_paths::{OPENNARS, PYNARS_MODULE, PYNARS_ROOT},
     };
     use nar_dev_utils::manipulate;
@@ -158,6 +659,21 @@ pub mod tests {
     use std::process::Command;
     use util::first;
 
+    /// 测试工具：回显输入的命令行虚拟机
+    /// * 🎯无需CIN可执行文件即可测试：每行输出原样转译为[`Output::OTHER`]
+    /// * 📌可在其上继续配置：后配置的转译器覆盖此处的转译器
+    pub fn echo_vm() -> CommandVm {
+        use crate::process_io::IoProcess;
+        #[cfg(not(windows))]
+        let io_process = IoProcess::new("cat");
+        #[cfg(windows)]
+        let io_process = IoProcess::new("findstr").arg("^");
+        manipulate!(
+            CommandVm::from(io_process)
+            => .output_translator(|content| Ok(Output::OTHER { content }))
+        )
+    }
+
     // ! 🚩【2024-04-07 12:09:44】现在路径统一迁移到`lib.rs`的`tests`模块下
 
     const COMMAND_JAVA: &str = "java";
@@ -165,7 +681,7 @@ pub mod tests {
 
     /// 实用测试工具/等待
     pub fn await_fetch_until(
-        vm: &mut CommandVmRuntime,
+        vm: &mut impl VmRuntime,
         criterion: impl Fn(&Output, &str) -> bool,
     ) -> Output {
         // 不断拉取输出
@@ -200,7 +716,7 @@ pub mod tests {
 
     /// 实用测试工具/输入并等待
     pub fn input_cmd_and_await(
-        vm: &mut CommandVmRuntime,
+        vm: &mut impl VmRuntime,
         cmd: Cmd,
         criterion: impl Fn(&Output, &str) -> bool,
     ) -> Output {
@@ -215,7 +731,7 @@ pub mod tests {
     /// * 🎯【2024-03-24 18:38:50】用于「输出转换」尚未成熟时
     #[inline(always)]
     pub fn input_cmd_and_await_contains(
-        vm: &mut CommandVmRuntime,
+        vm: &mut impl VmRuntime,
         cmd: Cmd,
         expected_contains: &str,
     ) -> Option<Output> {
@@ -237,27 +753,19 @@ pub mod tests {
     /// 实用测试工具/输入并等待「Narsese回显」
     /// * 🚩`input_cmd_and_await`的简单封装
     /// * ✅【2024-03-29 22:55:11】现在「输出转换」已经成熟（可以提取出Narsese）
-    /// * 🚩通过「转换为『枚举Narsese』」以实现判等逻辑（主要为「语义相等」）
+    /// * 🚩按「输入回显」判等：忽略各CIN附加的预算值、时间戳数值
+    ///   * 🔗见[`is_expected_echo`]
     #[inline(always)]
     pub fn input_cmd_and_await_narsese(
-        vm: &mut CommandVmRuntime,
+        vm: &mut impl VmRuntime,
         cmd: Cmd,
         expected: Narsese,
     ) -> Output {
-        // 预先构建预期
-        let expected = expected
-            .clone()
-            .try_fold_into(&FORMAT_ASCII_ENUM)
-            .expect("作为预期的词法Narsese无法折叠！");
         // 输入 & 等待
         input_cmd_and_await(vm, cmd, |out, _| {
-            // 有Narsese
-            out.get_narsese().is_some_and(|out| {
-                // 且与预期一致
-                out.clone() // 必须复制：折叠消耗自身
-                    .try_fold_into(&FORMAT_ASCII_ENUM)
-                    .is_ok_and(|out| is_expected_narsese(&expected, &out))
-            })
+            // 有Narsese，且与预期一致
+            out.get_narsese()
+                .is_some_and(|out| is_expected_echo(&expected, out, EchoMatchOptions::default()))
         })
     }
 
@@ -382,7 +890,7 @@ pub mod tests {
 
         /// 临时构建的「输入转换」函数
         /// * 🎯用于转换`VOL 0`⇒`*volume=0`，避免大量输出造成进程卡顿
-        fn input_translate(cmd: Cmd) -> Result<String> {
+        fn input_translate(cmd: Cmd) -> Result<Option<String>> {
             let content = match cmd {
                 // 直接使用「末尾」，此时将自动格式化任务（可兼容「空预算」的形式）
                 Cmd::NSE(..) => cmd.tail(),
@@ -394,7 +902,7 @@ pub mod tests {
                 _ => return Err(TranslateError::UnsupportedInput(cmd).into()),
             };
             // 转换
-            Ok(content)
+            Ok(Some(content))
         }
 
         /// 临时构建的「输出转换」函数
@@ -452,9 +960,14 @@ pub mod tests {
     #[test]
     fn test_pynars() {
         let vm = manipulate!(
-            CommandVm::from(generate_command("python", Some(PYNARS_ROOT), ["-m", PYNARS_MODULE]))
+            CommandVm::from(generate_command(
+                "python",
+                Some(PYNARS_ROOT),
+                ["-m", PYNARS_MODULE],
+                [("PYTHONIOENCODING", "utf-8")],
+            ))
             // 输入转译器：直接取其尾部
-            => .input_translator(|cmd| Ok(cmd.tail()))
+            => .input_translator(|cmd| Ok(Some(cmd.tail())))
             // 暂无输出转译器
             // => .output_translator(output_translate)
         )
@@ -525,7 +1038,8 @@ pub mod tests {
 
     /// 通用测试/简单回答 | 基于Narsese
     /// * 📌考察NARS最基础的「继承演绎推理」
-    pub fn test_simple_answer(mut vm: CommandVmRuntime) {
+    /// * 🚩对所有运行时通用：亦可用于[`crate::runtimes::FakeVmRuntime`]
+    pub fn test_simple_answer(mut vm: impl VmRuntime) {
         // 构造并输入任务 | 输入进PyNARS后变成了紧凑版本
         let _ = vm.input_cmd(Cmd::VOL(0)); // * 尝试静音
         input_cmd_and_await_narsese(&mut vm, Cmd::NSE(nse_task!(<A --> B>.)), nse!(<A --> B>.));
@@ -555,4 +1069,572 @@ pub mod tests {
         vm.terminate().expect("无法终止虚拟机");
         println!("Virtual machine terminated...");
     }
+
+    /// 测试/优先输入的控制指令
+    /// * 🎯`VOL`、`EXI`、`RES`经优先通道；其它指令按顺序输入
+    #[test]
+    fn test_is_priority_cmd() {
+        assert!(is_priority_cmd(&Cmd::VOL(0)));
+        assert!(is_priority_cmd(&Cmd::EXI { reason: "".into() }));
+        assert!(is_priority_cmd(&Cmd::RES { target: "".into() }));
+        assert!(!is_priority_cmd(&Cmd::CYC(1)));
+    }
+
+    /// 测试/`RES`越过排队中的普通输入
+    /// * 🎯有意为之：重置尽快生效，此前尚未写入的普通输入在重置之后才被输入
+    /// * 🎯`RES`之后的普通输入仍在`RES`之后：优先通道先被取尽
+    /// * 🚩子进程逐行缓慢回显输入的前八个字符：大量普通输入在管道与通道中排队
+    #[test]
+    #[cfg(not(windows))]
+    fn test_res_overtakes_queued_inputs() {
+        use crate::process_io::IoProcess;
+        const N: usize = 1000;
+        let script = r#"while IFS= read -r line; do printf '%.8s\n' "$line"; sleep 0.01; done"#;
+        let mut vm = manipulate!(
+            CommandVm::from(IoProcess::new("sh").arg("-c").arg(script))
+            => .input_translator(|cmd| Ok(Some(cmd.to_string())))
+            => .output_translator(|content| Ok(Output::OTHER { content }))
+        )
+        .launch()
+        .expect("无法启动虚拟机");
+        // 每行约1KB：塞满管道缓冲区，使后续输入滞留于通道中
+        let padding = "#".repeat(1000);
+        for i in 0..N {
+            let comment = format!("{i:04}{padding}");
+            vm.input_cmd(Cmd::REM { comment }).expect("无法输入REM");
+        }
+        vm.input_cmd(Cmd::RES {
+            target: "memory".into(),
+        })
+        .expect("无法输入RES");
+        vm.input_cmd(Cmd::REM {
+            comment: "after".into(),
+        })
+        .expect("无法输入REM");
+        // 收集`RES`之前的输出
+        let mut before_res = vec![];
+        loop {
+            let output = vm.fetch_output().expect("无法拉取输出");
+            match output.raw_content().trim_end() {
+                "RES memo" => break,
+                line => before_res.push(line.to_string()),
+            }
+        }
+        // 越过绝大部分排队中的输入，但不越过此后的输入
+        assert!(before_res.len() < N / 5, "{}", before_res.len());
+        assert!(!before_res.iter().any(|line| line == "REM afte"));
+        let _ = vm.terminate();
+    }
+
+    /// 测试/批量拉取输出
+    /// * 🎯一万行已排队的输出：一次性按序取出，单行转译失败不中断整批
+    /// * 🚩使用系统shell逐行打印数字，无需CIN可执行文件
+    #[test]
+    fn test_drain_outputs() {
+        const N: usize = 10000;
+        // 逐行打印`1..=N`
+        #[cfg(not(windows))]
+        let command = manipulate!(
+            Command::new("sh")
+            => .args(["-c", &format!("seq 1 {N}")])
+        );
+        #[cfg(windows)]
+        let command = manipulate!(
+            Command::new("cmd")
+            => .args(["/C", &format!("for /L %i in (1,1,{N}) do @echo %i")])
+        );
+        // 以`7`结尾的行⇒转译失败
+        let mut vm = manipulate!(
+            CommandVm::from(command)
+            => .output_translator(|line| match line.trim().ends_with('7') {
+                true => Err(anyhow!("不予转译：{line}")),
+                false => Ok(Output::OTHER { content: line }),
+            })
+        )
+        .launch()
+        .expect("无法启动虚拟机");
+        // 批量拉取，直到取得所有行
+        let start = std::time::Instant::now();
+        let mut outputs = vec![];
+        while outputs.len() < N {
+            assert!(start.elapsed().as_secs() < 30, "等待子进程输出超时");
+            outputs.extend(vm.drain_outputs().expect("无法批量拉取输出"));
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(outputs.len(), N);
+        for (i, output) in outputs.iter().enumerate() {
+            let n = (i + 1).to_string();
+            match output {
+                Output::OTHER { content } => assert_eq!(content.trim(), n),
+                Output::ERROR { description } => {
+                    assert!(
+                        n.ends_with('7') && description.contains(&n),
+                        "{description}"
+                    )
+                }
+                _ => panic!("非预期的输出：{output:?}"),
+            }
+        }
+        // 已取空⇒此后仅有一个`TERMINATED`输出
+        let mut rest = vec![];
+        while rest.is_empty() {
+            assert!(start.elapsed().as_secs() < 30, "等待子进程退出超时");
+            rest = vm.drain_outputs().expect("无法批量拉取输出");
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(
+            matches!(rest.as_slice(), [Output::TERMINATED { .. }]),
+            "{rest:?}"
+        );
+        assert!(vm.drain_outputs().expect("无法批量拉取输出").is_empty());
+        let _ = vm.terminate();
+    }
+
+    /// 测试/无法转译的输出行
+    /// * 🎯混入的异常堆栈不中断拉取：产生`ERROR`输出，并留存以供诊断
+    /// * 🎯严格转译⇒直接报错
+    #[test]
+    fn test_untranslated() {
+        let launch = |strict| {
+            manipulate!(
+                echo_vm()
+                => .output_translator(|line| match line.trim_end().split_once(": ") {
+                    Some((_, content)) => Ok(Output::OUT {
+                        content_raw: line.clone(),
+                        narsese: Some(FORMAT_ASCII.parse(content)?),
+                    }),
+                    None => Err(anyhow!("无法识别的输出")),
+                })
+                => .strict_translation(strict)
+            )
+            .launch()
+            .expect("无法启动虚拟机")
+        };
+        let lines = [
+            "OUT: <A --> B>.",
+            "\tat org.opennars.main.Nar.cycle(Nar.java:42)",
+            "OUT: <B --> C>.",
+            "Exception in thread \"main\" java.lang.NullPointerException",
+            "OUT: <A --> C>.",
+        ];
+
+        // 默认：不中断拉取
+        let mut vm = launch(false);
+        for line in lines {
+            vm.input_raw(line).expect("无法原始输入");
+        }
+        let mut outputs = vec![];
+        while outputs.len() < lines.len() {
+            outputs.extend(vm.try_fetch_output().expect("转译失败不应中断拉取"));
+        }
+        for (output, line) in outputs.iter().zip(lines) {
+            match output {
+                Output::OUT { .. } => assert!(line.starts_with("OUT: ")),
+                Output::ERROR { description } => {
+                    assert!(description.contains("无法识别的输出"), "{description}");
+                    assert!(description.contains(line), "{description}");
+                }
+                _ => panic!("非预期的输出：{output:?}"),
+            }
+        }
+        let untranslated = vm
+            .take_untranslated()
+            .into_iter()
+            .map(|line| line.trim_end().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(untranslated, [lines[1], lines[3]]);
+        assert!(vm.take_untranslated().is_empty());
+        // 此后仍正常运行
+        vm.input_raw("OUT: <C --> D>.").expect("无法原始输入");
+        assert!(matches!(vm.fetch_output(), Ok(Output::OUT { .. })));
+        assert!(!vm.is_terminated());
+        let _ = vm.terminate();
+
+        // 严格转译：直接报错
+        let mut vm = launch(true);
+        vm.input_raw(lines[1]).expect("无法原始输入");
+        assert!(vm.fetch_output().is_err());
+        let _ = vm.terminate();
+
+        // 严格转译的批量拉取：逐个报错，不丢失同批次的其它输出
+        let mut vm = launch(true);
+        for line in lines {
+            vm.input_raw(line).expect("无法原始输入");
+        }
+        let start = Instant::now();
+        let (mut outputs, mut errors) = (vec![], 0);
+        while outputs.len() + errors < lines.len() {
+            assert!(start.elapsed() < Duration::from_secs(10), "等待输出超时");
+            match vm.drain_outputs() {
+                Ok(batch) => outputs.extend(batch),
+                Err(..) => errors += 1,
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(errors, 2);
+        let contents = outputs
+            .iter()
+            .map(|output| output.raw_content().trim_end())
+            .collect::<Vec<_>>();
+        assert_eq!(contents, [lines[0], lines[2], lines[4]]);
+        let _ = vm.terminate();
+
+        // 严格转译的批量拉取：报错前新到达的输出也不丢失
+        let mut vm = launch(true);
+        vm.input_raw(lines[0]).expect("无法原始输入");
+        vm.input_raw(lines[1]).expect("无法原始输入");
+        // * 🚩等两行一同到达，使首次拉取「先传出、再留存错误」
+        thread::sleep(Duration::from_millis(100));
+        let start = Instant::now();
+        let mut outputs = vec![];
+        while outputs.is_empty() {
+            assert!(start.elapsed() < Duration::from_secs(10), "等待输出超时");
+            if let Ok(batch) = vm.drain_outputs() {
+                outputs.extend(batch);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        // 在报错之前到达新的输出
+        vm.input_raw(lines[2]).expect("无法原始输入");
+        thread::sleep(Duration::from_millis(100));
+        let mut errors = 0;
+        while outputs.len() < 2 {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "新到达的输出已丢失"
+            );
+            match vm.drain_outputs() {
+                Ok(batch) => outputs.extend(batch),
+                Err(..) => errors += 1,
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(errors, 1);
+        let contents = outputs
+            .iter()
+            .map(|output| output.raw_content().trim_end())
+            .collect::<Vec<_>>();
+        assert_eq!(contents, [lines[0], lines[2]]);
+        let _ = vm.terminate();
+    }
+
+    /// 测试/合并多行输出
+    /// * 🎯OpenNARS的Java异常堆栈⇒恰好一个`ERROR`输出，包含所有行
+    /// * 🎯前后的常规输出不受影响
+    #[test]
+    fn test_coalesce_lines() {
+        use crate::cin_implements::opennars;
+        let mut vm = manipulate!(
+            echo_vm()
+            => .output_translator(opennars::output_translate)
+            => .coalesce_lines(opennars::continues_previous, DEFAULT_COALESCE_TIMEOUT)
+        )
+        .launch()
+        .expect("无法启动虚拟机");
+        let trace = [
+            "Exception in thread \"main\" java.lang.NullPointerException",
+            "\tat org.opennars.control.DerivationContext.doublePremiseTask(DerivationContext.java:263)",
+            "\tat org.opennars.main.Nar.cycle(Nar.java:42)",
+            "Caused by: java.lang.IllegalStateException: concept not found",
+            "\t... 2 more",
+        ];
+        vm.input_raw("OUT: <A --> B>.").expect("无法原始输入");
+        for line in trace {
+            vm.input_raw(line).expect("无法原始输入");
+        }
+        vm.input_raw("OUT: <B --> C>.").expect("无法原始输入");
+        let outputs = [(); 3].map(|_| vm.fetch_output().expect("无法拉取输出"));
+        assert!(matches!(outputs[0], Output::OUT { .. }), "{outputs:?}");
+        assert!(matches!(outputs[2], Output::OUT { .. }), "{outputs:?}");
+        let Output::ERROR { description } = &outputs[1] else {
+            panic!("异常堆栈未被合并为一个ERROR：{outputs:?}");
+        };
+        let lines = description.lines().collect::<Vec<_>>();
+        assert_eq!(lines, trace);
+        // 已取空
+        assert!(vm.drain_outputs().expect("无法批量拉取输出").is_empty());
+        let _ = vm.terminate();
+    }
+
+    /// 测试/辅助输出源
+    /// * 🎯子进程不向标准输出写入结果时，从追加写入的文件中获取输出
+    /// * 🚩分多次追加写入（含不完整的行），按行转译为NAVM输出
+    #[test]
+    fn test_aux_output() {
+        use crate::process_io::IoProcess;
+        use std::{fs, io::Write};
+        // 一个不产生输出的子进程
+        #[cfg(not(windows))]
+        let io_process = IoProcess::new("sh").arg("-c").arg("sleep 10");
+        #[cfg(windows)]
+        let io_process = IoProcess::new("cmd")
+            .arg("/C")
+            .arg("ping -n 11 127.0.0.1 > nul");
+        // 追踪临时文件
+        let path = std::env::temp_dir().join(format!("babelnar_aux_{}.log", std::process::id()));
+        let mut file = fs::File::create(&path).expect("无法创建临时文件");
+        let io_process = io_process.aux_output(fs::File::open(&path).expect("无法打开临时文件"));
+        let mut vm = manipulate!(
+            CommandVm::from(io_process)
+            => .output_translator(|content| Ok(Output::OTHER { content }))
+        )
+        .launch()
+        .expect("无法启动虚拟机");
+        // 分批写入
+        write!(file, "ANSWER: <A --> C>.\nOUT: <B").unwrap();
+        file.flush().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        writeln!(file, " --> D>.").unwrap();
+        file.flush().unwrap();
+        // 按行、按序截获
+        let mut lines = vec![];
+        while lines.len() < 2 {
+            let output = vm.fetch_output().expect("无法拉取输出");
+            lines.push(output.raw_content().trim().to_string());
+        }
+        assert_eq!(lines, ["ANSWER: <A --> C>.", "OUT: <B --> D>."]);
+        let _ = vm.terminate();
+        let _ = fs::remove_file(path);
+    }
+
+    /// 测试/原始输入
+    /// * 🎯原样送达子进程，包括CIN特有的前缀`*`
+    #[test]
+    fn test_input_raw() {
+        let mut vm = echo_vm().launch().expect("无法启动虚拟机");
+        let lines = ["*stats", "/reset", "  *volume=0 "];
+        for line in lines {
+            vm.input_raw(line).expect("无法原始输入");
+        }
+        let mut received = vec![];
+        while received.len() < lines.len() {
+            let output = vm.fetch_output().expect("无法拉取输出");
+            received.push(
+                output
+                    .raw_content()
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string(),
+            );
+        }
+        assert_eq!(received, lines);
+        let _ = vm.terminate();
+    }
+
+    /// 测试/CYC分批
+    /// * 🎯大步数的`CYC`被拆成多批；输入立即返回；后续输入位于最后一批之后
+    #[test]
+    fn test_cyc_pacing() {
+        let interval = Duration::from_millis(100);
+        let mut vm = manipulate!(
+            echo_vm()
+            => .input_translator(|cmd| Ok(Some(cmd.to_string())))
+            => .cyc_pacing(CycPacing { chunk: 50, interval })
+        )
+        .launch()
+        .expect("无法启动虚拟机");
+        let start = Instant::now();
+        vm.input_cmd(Cmd::CYC(120)).expect("无法输入CYC");
+        // 在后台分批输入⇒立即返回
+        assert!(start.elapsed() < interval);
+        // 不超过每批步数⇒不分批
+        vm.input_cmd(Cmd::CYC(7)).expect("无法输入CYC");
+        vm.input_cmd(Cmd::NSE(narsese::lexical_nse_task!(<A --> B>.)))
+            .expect("无法输入NSE");
+        // 原始输入⇒同样位于分批的`CYC`之后
+        vm.input_cmd(Cmd::CYC(60)).expect("无法输入CYC");
+        vm.input_raw("*stats").expect("无法原始输入");
+        let expected = [
+            "CYC 50",
+            "CYC 50",
+            "CYC 20",
+            "CYC 7",
+            "NSE <A --> B>.",
+            "CYC 50",
+            "CYC 10",
+            "*stats",
+        ];
+        let mut received = vec![];
+        while received.len() < expected.len() {
+            let output = vm.fetch_output().expect("无法拉取输出");
+            received.push(
+                output
+                    .raw_content()
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string(),
+            );
+        }
+        assert_eq!(received, expected);
+        // 批间等待
+        assert!(start.elapsed() >= interval * 2);
+        let _ = vm.terminate();
+    }
+
+    /// 测试/子进程自行退出⇒产生`TERMINATED`输出
+    /// * 🎯输出先于终止报告，且终止只报告一次
+    #[test]
+    fn test_exit_terminated() {
+        use crate::process_io::IoProcess;
+        let launch = || {
+            #[cfg(not(windows))]
+            let io_process = IoProcess::new("sh").arg("-c").arg("echo hi; exit 3");
+            #[cfg(windows)]
+            let io_process = IoProcess::new("cmd").arg("/C").arg("echo hi & exit 3");
+            manipulate!(
+                CommandVm::from(io_process)
+                => .output_translator(|content| Ok(Output::OTHER { content }))
+            )
+            .launch()
+            .expect("无法启动虚拟机")
+        };
+        let is_exit = |output: &Output| {
+            matches!(
+                output,
+                Output::TERMINATED { description } if description == "process exited with code 3"
+            )
+        };
+
+        // 阻塞拉取
+        let mut vm = launch();
+        let output = vm.fetch_output().expect("无法拉取输出");
+        assert_eq!(output.raw_content().trim(), "hi");
+        let output = vm.fetch_output().expect("无法拉取输出");
+        assert!(is_exit(&output), "{output:?}");
+        assert!(matches!(vm.status(), VmStatus::Terminated(Err(..))));
+        assert!(vm.fetch_output().is_err());
+        vm.terminate().expect("终止已退出的虚拟机失败");
+
+        // 非阻塞拉取
+        let mut vm = launch();
+        let mut outputs = vec![];
+        while !vm.is_terminated() {
+            outputs.extend(vm.try_fetch_output().expect("无法拉取输出"));
+            std::thread::sleep(EXIT_POLL_INTERVAL);
+        }
+        assert_eq!(outputs.len(), 2, "{outputs:?}");
+        assert!(is_exit(&outputs[1]), "{outputs:?}");
+        assert!(vm.drain_outputs().expect("无法批量拉取输出").is_empty());
+        vm.terminate().expect("终止已退出的虚拟机失败");
+    }
+
+    /// 测试/重复终止
+    /// * 🎯无论子进程仍在运行还是已自行退出，终止都不报错；重复终止为无操作
+    #[test]
+    fn test_terminate_twice() {
+        use crate::process_io::IoProcess;
+        #[cfg(not(windows))]
+        let (running, exiting) = (
+            IoProcess::new("sh").arg("-c").arg("sleep 10"),
+            IoProcess::new("sh").arg("-c").arg("exit 0"),
+        );
+        #[cfg(windows)]
+        let (running, exiting) = (
+            IoProcess::new("cmd")
+                .arg("/C")
+                .arg("ping -n 11 127.0.0.1 > nul"),
+            IoProcess::new("cmd").arg("/C").arg("exit 0"),
+        );
+        for (io_process, wait) in [(running, false), (exiting, true)] {
+            let mut vm = CommandVm::from(io_process)
+                .launch()
+                .expect("无法启动虚拟机");
+            if wait {
+                // 等待子进程自行退出
+                std::thread::sleep(std::time::Duration::from_millis(200));
+            }
+            vm.terminate().expect("首次终止失败");
+            assert!(matches!(vm.status(), VmStatus::Terminated(Ok(()))));
+            vm.terminate().expect("重复终止失败");
+            assert!(matches!(vm.status(), VmStatus::Terminated(Ok(()))));
+        }
+    }
+
+    /// 测试/启动检查
+    /// * 🎯启动即退出⇒报错并附带输出；就绪或超时⇒成功，且不丢失启动时的输出
+    #[test]
+    #[cfg(not(windows))]
+    fn test_startup_check() {
+        use crate::process_io::IoProcess;
+        let launch = |script: &str, ready: Option<fn(&str) -> bool>| {
+            let io_process = IoProcess::new("sh").arg("-c").arg(script);
+            let mut vm = manipulate!(
+                CommandVm::from(io_process)
+                => .output_translator(|content| Ok(Output::OTHER { content }))
+                => .startup_timeout(Duration::from_secs(5))
+            );
+            if let Some(ready) = ready {
+                vm.ready_marker(ready);
+            }
+            vm.launch()
+        };
+
+        let is_ready = |line: &str| line.starts_with("ready");
+        // 未就绪即退出⇒报错，附带退出状态与标准输出
+        let e = launch("echo loading && exit 2", Some(is_ready))
+            .err()
+            .expect("子进程启动即退出，却启动成功");
+        let message = e.to_string();
+        assert!(message.contains("exited with code 2"), "{message}");
+        assert!(message.contains("loading"), "{message}");
+
+        // 就绪标志⇒跳过就绪前的输出，且这些输出不丢失
+        let mut vm = launch("echo loading && echo ready && sleep 10", Some(is_ready))
+            .expect("无法启动虚拟机");
+        let lines = [vm.fetch_output(), vm.fetch_output()].map(|output| {
+            output
+                .expect("无法拉取输出")
+                .raw_content()
+                .trim()
+                .to_string()
+        });
+        assert_eq!(lines, ["loading", "ready"]);
+        vm.terminate().expect("无法终止虚拟机");
+
+        // 超时而仍在运行⇒视作启动成功
+        let mut vm = manipulate!(
+            CommandVm::from(IoProcess::new("sh").arg("-c").arg("sleep 10"))
+            => .startup_timeout(Duration::from_millis(200))
+        )
+        .launch()
+        .expect("静默的子进程应视作启动成功");
+        vm.terminate().expect("无法终止虚拟机");
+    }
+
+    /// 测试/等待CIN安静下来
+    /// * 🎯一阵输出后沉默⇒在安静期后返回，且输出仍留在通道中
+    /// * 🎯持续输出⇒超时
+    #[test]
+    #[cfg(not(windows))]
+    fn test_wait_for_idle() {
+        use crate::process_io::IoProcess;
+        let launch = |script: &str| {
+            manipulate!(
+                CommandVm::from(IoProcess::new("sh").arg("-c").arg(script))
+                => .output_translator(|content| Ok(Output::OTHER { content }))
+            )
+            .launch()
+            .expect("无法启动虚拟机")
+        };
+        let quiet_period = Duration::from_millis(300);
+
+        // 一阵输出，然后沉默
+        let mut vm = launch("for i in 1 2 3 4 5; do echo $i; sleep 0.1; done; sleep 10");
+        let start = Instant::now();
+        let result = vm.wait_for_idle(quiet_period, Duration::from_secs(10));
+        assert_eq!(result.expect("等待出错"), IdleResult::Idle);
+        assert!(start.elapsed() >= quiet_period + Duration::from_millis(300));
+        // 输出未被丢弃
+        let outputs = vm.drain_outputs().expect("无法批量拉取输出");
+        let lines = outputs
+            .iter()
+            .map(|o| o.raw_content().trim())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["1", "2", "3", "4", "5"]);
+        vm.terminate().expect("无法终止虚拟机");
+
+        // 持续输出⇒超时
+        let mut vm = launch("while true; do echo x; sleep 0.05; done");
+        let result = vm.wait_for_idle(quiet_period, Duration::from_millis(800));
+        assert_eq!(result.expect("等待出错"), IdleResult::TimedOut);
+        vm.terminate().expect("无法终止虚拟机");
+    }
 }
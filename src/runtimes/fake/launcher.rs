@@ -0,0 +1,113 @@
+//! 模拟虚拟机（构建者）
+
+use navm::{cmd::Cmd, output::Output};
+
+/// 预设响应的时机
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FakeResponseTiming {
+    /// 输入指令后立即产生输出
+    /// * 📄CIN对输入的回显、报错等
+    Immediate,
+    /// 在下一个`CYC`指令后产生输出
+    /// * 📄推理得出的「回答」「导出」等
+    AfterCycle,
+}
+
+/// 一条预设响应
+/// * 🚩当输入的指令与[`Self::pattern`]相等时，按[`Self::timing`]产生[`Self::outputs`]
+/// * 📌可重复触发：每次匹配都会产生一遍输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeResponse {
+    /// 要匹配的指令
+    pub pattern: Cmd,
+    /// 产生输出的时机
+    pub timing: FakeResponseTiming,
+    /// 产生的输出
+    pub outputs: Vec<Output>,
+}
+
+/// 模拟虚拟机（构建者）
+/// * 🎯配置化构造[`super::FakeVmRuntime`]
+/// * 📌链式操作可以使用[`util::manipulate`]简化
+///
+/// ## 用例
+///
+/// ```
+/// use babel_nar::runtimes::FakeVm;
+/// use nar_dev_utils::manipulate;
+/// use narsese::lexical_nse_task as nse_task;
+/// use navm::{cmd::Cmd, output::Output, vm::{VmLauncher, VmRuntime}};
+///
+/// let mut vm = manipulate!(
+///     FakeVm::new()
+///     // 在下一个`CYC`后回答问题
+///     => .respond_after_cycle(
+///         Cmd::NSE(nse_task!(<A --> C>?)),
+///         [Output::ANSWER { content_raw: "<A --> C>.".into(), narsese: None }],
+///     )
+/// )
+/// .launch()
+/// .unwrap();
+/// vm.input_cmd(Cmd::NSE(nse_task!(<A --> C>?))).unwrap();
+/// // 回显
+/// assert!(matches!(vm.try_fetch_output().unwrap(), Some(Output::IN { .. })));
+/// // 步进前不会回答
+/// assert!(vm.try_fetch_output().unwrap().is_none());
+/// vm.input_cmd(Cmd::CYC(1)).unwrap();
+/// assert!(matches!(vm.try_fetch_output().unwrap(), Some(Output::ANSWER { .. })));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeVm {
+    /// 预设响应
+    pub(super) responses: Vec<FakeResponse>,
+
+    /// 是否回显输入的Narsese
+    /// * 📌默认开启：大多数CIN都会以`IN`输出回显输入
+    pub(super) echo_input: bool,
+}
+
+impl FakeVm {
+    /// 构造函数
+    /// * 🚩无预设响应，回显输入
+    pub fn new() -> Self {
+        Self {
+            responses: vec![],
+            echo_input: true,
+        }
+    }
+
+    /// 配置/是否回显输入
+    pub fn echo_input(&mut self, echo_input: bool) {
+        self.echo_input = echo_input;
+    }
+
+    /// 配置/添加预设响应
+    pub fn respond_with(
+        &mut self,
+        pattern: Cmd,
+        timing: FakeResponseTiming,
+        outputs: impl IntoIterator<Item = Output>,
+    ) {
+        self.responses.push(FakeResponse {
+            pattern,
+            timing,
+            outputs: outputs.into_iter().collect(),
+        });
+    }
+
+    /// 配置/添加「立即产生」的预设响应
+    pub fn respond(&mut self, pattern: Cmd, outputs: impl IntoIterator<Item = Output>) {
+        self.respond_with(pattern, FakeResponseTiming::Immediate, outputs)
+    }
+
+    /// 配置/添加「在下一个`CYC`后产生」的预设响应
+    pub fn respond_after_cycle(&mut self, pattern: Cmd, outputs: impl IntoIterator<Item = Output>) {
+        self.respond_with(pattern, FakeResponseTiming::AfterCycle, outputs)
+    }
+}
+
+impl Default for FakeVm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
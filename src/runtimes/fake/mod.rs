@@ -0,0 +1,11 @@
+//! 基于「预设脚本」的「模拟运行时」
+//! * 🎯在没有任何CIN可执行文件的环境下测试NAVM相关逻辑
+//!   * 📄NAL测试工具：`put_nal`、输出预期……
+//! * 🚩不启动任何子进程：按照「指令⇒输出」的预设脚本产生输出
+
+util::pub_mod_and_pub_use! {
+    // 启动器
+    launcher
+    // 运行时
+    runtime
+}
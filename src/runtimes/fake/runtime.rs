@@ -0,0 +1,200 @@
+//! 模拟虚拟机 运行时
+//! * ✨按预设脚本产生输出
+//!   * 📌核心流程：NAVM指令[`Cmd`] >>> 匹配预设响应 >>> 输出队列 >>> NAVM输出[`Output`]
+
+use super::{FakeResponse, FakeResponseTiming, FakeVm};
+use crate::runtimes::VmRuntimeBoost;
+use anyhow::{anyhow, Result};
+use narsese::lexical::Narsese;
+use navm::{
+    cmd::Cmd,
+    output::Output,
+    vm::{VmLauncher, VmRuntime, VmStatus},
+};
+use std::{collections::VecDeque, time::Instant};
+
+/// 模拟虚拟机运行时
+/// * 🎯不依赖外部程序，按预设脚本产生输出
+/// * ⚠️不会自发产生输出：所有输出都由输入的指令触发
+pub struct FakeVmRuntime {
+    /// 预设响应
+    responses: Vec<FakeResponse>,
+
+    /// 是否回显输入的Narsese
+    echo_input: bool,
+
+    /// 待拉取的输出，及其「产生时间」
+    /// * 🎯模拟CIN「收到输出」的时刻：见[`VmRuntimeBoost::drain_outputs_timed`]
+    outputs: VecDeque<(Output, Instant)>,
+
+    /// 等待下一个`CYC`的输出
+    pending: Vec<Output>,
+
+    /// 用于指示的「状态」变量
+    status: VmStatus,
+}
+
+impl FakeVmRuntime {
+    /// 根据输出更新自身状态
+    /// * 🚩同[`crate::runtimes::CommandVmRuntime`]：将「TERMINATED」输出视作「意外终止」
+    fn update_status_from(&mut self, output: &Output) {
+        if let Output::TERMINATED { description } = output {
+            self.status = VmStatus::Terminated(Err(anyhow!(description.clone())));
+        }
+    }
+}
+
+impl VmRuntime for FakeVmRuntime {
+    fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        // 已终止⇒不再接收指令
+        if self.is_terminated() {
+            return Err(anyhow!("模拟虚拟机已终止，无法输入指令：{cmd}"));
+        }
+        let now = Instant::now();
+        // 回显
+        if let (true, Cmd::NSE(task)) = (self.echo_input, &cmd) {
+            let echo = Output::IN {
+                content: cmd.tail(),
+                narsese: Some(Narsese::Task(task.clone())),
+            };
+            self.outputs.push_back((echo, now));
+        }
+        // 步进⇒释放等待中的输出
+        if let Cmd::CYC(..) = cmd {
+            let released = self.pending.drain(..).map(|output| (output, now));
+            self.outputs.extend(released);
+        }
+        // 匹配预设响应
+        for response in self.responses.iter().filter(|r| r.pattern == cmd) {
+            let outputs = response.outputs.iter().cloned();
+            match response.timing {
+                FakeResponseTiming::Immediate => {
+                    self.outputs.extend(outputs.map(|output| (output, now)))
+                }
+                FakeResponseTiming::AfterCycle => self.pending.extend(outputs),
+            }
+        }
+        Ok(())
+    }
+
+    /// 拉取一个输出
+    /// * ⚠️真实的CIN在「无输出」时会阻塞，而模拟运行时不会再有新输出
+    ///   * 🚩此时视作「意外终止」并返回错误，避免调用方死锁
+    fn fetch_output(&mut self) -> Result<Output> {
+        match self.outputs.pop_front() {
+            Some((output, _)) => {
+                self.update_status_from(&output);
+                Ok(output)
+            }
+            None => {
+                let message = "模拟虚拟机已无输出，拉取将永久阻塞";
+                self.status = VmStatus::Terminated(Err(anyhow!(message)));
+                Err(anyhow!(message))
+            }
+        }
+    }
+
+    fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        let output = self.outputs.pop_front().map(|(output, _)| output);
+        if let Some(output) = &output {
+            self.update_status_from(output);
+        }
+        Ok(output)
+    }
+
+    fn status(&self) -> &VmStatus {
+        &self.status
+    }
+
+    fn terminate(&mut self) -> Result<()> {
+        self.status = VmStatus::Terminated(Ok(()));
+        Ok(())
+    }
+}
+
+/// 增强功能
+impl VmRuntimeBoost for FakeVmRuntime {
+    /// 拉取所有输出，并附带其「产生时间」
+    /// * 🚩时间为输出进入队列的时刻：不受拉取时刻影响
+    fn drain_outputs_timed(&mut self) -> Result<Vec<(Output, Instant)>> {
+        let outputs = self.outputs.drain(..).collect::<Vec<_>>();
+        for (output, _) in &outputs {
+            self.update_status_from(output);
+        }
+        Ok(outputs)
+    }
+
+    /// 原始输入
+    /// * 🚩不匹配预设响应：开启回显时，原样回显为（不含Narsese的）`IN`输出
+    fn input_raw(&mut self, line: &str) -> Result<()> {
+        if self.is_terminated() {
+            return Err(anyhow!("模拟虚拟机已终止，无法输入：{line}"));
+        }
+        if self.echo_input {
+            let echo = Output::IN {
+                content: line.to_string(),
+                narsese: None,
+            };
+            self.outputs.push_back((echo, Instant::now()));
+        }
+        Ok(())
+    }
+}
+
+/// 构建功能：启动模拟虚拟机
+impl VmLauncher for FakeVm {
+    type Runtime = FakeVmRuntime;
+    fn launch(self) -> Result<FakeVmRuntime> {
+        Ok(FakeVmRuntime {
+            responses: self.responses,
+            echo_input: self.echo_input,
+            outputs: VecDeque::new(),
+            pending: vec![],
+            // 状态：正在运行
+            status: VmStatus::Running,
+        })
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::command_vm::tests::test_simple_answer;
+    use nar_dev_utils::manipulate;
+    use narsese::{conversion::string::impl_lexical::shortcuts::*, lexical::Task};
+
+    /// 「简单演绎」的模拟CIN
+    /// * 🎯以`<A --> B>.` `<B --> C>.` `<A --> C>?`为输入，在下一个`CYC`后回答`<A --> C>.`
+    fn fake_simple_deduction() -> FakeVmRuntime {
+        let answer: Task = nse_task!(<A --> C>. %1.0;0.81%);
+        manipulate!(
+            FakeVm::new()
+            => .respond_after_cycle(
+                Cmd::NSE(nse_task!(<A --> C>?)),
+                [Output::ANSWER {
+                    content_raw: "<A --> C>. %1.00;0.81%".into(),
+                    narsese: Some(Narsese::Task(answer)),
+                }],
+            )
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败")
+    }
+
+    /// 简单回答
+    /// * 🎯无需任何CIN，运行通用测试
+    #[test]
+    fn test() {
+        test_simple_answer(fake_simple_deduction())
+    }
+
+    /// 终止
+    #[test]
+    fn test_terminate() {
+        let mut vm = fake_simple_deduction();
+        vm.terminate().expect("无法终止虚拟机");
+        assert!(vm.is_terminated());
+        assert!(vm.input_cmd(Cmd::CYC(1)).is_err());
+    }
+}
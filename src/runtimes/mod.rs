@@ -5,4 +5,19 @@
 util::mods! {
     // 命令行运行时
     pub pub command_vm;
+
+    // 模拟运行时
+    pub pub fake;
+
+    // Websocket运行时
+    "websocket_vm" => pub pub websocket_vm;
+
+    // 运行时增强
+    pub pub vm_runtime_boost;
+
+    // 功能支持声明
+    pub pub capabilities;
+
+    // 异步运行时
+    "async_runtime" => pub pub async_runtime;
 }
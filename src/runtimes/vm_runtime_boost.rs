@@ -0,0 +1,153 @@
+//! 对NAVM运行时的功能增强
+//! * 🎯在[`VmRuntime`]之上，提供可被具体运行时优化的通用功能
+//! * 📌[`VmRuntime`]由外部库定义，故在此以「扩展特征」的形式提供
+
+use anyhow::{anyhow, Result};
+use navm::{output::Output, vm::VmRuntime};
+use std::{
+    fmt,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// NAVM运行时的增强特征
+/// * 🚩所有方法都有默认实现：只需`impl VmRuntimeBoost for XXX {}`即可使用
+/// * ✨具体运行时可覆盖默认实现，以提升性能
+pub trait VmRuntimeBoost: VmRuntime {
+    /// 拉取当前所有待处理的输出
+    /// * 🎯替代调用方各自的`while let Some(o) = vm.try_fetch_output()?`循环
+    /// * 🚩默认实现：逐个调用[`VmRuntime::try_fetch_output`]，直到没有输出
+    /// * 📌不会阻塞：无输出⇒返回空数组
+    fn drain_outputs(&mut self) -> Result<Vec<Output>> {
+        let mut outputs = vec![];
+        while let Some(output) = self.try_fetch_output()? {
+            outputs.push(output);
+        }
+        Ok(outputs)
+    }
+
+    /// 拉取当前所有待处理的输出，并附带各输出的「收到时间」
+    /// * 🎯测量输出延迟：以输出到达的时刻为准，而非被拉取的时刻
+    ///   * 📄NAL中带「最大延迟」的`''expect-contains`
+    /// * 🚩默认实现：不记录收到时间⇒以拉取的时刻代替
+    fn drain_outputs_timed(&mut self) -> Result<Vec<(Output, Instant)>> {
+        let now = Instant::now();
+        let outputs = self.drain_outputs()?;
+        Ok(outputs.into_iter().map(|output| (output, now)).collect())
+    }
+
+    /// 向CIN直接输入一行原始内容
+    /// * 🎯绕过NAVM指令与输入转译器，使用CIN特有的命令
+    ///   * 📄ONA的`*stats`、PyNARS的`/reset`
+    /// * 🚩默认实现：不支持⇒返回错误
+    /// * ⚠️输入内容原样送达，不做任何检查
+    fn input_raw(&mut self, line: &str) -> Result<()> {
+        Err(anyhow!("该运行时不支持原始输入：{line:?}"))
+    }
+
+    /// 置入`CYC steps`后，CIN将报告「步进完成」的次数
+    /// * 🎯分批输入的`CYC`：每批各有一个回执
+    /// * 🚩默认实现：不分批⇒一次
+    fn cyc_ack_count(&self, _steps: usize) -> usize {
+        1
+    }
+
+    /// 等待所有已置入的输入送达CIN
+    /// * 🎯后台分批输入的`CYC`：送达之前，CIN既不会安静下来，也不会报告全部回执
+    /// * 🚩默认实现：没有后台输入⇒直接返回
+    fn flush_inputs(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// 获取「从外部终止CIN」的句柄
+    /// * 🎯运行时正被其它线程占用（如阻塞于`''await`）时，仍能终止CIN
+    ///   * 📄NAL测试的看门狗：超时⇒直接终止CIN，而非等待阻塞的输入返回
+    /// * 🚩默认实现：不支持⇒[`None`]
+    fn kill_handle(&self) -> Option<KillHandle> {
+        None
+    }
+}
+
+/// 从外部终止CIN的句柄
+/// * 🚩可跨线程传递、多次调用
+/// * 📌只负责终止CIN：运行时自身的清理仍由其持有者完成
+#[derive(Clone)]
+pub struct KillHandle(Arc<dyn Fn() + Send + Sync>);
+
+impl KillHandle {
+    /// 从「终止函数」构造
+    pub fn new(kill: impl Fn() + Send + Sync + 'static) -> Self {
+        Self(Arc::new(kill))
+    }
+
+    /// 终止CIN
+    pub fn kill(&self) {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for KillHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("KillHandle").finish_non_exhaustive()
+    }
+}
+
+/// 「等待CIN安静下来」的结果
+/// * 🎯[`CommandVmRuntime::wait_for_idle`](super::CommandVmRuntime::wait_for_idle)、NAL中的`''await-idle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleResult {
+    /// 已在「安静期」内没有新的输出
+    Idle,
+    /// 超出最长等待时间，CIN仍未安静下来
+    TimedOut,
+}
+
+/// 「等待CIN安静下来」时，一次探测的结果
+/// * 🎯由调用方决定「何为活动」：观察输出时间、拉取输出……
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleProbe {
+    /// 自上次探测以来有新的活动
+    Active,
+    /// 自上次探测以来没有新的活动
+    Quiet,
+    /// 不会再有活动：如CIN已终止
+    Closed,
+}
+
+/// 「等待CIN安静下来」时，探测的间隔
+/// * 📌亦用于其它「轮询等待输出」的场合
+pub const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 等待CIN安静下来
+/// * 🎯统一「安静」的判定：[`CommandVmRuntime::wait_for_idle`](super::CommandVmRuntime::wait_for_idle)与NAL中的`''await-idle`共用
+/// * 🚩自调用时起计时，每探测到活动便重新计时
+///   * 📌刚输入的指令尚未被响应时，不会立即返回
+/// * 🚩连续`quiet_period`没有活动⇒[`IdleResult::Idle`]
+///   * 📌[`IdleProbe::Closed`]⇒不会再有活动，亦算安静
+/// * 🚩超出`max_wait`⇒[`IdleResult::TimedOut`]
+/// * 📌探测出错⇒立即上抛
+pub fn wait_until_idle(
+    quiet_period: Duration,
+    max_wait: Duration,
+    mut probe: impl FnMut() -> Result<IdleProbe>,
+) -> Result<IdleResult> {
+    let start = Instant::now();
+    let mut last_activity = start;
+    loop {
+        let probed = probe()?;
+        let now = Instant::now();
+        match probed {
+            IdleProbe::Closed => return Ok(IdleResult::Idle),
+            IdleProbe::Active => last_activity = now,
+            IdleProbe::Quiet => {}
+        }
+        if now >= last_activity + quiet_period {
+            return Ok(IdleResult::Idle);
+        }
+        if now >= start + max_wait {
+            return Ok(IdleResult::TimedOut);
+        }
+        thread::sleep(IDLE_POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,83 @@
+//! Websocket虚拟机（构建者）
+
+use crate::runtimes::{InputTranslator, IoTranslators, OutputTranslator};
+use anyhow::Result;
+use navm::{cmd::Cmd, output::Output};
+use std::time::Duration;
+
+/// 默认的连接超时时间
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Websocket虚拟机（构建者）
+/// * 🎯配置化构造[`super::WebsocketVmRuntime`]
+/// * 🚩有关「连接」的流程，放在「虚拟机运行时」[`super::runtime`]中
+/// * 📌链式操作可以使用[`util::manipulate`]简化
+pub struct WebsocketVm {
+    /// 服务端地址
+    /// * 📄`localhost:8765`、`[::1]:8765`
+    /// * 🚩不含`ws://`前缀：连接时添加
+    pub(super) address: String,
+
+    /// [`Cmd`]→消息 转译器
+    pub(super) input_translator: Option<Box<InputTranslator>>,
+
+    /// 消息→[`Output`]转译器
+    pub(super) output_translator: Option<Box<OutputTranslator>>,
+
+    /// 连接断开时是否自动重连
+    /// * 🚩重连失败⇒视作终止
+    pub(super) auto_reconnect: bool,
+
+    /// 连接超时时间
+    pub(super) connect_timeout: Duration,
+}
+
+impl WebsocketVm {
+    /// 构造函数
+    /// * 🚩接收一个服务端地址：`主机地址:连接端口`
+    ///   * 📌IPv6主机地址需带方括号：`[::1]:8765`
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            input_translator: None,
+            output_translator: None,
+            auto_reconnect: false,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+        }
+    }
+
+    /// 配置/输入转译器
+    pub fn input_translator(
+        &mut self,
+        translator: impl Fn(Cmd) -> Result<Option<String>> + Send + Sync + 'static,
+    ) {
+        self.input_translator = Some(Box::new(translator));
+    }
+
+    /// 配置/输出转译器
+    pub fn output_translator(
+        &mut self,
+        translator: impl Fn(String) -> Result<Output> + Send + Sync + 'static,
+    ) {
+        self.output_translator = Some(Box::new(translator));
+    }
+
+    /// 配置/输入输出转译器组
+    pub fn translators(&mut self, translators: impl Into<IoTranslators>) {
+        let translators = translators.into();
+        self.input_translator = Some(translators.input_translator);
+        self.output_translator = Some(translators.output_translator);
+    }
+
+    /// 配置/连接断开时是否自动重连
+    /// * 🎯服务端重启、网络波动时保持运行
+    pub fn auto_reconnect(&mut self, auto_reconnect: bool) {
+        self.auto_reconnect = auto_reconnect;
+    }
+
+    /// 配置/连接超时时间
+    /// * 🚩超时仍未连上⇒启动（或重连）失败
+    pub fn connect_timeout(&mut self, timeout: Duration) {
+        self.connect_timeout = timeout;
+    }
+}
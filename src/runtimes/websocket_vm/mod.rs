@@ -0,0 +1,11 @@
+//! 基于「Websocket通信」与「IO转译器」的「Websocket运行时」
+//! * 🎯作为客户端，连接到已在运行的CIN
+//!   * 📄BabelNAR.jl等在Websocket服务端后运行的CIN
+//! * 🚩不启动任何子进程：指令与输出均以Websocket文本消息收发
+
+util::pub_mod_and_pub_use! {
+    // 启动器
+    launcher
+    // 运行时
+    runtime
+}
@@ -0,0 +1,480 @@
+//! Websocket虚拟机 运行时
+//! * ✨核心内容
+//!   * ⇄ 基于「Websocket通信」的消息互转
+//!     * 📌核心IO流程：
+//!       1. NAVM指令[`Cmd`] >>> 文本消息 >>> 服务端
+//!       2. 服务端 >>> 文本消息 >>> NAVM输出[`Output`]
+//!     * 🚩实现方式：两处转译器 + 后台的Websocket事件循环线程
+
+use super::WebsocketVm;
+use crate::runtimes::{
+    default_input_translator, default_output_translator, InputTranslator, OutputTranslator,
+    VmRuntimeBoost,
+};
+use anyhow::{anyhow, Result};
+use navm::{
+    cmd::Cmd,
+    output::Output,
+    vm::{VmLauncher, VmRuntime, VmStatus},
+};
+use std::{
+    sync::{
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use ws::{CloseCode, Handler, Handshake, Message};
+
+/// 等待输出时，检查「是否已终止」的间隔
+const RECV_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 自动重连的最大尝试次数
+pub const RECONNECT_ATTEMPTS: usize = 5;
+
+/// 自动重连的间隔
+pub const RECONNECT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 连接事件
+/// * 🚩由后台的Websocket事件循环线程传出
+enum WsEvent {
+    /// 连接已建立 | 附带用于发送消息的[`ws::Sender`]
+    Opened(ws::Sender),
+    /// 收到文本消息
+    Message(String),
+    /// 连接已断开 | 附带原因
+    Closed(String),
+}
+
+/// 客户端连接处理者
+/// * 🚩将所有事件转发到通道中
+struct ClientHandler {
+    out: ws::Sender,
+    events: Sender<WsEvent>,
+}
+
+impl Handler for ClientHandler {
+    fn on_open(&mut self, _: Handshake) -> ws::Result<()> {
+        let _ = self.events.send(WsEvent::Opened(self.out.clone()));
+        Ok(())
+    }
+
+    /// 收到消息
+    /// * 🚩只接收文本消息：二进制消息无法转译，忽略
+    fn on_message(&mut self, msg: Message) -> ws::Result<()> {
+        if let Message::Text(text) = msg {
+            let _ = self.events.send(WsEvent::Message(text));
+        }
+        Ok(())
+    }
+
+    fn on_close(&mut self, code: CloseCode, reason: &str) {
+        let _ = self.events.send(WsEvent::Closed(format!(
+            "Websocket connection closed ({code:?}) {reason}"
+        )));
+    }
+
+    /// 连接错误
+    /// * 🚩转为「连接已断开」，而非打印到标准输出
+    fn on_error(&mut self, err: ws::Error) {
+        let _ = self
+            .events
+            .send(WsEvent::Closed(format!("Websocket error: {err}")));
+    }
+}
+
+/// 一次Websocket连接
+/// * 🚩后台线程运行事件循环；销毁时关闭事件循环
+struct WsConnection {
+    /// 用于发送消息
+    sender: ws::Sender,
+    /// 连接事件
+    /// * 🚩使用[`Mutex`]包裹：[`Receiver`]不可在线程间共享，而运行时需要[`Sync`]
+    ///   * 📌总是经由`&mut self`访问：使用[`Mutex::get_mut`]，无需真正加锁
+    events: Mutex<Receiver<WsEvent>>,
+}
+
+impl WsConnection {
+    /// 连接到服务端
+    /// * 🚩在超时时间内等待连接建立
+    /// * ⚠️连接失败、超时⇒返回错误
+    fn open(address: &str, timeout: Duration) -> Result<Self> {
+        let url = format!("ws://{address}");
+        let (tx, events) = channel();
+        let events_tx = tx.clone();
+        thread::spawn(move || {
+            let result = ws::connect(url, |out| ClientHandler {
+                out,
+                events: events_tx.clone(),
+            });
+            // 事件循环结束⇒连接已断开 | 连接建立前的失败亦在此报告
+            let reason = match result {
+                Ok(..) => "Websocket event loop finished".to_string(),
+                Err(e) => format!("Websocket error: {e}"),
+            };
+            let _ = tx.send(WsEvent::Closed(reason));
+        });
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match events.recv_timeout(remaining) {
+                Ok(WsEvent::Opened(sender)) => {
+                    let events = Mutex::new(events);
+                    return Ok(Self { sender, events });
+                }
+                Ok(WsEvent::Closed(reason)) => {
+                    return Err(anyhow!("无法连接到Websocket服务端{address}：{reason}"))
+                }
+                // 连接建立前不会有消息
+                Ok(WsEvent::Message(..)) => {}
+                Err(..) => return Err(anyhow!("连接Websocket服务端{address}超时")),
+            }
+        }
+    }
+
+    /// 获取连接事件的接收端
+    fn events(&mut self) -> Result<&mut Receiver<WsEvent>> {
+        self.events
+            .get_mut()
+            .map_err(|e| anyhow!("Websocket连接事件通道已损坏：{e}"))
+    }
+}
+
+impl Drop for WsConnection {
+    /// 关闭后台的事件循环
+    fn drop(&mut self) {
+        let _ = self.sender.shutdown();
+    }
+}
+
+/// Websocket虚拟机运行时
+/// * 🎯封装「Websocket通信」逻辑
+pub struct WebsocketVmRuntime {
+    /// 服务端地址 | 用于重连
+    address: String,
+
+    /// 当前的连接
+    /// * 🚩已终止⇒[`None`]
+    connection: Option<WsConnection>,
+
+    /// [`Cmd`]→消息 转译器
+    input_translator: Box<InputTranslator>,
+
+    /// 消息→[`Output`]转译器
+    output_translator: Box<OutputTranslator>,
+
+    /// 连接断开时是否自动重连
+    auto_reconnect: bool,
+
+    /// 连接超时时间 | 用于重连
+    connect_timeout: Duration,
+
+    /// 用于指示的「状态」变量
+    status: VmStatus,
+}
+
+impl VmRuntime for WebsocketVmRuntime {
+    fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+        // 尝试转译
+        // * 🚩转译器返回`None`⇒有意不输入，提前返回
+        let input = match (self.input_translator)(cmd)? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        self.send(input)
+    }
+
+    /// 拉取一个输出
+    /// * 🚩等待消息的同时，定期检查是否已终止
+    ///   * 📌连接断开⇒（自动重连失败后）产生一个`TERMINATED`输出（仅一次）
+    fn fetch_output(&mut self) -> Result<Output> {
+        loop {
+            let connection = self
+                .connection
+                .as_mut()
+                .ok_or(anyhow!("Websocket连接已关闭，无法再拉取输出"))?;
+            let event = match connection.events()?.recv_timeout(RECV_POLL_INTERVAL) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => {
+                    WsEvent::Closed("Websocket event loop exited".into())
+                }
+            };
+            if let Some(output) = self.handle_event(event)? {
+                return Ok(output);
+            }
+        }
+    }
+
+    fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+        loop {
+            let Some(connection) = &mut self.connection else {
+                return Ok(None);
+            };
+            let event = match connection.events()?.try_recv() {
+                Ok(event) => event,
+                Err(TryRecvError::Empty) => return Ok(None),
+                Err(TryRecvError::Disconnected) => {
+                    WsEvent::Closed("Websocket event loop exited".into())
+                }
+            };
+            if let Some(output) = self.handle_event(event)? {
+                return Ok(Some(output));
+            }
+        }
+    }
+
+    fn status(&self) -> &VmStatus {
+        &self.status
+    }
+
+    /// 终止
+    /// * 🚩仅关闭连接：服务端后的CIN不由自身管理，不发送`EXI`
+    fn terminate(&mut self) -> Result<()> {
+        if let Some(connection) = self.connection.take() {
+            let _ = connection.sender.close(CloseCode::Normal);
+        }
+        if !self.is_terminated() {
+            self.status = VmStatus::Terminated(Ok(()));
+        }
+        Ok(())
+    }
+}
+
+/// 增强功能
+impl VmRuntimeBoost for WebsocketVmRuntime {
+    /// 直接发送一条文本消息
+    /// * 🚩不经过「输入转译器」
+    fn input_raw(&mut self, line: &str) -> Result<()> {
+        self.send(line.to_string())
+    }
+}
+
+impl WebsocketVmRuntime {
+    /// 发送一条文本消息
+    /// * 🚩发送失败且允许自动重连⇒重连后再发送一次
+    fn send(&mut self, message: String) -> Result<()> {
+        let connection = self
+            .connection
+            .as_ref()
+            .ok_or(anyhow!("Websocket连接已关闭，无法发送消息：{message:?}"))?;
+        match connection.sender.send(message.as_str()) {
+            Ok(..) => Ok(()),
+            Err(e) if !self.auto_reconnect => Err(anyhow!("Websocket消息发送失败：{e}")),
+            Err(..) => {
+                self.reconnect()?;
+                self.send(message)
+            }
+        }
+    }
+
+    /// 处理一个连接事件
+    /// * 🚩消息⇒转译为输出
+    /// * 🚩断开⇒自动重连（`INFO`输出）或终止（`TERMINATED`输出）
+    fn handle_event(&mut self, event: WsEvent) -> Result<Option<Output>> {
+        match event {
+            WsEvent::Message(text) => {
+                let output = (self.output_translator)(text)?;
+                self.update_status_from(&output);
+                Ok(Some(output))
+            }
+            WsEvent::Opened(..) => Ok(None),
+            WsEvent::Closed(reason) => self.on_closed(reason).map(Some),
+        }
+    }
+
+    /// 连接断开
+    /// * 🚩允许自动重连⇒尝试重连：成功⇒`INFO`输出
+    /// * 🚩否则（或重连失败）⇒`TERMINATED`输出，并更新自身状态
+    fn on_closed(&mut self, reason: String) -> Result<Output> {
+        let description = match self.auto_reconnect {
+            true => match self.reconnect() {
+                Ok(..) => {
+                    return Ok(Output::INFO {
+                        message: format!("reconnected to {} after: {reason}", self.address),
+                    })
+                }
+                Err(e) => format!("{reason}; {e}"),
+            },
+            false => reason,
+        };
+        self.connection = None;
+        let output = Output::TERMINATED { description };
+        self.update_status_from(&output);
+        Ok(output)
+    }
+
+    /// 重新连接
+    /// * 🚩至多尝试[`RECONNECT_ATTEMPTS`]次，每次间隔[`RECONNECT_INTERVAL`]
+    fn reconnect(&mut self) -> Result<()> {
+        // 先关闭旧连接
+        self.connection = None;
+        let mut last_error = anyhow!("未尝试重连");
+        for _ in 0..RECONNECT_ATTEMPTS {
+            thread::sleep(RECONNECT_INTERVAL);
+            match WsConnection::open(&self.address, self.connect_timeout) {
+                Ok(connection) => {
+                    self.connection = Some(connection);
+                    return Ok(());
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(anyhow!("重连{RECONNECT_ATTEMPTS}次均失败：{last_error}"))
+    }
+
+    /// 根据输出更新自身状态
+    /// * 🚩同[`crate::runtimes::CommandVmRuntime`]：将「TERMINATED」输出视作「意外终止」
+    fn update_status_from(&mut self, output: &Output) {
+        if let Output::TERMINATED { description } = output {
+            self.status = VmStatus::Terminated(Err(anyhow!(description.clone())));
+        }
+    }
+}
+
+/// 构建功能：连接到服务端
+impl VmLauncher for WebsocketVm {
+    type Runtime = WebsocketVmRuntime;
+    fn launch(self) -> Result<WebsocketVmRuntime> {
+        let connection = WsConnection::open(&self.address, self.connect_timeout)?;
+        Ok(WebsocketVmRuntime {
+            address: self.address,
+            connection: Some(connection),
+            input_translator: self.input_translator.unwrap_or(default_input_translator()),
+            output_translator: self
+                .output_translator
+                .unwrap_or(default_output_translator()),
+            auto_reconnect: self.auto_reconnect,
+            connect_timeout: self.connect_timeout,
+            status: VmStatus::Running,
+        })
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::{default_input_translate, IoTranslators};
+    use nar_dev_utils::manipulate;
+    use narsese::lexical_nse_task as nse_task;
+
+    /// 启动一个回传服务端
+    /// * 🚩原样回传收到的消息；收到`CLOSE`⇒断开该连接
+    /// * 🚩绑定端口`0`：由系统分配空闲端口，避免并行测试间的冲突
+    /// * ⚙️返回实际绑定的端口
+    #[allow(clippy::result_large_err)]
+    fn spawn_echo_server() -> u16 {
+        let server = ws::WebSocket::new(|out: ws::Sender| {
+            move |msg: Message| match msg.as_text()? {
+                "CLOSE" => out.close(CloseCode::Away),
+                _ => out.send(msg),
+            }
+        })
+        .and_then(|server| server.bind("127.0.0.1:0"))
+        .expect("无法启动回传服务端");
+        let port = server.local_addr().expect("无法获取服务端地址").port();
+        thread::spawn(move || server.run());
+        port
+    }
+
+    /// 获取一个无服务端的端口
+    /// * 🚩绑定端口`0`后立即释放
+    fn unused_port() -> u16 {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("无法绑定端口");
+        listener.local_addr().expect("无法获取地址").port()
+    }
+
+    /// 测试用输出转译器
+    /// * 📄`NSE <A --> B>.` ⇒ `IN`；`ANSWER <A --> B>.` ⇒ `ANSWER`
+    fn output_translate(content: String) -> Result<Output> {
+        Ok(match content.split_once(' ') {
+            Some(("NSE", narsese)) => Output::IN {
+                content: narsese.into(),
+                narsese: None,
+            },
+            Some(("ANSWER", narsese)) => Output::ANSWER {
+                content_raw: narsese.into(),
+                narsese: None,
+            },
+            _ => Output::OTHER { content },
+        })
+    }
+
+    /// 连接到本地服务端
+    fn launch(port: u16, auto_reconnect: bool) -> Result<WebsocketVmRuntime> {
+        manipulate!(
+            WebsocketVm::new(format!("127.0.0.1:{port}"))
+            => .translators(IoTranslators::new(default_input_translate, output_translate))
+            => .auto_reconnect(auto_reconnect)
+            => .connect_timeout(Duration::from_secs(2))
+        )
+        .launch()
+    }
+
+    /// 发送指令，并检查回传的NSE与ANSWER
+    fn assert_round_trip(vm: &mut WebsocketVmRuntime) -> Result<()> {
+        vm.input_cmd(Cmd::NSE(nse_task!(<A --> B>.)))?;
+        assert_eq!(
+            vm.fetch_output()?,
+            Output::IN {
+                content: "<A --> B>.".into(),
+                narsese: None
+            }
+        );
+        vm.input_raw("ANSWER <A --> B>.")?;
+        assert_eq!(
+            vm.fetch_output()?,
+            Output::ANSWER {
+                content_raw: "<A --> B>.".into(),
+                narsese: None
+            }
+        );
+        Ok(())
+    }
+
+    /// 测试/消息往返
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let port = spawn_echo_server();
+        let mut vm = launch(port, false)?;
+        assert_round_trip(&mut vm)?;
+        assert!(vm.try_fetch_output()?.is_none());
+        // 终止⇒不再可用
+        vm.terminate()?;
+        assert!(matches!(vm.status(), VmStatus::Terminated(Ok(..))));
+        assert!(vm.input_raw("ANSWER <A --> B>.").is_err());
+        Ok(())
+    }
+
+    /// 测试/连接断开⇒终止
+    #[test]
+    fn test_closed_terminates() -> Result<()> {
+        let port = spawn_echo_server();
+        let mut vm = launch(port, false)?;
+        vm.input_raw("CLOSE")?;
+        assert!(matches!(vm.fetch_output()?, Output::TERMINATED { .. }));
+        assert!(vm.is_terminated());
+        assert!(vm.input_cmd(Cmd::CYC(1)).is_err());
+        Ok(())
+    }
+
+    /// 测试/连接断开⇒自动重连
+    #[test]
+    fn test_auto_reconnect() -> Result<()> {
+        let port = spawn_echo_server();
+        let mut vm = launch(port, true)?;
+        vm.input_raw("CLOSE")?;
+        assert!(matches!(vm.fetch_output()?, Output::INFO { .. }));
+        assert!(!vm.is_terminated());
+        assert_round_trip(&mut vm)?;
+        Ok(())
+    }
+
+    /// 测试/无服务端⇒启动失败
+    #[test]
+    fn test_connect_failed() {
+        assert!(launch(unused_port(), false).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+//! CommonNarsese（ASCII）的完整解析
+//! * 🎯NAL测试与各CIN的「Narsese解析链」共用
+
+use anyhow::{anyhow, Result};
+use narsese::{conversion::string::impl_lexical::format_instances::FORMAT_ASCII, lexical::Narsese};
+
+/// 将整个字符串解析为CommonNarsese（ASCII）
+/// * ⚠️[`FORMAT_ASCII`]会接受「开头一段合法」的字符串：如`it's`被解析为词项`it`
+/// * 🚩格式化回字符串，忽略空白与分隔符`,`后须与原文一致；否则⇒报错
+///   * 📌格式化时会补上分隔符：`(* {SELF})`⇒`(*, {SELF})`
+pub fn parse_common_ascii(input: &str) -> Result<Narsese> {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            .collect::<String>()
+    };
+    let narsese = FORMAT_ASCII.parse(input)?;
+    let formatted = FORMAT_ASCII.format_narsese(&narsese);
+    match normalize(&formatted) == normalize(input) {
+        true => Ok(narsese),
+        false => Err(anyhow!(
+            "CommonNarsese仅解析了{input:?}的一部分：{formatted:?}"
+        )),
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/完整解析CommonNarsese：只解析了开头一段⇒报错
+    #[test]
+    fn test_parse_common_ascii() {
+        assert!(parse_common_ascii("<A --> B>. %1.00;0.90%").is_ok());
+        assert!(parse_common_ascii("<(*, {SELF}) --> ^left>. :|:").is_ok());
+        assert!(parse_common_ascii("it's").is_err());
+        assert!(parse_common_ascii("<A --> B>. %1.00;0.90% {0 : 1}").is_err());
+    }
+}
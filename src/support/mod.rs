@@ -0,0 +1,17 @@
+//! 不依赖可选特性的共用支持
+//! * 🎯供`cin_implements`、`test_tools`、`cli_support`共用，避免可选模块之间互相依赖
+//!   * 📄`cargo check --no-default-features --features test_tools`也应能通过
+
+util::mods! {
+    // 「参数设置」指令
+    pub pub param_cmd;
+
+    // CommonNarsese完整解析
+    pub pub common_narsese;
+
+    // 终止标志
+    pub pub shutdown_flag;
+
+    // 提示信息打印
+    pub pub notice;
+}
@@ -0,0 +1,23 @@
+//! 提示信息打印
+//! * 🎯让CIN实现、测试工具在不启用`cli_support`时也能报告信息、警告
+//! * 🚩启用`cli_support`⇒交给[`OutputType`](crate::cli_support::io::output_print::OutputType)，沿用其着色与重定向
+//!   * 📌未启用⇒直接打印`[类型] 内容`
+
+/// 打印一行提示
+/// * 📄`print_notice("WARN", "……")`⇒`[WARN] ……`
+/// * 📌类型名与[`OutputType::as_str`](crate::cli_support::io::output_print::OutputType::as_str)一致：`INFO`、`WARN`、`ERROR`……
+pub fn print_notice(head: &str, message: &str) {
+    #[cfg(feature = "cli_support")]
+    crate::cli_support::io::output_print::OutputType::Vm(head).print_line(message);
+    #[cfg(not(feature = "cli_support"))]
+    println!("[{head}] {message}");
+}
+
+/// 打印一行提示到标准错误
+/// * 🎯不应混入CIN输出的错误信息
+pub fn eprint_notice(head: &str, message: &str) {
+    #[cfg(feature = "cli_support")]
+    crate::cli_support::io::output_print::OutputType::Vm(head).eprint_line(message);
+    #[cfg(not(feature = "cli_support"))]
+    eprintln!("[{head}] {message}");
+}
@@ -0,0 +1,31 @@
+//! 统一的「参数设置」指令
+//! * 📌NAVM未内置此指令⇒借助[`Cmd::Custom`]扩展：`SET decisionthreshold=0.6`
+//!   * 📄NAL中可写作`''set-param: decisionthreshold=0.6`
+//! * 🚩各CIN的转译见`cin_implements::common::param`
+
+use anyhow::{anyhow, Result};
+use navm::cmd::Cmd;
+
+/// 「参数设置」指令的指令头
+pub const PARAM_CMD_HEAD: &str = "SET";
+
+/// 构造「参数设置」指令
+pub fn param_cmd(key: &str, value: &str) -> Cmd {
+    Cmd::Custom {
+        head: PARAM_CMD_HEAD.into(),
+        tail: format!("{key}={value}"),
+    }
+}
+
+/// 从「参数设置」指令的「指令尾」中解析参数名与参数值
+/// * 🚩格式：`key=value`，两侧空白忽略
+/// * ⚠️参数名、参数值均不能为空
+pub fn parse_param(tail: &str) -> Result<(&str, &str)> {
+    let (key, value) = tail
+        .split_once('=')
+        .ok_or_else(|| anyhow!("无效的参数设置 {tail:?}：应为`参数名=参数值`"))?;
+    match (key.trim(), value.trim()) {
+        ("", _) | (_, "") => Err(anyhow!("无效的参数设置 {tail:?}：参数名、参数值不能为空")),
+        (key, value) => Ok((key, value)),
+    }
+}
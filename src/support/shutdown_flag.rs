@@ -0,0 +1,34 @@
+//! 终止标志
+//! * 🎯各线程在循环中检查：收到终止信号⇒终止CIN并退出循环
+//! * 📌信号处理函数的安装见`cli_support::shutdown`；库的使用者也可直接[请求终止](request_shutdown)
+
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// 收到的终止信号
+/// * 📌`0`⇒尚未收到
+static SHUTDOWN_SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+/// 是否已收到终止信号
+/// * 🎯供各线程在循环中检查：收到⇒终止CIN并退出循环
+pub fn is_shutdown_requested() -> bool {
+    SHUTDOWN_SIGNAL.load(Ordering::SeqCst) != 0
+}
+
+/// 请求终止
+/// * 🚩记录信号；已收到过终止信号⇒返回`false`，表示「应强制退出」
+/// * ⚠️会在信号处理函数中调用：只能使用原子操作
+pub fn request_shutdown(signal: i32) -> bool {
+    SHUTDOWN_SIGNAL
+        .compare_exchange(0, signal, Ordering::SeqCst, Ordering::SeqCst)
+        .is_ok()
+}
+
+/// 收到终止信号后，程序应使用的退出码
+/// * 📌按惯例为`128+信号值`：`SIGINT`⇒`130`，`SIGTERM`⇒`143`
+/// * 🚩尚未收到⇒[`None`]
+pub fn shutdown_exit_code() -> Option<i32> {
+    match SHUTDOWN_SIGNAL.load(Ordering::SeqCst) {
+        0 => None,
+        signal => Some(128 + signal),
+    }
+}
@@ -0,0 +1,301 @@
+//! 跨CIN的NAL测试
+//! * 🎯将同一份`.nal`测试同时运行在多个CIN上，汇总「预期×CIN」的通过情况
+//!   * 📄替代「逐个CIN运行，再手动整理通过矩阵」的流程
+//! * 🚩每个CIN在独立线程中启动、运行，互不影响
+//!   * 📌单个CIN出错（启动失败、崩溃、预期失败）不会中止其它CIN
+//!   * 📌超时的CIN被标记为「超时」，而不会拖住整个测试
+//! * ✨结果可渲染为Markdown表格：[`CrossTestReport::to_markdown`]
+
+use super::{
+    nal_format::parse_with_line_no,
+    nal_test::{expectation_label, run_nal_inputs, TestOptions},
+    SetAck,
+};
+use crate::runtimes::{Capabilities, VmRuntimeBoost};
+use anyhow::Result;
+use std::{path::Path, thread, time::Duration};
+
+pub use super::nal_test::{ExpectationResult, DEFAULT_TIMEOUT};
+
+/// 跨CIN测试的目标
+/// * 📌`launcher`在CIN自己的线程中调用
+///   * ⚠️若启动过程依赖「当前工作目录」等全局状态，应预先启动，再在此返回启动结果
+pub struct CrossTestTarget<L> {
+    /// CIN名称 | 用作表头
+    pub name: String,
+
+    /// 启动器：返回启动后的运行时
+    pub launcher: L,
+
+    /// 「设置」指令的回执
+    /// * 🎯`''set:`，参见[`SetAck`]
+    pub set_ack: SetAck,
+
+    /// CIN所支持的功能
+    /// * 🎯`''requires:`：缺少功能⇒相应预期记为[`ExpectationResult::Skipped`]
+    pub capabilities: Capabilities,
+}
+
+/// 跨CIN测试的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrossTestReport {
+    /// 所有预期的描述 | 按在NAL文件中的顺序
+    pub expectations: Vec<String>,
+
+    /// 各CIN的结果
+    /// * 📌每个CIN的结果与`expectations`一一对应
+    pub per_cin: Vec<(String, Vec<ExpectationResult>)>,
+}
+
+impl CrossTestReport {
+    /// 是否所有CIN都通过了所有预期
+    pub fn is_all_passed(&self) -> bool {
+        self.per_cin
+            .iter()
+            .all(|(_, results)| results.iter().all(ExpectationResult::is_passed))
+    }
+
+    /// 渲染为Markdown表格
+    /// * 🚩行：预期；列：CIN
+    /// * 🚩表格之后列出所有失败原因
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("| 预期 |");
+        for (name, _) in &self.per_cin {
+            md += &format!(" {name} |");
+        }
+        md += "\n| --- |";
+        md += &" :-: |".repeat(self.per_cin.len());
+        for (i, expectation) in self.expectations.iter().enumerate() {
+            md += &format!("\n| `{expectation}` |");
+            for (_, results) in &self.per_cin {
+                md += &format!(" {} |", results[i].mark());
+            }
+        }
+        // 失败原因
+        for (name, results) in &self.per_cin {
+            for (expectation, result) in self.expectations.iter().zip(results) {
+                if let ExpectationResult::Failed(reason) | ExpectationResult::Skipped(reason) =
+                    result
+                {
+                    md += &format!("\n\n* {name} | `{expectation}`：{reason}");
+                }
+            }
+        }
+        md
+    }
+}
+
+/// 在多个CIN上并行运行同一份NAL测试
+/// * 🎯跨CIN对比：每个CIN一个线程，共享同一份解析后的NAL输入
+/// * 🚩NAL解析失败的行会被跳过：只比对能解析的预期
+/// * 🚩所有CIN同时开始，并共享同一个截止时刻（开始时刻+`timeout`）
+///   * 📌截止时仍未运行完的CIN，其余下的预期均记为[`ExpectationResult::TimedOut`]
+///   * 🚩超时的CIN被直接终止，其运行线程随之回收，参见[`run_nal_inputs`]
+/// * ⚠️`''include:`需由调用方展开，此处视作普通的失败输入
+pub fn cross_test<L, R>(
+    targets: impl IntoIterator<Item = CrossTestTarget<L>>,
+    nal: &str,
+    nal_root_path: &Path,
+    timeout: Duration,
+) -> CrossTestReport
+where
+    L: FnOnce() -> Result<R> + Send + 'static,
+    R: VmRuntimeBoost + 'static,
+{
+    let inputs = parse_with_line_no(nal)
+        .into_iter()
+        .filter_map(|(line_no, result)| Some((line_no, result.ok()?)))
+        .collect::<Vec<_>>();
+    let expectations = inputs
+        .iter()
+        .filter_map(|(_, input)| expectation_label(input))
+        .collect::<Vec<_>>();
+
+    // 启动所有线程 | 各CIN的看门狗同时开始计时
+    let handles = targets
+        .into_iter()
+        .map(|target| {
+            let inputs = inputs.clone();
+            let CrossTestTarget {
+                name,
+                launcher,
+                set_ack,
+                capabilities,
+            } = target;
+            let options = TestOptions {
+                timeout,
+                nal_root_path: nal_root_path.to_path_buf(),
+                set_ack,
+                capabilities,
+                ..Default::default()
+            };
+            let handle = thread::spawn(move || run_nal_inputs(launcher, inputs, options));
+            (name, handle)
+        })
+        .collect::<Vec<_>>();
+
+    // 收集结果
+    let per_cin = handles
+        .into_iter()
+        .map(|(name, handle)| {
+            let results = match handle.join() {
+                Ok(Ok(outcome)) => outcome.results().cloned().collect(),
+                Ok(Err(e)) => vec![ExpectationResult::Skipped(e.to_string()); expectations.len()],
+                Err(..) => {
+                    let reason = "运行线程异常退出".to_string();
+                    vec![ExpectationResult::Skipped(reason); expectations.len()]
+                }
+            };
+            (name, results)
+        })
+        .collect();
+
+    CrossTestReport {
+        expectations,
+        per_cin,
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::{FakeVm, FakeVmRuntime};
+    use anyhow::anyhow;
+    use nar_dev_utils::manipulate;
+    use narsese::conversion::string::impl_lexical::shortcuts::*;
+    use navm::{cmd::Cmd, output::Output, vm::VmLauncher};
+    use std::time::Instant;
+
+    /// 测试用NAL
+    const NAL: &str = "\
+<A --> B>.
+<B --> C>.
+<A --> C>?
+''expect-cycle(5, 1, 1ms): ANSWER <A --> C>.
+''expect-contains: ANSWER <A --> B>.";
+
+    /// 会回答的模拟CIN
+    fn answering() -> Result<FakeVmRuntime> {
+        manipulate!(
+            FakeVm::new()
+            => .respond_after_cycle(
+                Cmd::NSE(nse_task!(<A --> C>?)),
+                [Output::ANSWER {
+                    content_raw: "<A --> C>.".into(),
+                    narsese: Some(nse!(<A --> C>.)),
+                }],
+            )
+        )
+        .launch()
+    }
+
+    /// 不会回答的模拟CIN
+    fn silent() -> Result<FakeVmRuntime> {
+        FakeVm::new().launch()
+    }
+
+    /// 迟迟不启动的模拟CIN
+    fn slow() -> Result<FakeVmRuntime> {
+        thread::sleep(Duration::from_secs(2));
+        silent()
+    }
+
+    /// 启动失败的CIN
+    fn broken() -> Result<FakeVmRuntime> {
+        Err(anyhow!("找不到CIN"))
+    }
+
+    fn target(
+        name: &str,
+        launcher: fn() -> Result<FakeVmRuntime>,
+    ) -> CrossTestTarget<fn() -> Result<FakeVmRuntime>> {
+        CrossTestTarget {
+            name: name.into(),
+            launcher,
+            set_ack: SetAck::default(),
+            capabilities: Capabilities::ALL,
+        }
+    }
+
+    /// 测试/各CIN互不影响
+    #[test]
+    fn test_cross_test() {
+        let start = Instant::now();
+        let report = cross_test(
+            [
+                target("answering", answering),
+                target("silent", silent),
+                target("slow", slow),
+                target("broken", broken),
+            ],
+            NAL,
+            Path::new("."),
+            Duration::from_millis(500),
+        );
+        // 不等待超时的CIN
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_eq!(
+            report.expectations,
+            [
+                "expect-cycle(5): ANSWER <A --> C>.",
+                "expect-contains: ANSWER <A --> B>."
+            ]
+        );
+        use ExpectationResult::*;
+        let marks = report
+            .per_cin
+            .iter()
+            .map(|(name, results)| {
+                let kinds = results
+                    .iter()
+                    .map(ExpectationResult::mark)
+                    .collect::<Vec<_>>();
+                (name.as_str(), kinds)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            marks,
+            [
+                (
+                    "answering",
+                    vec![Passed.mark(), Failed(String::new()).mark()]
+                ),
+                ("silent", vec![Failed(String::new()).mark(); 2]),
+                ("slow", vec![TimedOut.mark(); 2]),
+                ("broken", vec![Skipped(String::new()).mark(); 2]),
+            ]
+        );
+        assert!(!report.is_all_passed());
+        let md = report.to_markdown();
+        assert!(
+            md.starts_with(
+                "| 预期 | answering | silent | slow | broken |\n| --- | :-: | :-: | :-: | :-: |"
+            ),
+            "{md}"
+        );
+        assert!(md.contains("找不到CIN"), "{md}");
+    }
+
+    /// 测试/缺少功能⇒跳过，而非失败
+    #[test]
+    fn test_cross_test_requires() {
+        let nal = "''requires: operations\n''expect-contains: EXE ^left\n''end-requires\n''expect-contains: ANSWER <A --> B>.";
+        let capable = target("capable", silent);
+        let incapable = CrossTestTarget {
+            capabilities: Capabilities::NONE,
+            ..target("incapable", silent)
+        };
+        let report = cross_test([capable, incapable], nal, Path::new("."), DEFAULT_TIMEOUT);
+        let (_, capable) = &report.per_cin[0];
+        let (_, incapable) = &report.per_cin[1];
+        assert!(matches!(
+            capable[..],
+            [ExpectationResult::Failed(..), ExpectationResult::Failed(..)]
+        ));
+        assert!(
+            matches!(&incapable[..], [ExpectationResult::Skipped(reason), ExpectationResult::Failed(..)] if reason.contains("operations")),
+            "{incapable:?}"
+        );
+    }
+}
@@ -12,4 +12,8 @@ util::mods! {
     pub nal_format;
     // NAVM交互
     pub pub vm_interact;
+    // 以库函数运行NAL测试
+    pub pub nal_test;
+    // 跨CIN测试
+    pub cross_test;
 }
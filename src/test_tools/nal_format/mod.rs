@@ -5,36 +5,154 @@
 
 use std::{result::Result::Err as StdErr, result::Result::Ok as StdOk, time::Duration};
 
-use super::structs::*;
+use super::{structs::*, EchoMatchOptions};
+use crate::{
+    runtimes::Capabilities,
+    support::{param_cmd, parse_common_ascii, parse_param},
+};
 use anyhow::{Ok, Result};
 use narsese::{
     conversion::string::impl_lexical::format_instances::FORMAT_ASCII,
     lexical::{Narsese, Sentence, Task},
 };
-use navm::{cmd::Cmd, output::Operation};
+use navm::{cmd::Cmd, output::type_names::IN};
 use pest::{iterators::Pair, Parser};
 use pest_derive::Parser;
-use util::{first, pipe};
+use util::pipe;
 
 #[derive(Parser)] // ! ↓ 必须从项目根目录开始
 #[grammar = "src/test_tools/nal_format/nal_grammar.pest"]
 pub struct NALParser;
 
+/// 续行符
+/// * 🎯允许过长的Narsese语句跨越多个物理行
+/// * 📄`<(&&, <$x --> flyer>, \`换行后接`<$x --> [chirping]>) ==> <$x --> bird>>.`
+/// * ⚠️仅对非注释行生效：注释（含「魔法注释」）始终按行解析
+/// * ⚠️须与前文以空白隔开：`\`亦是Narsese语法的一部分
+///   * 📄如连接符`(\, R, _, A)`、时序蕴含`=\>`：紧贴前文的行末`\`不视作续行符
+pub const LINE_CONTINUATION: char = '\\';
+
+/// 魔法注释`''reset`所置入的`RES`指令的目标
+/// * 🎯重置整个记忆区
+pub const RESET_TARGET: &str = "memory";
+
 /// 使用[`pest`]将整个`.nal`文件内容转换为[`NALInput`]结果序列
 /// * ✨也可只输入一行，用以解析单个[`NALInput`]
+/// * ✨支持以[续行符](LINE_CONTINUATION)结尾的多行输入
 /// * 📌重点在其简写的「操作」语法`(^left, {SELF}, x)` => `<(*, {SELF}, x) --> ^left>`
+/// * 📌解析错误会附带其（起始）行号，以便定位
 pub fn parse(input: &str) -> Vec<Result<NALInput>> {
-    input
-        // 切分并过滤空行
-        .split('\n')
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
-        // 逐行解析
-        .map(parse_single)
+    parse_with_line_no(input)
+        .into_iter()
+        .map(|(_, result)| result)
+        .collect::<Vec<_>>()
+}
+
+/// `.nal`文件中的行号
+/// * 📌从`1`开始
+pub type LineNo = usize;
+
+/// 同[`parse`]，但每个结果都附带其（起始）行号
+/// * 🎯置入失败时，也能定位到`.nal`文件中的位置
+/// * 📌行号从`1`开始
+/// * 📌`''precision:`在此应用：填入此后未指定精度的预期
+pub fn parse_with_line_no(input: &str) -> Vec<(LineNo, Result<NALInput>)> {
+    parse_lines(input, None)
+}
+
+/// 同[`parse_with_line_no`]，但解析错误以`文件名:行号:`开头
+/// * 🎯`preludeNAL`、`''include:`的文件：在多个文件中定位出错的行
+/// * 📌`source_name`仅用于错误信息，通常为文件路径
+pub fn parse_with_source(input: &str, source_name: &str) -> Vec<(LineNo, Result<NALInput>)> {
+    parse_lines(input, Some(source_name))
+}
+
+/// 获取指定行号所在的原始行
+/// * 🎯在错误信息中附带出错的那一行
+/// * 📌续行⇒仅返回起始行；行号超出范围⇒空字串
+pub fn source_line(input: &str, line_no: LineNo) -> &str {
+    match line_no.checked_sub(1) {
+        Some(i) => input.split('\n').nth(i).unwrap_or_default().trim(),
+        None => "",
+    }
+}
+
+/// 逐行解析，并附带行号
+/// * 🚩有来源名⇒错误以`来源名:行号:`开头；否则⇒以`第N行`开头
+fn parse_lines(input: &str, source_name: Option<&str>) -> Vec<(LineNo, Result<NALInput>)> {
+    // 当前精度 | 由`''precision:`设置
+    let mut precision = None;
+    join_lines(input)
+        // 逐行解析，并附带行号
+        .into_iter()
+        .map(|(line_no, line)| {
+            let result = parse_single(&line)
+                .map(|mut input| {
+                    match input {
+                        NALInput::Precision(p) => precision = Some(p),
+                        _ => input.default_precision(precision),
+                    }
+                    input
+                })
+                .map_err(|e| {
+                    let message = match source_name {
+                        Some(source_name) => {
+                            format!("{source_name}:{line_no}: 「{line}」解析失败：{e}")
+                        }
+                        None => format!("第{line_no}行「{line}」解析失败：{e}"),
+                    };
+                    e.context(message)
+                });
+            (line_no, result)
+        })
         // 收集所有结果
         .collect::<Vec<_>>()
 }
 
+/// 预处理：切分、合并续行、过滤空行
+/// * 🚩返回`(起始行号, 合并后的逻辑行)`的序列 | 行号从`1`开始
+/// * 📌空行总是会截断续行：不会因「末尾忘了删续行符」吞掉下一个输入
+/// * 📌注释行不会续行，也不会被并入之前的续行：以`'`开头的行总是独立解析
+/// * 📌续行符须与前文以空白隔开：见[`LINE_CONTINUATION`]
+fn join_lines(input: &str) -> Vec<(usize, String)> {
+    let mut lines = vec![];
+    // 尚未结束的续行 | (起始行号, 已合并的内容)
+    let mut pending: Option<(usize, String)> = None;
+    for (i, line) in input.split('\n').enumerate() {
+        let line = line.trim();
+        // 空行⇒截断续行
+        if line.is_empty() {
+            lines.extend(pending.take());
+            continue;
+        }
+        // 注释行⇒同样截断续行，且自身独立成行
+        if line.starts_with('\'') {
+            lines.extend(pending.take());
+            lines.push((i + 1, line.to_string()));
+            continue;
+        }
+        // 合并到之前的续行中，或新起一行
+        let (line_no, mut joined) = match pending.take() {
+            Some((line_no, joined)) => (line_no, joined + " "),
+            None => (i + 1, String::new()),
+        };
+        // 判断是否还要继续
+        match line.strip_suffix(LINE_CONTINUATION) {
+            Some(head) if head.ends_with(char::is_whitespace) => {
+                joined += head.trim_end();
+                pending = Some((line_no, joined));
+            }
+            _ => {
+                joined += line;
+                lines.push((line_no, joined));
+            }
+        }
+    }
+    // 末尾未结束的续行
+    lines.extend(pending);
+    lines
+}
+
 pub fn parse_single(line: &str) -> Result<NALInput> {
     // 解析一行
     pipe! {
@@ -99,6 +217,51 @@ fn fold_pest(pair: Pair<Rule>) -> Result<NALInput> {
             let input = NALInput::Put(Cmd::parse(line)?);
             Ok(input)
         }
+        // 魔法注释/设置
+        Rule::comment_set => {
+            // 取其中第一个`comment_raw`元素 | 一定只有唯一一个`comment_raw`
+            let line = pair.into_inner().next().unwrap().as_str().trim();
+            // * 🚩同样作为NAVM指令解析，但需等待回执
+            let input = NALInput::Set(Cmd::parse(line)?);
+            Ok(input)
+        }
+        // 魔法注释/参数设置
+        Rule::comment_set_param => {
+            // 取其中第一个`comment_raw`元素 | 一定只有唯一一个`comment_raw`
+            let line = pair.into_inner().next().unwrap().as_str().trim();
+            // * 🚩作为`SET`指令置入 | 参数格式有误⇒报错
+            let (key, value) = parse_param(line)?;
+            Ok(NALInput::Put(param_cmd(key, value)))
+        }
+        // 魔法注释/包含文件
+        Rule::comment_include => {
+            // 取其中第一个`comment_raw`元素 | 一定只有唯一一个`comment_raw`
+            let path = pair.into_inner().next().unwrap().as_str().trim();
+            Ok(NALInput::Include(path.into()))
+        }
+        // 魔法注释/设置精度
+        Rule::comment_precision => {
+            // 取其中第一个`comment_raw`元素 | 一定只有唯一一个`comment_raw`
+            let precision = pair.into_inner().next().unwrap().as_str();
+            Ok(NALInput::Precision(FloatPrecision::parse(precision)?))
+        }
+        // 魔法注释/报告运行状态
+        Rule::comment_status => Ok(NALInput::Status),
+        // 魔法注释/功能要求
+        Rule::comment_requires => {
+            // 取其中第一个`comment_raw`元素 | 一定只有唯一一个`comment_raw`
+            let names = pair.into_inner().next().unwrap().as_str();
+            Ok(NALInput::Requires(parse_capability_names(names)?))
+        }
+        // 魔法注释/结束功能要求
+        Rule::comment_end_requires => Ok(NALInput::EndRequires),
+        // 魔法注释/重置预期窗口
+        Rule::comment_reset_expect_window => Ok(NALInput::ResetExpectWindow),
+        // 魔法注释/重置
+        // * 🚩作为`RES`指令置入 | 重置整个记忆区
+        Rule::comment_reset => Ok(NALInput::Put(Cmd::RES {
+            target: RESET_TARGET.into(),
+        })),
         // 魔法注释/睡眠等待
         Rule::comment_sleep => {
             // 取其中第一个`comment_raw`元素 | 一定只有唯一一个`comment_raw`
@@ -109,6 +272,17 @@ fn fold_pest(pair: Pair<Rule>) -> Result<NALInput> {
             let input = NALInput::Sleep(duration);
             Ok(input)
         }
+        // 魔法注释/等待CIN安静下来
+        Rule::comment_await_idle => {
+            let mut durations = pair.into_inner().map(|pair| parse_duration(pair.as_str()));
+            // * 🚩语法保证恰有两个时间参数
+            let quiet_period = durations.next().unwrap()?;
+            let max_wait = durations.next().unwrap()?;
+            Ok(NALInput::AwaitIdle {
+                quiet_period,
+                max_wait,
+            })
+        }
         // 魔法注释/等待
         Rule::comment_await => {
             // 取其中唯一一个「输出预期」
@@ -116,21 +290,91 @@ fn fold_pest(pair: Pair<Rule>) -> Result<NALInput> {
             let output_expectation = fold_pest_output_expectation(output_expectation)?;
             Ok(NALInput::Await(output_expectation))
         }
-        // 魔法注释/输出包含
-        Rule::comment_expect_contains => {
+        // 魔法注释/等待回显
+        // * 🚩等待「输入」类输出，按回显比对Narsese
+        Rule::comment_await_echo => {
+            let narsese = pair.into_inner().next().unwrap().as_str();
+            Ok(NALInput::Await(OutputExpectation {
+                output_type: Some(IN.into()),
+                narsese: Some(FORMAT_ASCII.parse(narsese)?),
+                echo: Some(EchoMatchOptions::default()),
+                ..Default::default()
+            }))
+        }
+        // 魔法注释/输出包含 | 含有子词项
+        Rule::comment_expect_contains | Rule::comment_expect_contains_term => {
+            let term_match = match pair.as_rule() {
+                Rule::comment_expect_contains_term => TermMatch::Contains,
+                _ => TermMatch::Exact,
+            };
+            let mut pairs = pair.into_inner();
+            let mut next = pairs.next().unwrap();
+            // 取其中可选的「精度」「时间容差」与「最大延迟」
+            let (mut precision, mut tick_tolerance, mut max_latency) = (None, None, None);
+            loop {
+                match next.as_rule() {
+                    Rule::comment_expect_tick_tolerance => {
+                        let value = next.into_inner().next().unwrap().as_str();
+                        tick_tolerance = Some(value.parse()?);
+                    }
+                    Rule::comment_expect_precision => {
+                        let value = next.into_inner().next().unwrap().as_str();
+                        precision = Some(FloatPrecision::parse(value)?);
+                    }
+                    Rule::comment_expect_contains_latency => {
+                        max_latency = Some(parse_duration(next.as_str().trim())?);
+                    }
+                    _ => break,
+                }
+                next = pairs.next().unwrap();
+            }
             // 取其中唯一一个「输出预期」
-            let output_expectation = pair.into_inner().next().unwrap();
-            let output_expectation = fold_pest_output_expectation(output_expectation)?;
-            Ok(NALInput::ExpectContains(output_expectation))
+            let output_expectation = OutputExpectation {
+                term_match,
+                precision,
+                tick_tolerance,
+                ..fold_pest_output_expectation(next)?
+            };
+            Ok(NALInput::ExpectContains(output_expectation, max_latency))
+        }
+        // 魔法注释/OpenNARS风格的输出包含 | 输出不含
+        // * 🚩「不含」⇒最大步数为`0`的「循环否定预期」：只检查预期窗口内已有的输出
+        Rule::comment_output_must_contain | Rule::comment_output_must_not_contain => {
+            let rule = pair.as_rule();
+            let quoted = pair.into_inner().next().unwrap().as_str();
+            let output_expectation = fold_quoted_output_expectation(&unescape_quoted(quoted))?;
+            Ok(match rule {
+                Rule::comment_output_must_not_contain => {
+                    NALInput::ExpectCycleNot(0, 1, None, output_expectation)
+                }
+                _ => NALInput::ExpectContains(output_expectation, None),
+            })
         }
         // 魔法注释/保存输出
         Rule::comment_save_outputs => {
-            // 取其中唯一一个「输出预期」
-            let file_path = pair.into_inner().next().unwrap().as_str().into();
-            Ok(NALInput::SaveOutputs(file_path))
+            let mut pairs = pair.into_inner();
+            let mut next = pairs.next().unwrap();
+            // 取其中可选的「文件格式」
+            let format = match next.as_rule() {
+                Rule::comment_save_outputs_format => {
+                    let format = match next.as_str() {
+                        "nal" => SaveOutputsFormat::Nal,
+                        "csv" => SaveOutputsFormat::Csv,
+                        _ => SaveOutputsFormat::Json,
+                    };
+                    next = pairs.next().unwrap();
+                    format
+                }
+                _ => SaveOutputsFormat::default(),
+            };
+            // 取其中唯一一个「输出预期」作为文件路径
+            let path = next.as_str().into();
+            Ok(NALInput::SaveOutputs { format, path })
         }
-        // 魔法注释/循环预期
-        Rule::comment_expect_cycle => {
+        // 魔法注释/循环预期 | 循环否定预期
+        // * 🚩二者参数相同，仅构造的变体不同
+        Rule::comment_expect_cycle | Rule::comment_expect_cycle_not => {
+            let rule = pair.as_rule();
             let mut pairs = pair.into_inner();
             // 取其中的「最大步数」
             let max_cycles = pipe! {
@@ -139,23 +383,21 @@ fn fold_pest(pair: Pair<Rule>) -> Result<NALInput> {
                 => {.parse::<usize>()}#
                 => {?}#
             };
-            // 取其中的「每次步长」
-            let step_cycles = pipe! {
-                pairs.next().unwrap()
-                => .as_str()
-                => {.parse::<usize>()}#
-                => {?}#
-            };
-            // 取其中的「输出预期」
-            let step_duration = pairs.next();
-            let step_duration = match step_duration {
-                Some(step_duration) => {
+            // 取其中的「每次步长」 | 不能为`0`
+            let step_cycles = parse_step_cycles(pairs.next().unwrap().as_str())?;
+            // 取其中的「每步后等待时间」（可选）
+            // * 🚩须检查规则：缺省时，下一个即为「输出预期」
+            let step_duration = match pairs.peek() {
+                Some(step_duration)
+                    if step_duration.as_rule() == Rule::comment_expect_cycle_step_time =>
+                {
                     // 尝试解析时间
                     let step_duration = parse_duration(step_duration.as_str())?;
+                    pairs.next();
                     // 封装
                     Some(step_duration)
                 }
-                None => None,
+                _ => None,
             };
             // 取其中的「输出预期」
             let output_expectation = pipe! {
@@ -164,31 +406,53 @@ fn fold_pest(pair: Pair<Rule>) -> Result<NALInput> {
                 => {?}#
             };
             // 构造 & 返回
-            Ok(NALInput::ExpectCycle(
+            let constructor = match rule {
+                Rule::comment_expect_cycle_not => NALInput::ExpectCycleNot,
+                _ => NALInput::ExpectCycle,
+            };
+            Ok(constructor(
                 max_cycles,
                 step_cycles,
                 step_duration,
                 output_expectation,
             ))
         }
+        // 魔法注释/比例预期
+        Rule::comment_expect_rate => {
+            let mut pairs = pair.into_inner();
+            // 取其中的「最大步数」与「每次步长」 | 步长不能为`0`
+            let max_cycles = pairs.next().unwrap().as_str().parse::<usize>()?;
+            let step_cycles = parse_step_cycles(pairs.next().unwrap().as_str())?;
+            // 取其中的「最低比例」
+            let threshold = parse_rate(pairs.next().unwrap().as_str())?;
+            // 取其中的「输出预期」
+            let expectation = fold_pest_output_expectation(pairs.next().unwrap())?;
+            Ok(NALInput::ExpectRate {
+                max_cycles,
+                step_cycles,
+                threshold,
+                expectation,
+            })
+        }
         // 魔法注释/终止
         Rule::comment_terminate => {
             // 预置默认值
             let mut if_not_user = false;
+            let mut if_no_client = false;
             let mut result = StdOk(());
 
             // 遍历其中的Pair
             for inner in pair.into_inner() {
                 // 逐个匹配规则类型
-                //   * ✨comment_terminate_option: `if-not-user`
+                //   * ✨comment_terminate_option: `if-no-user` | `if-no-client`
                 //   * ✨comment_raw: Err(`message`)
                 match inner.as_rule() {
                     // 可选规则
-                    Rule::comment_terminate_option => {
-                        if inner.as_str() == "if-no-user" {
-                            if_not_user = true;
-                        }
-                    }
+                    Rule::comment_terminate_option => match inner.as_str() {
+                        "if-no-user" => if_not_user = true,
+                        "if-no-client" => if_no_client = true,
+                        _ => unreachable!("不该被匹配到的选项\tinner = {inner:?}"),
+                    },
                     // 错误消息
                     Rule::comment_raw => {
                         // 构造错误 | 仅取注释部分
@@ -202,6 +466,7 @@ fn fold_pest(pair: Pair<Rule>) -> Result<NALInput> {
             // 构造&返回
             Ok(NALInput::Terminate {
                 if_not_user,
+                if_no_client,
                 result,
             })
         }
@@ -219,10 +484,11 @@ fn fold_pest_output_expectation(pair: Pair<Rule>) -> Result<OutputExpectation> {
     // 开始遍历其中的元素
     for inner in pair.into_inner() {
         // 逐个匹配规则类型
-        // * 🚩【2024-04-01 00:18:23】目前只可能有三个
+        // * 🚩目前只可能有四个
         //   * ✨输出类型
         //   * ✨Narsese
         //   * ✨NAVM操作
+        //   * ✨原始内容
         match inner.as_rule() {
             // 输出类型
             Rule::output_type => {
@@ -243,6 +509,18 @@ fn fold_pest_output_expectation(pair: Pair<Rule>) -> Result<OutputExpectation> {
             }
             // NAVM操作
             Rule::output_operation => result.operation = Some(fold_pest_output_operation(inner)?),
+            // 原始内容
+            // * 🚩解析时即编译：正则表达式有误⇒报错（并由调用方附上行号）
+            Rule::output_raw_regex => {
+                // 取其中唯一一个`output_raw_regex_content`，并还原转义的`/`
+                let pattern = inner
+                    .into_inner()
+                    .next()
+                    .unwrap()
+                    .as_str()
+                    .replace("\\/", "/");
+                result.raw_regex = Some(RawRegex::new(&pattern)?);
+            }
             // 其它情况
             _ => unreachable!("不该被匹配到的规则\tpair = {inner:?}"),
         }
@@ -252,10 +530,42 @@ fn fold_pest_output_expectation(pair: Pair<Rule>) -> Result<OutputExpectation> {
     Ok(result)
 }
 
+/// 还原引号字符串中的转义
+/// * 🚩`\x`⇒`x`：如`\'`⇒`'`、`\\`⇒`\`
+fn unescape_quoted(quoted: &str) -> String {
+    let mut result = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// 从OpenNARS风格的引号内容构造「输出预期」
+/// * 🚩能完整解析为CommonNarsese⇒按Narsese匹配，不限输出类型
+/// * 🚩否则⇒按原始内容的子串匹配
+fn fold_quoted_output_expectation(content: &str) -> Result<OutputExpectation> {
+    let content = content.trim();
+    Ok(match parse_common_ascii(content) {
+        StdOk(narsese) => OutputExpectation {
+            narsese: Some(narsese),
+            ..Default::default()
+        },
+        StdErr(..) => OutputExpectation {
+            raw_regex: Some(RawRegex::new(&regex::escape(content))?),
+            ..Default::default()
+        },
+    })
+}
+
 /// 解析其中的「NAVM操作」[`Pair`]
 /// * 其中[`Pair`]的`rule`属性必是`output_operation`
+/// * ⚠️「其余参数」`..`不在末尾⇒报错
 #[inline]
-fn fold_pest_output_operation(pair: Pair<Rule>) -> Result<Operation> {
+fn fold_pest_output_operation(pair: Pair<Rule>) -> Result<OperationExpectation> {
     // 生成迭代器
     let mut pairs = pair.into_inner();
     // 取第一个子Pair当操作名 | 语法上保证一定有
@@ -264,42 +574,162 @@ fn fold_pest_output_operation(pair: Pair<Rule>) -> Result<Operation> {
     let mut params = vec![];
     // 消耗剩下的，填充参数
     for inner in pairs {
-        // 尝试作为Narsese词项解析 | 无法使用 *narsese.get_term()强制转换成词项
-        let term = match FORMAT_ASCII.parse(inner.as_str())? {
-            Narsese::Term(term)
-            | Narsese::Sentence(Sentence { term, .. })
-            | Narsese::Task(Task {
-                sentence: Sentence { term, .. },
-                ..
-            }) => term,
+        if params.last() == Some(&ParamPattern::Rest) {
+            return Err(anyhow::anyhow!(
+                "操作(^{operator_name}, ...)中，`..`只能位于参数末尾"
+            ));
+        }
+        let param = match inner.as_rule() {
+            Rule::output_operation_param_any => ParamPattern::Any,
+            Rule::output_operation_param_rest => ParamPattern::Rest,
+            // 尝试作为Narsese词项解析 | 无法使用 *narsese.get_term()强制转换成词项
+            _ => ParamPattern::Term(match FORMAT_ASCII.parse(inner.as_str())? {
+                Narsese::Term(term)
+                | Narsese::Sentence(Sentence { term, .. })
+                | Narsese::Task(Task {
+                    sentence: Sentence { term, .. },
+                    ..
+                }) => term,
+            }),
         };
         // 添加到参数中
-        params.push(term);
+        params.push(param);
     }
     // 返回
-    Ok(Operation {
+    Ok(OperationExpectation {
         operator_name,
         params,
     })
 }
 
+/// 解析「功能要求」中的功能名
+/// * 📄`operations temporal`、`operations, temporal`
+/// * ⚠️空列表、未知的功能名均报错
+fn parse_capability_names(names_raw: &str) -> Result<Vec<String>> {
+    let names = names_raw
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        return Err(anyhow::anyhow!("「功能要求」中缺少功能名"));
+    }
+    if let Some(name) = names
+        .iter()
+        .find(|name| !Capabilities::NAMES.contains(&name.as_str()))
+    {
+        return Err(anyhow::anyhow!(
+            "未知的功能「{name}」，可用的功能：{}",
+            Capabilities::NAMES.join(", ")
+        ));
+    }
+    Ok(names)
+}
+
+/// 解析时间
+/// * 📄`1s`、`0.5s`、`500ms`、`500us`、`500μs`、`100ns`、`1m`、`1min`
+/// * 📄复合形式：`1s500ms`、`1m 30s` | 从左到右累加
+/// * 📌数值与单位之间可有空白；所有单位均支持小数
+/// * ⚠️负数、缺少单位、未知单位、溢出均报错
 fn parse_duration(duration_raw: &str) -> Result<Duration> {
-    Ok(first! {
-        // 毫秒→微秒→纳秒→秒 | 对于「秒」分「整数」「浮点」两种
-        duration_raw.ends_with("ms") => Duration::from_millis(duration_raw.strip_suffix("ms").unwrap().parse()?),
-        duration_raw.ends_with("μs") => Duration::from_micros(duration_raw.strip_suffix("μs").unwrap().parse()?),
-        duration_raw.ends_with("ns") => Duration::from_nanos(duration_raw.strip_suffix("ns").unwrap().parse()?),
-        duration_raw.ends_with('s') && duration_raw.contains('.') => Duration::try_from_secs_f64(duration_raw.strip_suffix('s').unwrap().parse()?)?,
-        duration_raw.ends_with('s') => Duration::from_secs(duration_raw.strip_suffix('s').unwrap().parse()?),
-        // 否则报错
-        _ => return Err(anyhow::anyhow!("未知的睡眠时间参数 {duration_raw:?}"))
-    })
+    let error = |reason: &str| anyhow::anyhow!("无效的时间参数 {duration_raw:?}：{reason}");
+    let mut rest = duration_raw.trim();
+    if rest.is_empty() {
+        return Err(error("为空"));
+    }
+    // 总纳秒数
+    let mut total_nanos = 0_u128;
+    while !rest.is_empty() {
+        if rest.starts_with('-') {
+            return Err(error("不能为负"));
+        }
+        // 数值
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(number_len);
+        if number.is_empty() {
+            return Err(error(&format!("缺少数值（位于{rest:?}）")));
+        }
+        // 单位 | 取连续的字母，以区分`m`、`ms`与`min`
+        let tail = tail.trim_start();
+        let unit_len = tail
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let unit_nanos = match unit {
+            "ns" => 1,
+            "us" | "μs" => 1_000,
+            "ms" => 1_000_000,
+            "s" => 1_000_000_000,
+            "m" | "min" => 60_000_000_000,
+            "" => return Err(error(&format!("数值{number:?}缺少单位"))),
+            _ => return Err(error(&format!("未知的时间单位{unit:?}"))),
+        };
+        // 累加
+        let nanos = decimal_nanos(number, unit_nanos).ok_or_else(|| error("数值无效或过大"))?;
+        total_nanos = total_nanos
+            .checked_add(nanos)
+            .ok_or_else(|| error("数值过大"))?;
+        rest = tail.trim_start();
+    }
+    // 转换为时长
+    let secs = u64::try_from(total_nanos / 1_000_000_000).map_err(|_| error("数值过大"))?;
+    Ok(Duration::new(secs, (total_nanos % 1_000_000_000) as u32))
+}
+
+/// 将十进制数值按单位换算为纳秒
+/// * 🚩整数、小数部分分别换算，避免浮点误差 | 不足一纳秒的部分截断
+/// * 📌格式无效或溢出⇒[`None`]
+fn decimal_nanos(number: &str, unit_nanos: u128) -> Option<u128> {
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let valid = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !valid(integer) || !valid(fraction) {
+        return None;
+    }
+    let integer = match integer {
+        "" => 0,
+        _ => integer.parse::<u128>().ok()?,
+    };
+    // 小数部分最多保留18位：足以精确到纳秒，且不会溢出
+    let fraction = &fraction[..fraction.len().min(18)];
+    let fraction_nanos = match fraction {
+        "" => 0,
+        _ => fraction.parse::<u128>().ok()? * unit_nanos / 10_u128.pow(fraction.len() as u32),
+    };
+    integer.checked_mul(unit_nanos)?.checked_add(fraction_nanos)
+}
+
+/// 解析比例
+/// * 📄`0.8`、`80%`
+/// * ⚠️须在`[0, 1]`之间
+fn parse_rate(rate_raw: &str) -> Result<Rate> {
+    let rate_raw = rate_raw.trim();
+    let rate = match rate_raw.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>()? / 100.0,
+        None => rate_raw.parse::<f64>()?,
+    };
+    match (0.0..=1.0).contains(&rate) {
+        true => Ok(Rate(rate)),
+        false => Err(anyhow::anyhow!("比例 {rate_raw:?} 不在0~1之间")),
+    }
+}
+
+/// 解析「每次步长」
+/// * 🎯循环预期中的步长：为`0`时永不步进，循环不会结束
+/// * 🚩`0`⇒解析时即报错
+fn parse_step_cycles(step_raw: &str) -> Result<usize> {
+    match step_raw.parse::<usize>()? {
+        0 => Err(anyhow::anyhow!("每次步长 {step_raw:?} 不能为0")),
+        step_cycles => Ok(step_cycles),
+    }
 }
 
 /// 单元测试
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use narsese::lexical_nse_term as nse_term;
     use util::{for_in_ifs, list};
 
     pub const TESTSET: &str = "\
@@ -310,6 +740,8 @@ pub mod tests {
 ' * 📝统一的NAL测试语法：`''expect-contains: 【输出类别】 【其它内容】`
 '   * 📄预期「回答」：`''expect-contains: ANSWER 【CommonNarsese】`
 '   * 📄预期「操作」：`''expect-contains: EXE (^【操作名】, 【操作参数（CommonNarsese词项）】)`
+'     * 📄操作参数可为通配符：`*`⇒任意一个参数，`..`⇒其余的任意个参数（只能位于末尾）
+'   * 📄限定延迟：`''expect-contains(2000ms): ANSWER 【CommonNarsese】`
 
 '/VOL 0
 <A --> B>.
@@ -318,6 +750,7 @@ pub mod tests {
 5
 ''sleep: 1s
 ''expect-contains: ANSWER <A --> C>.
+''expect-contains(5s): ANSWER <A --> C>.
 
 A3. :|:
 <(*, {SELF}, (*, P1, P2)) --> ^left>. :|:
@@ -338,6 +771,7 @@ G3! :|:
         _test_parse("'/VOL 0");
         _test_parse("'''VOL 0");
         _test_parse("''await: OUT <A --> B>.");
+        _test_parse("''set: VOL 0");
         _test_parse("''sleep: 500ms");
         _test_parse("''sleep: 5000μs");
         _test_parse("''sleep: 600ns");
@@ -345,6 +779,712 @@ G3! :|:
         _test_parse(TESTSET);
     }
 
+    /// 测试/终止指令的选项
+    #[test]
+    fn test_parse_terminate_options() {
+        let terminate = |nal| match parse_single(nal).unwrap() {
+            NALInput::Terminate {
+                if_not_user,
+                if_no_client,
+                ..
+            } => (if_not_user, if_no_client),
+            other => panic!("应为终止指令：{other:?}"),
+        };
+        assert_eq!(terminate("''terminate"), (false, false));
+        assert_eq!(terminate("''terminate(if-no-user)"), (true, false));
+        assert_eq!(terminate("''terminate(if-no-client)"), (false, true));
+    }
+
+    /// 测试/设置：与「置入」区分
+    #[test]
+    fn test_parse_set() {
+        assert_eq!(
+            parse_single("''set: VOL 0").unwrap(),
+            NALInput::Set(Cmd::VOL(0))
+        );
+        assert_eq!(
+            parse_single("'''VOL 0").unwrap(),
+            NALInput::Put(Cmd::VOL(0))
+        );
+        assert!(parse_single("''set: 不存在的指令").is_err());
+    }
+
+    /// 测试/参数设置：置入`SET`指令
+    #[test]
+    fn test_parse_set_param() {
+        assert_eq!(
+            parse_single("''set-param: decisionthreshold = 0.6").unwrap(),
+            NALInput::Put(param_cmd("decisionthreshold", "0.6"))
+        );
+        assert!(parse_single("''set-param: decisionthreshold").is_err());
+        assert!(parse_single("''set-param: =0.6").is_err());
+    }
+
+    /// 测试/输出预期的最大延迟
+    #[test]
+    fn test_parse_expect_contains_latency() {
+        let expectation = |nal| match parse_single(nal).unwrap() {
+            NALInput::ExpectContains(expectation, max_latency) => (expectation, max_latency),
+            input => panic!("解析结果不是「输出预期」：{input:?}"),
+        };
+        let (plain, none) = expectation("''expect-contains: ANSWER <A --> C>.");
+        let (timed, some) = expectation("''expect-contains(2000ms): ANSWER <A --> C>.");
+        assert_eq!(plain, timed);
+        assert_eq!(none, None);
+        assert_eq!(some, Some(Duration::from_millis(2000)));
+        let (_, some) = expectation("''expect-contains( 1.5s ): ANSWER <A --> C>.");
+        assert_eq!(some, Some(Duration::from_millis(1500)));
+        assert!(parse_single("''expect-contains(两秒): ANSWER <A --> C>.").is_err());
+    }
+
+    /// 测试/解析时间
+    #[test]
+    fn test_parse_duration() {
+        // 有效输入
+        for (raw, expected) in [
+            // 原有的形式
+            ("1s", Duration::from_secs(1)),
+            ("0.5s", Duration::from_millis(500)),
+            ("2000ms", Duration::from_millis(2000)),
+            ("500μs", Duration::from_micros(500)),
+            ("100ns", Duration::from_nanos(100)),
+            (" 1.5s ", Duration::from_millis(1500)),
+            // ASCII的微秒
+            ("500us", Duration::from_micros(500)),
+            // 所有单位均支持小数
+            ("1.5ms", Duration::from_micros(1500)),
+            ("0.25us", Duration::from_nanos(250)),
+            ("1.9ns", Duration::from_nanos(1)),
+            (".5s", Duration::from_millis(500)),
+            ("1.s", Duration::from_secs(1)),
+            // 数值与单位之间的空白
+            ("1 s", Duration::from_secs(1)),
+            ("20 ms", Duration::from_millis(20)),
+            // 分钟
+            ("1m", Duration::from_secs(60)),
+            ("2min", Duration::from_secs(120)),
+            ("0.5m", Duration::from_secs(30)),
+            // 复合形式
+            ("1s500ms", Duration::from_millis(1500)),
+            ("1m 30s", Duration::from_secs(90)),
+            ("1min30s500ms", Duration::from_millis(90500)),
+            ("1ms1ms", Duration::from_millis(2)),
+        ] {
+            assert_eq!(parse_duration(raw).unwrap(), expected, "{raw:?}");
+        }
+        // 无效输入
+        for (raw, reason) in [
+            ("", "为空"),
+            ("  ", "为空"),
+            ("-1s", "不能为负"),
+            ("1s-500ms", "不能为负"),
+            ("1", "缺少单位"),
+            ("1s 500", "缺少单位"),
+            ("ms", "缺少数值"),
+            ("+1s", "缺少数值"),
+            ("1h", "未知的时间单位"),
+            ("1sec", "未知的时间单位"),
+            ("两秒", "缺少数值"),
+            ("1.2.3s", "数值无效"),
+            (".s", "数值无效"),
+            ("99999999999999999999999999999999999999999s", "过大"),
+            ("18446744073709551616s", "过大"),
+            ("18446744073709551615s1s", "过大"),
+        ] {
+            let e = parse_duration(raw).unwrap_err().to_string();
+            assert!(e.contains(reason), "{raw:?}：{e}");
+        }
+    }
+
+    /// 测试/睡眠与循环预期中的时间
+    #[test]
+    fn test_parse_sleep() {
+        assert_eq!(
+            parse_single("''sleep: 1 s").unwrap(),
+            NALInput::Sleep(Duration::from_secs(1))
+        );
+        assert_eq!(
+            parse_single("''sleep: 1s500ms").unwrap(),
+            NALInput::Sleep(Duration::from_millis(1500))
+        );
+        assert!(parse_single("''sleep: -1s").is_err());
+        assert_eq!(
+            parse_single("''await-idle: 300ms 1s500ms").unwrap(),
+            NALInput::AwaitIdle {
+                quiet_period: Duration::from_millis(300),
+                max_wait: Duration::from_millis(1500),
+            }
+        );
+        // 缺少最长等待时间⇒不视作「等待CIN安静下来」
+        assert!(!matches!(
+            parse_single("''await-idle: 300ms"),
+            StdOk(NALInput::AwaitIdle { .. })
+        ));
+        match parse_single("''expect-cycle(10, 1, 1.5ms): ANSWER <A --> C>.").unwrap() {
+            NALInput::ExpectCycle(_, _, step_duration, _) => {
+                assert_eq!(step_duration, Some(Duration::from_micros(1500)))
+            }
+            input => panic!("解析结果不是「循环预期」：{input:?}"),
+        }
+    }
+
+    /// 测试/循环否定预期
+    #[test]
+    fn test_parse_expect_cycle_not() {
+        let expectation = OutputExpectation {
+            output_type: Some("EXE".into()),
+            operation: Some(OperationExpectation {
+                operator_name: "left".into(),
+                params: vec![ParamPattern::Term(nse_term!({ SELF }))],
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_single("''expect-cycle-not(500, 10): EXE (^left, {SELF})").unwrap(),
+            NALInput::ExpectCycleNot(500, 10, None, expectation.clone())
+        );
+        assert_eq!(
+            parse_single("''expect-cycle-not(500, 10, 1ms): EXE (^left, {SELF})").unwrap(),
+            NALInput::ExpectCycleNot(500, 10, Some(Duration::from_millis(1)), expectation)
+        );
+        // 肯定的循环预期不受影响
+        assert!(matches!(
+            parse_single("''expect-cycle(500, 10): EXE (^left, {SELF})").unwrap(),
+            NALInput::ExpectCycle(..)
+        ));
+        // 步长为0⇒两种形式均报错
+        assert!(parse_single("''expect-cycle-not(500, 0): EXE (^left, {SELF})").is_err());
+        assert!(parse_single("''expect-cycle(500, 0): EXE (^left, {SELF})").is_err());
+    }
+
+    /// 测试/OpenNARS风格的输出预期
+    /// * 📄节选自OpenNARS的`nal1.1.nal`
+    #[test]
+    fn test_parse_output_must_contain() {
+        const NAL_1_1: &str = "\
+'Deduction
+
+'Bird is a type of animal.
+<bird --> animal>.
+
+'Robin is a type of bird.
+<robin --> bird>.
+
+3
+
+'Robin is a type of animal.
+''outputMustContain('<robin --> animal>. %1.00;0.81%')
+
+'I guess that animal is a type of robin.
+''outputMustContain('<animal --> robin>. %1.00;0.45%')";
+        let nse = |narsese| {
+            NALInput::Put(Cmd::NSE(
+                FORMAT_ASCII
+                    .parse(narsese)
+                    .unwrap()
+                    .try_into_task_compatible()
+                    .unwrap(),
+            ))
+        };
+        let rem = |comment: &str| {
+            NALInput::Put(Cmd::REM {
+                comment: comment.into(),
+            })
+        };
+        let must_contain = |narsese| {
+            NALInput::ExpectContains(
+                OutputExpectation {
+                    narsese: Some(FORMAT_ASCII.parse(narsese).unwrap()),
+                    ..Default::default()
+                },
+                None,
+            )
+        };
+        let inputs = parse(NAL_1_1)
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            inputs,
+            [
+                rem("Deduction"),
+                rem("Bird is a type of animal."),
+                nse("<bird --> animal>."),
+                rem("Robin is a type of bird."),
+                nse("<robin --> bird>."),
+                NALInput::Put(Cmd::CYC(3)),
+                rem("Robin is a type of animal."),
+                must_contain("<robin --> animal>. %1.00;0.81%"),
+                rem("I guess that animal is a type of robin."),
+                must_contain("<animal --> robin>. %1.00;0.45%"),
+            ]
+        );
+        // 否定⇒不步进的「循环否定预期」
+        assert_eq!(
+            parse_single("''outputMustNotContain('<robin --> animal>. %1.00;0.81%')").unwrap(),
+            NALInput::ExpectCycleNot(
+                0,
+                1,
+                None,
+                OutputExpectation {
+                    narsese: Some(
+                        FORMAT_ASCII
+                            .parse("<robin --> animal>. %1.00;0.81%")
+                            .unwrap()
+                    ),
+                    ..Default::default()
+                }
+            )
+        );
+        // 双引号、转义的引号
+        let expectation = |nal| match parse_single(nal).unwrap() {
+            NALInput::ExpectContains(expectation, _) => expectation,
+            input => panic!("解析结果不是「输出预期」：{input:?}"),
+        };
+        assert_eq!(
+            expectation(r#"''outputMustContain("<A --> B>.")"#),
+            expectation("''outputMustContain('<A --> B>.')")
+        );
+        // 非Narsese⇒按原始内容的子串匹配
+        let e = expectation(r"''outputMustContain('it\'s (1+1)')");
+        assert_eq!(e.narsese, None);
+        assert_eq!(e.raw_regex.unwrap().as_str(), r"it's \(1\+1\)");
+        let e = expectation(r#"''outputMustContain("say \"hi\"")"#);
+        assert_eq!(e.raw_regex.unwrap().as_str(), r#"say "hi""#);
+    }
+
+    /// 测试/操作参数的通配符
+    #[test]
+    fn test_parse_operation_wildcards() {
+        let operation = |nal: &str| match parse_single(nal) {
+            StdOk(NALInput::ExpectContains(expectation, _)) => {
+                expectation.operation.expect("应有操作")
+            }
+            result => panic!("解析结果不是「含有预期」：{result:?}"),
+        };
+        // 中间的`*`
+        assert_eq!(
+            operation("''expect-contains: EXE (^left, *, {SELF})"),
+            OperationExpectation {
+                operator_name: "left".into(),
+                params: vec![ParamPattern::Any, ParamPattern::Term(nse_term!({ SELF }))],
+            }
+        );
+        // 末尾的`..`
+        assert_eq!(
+            operation("''expect-contains: EXE (^left, {SELF}, ..)"),
+            OperationExpectation {
+                operator_name: "left".into(),
+                params: vec![ParamPattern::Term(nse_term!({ SELF })), ParamPattern::Rest],
+            }
+        );
+        // 乘积词项中的`*`不受影响
+        assert_eq!(
+            operation("''expect-contains: EXE (^left, (*, a, b), *)").params,
+            [ParamPattern::Term(nse_term!((*, a, b))), ParamPattern::Any]
+        );
+        // `..`不在末尾⇒报错
+        let e = parse_single("''expect-contains: EXE (^left, .., {SELF})").unwrap_err();
+        assert!(e.to_string().contains(".."), "{e}");
+    }
+
+    /// 测试/比例预期
+    #[test]
+    fn test_parse_expect_rate() {
+        let expected = |threshold| NALInput::ExpectRate {
+            max_cycles: 1000,
+            step_cycles: 10,
+            threshold,
+            expectation: OutputExpectation {
+                output_type: Some("EXE".into()),
+                operation: Some(OperationExpectation {
+                    operator_name: "left".into(),
+                    params: vec![ParamPattern::Term(nse_term!({ SELF }))],
+                }),
+                ..Default::default()
+            },
+        };
+        assert_eq!(
+            parse_single("''expect-rate(1000, 10, 80%): EXE (^left, {SELF})").unwrap(),
+            expected(Rate(0.8))
+        );
+        assert_eq!(
+            parse_single("''expect-rate(1000, 10, 0.5): EXE (^left, {SELF})").unwrap(),
+            expected(Rate(0.5))
+        );
+        assert!(parse_single("''expect-rate(1000, 10, 120%): EXE (^left, {SELF})").is_err());
+        // 步长为0⇒报错
+        assert!(parse_single("''expect-rate(1000, 0, 80%): EXE (^left, {SELF})").is_err());
+        assert!(parse_single("''expect-rate(1000, 10, 八成): EXE (^left, {SELF})").is_err());
+    }
+
+    /// 测试/原始内容预期
+    #[test]
+    fn test_parse_raw_regex() {
+        let expectation = |nal| match parse_single(nal).unwrap() {
+            NALInput::ExpectContains(expectation, _) => expectation,
+            input => panic!("解析结果不是「输出预期」：{input:?}"),
+        };
+        // 仅「类型」与「原始内容」
+        let e = expectation(r"''expect-contains: OUT /frequency=1\.000000/");
+        assert_eq!(e.output_type.as_deref(), Some("OUT"));
+        assert_eq!(e.narsese, None);
+        assert_eq!(e.raw_regex.unwrap().as_str(), r"frequency=1\.000000");
+        // 仅「原始内容」
+        let e = expectation("''expect-contains: /executed/");
+        assert_eq!(e.output_type, None);
+        assert_eq!(e.raw_regex.unwrap().as_str(), "executed");
+        // 与Narsese结合 | 转义的`/`
+        let e = expectation(r"''expect-contains: ANSWER <A --> C>. /a\/b/");
+        assert_eq!(e.narsese, Some(FORMAT_ASCII.parse("<A --> C>.").unwrap()));
+        assert_eq!(e.raw_regex.unwrap().as_str(), "a/b");
+        // 正则表达式有误⇒解析时报错，并附带行号
+        let results = parse("<A --> B>.\n''expect-contains: OUT /frequency=(/");
+        let e = results[1].as_ref().expect_err("应该解析失败");
+        assert!(e.to_string().starts_with("第2行"), "{e}");
+    }
+
+    /// 测试/含有子词项的预期
+    #[test]
+    fn test_parse_expect_contains_term() {
+        let expected = |max_latency| {
+            NALInput::ExpectContains(
+                OutputExpectation {
+                    output_type: Some("OUT".into()),
+                    narsese: Some(FORMAT_ASCII.parse("^left").unwrap()),
+                    term_match: TermMatch::Contains,
+                    ..Default::default()
+                },
+                max_latency,
+            )
+        };
+        assert_eq!(
+            parse_single("''expect-contains-term: OUT ^left").unwrap(),
+            expected(None)
+        );
+        assert_eq!(
+            parse_single("''expect-contains-term(2s): OUT ^left").unwrap(),
+            expected(Some(Duration::from_secs(2)))
+        );
+        // 普通的「输出包含」不受影响
+        match parse_single("''expect-contains: OUT ^left").unwrap() {
+            NALInput::ExpectContains(expectation, None) => {
+                assert_eq!(expectation.term_match, TermMatch::Exact)
+            }
+            input => panic!("解析结果不是「输出预期」：{input:?}"),
+        }
+    }
+
+    /// 测试/等待回显
+    #[test]
+    fn test_parse_await_echo() {
+        assert_eq!(
+            parse_single("''await-echo: <A --> B>.").unwrap(),
+            NALInput::Await(OutputExpectation {
+                output_type: Some("IN".into()),
+                narsese: Some(FORMAT_ASCII.parse("<A --> B>.").unwrap()),
+                echo: Some(EchoMatchOptions::default()),
+                ..Default::default()
+            })
+        );
+        // 普通的「等待」不受影响
+        match parse_single("''await: IN <A --> B>.").unwrap() {
+            NALInput::Await(expectation) => assert_eq!(expectation.echo, None),
+            input => panic!("解析结果不是「等待」：{input:?}"),
+        }
+    }
+
+    /// 测试/精度：单个预期的`(±…)`与文件级的`''precision:`
+    #[test]
+    fn test_parse_precision() {
+        // 单个预期
+        let NALInput::ExpectContains(expectation, max_latency) =
+            parse_single("''expect-contains(±0.05): ANSWER <A --> C>. %1.0;0.4%").unwrap()
+        else {
+            panic!("应为「输出包含」预期");
+        };
+        assert_eq!(expectation.precision, Some(FloatPrecision(0.05)));
+        assert_eq!(max_latency, None);
+        // 与「最大延迟」同用
+        let NALInput::ExpectContains(expectation, max_latency) =
+            parse_single("''expect-contains(±0.05, 2s): ANSWER <A --> C>.").unwrap()
+        else {
+            panic!("应为「输出包含」预期");
+        };
+        assert_eq!(expectation.precision, Some(FloatPrecision(0.05)));
+        assert_eq!(max_latency, Some(Duration::from_secs(2)));
+        // 文件级：作用于此后未指定精度的预期
+        let inputs = parse(
+            "''expect-contains: OUT <A --> B>.\n\
+             ''precision: 0.01\n\
+             ''await: OUT <A --> B>.\n\
+             ''expect-contains(±0.1): OUT <A --> B>.",
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+        let precisions = inputs
+            .into_iter()
+            .map(|mut input| input.expectation_mut().and_then(|e| e.precision))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            precisions,
+            [
+                None,
+                None,
+                Some(FloatPrecision(0.01)),
+                Some(FloatPrecision(0.1))
+            ]
+        );
+        // 无效精度
+        assert!(parse_single("''precision: -1").is_err());
+        assert!(parse_single("''precision: abc").is_err());
+        assert!(parse_single("''expect-contains(±x): OUT <A --> B>.").is_err());
+    }
+
+    /// 测试/时间容差：与「精度」「最大延迟」同用
+    #[test]
+    fn test_parse_tick_tolerance() {
+        let parse_expectation = |nal| {
+            let NALInput::ExpectContains(expectation, max_latency) = parse_single(nal).unwrap()
+            else {
+                panic!("应为「输出包含」预期");
+            };
+            (expectation, max_latency)
+        };
+        let (expectation, _) = parse_expectation("''expect-contains(±3t): OUT <A --> B>. :!10:");
+        assert_eq!(expectation.tick_tolerance, Some(3));
+        assert_eq!(expectation.precision, None);
+        let (expectation, max_latency) =
+            parse_expectation("''expect-contains(±0.05, ±3t, 2s): OUT <A --> B>. :!10:");
+        assert_eq!(expectation.tick_tolerance, Some(3));
+        assert_eq!(expectation.precision, Some(FloatPrecision(0.05)));
+        assert_eq!(max_latency, Some(Duration::from_secs(2)));
+        let (expectation, _) = parse_expectation("''expect-contains: OUT <A --> B>. :|:");
+        assert_eq!(expectation.tick_tolerance, None);
+    }
+
+    /// 测试/「无回答」预期
+    #[test]
+    fn test_parse_no_answer() {
+        let expected = OutputExpectation {
+            output_type: Some(crate::runtimes::NO_ANSWER.into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_single("''expect-contains: NO_ANSWER").unwrap(),
+            NALInput::ExpectContains(expected, None)
+        );
+    }
+
+    /// 测试/重置
+    #[test]
+    fn test_parse_reset() {
+        assert_eq!(
+            parse_single("''reset").unwrap(),
+            NALInput::Put(Cmd::RES {
+                target: RESET_TARGET.into()
+            })
+        );
+        // 重置预期窗口 | 不被误认作`''reset`
+        assert_eq!(
+            parse_single("''reset-expect-window").unwrap(),
+            NALInput::ResetExpectWindow
+        );
+        // 仅以`reset`开头的普通注释 | 不被误认作`''reset`
+        assert_eq!(
+            parse_single("''resetting the counters").unwrap(),
+            NALInput::Put(Cmd::REM {
+                comment: "'resetting the counters".into()
+            })
+        );
+    }
+
+    /// 测试/报告运行状态
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(parse_single("''status").unwrap(), NALInput::Status);
+        // 仅以`status`开头的普通注释 | 不被误认作`''status`
+        assert_eq!(
+            parse_single("''status of the test").unwrap(),
+            NALInput::Put(Cmd::REM {
+                comment: "'status of the test".into()
+            })
+        );
+    }
+
+    /// 测试/功能要求
+    #[test]
+    fn test_parse_requires() {
+        assert_eq!(
+            parse_single("''requires: operations, temporal").unwrap(),
+            NALInput::Requires(vec!["operations".into(), "temporal".into()])
+        );
+        assert_eq!(
+            parse_single("''requires:answers").unwrap(),
+            NALInput::Requires(vec!["answers".into()])
+        );
+        assert_eq!(
+            parse_single("''end-requires").unwrap(),
+            NALInput::EndRequires
+        );
+        // 未知的功能名、缺少功能名⇒报错
+        let e = parse_single("''requires: operation")
+            .unwrap_err()
+            .to_string();
+        assert!(e.contains("operation") && e.contains("registration"), "{e}");
+        assert!(parse_single("''requires: ").is_err());
+    }
+
+    /// 测试/保存输出的文件格式
+    #[test]
+    fn test_parse_save_outputs() {
+        let expected = [
+            (
+                "''save-outputs: out.json",
+                SaveOutputsFormat::Json,
+                "out.json",
+            ),
+            (
+                "''save-outputs(json): out.json",
+                SaveOutputsFormat::Json,
+                "out.json",
+            ),
+            (
+                "''save-outputs(nal): out.nal",
+                SaveOutputsFormat::Nal,
+                "out.nal",
+            ),
+            (
+                "''save-outputs(csv): out.csv",
+                SaveOutputsFormat::Csv,
+                "out.csv",
+            ),
+        ];
+        for (input, format, path) in expected {
+            assert_eq!(
+                parse_single(input).unwrap(),
+                NALInput::SaveOutputs {
+                    format,
+                    path: path.into()
+                },
+                "{input}"
+            );
+        }
+        // 未知格式⇒不视作「保存输出」
+        assert!(!matches!(
+            parse_single("''save-outputs(xml): out.xml"),
+            StdOk(NALInput::SaveOutputs { .. })
+        ));
+    }
+
+    /// 测试/包含文件
+    #[test]
+    fn test_parse_include() {
+        assert_eq!(
+            parse_single("''include: ./env.nal").unwrap(),
+            NALInput::Include("./env.nal".into())
+        );
+    }
+
+    /// 测试/多行Narsese
+    /// * 📄语句取自OpenNARS的NAL-6测试文件
+    #[test]
+    fn test_parse_multiline() {
+        const MULTILINE: &str = "\
+'多行语句\\
+<(&&,<$x --> flyer>,<$x --> [chirping]>, \\
+    <(*, $x, worms) --> food>) \\
+    ==> <$x --> bird>>.
+
+5";
+        let results = list![
+            (r.expect("解析失败！"))
+            for r in (parse(MULTILINE))
+        ];
+        let expected = parse_single(
+            "<(&&,<$x --> flyer>,<$x --> [chirping]>, <(*, $x, worms) --> food>) ==> <$x --> bird>>.",
+        )
+        .expect("解析失败！");
+        // 注释不续行、多行语句合并为一个输入、空行照常分隔
+        assert_eq!(results.len(), 3);
+        assert!(
+            matches!(&results[0], NALInput::Put(Cmd::REM { comment }) if comment.ends_with('\\'))
+        );
+        assert_eq!(results[1], expected);
+        assert_eq!(results[2], NALInput::Put(Cmd::CYC(5)));
+    }
+
+    /// 测试/续行符须与前文以空白隔开
+    /// * 🎯行末紧贴前文的`\`属于Narsese：不与下一行合并
+    #[test]
+    fn test_multiline_backslash() {
+        let results = join_lines("<A --> (\\\n<B --> C>.");
+        assert_eq!(results.len(), 2, "{results:?}");
+        assert_eq!(results[0], (1, "<A --> (\\".into()));
+        assert_eq!(results[1], (2, "<B --> C>.".into()));
+        // 以空白隔开⇒续行
+        let results = join_lines("<A --> \\\n  B>.");
+        assert_eq!(results, [(1, "<A --> B>.".into())]);
+    }
+
+    /// 测试/注释行截断续行
+    /// * 🎯续行之后紧跟注释行⇒注释不被并入续行，此后的输入也不受影响
+    #[test]
+    fn test_multiline_comment() {
+        let results = join_lines("<A --> \\\n'注释\n<B --> C>.");
+        assert_eq!(
+            results,
+            [
+                (1, "<A -->".into()),
+                (2, "'注释".into()),
+                (3, "<B --> C>.".into())
+            ]
+        );
+    }
+
+    /// 测试/错误行号
+    /// * 🎯多行输入时，错误信息应指向（逻辑行的）起始行号
+    #[test]
+    fn test_parse_error_line() {
+        let results = parse("<A --> B>.\n\n<A --> \\\n  B\n\n<A --> C>?");
+        assert_eq!(results.len(), 3);
+        let e = results[1].as_ref().expect_err("应该解析失败");
+        assert!(e.to_string().starts_with("第3行"), "{e}");
+        assert!(results[2].is_ok());
+    }
+
+    /// 测试/错误的来源与行号
+    /// * 🎯错误信息以`文件名:行号:`开头，并附带出错的原始行
+    #[test]
+    fn test_parse_error_source() {
+        let nal = "\
+<A --> B>.
+<B --> C>.
+5
+<A --> C>?
+''expect-contains: ANSWER <A --> C>.
+
+<(&&, A, B) --> >.
+<C --> D>.";
+        let results = parse_with_source(nal, "tests/bad.nal");
+        let errors = results
+            .iter()
+            .filter_map(|(line_no, result)| Some((line_no, result.as_ref().err()?)))
+            .collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        let (line_no, e) = errors[0];
+        assert_eq!(*line_no, 7);
+        assert!(
+            e.to_string()
+                .starts_with("tests/bad.nal:7: 「<(&&, A, B) --> >.」解析失败："),
+            "{e}"
+        );
+        assert_eq!(source_line(nal, 7), "<(&&, A, B) --> >.");
+        assert_eq!(source_line(nal, 0), "");
+        assert_eq!(source_line(nal, 100), "");
+    }
+
     fn _test_parse(input: &str) {
         let results = parse(input);
         let results = list![
@@ -0,0 +1,654 @@
+//! 以库函数的形式运行NAL测试
+//! * 🎯不经由CLI，直接在Rust代码中「对一个CIN运行一段NAL，并取得结构化的结果」
+//!   * 📄集成测试、跨CIN测试、外部工具
+//! * 🚩启动、输入、拉取剩余输出、终止，均在此完成
+//!   * 📌CIN在独立的线程中运行；调用方所在线程充当「看门狗」，负责计时
+//!   * 📌超时、严格模式中止、运行线程panic时，均会终止CIN
+//!   * 📌超时⇒看门狗经[`KillHandle`]直接终止CIN：即便运行线程正阻塞于某条输入
+
+use super::{
+    nal_format::parse_with_line_no, put_nal, with_nal_line_no, CycAwait, FloatPrecision,
+    InteractivityState, NALInput, PutNalContext, RequiresRegion, SetAck,
+};
+use crate::runtimes::{Capabilities, KillHandle, VmRuntimeBoost};
+use anyhow::{anyhow, Result};
+use navm::{output::Output, vm::VmLauncher};
+use std::{
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// 默认的超时时间
+/// * 🎯单个CIN运行完整段NAL的最长时间
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 超时终止CIN后，等待运行线程退出的最长时间
+/// * 🎯回收运行线程：CIN被终止后，阻塞的输入随之返回
+/// * 📌不提供[`KillHandle`]的运行时无法被直接终止：不等待
+const RUNNER_JOIN_GRACE: Duration = Duration::from_secs(5);
+
+/// 等待运行线程结束时，轮询的间隔
+const RUNNER_JOIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// NAL测试的选项
+#[derive(Debug, Clone)]
+pub struct TestOptions {
+    /// 严格模式
+    /// * 🚩任一输入出错⇒中止测试，其后的预期均记为[`ExpectationResult::Skipped`]
+    /// * 📌非严格模式下，非预期输入的错误被忽略
+    pub strict_mode: bool,
+
+    /// 超时时间
+    /// * 🚩从开始启动时计时，包括启动所用的时间
+    pub timeout: Duration,
+
+    /// 默认精度
+    /// * 🎯为未指定精度（且不在`''precision:`之后）的预期填入精度
+    pub precision_epoch: Option<FloatPrecision>,
+
+    /// NAL执行路径 | 用于`''save-outputs:`等相对路径
+    pub nal_root_path: PathBuf,
+
+    /// 「设置」指令的回执
+    /// * 🎯`''set:`，参见[`SetAck`]
+    pub set_ack: SetAck,
+
+    /// 置入`CYC`后的等待方式
+    /// * 🎯免去NAL中为等待推理而加的`''sleep`，参见[`put_nal`]
+    /// * 🚩[`None`]⇒置入即返回
+    pub cyc_await: Option<CycAwait>,
+
+    /// CIN所支持的功能
+    /// * 🎯`''requires:`：缺少功能⇒相应预期记为[`ExpectationResult::Skipped`]
+    pub capabilities: Capabilities,
+}
+
+impl Default for TestOptions {
+    fn default() -> Self {
+        Self {
+            strict_mode: false,
+            timeout: DEFAULT_TIMEOUT,
+            precision_epoch: None,
+            nal_root_path: PathBuf::from("."),
+            set_ack: SetAck::default(),
+            cyc_await: None,
+            capabilities: Capabilities::ALL,
+        }
+    }
+}
+
+/// 单条预期的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectationResult {
+    /// 通过
+    Passed,
+    /// 未通过 | 附带错误信息
+    Failed(String),
+    /// 超时：在限定时间内未执行到此预期
+    TimedOut,
+    /// 未执行：CIN启动失败、线程异常退出等 | 附带原因
+    Skipped(String),
+}
+
+impl ExpectationResult {
+    /// 是否通过
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed)
+    }
+
+    /// 在Markdown表格中的标记
+    pub fn mark(&self) -> &'static str {
+        match self {
+            Self::Passed => "✅",
+            Self::Failed(..) => "❌",
+            Self::TimedOut => "⏱️",
+            Self::Skipped(..) => "➖",
+        }
+    }
+}
+
+/// 单条预期在测试中的情况
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectationOutcome {
+    /// 所在行号 | 从`1`开始
+    pub line_no: usize,
+
+    /// 预期的描述 | 与NAL文件中的写法一致
+    pub label: String,
+
+    /// 结果
+    pub result: ExpectationResult,
+
+    /// 得出结果的时刻（自测试开始）
+    /// * 🚩未执行（超时、被中止）⇒[`None`]
+    pub elapsed: Option<Duration>,
+}
+
+/// NAL测试的结果
+#[derive(Debug, Clone, Default)]
+pub struct TestOutcome {
+    /// 各预期的情况 | 按在NAL中的顺序
+    pub expectations: Vec<ExpectationOutcome>,
+
+    /// CIN的所有输出 | 按接收顺序
+    /// * ⚠️超时时，仍阻塞在运行线程中的那条输入，其输出不被收录
+    pub outputs: Vec<Output>,
+
+    /// 整个测试所用的时间 | 包括启动与终止
+    pub elapsed: Duration,
+}
+
+impl TestOutcome {
+    /// 是否通过了所有预期
+    pub fn is_all_passed(&self) -> bool {
+        self.expectations
+            .iter()
+            .all(|expectation| expectation.result.is_passed())
+    }
+
+    /// 获取各预期的结果
+    pub fn results(&self) -> impl Iterator<Item = &ExpectationResult> {
+        self.expectations
+            .iter()
+            .map(|expectation| &expectation.result)
+    }
+}
+
+/// 对一个CIN运行一段NAL测试
+/// * 🚩解析NAL⇒启动CIN⇒逐条输入⇒拉取剩余输出⇒终止CIN
+/// * 🚩NAL解析失败、CIN启动失败⇒返回错误
+/// * 🚩超时⇒终止CIN，尚未得出结果的预期记为[`ExpectationResult::TimedOut`]
+///   * 📌CIN正阻塞于某条输入（如`''await:`）时，亦直接终止
+///   * ⚠️运行时不提供[`KillHandle`]⇒须待该输入返回后才能终止
+/// * 📌`''include:`由[`put_nal`]展开：相对路径基于[`TestOptions::nal_root_path`]
+/// * 📌`''status`⇒以`INFO`输出报告已收到的输出数：此处没有CLI的运行统计
+pub fn run_nal_test<L>(launcher: L, nal: &str, options: TestOptions) -> Result<TestOutcome>
+where
+    L: VmLauncher + Send + 'static,
+    L::Runtime: VmRuntimeBoost + 'static,
+{
+    let mut inputs = vec![];
+    for (line_no, result) in parse_with_line_no(nal) {
+        let mut input = result?;
+        input.default_precision(options.precision_epoch);
+        inputs.push((line_no, input));
+    }
+    run_nal_inputs(move || launcher.launch(), inputs, options)
+}
+
+/// 同[`run_nal_test`]，但使用已解析的NAL输入
+/// * 🎯跨CIN测试：多个CIN共享同一份解析结果
+/// * 📌启动器为闭包：调用方可在其中完成额外的准备工作
+pub(super) fn run_nal_inputs<R: VmRuntimeBoost + 'static>(
+    launcher: impl FnOnce() -> Result<R> + Send + 'static,
+    inputs: Vec<(usize, NALInput)>,
+    options: TestOptions,
+) -> Result<TestOutcome> {
+    let start = Instant::now();
+    let deadline = start + options.timeout;
+    let labels = inputs
+        .iter()
+        .filter_map(|(line_no, input)| Some((*line_no, expectation_label(input)?)))
+        .collect::<Vec<_>>();
+
+    // 启动运行线程
+    let (sender, receiver) = channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let cancelled_in_thread = cancelled.clone();
+    let runner = thread::spawn(move || {
+        run_in_thread(launcher, inputs, options, cancelled_in_thread, sender)
+    });
+
+    // 看门狗：按截止时刻接收进度
+    let mut outcome = TestOutcome::default();
+    let mut results = vec![];
+    let mut kill_handle = None;
+    let rest = loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(remaining) {
+            Ok(Progress::Launched(handle)) => kill_handle = handle,
+            Ok(Progress::Outputs(outputs)) => outcome.outputs.extend(outputs),
+            Ok(Progress::Expectation(result)) => results.push((result, start.elapsed())),
+            Ok(Progress::LaunchFailed(e)) => return Err(e),
+            Ok(Progress::Aborted(reason)) => break ExpectationResult::Skipped(reason),
+            Ok(Progress::Finished) => break ExpectationResult::Skipped("未执行".into()),
+            Err(RecvTimeoutError::Timeout) => {
+                cancelled.store(true, Ordering::SeqCst);
+                if let Some(handle) = &kill_handle {
+                    handle.kill();
+                }
+                break ExpectationResult::TimedOut;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                break ExpectationResult::Skipped("运行线程异常退出".into())
+            }
+        }
+    };
+
+    // 回收运行线程 | CIN已被终止⇒至多等待片刻；否则不等待
+    let grace = match rest == ExpectationResult::TimedOut && kill_handle.is_some() {
+        true => RUNNER_JOIN_GRACE,
+        false => Duration::ZERO,
+    };
+    let join_deadline = Instant::now() + grace;
+    while !runner.is_finished() && Instant::now() < join_deadline {
+        thread::sleep(RUNNER_JOIN_POLL_INTERVAL);
+    }
+    if runner.is_finished() {
+        let _ = runner.join();
+    }
+
+    // 汇总
+    let mut results = results.into_iter();
+    outcome.expectations = labels
+        .into_iter()
+        .map(|(line_no, label)| {
+            let (result, elapsed) = match results.next() {
+                Some((result, elapsed)) => (result, Some(elapsed)),
+                None => (rest.clone(), None),
+            };
+            ExpectationOutcome {
+                line_no,
+                label,
+                result,
+                elapsed,
+            }
+        })
+        .collect();
+    outcome.elapsed = start.elapsed();
+    Ok(outcome)
+}
+
+/// 运行线程向看门狗报告的进度
+enum Progress {
+    /// 已启动 | 附带终止CIN的句柄
+    Launched(Option<KillHandle>),
+    /// 新的CIN输出
+    Outputs(Vec<Output>),
+    /// 一条预期的结果
+    Expectation(ExpectationResult),
+    /// 启动失败
+    LaunchFailed(anyhow::Error),
+    /// 严格模式下出错而中止 | 附带原因
+    Aborted(String),
+    /// 所有输入均已完成
+    Finished,
+}
+
+/// 运行中的CIN
+/// * 🎯离开作用域时（含panic）终止CIN：不残留子进程
+struct TerminateOnDrop<R: VmRuntimeBoost>(R);
+
+impl<R: VmRuntimeBoost> Deref for TerminateOnDrop<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        &self.0
+    }
+}
+
+impl<R: VmRuntimeBoost> DerefMut for TerminateOnDrop<R> {
+    fn deref_mut(&mut self) -> &mut R {
+        &mut self.0
+    }
+}
+
+impl<R: VmRuntimeBoost> Drop for TerminateOnDrop<R> {
+    fn drop(&mut self) {
+        if !self.0.is_terminated() {
+            let _ = self.0.terminate();
+        }
+    }
+}
+
+/// 在运行线程中启动CIN，并逐条输入
+/// * 🚩每条输入之后，报告新的输出；每条预期之后，报告其结果
+/// * 🚩「功能要求」区段中被跳过的预期⇒[`ExpectationResult::Skipped`]
+/// * 🚩看门狗已取消（超时）、不再接收（已返回）⇒提前结束
+fn run_in_thread<R: VmRuntimeBoost>(
+    launcher: impl FnOnce() -> Result<R>,
+    inputs: Vec<(usize, NALInput)>,
+    options: TestOptions,
+    cancelled: Arc<AtomicBool>,
+    sender: Sender<Progress>,
+) {
+    let mut vm = match launcher() {
+        Ok(vm) => TerminateOnDrop(vm),
+        Err(e) => {
+            let _ = sender.send(Progress::LaunchFailed(anyhow!("启动失败：{e}")));
+            return;
+        }
+    };
+    if sender.send(Progress::Launched(vm.kill_handle())).is_err() {
+        return;
+    }
+    let mut requires = RequiresRegion::new(options.capabilities);
+    let mut context = PutNalContext {
+        interactivity: &InteractivityState::default(),
+        nal_root_path: &options.nal_root_path,
+        set_ack: &options.set_ack,
+        cyc_await: options.cyc_await,
+        requires: &mut requires,
+    };
+    let mut output_cache: Vec<Output> = vec![];
+    let mut reported = 0;
+    for (line_no, input) in inputs {
+        if cancelled.load(Ordering::SeqCst) {
+            return;
+        }
+        let is_expectation = expectation_label(&input).is_some();
+        let requires = &context.requires;
+        let skipped = requires
+            .skips(&input)
+            .then(|| format!("CIN缺少功能「{}」", requires.missing().join(", ")));
+        let result = match input {
+            // 报告运行状态 | 不交给`put_nal`：其要求由调用方处理
+            NALInput::Status => {
+                output_cache.push(Output::INFO {
+                    message: format!("status: 已收到{}条输出", output_cache.len()),
+                });
+                Ok(())
+            }
+            input => put_nal(&mut *vm, input, &mut output_cache, &mut context)
+                .map_err(|e| with_nal_line_no(e, line_no)),
+        };
+        // 报告新的输出
+        let outputs = output_cache[reported..].to_vec();
+        reported = output_cache.len();
+        if !outputs.is_empty() && sender.send(Progress::Outputs(outputs)).is_err() {
+            return;
+        }
+        // 报告预期的结果
+        let aborted = match &result {
+            Err(e) if options.strict_mode && skipped.is_none() => Some(format!("严格模式：{e}")),
+            _ => None,
+        };
+        if is_expectation {
+            let result = match (skipped, result) {
+                (Some(reason), _) => ExpectationResult::Skipped(reason),
+                (None, Ok(())) => ExpectationResult::Passed,
+                (None, Err(e)) => ExpectationResult::Failed(e.to_string()),
+            };
+            if sender.send(Progress::Expectation(result)).is_err() {
+                return;
+            }
+        }
+        // 严格模式下出错⇒中止
+        if let Some(reason) = aborted {
+            let _ = sender.send(Progress::Aborted(reason));
+            return;
+        }
+    }
+    // 拉取剩余输出
+    if let Ok(outputs) = vm.drain_outputs() {
+        if !outputs.is_empty() {
+            let _ = sender.send(Progress::Outputs(outputs));
+        }
+    }
+    let _ = sender.send(Progress::Finished);
+}
+
+/// 获取「预期」的描述
+/// * 🚩非预期⇒[`None`]
+/// * 📌「输出预期」部分使用其[`Display`](std::fmt::Display)：与NAL文件中的写法一致
+pub(super) fn expectation_label(input: &NALInput) -> Option<String> {
+    match input {
+        NALInput::ExpectContains(expectation, None) => {
+            Some(format!("expect-contains: {expectation}"))
+        }
+        NALInput::ExpectContains(expectation, Some(max_latency)) => {
+            Some(format!("expect-contains({max_latency:?}): {expectation}"))
+        }
+        NALInput::ExpectCycle(max_cycles, _, _, expectation) => {
+            Some(format!("expect-cycle({max_cycles}): {expectation}"))
+        }
+        NALInput::ExpectCycleNot(max_cycles, _, _, expectation) => {
+            Some(format!("expect-cycle-not({max_cycles}): {expectation}"))
+        }
+        NALInput::ExpectRate {
+            max_cycles,
+            threshold,
+            expectation,
+            ..
+        } => Some(format!(
+            "expect-rate({max_cycles}, {threshold}): {expectation}"
+        )),
+        _ => None,
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtimes::{FakeVm, FakeVmRuntime};
+    use nar_dev_utils::manipulate;
+    use narsese::conversion::string::impl_lexical::shortcuts::*;
+    use navm::cmd::Cmd;
+
+    /// 测试用NAL
+    const NAL: &str = "\
+<A --> B>.
+<A --> B>?
+''expect-cycle(5, 1, 1ms): ANSWER <A --> B>.
+''expect-contains: ANSWER <B --> C>.";
+
+    /// 会回答的模拟CIN
+    fn answering() -> FakeVm {
+        manipulate!(
+            FakeVm::new()
+            => .respond_after_cycle(
+                Cmd::NSE(nse_task!(<A --> B>?)),
+                [Output::ANSWER {
+                    content_raw: "<A --> B>.".into(),
+                    narsese: Some(nse!(<A --> B>.)),
+                }],
+            )
+        )
+    }
+
+    /// 启动前先等待的模拟CIN
+    struct Slow(Duration);
+
+    impl VmLauncher for Slow {
+        type Runtime = FakeVmRuntime;
+
+        fn launch(self) -> Result<FakeVmRuntime> {
+            thread::sleep(self.0);
+            answering().launch()
+        }
+    }
+
+    /// 测试/结果、输出、计时
+    #[test]
+    fn test_run_nal_test() {
+        let outcome = run_nal_test(answering(), NAL, TestOptions::default()).expect("测试失败");
+        let results = outcome.results().collect::<Vec<_>>();
+        assert!(matches!(
+            results[..],
+            [ExpectationResult::Passed, ExpectationResult::Failed(..)]
+        ));
+        assert_eq!(outcome.expectations[0].line_no, 3);
+        assert_eq!(
+            outcome.expectations[1].label,
+            "expect-contains: ANSWER <B --> C>."
+        );
+        assert!(outcome.expectations.iter().all(|e| e.elapsed.is_some()));
+        assert!(!outcome.is_all_passed());
+        // 输出日志：两条输入的回显与回答
+        let answers = outcome
+            .outputs
+            .iter()
+            .filter(|output| matches!(output, Output::ANSWER { .. }))
+            .count();
+        assert_eq!(answers, 1);
+        assert!(outcome.outputs.len() >= 3, "{:?}", outcome.outputs);
+    }
+
+    /// 测试/严格模式：出错即中止
+    #[test]
+    fn test_strict_mode() {
+        let nal = "''expect-contains: OUT <C --> D>.\n''expect-contains: IN <C --> D>.";
+        let options = TestOptions {
+            strict_mode: true,
+            ..Default::default()
+        };
+        let outcome = run_nal_test(answering(), nal, options).expect("测试失败");
+        let results = outcome.results().collect::<Vec<_>>();
+        assert!(
+            matches!(
+                results[..],
+                [ExpectationResult::Failed(..), ExpectationResult::Skipped(reason)] if reason.contains("严格模式")
+            ),
+            "{results:?}"
+        );
+    }
+
+    /// 测试/严格模式：`''status`不算出错，而是产生`INFO`输出
+    #[test]
+    fn test_status() {
+        let options = TestOptions {
+            strict_mode: true,
+            ..Default::default()
+        };
+        let nal = format!("''status\n{NAL}");
+        let outcome = run_nal_test(answering(), &nal, options).expect("测试失败");
+        let results = outcome.results().collect::<Vec<_>>();
+        assert!(
+            matches!(
+                results[..],
+                [ExpectationResult::Passed, ExpectationResult::Failed(..)]
+            ),
+            "{results:?}"
+        );
+        assert!(
+            outcome.outputs.iter().any(
+                |output| matches!(output, Output::INFO { message } if message.starts_with("status"))
+            ),
+            "{:?}",
+            outcome.outputs
+        );
+    }
+
+    /// 测试/超时：启动、输入均受看门狗限制
+    #[test]
+    fn test_timeout() {
+        let options = TestOptions {
+            timeout: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let outcome =
+            run_nal_test(Slow(Duration::from_secs(2)), NAL, options.clone()).expect("测试失败");
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(outcome.results().all(|r| *r == ExpectationResult::TimedOut));
+        // 运行中超时：已得出的结果保留
+        let nal = format!("''expect-contains: IN <X --> Y>.\n''sleep: 2s\n{NAL}");
+        let outcome = run_nal_test(answering(), &nal, options).expect("测试失败");
+        let results = outcome.results().collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], ExpectationResult::Failed(..)));
+        assert!(results[1..]
+            .iter()
+            .all(|r| **r == ExpectationResult::TimedOut));
+        assert_eq!(outcome.expectations[1].elapsed, None);
+    }
+
+    /// 测试/超时：阻塞于输入时，看门狗直接终止CIN
+    /// * 🚩以`sh`记下进程id后`cat`：`''await`永远等不到输出
+    /// * 🚩CIN被终止⇒阻塞的输入返回⇒运行线程退出、回收子进程
+    #[test]
+    #[cfg(unix)]
+    fn test_timeout_kills_blocked_cin() {
+        use crate::runtimes::CommandVm;
+        use std::process::Command;
+        let pid_path =
+            std::env::temp_dir().join(format!("babelnar_nal_test_kill_{}.pid", std::process::id()));
+        let mut command = Command::new("sh");
+        command.args(["-c", &format!("echo $$ > {pid_path:?}; exec cat")]);
+        let options = TestOptions {
+            timeout: Duration::from_millis(500),
+            ..Default::default()
+        };
+        let outcome = run_nal_test(
+            CommandVm::from(command),
+            "''await: OUT <A --> B>.\n''expect-contains: OUT <A --> B>.",
+            options,
+        )
+        .expect("测试失败");
+        assert!(outcome.results().all(|r| *r == ExpectationResult::TimedOut));
+        // CIN进程应在不久后消失
+        let pid = std::fs::read_to_string(&pid_path).expect("无法读取进程id");
+        let _ = std::fs::remove_file(&pid_path);
+        let pid = pid.trim().parse::<libc::pid_t>().expect("进程id无效");
+        let start = Instant::now();
+        while unsafe { libc::kill(pid, 0) } == 0 {
+            assert!(start.elapsed() < Duration::from_secs(10), "CIN未被终止");
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// 测试/默认精度
+    /// * 🎯未指定精度的预期，使用选项中的精度
+    #[test]
+    fn test_precision_epoch() {
+        let nal = "<A --> B>?\n''expect-cycle(5, 1, 1ms): ANSWER <A --> B>. %1.0;0.9%";
+        let run = |precision_epoch| {
+            let vm = manipulate!(
+                FakeVm::new()
+                => .respond_after_cycle(
+                    Cmd::NSE(nse_task!(<A --> B>?)),
+                    [Output::ANSWER {
+                        content_raw: "<A --> B>. %0.98;0.9%".into(),
+                        narsese: Some(nse!(<A --> B>. %0.98;0.9%)),
+                    }],
+                )
+            );
+            let options = TestOptions {
+                precision_epoch,
+                ..Default::default()
+            };
+            let outcome = run_nal_test(vm, nal, options).expect("测试失败");
+            outcome.is_all_passed()
+        };
+        assert!(!run(None));
+        assert!(run(Some(FloatPrecision(0.05))));
+    }
+
+    /// 测试/解析、启动失败⇒返回错误
+    #[test]
+    fn test_errors() {
+        assert!(run_nal_test(FakeVm::new(), "''precision: -1", TestOptions::default()).is_err());
+        struct Broken;
+        impl VmLauncher for Broken {
+            type Runtime = FakeVmRuntime;
+            fn launch(self) -> Result<FakeVmRuntime> {
+                Err(anyhow!("找不到CIN"))
+            }
+        }
+        let error = run_nal_test(Broken, NAL, TestOptions::default()).expect_err("应启动失败");
+        assert!(error.to_string().contains("找不到CIN"));
+    }
+
+    /// 测试/ONA
+    #[test]
+    #[ignore = "需要本地ONA"]
+    fn test_ona() {
+        use crate::cin_implements::ona::ONA;
+        let path = crate::exists_or_exit!(crate::tests::cin_paths::ONA);
+        let nal = "\
+<A --> B>.
+<B --> C>.
+<A --> C>?
+''expect-cycle(50, 5): ANSWER <A --> C>.";
+        let outcome = run_nal_test(ONA::new(path), nal, TestOptions::default()).expect("测试失败");
+        assert!(outcome.is_all_passed(), "{:?}", outcome.expectations);
+    }
+}
@@ -3,9 +3,17 @@
 //! * ✨[`NALInput`]：在「直接对应CIN输入输出」的「NAVM指令」之上，引入「等待」「预期」等机制
 //! * ✨[`OutputExpectation`]：面向NAL测试，具体实现「预期」机制
 
-use narsese::{conversion::string::impl_lexical::format_instances::FORMAT_ASCII, lexical::Narsese};
-use navm::{cmd::Cmd, output::Operation};
-use std::{fmt::Display, time::Duration};
+use crate::test_tools::EchoMatchOptions;
+use narsese::{
+    conversion::string::impl_lexical::format_instances::FORMAT_ASCII,
+    lexical::{Narsese, Term},
+};
+use navm::{
+    cmd::Cmd,
+    output::{Operation, Output},
+};
+use regex::Regex;
+use std::{collections::BTreeMap, fmt::Display, ops::Deref, time::Duration};
 use thiserror::Error;
 
 /// NAVM测试中的「NAL输入」
@@ -15,14 +23,39 @@ use thiserror::Error;
 pub enum NALInput {
     /// 置入
     /// * 🎯向CIN置入NAVM指令
+    /// * 📄`''set-param: decisionthreshold=0.6`亦解析为此：置入`SET`指令
     Put(Cmd),
 
+    /// 设置
+    /// * 📄语法示例：`''set: VOL 0`
+    /// * 🎯向CIN置入NAVM指令，并等待CIN的回执（确认）
+    ///   * 📌与[`NALInput::Put`]不同：超时未收到回执⇒报错
+    ///   * 📄可发现「CIN尚未启动完毕时，`VOL`被静默丢弃」的情况
+    Set(Cmd),
+
+    /// 包含文件
+    /// * 📄语法示例：`''include: env.nal`
+    /// * 🎯在此处依次输入另一个NAL文件中的所有输入
+    /// * 📌路径基于「NAL执行路径」解析；被包含文件中的相对路径，则基于其自身所在目录
+    /// * 🚩[`put_nal`](crate::test_tools::put_nal)逐条置入被包含文件中的输入，并拒绝循环包含
+    Include(String),
+
     /// 睡眠
     /// * 📄语法示例：`''sleep 1s`
     /// * 📌调用[`thread::sleep`]单纯等待一段时间（单位：[`Duration`]）
     ///   * 🚩语法中常用的是秒数，但这里不直接存储
     Sleep(Duration),
 
+    /// 等待CIN安静下来
+    /// * 📄语法示例：`''await-idle: 300ms 10s`
+    /// * 📌持续拉取输出，直到连续`quiet_period`没有新的输出
+    ///   * 🎯在一阵`CYC`之后，等到CIN不再产出新的推理结果
+    /// * 📌超出`max_wait`⇒[`OutputExpectationError::IdleTimedOut`]
+    AwaitIdle {
+        quiet_period: Duration,
+        max_wait: Duration,
+    },
+
     /// 输出等待
     /// * 📄语法示例：`''await: IN <A --> B>.`
     /// * 📌在CIN输出与指定[`Output`]符合后，再继续运行
@@ -33,7 +66,19 @@ pub enum NALInput {
     /// * 📄语法示例：`''expect-contains: ANSWER <A --> C>.`
     /// * 🎯用于「在现有的输出中检查是否任一和指定的[`Output`]符合」
     /// * 📄对应OpenNARS中常有的`''outputMustContain('')`
-    ExpectContains(OutputExpectation),
+    ///   * ✅亦可直接写作`''outputMustContain('<A --> C>.')`：不限输出类型，引号内非Narsese⇒按原始内容匹配
+    /// * 📌可选的「最大延迟」
+    ///   * 📄语法示例：`''expect-contains(2000ms): ANSWER <A --> C>.`
+    ///   * 🚩自同一NAL文件中最近一次置入[`Cmd::NSE`]起，到符合预期的输出存入「输出缓存」为止计时
+    ///   * 📌需要「输出缓存」记录时间，参见[`VmOutputCache::for_each_timed`](crate::test_tools::VmOutputCache::for_each_timed)
+    /// * 📌亦可只预期「含有某个子词项」：[`TermMatch::Contains`]
+    ///   * 📄语法示例：`''expect-contains-term: OUT ^left`
+    /// * 📌可选的「精度」：仅对此预期生效，见[`OutputExpectation::precision`]
+    ///   * 📄语法示例：`''expect-contains(±0.05): ANSWER <A --> C>. %1.0;0.4%`
+    ///   * 📄与「最大延迟」同用：`''expect-contains(±0.05, 2000ms): ...`
+    /// * 📌可选的「时间容差」：仅对此预期生效，见[`OutputExpectation::tick_tolerance`]
+    ///   * 📄语法示例：`''expect-contains(±3t): OUT <A --> B>. :!10:`
+    ExpectContains(OutputExpectation, Option<Duration>),
 
     /// 对「输出含有」的循环预期
     /// * 📄语法示例：`''expect-cycle(500, 10, 0.1s): ANSWER <A --> C>.`
@@ -44,10 +89,84 @@ pub enum NALInput {
     /// * 📄在「最大步数=0」的情形之下，`expect-cycle(0)`等价于[`expect-contains`](NALInput::ExpectContains)
     ExpectCycle(usize, usize, Option<Duration>, OutputExpectation),
 
+    /// 对「输出不含」的循环预期
+    /// * 📄语法示例：`''expect-cycle-not(500, 10, 0.1s): EXE (^left, {SELF})`
+    /// * 🎯用于「在『最大步数』内，指定的输出始终不出现」：如受惩罚后的操作不再被执行
+    /// * 🚩像[`NALInput::ExpectCycle`]一样步进，并在每步后检查预期
+    ///   * 只检查预期开始后新存入「输出缓存」的输出
+    ///   * 一旦有输出符合⇒立即视作「预期不符」
+    ///   * 步进完所有步数仍无⇒通过，打印输出`expect-cycle-not(【次数】): 【输出】`
+    /// * 📄在「最大步数=0」的情形之下，不步进，只检查预期窗口内已有的输出
+    ///   * 🎯对应OpenNARS `.nal`文件中的`''outputMustNotContain('...')`
+    ExpectCycleNot(usize, usize, Option<Duration>, OutputExpectation),
+
+    /// 对「输出比例」的循环预期
+    /// * 📄语法示例：`''expect-rate(1000, 10, 80%): EXE (^left, {SELF})`
+    /// * 🎯用于「操作条件反射」一类的测试：单次`expect-contains`太弱，需要统计多次输出
+    /// * 🚩像[`NALInput::ExpectCycle`]一样步进，但不提前结束
+    ///   * 只统计预期开始后新存入「输出缓存」的输出
+    ///   * 分母：与预期「输出类型」相同的输出（预期无类型⇒所有输出）
+    ///   * 分子：其中与预期相符的输出
+    ///   * 符合比例低于`threshold`⇒预期失败
+    ///   * 没有任何同类输出⇒视作「预期不符」
+    ExpectRate {
+        /// 最大步数
+        max_cycles: usize,
+        /// 每次步进的步数
+        step_cycles: usize,
+        /// 最低比例 | `0~1`
+        threshold: Rate,
+        /// 输出预期
+        expectation: OutputExpectation,
+    },
+
+    /// 设置精度
+    /// * 📄语法示例：`''precision: 0.01`
+    /// * 🎯改变同一NAL文件中此后所有预期的「精度」：比对真值、预算值时允许的误差
+    /// * 📌在解析时即应用：[`parse`](crate::test_tools::nal_format::parse)将其填入此后未指定精度的预期
+    /// * 📌置入时亦记入「交互状态」：[`put_nal`](crate::test_tools::put_nal)将其填入此后置入的预期
+    ///   * 🎯逐行输入的用户输入、Websocket输入中同样有效
+    Precision(FloatPrecision),
+
+    /// 重置预期窗口
+    /// * 📄语法示例：`''reset-expect-window`
+    /// * 🎯让此后的预期重新检查「输出缓存」中的所有输出
+    ///   * 📌默认情况下，预期只检查「上一组预期之后首次置入」以来的输出，见[`VmOutputCache::expect_window`](crate::test_tools::VmOutputCache::expect_window)
+    ResetExpectWindow,
+
+    /// 报告运行状态
+    /// * 📄语法示例：`''status`
+    /// * 🎯用于长时间运行的智能体：查询各类输出的数目、输入输出的吞吐量等
+    /// * ⚠️需由调用方处理：[`put_nal`](crate::test_tools::put_nal)不持有运行统计，不处理此变体
+    Status,
+
+    /// 功能要求
+    /// * 📄语法示例：`''requires: operations temporal`
+    /// * 🎯跨CIN的NAL测试：CIN缺少所列功能⇒跳过此后的输入，而非报告失败
+    ///   * 📌跳过至[`NALInput::EndRequires`]或文件末尾
+    ///   * 📌功能名见[`Capabilities::NAMES`](crate::runtimes::Capabilities::NAMES)
+    /// * 🚩由[`put_nal`](crate::test_tools::put_nal)根据[「功能要求」区段](crate::test_tools::RequiresRegion)处理
+    Requires(Vec<String>),
+
+    /// 结束功能要求
+    /// * 📄语法示例：`''end-requires`
+    /// * 🎯结束由[`NALInput::Requires`]开始的区段：此后的输入照常执行
+    EndRequires,
+
     /// 保存「输出缓存」到指定文件
-    /// * 📄语法示例：`''save-outputs: outputs.log`
-    /// * 🎯用于「将现有所有输出以『NAVM输出的JSON格式』存档至指定文件中」
-    SaveOutputs(String),
+    /// * 📄语法示例：
+    ///   * `''save-outputs: outputs.log`
+    ///   * `''save-outputs(csv): outputs.csv`
+    /// * 🎯用于「将现有所有输出存档至指定文件中」
+    ///   * 📌默认为「NAVM输出的JSON格式」
+    /// * 📌路径中的`{timestamp}`在写入时替换为当前时间：`''save-outputs: logs/run-{timestamp}.json`
+    ///   * 🚩详见[`write_saved_outputs`](crate::test_tools::write_saved_outputs)
+    SaveOutputs {
+        /// 文件格式
+        format: SaveOutputsFormat,
+        /// 文件路径
+        path: String,
+    },
 
     /// 终止虚拟机
     /// * 🎯用于「预加载NAL『测试』结束后，程序自动退出/交给用户输入」
@@ -55,12 +174,18 @@ pub enum NALInput {
     ///   * `''terminate`
     ///   * `''terminate(if-no-user): 异常的退出消息！`
     /// * 🔧可选的「子参数」
-    ///   * `if-no-user`：仅在「用户无法输入」时退出
+    ///   * `if-no-user`：仅在「无人交互」时退出
+    ///   * `if-no-client`：仅在「无Websocket客户端」时退出
+    /// * 📌交互状态见[`InteractivityState`](crate::test_tools::InteractivityState)
     Terminate {
-        /// 仅在「用户无法输入」时退出
+        /// 仅在「无人交互」时退出：用户无法输入，且无Websocket客户端
         /// * 🎯用于「测试完毕后交给用户输入」的测试
         if_not_user: bool,
 
+        /// 仅在「无Websocket客户端」时退出
+        /// * 🎯不论是否启用用户输入：仅由Websocket客户端决定去留
+        if_no_client: bool,
+
         /// 退出的返回值
         /// * 🎯用于「测试完毕后向外部传递结果」的测试
         /// * 💭始终注意这只是个线性执行的指令，不要做得太复杂
@@ -69,6 +194,63 @@ pub enum NALInput {
     },
 }
 
+impl NALInput {
+    /// 是否为「预期」
+    /// * 🎯测试统计、预期窗口
+    /// * 📌不含[`NALInput::Await`]：仅等待，不计入测试结果
+    pub fn is_expectation(&self) -> bool {
+        matches!(
+            self,
+            NALInput::ExpectContains(..)
+                | NALInput::ExpectCycle(..)
+                | NALInput::ExpectCycleNot(..)
+                | NALInput::ExpectRate { .. }
+        )
+    }
+
+    /// 获取其中的「输出预期」（若有）
+    pub fn expectation_mut(&mut self) -> Option<&mut OutputExpectation> {
+        match self {
+            NALInput::Await(expectation)
+            | NALInput::ExpectContains(expectation, ..)
+            | NALInput::ExpectCycle(.., expectation)
+            | NALInput::ExpectCycleNot(.., expectation)
+            | NALInput::ExpectRate { expectation, .. } => Some(expectation),
+            _ => None,
+        }
+    }
+
+    /// 为未指定精度的「输出预期」填入精度
+    /// * 🎯`''precision:`、启动配置中的`precisionEpoch`
+    /// * 📌已指定精度⇒保持不变：越具体越优先
+    pub fn default_precision(&mut self, precision: Option<FloatPrecision>) {
+        if let Some(expectation) = self.expectation_mut() {
+            expectation.precision = expectation.precision.or(precision);
+        }
+    }
+
+    /// 为「输出预期」填入其输出类型的别名
+    /// * 🎯启动配置中的`outputTypeAliases`：以统一的类型名书写预期，兼容各CIN
+    /// * 📌预期的输出类型须为别名表中的「标准名」：别名之间不互通
+    pub fn default_type_aliases(&mut self, aliases: &OutputTypeAliases) {
+        if let Some(expectation) = self.expectation_mut() {
+            if let Some(names) = expectation
+                .output_type
+                .as_ref()
+                .and_then(|output_type| aliases.get(output_type))
+            {
+                expectation.output_type_aliases = names.clone();
+            }
+        }
+    }
+}
+
+/// 输出类型的别名表
+/// * 🎯不同CIN对同一类输出使用不同的类型名：ONA的`ANTICIPATE`、其它CIN的`EXPECT`
+/// * 📌键为「标准名」，值为其所有别名
+///   * 📄`{ "ANTICIPATE": ["EXPECT", "ANTICIPATION"] }`
+pub type OutputTypeAliases = BTreeMap<String, Vec<String>>;
+
 /// 输出预期
 /// * 📌对应语法中的`output_expectation`结构
 /// * 🎯用于统一表示对「NAVM输出」的预期
@@ -90,35 +272,477 @@ pub struct OutputExpectation {
     /// 预期的「NAVM操作」字段
     /// * 🚩可能没有：此时是「通配」情形
     ///   * 对任何可能的输入都适用
-    pub operation: Option<Operation>,
+    /// * 📌参数可含通配符：见[`ParamPattern`]
+    pub operation: Option<OperationExpectation>,
+
+    /// 预期的「原始内容」正则表达式
+    /// * 🚩可能没有：此时是「通配」情形
+    ///   * 对任何可能的输入都适用
+    /// * 🎯匹配没有可解析Narsese的输出
+    ///   * 📄ONA的`decision expectation=...`、OpenNARS的`Executed based on...`
+    /// * 📌与其它字段是「且」的关系：均符合才算符合
+    /// * 🚩在输出的「原始内容」中搜索：无需匹配整行
+    pub raw_regex: Option<RawRegex>,
+
+    /// 预期「Narsese」中词项的匹配方式
+    /// * 🚩默认为「整体相等」
+    /// * 📄`''expect-contains-term: OUT ^left`⇒输出的词项中含有`^left`即可
+    pub term_match: TermMatch,
+
+    /// 比对真值、预算值时允许的误差
+    /// * 🚩可能没有：此时精确比对
+    /// * 📄`''expect-contains(±0.05): ANSWER <A --> C>. %1.0;0.4%`
+    pub precision: Option<FloatPrecision>,
+
+    /// 比对固定时间戳`:!N:`时允许相差的时间步数
+    /// * 🚩可能没有：此时须精确相等
+    /// * 📄`''expect-contains(±3t): OUT <A --> B>. :!10:`⇒`:!7:`～`:!13:`均可
+    /// * 📌其它时间戳的比对见[`is_expected_stamp`](crate::test_tools::is_expected_stamp)
+    pub tick_tolerance: Option<usize>,
+
+    /// 按「输入回显」比对Narsese
+    /// * 🚩可能没有：此时照常比对
+    /// * 🎯忽略CIN回显时附加的预算值、时间戳数值等
+    /// * 📄`''await-echo: <A --> B>.`
+    pub echo: Option<EchoMatchOptions>,
+
+    /// 预期「输出类型」的别名
+    /// * 🚩输出类型为其中之一，亦视作符合
+    /// * 📌来自[别名表](OutputTypeAliases)：由[`NALInput::default_type_aliases`]填入
+    pub output_type_aliases: Vec<String>,
+}
+
+/// 对「NAVM操作」的预期
+/// * 📌对应语法中的`output_operation`结构
+/// * 📄`(^left, {SELF})`、`(^left, {SELF}, *)`、`(^go, {SELF}, ..)`
+/// * 🎯跨CIN测试：不同CIN的操作参数顺序、个数不尽相同
+/// * 🚩无参数⇒通配所有同名操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationExpectation {
+    /// 操作符名 | 不含`^`
+    pub operator_name: String,
+
+    /// 参数的模式
+    /// * 📌[`ParamPattern::Rest`]只能位于末尾
+    pub params: Vec<ParamPattern>,
+}
+
+/// 操作参数的模式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamPattern {
+    /// 语义上相等的词项
+    Term(Term),
+
+    /// 任意一个参数
+    /// * 📄语法：`*`
+    Any,
+
+    /// 其余的任意个参数（含零个）
+    /// * 📄语法：`..`
+    /// * ⚠️只能位于末尾
+    Rest,
+}
+
+/// 从「NAVM操作」转换：所有参数均精确匹配
+impl From<Operation> for OperationExpectation {
+    fn from(operation: Operation) -> Self {
+        Self {
+            operator_name: operation.operator_name,
+            params: operation
+                .params
+                .into_iter()
+                .map(ParamPattern::Term)
+                .collect(),
+        }
+    }
+}
+
+/// 与NAL中的写法一致
+/// * 📄`*`、`..`、`{SELF}`
+impl Display for ParamPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamPattern::Term(term) => write!(f, "{}", FORMAT_ASCII.format(term)),
+            ParamPattern::Any => write!(f, "*"),
+            ParamPattern::Rest => write!(f, ".."),
+        }
+    }
+}
+
+/// 浮点精度
+/// * 🎯比对真值、预算值时允许的误差：`|预期 - 输出| <= 精度`
+/// * 🚩按二进制表示判等：以便[`OutputExpectation`]实现[`Eq`]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct FloatPrecision(pub f64);
+
+impl FloatPrecision {
+    /// 从字符串解析
+    /// * ⚠️须为非负有限数
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let value = raw.trim().parse::<f64>()?;
+        match value.is_finite() && value >= 0.0 {
+            true => Ok(Self(value)),
+            false => Err(anyhow::anyhow!("无效的精度「{raw}」：应为非负数")),
+        }
+    }
+
+    /// 判断两个浮点数是否在误差内相等
+    pub fn approx_eq(self, expected: f64, out: f64) -> bool {
+        (expected - out).abs() <= self.0
+    }
+}
+
+impl PartialEq for FloatPrecision {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
 }
 
+impl Eq for FloatPrecision {}
+
+impl Display for FloatPrecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "±{}", self.0)
+    }
+}
+
+/// 比例
+/// * 🎯[`NALInput::ExpectRate`]中的「最低比例」：`0~1`
+/// * 🚩与[`FloatPrecision`]相同，按二进制表示判等：以便[`NALInput`]实现[`Eq`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rate(pub f64);
+
+impl PartialEq for Rate {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Rate {}
+
+/// 展示为百分数：📄`80%`
+impl Display for Rate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0 * 100.0)
+    }
+}
+
+/// 保存输出的文件格式
+/// * 🎯[`NALInput::SaveOutputs`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SaveOutputsFormat {
+    /// NAVM输出的JSON对象数组
+    /// * 📄`''save-outputs(json): outputs.json`
+    #[default]
+    Json,
+    /// 每个含Narsese的输出一行CommonNarsese，前附「输出类型」注释
+    /// * 📄`''save-outputs(nal): outputs.nal`
+    /// * 🎯将派生出的信念重新输入CIN
+    Nal,
+    /// 表格：`type,narsese,raw_content`三列
+    /// * 📄`''save-outputs(csv): outputs.csv`
+    /// * 🎯在电子表格中分析
+    Csv,
+}
+
+/// 词项的匹配方式
+/// * 🎯只关心「输出的词项是否提及某个子词项」，而非整体相等
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TermMatch {
+    /// 整体在语义上相等
+    #[default]
+    Exact,
+    /// 含有在语义上相等的子词项（含自身）
+    /// * 🚩变量在子词项层面重命名
+    Contains,
+}
+
+/// 对「原始内容」的正则表达式
+/// * 🎯让[`OutputExpectation`]可以判等：[`Regex`]本身未实现[`PartialEq`]
+/// * 🚩按「正则表达式的源码」判等
+#[derive(Debug, Clone)]
+pub struct RawRegex(pub Regex);
+
+impl RawRegex {
+    /// 编译正则表达式
+    /// * ⚠️语法错误⇒返回错误
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+}
+
+impl Deref for RawRegex {
+    type Target = Regex;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl PartialEq for RawRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for RawRegex {}
+
+/// 单行、可读的「输出预期」
+/// * 🎯用于错误信息、测试日志：与NAL文件中的写法一致
+/// * 🚩只显示有指定的字段，以空格分隔；类型未指定⇒`*`
+///   * Narsese：[`FORMAT_ASCII`]；「包含」匹配⇒前缀`⊇`
+///   * 操作：NAL中的简写`(^left, {SELF})`
+///   * 原始内容：`/正则表达式/`
+///   * 精度、回显：附加的`±0.05`、`echo`
+/// * 📄`ANSWER <A --> C>.`、`EXE (^left, {SELF})`
 impl Display for OutputExpectation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "OutputExpectation {{ {} {} {} }}",
-            self.output_type.as_deref().unwrap_or("*"),
-            match &self.narsese {
-                Some(narsese) => FORMAT_ASCII.format_narsese(narsese),
-                None => "*".to_string(),
-            },
-            self.operation
-                .as_ref()
-                .map(|op| op.to_string())
-                .unwrap_or("*".to_string()),
-        )
+        write!(f, "{}", self.output_type.as_deref().unwrap_or("*"))?;
+        if let Some(narsese) = &self.narsese {
+            // 「包含」匹配⇒前缀`⊇`
+            let prefix = match self.term_match {
+                TermMatch::Exact => "",
+                TermMatch::Contains => "⊇",
+            };
+            write!(f, " {prefix}{}", FORMAT_ASCII.format_narsese(narsese))?;
+        }
+        if let Some(operation) = &self.operation {
+            write!(f, " (^{},", operation.operator_name)?;
+            for (i, param) in operation.params.iter().enumerate() {
+                let separator = if i == 0 { " " } else { ", " };
+                write!(f, "{separator}{param}")?;
+            }
+            write!(f, ")")?;
+        }
+        if let Some(raw_regex) = &self.raw_regex {
+            write!(f, " /{}/", raw_regex.as_str())?;
+        }
+        if let Some(precision) = &self.precision {
+            write!(f, " {precision}")?;
+        }
+        if let Some(tick_tolerance) = self.tick_tolerance {
+            write!(f, " ±{tick_tolerance}t")?;
+        }
+        if self.echo.is_some() {
+            write!(f, " echo")?;
+        }
+        Ok(())
+    }
+}
+
+/// 预期失败时的诊断信息
+/// * 🎯无需保存输出后重跑，即可得知「实际输出了什么」
+/// * 📄`（共检查12条输出），最接近的输出：\n  1. ANSWER <A --> B>. %0.8;0.9%`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NearestMisses {
+    /// 检查过的输出总数
+    pub scanned: usize,
+    /// 最接近预期的输出 | 越靠前越接近
+    pub outputs: Vec<Output>,
+}
+
+impl Display for NearestMisses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "（共检查{}条输出）", self.scanned)?;
+        if !self.outputs.is_empty() {
+            write!(f, "，最接近的输出：")?;
+        }
+        for (i, output) in self.outputs.iter().enumerate() {
+            write!(
+                f,
+                "\n  {}. {} {}",
+                i + 1,
+                output.type_name(),
+                output.raw_content()
+            )?;
+        }
+        Ok(())
     }
 }
 
 /// 预期错误
 /// * 🎯用于定义可被识别的「NAL预期失败/脱离预期」错误
 /// * 🚩使用[`thiserror`]快捷定义
+/// * 📌各变体大小差异较大，但仅在「预期失败」时构造，无需装箱
+#[allow(clippy::large_enum_variant)]
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum OutputExpectationError {
     /// 输出未包含预期
     /// * 🎯对应[`NALInput::ExpectContains`]
+    /// * 📌附带诊断信息：检查过的输出数，以及最接近预期的几条输出
     /// * 📝此处`{0:?}`参照<https://lib.rs/crates/thiserror>
-    #[error("输出内容中不存在符合预期的输出：{0}")]
-    ExpectedNotExists(OutputExpectation),
+    #[error("输出内容中不存在符合预期的输出：{0}{1}")]
+    ExpectedNotExists(OutputExpectation, NearestMisses),
+
+    /// 虚拟机在预期达成前已终止
+    /// * 🎯对应[`NALInput::Await`]、[`NALInput::ExpectCycle`]
+    /// * 🎯与「预期不符」区分开：CIN崩溃时直接上报真正原因
+    /// * 📌内容：终止的描述信息
+    #[error("虚拟机在预期达成前已终止：{0}")]
+    VmTerminated(String),
+
+    /// 出现了不应出现的输出
+    /// * 🎯对应[`NALInput::ExpectCycleNot`]
+    /// * 📌`output`：首个符合预期的输出；`cycles`：出现时已步进的步数
+    #[error(
+        "在第{cycles}步出现了不应出现的输出「{} {}」：{expectation}",
+        .output.type_name(),
+        .output.raw_content()
+    )]
+    UnexpectedExists {
+        expectation: OutputExpectation,
+        output: Output,
+        cycles: usize,
+    },
+
+    /// 指令未被确认
+    /// * 🎯对应[`NALInput::Set`]，以及等待步进完成的`CYC`
+    /// * 📌内容：未被确认的指令
+    #[error("指令「{0}」在超时前未收到CIN的回执")]
+    SetNotAcknowledged(String),
+
+    /// 未指定指令的回执
+    /// * 🎯对应[`NALInput::Set`]：不知道哪条输出是回执，无法确认
+    /// * 📌内容：已置入、但无法确认的指令
+    #[error("指令「{0}」未指定回执内容，无法确认：请在配置的`setAck`中指定")]
+    SetAckUnspecified(String),
+
+    /// CIN未能安静下来
+    /// * 🎯对应[`NALInput::AwaitIdle`]
+    /// * 📌内容：最长等待时间
+    #[error("CIN在{0:?}内未能安静下来：仍在持续输出")]
+    IdleTimedOut(Duration),
+
+    /// 符合预期的输出来得太迟
+    /// * 🎯对应带「最大延迟」的[`NALInput::ExpectContains`]
+    /// * 📌`latency`：实际延迟（取最早符合预期的输出）
+    #[error("符合预期的输出延迟为{latency:?}，超出了限定的{max_latency:?}：{expectation}")]
+    LatencyExceeded {
+        expectation: OutputExpectation,
+        max_latency: Duration,
+        latency: Duration,
+    },
+
+    /// 符合预期的输出比例过低
+    /// * 🎯对应[`NALInput::ExpectRate`]
+    /// * 📌`matched`/`total`：符合预期的输出数/同类输出总数
+    /// * ⚠️`total`不为零：没有任何同类输出时，上报[`OutputExpectationError::ExpectedNotExists`]
+    #[error(
+        "符合预期的输出比例为{matched}/{total}（{:.1}%），低于{:.1}%：{expectation}",
+        *.matched as f64 / *.total as f64 * 100.0,
+        .threshold.0 * 100.0
+    )]
+    RateBelowThreshold {
+        expectation: OutputExpectation,
+        matched: usize,
+        total: usize,
+        threshold: Rate,
+    },
+
+    /// 无法测量延迟
+    /// * 🎯对应带「最大延迟」的[`NALInput::ExpectContains`]
+    /// * 📄此前未置入过Narsese，或「输出缓存」不记录时间
+    #[error("无法测量输出延迟：此前未置入Narsese，或输出缓存不记录时间：{0}")]
+    LatencyUnmeasurable(OutputExpectation),
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_tools::nal_format::parse;
+    use nar_dev_utils::asserts;
+    use narsese::{lexical_nse as nse, lexical_nse_term as nse_term};
+
+    /// 从NAL解析「输出预期」
+    fn expectation(nal: &str) -> OutputExpectation {
+        match parse(nal).pop() {
+            Some(Ok(NALInput::ExpectContains(expectation, _))) => expectation,
+            other => panic!("应解析为「输出预期」：{other:?}"),
+        }
+    }
+
+    /// 测试/显示：与NAL文件中的写法一致
+    #[test]
+    fn test_display_round_trip() {
+        for expected in [
+            "ANSWER <A --> C>.",
+            "ANSWER <A --> C>. %1.0;0.9%",
+            "OUT $0.5;0.5;0.5$ <A --> C>.",
+            "EXE (^left, {SELF})",
+            "EXE (^left, {SELF}, x)",
+            "EXE (^left, *, {SELF}, ..)",
+            "EXE (^pick,)",
+            "OUT /frequency=1\\.000000/",
+            "ANSWER <A --> C>. /confidence=0\\.81/",
+            "NO_ANSWER",
+        ] {
+            let nal = format!("''expect-contains: {expected}");
+            assert_eq!(expectation(&nal).to_string(), expected);
+        }
+    }
+
+    /// 测试/显示：各字段的组合
+    #[test]
+    fn test_display_fields() {
+        let operation = Operation::new("left", [nse_term!({ SELF })].into_iter());
+        asserts! {
+            // 全通配
+            OutputExpectation::default().to_string() => "*"
+            // 仅Narsese
+            OutputExpectation {
+                narsese: Some(nse!(<A --> B>)),
+                ..Default::default()
+            }.to_string() => "* <A --> B>"
+            // 「包含」匹配
+            OutputExpectation {
+                output_type: Some("OUT".into()),
+                narsese: Some(nse!(^left)),
+                term_match: TermMatch::Contains,
+                ..Default::default()
+            }.to_string() => "OUT ⊇^left"
+            // Narsese与操作
+            OutputExpectation {
+                output_type: Some("EXE".into()),
+                narsese: Some(nse!(<(*, {SELF}) --> ^left>)),
+                operation: Some(operation.clone().into()),
+                ..Default::default()
+            }.to_string() => "EXE <(*, {SELF}) --> ^left> (^left, {SELF})"
+            // 操作参数的通配符
+            OutputExpectation {
+                output_type: Some("EXE".into()),
+                operation: Some(OperationExpectation {
+                    operator_name: "go".into(),
+                    params: vec![ParamPattern::Any, ParamPattern::Term(nse_term!({SELF})), ParamPattern::Rest],
+                }),
+                ..Default::default()
+            }.to_string() => "EXE (^go, *, {SELF}, ..)"
+            // 精度、回显
+            OutputExpectation {
+                output_type: Some("ANSWER".into()),
+                narsese: Some(nse!(<A --> C>. %1.0;0.4%)),
+                precision: Some(FloatPrecision(0.05)),
+                echo: Some(EchoMatchOptions::default()),
+                ..Default::default()
+            }.to_string() => "ANSWER <A --> C>. %1.0;0.4% ±0.05 echo"
+        }
+    }
+
+    /// 测试/错误信息中的「输出预期」
+    #[test]
+    fn test_error_display() {
+        let expectation = expectation("''expect-contains: ANSWER <A --> C>.");
+        let misses = NearestMisses {
+            scanned: 2,
+            outputs: vec![],
+        };
+        asserts! {
+            OutputExpectationError::ExpectedNotExists(expectation.clone(), misses).to_string()
+                => "输出内容中不存在符合预期的输出：ANSWER <A --> C>.（共检查2条输出）"
+            OutputExpectationError::LatencyUnmeasurable(expectation).to_string()
+                => "无法测量输出延迟：此前未置入Narsese，或输出缓存不记录时间：ANSWER <A --> C>."
+        }
+    }
 }
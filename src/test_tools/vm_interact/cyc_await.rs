@@ -0,0 +1,35 @@
+//! 置入`CYC`后的等待
+//! * 🎯`CYC`指令一经输入便返回，CIN却仍在推理：免去NAL中为此而加的`''sleep`
+//!   * 📄启动配置中的`cycAwait: true`
+//! * 🚩CIN会报告「步进完成」⇒等待其回执（见各CIN模块的`CYC_ACK_MATCHER`）
+//! * 🚩CIN不报告⇒等待CIN安静下来
+
+use navm::output::Output;
+use std::time::Duration;
+
+/// 等待`CYC`步进完成的最长时间
+/// * 🚩超时⇒[`OutputExpectationError::SetNotAcknowledged`](crate::test_tools::OutputExpectationError::SetNotAcknowledged)
+pub const CYC_AWAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// CIN不报告「步进完成」时，视作「已完成」的安静期
+pub const CYC_AWAIT_QUIET_PERIOD: Duration = Duration::from_millis(200);
+
+/// 置入`CYC`后的等待方式
+#[derive(Debug, Clone, Copy)]
+pub enum CycAwait {
+    /// 等到符合判断的输出，作为「步进完成」的回执
+    Ack(fn(&Output) -> bool),
+    /// 等到CIN在[安静期](CYC_AWAIT_QUIET_PERIOD)内不再输出
+    Idle,
+}
+
+impl CycAwait {
+    /// 从CIN的「步进完成」判断构造
+    /// * 🚩无判断⇒[`CycAwait::Idle`]
+    pub fn new(ack_matcher: Option<fn(&Output) -> bool>) -> Self {
+        match ack_matcher {
+            Some(matcher) => Self::Ack(matcher),
+            None => Self::Idle,
+        }
+    }
+}
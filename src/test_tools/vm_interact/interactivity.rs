@@ -0,0 +1,127 @@
+//! 交互状态
+//! * 🎯[`NALInput::Terminate`](crate::test_tools::NALInput::Terminate)的前提条件：是否仍有人在与CIN交互
+//!   * 📌用户输入：由启动配置决定，运行中不变
+//!   * 📌Websocket客户端：由Websocket服务端在连接建立、断开时维护
+//! * 🎯[`NALInput::Precision`](crate::test_tools::NALInput::Precision)：逐行输入时，此后预期的「精度」
+//!   * 📌用户输入与Websocket客户端共用：同一CIN的交互状态
+//! * 🚩多线程共享：客户端数为原子量，精度加锁
+
+use crate::test_tools::FloatPrecision;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex, PoisonError,
+};
+
+/// 交互状态
+/// * 🚩默认：无用户输入，无Websocket客户端
+#[derive(Debug, Default)]
+pub struct InteractivityState {
+    /// 是否启用用户输入
+    user_input_enabled: bool,
+
+    /// 已连接的Websocket客户端数
+    websocket_clients: AtomicUsize,
+
+    /// 最近一次`''precision:`设置的精度
+    /// * 🚩[`None`]⇒未设置过
+    precision: Mutex<Option<FloatPrecision>>,
+}
+
+impl InteractivityState {
+    /// 构造函数
+    /// * 🚩初始无Websocket客户端
+    pub fn new(user_input_enabled: bool) -> Self {
+        Self {
+            user_input_enabled,
+            websocket_clients: AtomicUsize::new(0),
+            precision: Mutex::new(None),
+        }
+    }
+
+    /// 是否启用用户输入
+    pub fn user_input_enabled(&self) -> bool {
+        self.user_input_enabled
+    }
+
+    /// 已连接的Websocket客户端数
+    pub fn websocket_clients(&self) -> usize {
+        self.websocket_clients.load(Ordering::SeqCst)
+    }
+
+    /// 是否有已连接的Websocket客户端
+    pub fn has_websocket_clients(&self) -> bool {
+        self.websocket_clients() > 0
+    }
+
+    /// 是否有人在交互：启用了用户输入，或有已连接的Websocket客户端
+    /// * 🎯`''terminate(if-no-user)`
+    pub fn is_interactive(&self) -> bool {
+        self.user_input_enabled || self.has_websocket_clients()
+    }
+
+    /// 记录「Websocket客户端已连接」
+    pub fn client_connected(&self) {
+        self.websocket_clients.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// 记录「Websocket客户端已断开」
+    /// * 🚩不会减到`0`以下
+    pub fn client_disconnected(&self) {
+        let _ = self
+            .websocket_clients
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+    }
+
+    /// 最近一次`''precision:`设置的精度
+    pub fn precision(&self) -> Option<FloatPrecision> {
+        *self
+            .precision
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 记录`''precision:`设置的精度
+    /// * 🎯此后置入的、未指定精度的预期均使用此精度
+    pub fn set_precision(&self, precision: FloatPrecision) {
+        *self
+            .precision
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(precision);
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/客户端计数与「是否有人在交互」
+    #[test]
+    fn test_interactivity() {
+        let state = InteractivityState::default();
+        assert!(!state.is_interactive());
+        state.client_connected();
+        state.client_connected();
+        assert_eq!(state.websocket_clients(), 2);
+        assert!(state.is_interactive());
+        state.client_disconnected();
+        state.client_disconnected();
+        state.client_disconnected();
+        assert_eq!(state.websocket_clients(), 0);
+        assert!(!state.is_interactive());
+        // 启用用户输入⇒始终有人在交互
+        let state = InteractivityState::new(true);
+        assert!(state.is_interactive());
+        assert!(!state.has_websocket_clients());
+    }
+
+    /// 测试/精度：未设置⇒[`None`]，设置后保持最近一次
+    #[test]
+    fn test_precision() {
+        let state = InteractivityState::default();
+        assert!(state.precision().is_none());
+        state.set_precision(FloatPrecision(0.05));
+        state.set_precision(FloatPrecision(0.01));
+        assert_eq!(state.precision().map(|p| p.0), Some(0.01));
+    }
+}
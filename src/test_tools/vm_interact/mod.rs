@@ -1,11 +1,28 @@
 //! 与NAVM虚拟机的交互逻辑
 
-use super::{NALInput, OutputExpectation, OutputExpectationError};
-use crate::cli_support::{error_handling_boost::error_anyhow, io::output_print::OutputType};
-use anyhow::Result;
-use nar_dev_utils::{if_return, ResultBoost};
-use navm::{cmd::Cmd, output::Output, vm::VmRuntime};
-use std::{ops::ControlFlow, path::Path};
+use super::{
+    nal_format::parse, NALInput, NearestMisses, OutputExpectation, OutputExpectationError, Rate,
+    SaveOutputsFormat,
+};
+use crate::{
+    output_handler::json::output_to_json,
+    runtimes::{wait_until_idle, IdleProbe, IdleResult, VmRuntimeBoost, IDLE_POLL_INTERVAL},
+    support::{is_shutdown_requested, print_notice},
+};
+use anyhow::{anyhow, Result};
+use nar_dev_utils::if_return;
+use narsese::{conversion::string::impl_lexical::format_instances::FORMAT_ASCII, lexical::Task};
+use navm::{
+    cmd::Cmd,
+    output::Output,
+    vm::{VmRuntime, VmStatus},
+};
+use std::{
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant, SystemTime},
+};
 
 // Narsese预期
 mod narsese_expectation;
@@ -14,38 +31,150 @@ pub use narsese_expectation::*;
 // 词项判等
 mod term_equal;
 
+// 问题与回答的关联
+mod question_answer;
+pub use question_answer::*;
+
+// 「功能要求」区段
+mod requires_region;
+pub use requires_region::*;
+
+// 交互状态
+mod interactivity;
+pub use interactivity::*;
+
+// 「保存输出」的文件路径
+mod save_outputs_path;
+pub use save_outputs_path::*;
+
+// 置入`CYC`后的等待
+mod cyc_await;
+pub use cyc_await::*;
+
+// 「设置」指令的回执
+mod set_ack;
+pub use set_ack::*;
+
 /// 实现/预期匹配功能
 impl OutputExpectation {
     /// 判断一个「NAVM输出」是否与自身相符合
+    /// * 🚩未指定精度⇒精确比对真值、预算值
     pub fn matches(&self, output: &Output) -> bool {
         // 输出类型
-        if let Some(expected) = &self.output_type {
-            if_return! { expected != output.type_name() => false }
-        }
+        if_return! { !self.matches_type(output.type_name()) => false }
 
         // Narsese
         match (&self.narsese, output.get_narsese()) {
             // 预期有，输出无⇒直接pass
             (Some(..), None) => return false,
             // 预期输出都有⇒判断Narsese是否相同
+            // * 🚩回显⇒忽略附加的预算值、时间戳数值等
             (Some(expected), Some(out)) => {
-                if_return! { !is_expected_narsese_lexical(expected, out) => false }
+                let precision = self.precision.unwrap_or_default();
+                let tick_tolerance = self.tick_tolerance.unwrap_or_default();
+                let matches = match self.echo {
+                    Some(options) => is_expected_echo(expected, out, options),
+                    None => is_expected_narsese_lexical_as(
+                        expected,
+                        out,
+                        self.term_match,
+                        precision,
+                        tick_tolerance,
+                    ),
+                };
+                if_return! { !matches => false }
             }
             _ => (),
         }
 
-        // 操作 | 最后返回
+        // 操作
         match (&self.operation, output.get_operation()) {
             // 预期无⇒通配
-            (None, ..) => true,
+            (None, ..) => (),
             // 预期有，输出无⇒直接pass
-            (Some(_), None) => false,
+            (Some(_), None) => return false,
             // 预期有，输出有⇒判断操作是否相同
-            (Some(expected), Some(out)) => is_expected_operation(expected, out),
+            (Some(expected), Some(out)) => {
+                if_return! { !is_expected_operation(expected, out) => false }
+            }
+        }
+
+        // 原始内容 | 最后返回
+        match &self.raw_regex {
+            // 预期无⇒通配
+            None => true,
+            // 预期有⇒在原始内容中搜索
+            Some(regex) => regex.is_match(output.raw_content()),
+        }
+    }
+
+    /// 判断输出类型是否与自身相符
+    /// * 🚩未指定类型⇒通配
+    /// * 🚩输出类型为预期类型的别名⇒相符
+    pub fn matches_type(&self, type_name: &str) -> bool {
+        match &self.output_type {
+            None => true,
+            Some(expected) => {
+                expected == type_name || self.output_type_aliases.iter().any(|a| a == type_name)
+            }
         }
     }
 }
 
+/// 预期失败时，默认列出的「最接近的输出」条数
+pub const DEFAULT_NEAREST_MISS_COUNT: usize = 3;
+
+/// 预期失败时，列出的「最接近的输出」条数
+/// * 🚩全局设置：影响所有[`OutputExpectationError::ExpectedNotExists`]
+static NEAREST_MISS_COUNT: AtomicUsize = AtomicUsize::new(DEFAULT_NEAREST_MISS_COUNT);
+
+/// 设置「预期失败时，列出的『最接近的输出』条数」
+/// * 📌`0`⇒不列出，仅报告检查过的输出数
+pub fn set_nearest_miss_count(count: usize) {
+    NEAREST_MISS_COUNT.store(count, Ordering::Relaxed);
+}
+
+/// 获取「预期失败时，列出的『最接近的输出』条数」
+pub fn nearest_miss_count() -> usize {
+    NEAREST_MISS_COUNT.load(Ordering::Relaxed)
+}
+
+impl NearestMisses {
+    /// 从「输出缓存」中收集诊断信息
+    /// * 🚩只收集[预期窗口](VmOutputCache::expect_window)内的输出
+    /// * 🚩按[`miss_distance`]排序，取最接近预期的`count`条输出
+    ///   * 📌距离相同⇒较晚的输出在前
+    pub fn collect(
+        expectation: &OutputExpectation,
+        output_cache: &impl VmOutputCache,
+        count: usize,
+    ) -> Result<Self> {
+        let mut scored = vec![];
+        output_cache.for_each_from(output_cache.expect_window(), |output| {
+            scored.push((miss_distance(expectation, output), output.clone()));
+            ControlFlow::<()>::Continue(())
+        })?;
+        let scanned = scored.len();
+        scored.reverse();
+        scored.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        scored.truncate(count);
+        Ok(Self {
+            scanned,
+            outputs: scored.into_iter().map(|(_, output)| output).collect(),
+        })
+    }
+}
+
+/// 构造「输出未包含预期」的错误
+/// * 🚩附带当前「输出缓存」的诊断信息
+fn expected_not_exists(
+    expectation: OutputExpectation,
+    output_cache: &impl VmOutputCache,
+) -> Result<()> {
+    let misses = NearestMisses::collect(&expectation, output_cache, nearest_miss_count())?;
+    Err(OutputExpectationError::ExpectedNotExists(expectation, misses).into())
+}
+
 /// 输出缓存
 /// * 🎯为「使用『推送』功能，而不引入具体数据类型」设置
 /// * 📌基础功能：推送输出、遍历输出
@@ -54,28 +183,277 @@ pub trait VmOutputCache {
     /// * 🎯统一的「打印输出」逻辑
     fn put(&mut self, output: Output) -> Result<()>;
 
+    /// 存入输出，并附带其「收到时间」
+    /// * 🎯以输出到达的时刻计算延迟：拉取不及时，不影响测得的延迟
+    /// * 🚩默认不记录时间：同[`VmOutputCache::put`]
+    fn put_received(&mut self, output: Output, _received: Instant) -> Result<()> {
+        self.put(output)
+    }
+
     /// 遍历输出
     /// * 🚩不是返回迭代器，而是用闭包开始计算
     /// * 📝使用最新的「控制流」数据结构
     ///   * 使用[`None`]代表「一路下来没`break`」
     fn for_each<T>(&self, f: impl FnMut(&Output) -> ControlFlow<T>) -> Result<Option<T>>;
+
+    /// 当前位置
+    /// * 📌即当前的输出数：此后存入的输出，索引均不小于此
+    /// * 🎯配合[`VmOutputCache::for_each_from`]划定「只检查此后输出」的窗口
+    fn snapshot(&self) -> Result<usize> {
+        let mut len = 0;
+        self.for_each(|_| {
+            len += 1;
+            ControlFlow::<()>::Continue(())
+        })?;
+        Ok(len)
+    }
+
+    /// 从指定位置起遍历输出
+    /// * 📌位置来自[`VmOutputCache::snapshot`]
+    fn for_each_from<T>(
+        &self,
+        start: usize,
+        mut f: impl FnMut(&Output) -> ControlFlow<T>,
+    ) -> Result<Option<T>> {
+        let mut index = 0;
+        self.for_each(|output| {
+            index += 1;
+            match index > start {
+                true => f(output),
+                false => ControlFlow::Continue(()),
+            }
+        })
+    }
+
+    /// 遍历输出，并附带其「收到时间」
+    /// * 📌时间：自缓存创建以来的时长
+    /// * 🚩默认不记录时间：均为[`None`]
+    fn for_each_timed<T>(
+        &self,
+        mut f: impl FnMut(&Output, Option<Duration>) -> ControlFlow<T>,
+    ) -> Result<Option<T>> {
+        self.for_each(|output| f(output, None))
+    }
+
+    /// 记录「置入Narsese」的时间
+    /// * 🎯带「最大延迟」的[`NALInput::ExpectContains`]：作为计时起点
+    /// * 🚩默认不记录
+    fn mark_nse_input(&mut self) {}
+
+    /// 最近一次「置入Narsese」的时间
+    /// * 📌时间：自缓存创建以来的时长
+    /// * 🚩默认不记录：始终为[`None`]
+    fn last_nse_input(&self) -> Option<Duration> {
+        None
+    }
+
+    /// 预期窗口的起点
+    /// * 🎯避免先后的预期匹配到同一条旧输出：如两次相同的提问，第二次的预期被第一次的回答满足
+    /// * 🚩预期只检查此位置及之后的输出
+    ///   * 📌检查过预期后，首次置入指令时，窗口移至当时的末尾
+    ///   * 📌同一组（中间无置入的）预期共用一个窗口
+    /// * 🚩默认不记录：始终为`0`，即检查所有输出
+    fn expect_window(&self) -> usize {
+        0
+    }
+
+    /// 标记「已检查过预期」
+    /// * 🚩此后首次[置入](VmOutputCache::advance_expect_window)时，移动预期窗口
+    /// * 🚩默认不记录
+    fn mark_expectation_checked(&mut self) {}
+
+    /// 置入指令时调用：若已检查过预期，则将预期窗口移至末尾
+    /// * 🚩默认不记录
+    fn advance_expect_window(&mut self) {}
+
+    /// 将预期窗口重置到缓存开头
+    /// * 🎯[`NALInput::ResetExpectWindow`]：此后的预期检查所有输出
+    /// * 🚩默认不记录
+    fn reset_expect_window(&mut self) {}
+
+    /// 置入Narsese时调用：若为问题，则登记之
+    /// * 🎯将此后的`ANSWER`关联到所回答的问题
+    /// * 🚩默认不记录
+    fn track_question(&mut self, _task: &Task) {}
+
+    /// 第`index`个输出所回答的问题编号
+    /// * 📌编号见[`QuestionTracker`]
+    /// * 🚩默认不记录：始终为[`None`]
+    fn answer_to(&self, _index: usize) -> Option<usize> {
+        None
+    }
+
+    /// 尚未被回答的问题
+    /// * 🚩默认不记录：始终为空
+    fn unanswered_questions(&self) -> Vec<TrackedQuestion> {
+        vec![]
+    }
+
+    /// 「保存输出」的选项
+    /// * 🎯[`NALInput::SaveOutputs`]：保留文件数、是否允许绝对路径
+    /// * 🚩默认：见[`SaveOutputsOptions::default`]
+    fn save_outputs_options(&self) -> SaveOutputsOptions {
+        SaveOutputsOptions::default()
+    }
+}
+
+/// 为[`Vec`]实现「输出缓存」
+/// * 🎯无需引入CLI的[`OutputCache`](crate::cli_support::io::navm_output_cache::OutputCache)即可调用[`put_nal`]
+/// * 🚩静默存入，不做任何打印
+impl VmOutputCache for Vec<Output> {
+    fn put(&mut self, output: Output) -> Result<()> {
+        self.push(output);
+        Ok(())
+    }
+
+    fn for_each<T>(&self, mut f: impl FnMut(&Output) -> ControlFlow<T>) -> Result<Option<T>> {
+        for output in self.iter() {
+            if let ControlFlow::Break(value) = f(output) {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// 「设置」指令等待回执的超时时间
+/// * 🎯[`NALInput::Set`]：超时未收到回执⇒报错
+pub const SET_ACK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 「设置」指令等待回执时，拉取输出的间隔
+const SET_ACK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 为「置入NAL输入」的错误附上其在`.nal`文件中的行号
+/// * 🎯严格模式中止运行时，能定位到出错的位置
+/// * 🚩作为上下文附加：仍可向下转型为原先的错误类型（如[`crate::runtimes::TranslateError`]）
+/// * 📌行号来自[`super::nal_format::parse_with_line_no`]
+pub fn with_nal_line_no(e: anyhow::Error, line_no: usize) -> anyhow::Error {
+    let message = format!("第{line_no}行：{e}");
+    e.context(message)
+}
+
+/// 同[`with_nal_line_no`]，但以`来源名:行号:`开头，并附带出错的原始行
+/// * 🎯`preludeNAL`、`''include:`的文件：在多个文件中定位出错的行
+/// * 📌原始行来自[`super::nal_format::source_line`]
+pub fn with_nal_source(
+    e: anyhow::Error,
+    source_name: &str,
+    line_no: usize,
+    line: &str,
+) -> anyhow::Error {
+    let message = format!("{source_name}:{line_no}: 「{line}」：{e}");
+    e.context(message)
+}
+
+/// 置入NAL时的上下文
+/// * 🎯由调用方持有、在多次[`put_nal`]之间保持的状态与设置
+#[derive(Debug)]
+pub struct PutNalContext<'a> {
+    /// 交互状态：用户输入、Websocket客户端
+    /// * 🎯不能传入「启动配置」，就要传入「交互状态」
+    pub interactivity: &'a InteractivityState,
+
+    /// NAL执行路径 | 用于`''save-outputs:`等相对路径
+    pub nal_root_path: &'a Path,
+
+    /// 「设置」指令的回执
+    /// * 📌因CIN（转译器）而异，需由调用方传入
+    pub set_ack: &'a SetAck,
+
+    /// 置入`CYC`后是否等待其步进完成
+    /// * 🚩[`None`]⇒置入即返回
+    pub cyc_await: Option<CycAwait>,
+
+    /// 「功能要求」区段
+    /// * 📌每个NAL文件一个
+    /// * 🚩处于被跳过的区段⇒除「功能要求」外，不执行任何输入
+    pub requires: &'a mut RequiresRegion,
 }
 
 /// 向虚拟机置入[`NALInput`]
 /// * 🎯除了「输入指令」之外，还附带其它逻辑
 /// * 🚩通过「输出缓存」参数，解决「缓存输出」问题
 /// * ❓需要迁移「符合预期」的逻辑
+/// * 📌其余的状态与设置见[`PutNalContext`]
 pub fn put_nal(
-    vm: &mut impl VmRuntime,
-    input: NALInput,
+    vm: &mut impl VmRuntimeBoost,
+    mut input: NALInput,
     output_cache: &mut impl VmOutputCache,
-    // 不能传入「启动配置」，就要传入「是否启用用户输入」状态变量
-    enabled_user_input: bool,
-    nal_root_path: &Path,
+    context: &mut PutNalContext,
 ) -> Result<()> {
+    let PutNalContext {
+        interactivity,
+        nal_root_path,
+        set_ack,
+        cyc_await,
+        requires,
+    } = context;
+    // 处于被跳过的区段⇒跳过
+    if_return! { requires.skips(&input) => Ok(()) }
+    // 未指定精度的预期⇒使用此前`''precision:`设置的精度
+    input.default_precision(interactivity.precision());
+    // 预期⇒标记：此后首次置入时，移动预期窗口
+    if input.is_expectation() {
+        output_cache.mark_expectation_checked();
+    }
     match input {
-        // 置入NAVM指令
-        NALInput::Put(cmd) => vm.input_cmd(cmd),
+        // 置入NAVM指令 | 置入Narsese⇒记录时间，作为「最大延迟」的计时起点
+        NALInput::Put(cmd) => {
+            let cyc_await = cyc_await.filter(|_| matches!(cmd, Cmd::CYC(..)));
+            output_cache.advance_expect_window();
+            // 先记录时间再置入：因此产生的输出，收到时间都不早于此
+            if let Cmd::NSE(task) = &cmd {
+                output_cache.track_question(task);
+                output_cache.mark_nse_input();
+            }
+            match cyc_await {
+                Some(cyc_await) => nal_await_cyc(vm, cmd, cyc_await, output_cache)?,
+                None => vm.input_cmd(cmd)?,
+            }
+            Ok(())
+        }
+        // 置入NAVM指令，并等待回执
+        NALInput::Set(cmd) => nal_set(vm, cmd, set_ack, output_cache),
+        // 包含文件 | 相对路径基于「NAL执行路径」
+        NALInput::Include(path) => {
+            let mut context = PutNalContext {
+                interactivity,
+                nal_root_path,
+                set_ack,
+                cyc_await: *cyc_await,
+                requires,
+            };
+            nal_include(vm, &path, output_cache, &mut context, &mut vec![])
+        }
+        // 报告运行状态 | 运行统计由调用方持有
+        NALInput::Status => Err(anyhow!("「报告运行状态」需由调用方处理")),
+        // 设置精度 | 同一次解析中已填入此后的预期；逐行输入时记入交互状态
+        NALInput::Precision(precision) => {
+            interactivity.set_precision(precision);
+            Ok(())
+        }
+        // 功能要求 | 缺少功能⇒以`INFO`输出记录跳过
+        NALInput::Requires(names) => {
+            let missing = requires.enter(&names);
+            if !missing.is_empty() {
+                let message = format!(
+                    "CIN缺少功能「{}」：跳过至`''end-requires`或文件末尾",
+                    missing.join(", ")
+                );
+                output_cache.put(Output::INFO { message })?;
+            }
+            Ok(())
+        }
+        // 结束功能要求
+        NALInput::EndRequires => {
+            requires.end();
+            Ok(())
+        }
+        // 重置预期窗口
+        NALInput::ResetExpectWindow => {
+            output_cache.reset_expect_window();
+            Ok(())
+        }
         // 睡眠
         NALInput::Sleep(duration) => {
             // 睡眠指定时间
@@ -83,42 +461,31 @@ pub fn put_nal(
             // 返回`ok`
             Ok(())
         }
-        // 等待一个符合预期的NAVM输出
-        NALInput::Await(expectation) => loop {
-            let output = match vm.fetch_output() {
-                Ok(output) => {
-                    // 加入缓存
-                    output_cache.put(output.clone())?;
-                    // ! ❌【2024-04-03 01:19:06】无法再返回引用：不再能直接操作数组，MutexGuard也不允许返回引用
-                    // output_cache.last().unwrap()
-                    output
-                }
-                Err(e) => {
-                    println!("尝试拉取输出出错：{e}");
-                    continue;
-                }
-            };
-            // 只有匹配了才返回
-            if expectation.matches(&output) {
-                break Ok(());
-            }
+        // 等待CIN安静下来
+        NALInput::AwaitIdle {
+            quiet_period,
+            max_wait,
+        } => match nal_await_idle(vm, quiet_period, max_wait, output_cache)? {
+            IdleResult::Idle => Ok(()),
+            IdleResult::TimedOut => Err(OutputExpectationError::IdleTimedOut(max_wait).into()),
         },
+        // 等待一个符合预期的NAVM输出
+        NALInput::Await(expectation) => nal_await(vm, expectation, output_cache),
         // 检查是否有NAVM输出符合预期
-        NALInput::ExpectContains(expectation) => {
+        NALInput::ExpectContains(expectation, max_latency) => {
             // 先尝试拉取所有输出到「输出缓存」
-            while let Some(output) = vm.try_fetch_output()? {
-                output_cache.put(output)?;
+            // * 🚩此处不关心「虚拟机是否已终止」：终止前的输出仍然有效
+            pull_outputs(vm, output_cache)?;
+            // 有「最大延迟」⇒检查延迟
+            if let Some(max_latency) = max_latency {
+                return nal_expect_latency(expectation, max_latency, output_cache);
             }
             // 然后读取并匹配缓存
-            let result = output_cache.for_each(|output| match expectation.matches(output) {
-                true => ControlFlow::Break(true),
-                false => ControlFlow::Continue(()),
-            })?;
-            match result {
+            match find_match(&expectation, output_cache)? {
                 // 只有匹配到了一个，才返回Ok
-                Some(true) => Ok(()),
+                Some(..) => Ok(()),
                 // 否则返回Err
-                _ => Err(OutputExpectationError::ExpectedNotExists(expectation).into()),
+                None => expected_not_exists(expectation, output_cache),
             }
             // for output in output_cache.for_each() {
             //     // 只有匹配了才返回Ok
@@ -128,74 +495,2010 @@ pub fn put_nal(
         }
         // 检查在指定的「最大步数」内，是否有NAVM输出符合预期（弹性步数`0~最大步数`）
         NALInput::ExpectCycle(max_cycles, step_cycles, step_duration, expectation) => {
-            let mut cycles = 0;
-            while cycles < max_cycles {
-                // 推理步进
-                vm.input_cmd(Cmd::CYC(step_cycles))?;
-                cycles += step_cycles;
-                // 等待指定时长
-                if let Some(duration) = step_duration {
-                    std::thread::sleep(duration);
-                }
-                // 先尝试拉取所有输出到「输出缓存」
-                while let Some(output) = vm.try_fetch_output()? {
-                    output_cache.put(output)?;
+            nal_expect_cycle(
+                vm,
+                max_cycles,
+                step_cycles,
+                step_duration,
+                expectation,
+                output_cache,
+            )
+        }
+        // 检查在指定的「最大步数」内，是否始终没有NAVM输出符合预期
+        NALInput::ExpectCycleNot(max_cycles, step_cycles, step_duration, expectation) => {
+            nal_expect_cycle_not(
+                vm,
+                max_cycles,
+                step_cycles,
+                step_duration,
+                expectation,
+                output_cache,
+            )
+        }
+        // 统计在指定的「最大步数」内，同类输出中符合预期的比例
+        NALInput::ExpectRate {
+            max_cycles,
+            step_cycles,
+            threshold,
+            expectation,
+        } => nal_expect_rate(
+            vm,
+            max_cycles,
+            step_cycles,
+            threshold,
+            expectation,
+            output_cache,
+        ),
+        // 保存（所有）输出
+        // * 🚩使用基于`nal_root_path`的相对路径
+        NALInput::SaveOutputs { format, path } => {
+            nal_save_outputs(format, nal_root_path, &path, output_cache)
+        }
+        // 终止虚拟机
+        NALInput::Terminate {
+            if_not_user,
+            if_no_client,
+            result,
+        } => nal_terminate(vm, if_not_user, if_no_client, result, interactivity),
+    }
+}
+
+/// 终止虚拟机
+/// * 🎯[`NALInput::Terminate`]
+/// * 🚩检查前提条件：不满足⇒放弃终止
+///   * 📌`if-no-user`：启用了用户输入，或有Websocket客户端⇒放弃
+///   * 📌`if-no-client`：有Websocket客户端⇒放弃
+/// * 🚩终止后返回指定的结果
+fn nal_terminate(
+    vm: &mut impl VmRuntimeBoost,
+    if_not_user: bool,
+    if_no_client: bool,
+    result: std::result::Result<(), String>,
+    interactivity: &InteractivityState,
+) -> Result<()> {
+    // 检查前提条件
+    if_return! {
+        if_not_user && interactivity.is_interactive() => Ok(())
+        if_no_client && interactivity.has_websocket_clients() => Ok(())
+    }
+
+    // 终止虚拟机
+    vm.terminate()?;
+
+    // 返回
+    result.map_err(|e| anyhow!("{e:?}"))
+}
+
+/// 保存（所有）输出到文件
+/// * 🎯[`NALInput::SaveOutputs`]
+/// * 🚩按文件格式分派
+///   * JSON：对象数组；若有「存入时间」，则附加到`t`字段（毫秒）
+///     * 📌回答有所关联的问题⇒附加到`answersTo`字段（问题编号）
+///   * NAL：每个含Narsese的输出一行CommonNarsese，前附「输出类型」注释
+///   * CSV：`type,narsese,raw_content`三列，首行为表头
+/// * 📌空缓存⇒仍为格式有效的空文件
+/// * 📌路径中的时间戳、旧文件的清理等见[`write_saved_outputs`]
+/// * ❌【2024-04-09 22:22:04】执行「NAL输入」时，应始终静默：不提示「已保存」
+fn nal_save_outputs(
+    format: SaveOutputsFormat,
+    nal_root_path: &Path,
+    path: &str,
+    output_cache: &impl VmOutputCache,
+) -> Result<()> {
+    let mut file_str = String::new();
+    match format {
+        SaveOutputsFormat::Json => {
+            let mut values = vec![];
+            output_cache.for_each_timed(|output, time| {
+                let mut value = output_to_json_timed(output, time);
+                let answer_to = output_cache.answer_to(values.len());
+                if let (Some(id), Some(object)) = (answer_to, value.as_object_mut()) {
+                    object.insert("answersTo".into(), id.into());
                 }
-                // 然后读取并匹配缓存
-                let result = output_cache.for_each(|output| match expectation.matches(output) {
-                    true => ControlFlow::Break(true),
-                    false => ControlFlow::Continue(()),
-                })?;
-                // 匹配到一个⇒提前返回Ok
-                if let Some(true) = result {
-                    OutputType::Info.print_line(&format!("expect-cycle({cycles}): {expectation}"));
-                    return Ok(());
+                values.push(value);
+                ControlFlow::<()>::Continue(())
+            })?;
+            file_str = serde_json::to_string_pretty(&values)?;
+        }
+        SaveOutputsFormat::Nal => {
+            output_cache.for_each(|output| {
+                if let Some(narsese) = output.get_narsese() {
+                    file_str += &format!("'{}\n", output.type_name());
+                    file_str += &FORMAT_ASCII.format_narsese(narsese);
+                    file_str.push('\n');
                 }
-            }
-            // 步进完所有步数，仍未有匹配⇒返回Err
-            Err(OutputExpectationError::ExpectedNotExists(expectation).into())
+                ControlFlow::<()>::Continue(())
+            })?;
         }
-        // 保存（所有）输出
-        // * 🚩输出到一个文本文件中
-        // * ✨复合JSON「对象数组」格式
-        NALInput::SaveOutputs(path_str) => {
-            // 先收集所有输出的字符串
-            let mut file_str = "[".to_string();
+        SaveOutputsFormat::Csv => {
+            file_str += "type,narsese,raw_content\n";
             output_cache.for_each(|output| {
-                // 换行制表
-                file_str += "\n\t";
-                // 统一追加到字符串中
-                file_str += &output.to_json_string();
-                // 逗号
-                file_str.push(',');
-                // 继续
+                let narsese = output
+                    .get_narsese()
+                    .map(|narsese| FORMAT_ASCII.format_narsese(narsese))
+                    .unwrap_or_default();
+                let fields = [output.type_name(), &narsese, output.raw_content()];
+                file_str += &fields.map(csv_field).join(",");
+                file_str.push('\n');
                 ControlFlow::<()>::Continue(())
             })?;
-            // 删去尾后逗号
-            file_str.pop();
-            // 换行，终止符
-            file_str += "\n]";
-            // 保存到文件中 | 使用基于`nal_root_path`的相对路径
-            let path = nal_root_path.join(path_str.trim());
-            std::fs::write(path, file_str)?;
-            // 提示 | ❌【2024-04-09 22:22:04】执行「NAL输入」时，应始终静默
-            // println_cli!([Info] "已将所有NAVM输出保存到文件{path:?}");
-            // 返回
+        }
+    }
+    write_saved_outputs(
+        nal_root_path,
+        path,
+        &file_str,
+        output_cache.save_outputs_options(),
+        SystemTime::now(),
+    )?;
+    Ok(())
+}
+
+/// 将NAVM输出转换为JSON值，并附带「存入时间」
+/// * 🎯[`NALInput::SaveOutputs`]
+/// * 🚩有时间⇒在JSON对象中追加`t`字段（毫秒）；无时间⇒与[`output_to_json`]一致
+fn output_to_json_timed(output: &Output, time: Option<Duration>) -> serde_json::Value {
+    let mut value = output_to_json(output);
+    if let (Some(time), Some(object)) = (time, value.as_object_mut()) {
+        object.insert("t".into(), (time.as_millis() as u64).into());
+    }
+    value
+}
+
+/// 转义CSV字段
+/// * 🚩含逗号、引号、换行⇒整体加引号，内部引号双写
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_owned(),
+    }
+}
+
+/// 检查「输出缓存」中是否有在「最大延迟」内符合预期的输出
+/// * 🎯带「最大延迟」的[`NALInput::ExpectContains`]
+/// * 🚩以最近一次「置入Narsese」为起点，只认其后存入的输出
+///   * 📌输出按存入顺序遍历：第一个符合的即为延迟最小者
+///   * 📌同样只认[预期窗口](VmOutputCache::expect_window)内的输出
+/// * 📌时间为运行时「收到输出」的时间：见[`VmRuntimeBoost::drain_outputs_timed`]
+///   * ⚠️运行时不记录收到时间⇒以拉取的时刻代替，拉取不及时则测得的延迟偏大
+fn nal_expect_latency(
+    expectation: OutputExpectation,
+    max_latency: Duration,
+    output_cache: &impl VmOutputCache,
+) -> Result<()> {
+    let Some(nse_time) = output_cache.last_nse_input() else {
+        return Err(OutputExpectationError::LatencyUnmeasurable(expectation).into());
+    };
+    // 找到第一个符合预期的输出 | 无时间⇒`Some(None)`
+    let latency = find_match_since(&expectation, nse_time, output_cache)?
+        .map(|(_, time)| time.map(|time| time - nse_time));
+    match latency {
+        Some(Some(latency)) if latency <= max_latency => Ok(()),
+        Some(Some(latency)) => Err(OutputExpectationError::LatencyExceeded {
+            expectation,
+            max_latency,
+            latency,
+        }
+        .into()),
+        Some(None) => Err(OutputExpectationError::LatencyUnmeasurable(expectation).into()),
+        None => expected_not_exists(expectation, output_cache),
+    }
+}
+
+/// 找到「置入Narsese」之后第一个符合预期的输出，连同其「收到时间」
+/// * 🎯[`nal_expect_latency`]
+/// * 📌只认[预期窗口](VmOutputCache::expect_window)内、不早于`nse_time`的输出
+///   * 📌无时间的输出不做时间上的筛选
+fn find_match_since(
+    expectation: &OutputExpectation,
+    nse_time: Duration,
+    output_cache: &impl VmOutputCache,
+) -> Result<Option<(Output, Option<Duration>)>> {
+    let (start, mut index) = (output_cache.expect_window(), 0);
+    output_cache.for_each_timed(|output, time| {
+        index += 1;
+        match time {
+            // 预期窗口之前的输出⇒不计
+            _ if index <= start => ControlFlow::Continue(()),
+            // 置入Narsese之前的输出⇒不计
+            Some(time) if time < nse_time => ControlFlow::Continue(()),
+            _ if !expectation.matches(output) => ControlFlow::Continue(()),
+            time => ControlFlow::Break((output.clone(), time)),
+        }
+    })
+}
+
+/// 满足了某项预期的输出
+/// * 🎯测试报告：记录「实际满足预期」的那条输出
+/// * ⚠️需在该预期通过后、置入下一条输入前调用：依赖当时的「输出缓存」与预期窗口
+/// * 🚩与各预期的判定方式保持一致
+///   * 📌`''await`：等到的最后一条相符输出
+///   * 📌`''expect-contains`、`''expect-cycle`：预期窗口内第一条相符输出
+///     * 📌带「最大延迟」⇒「置入Narsese」之后的第一条
+///   * 📌`''expect-cycle-not`、`''expect-rate`及其它输入：没有单独满足预期的输出⇒[`None`]
+pub fn matched_output(
+    input: &NALInput,
+    output_cache: &impl VmOutputCache,
+) -> Result<Option<Output>> {
+    match input {
+        NALInput::Await(expectation) => {
+            let mut matched = None;
+            output_cache.for_each(|output| {
+                if expectation.matches(output) {
+                    matched = Some(output.clone());
+                }
+                ControlFlow::<()>::Continue(())
+            })?;
+            Ok(matched)
+        }
+        NALInput::ExpectContains(expectation, Some(..)) => match output_cache.last_nse_input() {
+            Some(nse_time) => Ok(
+                find_match_since(expectation, nse_time, output_cache)?.map(|(output, _)| output)
+            ),
+            None => Ok(None),
+        },
+        NALInput::ExpectContains(expectation, None) | NALInput::ExpectCycle(.., expectation) => {
+            find_match(expectation, output_cache)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// 等待一个符合预期的NAVM输出
+/// * 🎯[`NALInput::Await`]
+/// * 🚩虚拟机终止⇒立即返回[`OutputExpectationError::VmTerminated`]
+///   * ⚠️否则在「CIN已崩溃」时会无限等待
+/// * 🚩收到终止信号⇒不再等待
+fn nal_await(
+    vm: &mut impl VmRuntime,
+    expectation: OutputExpectation,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<()> {
+    loop {
+        if_return! { is_shutdown_requested() => Err(anyhow!("已收到终止信号，不再等待")) }
+        let output = match vm.fetch_output() {
+            Ok(output) => {
+                // 加入缓存
+                output_cache.put(output.clone())?;
+                // ! ❌【2024-04-03 01:19:06】无法再返回引用：不再能直接操作数组，MutexGuard也不允许返回引用
+                // output_cache.last().unwrap()
+                output
+            }
+            Err(e) => {
+                // 虚拟机已终止⇒不再等待
+                check_terminated(vm)?;
+                print_notice("WARN", &format!("尝试拉取输出出错：{e}"));
+                continue;
+            }
+        };
+        // 只有匹配了才返回
+        if expectation.matches(&output) {
+            break Ok(());
+        }
+        // 收到「终止」输出⇒不再等待
+        if let Output::TERMINATED { description } = output {
+            break Err(OutputExpectationError::VmTerminated(description).into());
+        }
+    }
+}
+
+/// 包含文件
+/// * 🎯[`NALInput::Include`]
+/// * 🚩相对路径基于「NAL执行路径」；被包含文件中的相对路径，则基于其自身所在目录
+/// * 🚩规范化路径后与「包含栈」比对，拒绝循环包含
+/// * 🚩逐条置入被包含文件中的输入：任一出错⇒立即返回
+///   * 📌「功能要求」区段不跨文件：被包含文件有自己的区段
+fn nal_include(
+    vm: &mut impl VmRuntimeBoost,
+    path: &str,
+    output_cache: &mut impl VmOutputCache,
+    context: &mut PutNalContext,
+    include_stack: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let (nal, path) = read_included_nal(path, context.nal_root_path, include_stack)?;
+    let root = path.parent().unwrap_or(context.nal_root_path).to_path_buf();
+    let mut requires = context.requires.for_another_file();
+    let mut included_context = PutNalContext {
+        interactivity: context.interactivity,
+        nal_root_path: &root,
+        set_ack: context.set_ack,
+        cyc_await: context.cyc_await,
+        requires: &mut requires,
+    };
+    include_stack.push(path);
+    let result = parse(&nal).into_iter().try_for_each(|input| match input? {
+        NALInput::Include(path) if !included_context.requires.is_skipping() => nal_include(
+            vm,
+            &path,
+            output_cache,
+            &mut included_context,
+            include_stack,
+        ),
+        input => put_nal(vm, input, output_cache, &mut included_context),
+    });
+    include_stack.pop();
+    result
+}
+
+/// 读取`''include:`所包含的NAL文件
+/// * 🚩相对路径基于「NAL执行路径」解析
+/// * 🚩规范化路径后与「包含栈」比对，拒绝循环包含
+/// * ⚙️返回「文件内容」与「规范化后的文件路径」
+pub fn read_included_nal(
+    path: &str,
+    nal_root_path: &Path,
+    include_stack: &[PathBuf],
+) -> Result<(String, PathBuf)> {
+    let path = nal_root_path.join(path.trim());
+    let path = path
+        .canonicalize()
+        .map_err(|e| anyhow!("无法定位文件{path:?}：{e}"))?;
+    if include_stack.contains(&path) {
+        let chain = include_stack
+            .iter()
+            .chain([&path])
+            .map(|p| format!("{p:?}"))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(anyhow!("检测到循环包含：{chain}"));
+    }
+    let nal = std::fs::read_to_string(&path)?;
+    Ok((nal, path))
+}
+
+/// 等待CIN安静下来
+/// * 🎯[`NALInput::AwaitIdle`]
+/// * 🚩计时与判定见[`wait_until_idle`]：拉取到输出即为活动
+///   * 📌持续拉取输出到「输出缓存」：不丢失等待期间的输出
+/// * 🚩虚拟机终止⇒不会再有输出，视作安静
+/// * 🚩收到终止信号⇒不再等待
+fn nal_await_idle(
+    vm: &mut impl VmRuntimeBoost,
+    quiet_period: Duration,
+    max_wait: Duration,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<IdleResult> {
+    wait_until_idle(quiet_period, max_wait, || {
+        if_return! { is_shutdown_requested() => Err(anyhow!("已收到终止信号，不再等待")) }
+        let outputs = vm.drain_outputs_timed()?;
+        let active = !outputs.is_empty();
+        for (output, received) in outputs {
+            output_cache.put_received(output, received)?;
+        }
+        Ok(match (active, vm.status()) {
+            (true, _) => IdleProbe::Active,
+            (false, VmStatus::Terminated(..)) => IdleProbe::Closed,
+            (false, VmStatus::Running) => IdleProbe::Quiet,
+        })
+    })
+}
+
+/// 置入NAVM指令，并等待CIN的回执
+/// * 🎯[`NALInput::Set`]
+/// * 🚩只认置入后新产生的输出：符合该指令的[回执](SetAck)⇒视作已确认
+/// * 🚩未指定该指令的回执⇒置入后返回[`OutputExpectationError::SetAckUnspecified`]
+/// * 🚩超时⇒返回[`OutputExpectationError::SetNotAcknowledged`]
+///   * 📌是否因此中止，交给调用方的「严格模式」决定
+fn nal_set(
+    vm: &mut impl VmRuntimeBoost,
+    cmd: Cmd,
+    set_ack: &SetAck,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<()> {
+    // 先拉取已有输出，避免被误认作回执
+    if let Some(description) = pull_outputs(vm, output_cache)? {
+        return Err(OutputExpectationError::VmTerminated(description).into());
+    }
+    let cmd_str = cmd.to_string();
+    if set_ack.ack_for(&cmd).is_none() {
+        vm.input_cmd(cmd)?;
+        return Err(OutputExpectationError::SetAckUnspecified(cmd_str).into());
+    }
+    vm.input_cmd(cmd.clone())?;
+    let start = Instant::now();
+    while start.elapsed() < SET_ACK_TIMEOUT {
+        let output = match vm.try_fetch_output()? {
+            Some(output) => output,
+            // 暂无输出⇒检查终止后稍等
+            None => {
+                check_terminated(vm)?;
+                std::thread::sleep(SET_ACK_POLL_INTERVAL);
+                continue;
+            }
+        };
+        let acknowledged = set_ack.is_ack(&cmd, &output);
+        let terminated = match &output {
+            Output::TERMINATED { description } => Some(description.clone()),
+            _ => None,
+        };
+        output_cache.put(output)?;
+        if_return! { acknowledged => Ok(()) }
+        if let Some(description) = terminated {
+            return Err(OutputExpectationError::VmTerminated(description).into());
+        }
+    }
+    Err(OutputExpectationError::SetNotAcknowledged(cmd_str).into())
+}
+
+/// 置入`CYC`指令，并等待其步进完成
+/// * 🎯[`NALInput::Put`]：启用了[`CycAwait`]时
+/// * 🚩先等待输入送达CIN：分批输入的`CYC`须全部输入
+/// * 🚩有回执⇒只认置入后新产生的输出，直到收齐每批的回执
+/// * 🚩无回执⇒等待CIN安静下来
+/// * 🚩超时⇒返回错误；是否因此中止，交给调用方的「严格模式」决定
+fn nal_await_cyc(
+    vm: &mut impl VmRuntimeBoost,
+    cmd: Cmd,
+    cyc_await: CycAwait,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<()> {
+    // 先拉取已有输出，避免此前的回执被误认
+    if let Some(description) = pull_outputs(vm, output_cache)? {
+        return Err(OutputExpectationError::VmTerminated(description).into());
+    }
+    let cmd_str = cmd.to_string();
+    let mut remaining_acks = match cmd {
+        Cmd::CYC(steps) => vm.cyc_ack_count(steps),
+        _ => 1,
+    };
+    vm.input_cmd(cmd)?;
+    vm.flush_inputs()?;
+    let is_ack = match cyc_await {
+        CycAwait::Ack(is_ack) => is_ack,
+        CycAwait::Idle => {
+            return match nal_await_idle(
+                vm,
+                CYC_AWAIT_QUIET_PERIOD,
+                CYC_AWAIT_TIMEOUT,
+                output_cache,
+            )? {
+                IdleResult::Idle => Ok(()),
+                IdleResult::TimedOut => {
+                    Err(OutputExpectationError::IdleTimedOut(CYC_AWAIT_TIMEOUT).into())
+                }
+            }
+        }
+    };
+    let start = Instant::now();
+    while start.elapsed() < CYC_AWAIT_TIMEOUT {
+        if_return! { is_shutdown_requested() => Err(anyhow!("已收到终止信号，不再等待")) }
+        let outputs = vm.drain_outputs_timed()?;
+        // 暂无输出⇒检查终止后稍等
+        if outputs.is_empty() {
+            check_terminated(vm)?;
+            std::thread::sleep(IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let acks = outputs.iter().filter(|(output, _)| is_ack(output)).count();
+        remaining_acks = remaining_acks.saturating_sub(acks);
+        for (output, received) in outputs {
+            output_cache.put_received(output, received)?;
+        }
+        if_return! { remaining_acks == 0 => Ok(()) }
+    }
+    Err(OutputExpectationError::SetNotAcknowledged(cmd_str).into())
+}
+
+/// 检查在指定的「最大步数」内，是否有NAVM输出符合预期（弹性步数`0~最大步数`）
+/// * 🎯[`NALInput::ExpectCycle`]
+/// * 🚩虚拟机终止⇒立即返回[`OutputExpectationError::VmTerminated`]
+///   * 🎯避免「CIN中途崩溃」被误报为「预期不符」
+fn nal_expect_cycle(
+    vm: &mut impl VmRuntimeBoost,
+    max_cycles: usize,
+    step_cycles: usize,
+    step_duration: Option<Duration>,
+    expectation: OutputExpectation,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<()> {
+    let mut cycles = 0;
+    while cycles < max_cycles {
+        // 已终止⇒不再步进
+        check_terminated(vm)?;
+        // 推理步进 | 失败时优先上报「虚拟机已终止」
+        if let Err(e) = vm.input_cmd(Cmd::CYC(step_cycles)) {
+            check_terminated(vm)?;
+            return Err(e);
+        }
+        cycles += step_cycles;
+        // 等待指定时长
+        if let Some(duration) = step_duration {
+            std::thread::sleep(duration);
+        }
+        // 先尝试拉取所有输出到「输出缓存」
+        let terminated = pull_outputs(vm, output_cache)?;
+        // 然后读取并匹配缓存 | 匹配到一个⇒提前返回Ok
+        if find_match(&expectation, output_cache)?.is_some() {
+            print_notice("INFO", &format!("expect-cycle({cycles}): {expectation}"));
+            return Ok(());
+        }
+        // 拉取到「终止」输出⇒提前返回
+        if let Some(description) = terminated {
+            return Err(OutputExpectationError::VmTerminated(description).into());
+        }
+    }
+    // 步进完所有步数，仍未有匹配⇒返回Err
+    expected_not_exists(expectation, output_cache)
+}
+
+/// 检查在指定的「最大步数」内，是否始终没有NAVM输出符合预期
+/// * 🎯[`NALInput::ExpectCycleNot`]
+/// * 🚩先拉取已有输出，以此划定检查窗口：预期开始前的输出不计
+/// * 🚩每步进后检查窗口内的输出：一旦符合⇒立即返回[`OutputExpectationError::UnexpectedExists`]
+/// * 🚩虚拟机终止⇒立即返回[`OutputExpectationError::VmTerminated`]
+///   * 📌无法再确认「此后不会出现」
+fn nal_expect_cycle_not(
+    vm: &mut impl VmRuntimeBoost,
+    max_cycles: usize,
+    step_cycles: usize,
+    step_duration: Option<Duration>,
+    expectation: OutputExpectation,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<()> {
+    // 最大步数为`0`⇒不步进，只检查预期窗口内已有的输出
+    if max_cycles == 0 {
+        pull_outputs(vm, output_cache)?;
+        let window_start = output_cache.expect_window();
+        find_unexpected(&expectation, window_start, 0, output_cache)?;
+        print_notice("INFO", &format!("expect-cycle-not(0): {expectation}"));
+        return Ok(());
+    }
+    // 划定检查窗口
+    let window_start = window_start(vm, output_cache)?;
+    let mut cycles = 0;
+    while cycles < max_cycles {
+        // 已终止⇒不再步进
+        check_terminated(vm)?;
+        // 推理步进 | 失败时优先上报「虚拟机已终止」
+        if let Err(e) = vm.input_cmd(Cmd::CYC(step_cycles)) {
+            check_terminated(vm)?;
+            return Err(e);
+        }
+        cycles += step_cycles;
+        // 等待指定时长
+        if let Some(duration) = step_duration {
+            std::thread::sleep(duration);
+        }
+        // 拉取输出，检查窗口内是否有输出符合预期 | 有⇒立即返回Err
+        let terminated = pull_outputs(vm, output_cache)?;
+        find_unexpected(&expectation, window_start, cycles, output_cache)?;
+        // 拉取到「终止」输出⇒提前返回
+        if let Some(description) = terminated {
+            return Err(OutputExpectationError::VmTerminated(description).into());
+        }
+    }
+    // 步进完所有步数，始终没有匹配⇒返回Ok
+    print_notice(
+        "INFO",
+        &format!("expect-cycle-not({cycles}): {expectation}"),
+    );
+    Ok(())
+}
+
+/// 检查窗口内是否有「不应出现」的输出
+/// * 🎯[`NALInput::ExpectCycleNot`]
+/// * 🚩有输出符合预期⇒返回[`OutputExpectationError::UnexpectedExists`]
+fn find_unexpected(
+    expectation: &OutputExpectation,
+    window_start: usize,
+    cycles: usize,
+    output_cache: &impl VmOutputCache,
+) -> Result<()> {
+    let found =
+        output_cache.for_each_from(window_start, |output| match expectation.matches(output) {
+            true => ControlFlow::Break(output.clone()),
+            false => ControlFlow::Continue(()),
+        })?;
+    match found {
+        Some(output) => Err(OutputExpectationError::UnexpectedExists {
+            expectation: expectation.clone(),
+            output,
+            cycles,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// 划定「只统计此后输出」的窗口
+/// * 🎯[`NALInput::ExpectCycleNot`]、[`NALInput::ExpectRate`]
+/// * 🚩先拉取已有输出，再返回「输出缓存」中的输出数：索引不小于此者为窗口内的输出
+/// * 🚩拉取到「终止」输出⇒返回[`OutputExpectationError::VmTerminated`]
+fn window_start(
+    vm: &mut impl VmRuntimeBoost,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<usize> {
+    if let Some(description) = pull_outputs(vm, output_cache)? {
+        return Err(OutputExpectationError::VmTerminated(description).into());
+    }
+    output_cache.snapshot()
+}
+
+/// 统计在指定的「最大步数」内，同类输出中符合预期的比例
+/// * 🎯[`NALInput::ExpectRate`]
+/// * 🚩先拉取已有输出，以此划定统计窗口：预期开始前的输出不计
+/// * 🚩步进完所有步数后再统计：不因早期样本而提前结束
+/// * 🚩虚拟机终止⇒立即返回[`OutputExpectationError::VmTerminated`]
+fn nal_expect_rate(
+    vm: &mut impl VmRuntimeBoost,
+    max_cycles: usize,
+    step_cycles: usize,
+    threshold: Rate,
+    expectation: OutputExpectation,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<()> {
+    // 划定统计窗口
+    let window_start = window_start(vm, output_cache)?;
+    // 步进
+    let mut cycles = 0;
+    while cycles < max_cycles {
+        check_terminated(vm)?;
+        if let Err(e) = vm.input_cmd(Cmd::CYC(step_cycles)) {
+            check_terminated(vm)?;
+            return Err(e);
+        }
+        cycles += step_cycles;
+        if let Some(description) = pull_outputs(vm, output_cache)? {
+            return Err(OutputExpectationError::VmTerminated(description).into());
+        }
+    }
+    // 统计窗口内的同类输出
+    let (mut matched, mut total) = (0, 0);
+    output_cache.for_each_from(window_start, |output| {
+        // * 🚩与[`OutputExpectation::matches`]一致：别名亦算同类
+        if expectation.matches_type(output.type_name()) {
+            total += 1;
+            if expectation.matches(output) {
+                matched += 1;
+            }
+        }
+        ControlFlow::<()>::Continue(())
+    })?;
+    if_return! { total == 0 => expected_not_exists(expectation, output_cache) }
+    match matched as f64 >= threshold.0 * total as f64 {
+        true => {
+            print_notice(
+                "INFO",
+                &format!("expect-rate({matched}/{total}): {expectation}"),
+            );
             Ok(())
         }
-        // 终止虚拟机
-        NALInput::Terminate {
-            if_not_user,
-            result,
-        } => {
-            // 检查前提条件 | 仅「非用户输入」&启用了用户输入 ⇒ 放弃终止
-            if_return! { if_not_user && enabled_user_input => Ok(()) }
+        false => Err(OutputExpectationError::RateBelowThreshold {
+            expectation,
+            matched,
+            total,
+            threshold,
+        }
+        .into()),
+    }
+}
+
+/// 拉取虚拟机现有的所有输出到「输出缓存」
+/// * 🚩非阻塞：使用[`VmRuntimeBoost::drain_outputs_timed`]，连同「收到时间」一并存入
+/// * ⚙️返回值：若拉取到[`Output::TERMINATED`]，则返回其描述
+fn pull_outputs(
+    vm: &mut impl VmRuntimeBoost,
+    output_cache: &mut impl VmOutputCache,
+) -> Result<Option<String>> {
+    let mut terminated = None;
+    for (output, received) in vm.drain_outputs_timed()? {
+        if let Output::TERMINATED { description } = &output {
+            terminated = Some(description.clone());
+        }
+        output_cache.put_received(output, received)?;
+    }
+    Ok(terminated)
+}
+
+/// 找到「输出缓存」中第一个符合预期的输出
+/// * 🚩只检查[预期窗口](VmOutputCache::expect_window)内的输出
+fn find_match(
+    expectation: &OutputExpectation,
+    output_cache: &impl VmOutputCache,
+) -> Result<Option<Output>> {
+    let start = output_cache.expect_window();
+    output_cache.for_each_from(start, |output| match expectation.matches(output) {
+        true => ControlFlow::Break(output.clone()),
+        false => ControlFlow::Continue(()),
+    })
+}
+
+/// 检查虚拟机是否已终止
+/// * 🚩已终止⇒返回[`OutputExpectationError::VmTerminated`]
+fn check_terminated(vm: &impl VmRuntime) -> Result<()> {
+    match vm.status() {
+        VmStatus::Running => Ok(()),
+        VmStatus::Terminated(result) => {
+            let description = match result {
+                Ok(..) => "正常终止".to_string(),
+                Err(e) => e.to_string(),
+            };
+            Err(OutputExpectationError::VmTerminated(description).into())
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cli_support::io::navm_output_cache::OutputCache,
+        runtimes::{Capabilities, FakeVm, FakeVmRuntime, TranslateError},
+        test_tools::{
+            nal_format::{parse, parse_single},
+            FloatPrecision, OutputTypeAliases,
+        },
+    };
+    use anyhow::anyhow;
+    use nar_dev_utils::manipulate;
+    use narsese::conversion::string::impl_lexical::shortcuts::*;
+    use navm::{output::Operation, vm::VmLauncher};
+    use std::time::Instant;
+
+    /// 以默认上下文置入NAL输入
+    /// * 🎯不涉及「设置」回执、`CYC`等待与「功能要求」的测试
+    fn put_nal_default(
+        vm: &mut impl VmRuntimeBoost,
+        input: NALInput,
+        output_cache: &mut impl VmOutputCache,
+    ) -> Result<()> {
+        let mut context = PutNalContext {
+            interactivity: &InteractivityState::default(),
+            nal_root_path: Path::new("."),
+            set_ack: &SetAck::default(),
+            cyc_await: None,
+            requires: &mut RequiresRegion::default(),
+        };
+        put_nal(vm, input, output_cache, &mut context)
+    }
+
+    /// 一个「即将崩溃」的运行时
+    /// * 🎯模拟「CIN中途崩溃」：第一次拉取输出时产生[`Output::TERMINATED`]，此后所有操作均报错
+    struct CrashingRuntime {
+        status: VmStatus,
+        /// 输入的指令数
+        num_inputs: usize,
+    }
+
+    impl CrashingRuntime {
+        fn new() -> Self {
+            Self {
+                status: VmStatus::Running,
+                num_inputs: 0,
+            }
+        }
+
+        /// 产生「终止」输出，并将自身置为「已终止」
+        fn crash(&mut self) -> Result<Output> {
+            match self.status {
+                VmStatus::Running => {
+                    self.status = VmStatus::Terminated(Err(anyhow!("CIN崩溃")));
+                    Ok(Output::TERMINATED {
+                        description: "CIN崩溃".into(),
+                    })
+                }
+                VmStatus::Terminated(..) => Err(anyhow!("虚拟机已终止")),
+            }
+        }
+    }
+
+    impl VmRuntime for CrashingRuntime {
+        fn input_cmd(&mut self, _: Cmd) -> Result<()> {
+            self.num_inputs += 1;
+            match self.status {
+                VmStatus::Running => Ok(()),
+                VmStatus::Terminated(..) => Err(anyhow!("虚拟机已终止")),
+            }
+        }
+
+        fn fetch_output(&mut self) -> Result<Output> {
+            self.crash()
+        }
 
-            // 终止虚拟机
-            vm.terminate()?;
+        fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+            match self.status {
+                VmStatus::Running => self.crash().map(Some),
+                VmStatus::Terminated(..) => Ok(None),
+            }
+        }
+
+        fn status(&self) -> &VmStatus {
+            &self.status
+        }
 
-            // 返回
-            result.transform_err(error_anyhow)
+        fn terminate(&mut self) -> Result<()> {
+            self.status = VmStatus::Terminated(Ok(()));
+            Ok(())
+        }
+    }
+
+    impl VmRuntimeBoost for CrashingRuntime {}
+
+    /// 断言结果为「虚拟机已终止」
+    fn assert_vm_terminated(result: Result<()>) {
+        let e = result.expect_err("应该返回错误");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::VmTerminated(..))
+            ),
+            "{e}"
+        );
+    }
+
+    /// 测试/循环预期：虚拟机终止后立即返回
+    #[test]
+    fn expect_cycle_vm_terminated() {
+        let mut vm = CrashingRuntime::new();
+        let mut cache = vec![];
+        let input = NALInput::ExpectCycle(usize::MAX, 1, None, OutputExpectation::default_answer());
+        let result = put_nal_default(&mut vm, input, &mut cache);
+        assert_vm_terminated(result);
+        // 只步进了一次，「终止」输出也被缓存
+        assert_eq!(vm.num_inputs, 1);
+        assert!(matches!(cache[..], [Output::TERMINATED { .. }]));
+    }
+
+    /// 测试/输出等待：虚拟机终止后不再无限等待
+    #[test]
+    fn await_vm_terminated() {
+        // 拉取到「终止」输出
+        let mut vm = CrashingRuntime::new();
+        let start = Instant::now();
+        let input = NALInput::Await(OutputExpectation::default_answer());
+        let result = put_nal_default(&mut vm, input.clone(), &mut vec![]);
+        assert_vm_terminated(result);
+        // 已终止，拉取输出出错
+        let result = put_nal_default(&mut vm, input, &mut vec![]);
+        assert_vm_terminated(result);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    /// 测试/附带行号
+    /// * 🎯错误信息中含行号，且仍可向下转型
+    #[test]
+    fn nal_line_no() {
+        let e = with_nal_line_no(TranslateError::UnsupportedInput(Cmd::CYC(1)).into(), 3);
+        assert_eq!(e.to_string(), "第3行：不支持的NAVM指令：\"CYC 1\"");
+        assert!(matches!(
+            e.downcast_ref::<TranslateError>(),
+            Some(TranslateError::UnsupportedInput(..))
+        ));
+        let e = with_nal_source(
+            TranslateError::UnsupportedInput(Cmd::CYC(1)).into(),
+            "a.nal",
+            7,
+            "1",
+        );
+        assert_eq!(e.to_string(), "a.nal:7: 「1」：不支持的NAVM指令：\"CYC 1\"");
+        assert!(e.downcast_ref::<TranslateError>().is_some());
+    }
+
+    /// 阵发输出的模拟运行时
+    /// * 🎯测试「等待CIN安静下来」
+    /// * 🚩在阵发期内，每隔固定时间产生一条输出；此后沉默
+    struct BurstRuntime {
+        burst_until: Instant,
+        next_output: Instant,
+        status: VmStatus,
+    }
+
+    /// 阵发输出的间隔
+    const BURST_INTERVAL: Duration = Duration::from_millis(20);
+
+    impl BurstRuntime {
+        fn new(burst: Duration) -> Self {
+            let now = Instant::now();
+            Self {
+                burst_until: now + burst,
+                next_output: now,
+                status: VmStatus::Running,
+            }
+        }
+    }
+
+    impl VmRuntime for BurstRuntime {
+        fn input_cmd(&mut self, _: Cmd) -> Result<()> {
+            Ok(())
+        }
+
+        fn fetch_output(&mut self) -> Result<Output> {
+            Err(anyhow!("不支持阻塞拉取"))
+        }
+
+        fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+            let now = Instant::now();
+            if now >= self.burst_until || now < self.next_output {
+                return Ok(None);
+            }
+            self.next_output = now + BURST_INTERVAL;
+            Ok(Some(Output::OTHER {
+                content: "derived".into(),
+            }))
+        }
+
+        fn status(&self) -> &VmStatus {
+            &self.status
+        }
+
+        fn terminate(&mut self) -> Result<()> {
+            self.status = VmStatus::Terminated(Ok(()));
+            Ok(())
+        }
+    }
+
+    impl VmRuntimeBoost for BurstRuntime {}
+
+    /// 测试/等待CIN安静下来
+    /// * 🎯阵发输出后沉默⇒安静期后返回，期间的输出均存入缓存
+    /// * 🎯持续输出⇒超时报错
+    #[test]
+    fn await_idle() {
+        let burst = Duration::from_millis(200);
+        let mut vm = BurstRuntime::new(burst);
+        let mut cache = vec![];
+        let start = Instant::now();
+        put_nal_str(&mut vm, "''await-idle: 100ms 5s", &mut cache).expect("应等到安静");
+        assert!(start.elapsed() >= burst, "{:?}", start.elapsed());
+        assert!(cache.len() > 1, "{cache:?}");
+
+        let mut vm = BurstRuntime::new(Duration::from_secs(10));
+        let result = put_nal_str(&mut vm, "''await-idle: 100ms 300ms", &mut cache);
+        let e = result.expect_err("应超时");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::IdleTimedOut(..))
+            ),
+            "{e}"
+        );
+    }
+
+    /// 延时回执的模拟运行时
+    /// * 🎯测试「等待CYC步进完成」
+    /// * 🚩每次置入`CYC`，都在延时后产生一条「步进完成」的回执；期间不断产生其它输出
+    struct DelayedAckRuntime {
+        delay: Duration,
+        ack_at: Option<Instant>,
+        status: VmStatus,
+    }
+
+    impl DelayedAckRuntime {
+        fn new(delay: Duration) -> Self {
+            Self {
+                delay,
+                ack_at: None,
+                status: VmStatus::Running,
+            }
+        }
+    }
+
+    impl VmRuntime for DelayedAckRuntime {
+        fn input_cmd(&mut self, cmd: Cmd) -> Result<()> {
+            if let Cmd::CYC(..) = cmd {
+                self.ack_at = Some(Instant::now() + self.delay);
+            }
+            Ok(())
+        }
+
+        fn fetch_output(&mut self) -> Result<Output> {
+            Err(anyhow!("不支持阻塞拉取"))
+        }
+
+        fn try_fetch_output(&mut self) -> Result<Option<Output>> {
+            let Some(ack_at) = self.ack_at else {
+                return Ok(None);
+            };
+            let content = match Instant::now() >= ack_at {
+                true => {
+                    self.ack_at = None;
+                    "done"
+                }
+                false => "derived",
+            };
+            std::thread::sleep(BURST_INTERVAL);
+            Ok(Some(Output::OTHER {
+                content: content.into(),
+            }))
+        }
+
+        fn status(&self) -> &VmStatus {
+            &self.status
+        }
+
+        fn terminate(&mut self) -> Result<()> {
+            self.status = VmStatus::Terminated(Ok(()));
+            Ok(())
+        }
+    }
+
+    impl VmRuntimeBoost for DelayedAckRuntime {}
+
+    /// 「步进完成」的回执判断 | 用于[`DelayedAckRuntime`]
+    fn is_done(output: &Output) -> bool {
+        matches!(output, Output::OTHER { content } if content == "done")
+    }
+
+    /// 测试/等待CYC步进完成
+    /// * 🎯有回执⇒等到回执才返回，期间的输出均存入缓存
+    /// * 🎯无回执⇒等到CIN安静下来
+    /// * 🎯不等待⇒置入即返回
+    #[test]
+    fn await_cyc() {
+        let delay = Duration::from_millis(300);
+        let cyc = |vm: &mut DelayedAckRuntime, cache: &mut Vec<Output>, cyc_await| {
+            put_nal(
+                vm,
+                parse_single("10")?,
+                cache,
+                &mut PutNalContext {
+                    interactivity: &InteractivityState::default(),
+                    nal_root_path: Path::new("."),
+                    set_ack: &SetAck::default(),
+                    cyc_await,
+                    requires: &mut RequiresRegion::default(),
+                },
+            )
+        };
+        // 等待回执
+        let mut vm = DelayedAckRuntime::new(delay);
+        let mut cache = vec![];
+        let start = Instant::now();
+        cyc(&mut vm, &mut cache, Some(CycAwait::new(Some(is_done)))).expect("应收到回执");
+        assert!(start.elapsed() >= delay, "{:?}", start.elapsed());
+        assert!(cache.len() > 1, "{cache:?}");
+        assert!(cache.last().is_some_and(is_done), "{cache:?}");
+        // 等待安静
+        let mut cache = vec![];
+        let start = Instant::now();
+        cyc(&mut vm, &mut cache, Some(CycAwait::new(None))).expect("应等到安静");
+        assert!(start.elapsed() >= delay + CYC_AWAIT_QUIET_PERIOD);
+        assert!(cache.iter().any(is_done), "{cache:?}");
+        // 不等待
+        let mut cache = vec![];
+        let start = Instant::now();
+        cyc(&mut vm, &mut cache, None).expect("置入不应出错");
+        assert!(start.elapsed() < delay, "{:?}", start.elapsed());
+        assert!(cache.is_empty(), "{cache:?}");
+    }
+
+    /// 测试/等待分批输入的CYC步进完成
+    /// * 🎯`cycAwait`与`cycPacing`同时启用：收齐每批的回执才返回
+    /// * 🚩子进程回显每批的`CYC`，作为该批的回执
+    #[test]
+    fn await_cyc_paced() {
+        use crate::runtimes::{command_vm::runtime::tests::echo_vm, CycPacing};
+        let interval = Duration::from_millis(100);
+        let mut vm = manipulate!(
+            echo_vm()
+            => .input_translator(|cmd| Ok(Some(cmd.to_string())))
+            => .cyc_pacing(CycPacing { chunk: 50, interval })
+        )
+        .launch()
+        .expect("无法启动虚拟机");
+        let is_cyc_echo = |output: &Output| output.raw_content().starts_with("CYC");
+        let mut cache = vec![];
+        let start = Instant::now();
+        put_nal(
+            &mut vm,
+            parse_single("120").unwrap(),
+            &mut cache,
+            &mut PutNalContext {
+                interactivity: &InteractivityState::default(),
+                nal_root_path: Path::new("."),
+                set_ack: &SetAck::default(),
+                cyc_await: Some(CycAwait::new(Some(is_cyc_echo))),
+                requires: &mut RequiresRegion::default(),
+            },
+        )
+        .expect("应收到每批的回执");
+        assert!(start.elapsed() >= interval * 2, "{:?}", start.elapsed());
+        let acks = cache.iter().filter(|o| is_cyc_echo(o)).count();
+        assert_eq!(acks, 3, "{cache:?}");
+        let _ = vm.terminate();
+    }
+
+    /// 「简单演绎」的模拟CIN
+    /// * 🎯无需任何CIN可执行文件，即可测试NAL输入
+    fn fake_simple_deduction() -> FakeVmRuntime {
+        manipulate!(
+            FakeVm::new()
+            => .respond_after_cycle(
+                Cmd::NSE(nse_task!(<A --> C>?)),
+                [Output::ANSWER {
+                    content_raw: "<A --> C>. %1.00;0.81%".into(),
+                    narsese: Some(nse!(<A --> C>. %1.0;0.81%)),
+                }],
+            )
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败")
+    }
+
+    /// 将NAL文本逐条置入虚拟机
+    fn put_nal_str(
+        vm: &mut impl VmRuntimeBoost,
+        nal: &str,
+        cache: &mut impl VmOutputCache,
+    ) -> Result<()> {
+        let mut requires = RequiresRegion::default();
+        for input in parse(nal) {
+            put_nal(
+                vm,
+                input?,
+                cache,
+                &mut PutNalContext {
+                    interactivity: &InteractivityState::default(),
+                    nal_root_path: Path::new("."),
+                    set_ack: &SetAck::default(),
+                    cyc_await: None,
+                    requires: &mut requires,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 测试/简单演绎 | 基于模拟CIN
+    #[test]
+    fn simple_deduction_fake() {
+        let mut vm = fake_simple_deduction();
+        let mut cache = vec![];
+        put_nal_str(
+            &mut vm,
+            include_str!("../../tests/nal/test_simple_deduction.nal"),
+            &mut cache,
+        )
+        .expect("NAL测试失败");
+        // 末尾的`terminate`已将虚拟机终止
+        assert!(vm.is_terminated());
+        assert!(cache.iter().any(|o| matches!(o, Output::ANSWER { .. })));
+    }
+
+    /// 测试/功能要求：CIN缺少功能⇒跳过至区段结束，不报告失败
+    #[test]
+    fn requires_region_fake() {
+        let mut vm = fake_simple_deduction();
+        let mut cache = vec![];
+        let mut requires = RequiresRegion::new(Capabilities {
+            operations: false,
+            ..Capabilities::ALL
+        });
+        let nal = "\
+''requires: operations
+<(*, {SELF}) --> ^left>!
+''expect-contains: EXE <(*, {SELF}) --> ^left>
+''end-requires
+<A --> C>?
+'/CYC 1
+''expect-contains: ANSWER <A --> C>.";
+        for input in parse(nal) {
+            put_nal(
+                &mut vm,
+                input.unwrap(),
+                &mut cache,
+                &mut PutNalContext {
+                    interactivity: &InteractivityState::default(),
+                    nal_root_path: Path::new("."),
+                    set_ack: &SetAck::default(),
+                    cyc_await: None,
+                    requires: &mut requires,
+                },
+            )
+            .expect("缺少功能的区段应被跳过");
+        }
+        // 区段内的输入未被置入；跳过时输出提示
+        assert!(matches!(
+            &cache[0],
+            Output::INFO { message } if message.contains("operations")
+        ));
+        assert!(!requires.is_skipping());
+        // 所要求的功能均被支持⇒照常执行
+        assert!(requires.enter(&["answers".into()]).is_empty());
+    }
+
+    /// 测试/包含文件：被包含文件中的相对路径基于其自身所在目录；拒绝循环包含
+    #[test]
+    fn include_fake() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("babelnar_include_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("inc"))?;
+        std::fs::write(dir.join("inc/premises.nal"), "<A --> B>.\n<B --> C>.")?;
+        std::fs::write(
+            dir.join("inc/deduction.nal"),
+            "''include: premises.nal\n<A --> C>?\n'/CYC 1\n''expect-contains: ANSWER <A --> C>.",
+        )?;
+        std::fs::write(dir.join("inc/loop.nal"), "''include: loop.nal")?;
+        let put_include = |vm: &mut FakeVmRuntime, cache: &mut Vec<Output>, path: &str| {
+            put_nal(
+                vm,
+                NALInput::Include(path.into()),
+                cache,
+                &mut PutNalContext {
+                    interactivity: &InteractivityState::default(),
+                    nal_root_path: &dir,
+                    set_ack: &SetAck::default(),
+                    cyc_await: None,
+                    requires: &mut RequiresRegion::default(),
+                },
+            )
+        };
+        let mut vm = fake_simple_deduction();
+        let mut cache = vec![];
+        put_include(&mut vm, &mut cache, "inc/deduction.nal")?;
+        assert!(cache.iter().any(|o| matches!(o, Output::ANSWER { .. })));
+        let e = put_include(&mut vm, &mut cache, "inc/loop.nal").unwrap_err();
+        assert!(e.to_string().contains("循环包含"), "{e}");
+        let e = put_include(&mut vm, &mut cache, "inc/not-exist.nal").unwrap_err();
+        assert!(e.to_string().contains("无法定位文件"), "{e}");
+        let _ = std::fs::remove_dir_all(&dir);
+        Ok(())
+    }
+
+    /// 测试/输出预期：未有回答⇒预期失败
+    #[test]
+    fn expect_contains_not_exists_fake() {
+        let mut vm = fake_simple_deduction();
+        let mut cache = vec![];
+        // 未步进⇒不会回答
+        let result = put_nal_str(
+            &mut vm,
+            "<A --> B>.\n<B --> C>.\n<A --> C>?\n''expect-contains: ANSWER <A --> C>.",
+            &mut cache,
+        );
+        let e = result.expect_err("应该返回错误");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::ExpectedNotExists(..))
+            ),
+            "{e}"
+        );
+        // 步进之后，预期满足
+        put_nal_str(
+            &mut vm,
+            "'/CYC 1\n''expect-contains: ANSWER <A --> C>.",
+            &mut cache,
+        )
+        .expect("步进后应有回答");
+    }
+
+    /// 测试/预期窗口：重复提问时，第二次的预期不被第一次的回答满足
+    #[test]
+    fn expect_window_duplicate_question_fake() {
+        let mut vm = fake_simple_deduction();
+        let mut cache = OutputCache::default();
+        put_nal_str(
+            &mut vm,
+            "<A --> C>?\n'/CYC 1\n''expect-contains: ANSWER <A --> C>.",
+            &mut cache,
+        )
+        .expect("步进后应有回答");
+        // 同一组预期共用窗口
+        put_nal_str(&mut vm, "''expect-contains: ANSWER <A --> C>.", &mut cache)
+            .expect("同一组预期应共用窗口");
+        // 再次提问但未步进⇒没有新的回答，不应被旧回答满足
+        let result = put_nal_str(
+            &mut vm,
+            "<A --> C>?\n''expect-contains: ANSWER <A --> C>.",
+            &mut cache,
+        );
+        let e = result.expect_err("旧回答不应满足新的预期");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::ExpectedNotExists(..))
+            ),
+            "{e}"
+        );
+        // 重置预期窗口⇒恢复检查所有输出
+        put_nal_str(
+            &mut vm,
+            "''reset-expect-window\n''expect-contains: ANSWER <A --> C>.",
+            &mut cache,
+        )
+        .expect("重置窗口后应匹配旧回答");
+    }
+
+    /// 测试/循环否定预期：出现⇒立即失败；预期开始前的输出不计
+    #[test]
+    fn expect_cycle_not_fake() {
+        let mut vm = fake_simple_deduction();
+        let mut cache = vec![];
+        // 步进后出现回答⇒在第1步即失败
+        let result = put_nal_str(
+            &mut vm,
+            "<A --> C>?\n''expect-cycle-not(100, 1): ANSWER <A --> C>.",
+            &mut cache,
+        );
+        let e = result.expect_err("出现了回答，应该返回错误");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::UnexpectedExists { cycles: 1, .. })
+            ),
+            "{e}"
+        );
+        // 回答已在缓存中，此后不再出现⇒通过
+        put_nal_str(
+            &mut vm,
+            "''expect-cycle-not(5, 1): ANSWER <A --> C>.",
+            &mut cache,
+        )
+        .expect("预期开始前的输出不应计入");
+    }
+
+    /// 测试/OpenNARS风格的「输出不含」：不步进，只检查预期窗口内已有的输出
+    #[test]
+    fn output_must_not_contain_fake() {
+        let mut vm = fake_simple_deduction();
+        let mut cache = OutputCache::default();
+        // 尚无回答⇒通过
+        put_nal_str(
+            &mut vm,
+            "<A --> C>?\n''outputMustNotContain('<A --> C>. %1.00;0.81%')",
+            &mut cache,
+        )
+        .expect("尚未步进，不应有回答");
+        // 步进后出现回答⇒失败
+        let result = put_nal_str(
+            &mut vm,
+            "1\n''outputMustContain('<A --> C>. %1.00;0.81%')\n''outputMustNotContain('<A --> C>. %1.00;0.81%')",
+            &mut cache,
+        );
+        let e = result.expect_err("已有回答，应该返回错误");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::UnexpectedExists { cycles: 0, .. })
+            ),
+            "{e}"
+        );
+    }
+
+    /// 测试/输出预期失败⇒列出最接近的输出
+    /// * 🎯频率不符的ANSWER应排在首位：同类输出、词项相同
+    #[test]
+    fn expect_contains_nearest_misses_fake() {
+        let mut vm = manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::NSE(nse_task!(<A --> C>?)),
+                [
+                    Output::OUT {
+                        content_raw: "<A --> C>. %1.00;0.90%".into(),
+                        narsese: Some(nse!(<A --> C>. %1.0;0.9%)),
+                    },
+                    Output::ANSWER {
+                        content_raw: "<A --> C>. %0.80;0.90%".into(),
+                        narsese: Some(nse!(<A --> C>. %0.8;0.9%)),
+                    },
+                    Output::ANSWER {
+                        content_raw: "<A --> D>. %1.00;0.90%".into(),
+                        narsese: Some(nse!(<A --> D>. %1.0;0.9%)),
+                    },
+                ],
+            )
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败");
+        let mut cache = vec![];
+        let result = put_nal_str(
+            &mut vm,
+            "<A --> C>?\n''expect-contains: ANSWER <A --> C>. %1.0;0.9%",
+            &mut cache,
+        );
+        let e = result.expect_err("频率不符，应该返回错误");
+        let Some(OutputExpectationError::ExpectedNotExists(_, misses)) = e.downcast_ref() else {
+            panic!("错误类型不符：{e}");
+        };
+        assert_eq!(misses.scanned, cache.len());
+        assert_eq!(
+            misses.outputs.first(),
+            Some(&Output::ANSWER {
+                content_raw: "<A --> C>. %0.80;0.90%".into(),
+                narsese: Some(nse!(<A --> C>. %0.8;0.9%)),
+            })
+        );
+        assert!(
+            e.to_string().contains("1. ANSWER <A --> C>. %0.80;0.90%"),
+            "{e}"
+        );
+        // 条数可配置
+        let expectation = OutputExpectation {
+            output_type: Some("ANSWER".into()),
+            narsese: Some(nse!(<A --> C>. %1.0;0.9%)),
+            ..Default::default()
+        };
+        let misses = NearestMisses::collect(&expectation, &cache, 1).expect("收集失败");
+        assert_eq!(misses.outputs.len(), 1);
+        assert!(matches!(misses.outputs[0], Output::ANSWER { .. }));
+    }
+
+    /// 测试/精度：文件级的`''precision:`与单个预期的`(±…)`
+    /// * 🎯派生结论的信度只需大致相符
+    #[test]
+    fn expect_contains_precision_fake() {
+        let mut cache = vec![Output::ANSWER {
+            content_raw: "<A --> C>. %1.00;0.42%".into(),
+            narsese: Some(nse!(<A --> C>. %1.0;0.42%)),
+        }];
+        let mut vm = CrashingRuntime::new();
+        // 默认精确比对
+        let nal = "''expect-contains: ANSWER <A --> C>. %1.0;0.4%";
+        assert!(put_nal_str(&mut vm, nal, &mut cache).is_err());
+        // 单个预期的精度
+        let nal = "''expect-contains(±0.05): ANSWER <A --> C>. %1.0;0.4%";
+        put_nal_str(&mut vm, nal, &mut cache).expect("误差在精度内");
+        let nal = "''expect-contains(±0.01): ANSWER <A --> C>. %1.0;0.4%";
+        assert!(put_nal_str(&mut vm, nal, &mut cache).is_err());
+        // 文件级的精度：作用于此后的预期
+        let nal = "''precision: 0.05\n''expect-contains: ANSWER <A --> C>. %1.0;0.4%";
+        put_nal_str(&mut vm, nal, &mut cache).expect("误差在精度内");
+        // 单个预期的精度优先
+        let nal = "''precision: 0.05\n''expect-contains(±0): ANSWER <A --> C>. %1.0;0.4%";
+        assert!(put_nal_str(&mut vm, nal, &mut cache).is_err());
+        // 配置中的默认精度：不覆盖已指定的精度
+        let mut input = parse_single("''expect-contains: ANSWER <A --> C>. %1.0;0.4%").unwrap();
+        input.default_precision(Some(FloatPrecision(0.05)));
+        put_nal_default(&mut vm, input, &mut cache).expect("误差在精度内");
+        let mut input = parse_single("''expect-contains(±0): ANSWER <A --> C>. %1.0;0.4%").unwrap();
+        input.default_precision(Some(FloatPrecision(0.05)));
+        assert!(put_nal_default(&mut vm, input, &mut cache,).is_err());
+    }
+
+    /// 测试/精度：逐行置入时，`''precision:`作用于此后置入的预期
+    /// * 🎯用户输入、Websocket输入逐行解析，解析时无从填入
+    #[test]
+    fn precision_line_by_line() {
+        let mut cache = vec![Output::ANSWER {
+            content_raw: "<A --> C>. %1.00;0.42%".into(),
+            narsese: Some(nse!(<A --> C>. %1.0;0.42%)),
+        }];
+        let mut vm = CrashingRuntime::new();
+        let interactivity = InteractivityState::default();
+        let mut requires = RequiresRegion::default();
+        let mut context = PutNalContext {
+            interactivity: &interactivity,
+            nal_root_path: Path::new("."),
+            set_ack: &SetAck::default(),
+            cyc_await: None,
+            requires: &mut requires,
+        };
+        let mut put_line = |line: &str| {
+            let input = parse_single(line).expect("NAL解析失败");
+            put_nal(&mut vm, input, &mut cache, &mut context)
+        };
+        let expectation = "''expect-contains: ANSWER <A --> C>. %1.0;0.4%";
+        assert!(put_line(expectation).is_err());
+        put_line("''precision: 0.05").expect("无法设置精度");
+        put_line(expectation).expect("误差在精度内");
+        // 单个预期的精度优先
+        assert!(put_line("''expect-contains(±0): ANSWER <A --> C>. %1.0;0.4%").is_err());
+        assert_eq!(interactivity.precision().map(|p| p.0), Some(0.05));
+    }
+
+    /// 测试/输出预期的最大延迟 | 基于带时间的「输出缓存」
+    /// * 🎯延迟按输出「到达」而非「被拉取」的时刻计算
+    #[test]
+    fn expect_contains_latency_fake() {
+        let mut vm = manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::NSE(nse_task!(<A --> C>?)),
+                [Output::ANSWER {
+                    content_raw: "<A --> C>. %1.00;0.81%".into(),
+                    narsese: Some(nse!(<A --> C>. %1.0;0.81%)),
+                }],
+            )
+            => .respond_after_cycle(
+                Cmd::NSE(nse_task!(<A --> D>?)),
+                [Output::ANSWER {
+                    content_raw: "<A --> D>. %1.00;0.81%".into(),
+                    narsese: Some(nse!(<A --> D>. %1.0;0.81%)),
+                }],
+            )
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败");
+        let mut cache = OutputCache::default();
+        // 延迟内⇒通过
+        put_nal_str(
+            &mut vm,
+            "<A --> C>?\n''expect-contains(2000ms): ANSWER <A --> C>.",
+            &mut cache,
+        )
+        .expect("应在延迟内收到回答");
+        // 回答立即到达，只是拉取晚了⇒仍在延迟内
+        put_nal_str(
+            &mut vm,
+            "<A --> C>?\n''sleep: 100ms\n''expect-contains(50ms): ANSWER <A --> C>.",
+            &mut cache,
+        )
+        .expect("拉取的时刻不应计入延迟");
+        // 回答在步进后才到达⇒超出延迟，并附带实际延迟
+        let result = put_nal_str(
+            &mut vm,
+            "<A --> D>?\n''sleep: 100ms\n1\n''expect-contains(50ms): ANSWER <A --> D>.",
+            &mut cache,
+        );
+        let e = result.expect_err("应该超出延迟");
+        match e.downcast_ref::<OutputExpectationError>() {
+            Some(OutputExpectationError::LatencyExceeded { latency, .. }) => {
+                assert!(*latency >= Duration::from_millis(100), "{e}")
+            }
+            _ => panic!("错误类型不符：{e}"),
+        }
+        // 输出缓存不记录时间⇒无法测量
+        let e = put_nal_str(
+            &mut vm,
+            "<A --> C>?\n''expect-contains(2000ms): ANSWER <A --> C>.",
+            &mut vec![],
+        )
+        .expect_err("应该无法测量");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::LatencyUnmeasurable(..))
+            ),
+            "{e}"
+        );
+    }
+
+    /// 测试/满足预期的输出
+    /// * 🎯报告的是实际满足预期的那条，而非最近一条相符的输出
+    #[test]
+    fn matched_output_first_or_last() -> Result<()> {
+        let answer = |content_raw: &str| Output::ANSWER {
+            content_raw: content_raw.into(),
+            narsese: Some(nse!(<A --> C>.)),
+        };
+        let cache = vec![answer("first"), answer("second")];
+        let matched = |line: &str| -> Result<Option<String>> {
+            let output = matched_output(&parse_single(line)?, &cache)?;
+            Ok(output.map(|output| output.raw_content().to_string()))
+        };
+        // 检查类预期：窗口内第一条
+        let first = Some("first".to_string());
+        assert_eq!(matched("''expect-contains: ANSWER <A --> C>.")?, first);
+        assert_eq!(matched("''expect-cycle(10, 1): ANSWER <A --> C>.")?, first);
+        // 等待类预期：等到的最后一条
+        assert_eq!(
+            matched("''await: ANSWER <A --> C>.")?,
+            Some("second".into())
+        );
+        // 否定预期⇒无
+        assert_eq!(
+            matched("''expect-cycle-not(10, 1): ANSWER <A --> C>.")?,
+            None
+        );
+        Ok(())
+    }
+
+    /// 以指定格式保存输出缓存，返回文件内容
+    fn save_outputs(format: SaveOutputsFormat, cache: &mut OutputCache) -> Result<String> {
+        let dir = std::env::temp_dir();
+        let file_name = format!("babelnar_save_outputs_{format:?}_{}", std::process::id());
+        let input = NALInput::SaveOutputs {
+            format,
+            path: file_name.clone(),
+        };
+        put_nal(
+            &mut CrashingRuntime::new(),
+            input,
+            cache,
+            &mut PutNalContext {
+                interactivity: &InteractivityState::default(),
+                nal_root_path: &dir,
+                set_ack: &SetAck::default(),
+                cyc_await: None,
+                requires: &mut RequiresRegion::default(),
+            },
+        )?;
+        let path = dir.join(file_name);
+        let content = std::fs::read_to_string(&path)?;
+        std::fs::remove_file(path)?;
+        Ok(content)
+    }
+
+    /// 用于「保存输出」的输出缓存
+    /// * 📄含Narsese的输出、内容含逗号引号换行的输出
+    fn outputs_to_save() -> Result<OutputCache> {
+        let mut cache = OutputCache::default();
+        cache.put(Output::INFO {
+            message: "timed".into(),
+        })?;
+        cache.put(Output::ANSWER {
+            content_raw: "Answer: <A --> C>.".into(),
+            narsese: Some(nse!(<A --> C>.)),
+        })?;
+        cache.put(Output::ERROR {
+            description: "a, \"b\"\nc".into(),
+        })?;
+        Ok(cache)
+    }
+
+    /// 测试/保存输出：JSON，附带「存入时间」
+    #[test]
+    fn save_outputs_timed() -> Result<()> {
+        let mut cache = outputs_to_save()?;
+        let content = save_outputs(SaveOutputsFormat::Json, &mut cache)?;
+        let saved: serde_json::Value = serde_json::from_str(&content)?;
+        assert_eq!(saved[0]["type"], "INFO");
+        assert!(saved[0]["t"].is_u64(), "{saved}");
+        // 转义正确
+        assert_eq!(saved[2]["content"], "a, \"b\"\nc");
+        Ok(())
+    }
+
+    /// 测试/保存输出：NAL，仅含Narsese的输出
+    #[test]
+    fn save_outputs_nal() -> Result<()> {
+        let mut cache = outputs_to_save()?;
+        let content = save_outputs(SaveOutputsFormat::Nal, &mut cache)?;
+        assert_eq!(content, "'ANSWER\n<A --> C>.\n");
+        // 可被重新解析为输入
+        assert!(parse(&content).into_iter().all(|result| result.is_ok()));
+        Ok(())
+    }
+
+    /// 测试/保存输出：CSV，含逗号、引号、换行的字段被转义
+    #[test]
+    fn save_outputs_csv() -> Result<()> {
+        let mut cache = outputs_to_save()?;
+        let content = save_outputs(SaveOutputsFormat::Csv, &mut cache)?;
+        assert_eq!(
+            content,
+            "type,narsese,raw_content\n\
+             INFO,,timed\n\
+             ANSWER,<A --> C>.,Answer: <A --> C>.\n\
+             ERROR,,\"a, \"\"b\"\"\nc\"\n"
+        );
+        Ok(())
+    }
+
+    /// 测试/保存输出：空缓存⇒格式有效的空文件
+    #[test]
+    fn save_outputs_empty() -> Result<()> {
+        let mut cache = OutputCache::default();
+        let json = save_outputs(SaveOutputsFormat::Json, &mut cache)?;
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json)?,
+            serde_json::json!([])
+        );
+        assert_eq!(save_outputs(SaveOutputsFormat::Nal, &mut cache)?, "");
+        assert_eq!(
+            save_outputs(SaveOutputsFormat::Csv, &mut cache)?,
+            "type,narsese,raw_content\n"
+        );
+        Ok(())
+    }
+
+    /// 测试/问题与回答的关联：交错的两个问题，以及未被回答的问题
+    #[test]
+    fn answers_to_questions_fake() -> Result<()> {
+        let answer = |narsese: &str| Output::ANSWER {
+            content_raw: format!("Answer: {narsese}"),
+            narsese: Some(FORMAT_ASCII.parse(narsese).unwrap()),
+        };
+        // 先提问的后回答
+        let mut vm = manipulate!(
+            FakeVm::new()
+            => .respond_after_cycle(Cmd::NSE(nse_task!(<A --> C>?)), [])
+            => .respond_after_cycle(
+                Cmd::NSE(nse_task!(<?x --> D>?)),
+                [answer("<B --> D>. %1.0;0.9%"), answer("<A --> C>. %1.0;0.81%")],
+            )
+        )
+        .launch()?;
+        let mut cache = OutputCache::default();
+        put_nal_str(
+            &mut vm,
+            "<A --> C>?\n<?x --> D>?\n<E --> F>?\n'/CYC 1\n''expect-contains: ANSWER <A --> C>.",
+            &mut cache,
+        )?;
+        let content = save_outputs(SaveOutputsFormat::Json, &mut cache)?;
+        let saved: Vec<serde_json::Value> = serde_json::from_str(&content)?;
+        let answers_to = saved
+            .iter()
+            .filter(|value| value["type"] == "ANSWER")
+            .map(|value| {
+                (
+                    value["narsese"].as_str().unwrap(),
+                    value["answersTo"].clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            answers_to,
+            [
+                ("<B --> D>. %1.0;0.9%", 2.into()),
+                ("<A --> C>. %1.0;0.81%", 1.into()),
+            ]
+        );
+        // 非回答⇒无关联
+        assert!(saved
+            .iter()
+            .filter(|value| value["type"] != "ANSWER")
+            .all(|value| value.get("answersTo").is_none()));
+        // 未被回答的问题
+        let unanswered = cache.unanswered_questions();
+        assert_eq!(
+            unanswered
+                .iter()
+                .map(|question| (question.id, question.narsese.as_str()))
+                .collect::<Vec<_>>(),
+            [(3, "<E --> F>?")]
+        );
+        Ok(())
+    }
+
+    /// 「二选一操作」的模拟CIN
+    /// * 🎯每步进一次，输出两次`^left`、一次`^right`，以及一条无关的`INFO`
+    /// * 🎯步进两步时，输出三次`^right`：用于检验「统计窗口」
+    fn fake_operant() -> FakeVmRuntime {
+        let exe = |operator_name: &str| Output::EXE {
+            content_raw: format!("^{operator_name}"),
+            operation: Operation {
+                operator_name: operator_name.into(),
+                params: vec![nse_term!({ SELF })],
+            },
+        };
+        manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::CYC(1),
+                [
+                    exe("left"),
+                    exe("left"),
+                    exe("right"),
+                    Output::INFO {
+                        message: "cycle".into(),
+                    },
+                ],
+            )
+            => .respond(Cmd::CYC(2), [exe("right"), exe("right"), exe("right")])
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败")
+    }
+
+    /// 测试/比例预期 | 基于模拟CIN
+    #[test]
+    fn expect_rate_fake() {
+        let mut vm = fake_operant();
+        let mut cache = vec![];
+        // 预期开始前的输出不计入 | 否则`^left`比例将低于一半
+        put_nal_str(&mut vm, "'/CYC 2\n'/CYC 2", &mut cache).expect("步进失败");
+        // 2/3 ≥ 60%
+        put_nal_str(
+            &mut vm,
+            "''expect-rate(10, 1, 60%): EXE (^left, {SELF})",
+            &mut cache,
+        )
+        .expect("比例应达标");
+        // 2/3 < 70% | 错误信息附带实际比例
+        let e = put_nal_str(
+            &mut vm,
+            "''expect-rate(10, 1, 0.7): EXE (^left, {SELF})",
+            &mut cache,
+        )
+        .expect_err("比例不应达标");
+        match e.downcast_ref::<OutputExpectationError>() {
+            Some(OutputExpectationError::RateBelowThreshold { matched, total, .. }) => {
+                assert_eq!((*matched, *total), (20, 30));
+                assert!(e.to_string().contains("20/30（66.7%）"), "{e}");
+            }
+            _ => panic!("错误类型不符：{e}"),
+        }
+        // 输出类型的别名：与匹配时一样计入同类输出 | 2/3 ≥ 60%，不超过100%
+        let mut input = parse_single("''expect-rate(10, 1, 60%): OPERATE (^left, {SELF})").unwrap();
+        input.default_type_aliases(&[("OPERATE".into(), vec!["EXE".into()])].into());
+        put_nal_default(&mut vm, input, &mut cache).expect("别名的比例应达标");
+        let mut input = parse_single("''expect-rate(10, 1, 70%): OPERATE (^left, {SELF})").unwrap();
+        input.default_type_aliases(&[("OPERATE".into(), vec!["EXE".into()])].into());
+        let e = put_nal_default(&mut vm, input, &mut cache).expect_err("别名的比例不应达标");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::RateBelowThreshold {
+                    matched: 20,
+                    total: 30,
+                    ..
+                })
+            ),
+            "{e}"
+        );
+        // 没有同类输出⇒预期不符
+        let e = put_nal_str(&mut vm, "''expect-rate(3, 1, 0%): ANSWER", &mut cache)
+            .expect_err("没有回答");
+        assert!(
+            matches!(
+                e.downcast_ref::<OutputExpectationError>(),
+                Some(OutputExpectationError::ExpectedNotExists(..))
+            ),
+            "{e}"
+        );
+    }
+
+    /// 测试/设置：有回执⇒成功，无回执⇒超时报错
+    #[test]
+    fn set_acknowledgement_fake() {
+        let mut vm = manipulate!(
+            FakeVm::new()
+            => .respond(
+                Cmd::VOL(0),
+                [Output::INFO {
+                    message: "volume = 0".into(),
+                }],
+            )
+        )
+        .launch()
+        .expect("模拟虚拟机启动失败");
+        let mut cache = vec![];
+        let mut set = |vm: &mut FakeVmRuntime, nal: &str, set_ack: SetAck| {
+            put_nal(
+                vm,
+                parse_single(nal)?,
+                &mut cache,
+                &mut PutNalContext {
+                    interactivity: &InteractivityState::default(),
+                    nal_root_path: Path::new("."),
+                    set_ack: &set_ack,
+                    cyc_await: None,
+                    requires: &mut RequiresRegion::default(),
+                },
+            )
+        };
+        let error_of = |result: Result<()>| {
+            let e = result.expect_err("应该报错");
+            e.downcast::<OutputExpectationError>()
+                .expect("应为「预期错误」")
+        };
+        // 有回执：所有指令共用 | 按指令头指定
+        set(&mut vm, "''set: VOL 0", SetAck::all("volume")).expect("应收到回执");
+        let set_ack = SetAck::default().with("VOL", "volume");
+        set(&mut vm, "''set: VOL 0", set_ack).expect("应收到回执");
+        // 未指定该指令的回执⇒立即报告「无法确认」，不把任意输出当作回执
+        let start = Instant::now();
+        let set_ack = SetAck::default().with("SET", "volume");
+        let e = error_of(set(&mut vm, "''set: VOL 0", set_ack));
+        assert!(
+            matches!(e, OutputExpectationError::SetAckUnspecified(..)),
+            "{e}"
+        );
+        assert!(start.elapsed() < SET_ACK_TIMEOUT);
+        // 回执内容不符⇒超时
+        let start = Instant::now();
+        let e = error_of(set(&mut vm, "''set: VOL 0", SetAck::all("音量")));
+        assert!(
+            matches!(e, OutputExpectationError::SetNotAcknowledged(..)),
+            "{e}"
+        );
+        assert!(start.elapsed() >= SET_ACK_TIMEOUT);
+    }
+
+    /// 测试/原始内容预期
+    /// * 📄输出源自ONA转译器的测试用例
+    #[test]
+    fn test_matches_raw_regex() {
+        use crate::cin_implements::ona::output_translate;
+        let output = |line: &str| output_translate(line.into()).expect("ONA输出解析失败");
+        let decision = output("decision expectation=0.578198 implication: <(a &/ ^left) =/> g>. Truth: frequency=1.000000 confidence=0.241351 dt=1.000000 precondition: a. :|: Truth: frequency=1.000000 confidence=0.900000 occurrenceTime=4");
+        let derived = output("Derived: <(a * b) --> (+ warm)>. :|: occurrenceTime=4 Priority=0.247200 Truth: frequency=1.000000, confidence=0.648000");
+        let expectation = |nal| match parse_single(nal).unwrap() {
+            NALInput::ExpectContains(expectation, _) => expectation,
+            input => panic!("解析结果不是「输出预期」：{input:?}"),
+        };
+        // 仅原始内容
+        let e = expectation(r"''expect-contains: /decision expectation=0\.5/");
+        assert!(e.matches(&decision));
+        assert!(!e.matches(&derived));
+        // 与「输出类型」结合：均符合才算符合
+        let e = expectation(r"''expect-contains: OUT /frequency=1\.000000/");
+        assert!(e.matches(&derived));
+        assert!(!e.matches(&decision));
+        // 与Narsese结合
+        let derived = output("Derived: <a --> b>. :|: occurrenceTime=4 Priority=0.246973 Truth: frequency=0.800000, confidence=0.162760");
+        let e = expectation(r"''expect-contains: OUT <a --> b>. :|: /confidence=0\.16/");
+        assert!(e.matches(&derived));
+        let e = expectation(r"''expect-contains: OUT <a --> b>. :|: /confidence=0\.9/");
+        assert!(!e.matches(&derived));
+    }
+
+    /// 测试/输出类型的别名
+    /// * 🎯「标准名」的预期亦匹配其别名；别名之间不互通
+    #[test]
+    fn test_matches_type_aliases() {
+        let output = |r#type: &str| Output::UNCLASSIFIED {
+            r#type: r#type.into(),
+            content: format!("{type}: <A --> B>"),
+            narsese: Some(nse!(<A --> B>)),
+        };
+        let aliases = OutputTypeAliases::from([(
+            "ANTICIPATE".into(),
+            vec!["EXPECT".into(), "ANTICIPATION".into()],
+        )]);
+        let expectation = |nal| {
+            let mut input = parse_single(nal).unwrap();
+            input.default_type_aliases(&aliases);
+            match input {
+                NALInput::ExpectContains(expectation, _) => expectation,
+                input => panic!("解析结果不是「输出预期」：{input:?}"),
+            }
+        };
+        let e = expectation("''expect-contains: ANTICIPATE <A --> B>");
+        assert!(e.matches(&output("ANTICIPATE")));
+        assert!(e.matches(&output("EXPECT")));
+        assert!(e.matches(&output("ANTICIPATION")));
+        assert!(!e.matches(&output("OUT")));
+        // 别名⇒不匹配标准名
+        let e = expectation("''expect-contains: EXPECT <A --> B>");
+        assert!(e.matches(&output("EXPECT")));
+        assert!(!e.matches(&output("ANTICIPATE")));
+        // 诊断：别名不计入「类型不符」
+        let e = expectation("''expect-contains: ANTICIPATE <A --> C>");
+        assert!(!miss_distance(&e, &output("EXPECT")).type_mismatch);
+    }
+
+    /// 测试/含有子词项的预期
+    /// * 📄输出源自ONA转译器的测试用例
+    #[test]
+    fn test_matches_contains_term() {
+        use crate::cin_implements::ona::output_translate;
+        let output = |line: &str| output_translate(line.into()).expect("ONA输出解析失败");
+        let derived = output("Derived: <(a &/ <(* {SELF}) --> ^left>) =/> g>. Priority=0.185871 Truth: frequency=1.000000, confidence=0.282230");
+        let expectation = |nal| match parse_single(nal).unwrap() {
+            NALInput::ExpectContains(expectation, _) => expectation,
+            input => panic!("解析结果不是「输出预期」：{input:?}"),
+        };
+        // 嵌套在复合词项中的原子词项
+        assert!(expectation("''expect-contains-term: OUT ^left").matches(&derived));
+        assert!(expectation("''expect-contains-term: OUT {SELF}").matches(&derived));
+        assert!(
+            expectation("''expect-contains-term: OUT <(*, {SELF}) --> ^left>").matches(&derived)
+        );
+        // 否定情况：不含该子词项、类型不符、整体相等模式
+        assert!(!expectation("''expect-contains-term: OUT ^right").matches(&derived));
+        assert!(!expectation("''expect-contains-term: ANSWER ^left").matches(&derived));
+        assert!(!expectation("''expect-contains: OUT ^left").matches(&derived));
+    }
+
+    /// 测试/等待回显：忽略CIN附加的预算值与时间戳数值
+    /// * 📄输出源自PyNARS、ONA转译器的测试用例
+    #[test]
+    fn test_matches_await_echo() {
+        use crate::cin_implements::{ona, pynars};
+        let expectation = |nal| match parse_single(nal).unwrap() {
+            NALInput::Await(expectation) => expectation,
+            input => panic!("解析结果不是「等待」：{input:?}"),
+        };
+        let echo = pynars::output_translate("|0.80|0.50|0.95| IN    : A. %1.000;0.900%".into())
+            .expect("PyNARS输出解析失败");
+        assert!(expectation("''await-echo: A. %1.0;0.9%").matches(&echo));
+        assert!(expectation("''await-echo: $0.5;0.5;0.5$ A. %1.0;0.9%").matches(&echo));
+        assert!(!expectation("''await-echo: A. %0.0;0.9%").matches(&echo));
+        assert!(!expectation("''await-echo: A?").matches(&echo));
+        // 「普通」等待：比对预算值
+        assert!(!expectation("''await: IN A. %1.0;0.9%").matches(&echo));
+        // 发生时间⇒视作「现在」
+        let echo = ona::output_translate("Input: <a --> b>. :|: occurrenceTime=1 Priority=1.000000 Truth: frequency=1.000000, confidence=0.900000".into())
+            .expect("ONA输出解析失败");
+        assert!(expectation("''await-echo: <a --> b>. :|: %1.0;0.9%").matches(&echo));
+        assert!(!expectation("''await-echo: <a --> b>. %1.0;0.9%").matches(&echo));
+        // 非「输入」类输出不算回显
+        let derived = ona::output_translate("Derived: <a --> b>. :|: occurrenceTime=1 Priority=0.246973 Truth: frequency=1.000000, confidence=0.900000".into())
+            .expect("ONA输出解析失败");
+        assert!(!expectation("''await-echo: <a --> b>. :|: %1.0;0.9%").matches(&derived));
+    }
+
+    impl OutputExpectation {
+        /// 预期任意「回答」
+        fn default_answer() -> Self {
+            Self {
+                output_type: Some("ANSWER".into()),
+                ..Default::default()
+            }
         }
     }
 }
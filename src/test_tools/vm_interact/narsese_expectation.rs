@@ -2,30 +2,136 @@
 //! * 🚩【2024-04-02 22:49:12】从[`crate::runtimes::command_vm::runtime::tests`]中迁移而来
 
 use super::term_equal::*;
+use crate::{
+    support::print_notice,
+    test_tools::{
+        FloatPrecision, OperationExpectation, OutputExpectation, ParamPattern, TermMatch,
+    },
+};
 use anyhow::Result;
 use nar_dev_utils::if_return;
 use narsese::{
     api::NarseseValue,
     conversion::{
         inter_type::lexical_fold::TryFoldInto,
-        string::impl_enum::{format_instances::FORMAT_ASCII as FORMAT_ASCII_ENUM, NarseseFormat},
+        string::{
+            impl_enum::{format_instances::FORMAT_ASCII as FORMAT_ASCII_ENUM, NarseseFormat},
+            impl_lexical::format_instances::FORMAT_ASCII,
+        },
     },
     enum_narsese::{
         Budget as EnumBudget, Punctuation as EnumPunctuation, Stamp as EnumStamp,
         Truth as EnumTruth,
     },
-    lexical::{Narsese, Sentence as LexicalSentence, Task as LexicalTask, Term},
+    lexical::{
+        Narsese, Sentence as LexicalSentence, Task as LexicalTask, Term, Truth as LexicalTruth,
+    },
 };
-use navm::output::Operation;
+use navm::output::{Operation, Output};
+use std::cmp::Ordering;
 use util::macro_once;
 
 /// 判断「输出是否（在Narsese语义层面）符合预期」
 /// * 🎯词法Narsese⇒枚举Narsese，以便从语义上判断
+/// * 📌时间戳直接判等：不使用[`is_expected_stamp`]的匹配规则
 pub fn is_expected_narsese_lexical(expected: &Narsese, out: &Narsese) -> bool {
-    _is_expected_narsese(expected.clone(), out.clone())
+    _is_expected_narsese(
+        expected.clone(),
+        out.clone(),
+        TermMatch::Exact,
+        FloatPrecision::default(),
+        None,
+    )
+}
+
+/// 回显匹配选项
+/// * 🎯各CIN回显输入时，会附加或改写部分内容
+///   * 📄PyNARS：附加默认预算值`$0.80;0.50;0.95$`
+///   * 📄ONA：事件附加发生时间`occurrenceTime=1`
+/// * 🚩默认：忽略预算值与时间戳数值，比对真值
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EchoMatchOptions {
+    /// 忽略预算值
+    /// * 🚩任务视作其中的语句
+    pub ignore_budget: bool,
+    /// 忽略时间戳数值
+    /// * 🚩固定时间`:!1:`视作「现在」`:|:`：只比对「永恒/过去/现在/将来」
+    pub ignore_stamp_values: bool,
+    /// 忽略真值
+    pub ignore_truth: bool,
+}
+
+impl Default for EchoMatchOptions {
+    fn default() -> Self {
+        Self {
+            ignore_budget: true,
+            ignore_stamp_values: true,
+            ignore_truth: false,
+        }
+    }
+}
+
+impl EchoMatchOptions {
+    /// 去掉比对时忽略的部分
+    fn normalize(self, narsese: Narsese) -> Narsese {
+        let mut narsese = match narsese {
+            NarseseValue::Task(task) if self.ignore_budget => NarseseValue::Sentence(task.sentence),
+            narsese => narsese,
+        };
+        if let NarseseValue::Sentence(sentence) | NarseseValue::Task(LexicalTask { sentence, .. }) =
+            &mut narsese
+        {
+            if self.ignore_stamp_values && sentence.stamp.starts_with(":!") {
+                sentence.stamp = ":|:".into();
+            }
+            if self.ignore_truth {
+                sentence.truth = LexicalTruth::new();
+            }
+        }
+        narsese
+    }
+}
+
+/// 判断输出是否为预期的「输入回显」
+/// * 🎯CIN回显输入时，预算值、时间戳、空格等可能与输入不同
+/// * 🚩按[选项](EchoMatchOptions)去掉双方被忽略的部分，再在语义层面比对
+pub fn is_expected_echo(expected: &Narsese, out: &Narsese, options: EchoMatchOptions) -> bool {
+    is_expected_narsese_lexical(
+        &options.normalize(expected.clone()),
+        &options.normalize(out.clone()),
+    )
+}
+
+/// 判断「输出是否（在Narsese语义层面）符合预期」，并指定词项的匹配方式、精度与时间容差
+/// * 🎯[`TermMatch::Contains`]：输出的词项含有预期的词项即可
+/// * 🚩词项以外的部分（标点、真值等）照常比对
+///   * 📌「包含」匹配时，只预期词项⇒仅比对词项
+///   * 📌真值、预算值在「精度」内相等即可
+///   * 📌时间戳见[`is_expected_stamp`]
+pub fn is_expected_narsese_lexical_as(
+    expected: &Narsese,
+    out: &Narsese,
+    term_match: TermMatch,
+    precision: FloatPrecision,
+    tick_tolerance: usize,
+) -> bool {
+    _is_expected_narsese(
+        expected.clone(),
+        out.clone(),
+        term_match,
+        precision,
+        Some(tick_tolerance),
+    )
 }
 
-fn _is_expected_narsese(mut expected: Narsese, mut out: Narsese) -> bool {
+/// * 📌`tick_tolerance`为空⇒时间戳直接判等
+fn _is_expected_narsese(
+    mut expected: Narsese,
+    mut out: Narsese,
+    term_match: TermMatch,
+    precision: FloatPrecision,
+    tick_tolerance: Option<usize>,
+) -> bool {
     // 先比对词项
     fn get_term_mut(narsese: &mut Narsese) -> &mut Term {
         use NarseseValue::*;
@@ -39,14 +145,26 @@ fn _is_expected_narsese(mut expected: Narsese, mut out: Narsese) -> bool {
         }
     }
     // * 🚩特制的「词项判等」截断性逻辑 | 🚩语义层面判等词项
+    let (expected_term, out_term) = (get_term_mut(&mut expected), get_term_mut(&mut out));
+    // * 🚩规整化未收敛⇒视作不符，并发出警告：不因单个词项中断整个测试
+    let term_matches = match term_match {
+        TermMatch::Exact => semantical_equal_mut(expected_term, out_term),
+        TermMatch::Contains => semantical_contains_mut(out_term, expected_term),
+    }
+    .unwrap_or_else(|e| {
+        print_notice("WARN", &format!("词项比对失败，视作不符：{e}"));
+        false
+    });
     if_return! {
-        !semantical_equal_mut(get_term_mut(&mut expected), get_term_mut(&mut out)) => false
+        !term_matches => false
+        // * 🚩「包含」匹配且只预期词项⇒不再比对标点、真值等
+        term_match == TermMatch::Contains && matches!(expected, NarseseValue::Term(..)) => true
     };
     // * 🚩折叠剩余部分，并开始判断
     let fold = PartialFoldResult::try_from;
     match (fold(expected), fold(out)) {
         // * 🚩若均解析成功⇒进一步判等
-        (Ok(expected), Ok(out)) => expected.is_expected_out(&out),
+        (Ok(expected), Ok(out)) => expected.is_expected_out(&out, precision, tick_tolerance),
         // * 🚩任一解析失败⇒直接失败
         _ => false,
     }
@@ -68,7 +186,12 @@ struct PartialFoldResult {
 /// * 🚩【2024-06-11 16:02:10】目前对「词项比对」使用特殊逻辑，而对其它结构照常比较
 /// * ✅均已经考虑「没有值可判断」的情况
 impl PartialFoldResult {
-    fn is_expected_out(&self, out: &Self) -> bool {
+    fn is_expected_out(
+        &self,
+        out: &Self,
+        precision: FloatPrecision,
+        tick_tolerance: Option<usize>,
+    ) -> bool {
         macro_once! {
             /// 一系列针对Option解包的条件判断：
             /// * 🚩均为Some⇒展开内部代码逻辑
@@ -100,17 +223,20 @@ impl PartialFoldResult {
                 // 时间戳一致
                 expected @ self.stamp,
                 out @ out.stamp =>
-                expected == out // * 🚩简单枚举类型：直接判等
+                match tick_tolerance {
+                    Some(tick_tolerance) => is_expected_stamp(expected, out, tick_tolerance),
+                    None => expected == out, // * 🚩简单枚举类型：直接判等
+                }
             } && {
                 // 真值一致
                 expected @ self.truth,
                 out @ out.truth =>
-                is_expected_truth(expected, out) // * 🚩特殊情况（需兼容）特殊处理
+                is_expected_truth(expected, out, precision) // * 🚩特殊情况（需兼容）特殊处理
             } && {
                 // 预算值一致
                 expected @ self.budget,
                 out @ out.budget =>
-                is_expected_budget(expected, out) // * 🚩特殊情况（需兼容）特殊处理
+                is_expected_budget(expected, out, precision) // * 🚩特殊情况（需兼容）特殊处理
             }
         }
     }
@@ -173,24 +299,53 @@ impl TryFrom<Narsese> for PartialFoldResult {
 
 /// 判断「输出是否在真值层面符合预期」
 /// * 🎯空真值的语句，应该符合「固定真值的语句」的预期——相当于「通配符」
+/// * 🚩各分量在「精度」内相等即可
 #[inline]
-fn is_expected_truth(expected: &EnumTruth, out: &EnumTruth) -> bool {
+fn is_expected_truth(expected: &EnumTruth, out: &EnumTruth, precision: FloatPrecision) -> bool {
+    let eq = |e: &f64, o: &f64| precision.approx_eq(*e, *o);
     match (expected, out) {
         // 预期空真值⇒通配
         (EnumTruth::Empty, ..) => true,
         // 预期单真值
-        (EnumTruth::Single(f_e), EnumTruth::Single(f_o) | EnumTruth::Double(f_o, ..)) => f_e == f_o,
+        (EnumTruth::Single(f_e), EnumTruth::Single(f_o) | EnumTruth::Double(f_o, ..)) => {
+            eq(f_e, f_o)
+        }
         // 预期双真值
-        (EnumTruth::Double(..), EnumTruth::Double(..)) => expected == out,
+        (EnumTruth::Double(f_e, c_e), EnumTruth::Double(f_o, c_o)) => eq(f_e, f_o) && eq(c_e, c_o),
         // 其它情况
         _ => false,
     }
 }
 
+/// 判断「输出是否在时间戳层面符合预期」
+/// * 🎯时序推理中，各CIN输出事件时附带的时间各不相同
+///   * 📄ONA：`:|:`后另附`occurrenceTime=35124`（转译时删去）
+///   * 📄OpenNARS：直接输出固定时间`:!35124:`
+/// * 🚩匹配矩阵（预期 ⇒ 相符的输出）：
+///   * 永恒 ⇒ 仅永恒
+///   * 过去`:\:` ⇒ 仅过去
+///   * 现在`:|:` ⇒ 现在、任意固定时间`:!M:`
+///   * 将来`:/:` ⇒ 仅将来
+///   * 固定时间`:!N:` ⇒ 固定时间`:!M:`，且`|N - M| <= tick_tolerance`
+/// * 📌预期「现在」⇒任何具体的发生时间均可：测试不必关心CIN内部的时钟
+/// * 📌预期固定时间、输出「现在」⇒无从比对，视作不符
+pub fn is_expected_stamp(expected: &EnumStamp, out: &EnumStamp, tick_tolerance: usize) -> bool {
+    match (expected, out) {
+        // 预期「现在」⇒任何具体时间
+        (EnumStamp::Present, EnumStamp::Fixed(..)) => true,
+        // 预期固定时间⇒在容差内
+        (EnumStamp::Fixed(t_e), EnumStamp::Fixed(t_o)) => t_e.abs_diff(*t_o) <= tick_tolerance,
+        // 其它情况⇒种类相同即可
+        _ => expected == out,
+    }
+}
+
 /// 判断「输出是否在预算值层面符合预期」
 /// * 🎯空预算的语句，应该符合「固定预算值的语句」的预期——相当于「通配符」
+/// * 🚩各分量在「精度」内相等即可
 #[inline]
-fn is_expected_budget(expected: &EnumBudget, out: &EnumBudget) -> bool {
+fn is_expected_budget(expected: &EnumBudget, out: &EnumBudget, precision: FloatPrecision) -> bool {
+    let eq = |e: &f64, o: &f64| precision.approx_eq(*e, *o);
     match (expected, out) {
         // 预期空预算⇒通配
         (EnumBudget::Empty, ..) => true,
@@ -198,14 +353,16 @@ fn is_expected_budget(expected: &EnumBudget, out: &EnumBudget) -> bool {
         (
             EnumBudget::Single(p_e),
             EnumBudget::Single(p_o) | EnumBudget::Double(p_o, ..) | EnumBudget::Triple(p_o, ..),
-        ) => p_e == p_o,
+        ) => eq(p_e, p_o),
         // 预期双预算
         (
             EnumBudget::Double(p_e, d_e),
             EnumBudget::Double(p_o, d_o) | EnumBudget::Triple(p_o, d_o, ..),
-        ) => p_e == p_o && d_e == d_o,
+        ) => eq(p_e, p_o) && eq(d_e, d_o),
         // 预期三预算
-        (EnumBudget::Triple(..), EnumBudget::Triple(..)) => expected == out,
+        (EnumBudget::Triple(p_e, d_e, q_e), EnumBudget::Triple(p_o, d_o, q_o)) => {
+            eq(p_e, p_o) && eq(d_e, d_o) && eq(q_e, q_o)
+        }
         // 其它情况
         _ => false,
     }
@@ -213,19 +370,320 @@ fn is_expected_budget(expected: &EnumBudget, out: &EnumBudget) -> bool {
 
 /// 判断「输出是否在操作层面符合预期」
 /// * 🎯仅有「操作符」的「NARS操作」应该能通配所有「NARS操作」
-pub fn is_expected_operation(expected: &Operation, out: &Operation) -> bool {
+/// * 🚩逐个比对参数
+///   * 词项⇒语义上相等
+///   * [`ParamPattern::Any`]⇒任意一个参数
+///   * [`ParamPattern::Rest`]⇒其余的任意个参数
+/// * 📌无通配符⇒参数个数须相同
+pub fn is_expected_operation(expected: &OperationExpectation, out: &Operation) -> bool {
     // 操作符名不同⇒直接pass
     if_return! { expected.operator_name != out.operator_name => false }
 
-    // 比对操作参数：先判空
-    match (expected.no_params(), out.no_params()) {
-        // 预期无⇒通配
-        (true, ..) => true,
-        // 预期有，输出无⇒直接pass
-        (false, true) => false,
-        // 预期有，输出有⇒判断参数是否相同
-        (false, false) => expected.params == out.params,
+    // 预期无参数⇒通配
+    if_return! { expected.params.is_empty() => true }
+
+    // 逐个比对参数
+    let mut out_params = out.params.iter();
+    for pattern in &expected.params {
+        let out_param = match pattern {
+            // 其余参数⇒不再比对
+            ParamPattern::Rest => return true,
+            // 其它⇒须有对应的参数
+            _ => match out_params.next() {
+                Some(out_param) => out_param,
+                None => return false,
+            },
+        };
+        if let ParamPattern::Term(expected_param) = pattern {
+            if_return! { !is_expected_param(expected_param, out_param) => false }
+        }
+    }
+    // 输出不能有多余的参数
+    out_params.next().is_none()
+}
+
+/// 判断「操作参数是否符合预期」
+/// * 🚩语义层面判等词项：同[`is_expected_narsese_lexical`]
+/// * 🚩规整化未收敛⇒视作不符，并发出警告
+fn is_expected_param(expected: &Term, out: &Term) -> bool {
+    semantical_equal_mut(&mut expected.clone(), &mut out.clone()).unwrap_or_else(|e| {
+        print_notice("WARN", &format!("操作参数比对失败，视作不符：{e}"));
+        false
+    })
+}
+
+/// 未命中的输出与预期之间的距离
+/// * 🎯预期失败时，找出「最接近」的输出以便排查
+/// * 📌越小越接近；按字段顺序比较：输出类型 > 词项的编辑距离 > 真值的差距
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MissDistance {
+    /// 输出类型是否不同
+    pub type_mismatch: bool,
+    /// 词项（ASCII字符串）之间的编辑距离
+    /// * 🚩输出无Narsese⇒与原始内容比较
+    pub term_distance: usize,
+    /// 真值的差距：频率、信度之差的绝对值之和
+    /// * 🚩无法比较（如输出缺少真值）⇒无穷大
+    pub truth_distance: f64,
+}
+
+impl MissDistance {
+    /// 按「越小越接近」比较
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        self.type_mismatch
+            .cmp(&other.type_mismatch)
+            .then(self.term_distance.cmp(&other.term_distance))
+            .then(self.truth_distance.total_cmp(&other.truth_distance))
     }
 }
 
-// TODO: 单元测试
+/// 计算输出与预期之间的距离
+/// * 🚩预期中未指定的部分⇒距离为零
+pub fn miss_distance(expectation: &OutputExpectation, output: &Output) -> MissDistance {
+    let type_mismatch = !expectation.matches_type(output.type_name());
+    let Some(expected) = &expectation.narsese else {
+        return MissDistance {
+            type_mismatch,
+            term_distance: 0,
+            truth_distance: 0.0,
+        };
+    };
+    let expected_term = format_term(get_term(expected));
+    let (term_distance, truth_distance) = match output.get_narsese() {
+        Some(out) => (
+            edit_distance(&expected_term, &format_term(get_term(out))),
+            truth_distance(get_truth(expected), get_truth(out)),
+        ),
+        None => (
+            edit_distance(&expected_term, output.raw_content()),
+            f64::INFINITY,
+        ),
+    };
+    MissDistance {
+        type_mismatch,
+        term_distance,
+        truth_distance,
+    }
+}
+
+/// 获取Narsese中的词项
+pub(super) fn get_term(narsese: &Narsese) -> &Term {
+    use NarseseValue::*;
+    match narsese {
+        Term(term)
+        | Sentence(LexicalSentence { term, .. })
+        | Task(LexicalTask {
+            sentence: LexicalSentence { term, .. },
+            ..
+        }) => term,
+    }
+}
+
+/// 获取Narsese中的真值
+/// * 🚩词项⇒无真值
+fn get_truth(narsese: &Narsese) -> Option<&LexicalTruth> {
+    use NarseseValue::*;
+    match narsese {
+        Term(..) => None,
+        Sentence(LexicalSentence { truth, .. })
+        | Task(LexicalTask {
+            sentence: LexicalSentence { truth, .. },
+            ..
+        }) => Some(truth),
+    }
+}
+
+/// 将词项格式化为ASCII字符串
+fn format_term(term: &Term) -> String {
+    FORMAT_ASCII.format_narsese(&Narsese::Term(term.clone()))
+}
+
+/// 两个字符串之间的（Levenshtein）编辑距离
+/// * 🚩按字符计算
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, char_a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, char_b) in b.iter().enumerate() {
+            let substitution = diagonal + (char_a != *char_b) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// 真值之间的差距
+/// * 🚩预期无真值或空真值⇒通配，差距为零
+/// * 🚩无法折叠或无法比较⇒无穷大
+fn truth_distance(expected: Option<&LexicalTruth>, out: Option<&LexicalTruth>) -> f64 {
+    const FORMAT: &NarseseFormat<&str> = &FORMAT_ASCII_ENUM;
+    let Some(expected) = expected else {
+        return 0.0;
+    };
+    let (Ok(expected), Some(Ok(out))) = (
+        expected.clone().try_fold_into(FORMAT),
+        out.map(|out| out.clone().try_fold_into(FORMAT)),
+    ) else {
+        return f64::INFINITY;
+    };
+    match (expected, out) {
+        (EnumTruth::Empty, ..) => 0.0,
+        (EnumTruth::Single(f_e), EnumTruth::Single(f_o) | EnumTruth::Double(f_o, ..)) => {
+            (f_e - f_o).abs()
+        }
+        (EnumTruth::Double(f_e, c_e), EnumTruth::Double(f_o, c_o)) => {
+            (f_e - f_o).abs() + (c_e - c_o).abs()
+        }
+        _ => f64::INFINITY,
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/回显匹配选项
+    #[test]
+    fn test_is_expected_echo() {
+        let parse = |narsese: &str| FORMAT_ASCII.parse(narsese).unwrap();
+        let default = EchoMatchOptions::default();
+        let strict = EchoMatchOptions {
+            ignore_budget: false,
+            ignore_stamp_values: false,
+            ignore_truth: false,
+        };
+        let echo =
+            |expected, out, options| is_expected_echo(&parse(expected), &parse(out), options);
+        // 预算值
+        assert!(echo("A.", "$0.8;0.5;0.95$ A.", default));
+        assert!(!echo("A.", "$0.8;0.5;0.95$ A.", strict));
+        // 时间戳数值
+        assert!(echo("A. :|:", "A. :!3:", default));
+        assert!(!echo("A. :|:", "A. :!3:", strict));
+        assert!(!echo("A.", "A. :!3:", default));
+        assert!(!echo("A. :/:", "A. :|:", default));
+        // 真值
+        assert!(!echo("A. %1.0;0.9%", "A. %0.0;0.9%", default));
+        let ignore_truth = EchoMatchOptions {
+            ignore_truth: true,
+            ..default
+        };
+        assert!(echo("A. %1.0;0.9%", "A. %0.0;0.9%", ignore_truth));
+        // 词项、标点始终比对
+        assert!(!echo("A.", "B.", ignore_truth));
+        assert!(!echo("A.", "A!", ignore_truth));
+    }
+
+    /// 测试/时间戳匹配
+    /// * 🎯ONA输出`:|:`（发生时间在转译时删去）、OpenNARS输出固定时间`:!N:`
+    #[test]
+    fn test_is_expected_stamp() {
+        let parse = |narsese: &str| FORMAT_ASCII.parse(narsese).unwrap();
+        let matches = |expected, out, tick_tolerance| {
+            is_expected_narsese_lexical_as(
+                &parse(expected),
+                &parse(out),
+                TermMatch::Exact,
+                FloatPrecision::default(),
+                tick_tolerance,
+            )
+        };
+        let ona = "<A --> B>. :|: %1.0;0.9%";
+        let opennars = "<A --> B>. :!35124: %1.00;0.90%";
+        let eternal = "<A --> B>. %1.0;0.9%";
+        // 现在⇒任意具体时间
+        assert!(matches("<A --> B>. :|:", ona, 0));
+        assert!(matches("<A --> B>. :|:", opennars, 0));
+        assert!(!matches("<A --> B>. :|:", eternal, 0));
+        assert!(!matches("<A --> B>. :|:", "<A --> B>. :/:", 0));
+        // 固定时间⇒容差内
+        assert!(matches("<A --> B>. :!35124:", opennars, 0));
+        assert!(!matches("<A --> B>. :!35120:", opennars, 3));
+        assert!(matches("<A --> B>. :!35120:", opennars, 4));
+        assert!(matches("<A --> B>. :!35128:", opennars, 4));
+        assert!(!matches("<A --> B>. :!35124:", ona, 100));
+        // 永恒⇒仅永恒
+        assert!(matches("<A --> B>.", eternal, 0));
+        assert!(!matches("<A --> B>.", ona, 0));
+        assert!(!matches("<A --> B>.", opennars, 100));
+        // 过去、将来⇒种类相同
+        assert!(matches("<A --> B>. :\\:", "<A --> B>. :\\:", 0));
+        assert!(!matches("<A --> B>. :\\:", opennars, 100));
+        assert!(!matches("<A --> B>. :/:", opennars, 100));
+        // 直接判等⇒不受影响
+        assert!(!is_expected_narsese_lexical(
+            &parse("<A --> B>. :|:"),
+            &parse(opennars)
+        ));
+    }
+
+    /// 测试/操作参数的通配符
+    #[test]
+    fn test_is_expected_operation() {
+        use ParamPattern::*;
+        let term = |term: &str| match FORMAT_ASCII.parse(term).unwrap() {
+            Narsese::Term(term) => term,
+            narsese => panic!("不是词项：{narsese:?}"),
+        };
+        let expected = |params: Vec<ParamPattern>| OperationExpectation {
+            operator_name: "go".into(),
+            params,
+        };
+        let out = |params: &[&str]| Operation::new("go", params.iter().map(|param| term(param)));
+        let self_ = || Term(term("{SELF}"));
+        // 无通配符⇒个数、词项均须相符
+        let exact = expected(vec![self_(), Term(term("left"))]);
+        assert!(is_expected_operation(&exact, &out(&["{SELF}", "left"])));
+        assert!(!is_expected_operation(&exact, &out(&["{SELF}", "right"])));
+        assert!(!is_expected_operation(
+            &exact,
+            &out(&["{SELF}", "left", "fast"])
+        ));
+        assert!(!is_expected_operation(&exact, &out(&["{SELF}"])));
+        // 词项语义上相等即可
+        let set = expected(vec![Term(term("{A, B}"))]);
+        assert!(is_expected_operation(&set, &out(&["{B, A}"])));
+        // 无参数⇒通配
+        assert!(is_expected_operation(&expected(vec![]), &out(&["x", "y"])));
+        // 中间的`*`⇒任意一个参数
+        let any = expected(vec![self_(), Any, Term(term("fast"))]);
+        assert!(is_expected_operation(
+            &any,
+            &out(&["{SELF}", "left", "fast"])
+        ));
+        assert!(is_expected_operation(
+            &any,
+            &out(&["{SELF}", "(*, a, b)", "fast"])
+        ));
+        assert!(!is_expected_operation(&any, &out(&["{SELF}", "fast"])));
+        assert!(!is_expected_operation(
+            &any,
+            &out(&["left", "{SELF}", "fast"])
+        ));
+        // 末尾的`..`⇒其余任意个参数
+        let rest = expected(vec![self_(), Rest]);
+        assert!(is_expected_operation(&rest, &out(&["{SELF}"])));
+        assert!(is_expected_operation(
+            &rest,
+            &out(&["{SELF}", "left", "fast"])
+        ));
+        assert!(!is_expected_operation(&rest, &out(&["left", "{SELF}"])));
+        assert!(!is_expected_operation(&rest, &out(&[])));
+        // 操作符名不同⇒不符
+        let other = Operation::new("left", [term("{SELF}")].into_iter());
+        assert!(!is_expected_operation(&rest, &other));
+    }
+
+    /// 测试/编辑距离
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("<A --> B>", "<A --> C>"), 1);
+        assert_eq!(edit_distance("<A --> B>", ""), 9);
+    }
+}
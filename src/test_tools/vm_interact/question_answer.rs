@@ -0,0 +1,148 @@
+//! 问题与回答的关联
+//! * 🎯追踪「置入的问题」与「CIN的回答」：每条回答回答了哪个问题、哪些问题始终未被回答
+//! * 🚩置入问题⇒登记其词项；收到`ANSWER`⇒关联到最早的、词项相符的问题
+//!   * 📌问题中的查询变量视作通配符：`<?1 --> C>?`可被`<A --> C>.`回答
+//!   * 📌优先关联「尚未被回答」的问题：两次相同的提问，其回答分别关联到各自的问题
+//! * 📌问题编号从`1`开始，按置入顺序递增
+
+use super::{narsese_expectation::get_term, term_equal::semantical_answers_mut};
+use crate::support::print_notice;
+use narsese::{
+    conversion::string::{
+        impl_enum::format_instances::FORMAT_ASCII as FORMAT_ASCII_ENUM,
+        impl_lexical::format_instances::FORMAT_ASCII,
+    },
+    lexical::{Task, Term},
+};
+use navm::output::Output;
+
+/// 被追踪的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedQuestion {
+    /// 问题编号
+    /// * 📌从`1`开始
+    pub id: usize,
+    /// 问题的CommonNarsese（ASCII）
+    /// * 🎯在「未被回答的问题」中展示
+    pub narsese: String,
+    /// 问题的词项
+    term: Term,
+    /// 已关联的回答数
+    pub num_answers: usize,
+}
+
+/// 问题追踪器
+/// * 🎯作为「输出缓存」的附表：不改动输出本身
+#[derive(Debug, Clone, Default)]
+pub struct QuestionTracker {
+    /// 所有登记过的问题
+    /// * 📌按置入顺序排列
+    questions: Vec<TrackedQuestion>,
+}
+
+impl QuestionTracker {
+    /// 登记问题
+    /// * 🚩仅登记问题（标点为`?`）：其它任务⇒忽略，返回[`None`]
+    /// * ⚙️返回问题编号
+    pub fn ask(&mut self, task: &Task) -> Option<usize> {
+        let sentence = &task.sentence;
+        if sentence.punctuation != FORMAT_ASCII_ENUM.sentence.punctuation_question {
+            return None;
+        }
+        let id = self.questions.len() + 1;
+        self.questions.push(TrackedQuestion {
+            id,
+            narsese: FORMAT_ASCII.format_sentence(sentence),
+            term: sentence.term.clone(),
+            num_answers: 0,
+        });
+        Some(id)
+    }
+
+    /// 为输出关联问题
+    /// * 🚩仅关联带Narsese的`ANSWER`：其它输出⇒[`None`]
+    /// * 🚩优先关联最早的「尚未被回答」的问题；均已回答⇒关联最早的相符问题
+    /// * ⚙️返回所关联的问题编号
+    pub fn answer(&mut self, output: &Output) -> Option<usize> {
+        let narsese = match output {
+            Output::ANSWER {
+                narsese: Some(narsese),
+                ..
+            } => narsese,
+            _ => return None,
+        };
+        let answer_term = get_term(narsese);
+        let matched = self
+            .questions
+            .iter()
+            .enumerate()
+            .filter(|(_, question)| answers(&question.term, answer_term))
+            .map(|(index, question)| (question.num_answers > 0, index))
+            .min()?;
+        let question = &mut self.questions[matched.1];
+        question.num_answers += 1;
+        Some(question.id)
+    }
+
+    /// 所有登记过的问题
+    pub fn questions(&self) -> &[TrackedQuestion] {
+        &self.questions
+    }
+
+    /// 尚未被回答的问题
+    pub fn unanswered(&self) -> impl Iterator<Item = &TrackedQuestion> {
+        self.questions
+            .iter()
+            .filter(|question| question.num_answers == 0)
+    }
+}
+
+/// 判断回答的词项是否回答了问题
+/// * 🚩规整化未收敛⇒视作不符，并发出警告
+fn answers(question: &Term, answer: &Term) -> bool {
+    semantical_answers_mut(&mut question.clone(), &mut answer.clone()).unwrap_or_else(|e| {
+        print_notice("WARN", &format!("词项比对失败，视作未回答：{e}"));
+        false
+    })
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use narsese::{lexical_nse as nse, lexical_nse_task as nse_task};
+
+    /// 回答
+    fn answer(narsese: &str) -> Output {
+        Output::ANSWER {
+            content_raw: format!("Answer: {narsese}"),
+            narsese: Some(FORMAT_ASCII.parse(narsese).unwrap()),
+        }
+    }
+
+    /// 测试/登记与关联
+    #[test]
+    fn test_question_tracker() {
+        let mut tracker = QuestionTracker::default();
+        // 仅登记问题
+        assert_eq!(tracker.ask(&nse_task!(<A --> B>.)), None);
+        assert_eq!(tracker.ask(&nse_task!(<?x --> C>?)), Some(1));
+        assert_eq!(tracker.ask(&nse_task!(<A --> C>?)), Some(2));
+        assert_eq!(tracker.ask(&nse_task!(<A --> C>?)), Some(3));
+        // 查询变量⇒通配；已回答⇒让给下一个未回答的问题
+        assert_eq!(tracker.answer(&answer("<A --> C>. %1.0;0.81%")), Some(1));
+        assert_eq!(tracker.answer(&answer("<A --> C>. %1.0;0.81%")), Some(2));
+        assert_eq!(tracker.answer(&answer("<A --> C>. %1.0;0.81%")), Some(3));
+        // 均已回答⇒关联最早的相符问题
+        assert_eq!(tracker.answer(&answer("<A --> C>. %1.0;0.9%")), Some(1));
+        // 不相符、非回答⇒不关联
+        assert_eq!(tracker.answer(&answer("<A --> D>.")), None);
+        let out = Output::OUT {
+            content_raw: "<A --> C>.".into(),
+            narsese: Some(nse!(<A --> C>.)),
+        };
+        assert_eq!(tracker.answer(&out), None);
+        assert_eq!(tracker.unanswered().count(), 0);
+        assert_eq!(tracker.questions()[0].num_answers, 2);
+    }
+}
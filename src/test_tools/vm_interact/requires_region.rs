@@ -0,0 +1,70 @@
+//! 「功能要求」区段
+//! * 🎯`''requires:`：CIN缺少所要求的功能⇒跳过此后的输入，而非报告失败
+//! * 🚩小型状态机：「执行」⇄「跳过」
+//!   * `''requires:`⇒所要求的功能均被支持⇒执行；否则⇒跳过
+//!   * `''end-requires`⇒执行
+//! * 📌由调用方按NAL文件持有：文件结束即区段结束
+
+use super::NALInput;
+use crate::runtimes::Capabilities;
+
+/// 「功能要求」区段
+/// * 🚩默认：支持所有功能，不跳过任何输入
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequiresRegion {
+    /// CIN所支持的功能
+    capabilities: Capabilities,
+    /// 当前区段所缺少的功能
+    /// * 📌空⇒执行；非空⇒跳过
+    missing: Vec<String>,
+}
+
+impl RequiresRegion {
+    /// 构造函数
+    pub fn new(capabilities: Capabilities) -> Self {
+        Self {
+            capabilities,
+            missing: vec![],
+        }
+    }
+
+    /// 同一CIN、另一个NAL文件的「功能要求」区段
+    /// * 🎯被包含的NAL文件：区段不跨文件
+    pub fn for_another_file(&self) -> Self {
+        Self::new(self.capabilities)
+    }
+
+    /// 进入新的区段
+    /// * 🚩覆盖先前的区段：`''requires:`不嵌套
+    /// * ⚙️返回所缺少的功能：空⇒执行此后的输入
+    pub fn enter(&mut self, names: &[String]) -> &[String] {
+        self.missing = self
+            .capabilities
+            .missing(names)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        &self.missing
+    }
+
+    /// 结束当前区段
+    pub fn end(&mut self) {
+        self.missing.clear();
+    }
+
+    /// 当前区段所缺少的功能
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// 是否处于被跳过的区段
+    pub fn is_skipping(&self) -> bool {
+        !self.missing.is_empty()
+    }
+
+    /// 是否跳过某个输入
+    /// * 🚩「功能要求」本身从不跳过：以便结束、切换区段
+    pub fn skips(&self, input: &NALInput) -> bool {
+        self.is_skipping() && !matches!(input, NALInput::Requires(..) | NALInput::EndRequires)
+    }
+}
@@ -0,0 +1,302 @@
+//! 「保存输出」的文件路径
+//! * 🎯[`NALInput::SaveOutputs`](crate::test_tools::NALInput::SaveOutputs)：不再覆盖上次运行保存的文件
+//!   * 📄`''save-outputs: logs/run-{timestamp}.json`
+//! * 🚩写入前：检查路径、替换时间戳、创建缺失的父目录
+//! * 🚩写入后：按「保留数量」清理同一目录下同模式的旧文件
+
+use anyhow::{anyhow, Result};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    time::SystemTime,
+};
+
+/// 路径中的「时间戳」占位符
+/// * 🚩写入时替换为[文件名安全的ISO-8601时间](format_timestamp)
+pub const TIMESTAMP_TOKEN: &str = "{timestamp}";
+
+/// 「保存输出」的选项
+/// * 🚩默认：不清理旧文件，不允许绝对路径
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SaveOutputsOptions {
+    /// 保留的文件数
+    /// * 🚩写入后只保留最新的若干个同模式文件，删去其余的
+    /// * 📌仅在文件名含[时间戳](TIMESTAMP_TOKEN)时生效：否则同一路径只有一个文件
+    /// * 📄启动配置中的`saveOutputsKeep`
+    pub keep: Option<usize>,
+
+    /// 是否允许绝对路径
+    /// * 🚩不允许⇒只能保存到NAL所在目录之下
+    /// * 📄启动配置中的`saveOutputsAllowAbsolute`
+    pub allow_absolute: bool,
+}
+
+/// 将时间格式化为文件名安全的ISO-8601时间（UTC）
+/// * 📄`2024-06-12T01-54-13.123Z`
+/// * 🚩时分秒之间的`:`换成`-`：Windows文件名不允许`:`
+/// * 📌同一格式下，字典序即时间顺序
+pub fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = ((secs / 86400) as i64, secs % 86400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}-{:02}-{:02}.{:03}Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// 自1970-01-01起的天数⇒公历年月日
+/// * 📝Howard Hinnant的`civil_from_days`算法
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// 检查「保存输出」的路径，并基于NAL所在目录解析
+/// * 🚩相对路径⇒不得以`..`越出NAL所在目录
+/// * 🚩绝对路径⇒须在选项中[允许](SaveOutputsOptions::allow_absolute)
+/// * 📌返回的路径仍含[时间戳占位符](TIMESTAMP_TOKEN)：清理旧文件时用作模式
+pub fn resolve_save_path(
+    nal_root_path: &Path,
+    raw_path: &str,
+    options: SaveOutputsOptions,
+) -> Result<PathBuf> {
+    let path = Path::new(raw_path.trim());
+    if path.has_root() || path.is_absolute() {
+        return match options.allow_absolute {
+            true => Ok(path.to_path_buf()),
+            false => Err(anyhow!(
+                "不允许保存输出到绝对路径{path:?}：需在启动配置中开启`saveOutputsAllowAbsolute`"
+            )),
+        };
+    }
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Normal(..) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return Err(anyhow!("保存输出的路径{path:?}越出了NAL所在目录")),
+        }
+    }
+    Ok(nal_root_path.join(path))
+}
+
+/// 将内容写入「保存输出」的文件
+/// * 🚩替换时间戳⇒创建父目录⇒写入⇒清理旧文件
+///   * 📌清理时至少保留刚写入的文件
+/// * ⚙️返回实际写入的路径
+pub fn write_saved_outputs(
+    nal_root_path: &Path,
+    raw_path: &str,
+    contents: &str,
+    options: SaveOutputsOptions,
+    time: SystemTime,
+) -> Result<PathBuf> {
+    let template = resolve_save_path(nal_root_path, raw_path, options)?;
+    let path = PathBuf::from(
+        template
+            .to_string_lossy()
+            .replace(TIMESTAMP_TOKEN, &format_timestamp(time)),
+    );
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, contents)?;
+    if let Some(keep) = options.keep {
+        prune_saved_outputs(&template, keep.max(1))?;
+    }
+    Ok(path)
+}
+
+/// 清理旧的「保存输出」文件
+/// * 🚩在模式所在目录中，找出「文件名仅在时间戳处不同」的文件，按文件名排序后删去最旧的
+///   * 📌文件名中的时间戳[可按字典序排序](format_timestamp)
+///   * 📌时间戳处须[形如时间戳](is_timestamp)：`run-{timestamp}.json`不会删去`run-notes.json`
+/// * 📌文件名不含时间戳、目录含时间戳⇒不清理
+/// * ⚙️返回被删去的文件
+pub fn prune_saved_outputs(template: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let (Some(dir), Some(file_name)) = (template.parent(), template.file_name()) else {
+        return Ok(vec![]);
+    };
+    let file_name = file_name.to_string_lossy();
+    let Some((prefix, suffix)) = file_name.split_once(TIMESTAMP_TOKEN) else {
+        return Ok(vec![]);
+    };
+    if dir.to_string_lossy().contains(TIMESTAMP_TOKEN) {
+        return Ok(vec![]);
+    }
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let matches = name.len() >= prefix.len() + suffix.len()
+            && name.starts_with(prefix)
+            && name.ends_with(suffix)
+            && is_timestamp(&name[prefix.len()..name.len() - suffix.len()]);
+        if matches && entry.file_type()?.is_file() {
+            files.push(entry.path());
+        }
+    }
+    // 新⇒旧
+    files.sort_unstable_by(|a, b| b.cmp(a));
+    let removed = files.split_off(keep.min(files.len()));
+    for path in &removed {
+        fs::remove_file(path)?;
+    }
+    Ok(removed)
+}
+
+/// 是否形如[`format_timestamp`]生成的时间戳
+/// * 📄`2024-06-12T01-54-13.123Z`
+/// * 🚩逐字符比对：数字处须为ASCII数字，其余须与分隔符一致
+pub fn is_timestamp(s: &str) -> bool {
+    const SHAPE: &[u8] = b"0000-00-00T00-00-00.000Z";
+    s.len() == SHAPE.len()
+        && s.bytes().zip(SHAPE).all(|(c, shape)| match shape {
+            b'0' => c.is_ascii_digit(),
+            _ => c == *shape,
+        })
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// 临时目录
+    /// * 🚩按测试名、进程号区分
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "babelnar_save_outputs_{name}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// 自1970-01-01起的指定秒数
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    /// 测试/时间戳格式
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(at(0)), "1970-01-01T00-00-00.000Z");
+        assert_eq!(
+            format_timestamp(at(1718157253) + Duration::from_millis(123)),
+            "2024-06-12T01-54-13.123Z"
+        );
+        assert_eq!(format_timestamp(at(951782400)), "2000-02-29T00-00-00.000Z");
+    }
+
+    /// 测试/时间戳形状
+    #[test]
+    fn test_is_timestamp() {
+        assert!(is_timestamp(&format_timestamp(at(1718157253))));
+        assert!(is_timestamp("1970-01-01T00-00-00.000Z"));
+        assert!(!is_timestamp("notes"));
+        assert!(!is_timestamp("2024-06-12"));
+        assert!(!is_timestamp("2024-06-12T01-54-13.123"));
+        assert!(!is_timestamp("2024-06-12T01:54:13.123Z"));
+        assert!(!is_timestamp("2024-O6-12T01-54-13.123Z"));
+    }
+
+    /// 测试/路径检查
+    #[test]
+    fn test_resolve_save_path() {
+        let root = Path::new("nal");
+        let default = SaveOutputsOptions::default();
+        let resolve = |raw, options| resolve_save_path(root, raw, options);
+        assert_eq!(
+            resolve("logs/run-{timestamp}.json", default).unwrap(),
+            root.join("logs/run-{timestamp}.json")
+        );
+        assert_eq!(
+            resolve("./logs/../out.json", default).unwrap(),
+            root.join("./logs/../out.json")
+        );
+        // 越出NAL所在目录
+        assert!(resolve("../out.json", default).is_err());
+        assert!(resolve("logs/../../out.json", default).is_err());
+        // 绝对路径
+        let absolute = std::env::temp_dir().join("out.json");
+        let absolute = absolute.to_str().unwrap();
+        assert!(resolve(absolute, default).is_err());
+        let allow_absolute = SaveOutputsOptions {
+            allow_absolute: true,
+            ..default
+        };
+        assert_eq!(
+            resolve(absolute, allow_absolute).unwrap(),
+            PathBuf::from(absolute)
+        );
+    }
+
+    /// 测试/写入：替换时间戳、创建父目录
+    #[test]
+    fn test_write_saved_outputs() {
+        let dir = temp_dir("write");
+        let path = write_saved_outputs(
+            &dir,
+            "logs/run-{timestamp}.json",
+            "[]",
+            SaveOutputsOptions::default(),
+            at(0),
+        )
+        .unwrap();
+        assert_eq!(path, dir.join("logs/run-1970-01-01T00-00-00.000Z.json"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[]");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// 测试/清理旧文件：只保留最新的若干个同模式文件
+    #[test]
+    fn test_prune_saved_outputs() {
+        let dir = temp_dir("prune");
+        fs::write(dir.join("other.json"), "").unwrap();
+        fs::write(dir.join("run-.json"), "").unwrap();
+        fs::write(dir.join("run-notes.json"), "").unwrap();
+        fs::write(dir.join("run-2024-06-12.json"), "").unwrap();
+        let options = SaveOutputsOptions {
+            keep: Some(2),
+            ..Default::default()
+        };
+        // 乱序写入
+        let paths = [30, 10, 20, 40]
+            .map(|secs| write_saved_outputs(&dir, "run-{timestamp}.json", "", options, at(secs)));
+        let [p30, p10, p20, p40] = paths.map(Result::unwrap);
+        // 最旧的被删去
+        assert!(!p10.exists());
+        assert!(!p20.exists());
+        assert!(p30.exists());
+        assert!(p40.exists());
+        // 模式不符的文件不受影响
+        assert!(dir.join("other.json").exists());
+        assert!(dir.join("run-.json").exists());
+        assert!(dir.join("run-notes.json").exists());
+        assert!(dir.join("run-2024-06-12.json").exists());
+        // 不含时间戳⇒不清理
+        let removed = prune_saved_outputs(&dir.join("other.json"), 0).unwrap();
+        assert!(removed.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,123 @@
+//! 「设置」指令的回执判断
+//! * 🎯`''set:`魔法注释：按指令分别指定「CIN的哪条输出算作回执」
+//!   * 📄`VOL`的回执是音量提示，`SET`的回执是参数回显：不能共用一个字串
+//! * 📌键为指令头（不区分大小写），值为回执的原始内容应包含的字串
+//!   * 🚩键`*`⇒未单独指定的指令
+//! * ⚠️空字串不算回执：未指定回执的指令⇒报告「无法确认」，而非把任意输出当作回执
+
+use navm::{cmd::Cmd, output::Output};
+use std::collections::BTreeMap;
+
+/// 「设置」指令的回执判断
+/// * 📄配置中写作`"volume"`（所有指令）或`{ "VOL": "volume", "*": "ok" }`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SetAckRepr", into = "SetAckRepr"))]
+pub struct SetAck {
+    /// 指令头（大写）⇒回执内容
+    by_head: BTreeMap<String, String>,
+}
+
+/// 通配的指令头
+pub const SET_ACK_ANY_HEAD: &str = "*";
+
+impl SetAck {
+    /// 所有指令使用同一回执
+    /// * 🚩空字串⇒不指定任何回执
+    pub fn all(ack: impl Into<String>) -> Self {
+        Self::default().with(SET_ACK_ANY_HEAD, ack)
+    }
+
+    /// 为某个指令头指定回执
+    /// * 🚩空字串⇒移除该指令头的回执
+    pub fn with(mut self, head: &str, ack: impl Into<String>) -> Self {
+        let head = head.trim().to_uppercase();
+        match ack.into() {
+            ack if ack.is_empty() => self.by_head.remove(&head),
+            ack => self.by_head.insert(head, ack),
+        };
+        self
+    }
+
+    /// 是否未指定任何回执
+    pub fn is_empty(&self) -> bool {
+        self.by_head.is_empty()
+    }
+
+    /// 获取指令的回执内容
+    /// * 🚩先按指令头查找，再查找通配的`*`
+    /// * 🚩未指定⇒[`None`]
+    pub fn ack_for(&self, cmd: &Cmd) -> Option<&str> {
+        self.by_head
+            .get(&cmd.head().to_uppercase())
+            .or_else(|| self.by_head.get(SET_ACK_ANY_HEAD))
+            .map(String::as_str)
+    }
+
+    /// 判断输出是否为指令的回执
+    /// * 🚩未指定回执⇒始终为`false`
+    pub fn is_ack(&self, cmd: &Cmd, output: &Output) -> bool {
+        self.ack_for(cmd)
+            .is_some_and(|ack| output.raw_content().contains(ack))
+    }
+}
+
+impl From<&str> for SetAck {
+    fn from(ack: &str) -> Self {
+        Self::all(ack)
+    }
+}
+
+/// 配置中的写法
+/// * 📌字串⇒所有指令；对象⇒按指令头分别指定
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum SetAckRepr {
+    All(String),
+    ByHead(BTreeMap<String, String>),
+}
+
+#[cfg(feature = "serde")]
+impl From<SetAckRepr> for SetAck {
+    fn from(repr: SetAckRepr) -> Self {
+        match repr {
+            SetAckRepr::All(ack) => Self::all(ack),
+            SetAckRepr::ByHead(by_head) => by_head
+                .into_iter()
+                .fold(Self::default(), |set_ack, (head, ack)| {
+                    set_ack.with(&head, ack)
+                }),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SetAck> for SetAckRepr {
+    fn from(set_ack: SetAck) -> Self {
+        Self::ByHead(set_ack.by_head)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试/按指令头查找回执
+    #[test]
+    fn test_ack_for() {
+        let set_ack = SetAck::all("ok").with("vol", "volume");
+        assert_eq!(set_ack.ack_for(&Cmd::VOL(0)), Some("volume"));
+        assert_eq!(set_ack.ack_for(&Cmd::CYC(1)), Some("ok"));
+        // 未指定⇒无回执，任何输出都不算
+        let set_ack = SetAck::default().with("VOL", "volume");
+        assert_eq!(set_ack.ack_for(&Cmd::CYC(1)), None);
+        let output = Output::INFO {
+            message: "anything".into(),
+        };
+        assert!(!set_ack.is_ack(&Cmd::CYC(1), &output));
+        // 空字串⇒不指定
+        assert!(SetAck::from("").is_empty());
+    }
+}
@@ -1,7 +1,13 @@
+use nar_dev_utils::if_return;
 use narsese::{
-    conversion::string::impl_enum::format_instances::FORMAT_ASCII as FORMAT_ASCII_ENUM, lexical::*,
+    conversion::string::{
+        impl_enum::format_instances::FORMAT_ASCII as FORMAT_ASCII_ENUM,
+        impl_lexical::format_instances::FORMAT_ASCII,
+    },
+    lexical::*,
 };
 use std::{cmp::Ordering, collections::HashMap};
+use thiserror::Error;
 
 /// 简单获取词项的「标识符」
 /// * 🎯识别是否为「可交换词项」
@@ -42,7 +48,11 @@ fn is_variable_atom_prefix(prefix: &str) -> bool {
 //     }
 // }
 
-type VariableNameMap = HashMap<String, String>;
+/// 变量映射表
+/// * 📌键：`(前缀, 原名称)`——前缀不同的同名变量，是不同的变量
+///   * 📄`$1`与`#1`
+/// * 📌值：新名称
+type VariableNameMap = HashMap<(String, String), String>;
 
 /// 重命名词项中的所有变量
 /// * 🎯给所有词项统一编号
@@ -57,7 +67,7 @@ fn rename_variables_in_term(term: &mut Term) -> (bool, VariableNameMap) {
 /// 带映射地递归重命名变量
 fn rename_variables_in_term_with_map(term: &mut Term, map: &mut VariableNameMap) -> bool {
     find_variables_renaming(term, map);
-    let modified = map.iter().any(|(k, v)| k != v);
+    let modified = map.iter().any(|((_, name), new_name)| name != new_name);
     if modified {
         apply_name_substitute(term, map);
     }
@@ -69,15 +79,10 @@ fn find_variables_renaming(term: &Term, map: &mut VariableNameMap) {
     match term {
         // * 🚩原子变量词项⇒尝试命名
         Atom { prefix, name } if is_variable_atom_prefix(prefix) => {
-            let new_name = match map.get(name) {
-                Some(n) => n.clone(),
-                None => (map.len() + 1).to_string(), // ? 避免已有的数字变量干扰 | 2 -> 1, 1 -> 2
-            };
-            // * 📌插入名称
-            // if *name != new_name {
-            map.insert(name.clone(), new_name.clone());
-            // }
-            // rename_variable_atom(term, &new_name);
+            // * 📌按首次出现的顺序编号；已编号⇒不变
+            let len = map.len();
+            map.entry((prefix.clone(), name.clone()))
+                .or_insert_with(|| (len + 1).to_string());
         }
         // * 🚩复合词项⇒递归深入
         Compound { terms, .. } | Set { terms, .. } => terms
@@ -93,10 +98,13 @@ fn find_variables_renaming(term: &Term, map: &mut VariableNameMap) {
     }
 }
 
+/// 按映射表替换变量名
+/// * ⚠️只替换变量：与变量同名的非变量原子（如`<$1 --> 1>`中的`1`）保持不变
+///   * 📝若一并替换，非变量原子的名称会随变量编号改变，进而改变排序结果，使「重命名+排序」来回振荡
 fn apply_name_substitute(term: &mut Term, map: &VariableNameMap) {
     match term {
-        Atom { name, .. } => {
-            if let Some(new_name) = map.get(name) {
+        Atom { prefix, name } => {
+            if let Some(new_name) = map.get(&(prefix.clone(), name.clone())) {
                 *name = new_name.clone()
             }
         }
@@ -114,11 +122,34 @@ fn apply_name_substitute(term: &mut Term, map: &VariableNameMap) {
     }
 }
 
+// 像 //
+
+/// 判断一个词项标识符是否为「像」
+/// * 📄外延像`(/, R, A, _)`、内涵像`(\, R, _, B)`
+/// * ⚠️像的占位符位置有意义：元素不可交换
+fn is_image_term(identifier: &str) -> bool {
+    identifier == FORMAT_ASCII_ENUM.compound.connecter_image_extension
+        || identifier == FORMAT_ASCII_ENUM.compound.connecter_image_intension
+}
+
+/// 判断一个词项是否为「像占位符」
+fn is_placeholder(term: &Term) -> bool {
+    matches!(term, Atom { prefix, .. } if prefix == FORMAT_ASCII_ENUM.atom.prefix_placeholder)
+}
+
+/// 获取像中占位符的位置
+/// * 🎯比较像时，占位符的位置先于其它元素参与比较
+fn placeholder_index(terms: &[Term]) -> Option<usize> {
+    terms.iter().position(is_placeholder)
+}
+
 // 对「可交换词项」排序 //
 
 /// 判断一个词项前缀是否为「可交换词项」
 /// * 🚩一元词项不被视作【可交换的】词项：无需交换
+/// * 🚩像永远不可交换：占位符的位置有意义
 fn is_communicative_term(identifier: &str) -> bool {
+    if_return! { is_image_term(identifier) => false }
     // 外延集&内涵集
     identifier == FORMAT_ASCII_ENUM.compound.brackets_set_extension.0
         || identifier == FORMAT_ASCII_ENUM.compound.brackets_set_intension.0
@@ -163,6 +194,24 @@ fn term_comparator(term1: &Term, term2: &Term) -> Ordering {
             // * 🚩其它情况⇒正常按名称判断
             (false, false) => p1.cmp(p2).then(n1.cmp(n2)),
         },
+        // * 🚩像🆚像 ⇒ 先比较占位符的位置，再逐个比较元素
+        (
+            Compound {
+                connecter: c1,
+                terms: t1,
+            },
+            Compound {
+                connecter: c2,
+                terms: t2,
+            },
+        ) if c1 == c2 && is_image_term(c1) => {
+            placeholder_index(t1).cmp(&placeholder_index(t2)).then(
+                t1.iter()
+                    .zip(t2.iter())
+                    .map(term_comparator_zipped)
+                    .fold(Equal, Ordering::then),
+            )
+        }
         // * 🚩复合🆚复合 | 集合🆚集合 ⇒ 深入
         (
             Compound {
@@ -216,6 +265,7 @@ fn term_comparator(term1: &Term, term2: &Term) -> Ordering {
 
 /// 对内部的「可交换词项」排序
 /// * 🚩可交换词项⇒排序；不可交换⇒对其内子项排序
+/// * 📌像的元素顺序保持不变：只对其内子项排序
 fn sort_communicative_terms(term: &mut Term) -> bool {
     // * 🚩尝试对内部词项排序
     let mut modified = match term {
@@ -288,51 +338,169 @@ fn sort_a_communicative_term(term: &mut Term) -> bool {
 
 const MAX_TRIES_FORMALIZE: usize = 0x100;
 
+/// 规整化未收敛的错误
+/// * 🎯取代panic：不至于因单个词项拖垮整个测试进程
+/// * 📌附带最后几轮的规整化结果，便于排查
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("词项「{term}」重复规整化{MAX_TRIES_FORMALIZE}次，仍未稳定：\n{}", .trace.join("\n"))]
+pub struct FormalizeError {
+    /// 原始词项
+    pub term: String,
+    /// 最后几轮的规整化结果
+    pub trace: Vec<String>,
+}
+
+/// 对词项进行一轮「重命名变量+排序」
+/// * ⚙️返回「是否修改」
+fn formalize_once(term: &mut Term) -> bool {
+    let mut map = VariableNameMap::new();
+    // 命名变量
+    let modified = rename_variables_in_term_with_map(term, &mut map);
+    // 排序 | 🚩放后头避免截断
+    sort_communicative_terms(term) || modified
+}
+
 /// 规范化一个词项
 /// * 📌语义上相等⇒一定会被规范到同一形式
-pub fn formalize_term(term: &mut Term) -> &mut Term {
-    let mut map = VariableNameMap::new();
-    let mut modified;
+/// * 📝变量只按「首次出现的顺序」编号，而排序忽略变量名：排序结果不受编号影响，故必定收敛
+/// * ⚠️循环次数有限：超出⇒返回错误，而非panic
+pub fn formalize_term(term: &mut Term) -> Result<(), FormalizeError> {
+    let original = FORMAT_ASCII.format(term);
     // 修改到无法修改为止
-    // * 📌循环次数有限，防止死循环
     for _ in 0..MAX_TRIES_FORMALIZE {
-        // 命名变量
-        modified = rename_variables_in_term_with_map(term, &mut map);
-        // 排序 | 🚩放后头避免截断
-        modified = sort_communicative_terms(term) || modified;
         // * 🚩若无变化⇒退出
-        if !modified {
-            return term;
+        if !formalize_once(term) {
+            return Ok(());
         }
-        // 重置
-        map.clear();
     }
-    // * 🚩尝试多次仍未稳定⇒收集信息，panic | 📝这是个程序漏洞，而非可失败的选项：重命名+排序 必定会收敛（有待论证）
+    // * 🚩尝试多次仍未稳定⇒收集信息
     const N: usize = 0x10;
-    let mut stack = Vec::with_capacity(N);
-    for _ in 0..N {
-        use narsese::conversion::string::impl_lexical::format_instances::FORMAT_ASCII;
-        // 命名变量
-        rename_variables_in_term_with_map(term, &mut map);
-        // 排序
-        sort_communicative_terms(term);
-        // 打印
-        stack.push(format!("modified: {:}", FORMAT_ASCII.format(term)));
-        // 重置
-        map.clear();
-    }
-    panic!(
-        "异常：程序重复尝试了{MAX_TRIES_FORMALIZE}次，仍未稳定。\n堆栈：\n{}",
-        stack.join("\n")
-    );
+    let trace = (0..N)
+        .map(|_| {
+            formalize_once(term);
+            FORMAT_ASCII.format(term)
+        })
+        .collect();
+    Err(FormalizeError {
+        term: original,
+        trace,
+    })
 }
 
 /// 入口：词项判等
 /// * 🚩通过「规整化词项」实现判等逻辑
 ///   * 📌可交换词项「顺序不影响相等」 ⇒ 固定顺序 ⇒ 排序
 ///   * 📌变量词项「编号不影响相等」 ⇒ 固定顺序 ⇒ 统一重命名
-pub fn semantical_equal_mut(term1: &mut Term, term2: &mut Term) -> bool {
-    *formalize_term(term1) == *formalize_term(term2)
+pub fn semantical_equal_mut(term1: &mut Term, term2: &mut Term) -> Result<bool, FormalizeError> {
+    formalize_term(term1)?;
+    formalize_term(term2)?;
+    Ok(term1 == term2)
+}
+
+/// 入口：词项包含
+/// * 🎯判断`whole`中是否含有与`part`语义相等的子词项（含自身）
+/// * 🚩逐个子词项规整化后比对：变量在子词项层面重命名
+///   * 📄`<$1 --> (/,open,$2,_)>`含有`(/,open,$1,_)`
+pub fn semantical_contains_mut(whole: &mut Term, part: &mut Term) -> Result<bool, FormalizeError> {
+    formalize_term(part)?;
+    contains_formalized(whole, part)
+}
+
+/// 入口：回答匹配问题
+/// * 🎯将「回答」关联到「问题」：问题中的查询变量视作通配符
+/// * 🚩先尝试语义判等；不等⇒规整化后逐层匹配，同名的查询变量须匹配同一词项
+///   * 📄`<?1 --> C>`匹配`<A --> C>`；`<?1 --> ?1>`不匹配`<A --> C>`
+/// * ⚠️可交换词项只按规整化后的顺序匹配，不尝试其它排列
+pub fn semantical_answers_mut(
+    question: &mut Term,
+    answer: &mut Term,
+) -> Result<bool, FormalizeError> {
+    if_return! { semantical_equal_mut(question, answer)? => Ok(true) }
+    let mut bindings = HashMap::new();
+    Ok(matches_query_variables(question, answer, &mut bindings))
+}
+
+/// 逐层匹配（已规整化的）问题与回答
+/// * 🚩查询变量⇒首次出现时绑定，此后须与所绑定的词项相等
+fn matches_query_variables<'a>(
+    question: &Term,
+    answer: &'a Term,
+    bindings: &mut HashMap<String, &'a Term>,
+) -> bool {
+    match (question, answer) {
+        // * 🚩查询变量⇒通配
+        (Atom { prefix, name }, _) if prefix == FORMAT_ASCII_ENUM.atom.prefix_variable_query => {
+            *bindings.entry(name.clone()).or_insert(answer) == answer
+        }
+        // * 🚩复合🆚复合 | 集合🆚集合 ⇒ 逐个深入
+        (
+            Compound {
+                connecter: c1,
+                terms: t1,
+            },
+            Compound {
+                connecter: c2,
+                terms: t2,
+            },
+        )
+        | (
+            Set {
+                left_bracket: c1,
+                terms: t1,
+                ..
+            },
+            Set {
+                left_bracket: c2,
+                terms: t2,
+                ..
+            },
+        ) => {
+            c1 == c2
+                && t1.len() == t2.len()
+                && (t1.iter().zip(t2))
+                    .all(|(term1, term2)| matches_query_variables(term1, term2, bindings))
+        }
+        // * 🚩陈述🆚陈述
+        (
+            Statement {
+                copula: c1,
+                subject: s1,
+                predicate: p1,
+            },
+            Statement {
+                copula: c2,
+                subject: s2,
+                predicate: p2,
+            },
+        ) => {
+            c1 == c2
+                && matches_query_variables(s1, s2, bindings)
+                && matches_query_variables(p1, p2, bindings)
+        }
+        // * 🚩其它⇒直接判等
+        _ => question == answer,
+    }
+}
+
+/// 在（已规整化的）目标词项下递归查找子词项
+/// * 🚩遍历方式同[`apply_name_substitute`]
+fn contains_formalized(whole: &Term, part: &Term) -> Result<bool, FormalizeError> {
+    // 自身⇒复制后规整化，不影响上层的变量编号
+    let mut formalized = whole.clone();
+    formalize_term(&mut formalized)?;
+    if_return! { formalized == *part => Ok(true) }
+    match whole {
+        Atom { .. } => Ok(false),
+        Compound { terms, .. } | Set { terms, .. } => {
+            for term in terms {
+                if_return! { contains_formalized(term, part)? => Ok(true) }
+            }
+            Ok(false)
+        }
+        Statement {
+            subject, predicate, ..
+        } => Ok(contains_formalized(subject, part)? || contains_formalized(predicate, part)?),
+    }
 }
 
 #[cfg(test)]
@@ -457,6 +625,11 @@ mod tests {
             "(&&, A)" > "#1"
             "(&&, #2)" == "(&&, $2)"
             "<$1 --> lock>" > "<$2 --> key>"
+            // * 🚩像：占位符的位置先于元素比较
+            "(/, open, _, $1)" < "(/, open, $1, _)"
+            "(/, open, _, B)" < "(/, open, A, _)"
+            "(/, open, $1, _)" == "(/, open, $2, _)"
+            "(\\, open, $1, _)" > "(\\, open, _, $1)"
         }
     }
 
@@ -465,17 +638,36 @@ mod tests {
         fn t(s: &str) {
             let mut term = parse_term(s);
             print_term(&term);
-            formalize_term(&mut term);
+            formalize_term(&mut term).expect("规整化未收敛");
             print_term(&term);
             // 幂等性
             let term_original = term.clone();
-            formalize_term(&mut term);
+            formalize_term(&mut term).expect("规整化未收敛");
             print_term(&term);
             assert!(term == term_original)
         }
         t("<(&&, <$2 --> $1>, <$3 <-> $2>, S, #4) ==> <<A <-> $1> ==> <$3 --> {(/, R, _, $3), $2}>>>");
     }
 
+    /// 测试/规整化收敛
+    /// * 📄曾来回振荡的词项：非变量原子`1`、`2`随变量编号被重命名，进而改变排序结果
+    #[test]
+    fn formalize_converge() {
+        fn t(s: &str) {
+            let mut term = parse_term(s);
+            formalize_term(&mut term).unwrap_or_else(|e| panic!("{e}"));
+            // 幂等性
+            let term_original = term.clone();
+            formalize_term(&mut term).unwrap_or_else(|e| panic!("{e}"));
+            assert_eq!(term, term_original, "{}", fmt_term(&term));
+        }
+        t("(&&, <$1 --> $2>, <$2 --> $1>)");
+        t("(&&, <$2 --> 1>, <$1 --> 2>)");
+        t("(*, $3, (&&, 2, <(&&, $3, $2, 1) <-> (&&, $3, #1, 2)>))");
+        t("{<(&&, 1, A) <-> $1>, (*, A, {<1 <-> $3>, <2 <-> $1>})}");
+        t("<(*, 2, (*, (*, #1, 1), {$3, #1})) <-> (*, 1, {(*, $2, 2), <#1 <-> #1>})>");
+    }
+
     #[test]
     fn semantical_eq() {
         macro_once! {
@@ -488,14 +680,14 @@ mod tests {
                 (@INNER $s1:literal == $s2:literal) => {
                     let mut t1 = term!($s1);
                     let mut t2 = term!($s2);
-                    let eq = semantical_equal_mut(&mut t1, &mut t2);
+                    let eq = semantical_equal_mut(&mut t1, &mut t2).expect("规整化未收敛");
                     assert!(eq, "{} != {}", fmt_term(&t1), fmt_term(&t2));
                 }
                 // * 📌不等号的情况
                 (@INNER $s1:literal != $s2:literal) => {
                     let mut t1 = term!($s1);
                     let mut t2 = term!($s2);
-                    let eq = semantical_equal_mut(&mut t1, &mut t2);
+                    let eq = semantical_equal_mut(&mut t1, &mut t2).expect("规整化未收敛");
                     assert!(!eq, "{} == {}", fmt_term(&t1), fmt_term(&t2));
                 }
             }
@@ -521,6 +713,107 @@ mod tests {
             "<$1 --> #2>" == "<$2 --> #1>"
             "<?1 --> #2>" == "<?2 --> #1>"
             "<$1 --> ?2>" == "<$2 --> ?1>"
+            // * 🚩只重命名变量：与变量同名的非变量原子不变
+            "<$2 --> 1>" == "<$1 --> 1>"
+            "<$2 --> (*, 1, $1)>" != "<$1 --> (*, 2, $2)>"
+            "(&&, <$2 --> 1>, <$1 --> 2>)" == "(&&, <$1 --> 2>, <$2 --> 1>)"
+            // * 🚩像：占位符的位置参与判等，且元素不参与排序
+            "(/,open,$1,_)" != "(/,open,_,$1)"
+            "(\\,open,$1,_)" != "(\\,open,_,$1)"
+            "(/,open,$1,_)" != "(\\,open,$1,_)"
+            "(/,open,A,_)" != "(/,A,open,_)"
+            "(/,R,{A,B},_)" == "(/,R,{B,A},_)"
+            "(&&,(/,R,$1,_),(/,R,_,$2))" == "(&&,(/,R,_,$1),(/,R,$2,_))"
+            "<$1 --> (/,open,$2,_)>" != "<$1 --> (/,open,_,$2)>"
+        }
+    }
+
+    #[test]
+    fn semantical_contains() {
+        macro_once! {
+            macro test_ {
+                ($($s1:literal $t1:tt $s2:literal)*) => {$(
+                    test_!{ @INNER $s1 $t1 $s2 }
+                )*}
+                // * 📌「包含」的情况
+                (@INNER $s1:literal >= $s2:literal) => {
+                    let mut t1 = term!($s1);
+                    let mut t2 = term!($s2);
+                    let contains = semantical_contains_mut(&mut t1, &mut t2).expect("规整化未收敛");
+                    assert!(contains, "{} ⊉ {}", fmt_term(&t1), fmt_term(&t2));
+                }
+                // * 📌「不包含」的情况
+                (@INNER $s1:literal != $s2:literal) => {
+                    let mut t1 = term!($s1);
+                    let mut t2 = term!($s2);
+                    let contains = semantical_contains_mut(&mut t1, &mut t2).expect("规整化未收敛");
+                    assert!(!contains, "{} ⊇ {}", fmt_term(&t1), fmt_term(&t2));
+                }
+            }
+            // * 🚩自身
+            "A" >= "A"
+            "<A --> B>" >= "<A --> B>"
+            // * 🚩嵌套的原子词项
+            "<(*, {SELF}) --> ^left>" >= "^left"
+            "<(*, {SELF}) --> ^left>" >= "SELF"
+            "<(&/, a, <(*, {SELF}) --> ^left>) =/> g>" >= "^left"
+            "<(&/, a, <(*, {SELF}) --> ^left>) =/> g>" >= "{SELF}"
+            "[B, {C, (&&, D, E)}]" >= "E"
+            // * 🚩可交换词项不计顺序
+            "<(&&, <B --> C>, <A --> B>) ==> D>" >= "(&&, <A --> B>, <B --> C>)"
+            // * 🚩变量在子词项层面重命名
+            "<$1 --> (/,open,$2,_)>" >= "(/,open,$1,_)"
+            "<(&&,<$x --> lock>,<$y --> key>) ==> Z>" >= "<$1 --> key>"
+            // * 🚩像：占位符位置不同⇒不包含
+            "<$1 --> (/,open,$2,_)>" != "(/,open,_,$1)"
+            // * 🚩否定情况
+            "<(*, {SELF}) --> ^left>" != "^right"
+            "<(*, {SELF}) --> ^left>" != "left"
+            "<A --> B>" != "<B --> A>"
+            "<(*, A, B) --> R>" != "(*, B, A)"
+            "<$1 --> key>" != "<#1 --> key>"
+            "A" != "<A --> B>"
+        }
+    }
+
+    #[test]
+    fn semantical_answers() {
+        macro_once! {
+            macro test_ {
+                ($($s1:literal $t1:tt $s2:literal)*) => {$(
+                    test_!{ @INNER $s1 $t1 $s2 }
+                )*}
+                // * 📌「回答」的情况
+                (@INNER $s1:literal <= $s2:literal) => {
+                    let mut t1 = term!($s1);
+                    let mut t2 = term!($s2);
+                    let answers = semantical_answers_mut(&mut t1, &mut t2).expect("规整化未收敛");
+                    assert!(answers, "{} 未回答 {}", fmt_term(&t2), fmt_term(&t1));
+                }
+                // * 📌「不回答」的情况
+                (@INNER $s1:literal != $s2:literal) => {
+                    let mut t1 = term!($s1);
+                    let mut t2 = term!($s2);
+                    let answers = semantical_answers_mut(&mut t1, &mut t2).expect("规整化未收敛");
+                    assert!(!answers, "{} 回答了 {}", fmt_term(&t2), fmt_term(&t1));
+                }
+            }
+            // * 🚩语义相等
+            "<A --> C>" <= "<A --> C>"
+            "<A <-> B>" <= "<B <-> A>"
+            // * 🚩查询变量⇒通配
+            "<?x --> C>" <= "<A --> C>"
+            "<?x --> C>" <= "<(*, A, B) --> C>"
+            "<(*, ?x, ?y) --> R>" <= "<(*, A, B) --> R>"
+            "<?x <-> A>" <= "<B <-> A>"
+            // * 🚩同名的查询变量须匹配同一词项
+            "<(*, ?x, ?x) --> R>" <= "<(*, A, A) --> R>"
+            "<(*, ?x, ?x) --> R>" != "<(*, A, B) --> R>"
+            // * 🚩否定情况
+            "<?x --> C>" != "<A --> B>"
+            "<?x --> C>" != "<A <-> C>"
+            "<A --> C>" != "<B --> C>"
+            "<$x --> C>" != "<A --> C>"
         }
     }
 }
@@ -0,0 +1,42 @@
+//! 集成测试/BabelNAR CLI启动命令的环境变量
+//! * 🎯验证启动配置`command.env`中的环境变量被传给CIN子进程
+//! * 🚩以`sh`作为「CIN」：先打印环境变量，再以`cat`回显输入
+
+mod common;
+
+use navm::output::Output;
+
+/// 测试/CIN读到配置中的环境变量
+#[test]
+#[cfg_attr(not(unix), ignore = "依赖`sh`作为CIN")]
+fn command_env() {
+    // 临时配置文件 | 打印环境变量后回显输入
+    let config_path =
+        std::env::temp_dir().join(format!("babelnar_cli_env_{}.hjson", std::process::id()));
+    std::fs::write(
+        &config_path,
+        // * ⚠️HJSON中无引号字符串会延续到行尾，因此需要分行
+        r#"{
+            translators: native
+            command: {
+                cmd: "sh"
+                cmdArgs: ["-c", "echo \"env: $BABELNAR_TEST_ENV\"; exec cat"]
+                env: { BABELNAR_TEST_ENV: "PYTHONIOENCODING=utf-8" }
+            }
+        }"#,
+    )
+    .expect("无法写入临时配置文件");
+
+    // 置入NAL：等待输出、终止
+    let run = common::run_cli(
+        &config_path,
+        b"''expect-cycle(50, 1, 0.1s): OTHER\n''terminate\n",
+    );
+    let _ = std::fs::remove_file(&config_path);
+
+    // 打印出的环境变量应作为`OTHER`输出
+    assert!(run.any_output(|output| matches!(
+        output,
+        Output::OTHER { content } if content.contains("env: PYTHONIOENCODING=utf-8")
+    )));
+}
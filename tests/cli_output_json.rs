@@ -0,0 +1,41 @@
+//! 集成测试/BabelNAR CLI的JSON输出模式
+//! * 🎯验证「作为子进程被调用」时，标准输出为可解析的NDJSON
+//! * 🚩以`cat`作为「回显CIN」：配合「原生」转译器，输入的指令会作为`OTHER`输出回显
+
+mod common;
+
+use navm::output::Output;
+
+/// 测试/JSON输出模式
+/// * 🚩启动CLI ⇒ 从标准输入置入NAL ⇒ 将标准输出逐行解析为[`Output`]
+#[test]
+#[cfg_attr(not(unix), ignore = "依赖`cat`作为回显CIN")]
+fn output_json() {
+    // 临时配置文件 | 使用`cat`回显输入
+    let config_path = std::env::temp_dir().join(format!(
+        "babelnar_cli_output_json_{}.hjson",
+        std::process::id()
+    ));
+    std::fs::write(
+        &config_path,
+        // * ⚠️HJSON中无引号字符串会延续到行尾，因此需要分行
+        r#"{
+            translators: native
+            command: { cmd: "cat" }
+        }"#,
+    )
+    .expect("无法写入临时配置文件");
+
+    // 置入NAL：输入、等待回显、终止
+    let run = common::run_cli(
+        &config_path,
+        b"<A --> B>.\n''expect-cycle(50, 1, 0.1s): OTHER\n''terminate\n",
+    );
+    let _ = std::fs::remove_file(&config_path);
+
+    // 回显的输入应被解析为`OTHER`输出
+    assert!(run.any_output(|output| matches!(
+        output,
+        Output::OTHER { content } if content.contains("<A --> B>.")
+    )));
+}
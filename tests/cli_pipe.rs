@@ -0,0 +1,52 @@
+//! 集成测试/BabelNAR CLI的管道输入
+//! * 🎯验证`echo "<A --> C>?" | babelnar_cli`：读完管道输入后，等CIN回答完再自行退出
+//! * 🚩以`sh`脚本作为「CIN」：每读到一行，延时后以JSON格式回答
+
+mod common;
+
+use navm::output::Output;
+
+/// 「CIN」脚本
+/// * 🚩延时回答：回答在管道输入读完之后才到达
+const CIN_SCRIPT: &str = r#"while read line; do
+    sleep 0.3
+    echo "{\"type\":\"ANSWER\",\"content\":\"$line\"}"
+done
+"#;
+
+/// 测试/读完管道输入后，打印回答并正常退出
+#[test]
+#[cfg_attr(not(unix), ignore = "依赖`sh`作为CIN")]
+fn piped_input_exits() {
+    // 临时的脚本、配置文件
+    let dir = std::env::temp_dir();
+    let script_path = dir.join(format!("babelnar_cli_pipe_{}.sh", std::process::id()));
+    let config_path = dir.join(format!("babelnar_cli_pipe_{}.hjson", std::process::id()));
+    std::fs::write(&script_path, CIN_SCRIPT).expect("无法写入临时脚本");
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"{{
+                translators: native
+                command: {{
+                    cmd: "sh"
+                    cmdArgs: [{script_path:?}]
+                }}
+            }}"#
+        ),
+    )
+    .expect("无法写入临时配置文件");
+
+    // 置入NAL后关闭管道 | 不输入`''terminate`
+    let run = common::run_cli(&config_path, b"<A --> C>?\n");
+    let _ = std::fs::remove_file(&script_path);
+    let _ = std::fs::remove_file(&config_path);
+
+    // 自行正常退出，且退出前已打印回答
+    let status = run.status.expect("CLI超时未退出");
+    assert!(status.success(), "CLI未正常退出：{status}");
+    assert!(run.any_output(|output| matches!(
+        output,
+        Output::ANSWER { content_raw, .. } if content_raw.contains("<A --> C>?")
+    )));
+}
@@ -0,0 +1,107 @@
+//! 集成测试/BabelNAR CLI的终止信号处理
+//! * 🎯验证「CLI被`SIGINT`终止」时，CIN子进程一并终止，不残留
+//! * 🚩以`cat`作为「CIN」：启动前将自身进程id写入文件
+#![cfg(unix)]
+
+use std::{
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// 超时时间
+/// * 🎯避免CLI未能正常退出时阻塞测试
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 进程是否仍在运行
+/// * 🚩已不存在，或已成为僵尸进程（等待回收）⇒未在运行
+fn is_alive(pid: &str) -> bool {
+    let output = Command::new("ps")
+        .args(["-o", "stat=", "-p", pid])
+        .output()
+        .expect("无法查询进程状态");
+    let stat = String::from_utf8_lossy(&output.stdout);
+    let stat = stat.trim();
+    !stat.is_empty() && !stat.starts_with('Z')
+}
+
+/// 在超时时间内等待条件成立
+fn wait_until(mut condition: impl FnMut() -> bool) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < TIMEOUT {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+/// 测试/`SIGINT`⇒终止CIN子进程，并以`130`退出
+#[test]
+fn sigint_terminates_child() {
+    let dir = std::env::temp_dir().join(format!("babelnar_cli_signal_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("无法创建临时目录");
+    let pid_path = dir.join("cin.pid");
+    let _ = std::fs::remove_file(&pid_path);
+    let config_path = dir.join("config.hjson");
+    std::fs::write(
+        &config_path,
+        format!(
+            // * ⚠️HJSON中无引号字符串会延续到行尾，因此需要分行
+            r#"{{
+                translators: native
+                command: {{
+                    cmd: "sh"
+                    cmdArgs: ["-c", "echo $$ > '{}'; exec cat"]
+                }}
+                userInput: false
+            }}"#,
+            pid_path.display()
+        ),
+    )
+    .expect("无法写入临时配置文件");
+
+    // 启动CLI
+    let mut cli = Command::new(env!("CARGO_BIN_EXE_babelnar_cli"))
+        .args(["-d", "--output-json", "-c"])
+        .arg(&config_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("无法启动BabelNAR CLI");
+
+    // 等待CIN启动
+    let mut pid = String::new();
+    let started = wait_until(|| {
+        pid = std::fs::read_to_string(&pid_path).unwrap_or_default();
+        pid.ends_with('\n')
+    });
+    let pid = pid.trim().to_string();
+    if !started || !is_alive(&pid) {
+        let _ = cli.kill();
+        panic!("CIN未能启动");
+    }
+
+    // 向CLI发送`SIGINT`
+    let cli_pid = libc::pid_t::try_from(cli.id()).unwrap();
+    // SAFETY: 只向刚启动的CLI进程发送信号
+    assert_eq!(unsafe { libc::kill(cli_pid, libc::SIGINT) }, 0);
+
+    // CLI应在超时时间内退出
+    let mut status = None;
+    wait_until(|| {
+        status = cli.try_wait().expect("无法查询CLI状态");
+        status.is_some()
+    });
+    let Some(status) = status else {
+        let _ = cli.kill();
+        panic!("CLI未在收到SIGINT后退出");
+    };
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // 以`128+SIGINT`退出，且CIN不残留
+    assert_eq!(status.code(), Some(130));
+    assert!(!is_alive(&pid), "CIN子进程仍存活：{pid}");
+}
@@ -0,0 +1,95 @@
+//! 集成测试的共用部分
+//! * 🎯启动BabelNAR CLI、置入标准输入、收集JSON输出
+//! * 🚩超时⇒强制终止CLI，避免测试阻塞
+//! * ⚠️各测试只用到其中一部分
+#![allow(dead_code)]
+
+use navm::output::Output;
+use std::{
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    process::{Command, ExitStatus, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// 超时时间
+/// * 🎯避免CLI未能正常退出时阻塞测试
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 轮询CLI是否退出的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// CLI退出后，等待剩余输出的时间
+const OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+/// 一次CLI运行的结果
+#[derive(Debug)]
+pub struct CliRun {
+    /// 标准输出中的各行，已解析为NAVM输出
+    pub outputs: Vec<Output>,
+    /// 退出状态
+    /// * 🚩超时被强制终止⇒[`None`]
+    pub status: Option<ExitStatus>,
+}
+
+impl CliRun {
+    /// 是否有满足条件的输出
+    pub fn any_output(&self, predicate: impl Fn(&Output) -> bool) -> bool {
+        self.outputs.iter().any(predicate)
+    }
+}
+
+/// 以JSON输出模式启动CLI，置入标准输入并收集输出
+/// * 🚩标准输入写完即关闭
+/// * 🚩标准输出的每一行都须是NAVM输出，否则直接panic
+/// * 🚩轮询[`std::process::Child::try_wait`]：超时⇒直接终止进程，无需与其它线程争锁
+pub fn run_cli(config_path: &Path, stdin: &[u8]) -> CliRun {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_babelnar_cli"))
+        .args(["-d", "--output-json", "-c"])
+        .arg(config_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("无法启动BabelNAR CLI");
+    let stdout = child.stdout.take().unwrap();
+    let mut child_stdin = child.stdin.take().unwrap();
+    child_stdin.write_all(stdin).expect("无法写入标准输入");
+    drop(child_stdin);
+
+    // 读取标准输出 | 独立线程，避免管道写满后CLI阻塞
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let line = line.expect("无法读取标准输出");
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    // 等待退出，超时则强制终止
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().expect("无法等待CLI退出") {
+            break Some(status);
+        }
+        if start.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // 收集输出 | 管道关闭或无新输出时结束
+    let mut outputs = vec![];
+    while let Ok(line) = rx.recv_timeout(OUTPUT_GRACE) {
+        let output = serde_json::from_str::<Output>(&line)
+            .unwrap_or_else(|e| panic!("标准输出「{line}」不是NAVM输出：{e}"));
+        outputs.push(output);
+    }
+    CliRun { outputs, status }
+}